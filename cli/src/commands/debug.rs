@@ -0,0 +1,82 @@
+use anstyle::*;
+use anyhow::Result;
+use clap::Parser;
+use sp1_build::{build_program, BuildArgs};
+use sp1_core::debugger::Debugger;
+use sp1_core::runtime::{Program, Runtime};
+use sp1_core::utils::{setup_logger, SP1CoreOpts};
+use std::{env, fs, path::PathBuf};
+
+use crate::commands::prove::Input;
+use crate::util::write_status;
+
+#[derive(Parser)]
+#[command(
+    name = "debug",
+    about = "Build a program and debug its execution with gdb or lldb"
+)]
+pub struct DebugCmd {
+    #[clap(long, value_parser)]
+    input: Option<Input>,
+
+    #[clap(
+        long,
+        default_value = "9001",
+        help = "The port to listen on for a debugger"
+    )]
+    port: u16,
+
+    #[clap(long, help = "Debug an existing ELF instead of building the program")]
+    elf: Option<PathBuf>,
+
+    #[clap(flatten)]
+    build_args: BuildArgs,
+}
+
+impl DebugCmd {
+    pub fn run(&self) -> Result<()> {
+        let elf_path = match &self.elf {
+            Some(path) => path.clone(),
+            None => {
+                // Keep the debug info in the release build of the program, so that the debugger
+                // can map instructions back to the source.
+                if env::var("CARGO_PROFILE_RELEASE_DEBUG").is_err() {
+                    env::set_var("CARGO_PROFILE_RELEASE_DEBUG", "true");
+                }
+                build_program(&self.build_args, None)?.into()
+            }
+        };
+        let elf_path = fs::canonicalize(elf_path)?;
+
+        if env::var("RUST_LOG").is_err() {
+            env::set_var("RUST_LOG", "info");
+        }
+        setup_logger();
+
+        let elf = fs::read(&elf_path)?;
+        let mut runtime = Runtime::new(Program::from(&elf), SP1CoreOpts::default());
+        if let Some(ref input) = self.input {
+            runtime.write_vecs(&[input.read_bytes()?]);
+        }
+
+        let mut debugger = Debugger::new(runtime).elf_path(&elf_path);
+        let green = AnsiColor::Green.on_default().effects(Effects::BOLD);
+        write_status(
+            &green,
+            "Listening",
+            format!(
+                "for a debugger on port {}, attach with `target remote :{}`",
+                self.port, self.port
+            )
+            .as_str(),
+        );
+        let reason = debugger.listen(("127.0.0.1", self.port))?;
+        write_status(
+            &green,
+            "Finished",
+            format!("debugging: {:?}", reason).as_str(),
+        );
+
+        Ok(())
+    }
+}
@@ -1,5 +1,6 @@
 pub mod build;
 pub mod build_toolchain;
+pub mod debug;
 pub mod install_toolchain;
 pub mod new;
 pub mod prove;
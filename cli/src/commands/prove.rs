@@ -1,5 +1,5 @@
 use anstyle::*;
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::Parser;
 use sp1_build::{build_program, BuildArgs};
 use sp1_core::utils::{setup_logger, setup_tracer};
@@ -11,11 +11,27 @@ use std::{env, fs::File, io::Read, path::PathBuf, str::FromStr};
 use crate::util::{elapsed, write_status};
 
 #[derive(Debug, Clone)]
-enum Input {
+pub(crate) enum Input {
     FilePath(PathBuf),
     HexBytes(Vec<u8>),
 }
 
+impl Input {
+    /// Reads the bytes of the input.
+    pub(crate) fn read_bytes(&self) -> Result<Vec<u8>> {
+        match self {
+            Input::FilePath(path) => {
+                let mut file = File::open(path)
+                    .with_context(|| format!("while opening input file {:?}", path))?;
+                let mut bytes = Vec::new();
+                file.read_to_end(&mut bytes)?;
+                Ok(bytes)
+            }
+            Input::HexBytes(bytes) => Ok(bytes.clone()),
+        }
+    }
+}
+
 fn is_valid_hex_string(s: &str) -> bool {
     if s.len() % 2 != 0 {
         return false;
@@ -97,17 +113,7 @@ impl ProveCmd {
 
         let mut stdin = SP1Stdin::new();
         if let Some(ref input) = self.input {
-            match input {
-                Input::FilePath(ref path) => {
-                    let mut file = File::open(path).expect("failed to open input file");
-                    let mut bytes = Vec::new();
-                    file.read_to_end(&mut bytes)?;
-                    stdin.write_slice(&bytes);
-                }
-                Input::HexBytes(ref bytes) => {
-                    stdin.write_slice(bytes);
-                }
-            }
+            stdin.write_slice(&input.read_bytes()?);
         }
 
         let start_time = Instant::now();
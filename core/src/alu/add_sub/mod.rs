@@ -7,8 +7,7 @@ use p3_air::{Air, AirBuilder, BaseAir};
 use p3_field::{AbstractField, PrimeField};
 use p3_matrix::dense::RowMajorMatrix;
 use p3_matrix::Matrix;
-use p3_maybe_rayon::prelude::ParallelSlice;
-use p3_maybe_rayon::prelude::{ParallelBridge, ParallelIterator};
+use p3_maybe_rayon::prelude::*;
 use sp1_derive::AlignedBorrow;
 
 use crate::air::MachineAir;
@@ -137,11 +136,10 @@ impl<F: PrimeField> MachineAir<F> for AddSubChip {
 
         let event_iter = input
             .add_events
-            .chunks(chunk_size)
-            .chain(input.sub_events.chunks(chunk_size));
+            .par_chunks(chunk_size)
+            .chain(input.sub_events.par_chunks(chunk_size));
 
         let blu_batches = event_iter
-            .par_bridge()
             .map(|events| {
                 let mut blu: HashMap<u32, HashMap<ByteLookupEvent, usize>> = HashMap::new();
                 events.iter().for_each(|event| {
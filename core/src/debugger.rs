@@ -0,0 +1,575 @@
+//! A GDB remote serial protocol stub for debugging guest programs.
+//!
+//! The stub wraps a [Runtime] and lets `gdb` or `lldb` attach to it over TCP to set breakpoints
+//! and watchpoints, single-step, and read or write registers and memory. When the path of the
+//! program's ELF is known, it is reported to the debugger, which loads its DWARF information for
+//! source-level debugging.
+//!
+//! Execution is untraced, so the runtime cannot be used to generate a proof afterwards.
+
+use std::collections::HashSet;
+use std::marker::PhantomData;
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::path::PathBuf;
+
+use gdbstub::common::Signal;
+use gdbstub::conn::{Connection, ConnectionExt};
+use gdbstub::stub::{run_blocking, DisconnectReason, GdbStub, SingleThreadStopReason};
+use gdbstub::target::ext::base::singlethread::{
+    SingleThreadBase, SingleThreadResume, SingleThreadResumeOps, SingleThreadSingleStep,
+    SingleThreadSingleStepOps,
+};
+use gdbstub::target::ext::base::BaseOps;
+use gdbstub::target::ext::breakpoints::{
+    Breakpoints, BreakpointsOps, HwWatchpoint, HwWatchpointOps, SwBreakpoint, SwBreakpointOps,
+    WatchKind,
+};
+use gdbstub::target::ext::exec_file::{ExecFile, ExecFileOps};
+use gdbstub::target::{Target, TargetError, TargetResult};
+use gdbstub_arch::riscv::reg::RiscvCoreRegs;
+use gdbstub_arch::riscv::Riscv32;
+use thiserror::Error;
+
+use crate::runtime::{ExecutionError, Instruction, MemoryRecord, Opcode, Runtime};
+
+/// The number of instructions executed between two checks for an interrupt from the debugger.
+const INTERRUPT_POLL_INTERVAL: u64 = 1 << 12;
+
+#[derive(Error, Debug)]
+pub enum DebuggerError {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("gdb stub error: {0}")]
+    Stub(String),
+}
+
+/// A debugger session over a [Runtime].
+pub struct Debugger<'a> {
+    runtime: Runtime<'a>,
+    elf_path: Option<PathBuf>,
+    breakpoints: HashSet<u32>,
+    watchpoints: Vec<Watchpoint>,
+    mode: ExecMode,
+    stopped_at_ebreak: bool,
+}
+
+/// A range of memory watched by the debugger.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Watchpoint {
+    addr: u32,
+    len: u32,
+    kind: WatchKind,
+}
+
+/// How the runtime is resumed by the debugger.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ExecMode {
+    Step,
+    Continue,
+}
+
+/// The reason the runtime stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StopEvent {
+    DoneStep,
+    Breakpoint,
+    Watch(WatchKind, u32),
+    Exited(u8),
+    Terminated(Signal),
+}
+
+/// The outcome of resuming the runtime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RunEvent {
+    IncomingData,
+    Stopped(StopEvent),
+}
+
+impl<'a> Debugger<'a> {
+    /// Creates a debugger for the given runtime, which is paused at its current state.
+    pub fn new(runtime: Runtime<'a>) -> Self {
+        Self {
+            runtime,
+            elf_path: None,
+            breakpoints: HashSet::new(),
+            watchpoints: Vec::new(),
+            mode: ExecMode::Continue,
+            stopped_at_ebreak: false,
+        }
+    }
+
+    /// Sets the path of the program's ELF, which the debugger loads for symbols and DWARF
+    /// information.
+    pub fn elf_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.elf_path = Some(path.into());
+        self
+    }
+
+    /// The runtime being debugged.
+    pub fn runtime(&self) -> &Runtime<'a> {
+        &self.runtime
+    }
+
+    /// Consumes the debugger, returning the runtime in the state it was left in.
+    pub fn into_runtime(self) -> Runtime<'a> {
+        self.runtime
+    }
+
+    /// Waits for a debugger to connect to the given address and serves it until it detaches or
+    /// the program ends.
+    pub fn listen(&mut self, addr: impl ToSocketAddrs) -> Result<DisconnectReason, DebuggerError> {
+        let listener = TcpListener::bind(addr)?;
+        tracing::info!("waiting for a debugger on {}", listener.local_addr()?);
+        let (stream, peer) = listener.accept()?;
+        tracing::info!("debugger connected from {}", peer);
+        self.attach(stream)
+    }
+
+    /// Serves a debugger connected over the given stream until it detaches or the program ends.
+    pub fn attach(&mut self, stream: TcpStream) -> Result<DisconnectReason, DebuggerError> {
+        GdbStub::new(stream)
+            .run_blocking::<DebuggerEventLoop<'a>>(self)
+            .map_err(|e| DebuggerError::Stub(e.to_string()))
+    }
+
+    /// Resumes the runtime in the current mode until it stops or `poll_incoming_data` reports
+    /// that the debugger sent data, such as an interrupt.
+    fn run(&mut self, mut poll_incoming_data: impl FnMut() -> bool) -> RunEvent {
+        match self.mode {
+            ExecMode::Step => RunEvent::Stopped(self.step().unwrap_or(StopEvent::DoneStep)),
+            ExecMode::Continue => {
+                let mut cycles = 0u64;
+                loop {
+                    if cycles % INTERRUPT_POLL_INTERVAL == 0 && poll_incoming_data() {
+                        return RunEvent::IncomingData;
+                    }
+                    cycles += 1;
+                    if let Some(event) = self.step() {
+                        return RunEvent::Stopped(event);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Executes a single instruction, returning why the runtime stopped, if it did.
+    fn step(&mut self) -> Option<StopEvent> {
+        let instruction = match self.instruction() {
+            Some(instruction) => instruction,
+            None => return Some(StopEvent::Exited(0)),
+        };
+
+        // Step over a breakpoint compiled into the program once it has been reported.
+        if instruction.opcode == Opcode::EBREAK && self.stopped_at_ebreak {
+            self.stopped_at_ebreak = false;
+            self.runtime.state.pc = self.runtime.state.pc.wrapping_add(4);
+            return None;
+        }
+
+        let access = self.memory_access(&instruction);
+        match self.runtime.step() {
+            Ok(true) => return Some(StopEvent::Exited(0)),
+            Ok(false) => {}
+            Err(ExecutionError::Breakpoint()) => {
+                self.stopped_at_ebreak = true;
+                return Some(StopEvent::Breakpoint);
+            }
+            Err(ExecutionError::HaltWithNonZeroExitCode(code)) => {
+                return Some(StopEvent::Exited(code as u8))
+            }
+            Err(ExecutionError::InvalidMemoryAccess(_, _)) => {
+                return Some(StopEvent::Terminated(Signal::SIGSEGV))
+            }
+            Err(ExecutionError::ExceededCycleLimit(_)) => {
+                return Some(StopEvent::Terminated(Signal::SIGXCPU))
+            }
+            Err(ExecutionError::UnsupportedSyscall(_) | ExecutionError::Unimplemented()) => {
+                return Some(StopEvent::Terminated(Signal::SIGILL))
+            }
+            Err(ExecutionError::InvalidSyscallInput(_, _)) => {
+                return Some(StopEvent::Terminated(Signal::SIGSYS))
+            }
+        }
+
+        if let Some((addr, len, is_write)) = access {
+            let hit = self.watchpoints.iter().find(|watchpoint| {
+                let kind_matches = match watchpoint.kind {
+                    WatchKind::Write => is_write,
+                    WatchKind::Read => !is_write,
+                    WatchKind::ReadWrite => true,
+                };
+                kind_matches
+                    && addr < watchpoint.addr.saturating_add(watchpoint.len)
+                    && watchpoint.addr < addr.saturating_add(len)
+            });
+            if let Some(watchpoint) = hit {
+                return Some(StopEvent::Watch(watchpoint.kind, addr));
+            }
+        }
+
+        if self.breakpoints.contains(&self.runtime.state.pc) {
+            return Some(StopEvent::Breakpoint);
+        }
+
+        None
+    }
+
+    /// The instruction at the current program counter, if the program has not finished.
+    fn instruction(&self) -> Option<Instruction> {
+        let program = &self.runtime.program;
+        let offset = self.runtime.state.pc.wrapping_sub(program.pc_base);
+        if offset % 4 != 0 {
+            return None;
+        }
+        program.instructions.get((offset / 4) as usize).copied()
+    }
+
+    /// The address, length and direction of the memory access made by a load or store.
+    ///
+    /// Memory accessed by precompiles is not reported, so watchpoints do not trigger on it.
+    fn memory_access(&self, instruction: &Instruction) -> Option<(u32, u32, bool)> {
+        let (len, is_write) = match instruction.opcode {
+            Opcode::LB | Opcode::LBU => (1, false),
+            Opcode::LH | Opcode::LHU => (2, false),
+            Opcode::LW => (4, false),
+            Opcode::SB => (1, true),
+            Opcode::SH => (2, true),
+            Opcode::SW => (4, true),
+            _ => return None,
+        };
+        let base = self.read_word(instruction.op_b);
+        Some((base.wrapping_add(instruction.op_c), len, is_write))
+    }
+
+    /// Reads the word at the given aligned address without recording the access.
+    fn read_word(&self, addr: u32) -> u32 {
+        let state = &self.runtime.state;
+        if let Some(record) = state.memory.get(&addr) {
+            return record.value;
+        }
+        // The memory image is only loaded once the first instruction is executed.
+        if state.global_clk == 0 {
+            if let Some(value) = self.runtime.program.memory_image.get(&addr) {
+                return *value;
+            }
+        }
+        state.uninitialized_memory.get(&addr).copied().unwrap_or(0)
+    }
+
+    /// Writes the word at the given aligned address without recording the access.
+    fn write_word(&mut self, addr: u32, value: u32) {
+        self.runtime
+            .state
+            .memory
+            .entry(addr)
+            .and_modify(|record| record.value = value)
+            .or_insert(MemoryRecord {
+                value,
+                shard: 0,
+                timestamp: 0,
+            });
+    }
+}
+
+impl<'a> Target for Debugger<'a> {
+    type Arch = Riscv32;
+    type Error = ExecutionError;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+
+    fn support_breakpoints(&mut self) -> Option<BreakpointsOps<'_, Self>> {
+        Some(self)
+    }
+
+    fn support_exec_file(&mut self) -> Option<ExecFileOps<'_, Self>> {
+        Some(self)
+    }
+}
+
+impl<'a> SingleThreadBase for Debugger<'a> {
+    fn read_registers(&mut self, regs: &mut RiscvCoreRegs<u32>) -> TargetResult<(), Self> {
+        for (i, reg) in regs.x.iter_mut().enumerate() {
+            *reg = self.read_word(i as u32);
+        }
+        regs.pc = self.runtime.state.pc;
+        Ok(())
+    }
+
+    fn write_registers(&mut self, regs: &RiscvCoreRegs<u32>) -> TargetResult<(), Self> {
+        // The zero register is hardwired, so writes to it are ignored.
+        for (i, value) in regs.x.iter().enumerate().skip(1) {
+            if self.read_word(i as u32) != *value {
+                self.write_word(i as u32, *value);
+            }
+        }
+        self.runtime.state.pc = regs.pc;
+        Ok(())
+    }
+
+    fn read_addrs(&mut self, start_addr: u32, data: &mut [u8]) -> TargetResult<usize, Self> {
+        for (i, byte) in data.iter_mut().enumerate() {
+            let addr = start_addr.wrapping_add(i as u32);
+            let word = self.read_word(addr - addr % 4);
+            *byte = (word >> ((addr % 4) * 8)) as u8;
+        }
+        Ok(data.len())
+    }
+
+    fn write_addrs(&mut self, start_addr: u32, data: &[u8]) -> TargetResult<(), Self> {
+        // Writes to the memory image before the first instruction would be overwritten when it
+        // is loaded.
+        if self.runtime.state.global_clk == 0 {
+            let end = start_addr.wrapping_add(data.len() as u32);
+            let image = &self.runtime.program.memory_image;
+            if image
+                .keys()
+                .any(|addr| *addr + 4 > start_addr && *addr < end)
+            {
+                return Err(TargetError::NonFatal);
+            }
+        }
+        for (i, byte) in data.iter().enumerate() {
+            let addr = start_addr.wrapping_add(i as u32);
+            let aligned = addr - addr % 4;
+            let shift = (addr % 4) * 8;
+            let word = self.read_word(aligned);
+            self.write_word(
+                aligned,
+                (word & !(0xff << shift)) | ((*byte as u32) << shift),
+            );
+        }
+        Ok(())
+    }
+
+    fn support_resume(&mut self) -> Option<SingleThreadResumeOps<'_, Self>> {
+        Some(self)
+    }
+}
+
+impl<'a> SingleThreadResume for Debugger<'a> {
+    fn resume(&mut self, signal: Option<Signal>) -> Result<(), Self::Error> {
+        if signal.is_some() {
+            tracing::warn!("signals are not supported by the debugger and are ignored");
+        }
+        self.mode = ExecMode::Continue;
+        Ok(())
+    }
+
+    fn support_single_step(&mut self) -> Option<SingleThreadSingleStepOps<'_, Self>> {
+        Some(self)
+    }
+}
+
+impl<'a> SingleThreadSingleStep for Debugger<'a> {
+    fn step(&mut self, signal: Option<Signal>) -> Result<(), Self::Error> {
+        if signal.is_some() {
+            tracing::warn!("signals are not supported by the debugger and are ignored");
+        }
+        self.mode = ExecMode::Step;
+        Ok(())
+    }
+}
+
+impl<'a> Breakpoints for Debugger<'a> {
+    fn support_sw_breakpoint(&mut self) -> Option<SwBreakpointOps<'_, Self>> {
+        Some(self)
+    }
+
+    fn support_hw_watchpoint(&mut self) -> Option<HwWatchpointOps<'_, Self>> {
+        Some(self)
+    }
+}
+
+impl<'a> SwBreakpoint for Debugger<'a> {
+    fn add_sw_breakpoint(&mut self, addr: u32, _kind: usize) -> TargetResult<bool, Self> {
+        self.breakpoints.insert(addr);
+        Ok(true)
+    }
+
+    fn remove_sw_breakpoint(&mut self, addr: u32, _kind: usize) -> TargetResult<bool, Self> {
+        Ok(self.breakpoints.remove(&addr))
+    }
+}
+
+impl<'a> HwWatchpoint for Debugger<'a> {
+    fn add_hw_watchpoint(
+        &mut self,
+        addr: u32,
+        len: u32,
+        kind: WatchKind,
+    ) -> TargetResult<bool, Self> {
+        self.watchpoints.push(Watchpoint { addr, len, kind });
+        Ok(true)
+    }
+
+    fn remove_hw_watchpoint(
+        &mut self,
+        addr: u32,
+        len: u32,
+        kind: WatchKind,
+    ) -> TargetResult<bool, Self> {
+        let watchpoint = Watchpoint { addr, len, kind };
+        match self.watchpoints.iter().position(|w| *w == watchpoint) {
+            Some(index) => {
+                self.watchpoints.remove(index);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+}
+
+impl<'a> ExecFile for Debugger<'a> {
+    fn get_exec_file(
+        &self,
+        _pid: Option<gdbstub::common::Pid>,
+        offset: u64,
+        length: usize,
+        buf: &mut [u8],
+    ) -> TargetResult<usize, Self> {
+        let path = match &self.elf_path {
+            Some(path) => path.to_string_lossy().into_owned(),
+            None => return Err(TargetError::NonFatal),
+        };
+        let bytes = path.as_bytes();
+        let start = (offset as usize).min(bytes.len());
+        let end = start
+            .saturating_add(length)
+            .min(bytes.len())
+            .min(start + buf.len());
+        buf[..end - start].copy_from_slice(&bytes[start..end]);
+        Ok(end - start)
+    }
+}
+
+/// Drives a [Debugger] from the requests of a connected debugger.
+struct DebuggerEventLoop<'a>(PhantomData<Debugger<'a>>);
+
+impl<'a> run_blocking::BlockingEventLoop for DebuggerEventLoop<'a> {
+    type Target = Debugger<'a>;
+    type Connection = TcpStream;
+    type StopReason = SingleThreadStopReason<u32>;
+
+    fn wait_for_stop_reason(
+        target: &mut Self::Target,
+        conn: &mut Self::Connection,
+    ) -> Result<
+        run_blocking::Event<Self::StopReason>,
+        run_blocking::WaitForStopReasonError<
+            <Self::Target as Target>::Error,
+            <Self::Connection as Connection>::Error,
+        >,
+    > {
+        let poll_incoming_data = || conn.peek().map(|b| b.is_some()).unwrap_or(true);
+        let event = match target.run(poll_incoming_data) {
+            RunEvent::IncomingData => {
+                let byte = conn
+                    .read()
+                    .map_err(run_blocking::WaitForStopReasonError::Connection)?;
+                return Ok(run_blocking::Event::IncomingData(byte));
+            }
+            RunEvent::Stopped(event) => event,
+        };
+        let stop_reason = match event {
+            StopEvent::DoneStep => SingleThreadStopReason::DoneStep,
+            StopEvent::Breakpoint => SingleThreadStopReason::SwBreak(()),
+            StopEvent::Watch(kind, addr) => SingleThreadStopReason::Watch {
+                tid: (),
+                kind,
+                addr,
+            },
+            StopEvent::Exited(code) => SingleThreadStopReason::Exited(code),
+            StopEvent::Terminated(signal) => SingleThreadStopReason::Terminated(signal),
+        };
+        Ok(run_blocking::Event::TargetStopped(stop_reason))
+    }
+
+    fn on_interrupt(
+        _target: &mut Self::Target,
+    ) -> Result<Option<Self::StopReason>, <Self::Target as Target>::Error> {
+        Ok(Some(SingleThreadStopReason::Signal(Signal::SIGINT)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gdbstub::target::ext::base::singlethread::SingleThreadBase;
+    use gdbstub::target::ext::breakpoints::{HwWatchpoint, SwBreakpoint, WatchKind};
+    use gdbstub_arch::riscv::reg::RiscvCoreRegs;
+
+    use super::{Debugger, ExecMode, RunEvent, StopEvent};
+    use crate::runtime::tests::{simple_memory_program, simple_program};
+    use crate::runtime::Runtime;
+    use crate::utils::SP1CoreOpts;
+
+    #[test]
+    fn test_breakpoint_and_step() {
+        let runtime = Runtime::new(simple_program(), SP1CoreOpts::default());
+        let mut debugger = Debugger::new(runtime);
+        assert!(debugger.add_sw_breakpoint(8, 4).is_ok());
+
+        assert_eq!(
+            debugger.run(|| false),
+            RunEvent::Stopped(StopEvent::Breakpoint)
+        );
+        let mut regs = RiscvCoreRegs::default();
+        assert!(debugger.read_registers(&mut regs).is_ok());
+        assert_eq!(regs.pc, 8);
+        assert_eq!(regs.x[29], 5);
+        assert_eq!(regs.x[30], 37);
+
+        debugger.mode = ExecMode::Step;
+        assert_eq!(
+            debugger.run(|| false),
+            RunEvent::Stopped(StopEvent::Exited(0))
+        );
+        assert!(debugger.read_registers(&mut regs).is_ok());
+        assert_eq!(regs.x[31], 42);
+    }
+
+    #[test]
+    fn test_watchpoint_and_memory() {
+        let runtime = Runtime::new(simple_memory_program(), SP1CoreOpts::default());
+        let mut debugger = Debugger::new(runtime);
+        assert!(debugger
+            .add_hw_watchpoint(0x27654322, 1, WatchKind::Read)
+            .is_ok());
+        assert!(debugger
+            .add_hw_watchpoint(0x43627530, 4, WatchKind::Write)
+            .is_ok());
+
+        // The word load is the first access covering the watched byte.
+        assert_eq!(
+            debugger.run(|| false),
+            RunEvent::Stopped(StopEvent::Watch(WatchKind::Read, 0x27654320))
+        );
+        let mut data = [0u8; 4];
+        assert!(debugger.read_addrs(0x27654320, &mut data).is_ok());
+        assert_eq!(u32::from_le_bytes(data), 0x12348765);
+
+        assert_eq!(
+            debugger.run(|| false),
+            RunEvent::Stopped(StopEvent::Watch(WatchKind::Read, 0x27654322))
+        );
+        assert_eq!(
+            debugger.run(|| false),
+            RunEvent::Stopped(StopEvent::Watch(WatchKind::Read, 0x27654322))
+        );
+
+        assert!(debugger.write_addrs(0x27654320, &[0xaa]).is_ok());
+        assert!(debugger.read_addrs(0x27654320, &mut data).is_ok());
+        assert_eq!(u32::from_le_bytes(data), 0x123487aa);
+
+        // Skip the last halfword load of the watched byte.
+        debugger.run(|| false);
+        assert_eq!(
+            debugger.run(|| false),
+            RunEvent::Stopped(StopEvent::Watch(WatchKind::Write, 0x43627530))
+        );
+
+        // An interrupt stops a continuing runtime before it reaches the next watchpoint.
+        assert_eq!(debugger.run(|| true), RunEvent::IncomingData);
+    }
+}
@@ -17,6 +17,8 @@ pub mod air;
 pub mod alu;
 pub mod bytes;
 pub mod cpu;
+#[cfg(feature = "debugger")]
+pub mod debugger;
 pub mod disassembler;
 pub mod io;
 pub mod lookup;
@@ -0,0 +1,114 @@
+use p3_air::AirBuilder;
+use p3_field::AbstractField;
+use p3_field::Field;
+use sp1_derive::AlignedBorrow;
+
+use crate::air::SP1AirBuilder;
+use crate::air::Word;
+use crate::air::WORD_SIZE;
+use crate::bytes::event::ByteRecord;
+
+/// The number of bytes in a 64-bit word.
+const U64_SIZE: usize = 2 * WORD_SIZE;
+
+/// A set of columns needed to compute the wrapping sum of up to five 64-bit words.
+///
+/// A 64-bit word is represented by its low and high 32-bit words, in that order. Instead of one
+/// boolean column per possible carry value, as in [super::Add5Operation], each carry is range
+/// checked to be a byte. Since the sum of at most five bytes and a carry is less than `2^16`, this
+/// is enough for the carries to be uniquely determined.
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct AddU64Operation<T> {
+    /// The wrapping sum of the inputs.
+    pub value: [Word<T>; 2],
+
+    /// The carry for the `i`th byte.
+    pub carry: [Word<T>; 2],
+}
+
+impl<F: Field> AddU64Operation<F> {
+    /// The maximum number of words that can be summed.
+    pub const MAX_INPUTS: usize = 5;
+
+    pub fn populate(
+        &mut self,
+        record: &mut impl ByteRecord,
+        shard: u32,
+        channel: u8,
+        inputs: &[u64],
+    ) -> u64 {
+        assert!(inputs.len() <= Self::MAX_INPUTS);
+        let expected = inputs.iter().fold(0u64, |acc, x| acc.wrapping_add(*x));
+        let expected_bytes = expected.to_le_bytes();
+
+        let mut carry = [0u8; U64_SIZE];
+        for i in 0..U64_SIZE {
+            let mut res = inputs
+                .iter()
+                .map(|x| x.to_le_bytes()[i] as u32)
+                .sum::<u32>();
+            if i > 0 {
+                res += carry[i - 1] as u32;
+            }
+            carry[i] = (res / 256) as u8;
+            debug_assert_eq!(res % 256, expected_bytes[i] as u32);
+
+            let (word, byte) = (i / WORD_SIZE, i % WORD_SIZE);
+            self.value[word][byte] = F::from_canonical_u8(expected_bytes[i]);
+            self.carry[word][byte] = F::from_canonical_u8(carry[i]);
+        }
+
+        // Range check.
+        {
+            for input in inputs {
+                record.add_u8_range_checks(shard, channel, &input.to_le_bytes());
+            }
+            record.add_u8_range_checks(shard, channel, &expected_bytes);
+            record.add_u8_range_checks(shard, channel, &carry);
+        }
+
+        expected
+    }
+
+    pub fn eval<AB: SP1AirBuilder>(
+        builder: &mut AB,
+        words: &[[Word<AB::Var>; 2]],
+        shard: AB::Var,
+        channel: impl Into<AB::Expr> + Copy,
+        is_real: AB::Var,
+        cols: AddU64Operation<AB::Var>,
+    ) {
+        assert!(words.len() <= Self::MAX_INPUTS);
+        builder.assert_bool(is_real);
+
+        // Range check each byte.
+        {
+            for word in words.iter().flatten() {
+                builder.slice_range_check_u8(&word.0, shard, channel, is_real);
+            }
+            for word in cols.value.iter().chain(cols.carry.iter()) {
+                builder.slice_range_check_u8(&word.0, shard, channel, is_real);
+            }
+        }
+
+        // For each byte, assert that the difference between the carried result and the
+        // non-carried result is the product of the carry and the base.
+        let base = AB::F::from_canonical_u32(256);
+        let mut builder_is_real = builder.when(is_real);
+        for i in 0..U64_SIZE {
+            let (word, byte) = (i / WORD_SIZE, i % WORD_SIZE);
+            let mut overflow: AB::Expr = AB::F::zero().into();
+            for input in words {
+                overflow += input[word][byte].into();
+            }
+            overflow -= cols.value[word][byte].into();
+
+            if i > 0 {
+                let (prev_word, prev_byte) = ((i - 1) / WORD_SIZE, (i - 1) % WORD_SIZE);
+                overflow += cols.carry[prev_word][prev_byte].into();
+            }
+            builder_is_real.assert_eq(cols.carry[word][byte] * base, overflow);
+        }
+    }
+}
@@ -3,6 +3,7 @@ use std::fmt::Debug;
 use num::{BigUint, Zero};
 use p3_air::AirBuilder;
 use p3_field::PrimeField32;
+use serde::{Deserialize, Serialize};
 use sp1_derive::AlignedBorrow;
 
 use super::params::{FieldParameters, Limbs};
@@ -14,7 +15,7 @@ use crate::bytes::event::ByteRecord;
 use typenum::Unsigned;
 
 /// Airthmetic operation for emulating modular arithmetic.
-#[derive(PartialEq, Copy, Clone, Debug)]
+#[derive(PartialEq, Copy, Clone, Debug, Serialize, Deserialize)]
 pub enum FieldOperation {
     Add,
     Mul,
@@ -131,7 +132,8 @@ impl<F: PrimeField32, P: FieldParameters> FieldOpCols<F, P> {
         let result = match op {
             // If doing the subtraction operation, a - b = result, equivalent to a = result + b.
             FieldOperation::Sub => {
-                let result = (modulus.clone() + a - b) % modulus;
+                // Reduce `b` first, so that the subtraction can't underflow.
+                let result = (modulus.clone() + a - b % modulus) % modulus;
                 // We populate the carry, witness_low, witness_high as if we were doing an addition with result + b.
                 // But we populate `result` with the actual result of the subtraction because those columns are expected
                 // to contain the result by the user.
@@ -207,11 +209,74 @@ impl<V: Copy, P: FieldParameters> FieldOpCols<V, P> {
             FieldOperation::Add | FieldOperation::Mul => (p_a_param, self.result.into()),
             FieldOperation::Sub | FieldOperation::Div => (self.result.into(), p_a_param),
         };
-        let p_carry: Polynomial<<AB as AirBuilder>::Expr> = self.carry.into();
         let p_op = match op {
             FieldOperation::Add | FieldOperation::Sub => p_a + p_b,
             FieldOperation::Mul | FieldOperation::Div => p_a * p_b,
         };
+        self.eval_with_polynomials(builder, p_op, p_result, p_modulus, shard, channel, is_real);
+    }
+
+    /// Evaluates the operation selected at runtime by the boolean flags `is_add`, `is_sub`,
+    /// `is_mul` and `is_div`.
+    ///
+    /// Exactly one of the flags is expected to be set on real rows. On padding rows, all flags may
+    /// be zero, in which case the columns are expected to be populated with zeros.
+    #[allow(clippy::too_many_arguments)]
+    pub fn eval_variable<AB: SP1AirBuilder<Var = V>>(
+        &self,
+        builder: &mut AB,
+        a: &(impl Into<Polynomial<AB::Expr>> + Clone),
+        b: &(impl Into<Polynomial<AB::Expr>> + Clone),
+        modulus: &(impl Into<Polynomial<AB::Expr>> + Clone),
+        is_add: impl Into<AB::Expr> + Clone,
+        is_sub: impl Into<AB::Expr> + Clone,
+        is_mul: impl Into<AB::Expr> + Clone,
+        is_div: impl Into<AB::Expr> + Clone,
+        shard: impl Into<AB::Expr> + Clone,
+        channel: impl Into<AB::Expr> + Clone,
+        is_real: impl Into<AB::Expr> + Clone,
+    ) where
+        V: Into<AB::Expr>,
+        Limbs<V, P::Limbs>: Copy,
+    {
+        let p_a: Polynomial<AB::Expr> = (a).clone().into();
+        let p_b: Polynomial<AB::Expr> = (b).clone().into();
+        let p_modulus: Polynomial<AB::Expr> = (modulus).clone().into();
+        let p_res: Polynomial<AB::Expr> = self.result.into();
+
+        let is_add: AB::Expr = is_add.into();
+        let is_sub: AB::Expr = is_sub.into();
+        let is_mul: AB::Expr = is_mul.into();
+        let is_div: AB::Expr = is_div.into();
+
+        // As in `eval_with_modulus`, subtraction and division are checked as `a = result + b` and
+        // `a = result * b` respectively.
+        let p_result =
+            &p_res * (is_add.clone() + is_mul.clone()) + &p_a * (is_sub.clone() + is_div.clone());
+        let p_op = (&p_a + &p_b) * is_add
+            + (&p_res + &p_b) * is_sub
+            + (&p_a * &p_b) * is_mul
+            + (&p_res * &p_b) * is_div;
+
+        self.eval_with_polynomials(builder, p_op, p_result, p_modulus, shard, channel, is_real);
+    }
+
+    /// Constrains `op = result + carry * modulus` and range checks the columns.
+    #[allow(clippy::too_many_arguments)]
+    fn eval_with_polynomials<AB: SP1AirBuilder<Var = V>>(
+        &self,
+        builder: &mut AB,
+        p_op: Polynomial<AB::Expr>,
+        p_result: Polynomial<AB::Expr>,
+        p_modulus: Polynomial<AB::Expr>,
+        shard: impl Into<AB::Expr> + Clone,
+        channel: impl Into<AB::Expr> + Clone,
+        is_real: impl Into<AB::Expr> + Clone,
+    ) where
+        V: Into<AB::Expr>,
+        Limbs<V, P::Limbs>: Copy,
+    {
+        let p_carry: Polynomial<<AB as AirBuilder>::Expr> = self.carry.into();
         let p_op_minus_result: Polynomial<AB::Expr> = p_op - &p_result;
         let p_vanishing = p_op_minus_result - &(&p_carry * &p_modulus);
         let p_witness_low = self.witness_low.0.iter().into();
@@ -0,0 +1,162 @@
+use p3_field::AbstractField;
+use p3_field::Field;
+use sp1_derive::AlignedBorrow;
+
+use crate::air::SP1AirBuilder;
+use crate::air::Word;
+use crate::bytes::event::ByteRecord;
+use crate::bytes::utils::shr_carry;
+use crate::bytes::ByteLookupEvent;
+use crate::bytes::ByteOpcode;
+use crate::disassembler::WORD_SIZE;
+
+/// The number of bytes in a 64-bit word.
+const U64_SIZE: usize = 2 * WORD_SIZE;
+
+/// A set of columns needed to compute `rotateright` of a 64-bit word with a fixed offset R.
+///
+/// A 64-bit word is represented by its low and high 32-bit words, in that order, matching its
+/// little-endian layout in memory. As with [super::FixedRotateRightOperation], we decompose
+/// rotations into a byte rotation and a bit shift.
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct FixedRotateRightU64Operation<T> {
+    /// The output value.
+    pub value: [Word<T>; 2],
+
+    /// The shift output of `shrcarry` on each byte of the word.
+    pub shift: [Word<T>; 2],
+
+    /// The carry output of `shrcarry` on each byte of the word.
+    pub carry: [Word<T>; 2],
+}
+
+impl<F: Field> FixedRotateRightU64Operation<F> {
+    pub const fn nb_bytes_to_shift(rotation: usize) -> usize {
+        rotation / 8
+    }
+
+    pub const fn nb_bits_to_shift(rotation: usize) -> usize {
+        rotation % 8
+    }
+
+    pub const fn carry_multiplier(rotation: usize) -> u32 {
+        let nb_bits_to_shift = Self::nb_bits_to_shift(rotation);
+        1 << (8 - nb_bits_to_shift)
+    }
+
+    pub fn populate(
+        &mut self,
+        record: &mut impl ByteRecord,
+        shard: u32,
+        channel: u8,
+        input: u64,
+        rotation: usize,
+    ) -> u64 {
+        let input_bytes = input.to_le_bytes();
+        let expected = input.rotate_right(rotation as u32);
+
+        // Compute some constants with respect to the rotation needed for the rotation.
+        let nb_bytes_to_shift = Self::nb_bytes_to_shift(rotation);
+        let nb_bits_to_shift = Self::nb_bits_to_shift(rotation);
+        let carry_multiplier = F::from_canonical_u32(Self::carry_multiplier(rotation));
+
+        // For each byte of the byte-rotated input, calculate the shift and carry. If it's not the
+        // first byte, calculate the new byte value using the current shifted byte and the last
+        // carry.
+        let mut first_shift = F::zero();
+        let mut last_carry = F::zero();
+        for i in (0..U64_SIZE).rev() {
+            let b = input_bytes[(i + nb_bytes_to_shift) % U64_SIZE];
+            let c = nb_bits_to_shift as u8;
+
+            let (shift, carry) = shr_carry(b, c);
+
+            let byte_event = ByteLookupEvent {
+                shard,
+                channel,
+                opcode: ByteOpcode::ShrCarry,
+                a1: shift as u16,
+                a2: carry,
+                b,
+                c,
+            };
+            record.add_byte_lookup_event(byte_event);
+
+            let (word, byte) = (i / WORD_SIZE, i % WORD_SIZE);
+            self.shift[word][byte] = F::from_canonical_u8(shift);
+            self.carry[word][byte] = F::from_canonical_u8(carry);
+
+            if i == U64_SIZE - 1 {
+                first_shift = self.shift[word][byte];
+            } else {
+                self.value[word][byte] = self.shift[word][byte] + last_carry * carry_multiplier;
+            }
+
+            last_carry = self.carry[word][byte];
+        }
+
+        // For the first byte, we didn't know the last carry so compute the rotated byte here.
+        self.value[1][WORD_SIZE - 1] = first_shift + last_carry * carry_multiplier;
+
+        // Check that the value is correct.
+        assert_eq!(
+            (self.value[1].to_u32() as u64) << 32 | self.value[0].to_u32() as u64,
+            expected
+        );
+
+        expected
+    }
+
+    pub fn eval<AB: SP1AirBuilder>(
+        builder: &mut AB,
+        input: [Word<AB::Var>; 2],
+        rotation: usize,
+        cols: FixedRotateRightU64Operation<AB::Var>,
+        shard: AB::Var,
+        channel: impl Into<AB::Expr> + Clone,
+        is_real: AB::Var,
+    ) {
+        // Compute some constants with respect to the rotation needed for the rotation.
+        let nb_bytes_to_shift = Self::nb_bytes_to_shift(rotation);
+        let nb_bits_to_shift = Self::nb_bits_to_shift(rotation);
+        let carry_multiplier = AB::F::from_canonical_u32(Self::carry_multiplier(rotation));
+
+        // For each byte of the byte-rotated input, calculate the shift and carry. If it's not the
+        // first byte, calculate the new byte value using the current shifted byte and the last
+        // carry.
+        let mut first_shift = AB::Expr::zero();
+        let mut last_carry = AB::Expr::zero();
+        for i in (0..U64_SIZE).rev() {
+            let j = (i + nb_bytes_to_shift) % U64_SIZE;
+            let (word, byte) = (i / WORD_SIZE, i % WORD_SIZE);
+            builder.send_byte_pair(
+                AB::F::from_canonical_u32(ByteOpcode::ShrCarry as u32),
+                cols.shift[word][byte],
+                cols.carry[word][byte],
+                input[j / WORD_SIZE][j % WORD_SIZE],
+                AB::F::from_canonical_usize(nb_bits_to_shift),
+                shard,
+                channel.clone(),
+                is_real,
+            );
+
+            if i == U64_SIZE - 1 {
+                first_shift = cols.shift[word][byte].into();
+            } else {
+                builder.assert_eq(
+                    cols.value[word][byte],
+                    cols.shift[word][byte] + last_carry * carry_multiplier,
+                );
+            }
+
+            last_carry = cols.carry[word][byte].into();
+        }
+
+        // For the first byte, we didn't know the last carry so compute the rotated byte here.
+        builder.assert_eq(
+            cols.value[1][WORD_SIZE - 1],
+            first_shift + last_carry * carry_multiplier,
+        );
+    }
+}
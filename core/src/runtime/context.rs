@@ -1,7 +1,11 @@
 use core::mem::take;
+use std::path::PathBuf;
 use std::sync::Arc;
 
-use super::{hookify, BoxedHook, HookEnv, HookRegistry, SubproofVerifier};
+use super::{
+    hookify, BoxedHook, CancellationToken, HookEnv, HookRegistry, PrecompileExtension,
+    PrecompileRegistry, ProgressCallback, ProgressEvent, SubproofVerifier,
+};
 
 /// Context to run a program inside SP1.
 #[derive(Clone, Default)]
@@ -16,6 +20,24 @@ pub struct SP1Context<'a> {
 
     /// The maximum number of cpu cycles to use for execution.
     pub max_cycles: Option<u64>,
+
+    /// The callback notified as the proof makes progress.
+    pub progress: Option<ProgressCallback<'a>>,
+
+    /// The token checked by the prover threads to stop proving early.
+    pub cancellation_token: Option<CancellationToken>,
+
+    /// The file the profile of the execution is written to.
+    pub profiler: Option<PathBuf>,
+
+    /// The registry of precompiles defined outside of `sp1-core`.
+    ///
+    /// Note: `None` denotes no precompile extensions.
+    pub precompiles: Option<PrecompileRegistry>,
+
+    /// The directory in which execution checkpoints and finished shard proofs are kept, so that an
+    /// interrupted core proof can be resumed.
+    pub checkpoint_dir: Option<PathBuf>,
 }
 
 #[derive(Clone, Default)]
@@ -24,6 +46,11 @@ pub struct SP1ContextBuilder<'a> {
     hook_registry_entries: Vec<(u32, BoxedHook<'a>)>,
     subproof_verifier: Option<Arc<dyn SubproofVerifier + 'a>>,
     max_cycles: Option<u64>,
+    progress: Option<ProgressCallback<'a>>,
+    cancellation_token: Option<CancellationToken>,
+    profiler: Option<PathBuf>,
+    precompiles: PrecompileRegistry,
+    checkpoint_dir: Option<PathBuf>,
 }
 
 impl<'a> SP1Context<'a> {
@@ -31,6 +58,20 @@ impl<'a> SP1Context<'a> {
     pub fn builder() -> SP1ContextBuilder<'a> {
         SP1ContextBuilder::new()
     }
+
+    /// Notify the progress callback, if any, of the given event.
+    pub fn report(&self, event: ProgressEvent) {
+        if let Some(progress) = &self.progress {
+            progress(event);
+        }
+    }
+
+    /// Whether the cancellation token, if any, has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation_token
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+    }
 }
 
 impl<'a> SP1ContextBuilder<'a> {
@@ -60,10 +101,21 @@ impl<'a> SP1ContextBuilder<'a> {
             });
         let subproof_verifier = take(&mut self.subproof_verifier);
         let cycle_limit = take(&mut self.max_cycles);
+        let progress = take(&mut self.progress);
+        let cancellation_token = take(&mut self.cancellation_token);
+        let profiler = take(&mut self.profiler);
+        let precompiles = take(&mut self.precompiles);
+        let precompiles = (!precompiles.is_empty()).then_some(precompiles);
+        let checkpoint_dir = take(&mut self.checkpoint_dir);
         SP1Context {
             hook_registry,
             subproof_verifier,
             max_cycles: cycle_limit,
+            progress,
+            cancellation_token,
+            profiler,
+            precompiles,
+            checkpoint_dir,
         }
     }
 
@@ -106,13 +158,61 @@ impl<'a> SP1ContextBuilder<'a> {
         self.max_cycles = Some(max_cycles);
         self
     }
+
+    /// Set a callback notified as the proof makes progress.
+    ///
+    /// See [ProgressEvent] for the reported events.
+    pub fn on_progress(&mut self, f: impl Fn(ProgressEvent) + Send + Sync + 'a) -> &mut Self {
+        self.progress = Some(Arc::new(f));
+        self
+    }
+
+    /// Set a token which stops proving once cancelled.
+    pub fn cancellation_token(&mut self, token: CancellationToken) -> &mut Self {
+        self.cancellation_token = Some(token);
+        self
+    }
+
+    /// Profile the call stacks of the execution and write the profile to the given path.
+    ///
+    /// See [Profiler](super::Profiler) for the supported formats.
+    pub fn profiler(&mut self, path: impl Into<PathBuf>) -> &mut Self {
+        self.profiler = Some(path.into());
+        self
+    }
+
+    /// Add a [PrecompileExtension] into the context.
+    ///
+    /// The machine proving the execution must include the chip of the precompile, see
+    /// [ExtendedRiscvAir](crate::stark::ExtendedRiscvAir). The recursive prover only proves
+    /// [RiscvAir](crate::stark::RiscvAir), so it rejects contexts with precompiles.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the syscall code of the precompile is invalid or already used, see
+    /// [PrecompileRegistry::register].
+    pub fn precompile(&mut self, precompile: impl PrecompileExtension) -> &mut Self {
+        self.precompiles.register(precompile);
+        self
+    }
+
+    /// Keep execution checkpoints and finished shard proofs in the given directory.
+    ///
+    /// If proving is interrupted, proving again with the same directory resumes from the last
+    /// completed checkpoint.
+    pub fn checkpoint_dir(&mut self, dir: impl Into<PathBuf>) -> &mut Self {
+        self.checkpoint_dir = Some(dir.into());
+        self
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use std::sync::Arc;
 
-    use crate::runtime::{DefaultSubproofVerifier, SP1Context};
+    use std::sync::Mutex;
+
+    use crate::runtime::{CancellationToken, DefaultSubproofVerifier, ProgressEvent, SP1Context};
 
     #[test]
     fn defaults() {
@@ -120,10 +220,20 @@ mod tests {
             hook_registry,
             subproof_verifier,
             max_cycles: cycle_limit,
+            progress,
+            cancellation_token,
+            profiler,
+            precompiles,
+            checkpoint_dir,
         } = SP1Context::builder().build();
         assert!(hook_registry.is_none());
         assert!(subproof_verifier.is_none());
         assert!(cycle_limit.is_none());
+        assert!(progress.is_none());
+        assert!(cancellation_token.is_none());
+        assert!(profiler.is_none());
+        assert!(precompiles.is_none());
+        assert!(checkpoint_dir.is_none());
     }
 
     #[test]
@@ -161,4 +271,23 @@ mod tests {
             .build();
         assert!(subproof_verifier.is_some());
     }
+
+    #[test]
+    fn progress_and_cancellation() {
+        let events = Mutex::new(Vec::new());
+        let token = CancellationToken::new();
+        let context = SP1Context::builder()
+            .on_progress(|event| events.lock().unwrap().push(event))
+            .cancellation_token(token.clone())
+            .build();
+        context.report(ProgressEvent::ShardsProven { shards: 1 });
+        assert!(!context.is_cancelled());
+        token.cancel();
+        assert!(context.is_cancelled());
+        drop(context);
+        assert_eq!(
+            events.into_inner().unwrap(),
+            vec![ProgressEvent::ShardsProven { shards: 1 }]
+        );
+    }
 }
@@ -0,0 +1,221 @@
+use core::fmt::Debug;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use strum::IntoEnumIterator;
+
+use super::{Syscall, SyscallCode};
+
+/// A precompile defined outside of `sp1-core`.
+///
+/// An extension is a [Syscall] which records events of type [PrecompileExtension::Event] with
+/// [ExecutionRecord::add_extension_event](super::ExecutionRecord::add_extension_event). The chip
+/// proving these events reads them back with
+/// [ExecutionRecord::extension_events](super::ExecutionRecord::extension_events), and is added to
+/// the machine with [ExtendedRiscvAir](crate::stark::ExtendedRiscvAir).
+pub trait PrecompileExtension: Syscall + 'static {
+    /// The event recorded by each invocation of the syscall.
+    type Event: Serialize + DeserializeOwned;
+
+    /// The code of the syscall.
+    ///
+    /// Like the codes of [SyscallCode], the first byte is the syscall id, the second byte is 1 as
+    /// the syscall is sent to its chip, and the third byte is the number of extra cycles.
+    const SYSCALL_CODE: u32;
+
+    /// The number of rows of the chip used by each event.
+    const ROWS_PER_EVENT: usize = 1;
+}
+
+/// A precompile extension registered with a [PrecompileRegistry].
+#[derive(Clone)]
+pub(crate) struct RegisteredPrecompile {
+    pub(crate) syscall: Arc<dyn Syscall>,
+    pub(crate) rows_per_event: usize,
+}
+
+/// A registry of precompile extensions, indexed by their syscall codes.
+#[derive(Clone, Default)]
+pub struct PrecompileRegistry {
+    /// Table of registered precompiles. Prefer using `PrecompileRegistry::register` and
+    /// `PrecompileRegistry::get` over interacting with this field directly.
+    pub(crate) table: HashMap<u32, RegisteredPrecompile>,
+}
+
+impl PrecompileRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Register a precompile extension.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the syscall code is not a valid code for a precompile, or if its syscall id is
+    /// already used by a builtin syscall or by another registered precompile.
+    pub fn register<P: PrecompileExtension>(&mut self, precompile: P) -> &mut Self {
+        let code = P::SYSCALL_CODE;
+        let [id, send, cycles, _] = code.to_le_bytes();
+        assert!(
+            SyscallCode::try_from_u32(code).is_none(),
+            "syscall code {:#010x} is a builtin syscall",
+            code
+        );
+        assert_eq!(send, 1, "precompile syscall {:#010x} must be sent", code);
+        assert_eq!(
+            cycles as u32,
+            precompile.num_extra_cycles(),
+            "syscall code {:#010x} does not encode the extra cycles of the precompile",
+            code
+        );
+        assert!(
+            P::ROWS_PER_EVENT > 0,
+            "precompile must use at least one row"
+        );
+        let id_taken = SyscallCode::iter()
+            .map(|syscall| syscall.syscall_id())
+            .chain(self.table.keys().map(|code| code.to_le_bytes()[0] as u32))
+            .any(|taken| taken == id as u32);
+        assert!(!id_taken, "syscall id {:#04x} is already used", id);
+
+        self.table.insert(
+            code,
+            RegisteredPrecompile {
+                syscall: Arc::new(precompile),
+                rows_per_event: P::ROWS_PER_EVENT,
+            },
+        );
+        self
+    }
+
+    /// Get the syscall of the precompile registered at the given code, if it exists.
+    pub fn get(&self, code: u32) -> Option<&Arc<dyn Syscall>> {
+        self.table.get(&code).map(|precompile| &precompile.syscall)
+    }
+
+    /// Whether no precompiles are registered.
+    pub fn is_empty(&self) -> bool {
+        self.table.is_empty()
+    }
+}
+
+impl Debug for PrecompileRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut keys = self.table.keys().collect::<Vec<_>>();
+        keys.sort_unstable();
+        f.debug_struct("PrecompileRegistry")
+            .field(
+                "table",
+                &format_args!(
+                    "{{{} precompiles registered at {:x?}}}",
+                    self.table.len(),
+                    keys
+                ),
+            )
+            .finish()
+    }
+}
+
+/// The events recorded by a precompile extension, serialized so that the record does not depend
+/// on the event types of the extensions.
+#[derive(Default, Clone, Debug, Serialize, Deserialize)]
+pub struct ExtensionEvents {
+    /// The number of rows of the chip used by each event.
+    pub rows_per_event: usize,
+
+    /// The serialized events.
+    pub events: Vec<Vec<u8>>,
+}
+
+impl ExtensionEvents {
+    pub fn new(rows_per_event: usize) -> Self {
+        Self {
+            rows_per_event,
+            events: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use crate::runtime::{ExecutionRecord, SyscallContext};
+    use crate::utils::SP1CoreOpts;
+
+    use super::*;
+
+    /// A precompile which records its arguments.
+    struct Echo;
+
+    impl Syscall for Echo {
+        fn execute(&self, ctx: &mut SyscallContext, arg1: u32, arg2: u32) -> Option<u32> {
+            ctx.record_mut().add_extension_event::<Self>(&(arg1, arg2));
+            None
+        }
+    }
+
+    impl PrecompileExtension for Echo {
+        type Event = (u32, u32);
+        const SYSCALL_CODE: u32 = 0x00_00_01_80;
+        const ROWS_PER_EVENT: usize = 4;
+    }
+
+    #[test]
+    pub fn registry_register_and_get() {
+        let mut registry = PrecompileRegistry::new();
+        assert!(registry.is_empty());
+        registry.register(Echo);
+        assert!(registry.get(Echo::SYSCALL_CODE).is_some());
+        assert!(registry.get(SyscallCode::HALT as u32).is_none());
+        assert_eq!(
+            format!("{:?}", registry),
+            "PrecompileRegistry { table: {1 precompiles registered at [180]} }"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "is already used")]
+    pub fn registry_rejects_duplicate_id() {
+        PrecompileRegistry::new().register(Echo).register(Echo);
+    }
+
+    #[test]
+    #[should_panic(expected = "is a builtin syscall")]
+    pub fn registry_rejects_builtin_code() {
+        struct Halt;
+        impl Syscall for Halt {
+            fn execute(&self, _: &mut SyscallContext, _: u32, _: u32) -> Option<u32> {
+                None
+            }
+        }
+        impl PrecompileExtension for Halt {
+            type Event = ();
+            const SYSCALL_CODE: u32 = SyscallCode::HALT as u32;
+        }
+        PrecompileRegistry::new().register(Halt);
+    }
+
+    #[test]
+    pub fn record_splits_extension_events() {
+        let mut record = ExecutionRecord::default();
+        for i in 0..10 {
+            record.add_extension_event::<Echo>(&(i, i + 1));
+        }
+        assert_eq!(record.num_extension_events::<Echo>(), 10);
+        assert_eq!(record.extension_events::<Echo>()[3], (3, 4));
+
+        let mut opts = SP1CoreOpts::default().split_opts;
+        opts.deferred_shift_threshold = 12;
+        let shards = record.split(true, opts);
+        let counts = shards
+            .iter()
+            .map(|shard| shard.num_extension_events::<Echo>())
+            .filter(|&count| count > 0)
+            .collect::<Vec<_>>();
+        assert_eq!(counts.iter().sum::<usize>(), 10);
+        assert!(counts.iter().all(|&count| count <= 3));
+        assert_eq!(record.num_extension_events::<Echo>(), 0);
+    }
+}
@@ -5,8 +5,16 @@ use std::sync::{Arc, RwLock, RwLockWriteGuard};
 
 use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
 use k256::elliptic_curve::ops::Invert;
+use num::{BigUint, One, Zero};
 
 use super::Runtime;
+use crate::operations::field::params::FieldParameters;
+use crate::utils::ec::edwards::ed25519::{ed25519_sqrt, Ed25519BaseField};
+use crate::utils::ec::weierstrass::bls12_381::{bls12381_sqrt, Bls12381BaseField};
+use crate::utils::ec::weierstrass::bn254::{bn254_sqrt, Bn254BaseField};
+use crate::utils::ec::weierstrass::secp256k1::{secp256k1_sqrt, Secp256k1BaseField};
+use crate::utils::ec::weierstrass::secp256r1::{secp256r1_sqrt, Secp256r1BaseField};
+use crate::utils::ec::CurveType;
 
 /// A runtime hook, wrapped in a smart pointer.
 pub type BoxedHook<'a> = Arc<RwLock<dyn Hook + Send + Sync + 'a>>;
@@ -14,6 +22,15 @@ pub type BoxedHook<'a> = Arc<RwLock<dyn Hook + Send + Sync + 'a>>;
 /// The file descriptor through which to access `hook_ecrecover`.
 pub const FD_ECRECOVER_HOOK: u32 = 5;
 
+/// The file descriptor through which to access `hook_fp_inverse`.
+pub const FD_FP_INVERSE_HOOK: u32 = 6;
+
+/// The file descriptor through which to access `hook_fp_sqrt`.
+pub const FD_FP_SQRT_HOOK: u32 = 7;
+
+/// The file descriptor through which to access `hook_bigint_divmod`.
+pub const FD_BIGINT_DIVMOD_HOOK: u32 = 8;
+
 /// A runtime hook. May be called during execution by writing to a specified file descriptor,
 /// accepting and returning arbitrary data.
 pub trait Hook {
@@ -74,6 +91,9 @@ impl<'a> Default for HookRegistry<'a> {
             // Note: To ensure any `fd` value is synced with `zkvm/precompiles/src/io.rs`,
             // add an assertion to the test `hook_fds_match` below.
             (FD_ECRECOVER_HOOK, hookify(hook_ecrecover)),
+            (FD_FP_INVERSE_HOOK, hookify(hook_fp_inverse)),
+            (FD_FP_SQRT_HOOK, hookify(hook_fp_sqrt)),
+            (FD_BIGINT_DIVMOD_HOOK, hookify(hook_bigint_divmod)),
         ]);
 
         Self { table }
@@ -141,6 +161,136 @@ pub fn hook_ecrecover(_env: HookEnv, buf: &[u8]) -> Vec<Vec<u8>> {
     vec![bytes.to_vec(), s_inverse.to_bytes().to_vec()]
 }
 
+/// Returns the curve whose base field is identified by the first byte of the input of the field
+/// hooks, along with the rest of the input.
+///
+/// The identifiers are the positions of the curves in [CurveType].
+fn hook_field(buf: &[u8]) -> (CurveType, BigUint, &[u8]) {
+    let (id, buf) = buf
+        .split_first()
+        .expect("field hook input should not be empty");
+    let curve = match id {
+        0 => CurveType::Secp256k1,
+        1 => CurveType::Secp256r1,
+        2 => CurveType::Bn254,
+        3 => CurveType::Ed25519,
+        4 => CurveType::Bls12381,
+        _ => panic!("invalid field hook field id: {}", id),
+    };
+    let modulus = match curve {
+        CurveType::Secp256k1 => Secp256k1BaseField::modulus(),
+        CurveType::Secp256r1 => Secp256r1BaseField::modulus(),
+        CurveType::Bn254 => Bn254BaseField::modulus(),
+        CurveType::Ed25519 => Ed25519BaseField::modulus(),
+        CurveType::Bls12381 => Bls12381BaseField::modulus(),
+    };
+    (curve, modulus, buf)
+}
+
+/// Encodes `n` as little endian bytes, padded to `len` bytes.
+fn to_bytes_le_padded(n: &BigUint, len: usize) -> Vec<u8> {
+    let mut bytes = n.to_bytes_le();
+    bytes.resize(len, 0);
+    bytes
+}
+
+/// Computes the inverse of an element of the base field of a curve.
+///
+/// # Arguments
+///
+/// * `env` - The environment in which the hook is invoked.
+/// * `buf` - The buffer containing the field identifier and the element.
+///     - The first byte identifies the field, see `sp1_zkvm::lib::hints`.
+///     - The element is given in little endian, with the size of a field element.
+///
+/// The result is the inverse of the element in little endian, or zero if the element is zero.
+///
+/// WARNING: This function is used to compute the inverse outside of the zkVM context. These values
+/// must be constrained by the zkVM for correctness.
+pub fn hook_fp_inverse(_env: HookEnv, buf: &[u8]) -> Vec<Vec<u8>> {
+    let (_, modulus, x) = hook_field(buf);
+    let len = x.len();
+    let x = BigUint::from_bytes_le(x) % &modulus;
+
+    // The modulus is prime, so the inverse is `x^(p - 2)`, which is zero for zero.
+    let inverse = x.modpow(&(&modulus - 2u32), &modulus);
+
+    vec![to_bytes_le_padded(&inverse, len)]
+}
+
+/// Computes a square root of an element of the base field of a curve.
+///
+/// # Arguments
+///
+/// * `env` - The environment in which the hook is invoked.
+/// * `buf` - The buffer containing the field identifier and the element, like for
+///   [hook_fp_inverse].
+///
+/// The result is a pair of a single byte, which is 1 if the element is a square and 0 otherwise,
+/// and of a root in little endian. If the element is not a square, the root is a square root of
+/// the element times the fixed non-residue of the field: -1, or 2 for the Ed25519 base field where
+/// -1 is a square.
+///
+/// WARNING: This function is used to compute the square root outside of the zkVM context. These
+/// values must be constrained by the zkVM for correctness.
+pub fn hook_fp_sqrt(_env: HookEnv, buf: &[u8]) -> Vec<Vec<u8>> {
+    let (curve, modulus, x) = hook_field(buf);
+    let len = x.len();
+    let x = BigUint::from_bytes_le(x) % &modulus;
+
+    // Euler's criterion: a nonzero element is a square if and only if `x^((p - 1) / 2) = 1`.
+    let is_square = x.is_zero() || x.modpow(&((&modulus - 1u32) >> 1), &modulus).is_one();
+    let square = if is_square {
+        x
+    } else {
+        let non_residue = match curve {
+            CurveType::Ed25519 => BigUint::from(2u32),
+            _ => &modulus - 1u32,
+        };
+        x * non_residue % &modulus
+    };
+    let root = match curve {
+        CurveType::Secp256k1 => secp256k1_sqrt(&square),
+        CurveType::Secp256r1 => secp256r1_sqrt(&square),
+        CurveType::Bn254 => bn254_sqrt(&square),
+        CurveType::Ed25519 => ed25519_sqrt(&square),
+        CurveType::Bls12381 => bls12381_sqrt(&square),
+    };
+
+    vec![vec![is_square as u8], to_bytes_le_padded(&root, len)]
+}
+
+/// Computes the quotient and remainder of the division of two big integers.
+///
+/// # Arguments
+///
+/// * `env` - The environment in which the hook is invoked.
+/// * `buf` - The buffer containing the dividend and the divisor.
+///     - The dividend and the divisor are given in little endian, with the same size.
+///
+/// The result is a pair of the quotient and the remainder in little endian, with the size of the
+/// inputs. The divisor must be nonzero.
+///
+/// WARNING: This function is used to compute the division outside of the zkVM context. These values
+/// must be constrained by the zkVM for correctness.
+pub fn hook_bigint_divmod(_env: HookEnv, buf: &[u8]) -> Vec<Vec<u8>> {
+    assert_eq!(
+        buf.len() % 2,
+        0,
+        "divmod input should have a dividend and a divisor of the same length"
+    );
+    let len = buf.len() / 2;
+    let (x, y) = buf.split_at(len);
+    let x = BigUint::from_bytes_le(x);
+    let y = BigUint::from_bytes_le(y);
+    assert!(!y.is_zero(), "divmod divisor should be nonzero");
+
+    vec![
+        to_bytes_le_padded(&(&x / &y), len),
+        to_bytes_le_padded(&(&x % &y), len),
+    ]
+}
+
 #[cfg(test)]
 pub mod tests {
     use crate::{
@@ -154,7 +304,21 @@ pub mod tests {
     #[test]
     pub fn hook_fds_match() {
         use sp1_zkvm::lib::io;
-        assert_eq!(FD_ECRECOVER_HOOK, io::FD_ECRECOVER_HOOK)
+        assert_eq!(FD_ECRECOVER_HOOK, io::FD_ECRECOVER_HOOK);
+        assert_eq!(FD_FP_INVERSE_HOOK, io::FD_FP_INVERSE_HOOK);
+        assert_eq!(FD_FP_SQRT_HOOK, io::FD_FP_SQRT_HOOK);
+        assert_eq!(FD_BIGINT_DIVMOD_HOOK, io::FD_BIGINT_DIVMOD_HOOK);
+    }
+
+    #[test]
+    pub fn hook_field_ids_match() {
+        use sp1_zkvm::lib::hints::{self, HookField};
+        let field = |id| hook_field(&[id]).0;
+        assert_eq!(field(hints::Secp256k1BaseField::ID), CurveType::Secp256k1);
+        assert_eq!(field(hints::Secp256r1BaseField::ID), CurveType::Secp256r1);
+        assert_eq!(field(hints::Bn254BaseField::ID), CurveType::Bn254);
+        assert_eq!(field(hints::Ed25519BaseField::ID), CurveType::Ed25519);
+        assert_eq!(field(hints::Bls12381BaseField::ID), CurveType::Bls12381);
     }
 
     #[test]
@@ -168,6 +332,56 @@ pub mod tests {
         assert_eq!(HookRegistry::empty().table.len(), 0);
     }
 
+    #[test]
+    fn test_fp_hooks() {
+        let runtime = Runtime::new(Program::default(), Default::default());
+        let fields = [
+            (0, Secp256k1BaseField::modulus(), 32),
+            (1, Secp256r1BaseField::modulus(), 32),
+            (2, Bn254BaseField::modulus(), 32),
+            (3, Ed25519BaseField::modulus(), 32),
+            (4, Bls12381BaseField::modulus(), 48),
+        ];
+        for (id, modulus, len) in fields {
+            for x in [1u32, 2, 3, 5, 7, 1 << 20].map(BigUint::from) {
+                let mut buf = vec![id];
+                buf.extend(to_bytes_le_padded(&x, len));
+
+                let inverse = hook_fp_inverse(runtime.hook_env(), &buf);
+                let inverse = BigUint::from_bytes_le(&inverse[0]);
+                assert!((&x * inverse % &modulus).is_one());
+
+                let sqrt = hook_fp_sqrt(runtime.hook_env(), &buf);
+                assert_eq!(sqrt[1].len(), len);
+                let root = BigUint::from_bytes_le(&sqrt[1]);
+                let non_residue = match id {
+                    3 => BigUint::from(2u32),
+                    _ => &modulus - 1u32,
+                };
+                let square = match sqrt[0][0] {
+                    1 => x.clone(),
+                    _ => &x * non_residue % &modulus,
+                };
+                assert_eq!(&root * &root % &modulus, square);
+            }
+        }
+    }
+
+    #[test]
+    fn test_bigint_divmod_hook() {
+        let runtime = Runtime::new(Program::default(), Default::default());
+        let x = BigUint::from(u128::MAX) * 12345u32 + 678u32;
+        let y = BigUint::from(u64::MAX);
+        let mut buf = to_bytes_le_padded(&x, 32);
+        buf.extend(to_bytes_le_padded(&y, 32));
+
+        let result = hook_bigint_divmod(runtime.hook_env(), &buf);
+        let q = BigUint::from_bytes_le(&result[0]);
+        let r = BigUint::from_bytes_le(&result[1]);
+        assert!(r < y);
+        assert_eq!(q * y + r, x);
+    }
+
     #[test]
     fn test_ecrecover_program_prove() {
         utils::setup_logger();
@@ -1,13 +1,17 @@
 mod context;
+mod extension;
 mod hooks;
 mod instruction;
 mod io;
 mod memory;
 mod opcode;
+mod profiler;
 mod program;
+mod progress;
 mod record;
 mod register;
 mod report;
+mod snapshot;
 mod state;
 mod syscall;
 #[macro_use]
@@ -15,14 +19,18 @@ mod utils;
 mod subproof;
 
 pub use context::*;
+pub use extension::*;
 pub use hooks::*;
 pub use instruction::*;
 pub use memory::*;
 pub use opcode::*;
+pub use profiler::*;
 pub use program::*;
+pub use progress::*;
 pub use record::*;
 pub use register::*;
 pub use report::*;
+pub use snapshot::*;
 pub use state::*;
 pub use subproof::*;
 pub use syscall::*;
@@ -42,6 +50,9 @@ use crate::alu::create_alu_lookup_id;
 use crate::alu::create_alu_lookups;
 use crate::bytes::NUM_BYTE_LOOKUP_CHANNELS;
 use crate::memory::MemoryInitializeFinalizeEvent;
+use crate::syscall::precompiles::blake::{BLAKE2B_NUM_ROUNDS, BLAKE3_NUM_ROUNDS};
+use crate::syscall::precompiles::keccak256::keccak256_hash_starts_shard;
+use crate::utils::ec::scalar_mul::SCALAR_MUL_NUM_WINDOWS;
 use crate::utils::SP1CoreOpts;
 use crate::{alu::AluEvent, cpu::CpuEvent};
 
@@ -73,8 +84,9 @@ pub struct Runtime<'a> {
 
     pub shard_batch_size: u32,
 
-    /// A counter for the number of cycles that have been executed in certain functions.
-    pub cycle_tracker: HashMap<String, (u64, u32)>,
+    /// The regions tracked with `cycle-tracker-start` which have not ended yet, from the
+    /// outermost, with the cycle each of them started at.
+    pub cycle_tracker: Vec<(String, u64)>,
 
     /// A buffer for stdout and stderr IO.
     pub io_buf: HashMap<u32, String>,
@@ -82,6 +94,9 @@ pub struct Runtime<'a> {
     /// A buffer for writing trace events to a file.
     pub trace_buf: Option<BufWriter<File>>,
 
+    /// The profiler of the call stacks of the program, if profiling is enabled.
+    pub profiler: Option<Profiler>,
+
     /// Whether the runtime is in constrained mode or not.
     ///
     /// In unconstrained mode, any events, clock, register, or memory changes are reset after leaving
@@ -94,6 +109,9 @@ pub struct Runtime<'a> {
     /// The mapping between syscall codes and their implementations.
     pub syscall_map: HashMap<SyscallCode, Arc<dyn Syscall>>,
 
+    /// The registry of precompiles defined outside of `sp1-core`.
+    pub precompiles: PrecompileRegistry,
+
     /// The maximum number of cycles for a syscall.
     pub max_syscall_cycles: u32,
 
@@ -117,6 +135,9 @@ pub struct Runtime<'a> {
 
     /// The maximum number of cpu cycles to use for execution.
     pub max_cycles: Option<u64>,
+
+    /// The condition at which [Runtime::run_until] pauses execution.
+    pause_condition: Option<PauseCondition>,
 }
 
 #[derive(Error, Debug, Serialize, Deserialize)]
@@ -133,6 +154,8 @@ pub enum ExecutionError {
     ExceededCycleLimit(u64),
     #[error("got unimplemented as opcode")]
     Unimplemented(),
+    #[error("invalid input to syscall {0}: {1}")]
+    InvalidSyscallInput(u32, String),
 }
 
 impl<'a> Runtime<'a> {
@@ -162,8 +185,15 @@ impl<'a> Runtime<'a> {
 
         // Determine the maximum number of cycles for any syscall.
         let syscall_map = default_syscall_map();
+        let precompiles = context.precompiles.unwrap_or_default();
         let max_syscall_cycles = syscall_map
             .values()
+            .chain(
+                precompiles
+                    .table
+                    .values()
+                    .map(|precompile| &precompile.syscall),
+            )
             .map(|syscall| syscall.num_extra_cycles())
             .max()
             .unwrap_or(0);
@@ -172,6 +202,9 @@ impl<'a> Runtime<'a> {
             .subproof_verifier
             .unwrap_or_else(|| Arc::new(DefaultSubproofVerifier::new()));
         let hook_registry = context.hook_registry.unwrap_or_default();
+        let profiler = context
+            .profiler
+            .map(|path| Profiler::new(path, program.pc_start));
 
         Self {
             record,
@@ -181,12 +214,14 @@ impl<'a> Runtime<'a> {
             memory_accesses: MemoryAccessRecord::default(),
             shard_size: (opts.shard_size as u32) * 4,
             shard_batch_size: opts.shard_batch_size as u32,
-            cycle_tracker: HashMap::new(),
+            cycle_tracker: Vec::new(),
             io_buf: HashMap::new(),
             trace_buf,
+            profiler,
             unconstrained: false,
             unconstrained_state: ForkState::default(),
             syscall_map,
+            precompiles,
             emit_events: true,
             max_syscall_cycles,
             report: ExecutionReport::default(),
@@ -195,6 +230,7 @@ impl<'a> Runtime<'a> {
             hook_registry,
             opts,
             max_cycles: context.max_cycles,
+            pause_condition: None,
         }
     }
 
@@ -219,6 +255,35 @@ impl<'a> Runtime<'a> {
         runtime
     }
 
+    /// Resume a paused execution from a snapshot, using the hooks and subproof verifier of the
+    /// given context.
+    pub fn from_snapshot(
+        program: Program,
+        snapshot: ExecutionSnapshot,
+        opts: SP1CoreOpts,
+        context: SP1Context<'a>,
+    ) -> Self {
+        let mut runtime = Self::with_context(program, opts, context);
+        runtime.state = snapshot.state;
+        runtime.report = snapshot.report;
+        runtime.cycle_tracker = snapshot.cycle_tracker;
+        runtime.io_buf = snapshot.io_buf;
+        runtime
+    }
+
+    /// Take a snapshot of the execution, which can be resumed with [Runtime::from_snapshot].
+    ///
+    /// Only snapshots taken outside of an unconstrained block, such as after [Runtime::run_until]
+    /// paused, can be resumed.
+    pub fn snapshot(&self) -> ExecutionSnapshot {
+        ExecutionSnapshot {
+            state: self.state.clone(),
+            report: self.report.clone(),
+            cycle_tracker: self.cycle_tracker.clone(),
+            io_buf: self.io_buf.clone(),
+        }
+    }
+
     /// Get the current values of the registers.
     pub fn registers(&self) -> [u32; 32] {
         let mut registers = [0; 32];
@@ -832,17 +897,23 @@ impl<'a> Runtime<'a> {
                 let syscall_id = self.register(t0);
                 c = self.rr(Register::X11, MemoryAccessPosition::C);
                 b = self.rr(Register::X10, MemoryAccessPosition::B);
-                let syscall = SyscallCode::from_u32(syscall_id);
+                // Syscalls which are not builtin are looked up in the registered precompiles.
+                let syscall = SyscallCode::try_from_u32(syscall_id);
 
                 if self.print_report && !self.unconstrained {
-                    self.report
-                        .syscall_counts
-                        .entry(syscall)
-                        .and_modify(|c| *c += 1)
-                        .or_insert(1);
+                    if let Some(syscall) = syscall {
+                        self.report
+                            .syscall_counts
+                            .entry(syscall)
+                            .and_modify(|c| *c += 1)
+                            .or_insert(1);
+                    }
                 }
 
-                let syscall_impl = self.get_syscall(syscall).cloned();
+                let syscall_impl = match syscall {
+                    Some(syscall) => self.get_syscall(syscall).cloned(),
+                    None => self.precompiles.get(syscall_id).cloned(),
+                };
                 let mut precompile_rt = SyscallContext::new(self);
                 precompile_rt.syscall_lookup_id = syscall_lookup_id;
                 let (precompile_next_pc, precompile_cycles, returned_exit_code) =
@@ -850,6 +921,9 @@ impl<'a> Runtime<'a> {
                         // Executing a syscall optionally returns a value to write to the t0 register.
                         // If it returns None, we just keep the syscall_id in t0.
                         let res = syscall_impl.execute(&mut precompile_rt, b, c);
+                        if let Some(reason) = precompile_rt.error.take() {
+                            return Err(ExecutionError::InvalidSyscallInput(syscall_id, reason));
+                        }
                         if let Some(val) = res {
                             a = val;
                         } else {
@@ -857,7 +931,7 @@ impl<'a> Runtime<'a> {
                         }
 
                         // If the syscall is `HALT` and the exit code is non-zero, return an error.
-                        if syscall == SyscallCode::HALT && precompile_rt.exit_code != 0 {
+                        if syscall == Some(SyscallCode::HALT) && precompile_rt.exit_code != 0 {
                             return Err(ExecutionError::HaltWithNonZeroExitCode(
                                 precompile_rt.exit_code,
                             ));
@@ -882,22 +956,11 @@ impl<'a> Runtime<'a> {
                 exit_code = returned_exit_code;
 
                 // Update the syscall counts.
-                let syscall_count = self.state.syscall_counts.entry(syscall).or_insert(0);
-                let (threshold, multiplier) = match syscall {
-                    SyscallCode::KECCAK_PERMUTE => {
-                        (self.opts.split_opts.keccak_split_threshold, 24)
-                    }
-                    SyscallCode::SHA_EXTEND => {
-                        (self.opts.split_opts.sha_extend_split_threshold, 48)
-                    }
-                    SyscallCode::SHA_COMPRESS => {
-                        (self.opts.split_opts.sha_compress_split_threshold, 80)
-                    }
-                    _ => (self.opts.split_opts.deferred_shift_threshold, 1),
+                let nonce = match syscall {
+                    Some(syscall) => self.syscall_nonce(syscall),
+                    None => self.extension_nonce(syscall_id),
                 };
-                let nonce = (((*syscall_count as usize) % threshold) * multiplier) as u32;
                 self.record.nonce_lookup.insert(syscall_lookup_id, nonce);
-                *syscall_count += 1;
             }
             Opcode::EBREAK => {
                 return Err(ExecutionError::Breakpoint());
@@ -1011,9 +1074,25 @@ impl<'a> Runtime<'a> {
         // Log the current state of the runtime.
         self.log(&instruction);
 
+        // Peek at the syscall invoked by the instruction, before executing it overwrites t0.
+        // Registered precompiles are not builtin syscalls, so their cycles are profiled as the
+        // cycles of their caller.
+        let pc = self.state.pc;
+        let profiled = self.profiler.is_some() && !self.unconstrained;
+        let syscall = (profiled && instruction.opcode == Opcode::ECALL)
+            .then(|| SyscallCode::try_from_u32(self.register(Register::X5)))
+            .flatten();
+
         // Execute the instruction.
         self.execute_instruction(instruction)?;
 
+        // Record the cycle in the profile, unless it entered or left an unconstrained block.
+        if profiled && !self.unconstrained {
+            if let Some(profiler) = &mut self.profiler {
+                profiler.record(&instruction, pc, self.state.pc, syscall);
+            }
+        }
+
         // Increment the clock.
         self.state.global_clk += 1;
 
@@ -1099,6 +1178,60 @@ impl<'a> Runtime<'a> {
         while !self.execute().unwrap() {}
     }
 
+    /// Executes the program without tracing until the pause condition is reached, returning
+    /// whether the program halted first.
+    ///
+    /// A [PauseCondition::Pc] condition is only checked after the first instruction, so that
+    /// running again with the same condition continues to its next occurrence. Execution never
+    /// pauses inside an unconstrained block, so that it can be resumed from a
+    /// [Runtime::snapshot]. A cycle count reached inside such a block pauses right after it, while a
+    /// program counter reached inside it is not a pause point.
+    pub fn run_until(&mut self, condition: PauseCondition) -> Result<bool, ExecutionError> {
+        if let PauseCondition::Cycle(cycle) = condition {
+            if self.state.global_clk >= cycle {
+                return Ok(false);
+            }
+        }
+
+        self.emit_events = false;
+        self.print_report = true;
+        self.pause_condition = Some(condition);
+        let result = loop {
+            match self.execute() {
+                Ok(true) => break Ok(true),
+                Ok(false) if self.is_paused() => break Ok(false),
+                Ok(false) => {}
+                Err(e) => break Err(e),
+            }
+        };
+        self.pause_condition = None;
+        result
+    }
+
+    /// Executes a single instruction without tracing, returning whether the program has finished.
+    ///
+    /// Unlike [Runtime::run_until], this does not postprocess the execution once the program
+    /// finishes, so it is meant for inspecting a program rather than proving it.
+    pub fn step(&mut self) -> Result<bool, ExecutionError> {
+        if self.state.global_clk == 0 {
+            self.initialize();
+        }
+        self.emit_events = false;
+        self.execute_cycle()
+    }
+
+    /// Whether execution reached the pause condition set by [Runtime::run_until].
+    fn is_paused(&self) -> bool {
+        if self.unconstrained {
+            return false;
+        }
+        match self.pause_condition {
+            Some(PauseCondition::Cycle(cycle)) => self.state.global_clk >= cycle,
+            Some(PauseCondition::Pc(pc)) => self.state.pc == pc,
+            None => false,
+        }
+    }
+
     /// Executes up to `self.shard_batch_size` cycles of the program, returning whether the program has finished.
     fn execute(&mut self) -> Result<bool, ExecutionError> {
         // Get the program.
@@ -1122,6 +1255,10 @@ impl<'a> Runtime<'a> {
                 break;
             }
 
+            if self.is_paused() {
+                break;
+            }
+
             if self.shard_batch_size > 0 && current_shard != self.state.current_shard {
                 num_shards_executed += 1;
                 current_shard = self.state.current_shard;
@@ -1251,6 +1388,75 @@ impl<'a> Runtime<'a> {
         }
     }
 
+    /// Computes the nonce of a builtin syscall, which is the row of its event in the trace of the
+    /// chip, and updates the count of the syscall.
+    fn syscall_nonce(&mut self, syscall: SyscallCode) -> u32 {
+        let syscall_count = self
+            .state
+            .syscall_counts
+            .entry(syscall.count_map())
+            .or_insert(0);
+        let (threshold, multiplier) = match syscall {
+            SyscallCode::KECCAK_PERMUTE | SyscallCode::KECCAK256_HASH => {
+                (self.opts.split_opts.keccak_split_threshold, 24)
+            }
+            SyscallCode::SHA_EXTEND => (self.opts.split_opts.sha_extend_split_threshold, 48),
+            SyscallCode::SHA_COMPRESS => (self.opts.split_opts.sha_compress_split_threshold, 80),
+            SyscallCode::SHA512_EXTEND => (self.opts.split_opts.sha512_extend_split_threshold, 64),
+            SyscallCode::SHA512_COMPRESS => {
+                (self.opts.split_opts.sha512_compress_split_threshold, 96)
+            }
+            SyscallCode::BLAKE3_COMPRESS => (
+                self.opts.split_opts.blake3_compress_split_threshold,
+                BLAKE3_NUM_ROUNDS,
+            ),
+            SyscallCode::BLAKE2B_COMPRESS => (
+                self.opts.split_opts.blake2b_compress_split_threshold,
+                BLAKE2B_NUM_ROUNDS,
+            ),
+            SyscallCode::ED_MUL | SyscallCode::SECP256K1_MUL | SyscallCode::BN254_MUL => (
+                self.opts.split_opts.ec_mul_split_threshold,
+                SCALAR_MUL_NUM_WINDOWS,
+            ),
+            _ => (self.opts.split_opts.deferred_shift_threshold, 1),
+        };
+        let nonce = if syscall == SyscallCode::KECCAK256_HASH {
+            // Hashes are split into shards by their number of permutations, so the nonce
+            // counts the permutations of the earlier hashes in the shard.
+            let num_blocks = self
+                .record
+                .keccak256_hash_events
+                .last()
+                .unwrap()
+                .num_blocks();
+            let shard_blocks = &mut self.state.keccak256_hash_shard_blocks;
+            if keccak256_hash_starts_shard(*shard_blocks, num_blocks, threshold) {
+                *shard_blocks = 0;
+            }
+            let nonce = (*shard_blocks * multiplier) as u32;
+            *shard_blocks += num_blocks;
+            nonce
+        } else {
+            (((*syscall_count as usize) % threshold) * multiplier) as u32
+        };
+        *syscall_count += 1;
+        nonce
+    }
+
+    /// Computes the nonce of a registered precompile, which is the row of its event in the trace
+    /// of the chip, and updates the count of the precompile.
+    fn extension_nonce(&mut self, code: u32) -> u32 {
+        let rows_per_event = self.precompiles.table[&code].rows_per_event;
+        let threshold = self
+            .opts
+            .split_opts
+            .extension_split_threshold(rows_per_event);
+        let count = self.state.extension_syscall_counts.entry(code).or_insert(0);
+        let nonce = ((*count as usize % threshold) * rows_per_event) as u32;
+        *count += 1;
+        nonce
+    }
+
     fn get_syscall(&mut self, code: SyscallCode) -> Option<&Arc<dyn Syscall>> {
         self.syscall_map.get(&code)
     }
@@ -1267,7 +1473,9 @@ pub mod tests {
         },
     };
 
-    use super::{Instruction, Opcode, Program, Runtime};
+    use super::{
+        ExecutionSnapshot, Instruction, Opcode, PauseCondition, Program, Runtime, SP1Context,
+    };
 
     pub fn simple_program() -> Program {
         let instructions = vec![
@@ -1305,6 +1513,50 @@ pub mod tests {
         assert_eq!(runtime.register(Register::X31), 42);
     }
 
+    #[test]
+    fn test_snapshot_resume_and_fork() {
+        let program = fibonacci_program();
+        let mut expected = Runtime::new(program.clone(), SP1CoreOpts::default());
+        expected.run_untraced().unwrap();
+
+        // Pause halfway and round-trip the snapshot through bincode.
+        let mut runtime = Runtime::new(program.clone(), SP1CoreOpts::default());
+        let cycle = expected.state.global_clk / 2;
+        assert!(!runtime.run_until(PauseCondition::Cycle(cycle)).unwrap());
+        assert_eq!(runtime.state.global_clk, cycle);
+        let snapshot = bincode::serialize(&runtime.snapshot()).unwrap();
+        let snapshot: ExecutionSnapshot = bincode::deserialize(&snapshot).unwrap();
+
+        // Run two continuations from the same snapshot.
+        for _ in 0..2 {
+            let mut runtime = Runtime::from_snapshot(
+                program.clone(),
+                snapshot.clone(),
+                SP1CoreOpts::default(),
+                SP1Context::default(),
+            );
+            runtime.run_untraced().unwrap();
+            assert_eq!(runtime.state.global_clk, expected.state.global_clk);
+            assert_eq!(
+                runtime.state.public_values_stream,
+                expected.state.public_values_stream
+            );
+            assert_eq!(runtime.report, expected.report);
+        }
+    }
+
+    #[test]
+    fn test_run_until_pc() {
+        let program = simple_program();
+        let mut runtime = Runtime::new(program, SP1CoreOpts::default());
+        assert!(!runtime.run_until(PauseCondition::Pc(8)).unwrap());
+        assert_eq!(runtime.state.global_clk, 2);
+        assert_eq!(runtime.register(Register::X30), 37);
+        assert_eq!(runtime.register(Register::X31), 0);
+        assert!(runtime.run_until(PauseCondition::Pc(8)).unwrap());
+        assert_eq!(runtime.register(Register::X31), 42);
+    }
+
     #[test]
     #[should_panic]
     fn test_panic() {
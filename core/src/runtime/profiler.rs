@@ -0,0 +1,376 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use elf::abi::{STB_GLOBAL, STT_FUNC, STT_NOTYPE};
+use elf::endian::LittleEndian;
+use elf::ElfBytes;
+use rustc_demangle::demangle;
+use serde::Serialize;
+
+use super::{Instruction, Opcode, Register, SyscallCode};
+
+/// A frame of a profiled call stack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Frame {
+    /// A function, identified by the address it was called at.
+    Function(u32),
+
+    /// A syscall, attributed to the function which invoked it.
+    Syscall(SyscallCode),
+}
+
+/// A node of the call tree, holding the cycles spent in its frame itself.
+#[derive(Debug, Clone)]
+struct Node {
+    parent: usize,
+    frame: Frame,
+    children: HashMap<Frame, usize>,
+    cycles: u64,
+}
+
+/// A profiler which tracks the call stack of a program on `JAL`/`JALR` and counts the cycles
+/// spent in each stack.
+///
+/// Calls and returns are detected with the RISC-V return address conventions, so tail calls are
+/// attributed to their caller. Cycles spent in a syscall are attributed to a frame named after
+/// it, under the function which invoked it.
+///
+/// The profile is written by [Profiler::write], either as folded stacks, which can be rendered by
+/// `inferno-flamegraph` or `flamegraph.pl`, or, if the path has a `.json` extension, as a
+/// [speedscope](https://www.speedscope.app) profile.
+#[derive(Debug, Clone)]
+pub struct Profiler {
+    path: PathBuf,
+    nodes: Vec<Node>,
+    current: usize,
+    /// The nodes of the open calls and the address each of them returns to.
+    stack: Vec<(usize, u32)>,
+}
+
+impl Profiler {
+    /// Creates a profiler writing to the given path, for a program starting at `pc_start`.
+    pub fn new(path: impl Into<PathBuf>, pc_start: u32) -> Self {
+        Self {
+            path: path.into(),
+            nodes: vec![Node {
+                parent: 0,
+                frame: Frame::Function(pc_start),
+                children: HashMap::new(),
+                cycles: 0,
+            }],
+            current: 0,
+            stack: Vec::new(),
+        }
+    }
+
+    /// The path the profile is written to.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Records a cycle executing `instruction` at `pc`, which moved the program counter to
+    /// `next_pc`. `syscall` is the syscall invoked by an `ECALL`.
+    pub fn record(
+        &mut self,
+        instruction: &Instruction,
+        pc: u32,
+        next_pc: u32,
+        syscall: Option<SyscallCode>,
+    ) {
+        let node = match syscall {
+            Some(syscall) => self.child(self.current, Frame::Syscall(syscall)),
+            None => self.current,
+        };
+        self.nodes[node].cycles += 1;
+
+        let is_link =
+            |register: u32| register == Register::X1 as u32 || register == Register::X5 as u32;
+        match instruction.opcode {
+            Opcode::JAL if is_link(instruction.op_a) => self.call(next_pc, pc.wrapping_add(4)),
+            Opcode::JALR => {
+                let (rd, rs1) = (instruction.op_a, instruction.op_b);
+                match (is_link(rd), is_link(rs1)) {
+                    (true, true) if rd != rs1 => {
+                        self.ret(next_pc);
+                        self.call(next_pc, pc.wrapping_add(4));
+                    }
+                    (true, _) => self.call(next_pc, pc.wrapping_add(4)),
+                    (false, true) => self.ret(next_pc),
+                    (false, false) => {}
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// The total number of cycles recorded.
+    pub fn total_cycles(&self) -> u64 {
+        self.nodes.iter().map(|node| node.cycles).sum()
+    }
+
+    /// Writes the profile, using the symbols of the given ELF to name the functions.
+    pub fn write(&self, elf: &[u8]) -> io::Result<()> {
+        let symbols = Symbols::from_elf(elf);
+        let mut writer = BufWriter::new(File::create(&self.path)?);
+        if self
+            .path
+            .extension()
+            .is_some_and(|extension| extension == "json")
+        {
+            serde_json::to_writer(&mut writer, &self.speedscope(&symbols))?;
+        } else {
+            writer.write_all(self.folded(&symbols).as_bytes())?;
+        }
+        writer.flush()
+    }
+
+    fn child(&mut self, parent: usize, frame: Frame) -> usize {
+        if let Some(child) = self.nodes[parent].children.get(&frame) {
+            return *child;
+        }
+        let child = self.nodes.len();
+        self.nodes.push(Node {
+            parent,
+            frame,
+            children: HashMap::new(),
+            cycles: 0,
+        });
+        self.nodes[parent].children.insert(frame, child);
+        child
+    }
+
+    fn call(&mut self, target: u32, return_addr: u32) {
+        self.stack.push((self.current, return_addr));
+        self.current = self.child(self.current, Frame::Function(target));
+    }
+
+    fn ret(&mut self, target: u32) {
+        // Unwind to the call returning to the target, skipping the frames of calls which never
+        // returned normally. If there is none, return from the innermost call.
+        let depth = self
+            .stack
+            .iter()
+            .rposition(|(_, return_addr)| *return_addr == target)
+            .unwrap_or(self.stack.len().saturating_sub(1));
+        if let Some((node, _)) = self.stack.get(depth) {
+            self.current = *node;
+            self.stack.truncate(depth);
+        }
+    }
+
+    /// The frames from the root to the given node.
+    fn frames(&self, mut node: usize) -> Vec<Frame> {
+        let mut frames = vec![self.nodes[node].frame];
+        while node != 0 {
+            node = self.nodes[node].parent;
+            frames.push(self.nodes[node].frame);
+        }
+        frames.reverse();
+        frames
+    }
+
+    /// The profile as folded stacks, one `caller;callee cycles` line per stack.
+    fn folded(&self, symbols: &Symbols) -> String {
+        let mut lines = self
+            .nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, node)| node.cycles > 0)
+            .map(|(i, node)| {
+                let stack = self
+                    .frames(i)
+                    .into_iter()
+                    .map(|frame| symbols.name(frame))
+                    .collect::<Vec<_>>()
+                    .join(";");
+                format!("{} {}\n", stack, node.cycles)
+            })
+            .collect::<Vec<_>>();
+        lines.sort();
+        lines.concat()
+    }
+
+    /// The profile in the speedscope sampled format, with one weighted sample per stack.
+    fn speedscope(&self, symbols: &Symbols) -> SpeedscopeFile {
+        let mut frames = Vec::new();
+        let mut frame_indices = HashMap::new();
+        let mut samples = Vec::new();
+        let mut weights = Vec::new();
+        for (i, node) in self.nodes.iter().enumerate() {
+            if node.cycles == 0 {
+                continue;
+            }
+            let sample = self
+                .frames(i)
+                .into_iter()
+                .map(|frame| {
+                    let name = symbols.name(frame);
+                    *frame_indices.entry(name.clone()).or_insert_with(|| {
+                        frames.push(SpeedscopeFrame { name });
+                        frames.len() - 1
+                    })
+                })
+                .collect();
+            samples.push(sample);
+            weights.push(node.cycles);
+        }
+        let total = weights.iter().sum();
+        SpeedscopeFile {
+            schema: "https://www.speedscope.app/file-format-schema.json",
+            shared: SpeedscopeShared { frames },
+            profiles: vec![SpeedscopeProfile {
+                kind: "sampled",
+                name: "cycles".to_string(),
+                unit: "none",
+                start_value: 0,
+                end_value: total,
+                samples,
+                weights,
+            }],
+            exporter: "sp1",
+        }
+    }
+}
+
+/// The function symbols of an ELF, sorted by address.
+#[derive(Debug, Clone, Default)]
+struct Symbols(Vec<(u32, u32, String)>);
+
+impl Symbols {
+    /// Reads the function symbols of the ELF, if it has a symbol table.
+    fn from_elf(elf: &[u8]) -> Self {
+        let Ok(elf) = ElfBytes::<LittleEndian>::minimal_parse(elf) else {
+            return Self::default();
+        };
+        let Ok(Some((table, strings))) = elf.symbol_table() else {
+            return Self::default();
+        };
+        let mut symbols = table
+            .iter()
+            // Entry points written in assembly, such as `_start`, are untyped global symbols.
+            .filter(|symbol| {
+                symbol.st_symtype() == STT_FUNC
+                    || (symbol.st_symtype() == STT_NOTYPE && symbol.st_bind() == STB_GLOBAL)
+            })
+            .filter_map(|symbol| {
+                let name = strings.get(symbol.st_name as usize).ok()?;
+                let name = format!("{:#}", demangle(name));
+                Some((symbol.st_value as u32, symbol.st_size as u32, name))
+            })
+            .collect::<Vec<_>>();
+        symbols.sort();
+        Self(symbols)
+    }
+
+    /// The name of the function containing `addr`, or the address itself if it is unknown.
+    fn function(&self, addr: u32) -> String {
+        let index = self.0.partition_point(|(start, _, _)| *start <= addr);
+        match index.checked_sub(1).map(|i| &self.0[i]) {
+            Some((start, size, name)) if addr - start < (*size).max(1) => name.clone(),
+            _ => format!("{:#x}", addr),
+        }
+    }
+
+    fn name(&self, frame: Frame) -> String {
+        match frame {
+            Frame::Function(addr) => self.function(addr),
+            Frame::Syscall(syscall) => format!("syscall {}", syscall),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct SpeedscopeFile {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    shared: SpeedscopeShared,
+    profiles: Vec<SpeedscopeProfile>,
+    exporter: &'static str,
+}
+
+#[derive(Serialize)]
+struct SpeedscopeShared {
+    frames: Vec<SpeedscopeFrame>,
+}
+
+#[derive(Serialize)]
+struct SpeedscopeFrame {
+    name: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SpeedscopeProfile {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    name: String,
+    unit: &'static str,
+    start_value: u64,
+    end_value: u64,
+    samples: Vec<Vec<usize>>,
+    weights: Vec<u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Symbols;
+    use crate::runtime::{Instruction, Opcode, Program, Runtime, SP1Context};
+    use crate::utils::tests::FIBONACCI_ELF;
+    use crate::utils::SP1CoreOpts;
+
+    #[test]
+    fn test_call_stacks() {
+        // main calls f, which calls g twice, then main calls g.
+        let instructions = vec![
+            Instruction::new(Opcode::JAL, 1, 16, 0, true, true),
+            Instruction::new(Opcode::JAL, 1, 28, 0, true, true),
+            Instruction::new(Opcode::JAL, 0, 36, 0, true, true),
+            Instruction::new(Opcode::ADD, 0, 0, 0, false, true),
+            // f
+            Instruction::new(Opcode::ADD, 5, 1, 0, false, true),
+            Instruction::new(Opcode::JAL, 1, 12, 0, true, true),
+            Instruction::new(Opcode::JAL, 1, 8, 0, true, true),
+            Instruction::new(Opcode::JALR, 0, 5, 0, false, true),
+            // g
+            Instruction::new(Opcode::JALR, 0, 1, 0, false, true),
+        ];
+        let program = Program::new(instructions, 0, 0);
+        let context = SP1Context::builder().profiler("unused").build();
+        let mut runtime = Runtime::with_context(program, SP1CoreOpts::default(), context);
+        runtime.run_untraced().unwrap();
+
+        let profiler = runtime.profiler.unwrap();
+        assert_eq!(profiler.total_cycles(), runtime.state.global_clk);
+        assert_eq!(
+            profiler.folded(&Symbols::default()),
+            "0x0 3\n0x0;0x10 4\n0x0;0x10;0x20 2\n0x0;0x20 1\n"
+        );
+
+        let speedscope = profiler.speedscope(&Symbols::default());
+        assert_eq!(speedscope.shared.frames.len(), 3);
+        assert_eq!(speedscope.profiles[0].samples.len(), 4);
+        assert_eq!(speedscope.profiles[0].end_value, 10);
+    }
+
+    #[test]
+    fn test_profile_fibonacci() {
+        let program = Program::from(FIBONACCI_ELF);
+        let context = SP1Context::builder().profiler("unused").build();
+        let mut runtime = Runtime::with_context(program, SP1CoreOpts::default(), context);
+        runtime.run_untraced().unwrap();
+
+        let profiler = runtime.profiler.unwrap();
+        let folded = profiler.folded(&Symbols::from_elf(FIBONACCI_ELF));
+        assert!(folded.lines().all(|line| line.starts_with("_start")));
+        assert!(folded.contains(";main;"));
+        assert!(folded.contains("syscall HALT"));
+
+        let total = folded
+            .lines()
+            .map(|line| line.rsplit_once(' ').unwrap().1.parse::<u64>().unwrap())
+            .sum::<u64>();
+        assert_eq!(total, runtime.state.global_clk);
+    }
+}
@@ -0,0 +1,79 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// A callback that is notified as a proof makes progress.
+///
+/// The callback is invoked from the prover threads, so it should return quickly. To consume the
+/// events elsewhere, forward them to a channel.
+pub type ProgressCallback<'a> = Arc<dyn Fn(ProgressEvent) + Send + Sync + 'a>;
+
+/// A step of proof generation reported to a [ProgressCallback].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgressEvent {
+    /// Execution reached a checkpoint after executing the given total number of cycles.
+    Executed { cycles: u64 },
+
+    /// The given total number of shards has been executed and split off.
+    ShardsExecuted { shards: usize },
+
+    /// The given total number of shards has been proven.
+    ShardsProven { shards: usize },
+
+    /// A layer of the compress tree has been fully proven. Layer `0` holds the leaves and layer
+    /// `num_layers - 1` the root.
+    CompressLayerFinished { layer: usize, num_layers: usize },
+
+    /// A wrapping stage has started.
+    Wrap(WrapStage),
+}
+
+/// The stages run after the compress tree to wrap the compressed proof.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WrapStage {
+    /// Shrinking the compressed proof.
+    Shrink,
+    /// Wrapping the shrunk proof into a BN254 friendly field.
+    WrapBn254,
+    /// Generating the Plonk proof.
+    Plonk,
+    /// Generating the Groth16 proof.
+    Groth16,
+}
+
+/// A token used to stop a running proof.
+///
+/// Cloned tokens share the same state, so any clone may be used to cancel the proof. The proving
+/// threads check the token between units of work and return a `Cancelled` error once it is set.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// Creates a new token which is not cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requests the cancellation of every proof using this token.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    /// Whether cancellation has been requested.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CancellationToken;
+
+    #[test]
+    fn cancel_is_shared_between_clones() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+        assert!(!token.is_cancelled());
+        clone.cancel();
+        assert!(token.is_cancelled());
+    }
+}
@@ -1,13 +1,14 @@
 use hashbrown::HashMap;
 use itertools::EitherOrBoth;
 use itertools::Itertools;
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use p3_field::AbstractField;
 use serde::{Deserialize, Serialize};
 
 use super::program::Program;
-use super::Opcode;
+use super::{ExtensionEvents, Opcode, PrecompileExtension};
 use crate::air::PublicValues;
 use crate::alu::AluEvent;
 use crate::bytes::event::add_sharded_byte_lookup_events;
@@ -17,12 +18,21 @@ use crate::cpu::CpuEvent;
 use crate::runtime::MemoryInitializeFinalizeEvent;
 use crate::runtime::MemoryRecordEnum;
 use crate::stark::MachineRecord;
+use crate::syscall::precompiles::blake::{
+    Blake2bCompressEvent, Blake3CompressEvent, BLAKE2B_NUM_ROUNDS, BLAKE3_NUM_ROUNDS,
+};
 use crate::syscall::precompiles::edwards::EdDecompressEvent;
-use crate::syscall::precompiles::keccak256::KeccakPermuteEvent;
+use crate::syscall::precompiles::fptower::{Fp2AddSubEvent, Fp2MulEvent, FpOpEvent};
+use crate::syscall::precompiles::keccak256::{
+    keccak256_hash_starts_shard, Keccak256HashEvent, KeccakPermuteEvent,
+};
+use crate::syscall::precompiles::poseidon2::Poseidon2PermuteEvent;
 use crate::syscall::precompiles::sha256::{ShaCompressEvent, ShaExtendEvent};
-use crate::syscall::precompiles::uint256::Uint256MulEvent;
+use crate::syscall::precompiles::sha512::{Sha512CompressEvent, Sha512ExtendEvent};
+use crate::syscall::precompiles::uint::UintOpEvent;
 use crate::syscall::precompiles::ECDecompressEvent;
-use crate::syscall::precompiles::{ECAddEvent, ECDoubleEvent};
+use crate::syscall::precompiles::{ECAddEvent, ECDoubleEvent, ECMulEvent};
+use crate::utils::ec::scalar_mul::SCALAR_MUL_NUM_WINDOWS;
 use crate::utils::SP1CoreOpts;
 
 /// A record of the execution of a program.
@@ -70,27 +80,55 @@ pub struct ExecutionRecord {
 
     pub sha_compress_events: Vec<ShaCompressEvent>,
 
+    pub sha512_extend_events: Vec<Sha512ExtendEvent>,
+
+    pub sha512_compress_events: Vec<Sha512CompressEvent>,
+
     pub keccak_permute_events: Vec<KeccakPermuteEvent>,
 
+    pub keccak256_hash_events: Vec<Keccak256HashEvent>,
+
+    pub poseidon2_permute_events: Vec<Poseidon2PermuteEvent>,
+
+    pub blake3_compress_events: Vec<Blake3CompressEvent>,
+
+    pub blake2b_compress_events: Vec<Blake2bCompressEvent>,
+
     pub ed_add_events: Vec<ECAddEvent>,
 
+    pub ed_mul_events: Vec<ECMulEvent>,
+
     pub ed_decompress_events: Vec<EdDecompressEvent>,
 
     pub secp256k1_add_events: Vec<ECAddEvent>,
 
     pub secp256k1_double_events: Vec<ECDoubleEvent>,
 
+    pub secp256k1_mul_events: Vec<ECMulEvent>,
+
+    pub secp256r1_add_events: Vec<ECAddEvent>,
+
+    pub secp256r1_double_events: Vec<ECDoubleEvent>,
+
+    pub secp256r1_decompress_events: Vec<ECDecompressEvent>,
+
     pub bn254_add_events: Vec<ECAddEvent>,
 
     pub bn254_double_events: Vec<ECDoubleEvent>,
 
+    pub bn254_mul_events: Vec<ECMulEvent>,
+
     pub k256_decompress_events: Vec<ECDecompressEvent>,
 
     pub bls12381_add_events: Vec<ECAddEvent>,
 
     pub bls12381_double_events: Vec<ECDoubleEvent>,
 
-    pub uint256_mul_events: Vec<Uint256MulEvent>,
+    pub uint256_events: Vec<UintOpEvent>,
+
+    pub uint384_events: Vec<UintOpEvent>,
+
+    pub uint512_events: Vec<UintOpEvent>,
 
     pub memory_initialize_events: Vec<MemoryInitializeFinalizeEvent>,
 
@@ -98,6 +136,21 @@ pub struct ExecutionRecord {
 
     pub bls12381_decompress_events: Vec<ECDecompressEvent>,
 
+    pub bn254_fp_events: Vec<FpOpEvent>,
+
+    pub bn254_fp2_addsub_events: Vec<Fp2AddSubEvent>,
+
+    pub bn254_fp2_mul_events: Vec<Fp2MulEvent>,
+
+    pub bls12381_fp_events: Vec<FpOpEvent>,
+
+    pub bls12381_fp2_addsub_events: Vec<Fp2AddSubEvent>,
+
+    pub bls12381_fp2_mul_events: Vec<Fp2MulEvent>,
+
+    /// The events of the registered precompiles, indexed by their syscall codes.
+    pub extension_events: BTreeMap<u32, ExtensionEvents>,
+
     /// The public values.
     pub public_values: PublicValues<u32, u32>,
 
@@ -110,6 +163,11 @@ pub struct SplitOpts {
     pub keccak_split_threshold: usize,
     pub sha_extend_split_threshold: usize,
     pub sha_compress_split_threshold: usize,
+    pub sha512_extend_split_threshold: usize,
+    pub sha512_compress_split_threshold: usize,
+    pub blake3_compress_split_threshold: usize,
+    pub blake2b_compress_split_threshold: usize,
+    pub ec_mul_split_threshold: usize,
     pub memory_split_threshold: usize,
 }
 
@@ -120,9 +178,20 @@ impl SplitOpts {
             keccak_split_threshold: deferred_shift_threshold / 24,
             sha_extend_split_threshold: deferred_shift_threshold / 48,
             sha_compress_split_threshold: deferred_shift_threshold / 80,
+            sha512_extend_split_threshold: deferred_shift_threshold / 64,
+            sha512_compress_split_threshold: deferred_shift_threshold / 96,
+            blake3_compress_split_threshold: deferred_shift_threshold / BLAKE3_NUM_ROUNDS,
+            blake2b_compress_split_threshold: deferred_shift_threshold / BLAKE2B_NUM_ROUNDS,
+            ec_mul_split_threshold: deferred_shift_threshold / SCALAR_MUL_NUM_WINDOWS,
             memory_split_threshold: deferred_shift_threshold * 4,
         }
     }
+
+    /// The number of events of a registered precompile in a shard, given the number of rows of
+    /// the chip used by each event.
+    pub fn extension_split_threshold(&self, rows_per_event: usize) -> usize {
+        std::cmp::max(self.deferred_shift_threshold / rows_per_event, 1)
+    }
 }
 
 impl MachineRecord for ExecutionRecord {
@@ -153,11 +222,36 @@ impl MachineRecord for ExecutionRecord {
             "sha_compress_events".to_string(),
             self.sha_compress_events.len(),
         );
+        stats.insert(
+            "sha512_extend_events".to_string(),
+            self.sha512_extend_events.len(),
+        );
+        stats.insert(
+            "sha512_compress_events".to_string(),
+            self.sha512_compress_events.len(),
+        );
         stats.insert(
             "keccak_permute_events".to_string(),
             self.keccak_permute_events.len(),
         );
+        stats.insert(
+            "keccak256_hash_events".to_string(),
+            self.keccak256_hash_events.len(),
+        );
+        stats.insert(
+            "poseidon2_permute_events".to_string(),
+            self.poseidon2_permute_events.len(),
+        );
+        stats.insert(
+            "blake3_compress_events".to_string(),
+            self.blake3_compress_events.len(),
+        );
+        stats.insert(
+            "blake2b_compress_events".to_string(),
+            self.blake2b_compress_events.len(),
+        );
         stats.insert("ed_add_events".to_string(), self.ed_add_events.len());
+        stats.insert("ed_mul_events".to_string(), self.ed_mul_events.len());
         stats.insert(
             "ed_decompress_events".to_string(),
             self.ed_decompress_events.len(),
@@ -170,11 +264,28 @@ impl MachineRecord for ExecutionRecord {
             "secp256k1_double_events".to_string(),
             self.secp256k1_double_events.len(),
         );
+        stats.insert(
+            "secp256k1_mul_events".to_string(),
+            self.secp256k1_mul_events.len(),
+        );
+        stats.insert(
+            "secp256r1_add_events".to_string(),
+            self.secp256r1_add_events.len(),
+        );
+        stats.insert(
+            "secp256r1_double_events".to_string(),
+            self.secp256r1_double_events.len(),
+        );
+        stats.insert(
+            "secp256r1_decompress_events".to_string(),
+            self.secp256r1_decompress_events.len(),
+        );
         stats.insert("bn254_add_events".to_string(), self.bn254_add_events.len());
         stats.insert(
             "bn254_double_events".to_string(),
             self.bn254_double_events.len(),
         );
+        stats.insert("bn254_mul_events".to_string(), self.bn254_mul_events.len());
         stats.insert(
             "k256_decompress_events".to_string(),
             self.k256_decompress_events.len(),
@@ -187,14 +298,40 @@ impl MachineRecord for ExecutionRecord {
             "bls12381_double_events".to_string(),
             self.bls12381_double_events.len(),
         );
-        stats.insert(
-            "uint256_mul_events".to_string(),
-            self.uint256_mul_events.len(),
-        );
+        stats.insert("uint256_events".to_string(), self.uint256_events.len());
+        stats.insert("uint384_events".to_string(), self.uint384_events.len());
+        stats.insert("uint512_events".to_string(), self.uint512_events.len());
         stats.insert(
             "bls12381_decompress_events".to_string(),
             self.bls12381_decompress_events.len(),
         );
+        stats.insert("bn254_fp_events".to_string(), self.bn254_fp_events.len());
+        stats.insert(
+            "bn254_fp2_addsub_events".to_string(),
+            self.bn254_fp2_addsub_events.len(),
+        );
+        stats.insert(
+            "bn254_fp2_mul_events".to_string(),
+            self.bn254_fp2_mul_events.len(),
+        );
+        stats.insert(
+            "bls12381_fp_events".to_string(),
+            self.bls12381_fp_events.len(),
+        );
+        stats.insert(
+            "bls12381_fp2_addsub_events".to_string(),
+            self.bls12381_fp2_addsub_events.len(),
+        );
+        stats.insert(
+            "bls12381_fp2_mul_events".to_string(),
+            self.bls12381_fp2_mul_events.len(),
+        );
+        for (code, extension) in self.extension_events.iter() {
+            stats.insert(
+                format!("extension_{:#010x}_events", code),
+                extension.events.len(),
+            );
+        }
         stats.insert(
             "memory_initialize_events".to_string(),
             self.memory_initialize_events.len(),
@@ -229,28 +366,69 @@ impl MachineRecord for ExecutionRecord {
         self.sha_extend_events.append(&mut other.sha_extend_events);
         self.sha_compress_events
             .append(&mut other.sha_compress_events);
+        self.sha512_extend_events
+            .append(&mut other.sha512_extend_events);
+        self.sha512_compress_events
+            .append(&mut other.sha512_compress_events);
         self.keccak_permute_events
             .append(&mut other.keccak_permute_events);
+        self.keccak256_hash_events
+            .append(&mut other.keccak256_hash_events);
+        self.poseidon2_permute_events
+            .append(&mut other.poseidon2_permute_events);
+        self.blake3_compress_events
+            .append(&mut other.blake3_compress_events);
+        self.blake2b_compress_events
+            .append(&mut other.blake2b_compress_events);
         self.ed_add_events.append(&mut other.ed_add_events);
+        self.ed_mul_events.append(&mut other.ed_mul_events);
         self.ed_decompress_events
             .append(&mut other.ed_decompress_events);
         self.secp256k1_add_events
             .append(&mut other.secp256k1_add_events);
         self.secp256k1_double_events
             .append(&mut other.secp256k1_double_events);
+        self.secp256k1_mul_events
+            .append(&mut other.secp256k1_mul_events);
+        self.secp256r1_add_events
+            .append(&mut other.secp256r1_add_events);
+        self.secp256r1_double_events
+            .append(&mut other.secp256r1_double_events);
+        self.secp256r1_decompress_events
+            .append(&mut other.secp256r1_decompress_events);
         self.bn254_add_events.append(&mut other.bn254_add_events);
         self.bn254_double_events
             .append(&mut other.bn254_double_events);
+        self.bn254_mul_events.append(&mut other.bn254_mul_events);
         self.k256_decompress_events
             .append(&mut other.k256_decompress_events);
         self.bls12381_add_events
             .append(&mut other.bls12381_add_events);
         self.bls12381_double_events
             .append(&mut other.bls12381_double_events);
-        self.uint256_mul_events
-            .append(&mut other.uint256_mul_events);
+        self.uint256_events.append(&mut other.uint256_events);
+        self.uint384_events.append(&mut other.uint384_events);
+        self.uint512_events.append(&mut other.uint512_events);
         self.bls12381_decompress_events
             .append(&mut other.bls12381_decompress_events);
+        self.bn254_fp_events.append(&mut other.bn254_fp_events);
+        self.bn254_fp2_addsub_events
+            .append(&mut other.bn254_fp2_addsub_events);
+        self.bn254_fp2_mul_events
+            .append(&mut other.bn254_fp2_mul_events);
+        self.bls12381_fp_events
+            .append(&mut other.bls12381_fp_events);
+        self.bls12381_fp2_addsub_events
+            .append(&mut other.bls12381_fp2_addsub_events);
+        self.bls12381_fp2_mul_events
+            .append(&mut other.bls12381_fp2_mul_events);
+        for (code, mut extension) in std::mem::take(&mut other.extension_events) {
+            self.extension_events
+                .entry(code)
+                .or_insert_with(|| ExtensionEvents::new(extension.rows_per_event))
+                .events
+                .append(&mut extension.events);
+        }
 
         if self.byte_lookups.is_empty() {
             self.byte_lookups = std::mem::take(&mut other.byte_lookups);
@@ -364,6 +542,34 @@ impl ExecutionRecord {
         }
     }
 
+    /// Records an event of the registered precompile `P`.
+    pub fn add_extension_event<P: PrecompileExtension>(&mut self, event: &P::Event) {
+        self.extension_events
+            .entry(P::SYSCALL_CODE)
+            .or_insert_with(|| ExtensionEvents::new(P::ROWS_PER_EVENT))
+            .events
+            .push(bincode::serialize(event).expect("failed to serialize extension event"));
+    }
+
+    /// The events of the registered precompile `P`, in the order they were recorded.
+    pub fn extension_events<P: PrecompileExtension>(&self) -> Vec<P::Event> {
+        self.extension_events
+            .get(&P::SYSCALL_CODE)
+            .map_or(&[][..], |extension| &extension.events)
+            .iter()
+            .map(|event| {
+                bincode::deserialize(event).expect("failed to deserialize extension event")
+            })
+            .collect()
+    }
+
+    /// The number of events of the registered precompile `P`.
+    pub fn num_extension_events<P: PrecompileExtension>(&self) -> usize {
+        self.extension_events
+            .get(&P::SYSCALL_CODE)
+            .map_or(0, |extension| extension.events.len())
+    }
+
     /// Take out events from the [ExecutionRecord] that should be deferred to a separate shard.
     ///
     /// Note: we usually defer events that would increase the recursion cost significantly if
@@ -371,21 +577,42 @@ impl ExecutionRecord {
     pub fn defer(&mut self) -> ExecutionRecord {
         ExecutionRecord {
             keccak_permute_events: std::mem::take(&mut self.keccak_permute_events),
+            keccak256_hash_events: std::mem::take(&mut self.keccak256_hash_events),
+            poseidon2_permute_events: std::mem::take(&mut self.poseidon2_permute_events),
+            blake3_compress_events: std::mem::take(&mut self.blake3_compress_events),
+            blake2b_compress_events: std::mem::take(&mut self.blake2b_compress_events),
             secp256k1_add_events: std::mem::take(&mut self.secp256k1_add_events),
             secp256k1_double_events: std::mem::take(&mut self.secp256k1_double_events),
+            secp256k1_mul_events: std::mem::take(&mut self.secp256k1_mul_events),
+            secp256r1_add_events: std::mem::take(&mut self.secp256r1_add_events),
+            secp256r1_double_events: std::mem::take(&mut self.secp256r1_double_events),
+            secp256r1_decompress_events: std::mem::take(&mut self.secp256r1_decompress_events),
             bn254_add_events: std::mem::take(&mut self.bn254_add_events),
             bn254_double_events: std::mem::take(&mut self.bn254_double_events),
+            bn254_mul_events: std::mem::take(&mut self.bn254_mul_events),
             bls12381_add_events: std::mem::take(&mut self.bls12381_add_events),
             bls12381_double_events: std::mem::take(&mut self.bls12381_double_events),
             sha_extend_events: std::mem::take(&mut self.sha_extend_events),
             sha_compress_events: std::mem::take(&mut self.sha_compress_events),
+            sha512_extend_events: std::mem::take(&mut self.sha512_extend_events),
+            sha512_compress_events: std::mem::take(&mut self.sha512_compress_events),
             ed_add_events: std::mem::take(&mut self.ed_add_events),
+            ed_mul_events: std::mem::take(&mut self.ed_mul_events),
             ed_decompress_events: std::mem::take(&mut self.ed_decompress_events),
             k256_decompress_events: std::mem::take(&mut self.k256_decompress_events),
-            uint256_mul_events: std::mem::take(&mut self.uint256_mul_events),
+            uint256_events: std::mem::take(&mut self.uint256_events),
+            uint384_events: std::mem::take(&mut self.uint384_events),
+            uint512_events: std::mem::take(&mut self.uint512_events),
             bls12381_decompress_events: std::mem::take(&mut self.bls12381_decompress_events),
+            bn254_fp_events: std::mem::take(&mut self.bn254_fp_events),
+            bn254_fp2_addsub_events: std::mem::take(&mut self.bn254_fp2_addsub_events),
+            bn254_fp2_mul_events: std::mem::take(&mut self.bn254_fp2_mul_events),
+            bls12381_fp_events: std::mem::take(&mut self.bls12381_fp_events),
+            bls12381_fp2_addsub_events: std::mem::take(&mut self.bls12381_fp2_addsub_events),
+            bls12381_fp2_mul_events: std::mem::take(&mut self.bls12381_fp2_mul_events),
             memory_initialize_events: std::mem::take(&mut self.memory_initialize_events),
             memory_finalize_events: std::mem::take(&mut self.memory_finalize_events),
+            extension_events: std::mem::take(&mut self.extension_events),
             ..Default::default()
         }
     }
@@ -429,6 +656,56 @@ impl ExecutionRecord {
             opts.keccak_split_threshold,
             last
         );
+
+        // Keccak-256 hashes vary in length, so they are split greedily by their number of
+        // permutations. The final chunk may still grow, so it is kept unless this is the last split.
+        let events = std::mem::take(&mut self.keccak256_hash_events);
+        let mut chunk = Vec::new();
+        let mut chunk_blocks = 0;
+        for event in events {
+            let num_blocks = event.num_blocks();
+            if keccak256_hash_starts_shard(chunk_blocks, num_blocks, opts.keccak_split_threshold) {
+                shards.push(ExecutionRecord {
+                    keccak256_hash_events: std::mem::take(&mut chunk),
+                    program: self.program.clone(),
+                    ..Default::default()
+                });
+                chunk_blocks = 0;
+            }
+            chunk_blocks += num_blocks;
+            chunk.push(event);
+        }
+        if !last {
+            self.keccak256_hash_events = chunk;
+        } else if !chunk.is_empty() {
+            shards.push(ExecutionRecord {
+                keccak256_hash_events: chunk,
+                program: self.program.clone(),
+                ..Default::default()
+            });
+        }
+
+        split_events!(
+            self,
+            poseidon2_permute_events,
+            shards,
+            opts.deferred_shift_threshold,
+            last
+        );
+        split_events!(
+            self,
+            blake3_compress_events,
+            shards,
+            opts.blake3_compress_split_threshold,
+            last
+        );
+        split_events!(
+            self,
+            blake2b_compress_events,
+            shards,
+            opts.blake2b_compress_split_threshold,
+            last
+        );
         split_events!(
             self,
             secp256k1_add_events,
@@ -443,6 +720,34 @@ impl ExecutionRecord {
             opts.deferred_shift_threshold,
             last
         );
+        split_events!(
+            self,
+            secp256k1_mul_events,
+            shards,
+            opts.ec_mul_split_threshold,
+            last
+        );
+        split_events!(
+            self,
+            secp256r1_add_events,
+            shards,
+            opts.deferred_shift_threshold,
+            last
+        );
+        split_events!(
+            self,
+            secp256r1_double_events,
+            shards,
+            opts.deferred_shift_threshold,
+            last
+        );
+        split_events!(
+            self,
+            secp256r1_decompress_events,
+            shards,
+            opts.deferred_shift_threshold,
+            last
+        );
         split_events!(
             self,
             bn254_add_events,
@@ -457,6 +762,13 @@ impl ExecutionRecord {
             opts.deferred_shift_threshold,
             last
         );
+        split_events!(
+            self,
+            bn254_mul_events,
+            shards,
+            opts.ec_mul_split_threshold,
+            last
+        );
         split_events!(
             self,
             bls12381_add_events,
@@ -485,6 +797,20 @@ impl ExecutionRecord {
             opts.sha_compress_split_threshold,
             last
         );
+        split_events!(
+            self,
+            sha512_extend_events,
+            shards,
+            opts.sha512_extend_split_threshold,
+            last
+        );
+        split_events!(
+            self,
+            sha512_compress_events,
+            shards,
+            opts.sha512_compress_split_threshold,
+            last
+        );
         split_events!(
             self,
             ed_add_events,
@@ -492,6 +818,13 @@ impl ExecutionRecord {
             opts.deferred_shift_threshold,
             last
         );
+        split_events!(
+            self,
+            ed_mul_events,
+            shards,
+            opts.ec_mul_split_threshold,
+            last
+        );
         split_events!(
             self,
             ed_decompress_events,
@@ -508,7 +841,21 @@ impl ExecutionRecord {
         );
         split_events!(
             self,
-            uint256_mul_events,
+            uint256_events,
+            shards,
+            opts.deferred_shift_threshold,
+            last
+        );
+        split_events!(
+            self,
+            uint384_events,
+            shards,
+            opts.deferred_shift_threshold,
+            last
+        );
+        split_events!(
+            self,
+            uint512_events,
             shards,
             opts.deferred_shift_threshold,
             last
@@ -520,6 +867,81 @@ impl ExecutionRecord {
             opts.deferred_shift_threshold,
             last
         );
+        split_events!(
+            self,
+            bn254_fp_events,
+            shards,
+            opts.deferred_shift_threshold,
+            last
+        );
+        split_events!(
+            self,
+            bn254_fp2_addsub_events,
+            shards,
+            opts.deferred_shift_threshold,
+            last
+        );
+        split_events!(
+            self,
+            bn254_fp2_mul_events,
+            shards,
+            opts.deferred_shift_threshold,
+            last
+        );
+        split_events!(
+            self,
+            bls12381_fp_events,
+            shards,
+            opts.deferred_shift_threshold,
+            last
+        );
+        split_events!(
+            self,
+            bls12381_fp2_addsub_events,
+            shards,
+            opts.deferred_shift_threshold,
+            last
+        );
+        split_events!(
+            self,
+            bls12381_fp2_mul_events,
+            shards,
+            opts.deferred_shift_threshold,
+            last
+        );
+
+        // The events of each registered precompile are split like the events of the builtin
+        // precompiles, by the number of rows of its chip.
+        for (&code, extension) in self.extension_events.iter_mut() {
+            let rows_per_event = extension.rows_per_event;
+            let events = std::mem::take(&mut extension.events);
+            let chunks = events.chunks_exact(opts.extension_split_threshold(rows_per_event));
+            let remainder = chunks.remainder().to_vec();
+            let chunks = if !last {
+                extension.events = remainder;
+                chunks.map(<[_]>::to_vec).collect::<Vec<_>>()
+            } else {
+                (!remainder.is_empty())
+                    .then_some(remainder)
+                    .into_iter()
+                    .chain(chunks.map(<[_]>::to_vec))
+                    .collect()
+            };
+            shards.extend(chunks.into_iter().map(|events| ExecutionRecord {
+                extension_events: BTreeMap::from([(
+                    code,
+                    ExtensionEvents {
+                        rows_per_event,
+                        events,
+                    },
+                )]),
+                program: self.program.clone(),
+                ..Default::default()
+            }));
+        }
+        if last {
+            self.extension_events.clear();
+        }
         // _ = last_pct;
 
         if last {
@@ -4,12 +4,53 @@ use std::fmt::{Display, Formatter, Result as FmtResult};
 use std::hash::Hash;
 use std::ops::{Add, AddAssign};
 
+use serde::{Deserialize, Serialize};
+
 use super::*;
 
-#[derive(Default, Debug, Clone, PartialEq, Eq)]
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ExecutionReport {
     pub opcode_counts: HashMap<Opcode, u64>,
     pub syscall_counts: HashMap<SyscallCode, u64>,
+
+    /// The regions tracked with `cycle-tracker-start` and `cycle-tracker-end`, in the order they
+    /// were first exited.
+    pub cycle_tracker: Vec<CycleTrackerReport>,
+}
+
+/// The cycles spent in a region of the program tracked with `cycle-tracker-start` and
+/// `cycle-tracker-end`.
+///
+/// A region is reported separately for each region it is nested in.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CycleTrackerReport {
+    /// The name of the region.
+    pub name: String,
+
+    /// The name of the region it is nested in, if any.
+    pub parent: Option<String>,
+
+    /// The number of times the region was executed.
+    pub count: u64,
+
+    /// The cycles spent in all executions of the region.
+    pub total_cycles: u64,
+
+    /// The cycles spent in the shortest execution of the region.
+    pub min_cycles: u64,
+
+    /// The cycles spent in the longest execution of the region.
+    pub max_cycles: u64,
+}
+
+impl CycleTrackerReport {
+    /// Adds the executions of another report of the same region.
+    fn merge(&mut self, other: &Self) {
+        self.count += other.count;
+        self.total_cycles += other.total_cycles;
+        self.min_cycles = self.min_cycles.min(other.min_cycles);
+        self.max_cycles = self.max_cycles.max(other.max_cycles);
+    }
 }
 
 impl ExecutionReport {
@@ -23,6 +64,29 @@ impl ExecutionReport {
         self.syscall_counts.values().sum()
     }
 
+    /// Record an execution of a tracked region which took the given number of cycles.
+    pub fn track_cycles(&mut self, name: &str, parent: Option<&str>, cycles: u64) {
+        self.merge_cycle_tracker(&CycleTrackerReport {
+            name: name.to_string(),
+            parent: parent.map(str::to_string),
+            count: 1,
+            total_cycles: cycles,
+            min_cycles: cycles,
+            max_cycles: cycles,
+        });
+    }
+
+    fn merge_cycle_tracker(&mut self, region: &CycleTrackerReport) {
+        let existing = self
+            .cycle_tracker
+            .iter_mut()
+            .find(|r| r.name == region.name && r.parent == region.parent);
+        match existing {
+            Some(existing) => existing.merge(region),
+            None => self.cycle_tracker.push(region.clone()),
+        }
+    }
+
     /// Returns sorted and formatted rows of a table of counts (e.g. `opcode_counts`).
     ///
     /// The table is sorted first by count (descending) and then by label (ascending).
@@ -76,6 +140,9 @@ impl AddAssign for ExecutionReport {
     fn add_assign(&mut self, rhs: Self) {
         hashmap_add_assign(&mut self.opcode_counts, rhs.opcode_counts);
         hashmap_add_assign(&mut self.syscall_counts, rhs.syscall_counts);
+        for region in rhs.cycle_tracker.iter() {
+            self.merge_cycle_tracker(region);
+        }
     }
 }
 
@@ -108,6 +175,91 @@ impl Display for ExecutionReport {
             writeln!(f, "  {line}")?;
         }
 
+        if !self.cycle_tracker.is_empty() {
+            writeln!(f, "cycle tracker (total / count / min / max cycles):")?;
+            for region in self.cycle_tracker.iter() {
+                let name = match &region.parent {
+                    Some(parent) => format!("{} > {}", parent, region.name),
+                    None => region.name.clone(),
+                };
+                writeln!(
+                    f,
+                    "  {name}: {} / {} / {} / {}",
+                    region.total_cycles, region.count, region.min_cycles, region.max_cycles
+                )?;
+            }
+        }
+
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{CycleTrackerReport, ExecutionReport};
+    use crate::runtime::{Program, Runtime};
+    use crate::utils::tests::CYCLE_TRACKER_ELF;
+    use crate::utils::SP1CoreOpts;
+
+    #[test]
+    fn test_track_cycles() {
+        let mut report = ExecutionReport::default();
+        report.track_cycles("inner", Some("outer"), 10);
+        report.track_cycles("inner", Some("outer"), 30);
+        report.track_cycles("outer", None, 50);
+        report.track_cycles("inner", None, 5);
+
+        let mut other = ExecutionReport::default();
+        other.track_cycles("outer", None, 70);
+        report += other;
+
+        assert_eq!(
+            report.cycle_tracker,
+            vec![
+                CycleTrackerReport {
+                    name: "inner".to_string(),
+                    parent: Some("outer".to_string()),
+                    count: 2,
+                    total_cycles: 40,
+                    min_cycles: 10,
+                    max_cycles: 30,
+                },
+                CycleTrackerReport {
+                    name: "outer".to_string(),
+                    parent: None,
+                    count: 2,
+                    total_cycles: 120,
+                    min_cycles: 50,
+                    max_cycles: 70,
+                },
+                CycleTrackerReport {
+                    name: "inner".to_string(),
+                    parent: None,
+                    count: 1,
+                    total_cycles: 5,
+                    min_cycles: 5,
+                    max_cycles: 5,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cycle_tracker_report_json() {
+        let program = Program::from(CYCLE_TRACKER_ELF);
+        let mut runtime = Runtime::new(program, SP1CoreOpts::default());
+        runtime.run_untraced().unwrap();
+
+        let report = runtime.report;
+        let names = report
+            .cycle_tracker
+            .iter()
+            .map(|region| (region.name.as_str(), region.parent.as_deref(), region.count))
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec![("f", None, 1), ("g", None, 1)]);
+
+        let json = serde_json::to_string(&report).unwrap();
+        let decoded: ExecutionReport = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, report);
+    }
+}
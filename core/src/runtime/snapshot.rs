@@ -0,0 +1,36 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::{ExecutionReport, ExecutionState};
+
+/// The point at which [Runtime::run_until](super::Runtime::run_until) pauses execution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PauseCondition {
+    /// Pause once the given total number of cycles has been executed.
+    Cycle(u64),
+
+    /// Pause the next time the program counter reaches the given address, before the instruction
+    /// at that address is executed.
+    Pc(u32),
+}
+
+/// A snapshot of a paused execution.
+///
+/// A snapshot can be serialized, resumed later with
+/// [Runtime::from_snapshot](super::Runtime::from_snapshot), possibly with different hooks, or
+/// cloned to run several continuations from the same point.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutionSnapshot {
+    /// The state of the execution when it was paused.
+    pub state: ExecutionState,
+
+    /// The report of the cycles executed before the pause.
+    pub report: ExecutionReport,
+
+    /// The cycle tracker regions open when the execution was paused.
+    pub cycle_tracker: Vec<(String, u64)>,
+
+    /// The stdout and stderr output not yet flushed when the execution was paused.
+    pub io_buf: HashMap<u32, String>,
+}
@@ -75,6 +75,12 @@ pub struct ExecutionState {
 
     /// Keeps track of how many times a certain syscall has been called.
     pub syscall_counts: HashMap<SyscallCode, u64>,
+
+    /// Keeps track of how many times each registered precompile has been called.
+    pub extension_syscall_counts: HashMap<u32, u64>,
+
+    /// The number of permutations of the Keccak-256 hashes in the current deferred shard.
+    pub keccak256_hash_shard_blocks: usize,
 }
 
 impl ExecutionState {
@@ -95,6 +101,8 @@ impl ExecutionState {
             proof_stream: Vec::new(),
             proof_stream_ptr: 0,
             syscall_counts: HashMap::new(),
+            extension_syscall_counts: HashMap::new(),
+            keccak256_hash_shard_blocks: 0,
         }
     }
 }
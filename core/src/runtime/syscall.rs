@@ -5,22 +5,36 @@ use std::sync::Arc;
 use serde::{Deserialize, Serialize};
 use strum_macros::EnumIter;
 
+use crate::operations::field::field_op::FieldOperation;
 use crate::runtime::{Register, Runtime};
+use crate::syscall::precompiles::blake::{Blake2bCompressChip, Blake3CompressChip};
 use crate::syscall::precompiles::edwards::EdAddAssignChip;
 use crate::syscall::precompiles::edwards::EdDecompressChip;
-use crate::syscall::precompiles::keccak256::KeccakPermuteChip;
+use crate::syscall::precompiles::edwards::EdMulAssignChip;
+use crate::syscall::precompiles::fptower::{Fp2AddSubSyscall, Fp2MulAssignChip, FpOpSyscall};
+use crate::syscall::precompiles::keccak256::{Keccak256HashChip, KeccakPermuteChip};
+use crate::syscall::precompiles::poseidon2::Poseidon2PermuteChip;
 use crate::syscall::precompiles::sha256::{ShaCompressChip, ShaExtendChip};
-use crate::syscall::precompiles::uint256::Uint256MulChip;
+use crate::syscall::precompiles::sha512::{Sha512CompressChip, Sha512ExtendChip};
+use crate::syscall::precompiles::uint::UintOpSyscall;
 use crate::syscall::precompiles::weierstrass::WeierstrassAddAssignChip;
 use crate::syscall::precompiles::weierstrass::WeierstrassDecompressChip;
 use crate::syscall::precompiles::weierstrass::WeierstrassDoubleAssignChip;
+use crate::syscall::precompiles::weierstrass::WeierstrassMulAssignChip;
 use crate::syscall::{
     SyscallCommit, SyscallCommitDeferred, SyscallEnterUnconstrained, SyscallExitUnconstrained,
     SyscallHalt, SyscallHintLen, SyscallHintRead, SyscallVerifySP1Proof, SyscallWrite,
 };
 use crate::utils::ec::edwards::ed25519::{Ed25519, Ed25519Parameters};
-use crate::utils::ec::weierstrass::bls12_381::Bls12381;
-use crate::utils::ec::weierstrass::{bn254::Bn254, secp256k1::Secp256k1};
+use crate::utils::ec::uint256::U256Field;
+use crate::utils::ec::uint384::U384Field;
+use crate::utils::ec::uint512::U512Field;
+use crate::utils::ec::weierstrass::bls12_381::{Bls12381, Bls12381BaseField};
+use crate::utils::ec::weierstrass::{
+    bn254::{Bn254, Bn254BaseField},
+    secp256k1::Secp256k1,
+    secp256r1::Secp256r1,
+};
 use crate::{runtime::ExecutionRecord, runtime::MemoryReadRecord, runtime::MemoryWriteRecord};
 
 /// A system call is invoked by the the `ecall` instruction with a specific value in register t0.
@@ -102,12 +116,113 @@ pub enum SyscallCode {
 
     /// Executes the `BLS12381_DOUBLE` precompile.
     BLS12381_DOUBLE = 0x00_00_01_1F,
+
+    /// Executes the `SECP256R1_ADD` precompile.
+    SECP256R1_ADD = 0x00_01_01_2C,
+
+    /// Executes the `SECP256R1_DOUBLE` precompile.
+    SECP256R1_DOUBLE = 0x00_00_01_2D,
+
+    /// Executes the `SECP256R1_DECOMPRESS` precompile.
+    SECP256R1_DECOMPRESS = 0x00_00_01_2E,
+
+    /// Executes the `BN254_FP_ADD` precompile.
+    BN254_FP_ADD = 0x00_01_01_26,
+
+    /// Executes the `BN254_FP_SUB` precompile.
+    BN254_FP_SUB = 0x00_01_01_27,
+
+    /// Executes the `BN254_FP_MUL` precompile.
+    BN254_FP_MUL = 0x00_01_01_28,
+
+    /// Executes the `BN254_FP2_ADD` precompile.
+    BN254_FP2_ADD = 0x00_01_01_29,
+
+    /// Executes the `BN254_FP2_SUB` precompile.
+    BN254_FP2_SUB = 0x00_01_01_2A,
+
+    /// Executes the `BN254_FP2_MUL` precompile.
+    BN254_FP2_MUL = 0x00_01_01_2B,
+
+    /// Executes the `SHA512_EXTEND` precompile.
+    SHA512_EXTEND = 0x00_40_01_2F,
+
+    /// Executes the `SHA512_COMPRESS` precompile.
+    SHA512_COMPRESS = 0x00_01_01_30,
+
+    /// Executes the `UINT256_ADD` precompile.
+    UINT256_ADD = 0x00_01_01_31,
+
+    /// Executes the `UINT256_SUB` precompile.
+    UINT256_SUB = 0x00_01_01_33,
+
+    /// Executes the `UINT384_ADD` precompile.
+    UINT384_ADD = 0x00_01_01_34,
+
+    /// Executes the `UINT384_SUB` precompile.
+    UINT384_SUB = 0x00_01_01_35,
+
+    /// Executes the `UINT384_MUL` precompile.
+    UINT384_MUL = 0x00_01_01_36,
+
+    /// Executes the `UINT512_ADD` precompile.
+    UINT512_ADD = 0x00_01_01_37,
+
+    /// Executes the `UINT512_SUB` precompile.
+    UINT512_SUB = 0x00_01_01_38,
+
+    /// Executes the `UINT512_MUL` precompile.
+    UINT512_MUL = 0x00_01_01_39,
+
+    /// Executes the `KECCAK256_HASH` precompile.
+    KECCAK256_HASH = 0x00_01_01_3A,
+
+    /// Executes the `POSEIDON2_PERMUTE` precompile.
+    POSEIDON2_PERMUTE = 0x00_01_01_3B,
+
+    /// Executes the `ED_MUL` precompile.
+    ED_MUL = 0x00_01_01_3C,
+
+    /// Executes the `SECP256K1_MUL` precompile.
+    SECP256K1_MUL = 0x00_01_01_3D,
+
+    /// Executes the `BN254_MUL` precompile.
+    BN254_MUL = 0x00_01_01_3E,
+
+    /// Executes the `BLAKE3_COMPRESS` precompile.
+    BLAKE3_COMPRESS = 0x00_01_01_3F,
+
+    /// Executes the `BLAKE2B_COMPRESS` precompile.
+    BLAKE2B_COMPRESS = 0x00_01_01_40,
+
+    /// Executes the `BLS12381_FP_ADD` precompile.
+    BLS12381_FP_ADD = 0x00_01_01_71,
+
+    /// Executes the `BLS12381_FP_SUB` precompile.
+    BLS12381_FP_SUB = 0x00_01_01_72,
+
+    /// Executes the `BLS12381_FP_MUL` precompile.
+    BLS12381_FP_MUL = 0x00_01_01_73,
+
+    /// Executes the `BLS12381_FP2_ADD` precompile.
+    BLS12381_FP2_ADD = 0x00_01_01_77,
+
+    /// Executes the `BLS12381_FP2_SUB` precompile.
+    BLS12381_FP2_SUB = 0x00_01_01_78,
+
+    /// Executes the `BLS12381_FP2_MUL` precompile.
+    BLS12381_FP2_MUL = 0x00_01_01_79,
 }
 
 impl SyscallCode {
     /// Create a syscall from a u32.
     pub fn from_u32(value: u32) -> Self {
-        match value {
+        Self::try_from_u32(value).unwrap_or_else(|| panic!("invalid syscall number: {}", value))
+    }
+
+    /// Create a syscall from a u32, returning `None` if it is not a builtin syscall.
+    pub fn try_from_u32(value: u32) -> Option<Self> {
+        let syscall = match value {
             0x00_00_00_00 => SyscallCode::HALT,
             0x00_00_00_02 => SyscallCode::WRITE,
             0x00_00_00_03 => SyscallCode::ENTER_UNCONSTRAINED,
@@ -131,8 +246,41 @@ impl SyscallCode {
             0x00_00_00_F1 => SyscallCode::HINT_READ,
             0x00_01_01_1D => SyscallCode::UINT256_MUL,
             0x00_00_01_1C => SyscallCode::BLS12381_DECOMPRESS,
-            _ => panic!("invalid syscall number: {}", value),
-        }
+            0x00_01_01_2C => SyscallCode::SECP256R1_ADD,
+            0x00_00_01_2D => SyscallCode::SECP256R1_DOUBLE,
+            0x00_00_01_2E => SyscallCode::SECP256R1_DECOMPRESS,
+            0x00_01_01_26 => SyscallCode::BN254_FP_ADD,
+            0x00_01_01_27 => SyscallCode::BN254_FP_SUB,
+            0x00_01_01_28 => SyscallCode::BN254_FP_MUL,
+            0x00_01_01_29 => SyscallCode::BN254_FP2_ADD,
+            0x00_01_01_2A => SyscallCode::BN254_FP2_SUB,
+            0x00_01_01_2B => SyscallCode::BN254_FP2_MUL,
+            0x00_40_01_2F => SyscallCode::SHA512_EXTEND,
+            0x00_01_01_30 => SyscallCode::SHA512_COMPRESS,
+            0x00_01_01_31 => SyscallCode::UINT256_ADD,
+            0x00_01_01_33 => SyscallCode::UINT256_SUB,
+            0x00_01_01_34 => SyscallCode::UINT384_ADD,
+            0x00_01_01_35 => SyscallCode::UINT384_SUB,
+            0x00_01_01_36 => SyscallCode::UINT384_MUL,
+            0x00_01_01_37 => SyscallCode::UINT512_ADD,
+            0x00_01_01_38 => SyscallCode::UINT512_SUB,
+            0x00_01_01_39 => SyscallCode::UINT512_MUL,
+            0x00_01_01_3A => SyscallCode::KECCAK256_HASH,
+            0x00_01_01_3B => SyscallCode::POSEIDON2_PERMUTE,
+            0x00_01_01_3C => SyscallCode::ED_MUL,
+            0x00_01_01_3D => SyscallCode::SECP256K1_MUL,
+            0x00_01_01_3E => SyscallCode::BN254_MUL,
+            0x00_01_01_3F => SyscallCode::BLAKE3_COMPRESS,
+            0x00_01_01_40 => SyscallCode::BLAKE2B_COMPRESS,
+            0x00_01_01_71 => SyscallCode::BLS12381_FP_ADD,
+            0x00_01_01_72 => SyscallCode::BLS12381_FP_SUB,
+            0x00_01_01_73 => SyscallCode::BLS12381_FP_MUL,
+            0x00_01_01_77 => SyscallCode::BLS12381_FP2_ADD,
+            0x00_01_01_78 => SyscallCode::BLS12381_FP2_SUB,
+            0x00_01_01_79 => SyscallCode::BLS12381_FP2_MUL,
+            _ => return None,
+        };
+        Some(syscall)
     }
 
     pub fn syscall_id(&self) -> u32 {
@@ -146,6 +294,25 @@ impl SyscallCode {
     pub fn num_cycles(&self) -> u32 {
         (*self as u32).to_le_bytes()[2].into()
     }
+
+    /// Maps a syscall to the syscall whose count determines its nonce.
+    ///
+    /// Syscalls proven by the same chip must share a count, since the nonce of an event is its
+    /// row in that chip's trace.
+    pub fn count_map(&self) -> Self {
+        match self {
+            SyscallCode::BN254_FP_SUB | SyscallCode::BN254_FP_MUL => SyscallCode::BN254_FP_ADD,
+            SyscallCode::BN254_FP2_SUB => SyscallCode::BN254_FP2_ADD,
+            SyscallCode::BLS12381_FP_SUB | SyscallCode::BLS12381_FP_MUL => {
+                SyscallCode::BLS12381_FP_ADD
+            }
+            SyscallCode::BLS12381_FP2_SUB => SyscallCode::BLS12381_FP2_ADD,
+            SyscallCode::UINT256_ADD | SyscallCode::UINT256_SUB => SyscallCode::UINT256_MUL,
+            SyscallCode::UINT384_ADD | SyscallCode::UINT384_SUB => SyscallCode::UINT384_MUL,
+            SyscallCode::UINT512_ADD | SyscallCode::UINT512_SUB => SyscallCode::UINT512_MUL,
+            _ => *self,
+        }
+    }
 }
 
 impl fmt::Display for SyscallCode {
@@ -179,6 +346,8 @@ pub struct SyscallContext<'a, 'b: 'a> {
     pub(crate) exit_code: u32,
     pub(crate) rt: &'a mut Runtime<'b>,
     pub syscall_lookup_id: u128,
+    /// The reason to abort the execution with once the syscall returns.
+    pub(crate) error: Option<String>,
 }
 
 impl<'a, 'b> SyscallContext<'a, 'b> {
@@ -192,6 +361,7 @@ impl<'a, 'b> SyscallContext<'a, 'b> {
             exit_code: 0,
             rt: runtime,
             syscall_lookup_id: 0,
+            error: None,
         }
     }
 
@@ -265,6 +435,14 @@ impl<'a, 'b> SyscallContext<'a, 'b> {
     pub fn set_exit_code(&mut self, exit_code: u32) {
         self.exit_code = exit_code;
     }
+
+    /// Abort the execution with [ExecutionError::InvalidSyscallInput] once the syscall returns,
+    /// instead of panicking on inputs that the guest controls.
+    ///
+    /// [ExecutionError::InvalidSyscallInput]: crate::runtime::ExecutionError::InvalidSyscallInput
+    pub fn fail(&mut self, reason: impl Into<String>) {
+        self.error = Some(reason.into());
+    }
 }
 
 pub fn default_syscall_map() -> HashMap<SyscallCode, Arc<dyn Syscall>> {
@@ -272,10 +450,22 @@ pub fn default_syscall_map() -> HashMap<SyscallCode, Arc<dyn Syscall>> {
     syscall_map.insert(SyscallCode::HALT, Arc::new(SyscallHalt {}));
     syscall_map.insert(SyscallCode::SHA_EXTEND, Arc::new(ShaExtendChip::new()));
     syscall_map.insert(SyscallCode::SHA_COMPRESS, Arc::new(ShaCompressChip::new()));
+    syscall_map.insert(
+        SyscallCode::SHA512_EXTEND,
+        Arc::new(Sha512ExtendChip::new()),
+    );
+    syscall_map.insert(
+        SyscallCode::SHA512_COMPRESS,
+        Arc::new(Sha512CompressChip::new()),
+    );
     syscall_map.insert(
         SyscallCode::ED_ADD,
         Arc::new(EdAddAssignChip::<Ed25519>::new()),
     );
+    syscall_map.insert(
+        SyscallCode::ED_MUL,
+        Arc::new(EdMulAssignChip::<Ed25519>::new()),
+    );
     syscall_map.insert(
         SyscallCode::ED_DECOMPRESS,
         Arc::new(EdDecompressChip::<Ed25519Parameters>::new()),
@@ -284,6 +474,22 @@ pub fn default_syscall_map() -> HashMap<SyscallCode, Arc<dyn Syscall>> {
         SyscallCode::KECCAK_PERMUTE,
         Arc::new(KeccakPermuteChip::new()),
     );
+    syscall_map.insert(
+        SyscallCode::KECCAK256_HASH,
+        Arc::new(Keccak256HashChip::new()),
+    );
+    syscall_map.insert(
+        SyscallCode::POSEIDON2_PERMUTE,
+        Arc::new(Poseidon2PermuteChip::new()),
+    );
+    syscall_map.insert(
+        SyscallCode::BLAKE3_COMPRESS,
+        Arc::new(Blake3CompressChip::new()),
+    );
+    syscall_map.insert(
+        SyscallCode::BLAKE2B_COMPRESS,
+        Arc::new(Blake2bCompressChip::new()),
+    );
     syscall_map.insert(
         SyscallCode::SECP256K1_ADD,
         Arc::new(WeierstrassAddAssignChip::<Secp256k1>::new()),
@@ -292,10 +498,26 @@ pub fn default_syscall_map() -> HashMap<SyscallCode, Arc<dyn Syscall>> {
         SyscallCode::SECP256K1_DOUBLE,
         Arc::new(WeierstrassDoubleAssignChip::<Secp256k1>::new()),
     );
+    syscall_map.insert(
+        SyscallCode::SECP256K1_MUL,
+        Arc::new(WeierstrassMulAssignChip::<Secp256k1>::new()),
+    );
     syscall_map.insert(
         SyscallCode::SECP256K1_DECOMPRESS,
         Arc::new(WeierstrassDecompressChip::<Secp256k1>::with_lsb_rule()),
     );
+    syscall_map.insert(
+        SyscallCode::SECP256R1_ADD,
+        Arc::new(WeierstrassAddAssignChip::<Secp256r1>::new()),
+    );
+    syscall_map.insert(
+        SyscallCode::SECP256R1_DOUBLE,
+        Arc::new(WeierstrassDoubleAssignChip::<Secp256r1>::new()),
+    );
+    syscall_map.insert(
+        SyscallCode::SECP256R1_DECOMPRESS,
+        Arc::new(WeierstrassDecompressChip::<Secp256r1>::with_lsb_rule()),
+    );
     syscall_map.insert(
         SyscallCode::BN254_ADD,
         Arc::new(WeierstrassAddAssignChip::<Bn254>::new()),
@@ -304,6 +526,10 @@ pub fn default_syscall_map() -> HashMap<SyscallCode, Arc<dyn Syscall>> {
         SyscallCode::BN254_DOUBLE,
         Arc::new(WeierstrassDoubleAssignChip::<Bn254>::new()),
     );
+    syscall_map.insert(
+        SyscallCode::BN254_MUL,
+        Arc::new(WeierstrassMulAssignChip::<Bn254>::new()),
+    );
     syscall_map.insert(
         SyscallCode::BLS12381_ADD,
         Arc::new(WeierstrassAddAssignChip::<Bls12381>::new()),
@@ -312,7 +538,42 @@ pub fn default_syscall_map() -> HashMap<SyscallCode, Arc<dyn Syscall>> {
         SyscallCode::BLS12381_DOUBLE,
         Arc::new(WeierstrassDoubleAssignChip::<Bls12381>::new()),
     );
-    syscall_map.insert(SyscallCode::UINT256_MUL, Arc::new(Uint256MulChip::new()));
+    syscall_map.insert(
+        SyscallCode::UINT256_ADD,
+        Arc::new(UintOpSyscall::<U256Field>::new(FieldOperation::Add)),
+    );
+    syscall_map.insert(
+        SyscallCode::UINT256_SUB,
+        Arc::new(UintOpSyscall::<U256Field>::new(FieldOperation::Sub)),
+    );
+    syscall_map.insert(
+        SyscallCode::UINT256_MUL,
+        Arc::new(UintOpSyscall::<U256Field>::new(FieldOperation::Mul)),
+    );
+    syscall_map.insert(
+        SyscallCode::UINT384_ADD,
+        Arc::new(UintOpSyscall::<U384Field>::new(FieldOperation::Add)),
+    );
+    syscall_map.insert(
+        SyscallCode::UINT384_SUB,
+        Arc::new(UintOpSyscall::<U384Field>::new(FieldOperation::Sub)),
+    );
+    syscall_map.insert(
+        SyscallCode::UINT384_MUL,
+        Arc::new(UintOpSyscall::<U384Field>::new(FieldOperation::Mul)),
+    );
+    syscall_map.insert(
+        SyscallCode::UINT512_ADD,
+        Arc::new(UintOpSyscall::<U512Field>::new(FieldOperation::Add)),
+    );
+    syscall_map.insert(
+        SyscallCode::UINT512_SUB,
+        Arc::new(UintOpSyscall::<U512Field>::new(FieldOperation::Sub)),
+    );
+    syscall_map.insert(
+        SyscallCode::UINT512_MUL,
+        Arc::new(UintOpSyscall::<U512Field>::new(FieldOperation::Mul)),
+    );
     syscall_map.insert(
         SyscallCode::ENTER_UNCONSTRAINED,
         Arc::new(SyscallEnterUnconstrained::new()),
@@ -337,7 +598,58 @@ pub fn default_syscall_map() -> HashMap<SyscallCode, Arc<dyn Syscall>> {
         SyscallCode::BLS12381_DECOMPRESS,
         Arc::new(WeierstrassDecompressChip::<Bls12381>::with_lexicographic_rule()),
     );
-    syscall_map.insert(SyscallCode::UINT256_MUL, Arc::new(Uint256MulChip::new()));
+    syscall_map.insert(
+        SyscallCode::BN254_FP_ADD,
+        Arc::new(FpOpSyscall::<Bn254BaseField>::new(FieldOperation::Add)),
+    );
+    syscall_map.insert(
+        SyscallCode::BN254_FP_SUB,
+        Arc::new(FpOpSyscall::<Bn254BaseField>::new(FieldOperation::Sub)),
+    );
+    syscall_map.insert(
+        SyscallCode::BN254_FP_MUL,
+        Arc::new(FpOpSyscall::<Bn254BaseField>::new(FieldOperation::Mul)),
+    );
+    syscall_map.insert(
+        SyscallCode::BN254_FP2_ADD,
+        Arc::new(Fp2AddSubSyscall::<Bn254BaseField>::new(FieldOperation::Add)),
+    );
+    syscall_map.insert(
+        SyscallCode::BN254_FP2_SUB,
+        Arc::new(Fp2AddSubSyscall::<Bn254BaseField>::new(FieldOperation::Sub)),
+    );
+    syscall_map.insert(
+        SyscallCode::BN254_FP2_MUL,
+        Arc::new(Fp2MulAssignChip::<Bn254BaseField>::new()),
+    );
+    syscall_map.insert(
+        SyscallCode::BLS12381_FP_ADD,
+        Arc::new(FpOpSyscall::<Bls12381BaseField>::new(FieldOperation::Add)),
+    );
+    syscall_map.insert(
+        SyscallCode::BLS12381_FP_SUB,
+        Arc::new(FpOpSyscall::<Bls12381BaseField>::new(FieldOperation::Sub)),
+    );
+    syscall_map.insert(
+        SyscallCode::BLS12381_FP_MUL,
+        Arc::new(FpOpSyscall::<Bls12381BaseField>::new(FieldOperation::Mul)),
+    );
+    syscall_map.insert(
+        SyscallCode::BLS12381_FP2_ADD,
+        Arc::new(Fp2AddSubSyscall::<Bls12381BaseField>::new(
+            FieldOperation::Add,
+        )),
+    );
+    syscall_map.insert(
+        SyscallCode::BLS12381_FP2_SUB,
+        Arc::new(Fp2AddSubSyscall::<Bls12381BaseField>::new(
+            FieldOperation::Sub,
+        )),
+    );
+    syscall_map.insert(
+        SyscallCode::BLS12381_FP2_MUL,
+        Arc::new(Fp2MulAssignChip::<Bls12381BaseField>::new()),
+    );
 
     syscall_map
 }
@@ -387,6 +699,36 @@ mod tests {
                 SyscallCode::SHA_COMPRESS => {
                     assert_eq!(code as u32, sp1_zkvm::syscalls::SHA_COMPRESS)
                 }
+                SyscallCode::SHA512_EXTEND => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::SHA512_EXTEND)
+                }
+                SyscallCode::SHA512_COMPRESS => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::SHA512_COMPRESS)
+                }
+                SyscallCode::UINT256_ADD => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::UINT256_ADD)
+                }
+                SyscallCode::UINT256_SUB => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::UINT256_SUB)
+                }
+                SyscallCode::UINT384_ADD => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::UINT384_ADD)
+                }
+                SyscallCode::UINT384_SUB => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::UINT384_SUB)
+                }
+                SyscallCode::UINT384_MUL => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::UINT384_MUL)
+                }
+                SyscallCode::UINT512_ADD => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::UINT512_ADD)
+                }
+                SyscallCode::UINT512_SUB => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::UINT512_SUB)
+                }
+                SyscallCode::UINT512_MUL => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::UINT512_MUL)
+                }
                 SyscallCode::ED_ADD => assert_eq!(code as u32, sp1_zkvm::syscalls::ED_ADD),
                 SyscallCode::ED_DECOMPRESS => {
                     assert_eq!(code as u32, sp1_zkvm::syscalls::ED_DECOMPRESS)
@@ -394,6 +736,25 @@ mod tests {
                 SyscallCode::KECCAK_PERMUTE => {
                     assert_eq!(code as u32, sp1_zkvm::syscalls::KECCAK_PERMUTE)
                 }
+                SyscallCode::KECCAK256_HASH => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::KECCAK256_HASH)
+                }
+                SyscallCode::POSEIDON2_PERMUTE => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::POSEIDON2_PERMUTE)
+                }
+                SyscallCode::ED_MUL => assert_eq!(code as u32, sp1_zkvm::syscalls::ED_MUL),
+                SyscallCode::SECP256K1_MUL => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::SECP256K1_MUL)
+                }
+                SyscallCode::BN254_MUL => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::BN254_MUL)
+                }
+                SyscallCode::BLAKE3_COMPRESS => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::BLAKE3_COMPRESS)
+                }
+                SyscallCode::BLAKE2B_COMPRESS => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::BLAKE2B_COMPRESS)
+                }
                 SyscallCode::SECP256K1_ADD => {
                     assert_eq!(code as u32, sp1_zkvm::syscalls::SECP256K1_ADD)
                 }
@@ -409,6 +770,51 @@ mod tests {
                 SyscallCode::SECP256K1_DECOMPRESS => {
                     assert_eq!(code as u32, sp1_zkvm::syscalls::SECP256K1_DECOMPRESS)
                 }
+                SyscallCode::SECP256R1_ADD => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::SECP256R1_ADD)
+                }
+                SyscallCode::SECP256R1_DOUBLE => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::SECP256R1_DOUBLE)
+                }
+                SyscallCode::SECP256R1_DECOMPRESS => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::SECP256R1_DECOMPRESS)
+                }
+                SyscallCode::BN254_FP_ADD => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::BN254_FP_ADD)
+                }
+                SyscallCode::BN254_FP_SUB => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::BN254_FP_SUB)
+                }
+                SyscallCode::BN254_FP_MUL => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::BN254_FP_MUL)
+                }
+                SyscallCode::BN254_FP2_ADD => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::BN254_FP2_ADD)
+                }
+                SyscallCode::BN254_FP2_SUB => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::BN254_FP2_SUB)
+                }
+                SyscallCode::BN254_FP2_MUL => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::BN254_FP2_MUL)
+                }
+                SyscallCode::BLS12381_FP_ADD => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::BLS12381_FP_ADD)
+                }
+                SyscallCode::BLS12381_FP_SUB => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::BLS12381_FP_SUB)
+                }
+                SyscallCode::BLS12381_FP_MUL => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::BLS12381_FP_MUL)
+                }
+                SyscallCode::BLS12381_FP2_ADD => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::BLS12381_FP2_ADD)
+                }
+                SyscallCode::BLS12381_FP2_SUB => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::BLS12381_FP2_SUB)
+                }
+                SyscallCode::BLS12381_FP2_MUL => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::BLS12381_FP2_MUL)
+                }
                 SyscallCode::BN254_ADD => assert_eq!(code as u32, sp1_zkvm::syscalls::BN254_ADD),
                 SyscallCode::BN254_DOUBLE => {
                     assert_eq!(code as u32, sp1_zkvm::syscalls::BN254_DOUBLE)
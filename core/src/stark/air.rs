@@ -1,10 +1,15 @@
 use super::StarkMachine;
 pub use crate::air::SP1AirBuilder;
 use crate::air::{MachineAir, SP1_PROOF_NUM_PV_ELTS};
+use crate::lookup::InteractionBuilder;
 use crate::memory::{MemoryChipType, MemoryProgramChip};
+use crate::runtime::{ExecutionRecord, Program};
 use crate::stark::Chip;
 use crate::StarkGenericConfig;
+use itertools::Itertools;
+use p3_air::Air;
 use p3_field::PrimeField32;
+use p3_uni_stark::SymbolicAirBuilder;
 pub use riscv_chips::*;
 use tracing::instrument;
 
@@ -21,20 +26,35 @@ pub(crate) mod riscv_chips {
     pub use crate::cpu::CpuChip;
     pub use crate::memory::MemoryChip;
     pub use crate::program::ProgramChip;
+    pub use crate::syscall::precompiles::blake::Blake2bCompressChip;
+    pub use crate::syscall::precompiles::blake::Blake3CompressChip;
     pub use crate::syscall::precompiles::edwards::EdAddAssignChip;
     pub use crate::syscall::precompiles::edwards::EdDecompressChip;
+    pub use crate::syscall::precompiles::edwards::EdMulAssignChip;
+    pub use crate::syscall::precompiles::fptower::Fp2AddSubAssignChip;
+    pub use crate::syscall::precompiles::fptower::Fp2MulAssignChip;
+    pub use crate::syscall::precompiles::fptower::FpOpChip;
+    pub use crate::syscall::precompiles::keccak256::Keccak256HashChip;
     pub use crate::syscall::precompiles::keccak256::KeccakPermuteChip;
+    pub use crate::syscall::precompiles::poseidon2::Poseidon2PermuteChip;
     pub use crate::syscall::precompiles::sha256::ShaCompressChip;
     pub use crate::syscall::precompiles::sha256::ShaExtendChip;
-    pub use crate::syscall::precompiles::uint256::Uint256MulChip;
+    pub use crate::syscall::precompiles::sha512::Sha512CompressChip;
+    pub use crate::syscall::precompiles::sha512::Sha512ExtendChip;
+    pub use crate::syscall::precompiles::uint::UintOpChip;
     pub use crate::syscall::precompiles::weierstrass::WeierstrassAddAssignChip;
     pub use crate::syscall::precompiles::weierstrass::WeierstrassDecompressChip;
     pub use crate::syscall::precompiles::weierstrass::WeierstrassDoubleAssignChip;
+    pub use crate::syscall::precompiles::weierstrass::WeierstrassMulAssignChip;
     pub use crate::utils::ec::edwards::ed25519::Ed25519Parameters;
     pub use crate::utils::ec::edwards::EdwardsCurve;
-    pub use crate::utils::ec::weierstrass::bls12_381::Bls12381Parameters;
-    pub use crate::utils::ec::weierstrass::bn254::Bn254Parameters;
+    pub use crate::utils::ec::uint256::U256Field;
+    pub use crate::utils::ec::uint384::U384Field;
+    pub use crate::utils::ec::uint512::U512Field;
+    pub use crate::utils::ec::weierstrass::bls12_381::{Bls12381BaseField, Bls12381Parameters};
+    pub use crate::utils::ec::weierstrass::bn254::{Bn254BaseField, Bn254Parameters};
     pub use crate::utils::ec::weierstrass::secp256k1::Secp256k1Parameters;
+    pub use crate::utils::ec::weierstrass::secp256r1::Secp256r1Parameters;
     pub use crate::utils::ec::weierstrass::SwCurve;
 }
 
@@ -77,6 +97,8 @@ pub enum RiscvAir<F: PrimeField32> {
     Sha256Compress(ShaCompressChip),
     /// A precompile for addition on the Elliptic curve ed25519.
     Ed25519Add(EdAddAssignChip<EdwardsCurve<Ed25519Parameters>>),
+    /// A precompile for scalar multiplication on the Elliptic curve ed25519.
+    Ed25519Mul(EdMulAssignChip<EdwardsCurve<Ed25519Parameters>>),
     /// A precompile for decompressing a point on the Edwards curve ed25519.
     Ed25519Decompress(EdDecompressChip<Ed25519Parameters>),
     /// A precompile for decompressing a point on the K256 curve.
@@ -85,20 +107,58 @@ pub enum RiscvAir<F: PrimeField32> {
     Secp256k1Add(WeierstrassAddAssignChip<SwCurve<Secp256k1Parameters>>),
     /// A precompile for doubling a point on the Elliptic curve secp256k1.
     Secp256k1Double(WeierstrassDoubleAssignChip<SwCurve<Secp256k1Parameters>>),
+    /// A precompile for scalar multiplication on the Elliptic curve secp256k1.
+    Secp256k1Mul(WeierstrassMulAssignChip<SwCurve<Secp256k1Parameters>>),
     /// A precompile for the Keccak permutation.
     KeccakP(KeccakPermuteChip),
+    /// A precompile for the Keccak-256 hash of a memory buffer.
+    Keccak256Hash(Keccak256HashChip),
+    /// A precompile for the Poseidon2 permutation over BabyBear.
+    Poseidon2Permute(Poseidon2PermuteChip),
+    /// A precompile for the BLAKE3 compression function.
+    Blake3Compress(Blake3CompressChip),
+    /// A precompile for the BLAKE2b compression function.
+    Blake2bCompress(Blake2bCompressChip),
     /// A precompile for addition on the Elliptic curve bn254.
     Bn254Add(WeierstrassAddAssignChip<SwCurve<Bn254Parameters>>),
     /// A precompile for doubling a point on the Elliptic curve bn254.
     Bn254Double(WeierstrassDoubleAssignChip<SwCurve<Bn254Parameters>>),
+    /// A precompile for scalar multiplication on the Elliptic curve bn254.
+    Bn254Mul(WeierstrassMulAssignChip<SwCurve<Bn254Parameters>>),
     /// A precompile for addition on the Elliptic curve bls12_381.
     Bls12381Add(WeierstrassAddAssignChip<SwCurve<Bls12381Parameters>>),
     /// A precompile for doubling a point on the Elliptic curve bls12_381.
     Bls12381Double(WeierstrassDoubleAssignChip<SwCurve<Bls12381Parameters>>),
-    /// A precompile for uint256 mul.
-    Uint256Mul(Uint256MulChip),
+    /// A precompile for uint256 modular add, sub and mul.
+    Uint256Op(UintOpChip<U256Field>),
+    /// A precompile for uint384 modular add, sub and mul.
+    Uint384Op(UintOpChip<U384Field>),
+    /// A precompile for uint512 modular add, sub and mul.
+    Uint512Op(UintOpChip<U512Field>),
     /// A precompile for decompressing a point on the BLS12-381 curve.
     Bls12381Decompress(WeierstrassDecompressChip<SwCurve<Bls12381Parameters>>),
+    /// A precompile for addition on the Elliptic curve secp256r1.
+    Secp256r1Add(WeierstrassAddAssignChip<SwCurve<Secp256r1Parameters>>),
+    /// A precompile for doubling a point on the Elliptic curve secp256r1.
+    Secp256r1Double(WeierstrassDoubleAssignChip<SwCurve<Secp256r1Parameters>>),
+    /// A precompile for decompressing a point on the secp256r1 curve.
+    Secp256r1Decompress(WeierstrassDecompressChip<SwCurve<Secp256r1Parameters>>),
+    /// A precompile for BN254 base field add, sub and mul.
+    Bn254Fp(FpOpChip<Bn254BaseField>),
+    /// A precompile for BN254 quadratic extension field add and sub.
+    Bn254Fp2AddSub(Fp2AddSubAssignChip<Bn254BaseField>),
+    /// A precompile for BN254 quadratic extension field mul.
+    Bn254Fp2Mul(Fp2MulAssignChip<Bn254BaseField>),
+    /// A precompile for BLS12-381 base field add, sub and mul.
+    Bls12381Fp(FpOpChip<Bls12381BaseField>),
+    /// A precompile for BLS12-381 quadratic extension field add and sub.
+    Bls12381Fp2AddSub(Fp2AddSubAssignChip<Bls12381BaseField>),
+    /// A precompile for BLS12-381 quadratic extension field mul.
+    Bls12381Fp2Mul(Fp2MulAssignChip<Bls12381BaseField>),
+    /// A precompile for sha512 extend.
+    Sha512Extend(Sha512ExtendChip),
+    /// A precompile for sha512 compress.
+    Sha512Compress(Sha512CompressChip),
 }
 
 impl<F: PrimeField32> RiscvAir<F> {
@@ -126,6 +186,8 @@ impl<F: PrimeField32> RiscvAir<F> {
         chips.push(RiscvAir::Sha256Compress(sha_compress));
         let ed_add_assign = EdAddAssignChip::<EdwardsCurve<Ed25519Parameters>>::new();
         chips.push(RiscvAir::Ed25519Add(ed_add_assign));
+        let ed_mul_assign = EdMulAssignChip::<EdwardsCurve<Ed25519Parameters>>::new();
+        chips.push(RiscvAir::Ed25519Mul(ed_mul_assign));
         let ed_decompress = EdDecompressChip::<Ed25519Parameters>::default();
         chips.push(RiscvAir::Ed25519Decompress(ed_decompress));
         let k256_decompress =
@@ -136,21 +198,61 @@ impl<F: PrimeField32> RiscvAir<F> {
         let secp256k1_double_assign =
             WeierstrassDoubleAssignChip::<SwCurve<Secp256k1Parameters>>::new();
         chips.push(RiscvAir::Secp256k1Double(secp256k1_double_assign));
+        let secp256k1_mul_assign = WeierstrassMulAssignChip::<SwCurve<Secp256k1Parameters>>::new();
+        chips.push(RiscvAir::Secp256k1Mul(secp256k1_mul_assign));
         let keccak_permute = KeccakPermuteChip::new();
         chips.push(RiscvAir::KeccakP(keccak_permute));
+        let keccak256_hash = Keccak256HashChip::new();
+        chips.push(RiscvAir::Keccak256Hash(keccak256_hash));
+        let poseidon2_permute = Poseidon2PermuteChip::new();
+        chips.push(RiscvAir::Poseidon2Permute(poseidon2_permute));
+        let blake3_compress = Blake3CompressChip::new();
+        chips.push(RiscvAir::Blake3Compress(blake3_compress));
+        let blake2b_compress = Blake2bCompressChip::new();
+        chips.push(RiscvAir::Blake2bCompress(blake2b_compress));
         let bn254_add_assign = WeierstrassAddAssignChip::<SwCurve<Bn254Parameters>>::new();
         chips.push(RiscvAir::Bn254Add(bn254_add_assign));
         let bn254_double_assign = WeierstrassDoubleAssignChip::<SwCurve<Bn254Parameters>>::new();
         chips.push(RiscvAir::Bn254Double(bn254_double_assign));
+        let bn254_mul_assign = WeierstrassMulAssignChip::<SwCurve<Bn254Parameters>>::new();
+        chips.push(RiscvAir::Bn254Mul(bn254_mul_assign));
         let bls12381_add = WeierstrassAddAssignChip::<SwCurve<Bls12381Parameters>>::new();
         chips.push(RiscvAir::Bls12381Add(bls12381_add));
         let bls12381_double = WeierstrassDoubleAssignChip::<SwCurve<Bls12381Parameters>>::new();
         chips.push(RiscvAir::Bls12381Double(bls12381_double));
-        let uint256_mul = Uint256MulChip::default();
-        chips.push(RiscvAir::Uint256Mul(uint256_mul));
+        let uint256_op = UintOpChip::<U256Field>::new();
+        chips.push(RiscvAir::Uint256Op(uint256_op));
+        let uint384_op = UintOpChip::<U384Field>::new();
+        chips.push(RiscvAir::Uint384Op(uint384_op));
+        let uint512_op = UintOpChip::<U512Field>::new();
+        chips.push(RiscvAir::Uint512Op(uint512_op));
         let bls12381_decompress =
             WeierstrassDecompressChip::<SwCurve<Bls12381Parameters>>::with_lexicographic_rule();
         chips.push(RiscvAir::Bls12381Decompress(bls12381_decompress));
+        let secp256r1_add_assign = WeierstrassAddAssignChip::<SwCurve<Secp256r1Parameters>>::new();
+        chips.push(RiscvAir::Secp256r1Add(secp256r1_add_assign));
+        let secp256r1_double_assign =
+            WeierstrassDoubleAssignChip::<SwCurve<Secp256r1Parameters>>::new();
+        chips.push(RiscvAir::Secp256r1Double(secp256r1_double_assign));
+        let secp256r1_decompress =
+            WeierstrassDecompressChip::<SwCurve<Secp256r1Parameters>>::with_lsb_rule();
+        chips.push(RiscvAir::Secp256r1Decompress(secp256r1_decompress));
+        let bn254_fp = FpOpChip::<Bn254BaseField>::new();
+        chips.push(RiscvAir::Bn254Fp(bn254_fp));
+        let bn254_fp2_addsub = Fp2AddSubAssignChip::<Bn254BaseField>::new();
+        chips.push(RiscvAir::Bn254Fp2AddSub(bn254_fp2_addsub));
+        let bn254_fp2_mul = Fp2MulAssignChip::<Bn254BaseField>::new();
+        chips.push(RiscvAir::Bn254Fp2Mul(bn254_fp2_mul));
+        let bls12381_fp = FpOpChip::<Bls12381BaseField>::new();
+        chips.push(RiscvAir::Bls12381Fp(bls12381_fp));
+        let bls12381_fp2_addsub = Fp2AddSubAssignChip::<Bls12381BaseField>::new();
+        chips.push(RiscvAir::Bls12381Fp2AddSub(bls12381_fp2_addsub));
+        let bls12381_fp2_mul = Fp2MulAssignChip::<Bls12381BaseField>::new();
+        chips.push(RiscvAir::Bls12381Fp2Mul(bls12381_fp2_mul));
+        let sha512_extend = Sha512ExtendChip::default();
+        chips.push(RiscvAir::Sha512Extend(sha512_extend));
+        let sha512_compress = Sha512CompressChip::default();
+        chips.push(RiscvAir::Sha512Compress(sha512_compress));
         let div_rem = DivRemChip::default();
         chips.push(RiscvAir::DivRem(div_rem));
         let add = AddSubChip::default();
@@ -191,3 +293,54 @@ impl<F: PrimeField32> core::hash::Hash for RiscvAir<F> {
         self.name().hash(state);
     }
 }
+
+/// An AIR for encoding RISC-V execution with precompiles defined outside of `sp1-core`.
+///
+/// The extension chips prove the events of the
+/// [PrecompileExtension](crate::runtime::PrecompileExtension)s registered in the
+/// [SP1Context](crate::runtime::SP1Context) of the execution.
+#[derive(MachineAir)]
+#[eval_trait_bound = "E: p3_air::Air<AB>"]
+pub enum ExtendedRiscvAir<
+    F: PrimeField32,
+    E: MachineAir<F, Record = ExecutionRecord, Program = Program>,
+> {
+    /// A chip of the RISC-V AIR.
+    Riscv(RiscvAir<F>),
+    /// The chip of a precompile extension.
+    Extension(E),
+}
+
+impl<F, E> ExtendedRiscvAir<F, E>
+where
+    F: PrimeField32,
+    E: MachineAir<F, Record = ExecutionRecord, Program = Program>,
+{
+    /// Builds the RISC-V machine with the chips of the given precompile extensions.
+    ///
+    /// The extension chips come after the RISC-V chips, and are bound to the keys of the machine.
+    /// Proofs of this machine can only be verified natively: the recursion programs only support
+    /// the chips of [RiscvAir].
+    #[instrument("construct ExtendedRiscvAir machine", level = "debug", skip_all)]
+    pub fn machine<SC: StarkGenericConfig<Val = F>>(
+        config: SC,
+        extensions: Vec<E>,
+    ) -> StarkMachine<SC, Self>
+    where
+        E: Air<InteractionBuilder<F>> + Air<SymbolicAirBuilder<F>>,
+    {
+        let num_extension_chips = extensions.len();
+        let chips = RiscvAir::get_all()
+            .into_iter()
+            .map(ExtendedRiscvAir::Riscv)
+            .chain(extensions.into_iter().map(ExtendedRiscvAir::Extension))
+            .map(Chip::new)
+            .collect::<Vec<_>>();
+        assert!(
+            chips.iter().map(|chip| chip.name()).all_unique(),
+            "the names of the extension chips must be unique"
+        );
+        StarkMachine::new(config, chips, SP1_PROOF_NUM_PV_ELTS)
+            .with_extension_chips(num_extension_chips)
+    }
+}
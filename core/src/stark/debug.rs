@@ -11,11 +11,34 @@ use p3_field::{ExtensionField, Field};
 use p3_matrix::dense::RowMajorMatrixView;
 use p3_matrix::stack::VerticalPair;
 use p3_matrix::{dense::RowMajorMatrix, Matrix};
+use thiserror::Error;
 
 use super::{MachineChip, StarkGenericConfig, Val};
 use crate::air::{EmptyMessageBuilder, MachineAir, MultiTableAirBuilder};
 
-/// Checks that the constraints of the given AIR are satisfied, including the permutation trace.
+/// A row of a chip whose constraints are not satisfied.
+#[derive(Error, Debug, Clone)]
+#[error("failed at row {row} of chip {chip}")]
+pub struct DebugConstraintError {
+    pub chip: String,
+    pub row: usize,
+    /// The main trace of the failing row and of the next row, formatted for display.
+    pub local: String,
+    pub next: String,
+}
+
+impl DebugConstraintError {
+    /// Reports the failing row on stderr and exits the process.
+    pub(crate) fn exit(&self) -> ! {
+        eprintln!("local: {}", self.local);
+        eprintln!("next:  {}", self.next);
+        eprintln!("{}", self);
+        exit(1);
+    }
+}
+
+/// Checks that the constraints of the given AIR are satisfied, including the permutation trace,
+/// and exits the process after reporting the failing row if they are not.
 ///
 /// Note that this does not actually verify the proof.
 pub fn debug_constraints<SC, A>(
@@ -29,17 +52,46 @@ pub fn debug_constraints<SC, A>(
     SC: StarkGenericConfig,
     Val<SC>: PrimeField32,
     A: MachineAir<Val<SC>> + for<'a> Air<DebugConstraintBuilder<'a, Val<SC>, SC::Challenge>>,
+{
+    if let Err(e) = try_debug_constraints::<SC, A>(
+        chip,
+        preprocessed,
+        main,
+        perm,
+        perm_challenges,
+        public_values,
+    ) {
+        e.exit();
+    }
+}
+
+/// Checks that the constraints of the given AIR are satisfied, including the permutation trace,
+/// and returns the first failing row if they are not.
+///
+/// Note that this does not actually verify the proof.
+pub fn try_debug_constraints<SC, A>(
+    chip: &MachineChip<SC, A>,
+    preprocessed: Option<&RowMajorMatrix<Val<SC>>>,
+    main: &RowMajorMatrix<Val<SC>>,
+    perm: &RowMajorMatrix<SC::Challenge>,
+    perm_challenges: &[SC::Challenge],
+    public_values: Vec<Val<SC>>,
+) -> Result<(), DebugConstraintError>
+where
+    SC: StarkGenericConfig,
+    Val<SC>: PrimeField32,
+    A: MachineAir<Val<SC>> + for<'a> Air<DebugConstraintBuilder<'a, Val<SC>, SC::Challenge>>,
 {
     assert_eq!(main.height(), perm.height());
     let height = main.height();
     if height == 0 {
-        return;
+        return Ok(());
     }
 
     let cumulative_sum = perm.row_slice(perm.height() - 1).last().copied().unwrap();
 
     // Check that constraints are satisfied.
-    (0..height).for_each(|i| {
+    for i in 0..height {
         let i_next = (i + 1) % height;
 
         let main_local = main.row_slice(i);
@@ -97,12 +149,15 @@ pub fn debug_constraints<SC, A>(
             chip.eval(&mut builder);
         }));
         if result.is_err() {
-            eprintln!("local: {:?}", main_local);
-            eprintln!("next:  {:?}", main_next);
-            eprintln!("failed at row {} of chip {}", i, chip.name());
-            exit(1);
+            return Err(DebugConstraintError {
+                chip: chip.name(),
+                row: i,
+                local: format!("{:?}", main_local),
+                next: format!("{:?}", main_next),
+            });
         }
-    });
+    }
+    Ok(())
 }
 
 fn catch_unwind_silent<F: FnOnce() -> R + panic::UnwindSafe, R>(f: F) -> std::thread::Result<R> {
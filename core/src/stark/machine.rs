@@ -1,6 +1,7 @@
 use hashbrown::HashMap;
 use itertools::Itertools;
 use p3_air::Air;
+use p3_air::BaseAir;
 use p3_challenger::CanObserve;
 use p3_challenger::FieldChallenger;
 use p3_commit::Pcs;
@@ -19,7 +20,8 @@ use std::cmp::Reverse;
 use std::fmt::Debug;
 use tracing::instrument;
 
-use super::debug_constraints;
+use super::try_debug_constraints;
+use super::DebugConstraintError;
 use super::Dom;
 use crate::air::MachineAir;
 use crate::air::MachineProgram;
@@ -50,6 +52,9 @@ pub struct StarkMachine<SC: StarkGenericConfig, A> {
 
     /// The number of public values elements that the machine uses
     num_pv_elts: usize,
+
+    /// The number of chips, at the end of `chips`, which are precompile extensions.
+    num_extension_chips: usize,
 }
 
 impl<SC: StarkGenericConfig, A> StarkMachine<SC, A> {
@@ -58,8 +63,17 @@ impl<SC: StarkGenericConfig, A> StarkMachine<SC, A> {
             config,
             chips,
             num_pv_elts,
+            num_extension_chips: 0,
         }
     }
+
+    /// Marks the last `num_extension_chips` chips of the machine as precompile extensions, whose
+    /// description is bound to the keys of the machine.
+    pub fn with_extension_chips(mut self, num_extension_chips: usize) -> Self {
+        assert!(num_extension_chips <= self.chips.len());
+        self.num_extension_chips = num_extension_chips;
+        self
+    }
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -71,12 +85,16 @@ pub struct StarkProvingKey<SC: StarkGenericConfig> {
     pub traces: Vec<RowMajorMatrix<Val<SC>>>,
     pub data: PcsProverData<SC>,
     pub chip_ordering: HashMap<String, usize>,
+    /// The description of the precompile extension chips of the machine, empty if it has none.
+    #[serde(default)]
+    pub extension_chip_set: Vec<Val<SC>>,
 }
 
 impl<SC: StarkGenericConfig> StarkProvingKey<SC> {
     pub fn observe_into(&self, challenger: &mut SC::Challenger) {
         challenger.observe(self.commit.clone());
         challenger.observe(self.pc_start);
+        challenger.observe_slice(&self.extension_chip_set);
     }
 }
 
@@ -88,12 +106,16 @@ pub struct StarkVerifyingKey<SC: StarkGenericConfig> {
     pub pc_start: Val<SC>,
     pub chip_information: Vec<(String, Dom<SC>, Dimensions)>,
     pub chip_ordering: HashMap<String, usize>,
+    /// The description of the precompile extension chips of the machine, empty if it has none.
+    #[serde(default)]
+    pub extension_chip_set: Vec<Val<SC>>,
 }
 
 impl<SC: StarkGenericConfig> StarkVerifyingKey<SC> {
     pub fn observe_into(&self, challenger: &mut SC::Challenger) {
         challenger.observe(self.commit.clone());
         challenger.observe(self.pc_start);
+        challenger.observe_slice(&self.extension_chip_set);
     }
 }
 
@@ -146,6 +168,51 @@ impl<SC: StarkGenericConfig, A: MachineAir<Val<SC>>> StarkMachine<SC, A> {
             .sorted_by_key(|chip| chip_ordering.get(&chip.name()))
     }
 
+    /// Describes the precompile extension chips of the machine, so that the keys of machines with
+    /// different extensions differ.
+    ///
+    /// Each chip is described as in [Self::chip_set]. The description is empty for a machine
+    /// without extensions.
+    pub fn extension_chip_set(&self) -> Vec<Val<SC>> {
+        Self::describe_chips(&self.chips[self.chips.len() - self.num_extension_chips..])
+    }
+
+    /// Describes all the chips of the machine, in order.
+    ///
+    /// Each chip is described by its name, its widths, its quotient degree and its number of
+    /// interactions.
+    pub fn chip_set(&self) -> Vec<Val<SC>> {
+        Self::describe_chips(&self.chips)
+    }
+
+    fn describe_chips(chips: &[MachineChip<SC, A>]) -> Vec<Val<SC>> {
+        chips
+            .iter()
+            .flat_map(|chip| {
+                let name = chip.name();
+                [name.len()]
+                    .into_iter()
+                    .map(Val::<SC>::from_canonical_usize)
+                    .chain(
+                        name.into_bytes()
+                            .into_iter()
+                            .map(Val::<SC>::from_canonical_u8),
+                    )
+                    .chain(
+                        [
+                            chip.width(),
+                            chip.preprocessed_width(),
+                            chip.log_quotient_degree(),
+                            chip.sends().len(),
+                            chip.receives().len(),
+                        ]
+                        .map(Val::<SC>::from_canonical_usize),
+                    )
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
     pub fn chips_sorted_indices(&self, proof: &ShardProof<SC>) -> Vec<Option<usize>> {
         self.chips()
             .iter()
@@ -218,6 +285,7 @@ impl<SC: StarkGenericConfig, A: MachineAir<Val<SC>>> StarkMachine<SC, A> {
             .collect::<Vec<_>>();
 
         let pc_start = program.pc_start();
+        let extension_chip_set = self.extension_chip_set();
 
         (
             StarkProvingKey {
@@ -226,12 +294,14 @@ impl<SC: StarkGenericConfig, A: MachineAir<Val<SC>>> StarkMachine<SC, A> {
                 traces,
                 data,
                 chip_ordering: chip_ordering.clone(),
+                extension_chip_set: extension_chip_set.clone(),
             },
             StarkVerifyingKey {
                 commit,
                 pc_start,
                 chip_information,
                 chip_ordering,
+                extension_chip_set,
             },
         )
     }
@@ -270,22 +340,56 @@ impl<SC: StarkGenericConfig, A: MachineAir<Val<SC>>> StarkMachine<SC, A> {
         SC::Challenger: Clone,
         A: for<'a> Air<VerifierConstraintFolder<'a, SC>>,
     {
+        self.verify_shards(vk, &proof.shard_proofs, challenger)?;
+
+        // Verify the cumulative sum is 0.
+        tracing::debug_span!("verify cumulative sum is 0").in_scope(|| {
+            let mut sum = SC::Challenge::zero();
+            for proof in proof.shard_proofs.iter() {
+                sum += proof.cumulative_sum();
+            }
+            match sum.is_zero() {
+                true => Ok(()),
+                false => Err(MachineVerificationError::NonZeroCumulativeSum),
+            }
+        })
+    }
+
+    /// Verify the shard proofs, opened against a challenger that observed all of them.
+    ///
+    /// Unlike [StarkMachine::verify], this does not check that the interactions balance over the
+    /// shards, so it also accepts the proofs of a subset of the shards of an execution.
+    pub fn verify_shards(
+        &self,
+        vk: &StarkVerifyingKey<SC>,
+        shard_proofs: &[ShardProof<SC>],
+        challenger: &mut SC::Challenger,
+    ) -> Result<(), MachineVerificationError<SC>>
+    where
+        SC::Challenger: Clone,
+        A: for<'a> Air<VerifierConstraintFolder<'a, SC>>,
+    {
+        // The verifying key must be for a machine with the same precompile extensions.
+        if vk.extension_chip_set != self.extension_chip_set() {
+            return Err(MachineVerificationError::ExtensionChipSetMismatch);
+        }
+
         // Observe the preprocessed commitment.
         vk.observe_into(challenger);
         tracing::debug_span!("observe challenges for all shards").in_scope(|| {
-            proof.shard_proofs.iter().for_each(|proof| {
+            shard_proofs.iter().for_each(|proof| {
                 challenger.observe(proof.commitment.main_commit.clone());
                 challenger.observe_slice(&proof.public_values[0..self.num_pv_elts()]);
             });
         });
 
         // Verify the shard proofs.
-        if proof.shard_proofs.is_empty() {
+        if shard_proofs.is_empty() {
             return Err(MachineVerificationError::EmptyProof);
         }
 
         tracing::debug_span!("verify shard proofs").in_scope(|| {
-            for (i, shard_proof) in proof.shard_proofs.iter().enumerate() {
+            for (i, shard_proof) in shard_proofs.iter().enumerate() {
                 tracing::debug_span!("verifying shard", shard = i).in_scope(|| {
                     let chips = self
                         .shard_chips_ordered(&shard_proof.chip_ordering)
@@ -302,18 +406,6 @@ impl<SC: StarkGenericConfig, A: MachineAir<Val<SC>>> StarkMachine<SC, A> {
             }
 
             Ok(())
-        })?;
-
-        // Verify the cumulative sum is 0.
-        tracing::debug_span!("verify cumulative sum is 0").in_scope(|| {
-            let mut sum = SC::Challenge::zero();
-            for proof in proof.shard_proofs.iter() {
-                sum += proof.cumulative_sum();
-            }
-            match sum.is_zero() {
-                true => Ok(()),
-                false => Err(MachineVerificationError::NonZeroCumulativeSum),
-            }
         })
     }
 
@@ -337,27 +429,58 @@ impl<SC: StarkGenericConfig, A: MachineAir<Val<SC>>> StarkMachine<SC, A> {
 
         let mut cumulative_sum = SC::Challenge::zero();
         for shard in records.iter() {
-            // Filter the chips based on what is used.
-            let chips = self.shard_chips(shard).collect::<Vec<_>>();
-
-            // Generate the main trace for each chip.
-            let pre_traces = chips
-                .iter()
-                .map(|chip| {
-                    pk.chip_ordering
-                        .get(&chip.name())
-                        .map(|index| &pk.traces[*index])
-                })
-                .collect::<Vec<_>>();
-            let mut traces = chips
-                .par_iter()
-                .map(|chip| chip.generate_trace(shard, &mut A::Record::default()))
-                .zip(pre_traces)
-                .collect::<Vec<_>>();
-
-            // Generate the permutation traces.
-            let mut permutation_traces = Vec::with_capacity(chips.len());
-            let mut cumulative_sums = Vec::with_capacity(chips.len());
+            match self.debug_shard_constraints(pk, shard, &permutation_challenges) {
+                Ok(shard_cumulative_sum) => cumulative_sum += shard_cumulative_sum,
+                Err(e) => e.exit(),
+            }
+        }
+
+        // If the cumulative sum is not zero, debug the interactions.
+        if !cumulative_sum.is_zero() {
+            debug_interactions_with_all_chips::<SC, A>(
+                self,
+                pk,
+                &records,
+                InteractionKind::all_kinds(),
+            );
+            panic!("Cumulative sum is not zero");
+        }
+    }
+
+    /// Checks the constraints of a single shard, and returns its cumulative sum, or the first row
+    /// whose constraints are not satisfied.
+    ///
+    /// The interactions are not checked, since they only balance over all the shards.
+    pub fn debug_shard_constraints(
+        &self,
+        pk: &StarkProvingKey<SC>,
+        shard: &A::Record,
+        permutation_challenges: &[SC::Challenge],
+    ) -> Result<SC::Challenge, DebugConstraintError>
+    where
+        SC::Val: PrimeField32,
+        A: for<'a> Air<DebugConstraintBuilder<'a, Val<SC>, SC::Challenge>>,
+    {
+        // Filter the chips based on what is used.
+        let chips = self.shard_chips(shard).collect::<Vec<_>>();
+
+        // Generate the main trace for each chip.
+        let pre_traces = chips
+            .iter()
+            .map(|chip| {
+                pk.chip_ordering
+                    .get(&chip.name())
+                    .map(|index| &pk.traces[*index])
+            })
+            .collect::<Vec<_>>();
+        let mut traces = chips
+            .par_iter()
+            .map(|chip| chip.generate_trace(shard, &mut A::Record::default()))
+            .zip(pre_traces)
+            .collect::<Vec<_>>();
+
+        // Generate the permutation traces.
+        let (permutation_traces, cumulative_sums): (Vec<_>, Vec<_>) =
             tracing::debug_span!("generate permutation traces").in_scope(|| {
                 chips
                     .par_iter()
@@ -366,7 +489,7 @@ impl<SC: StarkGenericConfig, A: MachineAir<Val<SC>>> StarkMachine<SC, A> {
                         let perm_trace = chip.generate_permutation_trace(
                             *pre_trace,
                             main_trace,
-                            &permutation_challenges,
+                            permutation_challenges,
                         );
                         let cumulative_sum = perm_trace
                             .row_slice(main_trace.height() - 1)
@@ -375,55 +498,44 @@ impl<SC: StarkGenericConfig, A: MachineAir<Val<SC>>> StarkMachine<SC, A> {
                             .unwrap();
                         (perm_trace, cumulative_sum)
                     })
-                    .unzip_into_vecs(&mut permutation_traces, &mut cumulative_sums);
+                    .unzip()
             });
 
-            cumulative_sum += cumulative_sums.iter().copied().sum::<SC::Challenge>();
+        // Compute some statistics.
+        for i in 0..chips.len() {
+            let trace_width = traces[i].0.width();
+            let permutation_width = permutation_traces[i].width()
+                * <SC::Challenge as AbstractExtensionField<SC::Val>>::D;
+            let total_width = trace_width + permutation_width;
+            tracing::debug!(
+                "{:<11} | Main Cols = {:<5} | Perm Cols = {:<5} | Rows = {:<10} | Cells = {:<10}",
+                chips[i].name(),
+                trace_width,
+                permutation_width,
+                traces[i].0.height(),
+                total_width * traces[i].0.height(),
+            );
+        }
 
-            // Compute some statistics.
+        tracing::info_span!("debug constraints").in_scope(|| {
             for i in 0..chips.len() {
-                let trace_width = traces[i].0.width();
-                let permutation_width = permutation_traces[i].width()
-                    * <SC::Challenge as AbstractExtensionField<SC::Val>>::D;
-                let total_width = trace_width + permutation_width;
-                tracing::debug!(
-                    "{:<11} | Main Cols = {:<5} | Perm Cols = {:<5} | Rows = {:<10} | Cells = {:<10}",
-                    chips[i].name(),
-                    trace_width,
-                    permutation_width,
-                    traces[i].0.height(),
-                    total_width * traces[i].0.height(),
-                );
+                let permutation_trace = pk
+                    .chip_ordering
+                    .get(&chips[i].name())
+                    .map(|index| &pk.traces[*index]);
+                try_debug_constraints::<SC, A>(
+                    chips[i],
+                    permutation_trace,
+                    &traces[i].0,
+                    &permutation_traces[i],
+                    permutation_challenges,
+                    shard.public_values(),
+                )?;
             }
+            Ok(())
+        })?;
 
-            tracing::info_span!("debug constraints").in_scope(|| {
-                for i in 0..chips.len() {
-                    let permutation_trace = pk
-                        .chip_ordering
-                        .get(&chips[i].name())
-                        .map(|index| &pk.traces[*index]);
-                    debug_constraints::<SC, A>(
-                        chips[i],
-                        permutation_trace,
-                        &traces[i].0,
-                        &permutation_traces[i],
-                        &permutation_challenges,
-                        shard.public_values(),
-                    );
-                }
-            });
-        }
-
-        // If the cumulative sum is not zero, debug the interactions.
-        if !cumulative_sum.is_zero() {
-            debug_interactions_with_all_chips::<SC, A>(
-                self,
-                pk,
-                &records,
-                InteractionKind::all_kinds(),
-            );
-            panic!("Cumulative sum is not zero");
-        }
+        Ok(cumulative_sums.iter().copied().sum())
     }
 }
 
@@ -439,6 +551,7 @@ pub enum MachineVerificationError<SC: StarkGenericConfig> {
     InvalidChipOccurence(String),
     MissingCpuInFirstShard,
     CpuLogDegreeTooLarge(usize),
+    ExtensionChipSetMismatch,
 }
 
 impl<SC: StarkGenericConfig> Debug for MachineVerificationError<SC> {
@@ -477,6 +590,9 @@ impl<SC: StarkGenericConfig> Debug for MachineVerificationError<SC> {
             MachineVerificationError::CpuLogDegreeTooLarge(log_degree) => {
                 write!(f, "CPU log degree too large: {}", log_degree)
             }
+            MachineVerificationError::ExtensionChipSetMismatch => {
+                write!(f, "Extension chip set mismatch")
+            }
         }
     }
 }
@@ -498,19 +614,28 @@ pub mod tests {
     use crate::runtime::tests::simple_memory_program;
     use crate::runtime::tests::simple_program;
     use crate::runtime::tests::ssz_withdrawals_program;
+    use crate::runtime::CancellationToken;
     use crate::runtime::Instruction;
     use crate::runtime::Opcode;
     use crate::runtime::Program;
+    use crate::runtime::ProgressEvent;
+    use crate::runtime::Runtime;
+    use crate::runtime::SP1Context;
     use crate::stark::CpuProver;
+    use crate::stark::MachineProver;
     use crate::stark::RiscvAir;
+    use crate::stark::StarkGenericConfig;
     use crate::stark::StarkProvingKey;
     use crate::stark::StarkVerifyingKey;
     use crate::utils;
     use crate::utils::prove;
+    use crate::utils::prove_with_context;
     use crate::utils::run_test;
     use crate::utils::setup_logger;
     use crate::utils::BabyBearPoseidon2;
+    use crate::utils::CheckpointStore;
     use crate::utils::SP1CoreOpts;
+    use crate::utils::SP1CoreProverError;
 
     #[test]
     fn test_simple_prove() {
@@ -665,6 +790,81 @@ pub mod tests {
         prove::<_, CpuProver<_, _>>(program, &stdin, BabyBearPoseidon2::new(), opts).unwrap();
     }
 
+    #[test]
+    fn test_fibonacci_prove_resume() {
+        setup_logger();
+
+        let program = fibonacci_program();
+        let stdin = SP1Stdin::new();
+        let checkpoint_dir = tempfile::tempdir().unwrap();
+        let mut opts = SP1CoreOpts::default();
+        opts.shard_size = 1024;
+        opts.shard_batch_size = 2;
+        let prover = CpuProver::new(RiscvAir::machine(BabyBearPoseidon2::new()));
+        let (pk, vk) = prover.setup(&program);
+
+        // Simulate a run that was interrupted after saving its first checkpoints.
+        let mut store =
+            CheckpointStore::open(checkpoint_dir.path(), &program, &stdin, &opts, &pk).unwrap();
+        let mut runtime = Runtime::new(program.clone(), opts);
+        for index in 0..2 {
+            let (checkpoint, done) = runtime.execute_state().unwrap();
+            store.save_checkpoint(index, &checkpoint).unwrap();
+            if done {
+                break;
+            }
+        }
+
+        // Resume execution from the saved checkpoints, then restore every shard proof.
+        for _ in 0..2 {
+            let context = SP1Context::builder()
+                .checkpoint_dir(checkpoint_dir.path())
+                .build();
+            let (proof, _, _) =
+                prove_with_context(&prover, &pk, program.clone(), &stdin, opts, context).unwrap();
+            let mut challenger = prover.config().challenger();
+            prover
+                .machine()
+                .verify(&vk, &proof, &mut challenger)
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_fibonacci_prove_cancel() {
+        setup_logger();
+
+        let program = fibonacci_program();
+        let stdin = SP1Stdin::new();
+        let mut opts = SP1CoreOpts::default();
+        opts.shard_size = 1024;
+        opts.shard_batch_size = 2;
+
+        // Cancel as soon as the first shards are executed.
+        let token = CancellationToken::new();
+        let events = std::sync::Mutex::new(Vec::new());
+        let context = SP1Context::builder()
+            .on_progress(|event| {
+                if let ProgressEvent::ShardsExecuted { .. } = event {
+                    token.cancel();
+                }
+                events.lock().unwrap().push(event);
+            })
+            .cancellation_token(token.clone())
+            .build();
+
+        let prover = CpuProver::new(RiscvAir::machine(BabyBearPoseidon2::new()));
+        let (pk, _) = prover.setup(&program);
+        let result = prove_with_context(&prover, &pk, program, &stdin, opts, context);
+        assert!(matches!(result, Err(SP1CoreProverError::Cancelled)));
+
+        let events = events.into_inner().unwrap();
+        assert!(matches!(events[0], ProgressEvent::Executed { .. }));
+        assert!(!events
+            .iter()
+            .any(|event| matches!(event, ProgressEvent::ShardsProven { .. })));
+    }
+
     #[test]
     fn test_fibonacci_prove_batch() {
         setup_logger();
@@ -679,6 +879,32 @@ pub mod tests {
         .unwrap();
     }
 
+    #[test]
+    fn test_debug_shard_constraints_failure() {
+        setup_logger();
+        let program = simple_program();
+        let mut runtime = Runtime::new(program.clone(), SP1CoreOpts::default());
+        runtime.run().unwrap();
+
+        let config = BabyBearPoseidon2::new();
+        let machine = RiscvAir::machine(config);
+        let prover = CpuProver::new(machine);
+        let (pk, _) = prover.setup(&program);
+        prover
+            .machine()
+            .generate_dependencies(&mut runtime.records, &SP1CoreOpts::default());
+        let mut record = runtime.records.remove(0);
+
+        // A jump that skips an instruction breaks the transition between the CPU rows.
+        record.cpu_events[1].pc += 4;
+        let mut challenger = prover.config().challenger();
+        let error = prover
+            .debug_shard_constraints(&pk, &record, &mut challenger)
+            .unwrap_err();
+        assert_eq!(error.chip, "CPU");
+        assert_eq!(error.row, 0);
+    }
+
     #[test]
     fn test_simple_memory_program_prove() {
         setup_logger();
@@ -24,6 +24,7 @@ use crate::air::MachineAir;
 use crate::lookup::InteractionBuilder;
 use crate::stark::record::MachineRecord;
 use crate::stark::DebugConstraintBuilder;
+use crate::stark::DebugConstraintError;
 use crate::stark::MachineChip;
 use crate::stark::PackedChallenge;
 use crate::stark::PcsProverData;
@@ -148,6 +149,23 @@ pub trait MachineProver<SC: StarkGenericConfig, A: MachineAir<SC::Val>>:
     {
         self.machine().debug_constraints(pk, records, challenger)
     }
+
+    /// Debug the constraints of a single shard, see [StarkMachine::debug_shard_constraints].
+    fn debug_shard_constraints(
+        &self,
+        pk: &StarkProvingKey<SC>,
+        record: &A::Record,
+        challenger: &mut SC::Challenger,
+    ) -> Result<SC::Challenge, DebugConstraintError>
+    where
+        SC::Val: PrimeField32,
+        A: for<'a> Air<DebugConstraintBuilder<'a, Val<SC>, SC::Challenge>>,
+    {
+        let permutation_challenges: [SC::Challenge; 2] =
+            core::array::from_fn(|_| challenger.sample_ext_element());
+        self.machine()
+            .debug_shard_constraints(pk, record, &permutation_challenges)
+    }
 }
 
 pub struct CpuProver<SC: StarkGenericConfig, A> {
@@ -272,31 +290,30 @@ where
             .collect::<Vec<_>>();
 
         // Generate the permutation traces.
-        let mut permutation_traces = Vec::with_capacity(chips.len());
-        let mut cumulative_sums = Vec::with_capacity(chips.len());
-        tracing::debug_span!("generate permutation traces").in_scope(|| {
-            chips
-                .par_iter()
-                .zip(traces.par_iter_mut())
-                .map(|(chip, main_trace)| {
-                    let preprocessed_trace = pk
-                        .chip_ordering
-                        .get(&chip.name())
-                        .map(|&index| &pk.traces[index]);
-                    let perm_trace = chip.generate_permutation_trace(
-                        preprocessed_trace,
-                        main_trace,
-                        &permutation_challenges,
-                    );
-                    let cumulative_sum = perm_trace
-                        .row_slice(main_trace.height() - 1)
-                        .last()
-                        .copied()
-                        .unwrap();
-                    (perm_trace, cumulative_sum)
-                })
-                .unzip_into_vecs(&mut permutation_traces, &mut cumulative_sums);
-        });
+        let (permutation_traces, cumulative_sums): (Vec<_>, Vec<_>) =
+            tracing::debug_span!("generate permutation traces").in_scope(|| {
+                chips
+                    .par_iter()
+                    .zip(traces.par_iter_mut())
+                    .map(|(chip, main_trace)| {
+                        let preprocessed_trace = pk
+                            .chip_ordering
+                            .get(&chip.name())
+                            .map(|&index| &pk.traces[index]);
+                        let perm_trace = chip.generate_permutation_trace(
+                            preprocessed_trace,
+                            main_trace,
+                            &permutation_challenges,
+                        );
+                        let cumulative_sum = perm_trace
+                            .row_slice(main_trace.height() - 1)
+                            .last()
+                            .copied()
+                            .unwrap();
+                        (perm_trace, cumulative_sum)
+                    })
+                    .unzip()
+            });
 
         // Compute some statistics.
         for i in 0..chips.len() {
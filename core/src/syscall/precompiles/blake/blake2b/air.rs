@@ -0,0 +1,316 @@
+use core::borrow::Borrow;
+
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::{AbstractField, Field};
+use p3_matrix::Matrix;
+
+use super::super::{BLAKE_BLOCK_WORDS, BLAKE_G_INDICES, BLAKE_STATE_WORDS};
+use super::{
+    columns::{Blake2bCompressCols, Blake2bMixCols, NUM_BLAKE2B_COMPRESS_COLS},
+    Blake2bCompressChip, BLAKE2B_G_ROTATIONS, BLAKE2B_NUM_ROUNDS, BLAKE2B_SIGMA,
+};
+use crate::{
+    air::{BaseAirBuilder, SP1AirBuilder, Word, WordAirBuilder},
+    memory::MemoryCols,
+    operations::{AddU64Operation, FixedRotateRightU64Operation, XorOperation},
+    runtime::SyscallCode,
+    syscall::precompiles::eval_step_flags,
+};
+
+impl<F> BaseAir<F> for Blake2bCompressChip {
+    fn width(&self) -> usize {
+        NUM_BLAKE2B_COMPRESS_COLS
+    }
+}
+
+impl<AB> Air<AB> for Blake2bCompressChip
+where
+    AB: SP1AirBuilder,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let (local, next) = (main.row_slice(0), main.row_slice(1));
+        let local: &Blake2bCompressCols<AB::Var> = (*local).borrow();
+        let next: &Blake2bCompressCols<AB::Var> = (*next).borrow();
+
+        // Constrain the incrementing nonce.
+        builder.when_first_row().assert_zero(local.nonce);
+        builder
+            .when_transition()
+            .assert_eq(local.nonce + AB::Expr::one(), next.nonce);
+
+        eval_step_flags(
+            builder,
+            BLAKE2B_NUM_ROUNDS,
+            local.is_real,
+            local.is_first_round,
+            local.is_last_round,
+            local.round,
+            local.round_inverse,
+            next.is_real,
+            next.is_first_round,
+        );
+
+        // Exactly one of the round flags is set on a real row, and it is the flag of the round.
+        let mut flag_sum = AB::Expr::zero();
+        let mut flag_round = AB::Expr::zero();
+        for (round, flag) in local.round_flags.into_iter().enumerate() {
+            builder.assert_bool(flag);
+            flag_sum += flag.into();
+            flag_round += flag * AB::Expr::from_canonical_usize(round);
+        }
+        builder.assert_eq(flag_sum, local.is_real);
+        builder.assert_eq(flag_round, local.round);
+
+        // The message words of the round are the message words selected by its schedule.
+        for (flag, sigma) in local.round_flags.into_iter().zip(BLAKE2B_SIGMA) {
+            for (i, j) in sigma.into_iter().enumerate() {
+                for half in 0..2 {
+                    builder
+                        .when(flag)
+                        .assert_word_eq(local.round_message[i][half], local.message[j][half]);
+                }
+            }
+        }
+
+        // The first round starts from the state and the message in memory, and the chaining value
+        // is the first half of the state.
+        for i in 0..BLAKE_STATE_WORDS {
+            for half in 0..2 {
+                builder.when(local.is_first_round).assert_word_eq(
+                    local.state[i][half],
+                    *local.state_read_mem[2 * i + half].value(),
+                );
+            }
+        }
+        for i in 0..BLAKE_BLOCK_WORDS {
+            for half in 0..2 {
+                builder.when(local.is_first_round).assert_word_eq(
+                    local.message[i][half],
+                    *local.message_read_mem[2 * i + half].value(),
+                );
+            }
+        }
+        for i in 0..8 {
+            for half in 0..2 {
+                builder
+                    .when(local.is_first_round)
+                    .assert_word_eq(local.h[i][half], local.state[i][half]);
+            }
+        }
+
+        // Apply the G functions of the round.
+        let mut v = local.state;
+        for (i, indices) in BLAKE_G_INDICES.into_iter().enumerate() {
+            for (half, rotations) in BLAKE2B_G_ROTATIONS.into_iter().enumerate() {
+                Blake2bMixCols::<AB::F>::eval(
+                    builder,
+                    local.g[i][half],
+                    &mut v,
+                    indices,
+                    local.round_message[2 * i + half],
+                    rotations,
+                    local.shard,
+                    local.channel,
+                    local.is_real,
+                );
+            }
+        }
+
+        // The next round of the same compression continues from the state after this round.
+        {
+            let mut transition_builder = builder.when_transition();
+            let mut builder = transition_builder.when(local.is_real - local.is_last_round);
+            builder.assert_eq(next.round, local.round + AB::Expr::one());
+            builder.assert_all_eq(
+                [
+                    next.shard,
+                    next.channel,
+                    next.clk,
+                    next.state_ptr,
+                    next.message_ptr,
+                ],
+                [
+                    local.shard,
+                    local.channel,
+                    local.clk,
+                    local.state_ptr,
+                    local.message_ptr,
+                ],
+            );
+            for half in 0..2 {
+                for i in 0..8 {
+                    builder.assert_word_eq(next.h[i][half], local.h[i][half]);
+                }
+                for i in 0..BLAKE_STATE_WORDS {
+                    builder.assert_word_eq(next.state[i][half], v[i][half]);
+                }
+                for i in 0..BLAKE_BLOCK_WORDS {
+                    builder.assert_word_eq(next.message[i][half], local.message[i][half]);
+                }
+            }
+        }
+
+        // The next chaining value, written on the last round, is `h ^ v[i] ^ v[i + 8]`.
+        for i in 0..8 {
+            for half in 0..2 {
+                XorOperation::<AB::F>::eval(
+                    builder,
+                    local.h[i][half],
+                    v[i][half],
+                    local.h_xor_v[i][half],
+                    local.shard,
+                    local.channel,
+                    local.is_last_round,
+                );
+                XorOperation::<AB::F>::eval(
+                    builder,
+                    local.h_xor_v[i][half].value,
+                    v[i + 8][half],
+                    local.output[i][half],
+                    local.shard,
+                    local.channel,
+                    local.is_last_round,
+                );
+                builder.when(local.is_last_round).assert_word_eq(
+                    *local.state_write_mem[2 * i + half].value(),
+                    local.output[i][half].value,
+                );
+            }
+        }
+
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.channel,
+            local.clk,
+            local.state_ptr,
+            &local.state_read_mem,
+            local.is_first_round,
+        );
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.channel,
+            local.clk,
+            local.message_ptr,
+            &local.message_read_mem,
+            local.is_first_round,
+        );
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.channel,
+            local.clk + AB::Expr::one(),
+            local.state_ptr,
+            &local.state_write_mem,
+            local.is_last_round,
+        );
+
+        builder.receive_syscall(
+            local.shard,
+            local.channel,
+            local.clk,
+            local.nonce,
+            AB::F::from_canonical_u32(SyscallCode::BLAKE2B_COMPRESS.syscall_id()),
+            local.state_ptr,
+            local.message_ptr,
+            local.is_first_round,
+        );
+    }
+}
+
+impl<F: Field> Blake2bMixCols<F> {
+    /// Evaluates one half of the G function over the words `a`, `b`, `c` and `d` of the state,
+    /// which is updated in place.
+    #[allow(clippy::too_many_arguments)]
+    pub fn eval<AB: SP1AirBuilder>(
+        builder: &mut AB,
+        cols: Blake2bMixCols<AB::Var>,
+        state: &mut [[Word<AB::Var>; 2]; BLAKE_STATE_WORDS],
+        [a, b, c, d]: [usize; 4],
+        m: [Word<AB::Var>; 2],
+        [rotation_d, rotation_b]: [usize; 2],
+        shard: AB::Var,
+        channel: AB::Var,
+        is_real: AB::Var,
+    ) {
+        // a = a + b + m.
+        AddU64Operation::<AB::F>::eval(
+            builder,
+            &[state[a], state[b], m],
+            shard,
+            channel,
+            is_real,
+            cols.a,
+        );
+        state[a] = cols.a.value;
+
+        // d = (d ^ a) >>> rotation_d.
+        eval_xor_u64(
+            builder,
+            state[d],
+            state[a],
+            cols.d_xor_a,
+            shard,
+            channel,
+            is_real,
+        );
+        FixedRotateRightU64Operation::<AB::F>::eval(
+            builder,
+            [cols.d_xor_a[0].value, cols.d_xor_a[1].value],
+            rotation_d,
+            cols.d,
+            shard,
+            channel,
+            is_real,
+        );
+        state[d] = cols.d.value;
+
+        // c = c + d.
+        AddU64Operation::<AB::F>::eval(
+            builder,
+            &[state[c], state[d]],
+            shard,
+            channel,
+            is_real,
+            cols.c,
+        );
+        state[c] = cols.c.value;
+
+        // b = (b ^ c) >>> rotation_b.
+        eval_xor_u64(
+            builder,
+            state[b],
+            state[c],
+            cols.b_xor_c,
+            shard,
+            channel,
+            is_real,
+        );
+        FixedRotateRightU64Operation::<AB::F>::eval(
+            builder,
+            [cols.b_xor_c[0].value, cols.b_xor_c[1].value],
+            rotation_b,
+            cols.b,
+            shard,
+            channel,
+            is_real,
+        );
+        state[b] = cols.b.value;
+    }
+}
+
+/// Evaluates the xor of two 64-bit words, one 32-bit half at a time.
+fn eval_xor_u64<AB: SP1AirBuilder>(
+    builder: &mut AB,
+    x: [Word<AB::Var>; 2],
+    y: [Word<AB::Var>; 2],
+    cols: [XorOperation<AB::Var>; 2],
+    shard: AB::Var,
+    channel: AB::Var,
+    is_real: AB::Var,
+) {
+    for half in 0..2 {
+        XorOperation::<AB::F>::eval(
+            builder, x[half], y[half], cols[half], shard, channel, is_real,
+        );
+    }
+}
@@ -0,0 +1,70 @@
+use core::mem::size_of;
+
+use sp1_derive::AlignedBorrow;
+
+use super::super::{BLAKE_BLOCK_WORDS, BLAKE_NUM_G, BLAKE_STATE_WORDS};
+use super::BLAKE2B_NUM_ROUNDS;
+use crate::air::Word;
+use crate::memory::{MemoryReadCols, MemoryWriteCols};
+use crate::operations::{AddU64Operation, FixedRotateRightU64Operation, XorOperation};
+
+pub const NUM_BLAKE2B_COMPRESS_COLS: usize = size_of::<Blake2bCompressCols<u8>>();
+
+/// A set of columns for one half of the BLAKE2b G function, which computes
+/// `a = a + b + m`, `d = (d ^ a) >>> r1`, `c = c + d` and `b = (b ^ c) >>> r2`.
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct Blake2bMixCols<T> {
+    pub a: AddU64Operation<T>,
+    pub d_xor_a: [XorOperation<T>; 2],
+    pub d: FixedRotateRightU64Operation<T>,
+    pub c: AddU64Operation<T>,
+    pub b_xor_c: [XorOperation<T>; 2],
+    pub b: FixedRotateRightU64Operation<T>,
+}
+
+/// Blake2bCompressCols is the column layout for the BLAKE2b compression function.
+///
+/// Each compression takes one row per round. Every 64-bit word is handled as its low and high
+/// 32-bit words, in that order.
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct Blake2bCompressCols<T> {
+    pub shard: T,
+    pub channel: T,
+    pub clk: T,
+    pub nonce: T,
+    pub state_ptr: T,
+    pub message_ptr: T,
+
+    pub is_real: T,
+    pub is_first_round: T,
+    pub is_last_round: T,
+    pub round: T,
+    /// The inverse of `round - (BLAKE2B_NUM_ROUNDS - 1)`, or zero on the last round.
+    pub round_inverse: T,
+    /// Which round this is, to select the message words of the round.
+    pub round_flags: [T; BLAKE2B_NUM_ROUNDS],
+
+    /// The state and the message, read at `clk` on the first round.
+    pub state_read_mem: [MemoryReadCols<T>; 2 * BLAKE_STATE_WORDS],
+    pub message_read_mem: [MemoryReadCols<T>; 2 * BLAKE_BLOCK_WORDS],
+    /// The next chaining value, written at `clk + 1` on the last round.
+    pub state_write_mem: [MemoryWriteCols<T>; BLAKE_STATE_WORDS],
+
+    /// The chaining value, which is the first half of the input state.
+    pub h: [[Word<T>; 2]; 8],
+    /// The state at the start of the round.
+    pub state: [[Word<T>; 2]; BLAKE_STATE_WORDS],
+    /// The message, in the order it was read.
+    pub message: [[Word<T>; 2]; BLAKE_BLOCK_WORDS],
+    /// The message words of the round.
+    pub round_message: [[Word<T>; 2]; BLAKE_BLOCK_WORDS],
+
+    /// The two halves of each G function of the round.
+    pub g: [[Blake2bMixCols<T>; 2]; BLAKE_NUM_G],
+
+    /// The next chaining value `h ^ v[i] ^ v[i + 8]`, computed on the last round.
+    pub h_xor_v: [[XorOperation<T>; 2]; 8],
+    pub output: [[XorOperation<T>; 2]; 8],
+}
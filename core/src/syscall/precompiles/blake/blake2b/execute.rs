@@ -0,0 +1,64 @@
+use super::super::{BLAKE_BLOCK_WORDS, BLAKE_STATE_WORDS};
+use super::{blake2b_compress, Blake2bCompressChip, Blake2bCompressEvent};
+use crate::{
+    runtime::Syscall,
+    syscall::precompiles::{sha512::u64_from_u32s, SyscallContext},
+};
+
+impl Syscall for Blake2bCompressChip {
+    fn num_extra_cycles(&self) -> u32 {
+        1
+    }
+
+    fn execute(&self, rt: &mut SyscallContext, arg1: u32, arg2: u32) -> Option<u32> {
+        let start_clk = rt.clk;
+        let state_ptr = arg1;
+        let message_ptr = arg2;
+
+        // The state and the message are both read at the start clk, so they must not overlap.
+        let state_end = state_ptr + 8 * BLAKE_STATE_WORDS as u32;
+        let message_end = message_ptr + 8 * BLAKE_BLOCK_WORDS as u32;
+        assert!(
+            state_end <= message_ptr || message_end <= state_ptr,
+            "the state and the message must not overlap"
+        );
+
+        let (state_read_records, state) = rt.mr_slice(state_ptr, 2 * BLAKE_STATE_WORDS);
+        let (message_read_records, message) = rt.mr_slice(message_ptr, 2 * BLAKE_BLOCK_WORDS);
+        let state: [u64; BLAKE_STATE_WORDS] =
+            core::array::from_fn(|i| u64_from_u32s(state[2 * i], state[2 * i + 1]));
+        let message: [u64; BLAKE_BLOCK_WORDS] =
+            core::array::from_fn(|i| u64_from_u32s(message[2 * i], message[2 * i + 1]));
+
+        let h = blake2b_compress(state, message);
+
+        // Increment the clk by 1 before writing because we read from memory at start_clk.
+        rt.clk += 1;
+        let h_words = h
+            .iter()
+            .flat_map(|word| [*word as u32, (*word >> 32) as u32])
+            .collect::<Vec<_>>();
+        let state_write_records = rt.mw_slice(state_ptr, &h_words);
+
+        let shard = rt.current_shard();
+        let channel = rt.current_channel();
+        let lookup_id = rt.syscall_lookup_id;
+        rt.record_mut()
+            .blake2b_compress_events
+            .push(Blake2bCompressEvent {
+                lookup_id,
+                shard,
+                channel,
+                clk: start_clk,
+                state_ptr,
+                message_ptr,
+                state,
+                message,
+                state_read_records,
+                message_read_records,
+                state_write_records,
+            });
+
+        None
+    }
+}
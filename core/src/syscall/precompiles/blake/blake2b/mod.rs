@@ -0,0 +1,186 @@
+mod air;
+mod columns;
+mod execute;
+mod trace;
+
+use serde::{Deserialize, Serialize};
+
+use super::{BLAKE_BLOCK_WORDS, BLAKE_G_INDICES, BLAKE_STATE_WORDS};
+use crate::runtime::{MemoryReadRecord, MemoryWriteRecord};
+
+/// The number of rounds of the BLAKE2b compression function.
+pub const BLAKE2B_NUM_ROUNDS: usize = 12;
+
+/// The message schedule, which selects the message words of each round.
+pub(crate) const BLAKE2B_SIGMA: [[usize; BLAKE_BLOCK_WORDS]; BLAKE2B_NUM_ROUNDS] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+];
+
+/// The rotations of the two halves of the G function.
+pub(crate) const BLAKE2B_G_ROTATIONS: [[usize; 2]; 2] = [[32, 24], [16, 63]];
+
+/// A BLAKE2b compression of a message block, in place.
+///
+/// The state is the initial working vector of the compression: the chaining value followed by
+/// the IV, with the offset counter and the final block flag already xored into its words 12 to
+/// 14. The chaining value in the first half of the state is overwritten with the next one, and
+/// the second half is left as is. Every 64-bit word is stored as its low and high 32-bit words.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Blake2bCompressEvent {
+    pub lookup_id: u128,
+    pub shard: u32,
+    pub channel: u8,
+    pub clk: u32,
+    pub state_ptr: u32,
+    pub message_ptr: u32,
+    pub state: [u64; BLAKE_STATE_WORDS],
+    pub message: [u64; BLAKE_BLOCK_WORDS],
+    pub state_read_records: Vec<MemoryReadRecord>,
+    pub message_read_records: Vec<MemoryReadRecord>,
+    pub state_write_records: Vec<MemoryWriteRecord>,
+}
+
+/// Implements the BLAKE2b compression function. The inputs to the syscall are a pointer to the
+/// 16-word state, whose first half is overwritten with the next chaining value, and a pointer to
+/// the 16-word message block. The words are 64-bit.
+///
+/// In the AIR, each compression takes [BLAKE2B_NUM_ROUNDS] rows, one per round.
+#[derive(Default)]
+pub struct Blake2bCompressChip;
+
+impl Blake2bCompressChip {
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+/// Mixes the words `a`, `b`, `c` and `d` of the state with the message words `mx` and `my`.
+fn g(state: &mut [u64; BLAKE_STATE_WORDS], [a, b, c, d]: [usize; 4], mx: u64, my: u64) {
+    for (m, [rotation_d, rotation_b]) in [mx, my].into_iter().zip(BLAKE2B_G_ROTATIONS) {
+        state[a] = state[a].wrapping_add(state[b]).wrapping_add(m);
+        state[d] = (state[d] ^ state[a]).rotate_right(rotation_d as u32);
+        state[c] = state[c].wrapping_add(state[d]);
+        state[b] = (state[b] ^ state[c]).rotate_right(rotation_b as u32);
+    }
+}
+
+/// Computes the next chaining value of the BLAKE2b compression function.
+pub fn blake2b_compress(
+    state: [u64; BLAKE_STATE_WORDS],
+    message: [u64; BLAKE_BLOCK_WORDS],
+) -> [u64; 8] {
+    let mut v = state;
+    for sigma in BLAKE2B_SIGMA {
+        for (i, indices) in BLAKE_G_INDICES.into_iter().enumerate() {
+            g(
+                &mut v,
+                indices,
+                message[sigma[2 * i]],
+                message[sigma[2 * i + 1]],
+            );
+        }
+    }
+    core::array::from_fn(|i| state[i] ^ v[i] ^ v[i + 8])
+}
+
+#[cfg(test)]
+pub mod blake2b_tests {
+    use crate::runtime::{Instruction, Opcode, Program, Runtime, SyscallCode};
+    use crate::stark::CpuProver;
+    use crate::utils::{run_test, setup_logger, SP1CoreOpts};
+
+    use super::super::BLAKE_STATE_WORDS;
+
+    /// The BLAKE2b IV.
+    const BLAKE2B_IV: [u64; 8] = [
+        0x6a09e667_f3bcc908,
+        0xbb67ae85_84caa73b,
+        0x3c6ef372_fe94f82b,
+        0xa54ff53a_5f1d36f1,
+        0x510e527f_ade682d1,
+        0x9b05688c_2b3e6c1f,
+        0x1f83d9ab_fb41bd6b,
+        0x5be0cd19_137e2179,
+    ];
+
+    /// The BLAKE2b-512 digest of `"abc"`.
+    const BLAKE2B_ABC: [u64; 8] = [
+        0x0d4d1c98_3fa580ba,
+        0xe9f6129f_b697276a,
+        0xb7c45a68_142f214c,
+        0xd1a2ffdb_6fbb124b,
+        0x2d79ab2a_39c5877d,
+        0x95cc3345_ded552c2,
+        0x5a92f1db_a88ad318,
+        0x239900d4_ed8623b9,
+    ];
+
+    /// A program hashing `"abc"` to a 64-byte digest, which is a single final block, with the
+    /// compress syscall.
+    pub fn blake2b_program() -> (Program, u32) {
+        let state_ptr = 0x1000;
+        let message_ptr = 0x2000;
+        let mut program = Program::new(
+            vec![
+                Instruction::new(
+                    Opcode::ADD,
+                    5,
+                    0,
+                    SyscallCode::BLAKE2B_COMPRESS as u32,
+                    false,
+                    true,
+                ),
+                Instruction::new(Opcode::ADD, 10, 0, state_ptr, false, true),
+                Instruction::new(Opcode::ADD, 11, 0, message_ptr, false, true),
+                Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            ],
+            0,
+            0,
+        );
+
+        // The parameter block sets a 64-byte digest and a fanout and depth of one.
+        let mut state = [0u64; BLAKE_STATE_WORDS];
+        state[..8].copy_from_slice(&BLAKE2B_IV);
+        state[0] ^= 0x01010040;
+        state[8..].copy_from_slice(&BLAKE2B_IV);
+        state[12] ^= 3;
+        state[14] = !state[14];
+        let mut message = [0u64; 16];
+        message[0] = u64::from_le_bytes(*b"abc\0\0\0\0\0");
+        for (ptr, words) in [(state_ptr, &state), (message_ptr, &message)] {
+            for (i, word) in words.iter().enumerate() {
+                let addr = ptr + 8 * i as u32;
+                program.memory_image.insert(addr, *word as u32);
+                program.memory_image.insert(addr + 4, (*word >> 32) as u32);
+            }
+        }
+        (program, state_ptr)
+    }
+
+    #[test]
+    fn test_blake2b_compress_prove() {
+        setup_logger();
+        let (program, state_ptr) = blake2b_program();
+
+        let mut runtime = Runtime::new(program.clone(), SP1CoreOpts::default());
+        runtime.run().unwrap();
+        for (i, word) in BLAKE2B_ABC.iter().enumerate() {
+            let addr = state_ptr + 8 * i as u32;
+            let value = (runtime.word(addr + 4) as u64) << 32 | runtime.word(addr) as u64;
+            assert_eq!(value, *word);
+        }
+
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+}
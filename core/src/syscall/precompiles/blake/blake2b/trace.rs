@@ -0,0 +1,225 @@
+use std::borrow::BorrowMut;
+
+use p3_field::PrimeField32;
+use p3_matrix::dense::RowMajorMatrix;
+use p3_matrix::Matrix;
+use p3_maybe_rayon::prelude::{ParallelIterator, ParallelSlice};
+
+use super::super::{BLAKE_G_INDICES, BLAKE_STATE_WORDS};
+use super::{
+    columns::{Blake2bCompressCols, Blake2bMixCols, NUM_BLAKE2B_COMPRESS_COLS},
+    Blake2bCompressChip, Blake2bCompressEvent, BLAKE2B_G_ROTATIONS, BLAKE2B_NUM_ROUNDS,
+    BLAKE2B_SIGMA,
+};
+use crate::{
+    air::MachineAir,
+    bytes::event::ByteRecord,
+    operations::XorOperation,
+    runtime::{ExecutionRecord, Program},
+    stark::MachineRecord,
+    syscall::precompiles::sha512::u64_to_words,
+    utils::pad_rows,
+};
+
+impl<F: PrimeField32> MachineAir<F> for Blake2bCompressChip {
+    type Record = ExecutionRecord;
+    type Program = Program;
+
+    fn name(&self) -> String {
+        "Blake2bCompress".to_string()
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        output: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let chunk_size = std::cmp::max(input.blake2b_compress_events.len() / num_cpus::get(), 1);
+
+        let rows_and_records = input
+            .blake2b_compress_events
+            .par_chunks(chunk_size)
+            .map(|events| {
+                let mut record = ExecutionRecord::default();
+                let mut new_byte_lookup_events = Vec::new();
+                let rows = events
+                    .iter()
+                    .flat_map(|event| event_to_rows(event, &mut new_byte_lookup_events))
+                    .collect::<Vec<_>>();
+                record.add_byte_lookup_events(new_byte_lookup_events);
+                (rows, record)
+            })
+            .collect::<Vec<_>>();
+
+        let mut rows = Vec::new();
+        for (mut chunk_rows, mut record) in rows_and_records {
+            rows.append(&mut chunk_rows);
+            output.append(&mut record);
+        }
+
+        pad_rows(&mut rows, || [F::zero(); NUM_BLAKE2B_COMPRESS_COLS]);
+
+        let mut trace = RowMajorMatrix::new(
+            rows.into_iter().flatten().collect::<Vec<_>>(),
+            NUM_BLAKE2B_COMPRESS_COLS,
+        );
+
+        // Write the nonces to the trace.
+        for i in 0..trace.height() {
+            let cols: &mut Blake2bCompressCols<F> = trace.values
+                [i * NUM_BLAKE2B_COMPRESS_COLS..(i + 1) * NUM_BLAKE2B_COMPRESS_COLS]
+                .borrow_mut();
+            cols.nonce = F::from_canonical_usize(i);
+        }
+
+        trace
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        !shard.blake2b_compress_events.is_empty()
+    }
+}
+
+/// Creates the rows of an event, one per round.
+fn event_to_rows<F: PrimeField32>(
+    event: &Blake2bCompressEvent,
+    record: &mut impl ByteRecord,
+) -> Vec<[F; NUM_BLAKE2B_COMPRESS_COLS]> {
+    let shard = event.shard;
+    let channel = event.channel;
+    let mut v = event.state;
+
+    let mut rows = Vec::with_capacity(BLAKE2B_NUM_ROUNDS);
+    for (round, sigma) in BLAKE2B_SIGMA.into_iter().enumerate() {
+        let mut row = [F::zero(); NUM_BLAKE2B_COMPRESS_COLS];
+        let cols: &mut Blake2bCompressCols<F> = row.as_mut_slice().borrow_mut();
+        let is_first_round = round == 0;
+        let is_last_round = round == BLAKE2B_NUM_ROUNDS - 1;
+
+        cols.shard = F::from_canonical_u32(shard);
+        cols.channel = F::from_canonical_u8(channel);
+        cols.clk = F::from_canonical_u32(event.clk);
+        cols.state_ptr = F::from_canonical_u32(event.state_ptr);
+        cols.message_ptr = F::from_canonical_u32(event.message_ptr);
+        cols.is_real = F::one();
+        cols.is_first_round = F::from_bool(is_first_round);
+        cols.is_last_round = F::from_bool(is_last_round);
+        cols.round = F::from_canonical_usize(round);
+        cols.round_inverse = (cols.round - F::from_canonical_usize(BLAKE2B_NUM_ROUNDS - 1))
+            .try_inverse()
+            .unwrap_or(F::zero());
+        cols.round_flags[round] = F::one();
+
+        if is_first_round {
+            for i in 0..2 * BLAKE_STATE_WORDS {
+                cols.state_read_mem[i].populate(channel, event.state_read_records[i], record);
+                cols.message_read_mem[i].populate(channel, event.message_read_records[i], record);
+            }
+        }
+
+        let round_message = sigma.map(|j| event.message[j]);
+        cols.h = core::array::from_fn(|i| u64_to_words(event.state[i]));
+        cols.state = v.map(u64_to_words);
+        cols.message = event.message.map(u64_to_words);
+        cols.round_message = round_message.map(u64_to_words);
+
+        for (i, indices) in BLAKE_G_INDICES.into_iter().enumerate() {
+            for (half, rotations) in BLAKE2B_G_ROTATIONS.into_iter().enumerate() {
+                cols.g[i][half].populate(
+                    record,
+                    shard,
+                    channel,
+                    &mut v,
+                    indices,
+                    round_message[2 * i + half],
+                    rotations,
+                );
+            }
+        }
+
+        if is_last_round {
+            for i in 0..8 {
+                let h_xor_v = populate_xor_u64(
+                    &mut cols.h_xor_v[i],
+                    record,
+                    shard,
+                    channel,
+                    event.state[i],
+                    v[i],
+                );
+                let output = populate_xor_u64(
+                    &mut cols.output[i],
+                    record,
+                    shard,
+                    channel,
+                    h_xor_v,
+                    v[i + 8],
+                );
+                for half in 0..2 {
+                    let write_record = event.state_write_records[2 * i + half];
+                    assert_eq!((output >> (32 * half)) as u32, write_record.value);
+                    cols.state_write_mem[2 * i + half].populate(channel, write_record, record);
+                }
+            }
+        }
+
+        rows.push(row);
+    }
+
+    rows
+}
+
+/// Populates the xor of two 64-bit words, one 32-bit half at a time.
+fn populate_xor_u64<F: PrimeField32>(
+    cols: &mut [XorOperation<F>; 2],
+    record: &mut impl ByteRecord,
+    shard: u32,
+    channel: u8,
+    x: u64,
+    y: u64,
+) -> u64 {
+    let lo = cols[0].populate(record, shard, channel, x as u32, y as u32);
+    let hi = cols[1].populate(record, shard, channel, (x >> 32) as u32, (y >> 32) as u32);
+    (hi as u64) << 32 | lo as u64
+}
+
+impl<F: PrimeField32> Blake2bMixCols<F> {
+    /// Populates one half of the G function over the words `a`, `b`, `c` and `d` of the state,
+    /// which is updated in place.
+    #[allow(clippy::too_many_arguments)]
+    pub fn populate(
+        &mut self,
+        record: &mut impl ByteRecord,
+        shard: u32,
+        channel: u8,
+        state: &mut [u64; BLAKE_STATE_WORDS],
+        [a, b, c, d]: [usize; 4],
+        m: u64,
+        [rotation_d, rotation_b]: [usize; 2],
+    ) {
+        state[a] = self
+            .a
+            .populate(record, shard, channel, &[state[a], state[b], m]);
+        let d_xor_a = populate_xor_u64(
+            &mut self.d_xor_a,
+            record,
+            shard,
+            channel,
+            state[d],
+            state[a],
+        );
+        state[d] = self.d.populate(record, shard, channel, d_xor_a, rotation_d);
+        state[c] = self
+            .c
+            .populate(record, shard, channel, &[state[c], state[d]]);
+        let b_xor_c = populate_xor_u64(
+            &mut self.b_xor_c,
+            record,
+            shard,
+            channel,
+            state[b],
+            state[c],
+        );
+        state[b] = self.b.populate(record, shard, channel, b_xor_c, rotation_b);
+    }
+}
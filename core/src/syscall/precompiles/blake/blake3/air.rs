@@ -0,0 +1,275 @@
+use core::borrow::Borrow;
+
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::{AbstractField, Field};
+use p3_matrix::Matrix;
+
+use super::super::{BLAKE_G_INDICES, BLAKE_STATE_WORDS};
+use super::{
+    columns::{Blake3CompressCols, Blake3MixCols, NUM_BLAKE3_COMPRESS_COLS},
+    Blake3CompressChip, BLAKE3_G_ROTATIONS, BLAKE3_MSG_PERMUTATION, BLAKE3_NUM_ROUNDS,
+};
+use crate::{
+    air::{BaseAirBuilder, SP1AirBuilder, Word, WordAirBuilder},
+    memory::MemoryCols,
+    operations::{AddOperation, FixedRotateRightOperation, XorOperation},
+    runtime::SyscallCode,
+    syscall::precompiles::eval_step_flags,
+};
+
+impl<F> BaseAir<F> for Blake3CompressChip {
+    fn width(&self) -> usize {
+        NUM_BLAKE3_COMPRESS_COLS
+    }
+}
+
+impl<AB> Air<AB> for Blake3CompressChip
+where
+    AB: SP1AirBuilder,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let (local, next) = (main.row_slice(0), main.row_slice(1));
+        let local: &Blake3CompressCols<AB::Var> = (*local).borrow();
+        let next: &Blake3CompressCols<AB::Var> = (*next).borrow();
+
+        // Constrain the incrementing nonce.
+        builder.when_first_row().assert_zero(local.nonce);
+        builder
+            .when_transition()
+            .assert_eq(local.nonce + AB::Expr::one(), next.nonce);
+
+        eval_step_flags(
+            builder,
+            BLAKE3_NUM_ROUNDS,
+            local.is_real,
+            local.is_first_round,
+            local.is_last_round,
+            local.round,
+            local.round_inverse,
+            next.is_real,
+            next.is_first_round,
+        );
+
+        // The first round starts from the state and the message in memory, and the chaining value
+        // is the first half of the state.
+        for i in 0..BLAKE_STATE_WORDS {
+            builder
+                .when(local.is_first_round)
+                .assert_word_eq(local.state[i], *local.state_read_mem[i].value());
+            builder
+                .when(local.is_first_round)
+                .assert_word_eq(local.message[i], *local.message_read_mem[i].value());
+        }
+        for i in 0..8 {
+            builder
+                .when(local.is_first_round)
+                .assert_word_eq(local.cv[i], local.state[i]);
+        }
+
+        // Apply the G functions of the round.
+        let mut v = local.state;
+        for (i, indices) in BLAKE_G_INDICES.into_iter().enumerate() {
+            for (half, rotations) in BLAKE3_G_ROTATIONS.into_iter().enumerate() {
+                Blake3MixCols::<AB::F>::eval(
+                    builder,
+                    local.g[i][half],
+                    &mut v,
+                    indices,
+                    local.message[2 * i + half],
+                    rotations,
+                    local.shard,
+                    local.channel,
+                    local.is_real,
+                );
+            }
+        }
+
+        // The next round of the same compression continues from the state after this round, with
+        // the permuted message.
+        {
+            let mut transition_builder = builder.when_transition();
+            let mut builder = transition_builder.when(local.is_real - local.is_last_round);
+            builder.assert_eq(next.round, local.round + AB::Expr::one());
+            builder.assert_all_eq(
+                [
+                    next.shard,
+                    next.channel,
+                    next.clk,
+                    next.state_ptr,
+                    next.message_ptr,
+                ],
+                [
+                    local.shard,
+                    local.channel,
+                    local.clk,
+                    local.state_ptr,
+                    local.message_ptr,
+                ],
+            );
+            for i in 0..8 {
+                builder.assert_word_eq(next.cv[i], local.cv[i]);
+            }
+            for i in 0..BLAKE_STATE_WORDS {
+                builder.assert_word_eq(next.state[i], v[i]);
+                builder.assert_word_eq(next.message[i], local.message[BLAKE3_MSG_PERMUTATION[i]]);
+            }
+        }
+
+        // The output of the last round is the xor of the two halves of the state, followed by the
+        // xor of the second half and the chaining value.
+        for i in 0..8 {
+            XorOperation::<AB::F>::eval(
+                builder,
+                v[i],
+                v[i + 8],
+                local.output[i],
+                local.shard,
+                local.channel,
+                local.is_last_round,
+            );
+            XorOperation::<AB::F>::eval(
+                builder,
+                v[i + 8],
+                local.cv[i],
+                local.output[i + 8],
+                local.shard,
+                local.channel,
+                local.is_last_round,
+            );
+        }
+        for i in 0..BLAKE_STATE_WORDS {
+            builder
+                .when(local.is_last_round)
+                .assert_word_eq(*local.state_write_mem[i].value(), local.output[i].value);
+        }
+
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.channel,
+            local.clk,
+            local.state_ptr,
+            &local.state_read_mem,
+            local.is_first_round,
+        );
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.channel,
+            local.clk,
+            local.message_ptr,
+            &local.message_read_mem,
+            local.is_first_round,
+        );
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.channel,
+            local.clk + AB::Expr::one(),
+            local.state_ptr,
+            &local.state_write_mem,
+            local.is_last_round,
+        );
+
+        builder.receive_syscall(
+            local.shard,
+            local.channel,
+            local.clk,
+            local.nonce,
+            AB::F::from_canonical_u32(SyscallCode::BLAKE3_COMPRESS.syscall_id()),
+            local.state_ptr,
+            local.message_ptr,
+            local.is_first_round,
+        );
+    }
+}
+
+impl<F: Field> Blake3MixCols<F> {
+    /// Evaluates one half of the G function over the words `a`, `b`, `c` and `d` of the state,
+    /// which is updated in place.
+    #[allow(clippy::too_many_arguments)]
+    pub fn eval<AB: SP1AirBuilder>(
+        builder: &mut AB,
+        cols: Blake3MixCols<AB::Var>,
+        state: &mut [Word<AB::Var>; BLAKE_STATE_WORDS],
+        [a, b, c, d]: [usize; 4],
+        m: Word<AB::Var>,
+        [rotation_d, rotation_b]: [usize; 2],
+        shard: AB::Var,
+        channel: AB::Var,
+        is_real: AB::Var,
+    ) {
+        // a = a + b + m.
+        AddOperation::<AB::F>::eval(
+            builder,
+            state[a],
+            state[b],
+            cols.a_plus_b,
+            shard,
+            channel,
+            is_real.into(),
+        );
+        AddOperation::<AB::F>::eval(
+            builder,
+            cols.a_plus_b.value,
+            m,
+            cols.a,
+            shard,
+            channel,
+            is_real.into(),
+        );
+        state[a] = cols.a.value;
+
+        // d = (d ^ a) >>> rotation_d.
+        XorOperation::<AB::F>::eval(
+            builder,
+            state[d],
+            state[a],
+            cols.d_xor_a,
+            shard,
+            channel,
+            is_real,
+        );
+        FixedRotateRightOperation::<AB::F>::eval(
+            builder,
+            cols.d_xor_a.value,
+            rotation_d,
+            cols.d,
+            shard,
+            channel,
+            is_real,
+        );
+        state[d] = cols.d.value;
+
+        // c = c + d.
+        AddOperation::<AB::F>::eval(
+            builder,
+            state[c],
+            state[d],
+            cols.c,
+            shard,
+            channel,
+            is_real.into(),
+        );
+        state[c] = cols.c.value;
+
+        // b = (b ^ c) >>> rotation_b.
+        XorOperation::<AB::F>::eval(
+            builder,
+            state[b],
+            state[c],
+            cols.b_xor_c,
+            shard,
+            channel,
+            is_real,
+        );
+        FixedRotateRightOperation::<AB::F>::eval(
+            builder,
+            cols.b_xor_c.value,
+            rotation_b,
+            cols.b,
+            shard,
+            channel,
+            is_real,
+        );
+        state[b] = cols.b.value;
+    }
+}
@@ -0,0 +1,65 @@
+use core::mem::size_of;
+
+use sp1_derive::AlignedBorrow;
+
+use super::super::{BLAKE_BLOCK_WORDS, BLAKE_NUM_G, BLAKE_STATE_WORDS};
+use crate::air::Word;
+use crate::memory::{MemoryReadCols, MemoryWriteCols};
+use crate::operations::{AddOperation, FixedRotateRightOperation, XorOperation};
+
+pub const NUM_BLAKE3_COMPRESS_COLS: usize = size_of::<Blake3CompressCols<u8>>();
+
+/// A set of columns for one half of the BLAKE3 G function, which computes
+/// `a = a + b + m`, `d = (d ^ a) >>> r1`, `c = c + d` and `b = (b ^ c) >>> r2`.
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct Blake3MixCols<T> {
+    pub a_plus_b: AddOperation<T>,
+    pub a: AddOperation<T>,
+    pub d_xor_a: XorOperation<T>,
+    pub d: FixedRotateRightOperation<T>,
+    pub c: AddOperation<T>,
+    pub b_xor_c: XorOperation<T>,
+    pub b: FixedRotateRightOperation<T>,
+}
+
+/// Blake3CompressCols is the column layout for the BLAKE3 compression function.
+///
+/// Each compression takes one row per round. The message is read in the order of the first round
+/// and permuted between rows.
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct Blake3CompressCols<T> {
+    pub shard: T,
+    pub channel: T,
+    pub clk: T,
+    pub nonce: T,
+    pub state_ptr: T,
+    pub message_ptr: T,
+
+    pub is_real: T,
+    pub is_first_round: T,
+    pub is_last_round: T,
+    pub round: T,
+    /// The inverse of `round - (BLAKE3_NUM_ROUNDS - 1)`, or zero on the last round.
+    pub round_inverse: T,
+
+    /// The state and the message, read at `clk` on the first round.
+    pub state_read_mem: [MemoryReadCols<T>; BLAKE_STATE_WORDS],
+    pub message_read_mem: [MemoryReadCols<T>; BLAKE_BLOCK_WORDS],
+    /// The output, written at `clk + 1` on the last round.
+    pub state_write_mem: [MemoryWriteCols<T>; BLAKE_STATE_WORDS],
+
+    /// The chaining value, which is the first half of the input state.
+    pub cv: [Word<T>; 8],
+    /// The state at the start of the round.
+    pub state: [Word<T>; BLAKE_STATE_WORDS],
+    /// The message, permuted for the round.
+    pub message: [Word<T>; BLAKE_BLOCK_WORDS],
+
+    /// The two halves of each G function of the round.
+    pub g: [[Blake3MixCols<T>; 2]; BLAKE_NUM_G],
+
+    /// The output, computed on the last round.
+    pub output: [XorOperation<T>; BLAKE_STATE_WORDS],
+}
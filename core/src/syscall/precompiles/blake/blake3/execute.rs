@@ -0,0 +1,55 @@
+use super::super::{BLAKE_BLOCK_WORDS, BLAKE_STATE_WORDS};
+use super::{blake3_compress, Blake3CompressChip, Blake3CompressEvent};
+use crate::{runtime::Syscall, syscall::precompiles::SyscallContext};
+
+impl Syscall for Blake3CompressChip {
+    fn num_extra_cycles(&self) -> u32 {
+        1
+    }
+
+    fn execute(&self, rt: &mut SyscallContext, arg1: u32, arg2: u32) -> Option<u32> {
+        let start_clk = rt.clk;
+        let state_ptr = arg1;
+        let message_ptr = arg2;
+
+        // The state and the message are both read at the start clk, so they must not overlap.
+        let state_end = state_ptr + 4 * BLAKE_STATE_WORDS as u32;
+        let message_end = message_ptr + 4 * BLAKE_BLOCK_WORDS as u32;
+        assert!(
+            state_end <= message_ptr || message_end <= state_ptr,
+            "the state and the message must not overlap"
+        );
+
+        let (state_read_records, state) = rt.mr_slice(state_ptr, BLAKE_STATE_WORDS);
+        let (message_read_records, message) = rt.mr_slice(message_ptr, BLAKE_BLOCK_WORDS);
+        let state: [u32; BLAKE_STATE_WORDS] = state.try_into().unwrap();
+        let message: [u32; BLAKE_BLOCK_WORDS] = message.try_into().unwrap();
+
+        let output = blake3_compress(state, message);
+
+        // Increment the clk by 1 before writing because we read from memory at start_clk.
+        rt.clk += 1;
+        let state_write_records = rt.mw_slice(state_ptr, &output);
+
+        let shard = rt.current_shard();
+        let channel = rt.current_channel();
+        let lookup_id = rt.syscall_lookup_id;
+        rt.record_mut()
+            .blake3_compress_events
+            .push(Blake3CompressEvent {
+                lookup_id,
+                shard,
+                channel,
+                clk: start_clk,
+                state_ptr,
+                message_ptr,
+                state,
+                message,
+                state_read_records,
+                message_read_records,
+                state_write_records,
+            });
+
+        None
+    }
+}
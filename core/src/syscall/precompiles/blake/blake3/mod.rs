@@ -0,0 +1,170 @@
+mod air;
+mod columns;
+mod execute;
+mod trace;
+
+use serde::{Deserialize, Serialize};
+
+use super::{BLAKE_BLOCK_WORDS, BLAKE_G_INDICES, BLAKE_STATE_WORDS};
+use crate::runtime::{MemoryReadRecord, MemoryWriteRecord};
+
+/// The number of rounds of the BLAKE3 compression function.
+pub const BLAKE3_NUM_ROUNDS: usize = 7;
+
+/// The permutation applied to the message words after each round.
+pub(crate) const BLAKE3_MSG_PERMUTATION: [usize; BLAKE_BLOCK_WORDS] =
+    [2, 6, 3, 10, 7, 0, 4, 13, 1, 11, 12, 5, 9, 14, 15, 8];
+
+/// The rotations of the two halves of the G function.
+pub(crate) const BLAKE3_G_ROTATIONS: [[usize; 2]; 2] = [[16, 12], [8, 7]];
+
+/// A BLAKE3 compression of a message block, in place.
+///
+/// The state holds the chaining value, the first four words of the IV, the low and high words of
+/// the counter, the block length and the flags, in that order. It is overwritten with the 16-word
+/// output of the compression, whose first half is the next chaining value.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Blake3CompressEvent {
+    pub lookup_id: u128,
+    pub shard: u32,
+    pub channel: u8,
+    pub clk: u32,
+    pub state_ptr: u32,
+    pub message_ptr: u32,
+    pub state: [u32; BLAKE_STATE_WORDS],
+    pub message: [u32; BLAKE_BLOCK_WORDS],
+    pub state_read_records: Vec<MemoryReadRecord>,
+    pub message_read_records: Vec<MemoryReadRecord>,
+    pub state_write_records: Vec<MemoryWriteRecord>,
+}
+
+/// Implements the BLAKE3 compression function. The inputs to the syscall are a pointer to the
+/// 16-word state, which is overwritten with the output, and a pointer to the 16-word message
+/// block.
+///
+/// In the AIR, each compression takes [BLAKE3_NUM_ROUNDS] rows, one per round.
+#[derive(Default)]
+pub struct Blake3CompressChip;
+
+impl Blake3CompressChip {
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+/// Mixes the words `a`, `b`, `c` and `d` of the state with the message words `mx` and `my`.
+fn g(state: &mut [u32; BLAKE_STATE_WORDS], [a, b, c, d]: [usize; 4], mx: u32, my: u32) {
+    for (m, [rotation_d, rotation_b]) in [mx, my].into_iter().zip(BLAKE3_G_ROTATIONS) {
+        state[a] = state[a].wrapping_add(state[b]).wrapping_add(m);
+        state[d] = (state[d] ^ state[a]).rotate_right(rotation_d as u32);
+        state[c] = state[c].wrapping_add(state[d]);
+        state[b] = (state[b] ^ state[c]).rotate_right(rotation_b as u32);
+    }
+}
+
+/// Computes the output of the BLAKE3 compression function.
+pub fn blake3_compress(
+    state: [u32; BLAKE_STATE_WORDS],
+    message: [u32; BLAKE_BLOCK_WORDS],
+) -> [u32; BLAKE_STATE_WORDS] {
+    let mut v = state;
+    let mut m = message;
+    for _ in 0..BLAKE3_NUM_ROUNDS {
+        for (i, indices) in BLAKE_G_INDICES.into_iter().enumerate() {
+            g(&mut v, indices, m[2 * i], m[2 * i + 1]);
+        }
+        m = BLAKE3_MSG_PERMUTATION.map(|j| m[j]);
+    }
+
+    let mut output = v;
+    for i in 0..8 {
+        output[i] = v[i] ^ v[i + 8];
+        output[i + 8] = v[i + 8] ^ state[i];
+    }
+    output
+}
+
+#[cfg(test)]
+pub mod blake3_tests {
+    use std::collections::BTreeMap;
+
+    use crate::runtime::{Instruction, Opcode, Program, Runtime, SyscallCode};
+    use crate::stark::CpuProver;
+    use crate::utils::{run_test, setup_logger, SP1CoreOpts};
+
+    use super::super::BLAKE_STATE_WORDS;
+
+    /// The BLAKE3 IV.
+    const BLAKE3_IV: [u32; 8] = [
+        0x6A09E667, 0xBB67AE85, 0x3C6EF372, 0xA54FF53A, 0x510E527F, 0x9B05688C, 0x1F83D9AB,
+        0x5BE0CD19,
+    ];
+
+    /// The BLAKE3 hash of `"abc"`.
+    const BLAKE3_ABC: [u8; 32] = [
+        0x64, 0x37, 0xb3, 0xac, 0x38, 0x46, 0x51, 0x33, 0xff, 0xb6, 0x3b, 0x75, 0x27, 0x3a, 0x8d,
+        0xb5, 0x48, 0xc5, 0x58, 0x46, 0x5d, 0x79, 0xdb, 0x03, 0xfd, 0x35, 0x9c, 0x6c, 0xd5, 0xbd,
+        0x9d, 0x85,
+    ];
+
+    /// The flags of the single block of a one-chunk input: CHUNK_START | CHUNK_END | ROOT.
+    const BLAKE3_SINGLE_BLOCK_FLAGS: u32 = 1 | 2 | 8;
+
+    /// A program hashing `"abc"`, which is a single block, with the compress syscall.
+    pub fn blake3_program() -> (Program, u32) {
+        let state_ptr = 0x1000;
+        let message_ptr = 0x2000;
+        let mut program = Program::new(
+            vec![
+                Instruction::new(
+                    Opcode::ADD,
+                    5,
+                    0,
+                    SyscallCode::BLAKE3_COMPRESS as u32,
+                    false,
+                    true,
+                ),
+                Instruction::new(Opcode::ADD, 10, 0, state_ptr, false, true),
+                Instruction::new(Opcode::ADD, 11, 0, message_ptr, false, true),
+                Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            ],
+            0,
+            0,
+        );
+
+        let mut state = [0u32; BLAKE_STATE_WORDS];
+        state[..8].copy_from_slice(&BLAKE3_IV);
+        state[8..12].copy_from_slice(&BLAKE3_IV[..4]);
+        state[14] = 3;
+        state[15] = BLAKE3_SINGLE_BLOCK_FLAGS;
+        let mut message = [0u32; 16];
+        message[0] = u32::from_le_bytes(*b"abc\0");
+        program.memory_image = state
+            .iter()
+            .enumerate()
+            .map(|(i, word)| (state_ptr + 4 * i as u32, *word))
+            .chain(
+                message
+                    .iter()
+                    .enumerate()
+                    .map(|(i, word)| (message_ptr + 4 * i as u32, *word)),
+            )
+            .collect::<BTreeMap<_, _>>();
+        (program, state_ptr)
+    }
+
+    #[test]
+    fn test_blake3_compress_prove() {
+        setup_logger();
+        let (program, state_ptr) = blake3_program();
+
+        let mut runtime = Runtime::new(program.clone(), SP1CoreOpts::default());
+        runtime.run().unwrap();
+        let digest = (0..8)
+            .flat_map(|i| runtime.word(state_ptr + 4 * i).to_le_bytes())
+            .collect::<Vec<_>>();
+        assert_eq!(digest, BLAKE3_ABC);
+
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+}
@@ -0,0 +1,183 @@
+use std::borrow::BorrowMut;
+
+use p3_field::PrimeField32;
+use p3_matrix::dense::RowMajorMatrix;
+use p3_matrix::Matrix;
+use p3_maybe_rayon::prelude::{ParallelIterator, ParallelSlice};
+
+use super::super::{BLAKE_G_INDICES, BLAKE_STATE_WORDS};
+use super::{
+    columns::{Blake3CompressCols, Blake3MixCols, NUM_BLAKE3_COMPRESS_COLS},
+    Blake3CompressChip, Blake3CompressEvent, BLAKE3_G_ROTATIONS, BLAKE3_MSG_PERMUTATION,
+    BLAKE3_NUM_ROUNDS,
+};
+use crate::{
+    air::{MachineAir, Word},
+    bytes::event::ByteRecord,
+    runtime::{ExecutionRecord, Program},
+    stark::MachineRecord,
+    utils::pad_rows,
+};
+
+impl<F: PrimeField32> MachineAir<F> for Blake3CompressChip {
+    type Record = ExecutionRecord;
+    type Program = Program;
+
+    fn name(&self) -> String {
+        "Blake3Compress".to_string()
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        output: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let chunk_size = std::cmp::max(input.blake3_compress_events.len() / num_cpus::get(), 1);
+
+        let rows_and_records = input
+            .blake3_compress_events
+            .par_chunks(chunk_size)
+            .map(|events| {
+                let mut record = ExecutionRecord::default();
+                let mut new_byte_lookup_events = Vec::new();
+                let rows = events
+                    .iter()
+                    .flat_map(|event| event_to_rows(event, &mut new_byte_lookup_events))
+                    .collect::<Vec<_>>();
+                record.add_byte_lookup_events(new_byte_lookup_events);
+                (rows, record)
+            })
+            .collect::<Vec<_>>();
+
+        let mut rows = Vec::new();
+        for (mut chunk_rows, mut record) in rows_and_records {
+            rows.append(&mut chunk_rows);
+            output.append(&mut record);
+        }
+
+        pad_rows(&mut rows, || [F::zero(); NUM_BLAKE3_COMPRESS_COLS]);
+
+        let mut trace = RowMajorMatrix::new(
+            rows.into_iter().flatten().collect::<Vec<_>>(),
+            NUM_BLAKE3_COMPRESS_COLS,
+        );
+
+        // Write the nonces to the trace.
+        for i in 0..trace.height() {
+            let cols: &mut Blake3CompressCols<F> = trace.values
+                [i * NUM_BLAKE3_COMPRESS_COLS..(i + 1) * NUM_BLAKE3_COMPRESS_COLS]
+                .borrow_mut();
+            cols.nonce = F::from_canonical_usize(i);
+        }
+
+        trace
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        !shard.blake3_compress_events.is_empty()
+    }
+}
+
+/// Creates the rows of an event, one per round.
+fn event_to_rows<F: PrimeField32>(
+    event: &Blake3CompressEvent,
+    record: &mut impl ByteRecord,
+) -> Vec<[F; NUM_BLAKE3_COMPRESS_COLS]> {
+    let shard = event.shard;
+    let channel = event.channel;
+    let mut v = event.state;
+    let mut m = event.message;
+
+    let mut rows = Vec::with_capacity(BLAKE3_NUM_ROUNDS);
+    for round in 0..BLAKE3_NUM_ROUNDS {
+        let mut row = [F::zero(); NUM_BLAKE3_COMPRESS_COLS];
+        let cols: &mut Blake3CompressCols<F> = row.as_mut_slice().borrow_mut();
+        let is_first_round = round == 0;
+        let is_last_round = round == BLAKE3_NUM_ROUNDS - 1;
+
+        cols.shard = F::from_canonical_u32(shard);
+        cols.channel = F::from_canonical_u8(channel);
+        cols.clk = F::from_canonical_u32(event.clk);
+        cols.state_ptr = F::from_canonical_u32(event.state_ptr);
+        cols.message_ptr = F::from_canonical_u32(event.message_ptr);
+        cols.is_real = F::one();
+        cols.is_first_round = F::from_bool(is_first_round);
+        cols.is_last_round = F::from_bool(is_last_round);
+        cols.round = F::from_canonical_usize(round);
+        cols.round_inverse = (cols.round - F::from_canonical_usize(BLAKE3_NUM_ROUNDS - 1))
+            .try_inverse()
+            .unwrap_or(F::zero());
+
+        if is_first_round {
+            for i in 0..BLAKE_STATE_WORDS {
+                cols.state_read_mem[i].populate(channel, event.state_read_records[i], record);
+                cols.message_read_mem[i].populate(channel, event.message_read_records[i], record);
+            }
+        }
+
+        cols.cv = core::array::from_fn(|i| Word::from(event.state[i]));
+        cols.state = v.map(Word::from);
+        cols.message = m.map(Word::from);
+
+        for (i, indices) in BLAKE_G_INDICES.into_iter().enumerate() {
+            for (half, rotations) in BLAKE3_G_ROTATIONS.into_iter().enumerate() {
+                cols.g[i][half].populate(
+                    record,
+                    shard,
+                    channel,
+                    &mut v,
+                    indices,
+                    m[2 * i + half],
+                    rotations,
+                );
+            }
+        }
+        m = BLAKE3_MSG_PERMUTATION.map(|j| m[j]);
+
+        if is_last_round {
+            for i in 0..8 {
+                cols.output[i].populate(record, shard, channel, v[i], v[i + 8]);
+                cols.output[i + 8].populate(record, shard, channel, v[i + 8], event.state[i]);
+            }
+            for i in 0..BLAKE_STATE_WORDS {
+                let write_record = event.state_write_records[i];
+                assert_eq!(cols.output[i].value.to_u32(), write_record.value);
+                cols.state_write_mem[i].populate(channel, write_record, record);
+            }
+        }
+
+        rows.push(row);
+    }
+
+    rows
+}
+
+impl<F: PrimeField32> Blake3MixCols<F> {
+    /// Populates one half of the G function over the words `a`, `b`, `c` and `d` of the state,
+    /// which is updated in place.
+    #[allow(clippy::too_many_arguments)]
+    pub fn populate(
+        &mut self,
+        record: &mut impl ByteRecord,
+        shard: u32,
+        channel: u8,
+        state: &mut [u32; BLAKE_STATE_WORDS],
+        [a, b, c, d]: [usize; 4],
+        m: u32,
+        [rotation_d, rotation_b]: [usize; 2],
+    ) {
+        let a_plus_b = self
+            .a_plus_b
+            .populate(record, shard, channel, state[a], state[b]);
+        state[a] = self.a.populate(record, shard, channel, a_plus_b, m);
+        let d_xor_a = self
+            .d_xor_a
+            .populate(record, shard, channel, state[d], state[a]);
+        state[d] = self.d.populate(record, shard, channel, d_xor_a, rotation_d);
+        state[c] = self.c.populate(record, shard, channel, state[c], state[d]);
+        let b_xor_c = self
+            .b_xor_c
+            .populate(record, shard, channel, state[b], state[c]);
+        state[b] = self.b.populate(record, shard, channel, b_xor_c, rotation_b);
+    }
+}
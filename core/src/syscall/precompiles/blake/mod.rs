@@ -0,0 +1,27 @@
+mod blake2b;
+mod blake3;
+
+pub use blake2b::*;
+pub use blake3::*;
+
+/// The number of words of the state of the BLAKE2b and BLAKE3 compression functions.
+pub const BLAKE_STATE_WORDS: usize = 16;
+
+/// The number of words of a message block of the BLAKE2b and BLAKE3 compression functions.
+pub const BLAKE_BLOCK_WORDS: usize = 16;
+
+/// The number of G functions in a round.
+pub(crate) const BLAKE_NUM_G: usize = 8;
+
+/// The words of the state mixed by each G function of a round: the four columns, then the four
+/// diagonals. The i-th G function mixes in the message words `2i` and `2i + 1` of the round.
+pub(crate) const BLAKE_G_INDICES: [[usize; 4]; BLAKE_NUM_G] = [
+    [0, 4, 8, 12],
+    [1, 5, 9, 13],
+    [2, 6, 10, 14],
+    [3, 7, 11, 15],
+    [0, 5, 10, 15],
+    [1, 6, 11, 12],
+    [2, 7, 8, 13],
+    [3, 4, 9, 14],
+];
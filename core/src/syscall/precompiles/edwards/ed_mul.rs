@@ -0,0 +1,754 @@
+use core::borrow::{Borrow, BorrowMut};
+use core::mem::size_of;
+use std::marker::PhantomData;
+
+use num::{BigUint, Zero};
+use p3_air::AirBuilder;
+use p3_air::{Air, BaseAir};
+use p3_field::AbstractField;
+use p3_field::PrimeField32;
+use p3_matrix::dense::RowMajorMatrix;
+use p3_matrix::Matrix;
+use p3_maybe_rayon::prelude::{ParallelIterator, ParallelSlice};
+use sp1_derive::AlignedBorrow;
+
+use super::WORDS_CURVE_POINT;
+use crate::air::BaseAirBuilder;
+use crate::air::MachineAir;
+use crate::air::SP1AirBuilder;
+use crate::bytes::event::ByteRecord;
+use crate::memory::value_as_limbs;
+use crate::memory::MemoryReadCols;
+use crate::memory::MemoryWriteCols;
+use crate::operations::field::field_den::FieldDenCols;
+use crate::operations::field::field_inner_product::FieldInnerProductCols;
+use crate::operations::field::field_op::FieldOpCols;
+use crate::operations::field::field_op::FieldOperation;
+use crate::operations::field::params::FieldParameters;
+use crate::runtime::ExecutionRecord;
+use crate::runtime::Program;
+use crate::runtime::Syscall;
+use crate::runtime::SyscallCode;
+use crate::stark::MachineRecord;
+use crate::syscall::precompiles::SyscallContext;
+use crate::syscall::precompiles::{
+    create_ec_mul_event, eval_scalar_bits, eval_step_flags, AffinePointCols, ECMulEvent,
+};
+use crate::utils::ec::edwards::ed25519::Ed25519BaseField;
+use crate::utils::ec::edwards::EdwardsParameters;
+use crate::utils::ec::scalar_mul::{
+    scalar_windows_be, SCALAR_MUL_NUM_WINDOWS, SCALAR_MUL_SCALAR_BITS, SCALAR_MUL_SCALAR_WORDS,
+    SCALAR_MUL_WINDOW_BITS,
+};
+use crate::utils::ec::utils::biguint_to_bits_le;
+use crate::utils::ec::AffinePoint;
+use crate::utils::ec::EllipticCurve;
+use crate::utils::pad_rows;
+
+pub const NUM_ED_MUL_COLS: usize = size_of::<EdMulAssignCols<u8>>();
+
+/// A set of columns to add two points on an Edwards curve.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct EdAddCols<T> {
+    pub(crate) x3_numerator: FieldInnerProductCols<T, Ed25519BaseField>,
+    pub(crate) y3_numerator: FieldInnerProductCols<T, Ed25519BaseField>,
+    pub(crate) x1_mul_y1: FieldOpCols<T, Ed25519BaseField>,
+    pub(crate) x2_mul_y2: FieldOpCols<T, Ed25519BaseField>,
+    pub(crate) f: FieldOpCols<T, Ed25519BaseField>,
+    pub(crate) d_mul_f: FieldOpCols<T, Ed25519BaseField>,
+    pub(crate) x3_ins: FieldDenCols<T, Ed25519BaseField>,
+    pub(crate) y3_ins: FieldDenCols<T, Ed25519BaseField>,
+}
+
+/// A set of columns for one step of a fixed-window scalar multiplication on an Edwards curve.
+///
+/// A scalar multiplication takes [SCALAR_MUL_NUM_WINDOWS] consecutive rows, one per window of the
+/// scalar starting from the most significant one. Each step computes `4 * acc + q`, where `q` is
+/// the multiple of `P` selected by the window. The first step instead uses its two doublings to
+/// compute the window table `[2P, 3P]`, and its result is `q`.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct EdMulAssignCols<T> {
+    pub is_real: T,
+    pub shard: T,
+    pub channel: T,
+    pub clk: T,
+    pub nonce: T,
+    pub p_ptr: T,
+    pub scalar_ptr: T,
+
+    /// Whether this row is the first step of a scalar multiplication.
+    pub is_first_step: T,
+    /// Whether this row is the last step of a scalar multiplication.
+    pub is_last_step: T,
+    /// The index of the step within the scalar multiplication.
+    pub step: T,
+    /// The inverse of `step - (SCALAR_MUL_NUM_WINDOWS - 1)`, or zero on the last step.
+    pub step_inverse: T,
+
+    /// The point and the scalar, read on the first step.
+    pub p_access: [MemoryReadCols<T>; WORDS_CURVE_POINT],
+    pub scalar_access: [MemoryReadCols<T>; SCALAR_MUL_SCALAR_WORDS],
+    /// The product, written on the last step.
+    pub result_access: [MemoryWriteCols<T>; WORDS_CURVE_POINT],
+
+    /// The bits of the scalar, shifted up by [SCALAR_MUL_WINDOW_BITS] at every step so that the
+    /// current window is held by the most significant bits.
+    pub scalar_bits: [T; SCALAR_MUL_SCALAR_BITS],
+
+    /// The window table.
+    pub p: AffinePointCols<T, Ed25519BaseField>,
+    pub p_times_2: AffinePointCols<T, Ed25519BaseField>,
+    pub p_times_3: AffinePointCols<T, Ed25519BaseField>,
+
+    /// The accumulator before this step.
+    pub acc: AffinePointCols<T, Ed25519BaseField>,
+    /// The right-hand side of `second_double`, which is `P` on the first step.
+    pub second_double_rhs: AffinePointCols<T, Ed25519BaseField>,
+    /// The multiple of `P` selected by the current window.
+    pub q: AffinePointCols<T, Ed25519BaseField>,
+    pub first_double: EdAddCols<T>,
+    pub second_double: EdAddCols<T>,
+    pub add: EdAddCols<T>,
+    /// The accumulator after this step.
+    pub result: AffinePointCols<T, Ed25519BaseField>,
+}
+
+#[derive(Default)]
+pub struct EdMulAssignChip<E> {
+    _marker: PhantomData<E>,
+}
+
+impl<E: EllipticCurve + EdwardsParameters> EdMulAssignChip<E> {
+    pub const fn new() -> Self {
+        Self {
+            _marker: PhantomData,
+        }
+    }
+
+    /// Populates the columns of a step, and returns the accumulator after the step.
+    #[allow(clippy::too_many_arguments)]
+    fn populate_step<F: PrimeField32>(
+        record: &mut impl ByteRecord,
+        shard: u32,
+        channel: u8,
+        cols: &mut EdMulAssignCols<F>,
+        table: &[AffinePoint<E>],
+        acc: &AffinePoint<E>,
+        window: usize,
+        is_first_step: bool,
+    ) -> AffinePoint<E> {
+        cols.p.populate(&table[0]);
+        cols.p_times_2.populate(&table[1]);
+        cols.p_times_3.populate(&table[2]);
+        cols.acc.populate(acc);
+
+        let acc_times_2 = cols.first_double.populate(record, shard, channel, acc, acc);
+        let second_double_rhs = if is_first_step {
+            table[0].clone()
+        } else {
+            acc_times_2.clone()
+        };
+        cols.second_double_rhs.populate(&second_double_rhs);
+        let acc_times_4 =
+            cols.second_double
+                .populate(record, shard, channel, &acc_times_2, &second_double_rhs);
+
+        let q = match window {
+            0 => E::ec_neutral().unwrap(),
+            _ => table[window - 1].clone(),
+        };
+        cols.q.populate(&q);
+        let sum = cols.add.populate(record, shard, channel, &acc_times_4, &q);
+
+        let result = if is_first_step { q } else { sum };
+        cols.result.populate(&result);
+        result
+    }
+
+    /// Creates the rows of an event.
+    fn event_to_rows<F: PrimeField32>(
+        &self,
+        event: &ECMulEvent,
+        record: &mut impl ByteRecord,
+    ) -> Vec<[F; NUM_ED_MUL_COLS]> {
+        let p = AffinePoint::<E>::from_words_le(&event.p);
+        let table = p.window_table();
+        let scalar = BigUint::from_slice(&event.scalar);
+        let mut scalar_bits = biguint_to_bits_le(&scalar, SCALAR_MUL_SCALAR_BITS);
+
+        let mut acc = p;
+        let mut rows = Vec::with_capacity(SCALAR_MUL_NUM_WINDOWS);
+        for (step, window) in scalar_windows_be(&scalar).into_iter().enumerate() {
+            let mut row = [F::zero(); NUM_ED_MUL_COLS];
+            let cols: &mut EdMulAssignCols<F> = row.as_mut_slice().borrow_mut();
+            let is_first_step = step == 0;
+            let is_last_step = step == SCALAR_MUL_NUM_WINDOWS - 1;
+
+            cols.is_real = F::one();
+            cols.shard = F::from_canonical_u32(event.shard);
+            cols.channel = F::from_canonical_u8(event.channel);
+            cols.clk = F::from_canonical_u32(event.clk);
+            cols.p_ptr = F::from_canonical_u32(event.p_ptr);
+            cols.scalar_ptr = F::from_canonical_u32(event.scalar_ptr);
+            cols.is_first_step = F::from_bool(is_first_step);
+            cols.is_last_step = F::from_bool(is_last_step);
+            cols.step = F::from_canonical_usize(step);
+            cols.step_inverse = (cols.step - F::from_canonical_usize(SCALAR_MUL_NUM_WINDOWS - 1))
+                .try_inverse()
+                .unwrap_or(F::zero());
+
+            if is_first_step {
+                for i in 0..WORDS_CURVE_POINT {
+                    cols.p_access[i].populate(event.channel, event.p_read_records[i], record);
+                }
+                for i in 0..SCALAR_MUL_SCALAR_WORDS {
+                    cols.scalar_access[i].populate(
+                        event.channel,
+                        event.scalar_read_records[i],
+                        record,
+                    );
+                }
+            }
+            if is_last_step {
+                for i in 0..WORDS_CURVE_POINT {
+                    cols.result_access[i].populate(event.channel, event.p_write_records[i], record);
+                }
+            }
+
+            cols.scalar_bits = scalar_bits
+                .iter()
+                .map(|bit| F::from_bool(*bit))
+                .collect::<Vec<_>>()
+                .try_into()
+                .unwrap();
+            scalar_bits.rotate_right(SCALAR_MUL_WINDOW_BITS);
+            scalar_bits[..SCALAR_MUL_WINDOW_BITS].fill(false);
+
+            acc = Self::populate_step(
+                record,
+                event.shard,
+                event.channel,
+                cols,
+                &table,
+                &acc,
+                window,
+                is_first_step,
+            );
+            rows.push(row);
+        }
+        assert_eq!(acc.to_words_le(), event.result);
+
+        rows
+    }
+}
+
+impl<F: PrimeField32> EdAddCols<F> {
+    /// Populates the columns to add `p` and `q`, and returns the sum.
+    pub fn populate<E: EllipticCurve + EdwardsParameters>(
+        &mut self,
+        record: &mut impl ByteRecord,
+        shard: u32,
+        channel: u8,
+        p: &AffinePoint<E>,
+        q: &AffinePoint<E>,
+    ) -> AffinePoint<E> {
+        let x3_numerator = self.x3_numerator.populate(
+            record,
+            shard,
+            channel,
+            &[p.x.clone(), q.x.clone()],
+            &[q.y.clone(), p.y.clone()],
+        );
+        let y3_numerator = self.y3_numerator.populate(
+            record,
+            shard,
+            channel,
+            &[p.y.clone(), p.x.clone()],
+            &[q.y.clone(), q.x.clone()],
+        );
+        let x1_mul_y1 =
+            self.x1_mul_y1
+                .populate(record, shard, channel, &p.x, &p.y, FieldOperation::Mul);
+        let x2_mul_y2 =
+            self.x2_mul_y2
+                .populate(record, shard, channel, &q.x, &q.y, FieldOperation::Mul);
+        let f = self.f.populate(
+            record,
+            shard,
+            channel,
+            &x1_mul_y1,
+            &x2_mul_y2,
+            FieldOperation::Mul,
+        );
+
+        let d = E::d_biguint();
+        let d_mul_f = self
+            .d_mul_f
+            .populate(record, shard, channel, &f, &d, FieldOperation::Mul);
+
+        let x3 = self
+            .x3_ins
+            .populate(record, shard, channel, &x3_numerator, &d_mul_f, true);
+        let y3 = self
+            .y3_ins
+            .populate(record, shard, channel, &y3_numerator, &d_mul_f, false);
+        AffinePoint::new(x3, y3)
+    }
+}
+
+impl<V: Copy> EdAddCols<V> {
+    /// Evaluates the addition of `p` and `q`. The sum is held by the results of `x3_ins` and
+    /// `y3_ins`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn eval<AB: SP1AirBuilder<Var = V>, E: EdwardsParameters>(
+        &self,
+        builder: &mut AB,
+        p: &AffinePointCols<V, Ed25519BaseField>,
+        q: &AffinePointCols<V, Ed25519BaseField>,
+        shard: impl Into<AB::Expr> + Clone,
+        channel: impl Into<AB::Expr> + Clone,
+        is_real: impl Into<AB::Expr> + Clone,
+    ) where
+        V: Into<AB::Expr>,
+    {
+        let (x1, y1, x2, y2) = (p.x, p.y, q.x, q.y);
+
+        // x3_numerator = x1 * y2 + x2 * y1.
+        self.x3_numerator.eval(
+            builder,
+            &[x1, x2],
+            &[y2, y1],
+            shard.clone(),
+            channel.clone(),
+            is_real.clone(),
+        );
+
+        // y3_numerator = y1 * y2 + x1 * x2.
+        self.y3_numerator.eval(
+            builder,
+            &[y1, x1],
+            &[y2, x2],
+            shard.clone(),
+            channel.clone(),
+            is_real.clone(),
+        );
+
+        // f = x1 * x2 * y1 * y2.
+        self.x1_mul_y1.eval(
+            builder,
+            &x1,
+            &y1,
+            FieldOperation::Mul,
+            shard.clone(),
+            channel.clone(),
+            is_real.clone(),
+        );
+        self.x2_mul_y2.eval(
+            builder,
+            &x2,
+            &y2,
+            FieldOperation::Mul,
+            shard.clone(),
+            channel.clone(),
+            is_real.clone(),
+        );
+        self.f.eval(
+            builder,
+            &self.x1_mul_y1.result,
+            &self.x2_mul_y2.result,
+            FieldOperation::Mul,
+            shard.clone(),
+            channel.clone(),
+            is_real.clone(),
+        );
+
+        // d * f.
+        let d_const = E::BaseField::to_limbs_field::<AB::Expr, _>(&E::d_biguint());
+        self.d_mul_f.eval(
+            builder,
+            &self.f.result,
+            &d_const,
+            FieldOperation::Mul,
+            shard.clone(),
+            channel.clone(),
+            is_real.clone(),
+        );
+
+        // x3 = x3_numerator / (1 + d * f).
+        self.x3_ins.eval(
+            builder,
+            &self.x3_numerator.result,
+            &self.d_mul_f.result,
+            true,
+            shard.clone(),
+            channel.clone(),
+            is_real.clone(),
+        );
+
+        // y3 = y3_numerator / (1 - d * f).
+        self.y3_ins.eval(
+            builder,
+            &self.y3_numerator.result,
+            &self.d_mul_f.result,
+            false,
+            shard,
+            channel,
+            is_real,
+        );
+    }
+
+    /// Returns the sum as point columns.
+    pub fn result(&self) -> AffinePointCols<V, Ed25519BaseField> {
+        AffinePointCols {
+            x: self.x3_ins.result,
+            y: self.y3_ins.result,
+        }
+    }
+}
+
+impl<E: EllipticCurve + EdwardsParameters> Syscall for EdMulAssignChip<E> {
+    fn num_extra_cycles(&self) -> u32 {
+        1
+    }
+
+    fn execute(&self, rt: &mut SyscallContext, arg1: u32, arg2: u32) -> Option<u32> {
+        // The neutral element of a twisted Edwards curve is affine, so the product always exists.
+        let event = create_ec_mul_event::<E>(rt, arg1, arg2)
+            .expect("the product of an Edwards curve point is affine");
+        rt.record_mut().ed_mul_events.push(event);
+        None
+    }
+}
+
+impl<F: PrimeField32, E: EllipticCurve + EdwardsParameters> MachineAir<F> for EdMulAssignChip<E> {
+    type Record = ExecutionRecord;
+
+    type Program = Program;
+
+    fn name(&self) -> String {
+        "EdMulAssign".to_string()
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        output: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let chunk_size = std::cmp::max(input.ed_mul_events.len() / num_cpus::get(), 1);
+
+        let rows_and_records = input
+            .ed_mul_events
+            .par_chunks(chunk_size)
+            .map(|events| {
+                let mut record = ExecutionRecord::default();
+                let mut new_byte_lookup_events = Vec::new();
+                let rows = events
+                    .iter()
+                    .flat_map(|event| self.event_to_rows(event, &mut new_byte_lookup_events))
+                    .collect::<Vec<_>>();
+                record.add_byte_lookup_events(new_byte_lookup_events);
+                (rows, record)
+            })
+            .collect::<Vec<_>>();
+
+        let mut rows = Vec::new();
+        for (mut chunk_rows, mut record) in rows_and_records {
+            rows.append(&mut chunk_rows);
+            output.append(&mut record);
+        }
+
+        pad_rows(&mut rows, || {
+            let mut row = [F::zero(); NUM_ED_MUL_COLS];
+            let cols: &mut EdMulAssignCols<F> = row.as_mut_slice().borrow_mut();
+            let zero = AffinePoint::<E>::new(BigUint::zero(), BigUint::zero());
+            let table = vec![zero.clone(); 3];
+            Self::populate_step(&mut vec![], 0, 0, cols, &table, &zero, 0, false);
+            row
+        });
+
+        // Convert the trace to a row major matrix.
+        let mut trace = RowMajorMatrix::new(
+            rows.into_iter().flatten().collect::<Vec<_>>(),
+            NUM_ED_MUL_COLS,
+        );
+
+        // Write the nonces to the trace.
+        for i in 0..trace.height() {
+            let cols: &mut EdMulAssignCols<F> =
+                trace.values[i * NUM_ED_MUL_COLS..(i + 1) * NUM_ED_MUL_COLS].borrow_mut();
+            cols.nonce = F::from_canonical_usize(i);
+        }
+
+        trace
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        !shard.ed_mul_events.is_empty()
+    }
+}
+
+impl<F, E: EllipticCurve + EdwardsParameters> BaseAir<F> for EdMulAssignChip<E> {
+    fn width(&self) -> usize {
+        NUM_ED_MUL_COLS
+    }
+}
+
+impl<AB, E: EllipticCurve + EdwardsParameters> Air<AB> for EdMulAssignChip<E>
+where
+    AB: SP1AirBuilder,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let local = main.row_slice(0);
+        let local: &EdMulAssignCols<AB::Var> = (*local).borrow();
+        let next = main.row_slice(1);
+        let next: &EdMulAssignCols<AB::Var> = (*next).borrow();
+
+        // Constrain the incrementing nonce.
+        builder.when_first_row().assert_zero(local.nonce);
+        builder
+            .when_transition()
+            .assert_eq(local.nonce + AB::Expr::one(), next.nonce);
+
+        eval_step_flags(
+            builder,
+            SCALAR_MUL_NUM_WINDOWS,
+            local.is_real,
+            local.is_first_step,
+            local.is_last_step,
+            local.step,
+            local.step_inverse,
+            next.is_real,
+            next.is_first_step,
+        );
+
+        // The columns which stay the same across the steps of a scalar multiplication.
+        let carried = |cols: &EdMulAssignCols<AB::Var>| {
+            [
+                cols.shard,
+                cols.channel,
+                cols.clk,
+                cols.p_ptr,
+                cols.scalar_ptr,
+            ]
+            .into_iter()
+            .chain(cols.p.limbs())
+            .chain(cols.p_times_2.limbs())
+            .chain(cols.p_times_3.limbs())
+            .collect::<Vec<_>>()
+        };
+
+        // Constrain the transition to the next step of the same scalar multiplication.
+        {
+            let mut transition_builder = builder.when_transition();
+            let mut builder = transition_builder.when(local.is_real - local.is_last_step);
+            builder.assert_eq(next.step, local.step + AB::Expr::one());
+            builder.assert_all_eq(carried(next), carried(local));
+            builder.assert_all_eq(next.acc.limbs(), local.result.limbs());
+            builder.assert_all_eq(
+                next.scalar_bits[SCALAR_MUL_WINDOW_BITS..].iter().copied(),
+                local.scalar_bits[..SCALAR_MUL_SCALAR_BITS - SCALAR_MUL_WINDOW_BITS]
+                    .iter()
+                    .copied(),
+            );
+        }
+
+        // Read the point and the scalar on the first step.
+        eval_scalar_bits(
+            builder,
+            local.is_first_step,
+            &local.scalar_access,
+            &local.scalar_bits,
+        );
+        builder
+            .when(local.is_first_step)
+            .assert_all_eq(local.p.limbs(), value_as_limbs(&local.p_access));
+        builder
+            .when(local.is_first_step)
+            .assert_all_eq(local.acc.limbs(), local.p.limbs());
+        builder
+            .when(local.is_first_step)
+            .assert_all_eq(local.p_times_2.limbs(), local.first_double.result().limbs());
+        builder.when(local.is_first_step).assert_all_eq(
+            local.p_times_3.limbs(),
+            local.second_double.result().limbs(),
+        );
+
+        // The first step computes `[2P, 3P]` with the doublings, and the other steps compute
+        // `4 * acc`.
+        let is_first_step: AB::Expr = local.is_first_step.into();
+        let not_first_step = AB::Expr::one() - is_first_step.clone();
+        builder.assert_all_eq(
+            local.second_double_rhs.limbs(),
+            local
+                .p
+                .limbs()
+                .into_iter()
+                .zip(local.first_double.result().limbs())
+                .map(|(p, acc_times_2)| {
+                    is_first_step.clone() * p + not_first_step.clone() * acc_times_2
+                }),
+        );
+        local.first_double.eval::<AB, E>(
+            builder,
+            &local.acc,
+            &local.acc,
+            local.shard,
+            local.channel,
+            local.is_real,
+        );
+        local.second_double.eval::<AB, E>(
+            builder,
+            &local.first_double.result(),
+            &local.second_double_rhs,
+            local.shard,
+            local.channel,
+            local.is_real,
+        );
+
+        // Select `q = window * P` with the two bits of the current window.
+        let (neutral_x, neutral_y) = E::neutral();
+        let neutral = E::BaseField::to_limbs_field::<AB::Expr, _>(&neutral_x)
+            .0
+            .into_iter()
+            .chain(E::BaseField::to_limbs_field::<AB::Expr, _>(&neutral_y).0)
+            .collect::<Vec<_>>();
+        let bit_low = local.scalar_bits[SCALAR_MUL_SCALAR_BITS - 2];
+        let bit_high = local.scalar_bits[SCALAR_MUL_SCALAR_BITS - 1];
+        let q = (0..neutral.len())
+            .map(|i| {
+                let o = neutral[i].clone();
+                let p = local.p.limbs()[i];
+                let p_times_2 = local.p_times_2.limbs()[i];
+                let p_times_3 = local.p_times_3.limbs()[i];
+                o.clone()
+                    + (p - o.clone()) * bit_low
+                    + (p_times_2 - o.clone()) * bit_high
+                    + (p_times_3 - p_times_2 - p + o) * bit_low * bit_high
+            })
+            .collect::<Vec<_>>();
+        builder.assert_all_eq(local.q.limbs(), q);
+        local.add.eval::<AB, E>(
+            builder,
+            &local.second_double.result(),
+            &local.q,
+            local.shard,
+            local.channel,
+            local.is_real,
+        );
+
+        // The result is `q` on the first step, and `4 * acc + q` otherwise.
+        builder.assert_all_eq(
+            local.result.limbs(),
+            local
+                .q
+                .limbs()
+                .into_iter()
+                .zip(local.add.result().limbs())
+                .map(|(q, sum)| is_first_step.clone() * q + not_first_step.clone() * sum),
+        );
+        builder
+            .when(local.is_last_step)
+            .assert_all_eq(local.result.limbs(), value_as_limbs(&local.result_access));
+
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.channel,
+            local.clk,
+            local.p_ptr,
+            &local.p_access,
+            local.is_first_step,
+        );
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.channel,
+            local.clk,
+            local.scalar_ptr,
+            &local.scalar_access,
+            local.is_first_step,
+        );
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.channel,
+            local.clk + AB::F::from_canonical_u32(1),
+            local.p_ptr,
+            &local.result_access,
+            local.is_last_step,
+        );
+
+        builder.receive_syscall(
+            local.shard,
+            local.channel,
+            local.clk,
+            local.nonce,
+            AB::F::from_canonical_u32(SyscallCode::ED_MUL.syscall_id()),
+            local.p_ptr,
+            local.scalar_ptr,
+            local.is_first_step,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use num::{BigUint, Num};
+
+    use crate::{
+        runtime::{Instruction, Opcode, Program, Runtime, SyscallCode},
+        stark::CpuProver,
+        utils::{
+            ec::{edwards::ed25519::Ed25519, EllipticCurve},
+            run_test, setup_logger, SP1CoreOpts,
+        },
+    };
+
+    #[test]
+    fn test_ed_mul_simple() {
+        setup_logger();
+        let p = Ed25519::ec_generator();
+        let p_ptr = 0x1000;
+        let scalar_ptr = 0x2000;
+        // Leading and inner zero windows exercise the neutral accumulator.
+        let scalar = BigUint::from_str_radix(
+            "0000000000000000c0ffee00000000000000000000000000000000000000beef",
+            16,
+        )
+        .unwrap();
+
+        let mut scalar_words = scalar.to_u32_digits();
+        scalar_words.resize(8, 0);
+        let memory_image = p
+            .to_words_le()
+            .into_iter()
+            .enumerate()
+            .map(|(i, word)| (p_ptr + 4 * i as u32, word))
+            .chain(
+                scalar_words
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, word)| (scalar_ptr + 4 * i as u32, word)),
+            )
+            .collect::<BTreeMap<_, _>>();
+        let instructions = vec![
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::ED_MUL as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, p_ptr, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, scalar_ptr, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+        ];
+        let mut program = Program::new(instructions, 0, 0);
+        program.memory_image = memory_image;
+
+        let mut runtime = Runtime::new(program.clone(), SP1CoreOpts::default());
+        runtime.run().unwrap();
+        let result = (0..16)
+            .map(|i| runtime.word(p_ptr + 4 * i))
+            .collect::<Vec<_>>();
+        assert_eq!(result, p.scalar_mul(&scalar).to_words_le());
+
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+}
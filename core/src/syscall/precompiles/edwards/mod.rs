@@ -1,8 +1,10 @@
 mod ed_add;
 mod ed_decompress;
+mod ed_mul;
 
 pub use ed_add::*;
 pub use ed_decompress::*;
+pub use ed_mul::*;
 
 use crate::operations::field::params::{NumLimbs, NumWords};
 use crate::utils::ec::edwards::ed25519::Ed25519BaseField;
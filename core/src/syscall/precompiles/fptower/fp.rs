@@ -0,0 +1,499 @@
+use core::borrow::{Borrow, BorrowMut};
+use core::mem::size_of;
+use std::marker::PhantomData;
+
+use generic_array::GenericArray;
+use num::{BigUint, Zero};
+use p3_air::AirBuilder;
+use p3_air::{Air, BaseAir};
+use p3_field::AbstractField;
+use p3_field::PrimeField32;
+use p3_matrix::dense::RowMajorMatrix;
+use p3_matrix::Matrix;
+use serde::{Deserialize, Serialize};
+use sp1_derive::AlignedBorrow;
+use typenum::Unsigned;
+
+use super::{FieldType, FpOpField};
+use crate::air::{BaseAirBuilder, MachineAir, Polynomial, SP1AirBuilder};
+use crate::bytes::event::ByteRecord;
+use crate::memory::{value_as_limbs, MemoryReadCols, MemoryWriteCols};
+use crate::operations::field::field_op::{FieldOpCols, FieldOperation};
+use crate::operations::field::params::{Limbs, NumLimbs, NumWords};
+use crate::operations::field::range::FieldLtCols;
+use crate::runtime::{ExecutionRecord, Program, Syscall, SyscallCode};
+use crate::runtime::{MemoryReadRecord, MemoryWriteRecord};
+use crate::syscall::precompiles::SyscallContext;
+use crate::utils::{limbs_from_prev_access, pad_rows, words_to_bytes_le_vec};
+
+pub const fn num_fp_cols<P: FpOpField>() -> usize {
+    size_of::<FpOpCols<u8, P>>()
+}
+
+/// An `Fp` arithmetic event, computing `x = x op y mod p` in place.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FpOpEvent {
+    pub lookup_id: u128,
+    pub shard: u32,
+    pub channel: u8,
+    pub clk: u32,
+    pub x_ptr: u32,
+    pub x: Vec<u32>,
+    pub y_ptr: u32,
+    pub y: Vec<u32>,
+    pub op: FieldOperation,
+    pub x_memory_records: Vec<MemoryWriteRecord>,
+    pub y_memory_records: Vec<MemoryReadRecord>,
+}
+
+/// The syscall computing `x = x op y mod p` for the base field `P`.
+///
+/// Each operation has its own syscall code, but all of them are proven by a single [FpOpChip].
+/// Both operands are expected to be reduced modulo `p`.
+pub struct FpOpSyscall<P> {
+    op: FieldOperation,
+    _marker: PhantomData<P>,
+}
+
+impl<P> FpOpSyscall<P> {
+    pub const fn new(op: FieldOperation) -> Self {
+        Self {
+            op,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<P: FpOpField> Syscall for FpOpSyscall<P> {
+    fn execute(&self, rt: &mut SyscallContext, arg1: u32, arg2: u32) -> Option<u32> {
+        let clk = rt.clk;
+        let x_ptr = arg1;
+        if x_ptr % 4 != 0 {
+            panic!();
+        }
+        let y_ptr = arg2;
+        if y_ptr % 4 != 0 {
+            panic!();
+        }
+
+        let num_words = <P as NumWords>::WordsFieldElement::USIZE;
+
+        // We can read a slice_unsafe for x because we write the result to x later.
+        let x = rt.slice_unsafe(x_ptr, num_words);
+        let (y_memory_records, y) = rt.mr_slice(y_ptr, num_words);
+
+        // Reduce the inputs first, so that the subtraction can't underflow.
+        let modulus = P::modulus();
+        let a = BigUint::from_bytes_le(&words_to_bytes_le_vec(&x)) % &modulus;
+        let b = BigUint::from_bytes_le(&words_to_bytes_le_vec(&y)) % &modulus;
+        let result = match self.op {
+            FieldOperation::Add => (a + b) % &modulus,
+            FieldOperation::Sub => (&modulus + a - b) % &modulus,
+            FieldOperation::Mul => (a * b) % &modulus,
+            FieldOperation::Div => panic!("Unsupported operation"),
+        };
+        let mut result_words = result.to_u32_digits();
+        result_words.resize(num_words, 0);
+
+        // Increment clk so that the write is not at the same cycle as the read.
+        rt.clk += 1;
+        let x_memory_records = rt.mw_slice(x_ptr, &result_words);
+
+        let event = FpOpEvent {
+            lookup_id: rt.syscall_lookup_id,
+            shard: rt.current_shard(),
+            channel: rt.current_channel(),
+            clk,
+            x_ptr,
+            x,
+            y_ptr,
+            y,
+            op: self.op,
+            x_memory_records,
+            y_memory_records,
+        };
+        match P::FIELD_TYPE {
+            FieldType::Bn254 => rt.record_mut().bn254_fp_events.push(event),
+            FieldType::Bls12381 => rt.record_mut().bls12381_fp_events.push(event),
+        }
+
+        None
+    }
+
+    fn num_extra_cycles(&self) -> u32 {
+        1
+    }
+}
+
+/// A set of columns for the `Fp` add, sub and mul operations.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct FpOpCols<T, P: FpOpField> {
+    pub is_real: T,
+    pub shard: T,
+    pub channel: T,
+    pub nonce: T,
+    pub clk: T,
+    pub is_add: T,
+    pub is_sub: T,
+    pub is_mul: T,
+    pub x_ptr: T,
+    pub y_ptr: T,
+    pub x_access: GenericArray<MemoryWriteCols<T>, P::WordsFieldElement>,
+    pub y_access: GenericArray<MemoryReadCols<T>, P::WordsFieldElement>,
+    pub(crate) output: FieldOpCols<T, P>,
+    pub(crate) output_range_check: FieldLtCols<T, P>,
+}
+
+/// A chip proving `Fp` add, sub and mul for the base field `P`.
+#[derive(Default)]
+pub struct FpOpChip<P> {
+    _marker: PhantomData<P>,
+}
+
+impl<P: FpOpField> FpOpChip<P> {
+    pub const fn new() -> Self {
+        Self {
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<F: PrimeField32, P: FpOpField> MachineAir<F> for FpOpChip<P> {
+    type Record = ExecutionRecord;
+    type Program = Program;
+
+    fn name(&self) -> String {
+        match P::FIELD_TYPE {
+            FieldType::Bn254 => "Bn254FpOpAssign".to_string(),
+            FieldType::Bls12381 => "Bls12381FpOpAssign".to_string(),
+        }
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        output: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let events = match P::FIELD_TYPE {
+            FieldType::Bn254 => &input.bn254_fp_events,
+            FieldType::Bls12381 => &input.bls12381_fp_events,
+        };
+
+        let mut rows = Vec::new();
+        let mut new_byte_lookup_events = Vec::new();
+
+        for event in events {
+            let mut row = vec![F::zero(); num_fp_cols::<P>()];
+            let cols: &mut FpOpCols<F, P> = row.as_mut_slice().borrow_mut();
+
+            let x = BigUint::from_bytes_le(&words_to_bytes_le_vec(&event.x));
+            let y = BigUint::from_bytes_le(&words_to_bytes_le_vec(&event.y));
+
+            cols.is_real = F::one();
+            cols.shard = F::from_canonical_u32(event.shard);
+            cols.channel = F::from_canonical_u8(event.channel);
+            cols.clk = F::from_canonical_u32(event.clk);
+            cols.is_add = F::from_bool(event.op == FieldOperation::Add);
+            cols.is_sub = F::from_bool(event.op == FieldOperation::Sub);
+            cols.is_mul = F::from_bool(event.op == FieldOperation::Mul);
+            cols.x_ptr = F::from_canonical_u32(event.x_ptr);
+            cols.y_ptr = F::from_canonical_u32(event.y_ptr);
+
+            let result = cols.output.populate(
+                &mut new_byte_lookup_events,
+                event.shard,
+                event.channel,
+                &x,
+                &y,
+                event.op,
+            );
+            cols.output_range_check.populate(
+                &mut new_byte_lookup_events,
+                event.shard,
+                event.channel,
+                &result,
+                &P::modulus(),
+            );
+
+            for i in 0..cols.y_access.len() {
+                cols.y_access[i].populate(
+                    event.channel,
+                    event.y_memory_records[i],
+                    &mut new_byte_lookup_events,
+                );
+            }
+            for i in 0..cols.x_access.len() {
+                cols.x_access[i].populate(
+                    event.channel,
+                    event.x_memory_records[i],
+                    &mut new_byte_lookup_events,
+                );
+            }
+
+            rows.push(row);
+        }
+        output.add_byte_lookup_events(new_byte_lookup_events);
+
+        pad_rows(&mut rows, || {
+            let mut row = vec![F::zero(); num_fp_cols::<P>()];
+            let cols: &mut FpOpCols<F, P> = row.as_mut_slice().borrow_mut();
+            let zero = BigUint::zero();
+            cols.output
+                .populate(&mut vec![], 0, 0, &zero, &zero, FieldOperation::Mul);
+            row
+        });
+
+        // Convert the trace to a row major matrix.
+        let mut trace = RowMajorMatrix::new(
+            rows.into_iter().flatten().collect::<Vec<_>>(),
+            num_fp_cols::<P>(),
+        );
+
+        // Write the nonces to the trace.
+        for i in 0..trace.height() {
+            let cols: &mut FpOpCols<F, P> =
+                trace.values[i * num_fp_cols::<P>()..(i + 1) * num_fp_cols::<P>()].borrow_mut();
+            cols.nonce = F::from_canonical_usize(i);
+        }
+
+        trace
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        match P::FIELD_TYPE {
+            FieldType::Bn254 => !shard.bn254_fp_events.is_empty(),
+            FieldType::Bls12381 => !shard.bls12381_fp_events.is_empty(),
+        }
+    }
+}
+
+impl<F, P: FpOpField> BaseAir<F> for FpOpChip<P> {
+    fn width(&self) -> usize {
+        num_fp_cols::<P>()
+    }
+}
+
+impl<AB, P: FpOpField> Air<AB> for FpOpChip<P>
+where
+    AB: SP1AirBuilder,
+    Limbs<AB::Var, <P as NumLimbs>::Limbs>: Copy,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let local = main.row_slice(0);
+        let local: &FpOpCols<AB::Var, P> = (*local).borrow();
+        let next = main.row_slice(1);
+        let next: &FpOpCols<AB::Var, P> = (*next).borrow();
+
+        // Constrain the incrementing nonce.
+        builder.when_first_row().assert_zero(local.nonce);
+        builder
+            .when_transition()
+            .assert_eq(local.nonce + AB::Expr::one(), next.nonce);
+
+        // Exactly one operation is selected on real rows, and none on padding rows.
+        builder.assert_bool(local.is_real);
+        builder.assert_bool(local.is_add);
+        builder.assert_bool(local.is_sub);
+        builder.assert_bool(local.is_mul);
+        builder.assert_eq(local.is_add + local.is_sub + local.is_mul, local.is_real);
+
+        let p_x: Limbs<AB::Var, <P as NumLimbs>::Limbs> = limbs_from_prev_access(&local.x_access);
+        let p_y: Limbs<AB::Var, <P as NumLimbs>::Limbs> = limbs_from_prev_access(&local.y_access);
+        let p_modulus = P::modulus_field_iter::<AB::F>()
+            .map(AB::Expr::from)
+            .collect::<Polynomial<_>>();
+
+        local.output.eval_variable(
+            builder,
+            &p_x,
+            &p_y,
+            &p_modulus,
+            local.is_add,
+            local.is_sub,
+            local.is_mul,
+            AB::Expr::zero(),
+            local.shard,
+            local.channel,
+            local.is_real,
+        );
+
+        // Verify that the result is below the modulus.
+        local.output_range_check.eval(
+            builder,
+            &local.output.result,
+            &p_modulus,
+            local.shard,
+            local.channel,
+            local.is_real,
+        );
+
+        // Assert that the result is written to x.
+        builder
+            .when(local.is_real)
+            .assert_all_eq(local.output.result, value_as_limbs(&local.x_access));
+
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.channel,
+            local.clk.into(),
+            local.y_ptr,
+            &local.y_access,
+            local.is_real,
+        );
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.channel,
+            local.clk + AB::F::from_canonical_u32(1),
+            local.x_ptr,
+            &local.x_access,
+            local.is_real,
+        );
+
+        // The syscall id is determined by the selected operation.
+        let (add_id, sub_id, mul_id) = match P::FIELD_TYPE {
+            FieldType::Bn254 => (
+                SyscallCode::BN254_FP_ADD,
+                SyscallCode::BN254_FP_SUB,
+                SyscallCode::BN254_FP_MUL,
+            ),
+            FieldType::Bls12381 => (
+                SyscallCode::BLS12381_FP_ADD,
+                SyscallCode::BLS12381_FP_SUB,
+                SyscallCode::BLS12381_FP_MUL,
+            ),
+        };
+        let syscall_id_felt = local.is_add * AB::F::from_canonical_u32(add_id.syscall_id())
+            + local.is_sub * AB::F::from_canonical_u32(sub_id.syscall_id())
+            + local.is_mul * AB::F::from_canonical_u32(mul_id.syscall_id());
+
+        builder.receive_syscall(
+            local.shard,
+            local.channel,
+            local.clk,
+            local.nonce,
+            syscall_id_felt,
+            local.x_ptr,
+            local.y_ptr,
+            local.is_real,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::borrow::BorrowMut;
+
+    use num::bigint::RandBigInt;
+    use num::{BigUint, One};
+    use p3_baby_bear::BabyBear;
+    use rand::thread_rng;
+
+    use super::super::tests::{debug_tampered_trace, execute, syscall_program, to_words};
+    use super::FpOpCols;
+    use crate::operations::field::field_op::FieldOperation;
+    use crate::operations::field::params::FieldParameters;
+    use crate::runtime::SyscallCode;
+    use crate::stark::CpuProver;
+    use crate::utils::ec::weierstrass::bls12_381::Bls12381BaseField;
+    use crate::utils::ec::weierstrass::bn254::Bn254BaseField;
+    use crate::utils::{run_test, setup_logger};
+
+    #[test]
+    fn test_fp_ops() {
+        setup_logger();
+        let mut rng = thread_rng();
+        let fields = [
+            (
+                Bn254BaseField::modulus(),
+                8,
+                [
+                    SyscallCode::BN254_FP_ADD,
+                    SyscallCode::BN254_FP_SUB,
+                    SyscallCode::BN254_FP_MUL,
+                ],
+            ),
+            (
+                Bls12381BaseField::modulus(),
+                12,
+                [
+                    SyscallCode::BLS12381_FP_ADD,
+                    SyscallCode::BLS12381_FP_SUB,
+                    SyscallCode::BLS12381_FP_MUL,
+                ],
+            ),
+        ];
+
+        let mut calls = Vec::new();
+        let mut expected = Vec::new();
+        let mut num_words = Vec::new();
+        for (modulus, words, [add, sub, mul]) in fields {
+            let a = rng.gen_biguint_below(&modulus);
+            let b = rng.gen_biguint_below(&modulus);
+            let results: [(SyscallCode, BigUint); 3] = [
+                (add, (&a + &b) % &modulus),
+                (sub, (&modulus + &a - &b) % &modulus),
+                (mul, (&a * &b) % &modulus),
+            ];
+            for (code, result) in results {
+                calls.push((code, to_words(&a, words), to_words(&b, words)));
+                expected.push(to_words(&result, words));
+                num_words.push(words);
+            }
+        }
+
+        let (program, x_ptrs) = syscall_program(&calls);
+        assert_eq!(execute(&program, &x_ptrs, &num_words), expected);
+
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+
+    #[test]
+    fn test_fp_sub_non_canonical() {
+        setup_logger();
+        // `b` is above the modulus, so `modulus + a - b` would underflow without reducing it.
+        let modulus = Bn254BaseField::modulus();
+        let a = BigUint::from(1u32);
+        let b = &modulus + 2u32;
+        let calls = [(SyscallCode::BN254_FP_SUB, to_words(&a, 8), to_words(&b, 8))];
+        let expected = to_words(&(&modulus - 1u32), 8);
+
+        let (program, x_ptrs) = syscall_program(&calls);
+        assert_eq!(execute(&program, &x_ptrs, &[8]), vec![expected]);
+
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+
+    #[test]
+    fn test_fp_non_canonical_result() {
+        setup_logger();
+        // `a + b` is above the modulus, so it is only canonical once reduced.
+        let modulus = Bn254BaseField::modulus();
+        let a = &modulus - 1u32;
+        let b = BigUint::from(2u32);
+        let calls = [(SyscallCode::BN254_FP_ADD, to_words(&a, 8), to_words(&b, 8))];
+        let (program, _) = syscall_program(&calls);
+        debug_tampered_trace(program.clone(), "Bn254FpOpAssign", |_| {}).unwrap();
+
+        // Write the unreduced sum with a zero carry, which satisfies the field operation itself.
+        let error = debug_tampered_trace(program, "Bn254FpOpAssign", |row| {
+            let cols: &mut FpOpCols<BabyBear, Bn254BaseField> = row.borrow_mut();
+            let result = cols.output.populate_with_modulus(
+                &mut vec![],
+                0,
+                0,
+                &a,
+                &b,
+                &(BigUint::one() << 256),
+                FieldOperation::Add,
+            );
+            assert!(result >= modulus);
+            for (access, word) in cols.x_access.iter_mut().zip(to_words(&result, 8)) {
+                access.access.value = word.into();
+            }
+        })
+        .unwrap_err();
+        assert_eq!(error.row, 0);
+    }
+}
@@ -0,0 +1,514 @@
+use core::borrow::{Borrow, BorrowMut};
+use core::mem::size_of;
+use std::marker::PhantomData;
+
+use generic_array::GenericArray;
+use num::{BigUint, Zero};
+use p3_air::AirBuilder;
+use p3_air::{Air, BaseAir};
+use p3_field::AbstractField;
+use p3_field::PrimeField32;
+use p3_matrix::dense::RowMajorMatrix;
+use p3_matrix::Matrix;
+use serde::{Deserialize, Serialize};
+use sp1_derive::AlignedBorrow;
+use typenum::Unsigned;
+
+use super::{FieldType, FpOpField};
+use crate::air::{BaseAirBuilder, MachineAir, Polynomial, SP1AirBuilder};
+use crate::bytes::event::ByteRecord;
+use crate::memory::{value_as_limbs, MemoryReadCols, MemoryWriteCols};
+use crate::operations::field::field_op::{FieldOpCols, FieldOperation};
+use crate::operations::field::params::{Limbs, NumLimbs, NumWords};
+use crate::operations::field::range::FieldLtCols;
+use crate::runtime::{ExecutionRecord, Program, Syscall, SyscallCode};
+use crate::runtime::{MemoryReadRecord, MemoryWriteRecord};
+use crate::syscall::precompiles::SyscallContext;
+use crate::utils::{limbs_from_prev_access, pad_rows, words_to_bytes_le_vec};
+
+pub const fn num_fp2_addsub_cols<P: FpOpField>() -> usize {
+    size_of::<Fp2AddSubAssignCols<u8, P>>()
+}
+
+/// An `Fp2` addition or subtraction event, computing `x = x op y` in place.
+///
+/// An `Fp2` element `c0 + c1 * u` is laid out in memory as `c0` followed by `c1`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Fp2AddSubEvent {
+    pub lookup_id: u128,
+    pub shard: u32,
+    pub channel: u8,
+    pub clk: u32,
+    pub op: FieldOperation,
+    pub x_ptr: u32,
+    pub x: Vec<u32>,
+    pub y_ptr: u32,
+    pub y: Vec<u32>,
+    pub x_memory_records: Vec<MemoryWriteRecord>,
+    pub y_memory_records: Vec<MemoryReadRecord>,
+}
+
+/// The syscall computing `x = x op y` for the quadratic extension of the base field `P`.
+///
+/// Both the addition and subtraction syscalls are proven by a single [Fp2AddSubAssignChip]. All
+/// coefficients are expected to be reduced modulo `p`.
+pub struct Fp2AddSubSyscall<P> {
+    op: FieldOperation,
+    _marker: PhantomData<P>,
+}
+
+impl<P> Fp2AddSubSyscall<P> {
+    pub const fn new(op: FieldOperation) -> Self {
+        Self {
+            op,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<P: FpOpField> Syscall for Fp2AddSubSyscall<P> {
+    fn execute(&self, rt: &mut SyscallContext, arg1: u32, arg2: u32) -> Option<u32> {
+        let clk = rt.clk;
+        let x_ptr = arg1;
+        if x_ptr % 4 != 0 {
+            panic!();
+        }
+        let y_ptr = arg2;
+        if y_ptr % 4 != 0 {
+            panic!();
+        }
+
+        let num_words = <P as NumWords>::WordsCurvePoint::USIZE;
+
+        let x = rt.slice_unsafe(x_ptr, num_words);
+        let (y_memory_records, y) = rt.mr_slice(y_ptr, num_words);
+
+        let modulus = P::modulus();
+        let (x_c0, x_c1) = x.split_at(num_words / 2);
+        let (y_c0, y_c1) = y.split_at(num_words / 2);
+        let mut result_words = Vec::with_capacity(num_words);
+        for (a, b) in [(x_c0, y_c0), (x_c1, y_c1)] {
+            // Reduce the inputs first, so that the subtraction can't underflow.
+            let a = BigUint::from_bytes_le(&words_to_bytes_le_vec(a)) % &modulus;
+            let b = BigUint::from_bytes_le(&words_to_bytes_le_vec(b)) % &modulus;
+            let c = match self.op {
+                FieldOperation::Add => (a + b) % &modulus,
+                FieldOperation::Sub => (&modulus + a - b) % &modulus,
+                _ => panic!("Unsupported operation"),
+            };
+            let mut c_words = c.to_u32_digits();
+            c_words.resize(num_words / 2, 0);
+            result_words.extend(c_words);
+        }
+
+        // Increment clk so that the write is not at the same cycle as the read.
+        rt.clk += 1;
+        let x_memory_records = rt.mw_slice(x_ptr, &result_words);
+
+        let event = Fp2AddSubEvent {
+            lookup_id: rt.syscall_lookup_id,
+            shard: rt.current_shard(),
+            channel: rt.current_channel(),
+            clk,
+            op: self.op,
+            x_ptr,
+            x,
+            y_ptr,
+            y,
+            x_memory_records,
+            y_memory_records,
+        };
+        match P::FIELD_TYPE {
+            FieldType::Bn254 => rt.record_mut().bn254_fp2_addsub_events.push(event),
+            FieldType::Bls12381 => rt.record_mut().bls12381_fp2_addsub_events.push(event),
+        }
+
+        None
+    }
+
+    fn num_extra_cycles(&self) -> u32 {
+        1
+    }
+}
+
+/// A set of columns for the `Fp2` add and sub operations.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct Fp2AddSubAssignCols<T, P: FpOpField> {
+    pub is_real: T,
+    pub shard: T,
+    pub channel: T,
+    pub nonce: T,
+    pub clk: T,
+    pub is_add: T,
+    pub x_ptr: T,
+    pub y_ptr: T,
+    pub x_access: GenericArray<MemoryWriteCols<T>, P::WordsCurvePoint>,
+    pub y_access: GenericArray<MemoryReadCols<T>, P::WordsCurvePoint>,
+    pub(crate) c0: FieldOpCols<T, P>,
+    pub(crate) c1: FieldOpCols<T, P>,
+    pub(crate) c0_range_check: FieldLtCols<T, P>,
+    pub(crate) c1_range_check: FieldLtCols<T, P>,
+}
+
+/// A chip proving `Fp2` add and sub for the quadratic extension of the base field `P`.
+#[derive(Default)]
+pub struct Fp2AddSubAssignChip<P> {
+    _marker: PhantomData<P>,
+}
+
+impl<P: FpOpField> Fp2AddSubAssignChip<P> {
+    pub const fn new() -> Self {
+        Self {
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<F: PrimeField32, P: FpOpField> MachineAir<F> for Fp2AddSubAssignChip<P> {
+    type Record = ExecutionRecord;
+    type Program = Program;
+
+    fn name(&self) -> String {
+        match P::FIELD_TYPE {
+            FieldType::Bn254 => "Bn254Fp2AddSubAssign".to_string(),
+            FieldType::Bls12381 => "Bls12381Fp2AddSubAssign".to_string(),
+        }
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        output: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let events = match P::FIELD_TYPE {
+            FieldType::Bn254 => &input.bn254_fp2_addsub_events,
+            FieldType::Bls12381 => &input.bls12381_fp2_addsub_events,
+        };
+
+        let mut rows = Vec::new();
+        let mut new_byte_lookup_events = Vec::new();
+
+        for event in events {
+            let mut row = vec![F::zero(); num_fp2_addsub_cols::<P>()];
+            let cols: &mut Fp2AddSubAssignCols<F, P> = row.as_mut_slice().borrow_mut();
+
+            let num_words = <P as NumWords>::WordsFieldElement::USIZE;
+            let x_c0 = BigUint::from_bytes_le(&words_to_bytes_le_vec(&event.x[..num_words]));
+            let x_c1 = BigUint::from_bytes_le(&words_to_bytes_le_vec(&event.x[num_words..]));
+            let y_c0 = BigUint::from_bytes_le(&words_to_bytes_le_vec(&event.y[..num_words]));
+            let y_c1 = BigUint::from_bytes_le(&words_to_bytes_le_vec(&event.y[num_words..]));
+
+            cols.is_real = F::one();
+            cols.shard = F::from_canonical_u32(event.shard);
+            cols.channel = F::from_canonical_u8(event.channel);
+            cols.clk = F::from_canonical_u32(event.clk);
+            cols.is_add = F::from_bool(event.op == FieldOperation::Add);
+            cols.x_ptr = F::from_canonical_u32(event.x_ptr);
+            cols.y_ptr = F::from_canonical_u32(event.y_ptr);
+
+            let c0 = cols.c0.populate(
+                &mut new_byte_lookup_events,
+                event.shard,
+                event.channel,
+                &x_c0,
+                &y_c0,
+                event.op,
+            );
+            let c1 = cols.c1.populate(
+                &mut new_byte_lookup_events,
+                event.shard,
+                event.channel,
+                &x_c1,
+                &y_c1,
+                event.op,
+            );
+            let modulus = P::modulus();
+            cols.c0_range_check.populate(
+                &mut new_byte_lookup_events,
+                event.shard,
+                event.channel,
+                &c0,
+                &modulus,
+            );
+            cols.c1_range_check.populate(
+                &mut new_byte_lookup_events,
+                event.shard,
+                event.channel,
+                &c1,
+                &modulus,
+            );
+
+            for i in 0..cols.y_access.len() {
+                cols.y_access[i].populate(
+                    event.channel,
+                    event.y_memory_records[i],
+                    &mut new_byte_lookup_events,
+                );
+            }
+            for i in 0..cols.x_access.len() {
+                cols.x_access[i].populate(
+                    event.channel,
+                    event.x_memory_records[i],
+                    &mut new_byte_lookup_events,
+                );
+            }
+
+            rows.push(row);
+        }
+        output.add_byte_lookup_events(new_byte_lookup_events);
+
+        pad_rows(&mut rows, || {
+            let mut row = vec![F::zero(); num_fp2_addsub_cols::<P>()];
+            let cols: &mut Fp2AddSubAssignCols<F, P> = row.as_mut_slice().borrow_mut();
+            let zero = BigUint::zero();
+            cols.c0
+                .populate(&mut vec![], 0, 0, &zero, &zero, FieldOperation::Add);
+            cols.c1
+                .populate(&mut vec![], 0, 0, &zero, &zero, FieldOperation::Add);
+            row
+        });
+
+        // Convert the trace to a row major matrix.
+        let mut trace = RowMajorMatrix::new(
+            rows.into_iter().flatten().collect::<Vec<_>>(),
+            num_fp2_addsub_cols::<P>(),
+        );
+
+        // Write the nonces to the trace.
+        for i in 0..trace.height() {
+            let cols: &mut Fp2AddSubAssignCols<F, P> = trace.values
+                [i * num_fp2_addsub_cols::<P>()..(i + 1) * num_fp2_addsub_cols::<P>()]
+                .borrow_mut();
+            cols.nonce = F::from_canonical_usize(i);
+        }
+
+        trace
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        match P::FIELD_TYPE {
+            FieldType::Bn254 => !shard.bn254_fp2_addsub_events.is_empty(),
+            FieldType::Bls12381 => !shard.bls12381_fp2_addsub_events.is_empty(),
+        }
+    }
+}
+
+impl<F, P: FpOpField> BaseAir<F> for Fp2AddSubAssignChip<P> {
+    fn width(&self) -> usize {
+        num_fp2_addsub_cols::<P>()
+    }
+}
+
+impl<AB, P: FpOpField> Air<AB> for Fp2AddSubAssignChip<P>
+where
+    AB: SP1AirBuilder,
+    Limbs<AB::Var, <P as NumLimbs>::Limbs>: Copy,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let local = main.row_slice(0);
+        let local: &Fp2AddSubAssignCols<AB::Var, P> = (*local).borrow();
+        let next = main.row_slice(1);
+        let next: &Fp2AddSubAssignCols<AB::Var, P> = (*next).borrow();
+
+        // Constrain the incrementing nonce.
+        builder.when_first_row().assert_zero(local.nonce);
+        builder
+            .when_transition()
+            .assert_eq(local.nonce + AB::Expr::one(), next.nonce);
+
+        // A real row is either an addition or a subtraction, and padding rows are neither.
+        builder.assert_bool(local.is_real);
+        builder.assert_bool(local.is_add);
+        builder.when_not(local.is_real).assert_zero(local.is_add);
+        let is_sub = local.is_real - local.is_add;
+
+        let num_words_field_element = <P as NumWords>::WordsFieldElement::USIZE;
+        let x_c0: Limbs<AB::Var, <P as NumLimbs>::Limbs> =
+            limbs_from_prev_access(&local.x_access[0..num_words_field_element]);
+        let x_c1: Limbs<AB::Var, <P as NumLimbs>::Limbs> =
+            limbs_from_prev_access(&local.x_access[num_words_field_element..]);
+        let y_c0: Limbs<AB::Var, <P as NumLimbs>::Limbs> =
+            limbs_from_prev_access(&local.y_access[0..num_words_field_element]);
+        let y_c1: Limbs<AB::Var, <P as NumLimbs>::Limbs> =
+            limbs_from_prev_access(&local.y_access[num_words_field_element..]);
+        let p_modulus = P::modulus_field_iter::<AB::F>()
+            .map(AB::Expr::from)
+            .collect::<Polynomial<_>>();
+
+        for (cols, range_check, x, y) in [
+            (&local.c0, &local.c0_range_check, &x_c0, &y_c0),
+            (&local.c1, &local.c1_range_check, &x_c1, &y_c1),
+        ] {
+            cols.eval_variable(
+                builder,
+                x,
+                y,
+                &p_modulus,
+                local.is_add,
+                is_sub.clone(),
+                AB::Expr::zero(),
+                AB::Expr::zero(),
+                local.shard,
+                local.channel,
+                local.is_real,
+            );
+
+            // Verify that the result is below the modulus.
+            range_check.eval(
+                builder,
+                &cols.result,
+                &p_modulus,
+                local.shard,
+                local.channel,
+                local.is_real,
+            );
+        }
+
+        // Assert that the result is written to x.
+        builder.when(local.is_real).assert_all_eq(
+            local.c0.result,
+            value_as_limbs(&local.x_access[0..num_words_field_element]),
+        );
+        builder.when(local.is_real).assert_all_eq(
+            local.c1.result,
+            value_as_limbs(&local.x_access[num_words_field_element..]),
+        );
+
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.channel,
+            local.clk.into(),
+            local.y_ptr,
+            &local.y_access,
+            local.is_real,
+        );
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.channel,
+            local.clk + AB::F::from_canonical_u32(1),
+            local.x_ptr,
+            &local.x_access,
+            local.is_real,
+        );
+
+        // The syscall id is determined by the selected operation.
+        let (add_id, sub_id) = match P::FIELD_TYPE {
+            FieldType::Bn254 => (SyscallCode::BN254_FP2_ADD, SyscallCode::BN254_FP2_SUB),
+            FieldType::Bls12381 => (SyscallCode::BLS12381_FP2_ADD, SyscallCode::BLS12381_FP2_SUB),
+        };
+        let syscall_id_felt = local.is_add * AB::F::from_canonical_u32(add_id.syscall_id())
+            + is_sub * AB::F::from_canonical_u32(sub_id.syscall_id());
+
+        builder.receive_syscall(
+            local.shard,
+            local.channel,
+            local.clk,
+            local.nonce,
+            syscall_id_felt,
+            local.x_ptr,
+            local.y_ptr,
+            local.is_real,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::borrow::BorrowMut;
+
+    use num::bigint::RandBigInt;
+    use num::{BigUint, One, Zero};
+    use p3_baby_bear::BabyBear;
+    use rand::thread_rng;
+
+    use super::super::tests::{debug_tampered_trace, execute, syscall_program, to_words};
+    use super::Fp2AddSubAssignCols;
+    use crate::operations::field::field_op::FieldOperation;
+    use crate::operations::field::params::FieldParameters;
+    use crate::runtime::SyscallCode;
+    use crate::stark::CpuProver;
+    use crate::utils::ec::weierstrass::bls12_381::Bls12381BaseField;
+    use crate::utils::ec::weierstrass::bn254::Bn254BaseField;
+    use crate::utils::{run_test, setup_logger};
+
+    #[test]
+    fn test_fp2_addsub() {
+        setup_logger();
+        let mut rng = thread_rng();
+        let fields = [
+            (
+                Bn254BaseField::modulus(),
+                8,
+                SyscallCode::BN254_FP2_ADD,
+                SyscallCode::BN254_FP2_SUB,
+            ),
+            (
+                Bls12381BaseField::modulus(),
+                12,
+                SyscallCode::BLS12381_FP2_ADD,
+                SyscallCode::BLS12381_FP2_SUB,
+            ),
+        ];
+
+        let mut calls = Vec::new();
+        let mut expected = Vec::new();
+        let mut num_words = Vec::new();
+        for (modulus, words, add, sub) in fields {
+            let [a0, a1, b0, b1]: [BigUint; 4] =
+                core::array::from_fn(|_| rng.gen_biguint_below(&modulus));
+            let fp2 =
+                |c0: &BigUint, c1: &BigUint| [to_words(c0, words), to_words(c1, words)].concat();
+
+            let sum = fp2(&((&a0 + &b0) % &modulus), &((&a1 + &b1) % &modulus));
+            let diff = fp2(
+                &((&modulus + &a0 - &b0) % &modulus),
+                &((&modulus + &a1 - &b1) % &modulus),
+            );
+            for (code, result) in [(add, sum), (sub, diff)] {
+                calls.push((code, fp2(&a0, &a1), fp2(&b0, &b1)));
+                expected.push(result);
+                num_words.push(2 * words);
+            }
+        }
+
+        let (program, x_ptrs) = syscall_program(&calls);
+        assert_eq!(execute(&program, &x_ptrs, &num_words), expected);
+
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+
+    #[test]
+    fn test_fp2_addsub_non_canonical_result() {
+        setup_logger();
+        // `a1 + b1` is above the modulus, so it is only canonical once reduced.
+        let modulus = Bn254BaseField::modulus();
+        let [a0, a1] = [BigUint::zero(), &modulus - 1u32];
+        let [b0, b1] = [BigUint::zero(), BigUint::from(2u32)];
+        let fp2 = |c0: &BigUint, c1: &BigUint| [to_words(c0, 8), to_words(c1, 8)].concat();
+        let calls = [(SyscallCode::BN254_FP2_ADD, fp2(&a0, &a1), fp2(&b0, &b1))];
+        let (program, _) = syscall_program(&calls);
+        debug_tampered_trace(program.clone(), "Bn254Fp2AddSubAssign", |_| {}).unwrap();
+
+        // Write the unreduced `c1` with a zero carry, which satisfies the field operation itself.
+        let error = debug_tampered_trace(program, "Bn254Fp2AddSubAssign", |row| {
+            let cols: &mut Fp2AddSubAssignCols<BabyBear, Bn254BaseField> = row.borrow_mut();
+            let c1 = cols.c1.populate_with_modulus(
+                &mut vec![],
+                0,
+                0,
+                &a1,
+                &b1,
+                &(BigUint::one() << 256),
+                FieldOperation::Add,
+            );
+            assert!(c1 >= modulus);
+            for (access, word) in cols.x_access[8..].iter_mut().zip(to_words(&c1, 8)) {
+                access.access.value = word.into();
+            }
+        })
+        .unwrap_err();
+        assert_eq!(error.row, 0);
+    }
+}
@@ -0,0 +1,551 @@
+use core::borrow::{Borrow, BorrowMut};
+use core::mem::size_of;
+use std::marker::PhantomData;
+
+use generic_array::GenericArray;
+use num::{BigUint, Zero};
+use p3_air::AirBuilder;
+use p3_air::{Air, BaseAir};
+use p3_field::AbstractField;
+use p3_field::PrimeField32;
+use p3_matrix::dense::RowMajorMatrix;
+use p3_matrix::Matrix;
+use serde::{Deserialize, Serialize};
+use sp1_derive::AlignedBorrow;
+use typenum::Unsigned;
+
+use super::{FieldType, FpOpField};
+use crate::air::{BaseAirBuilder, MachineAir, Polynomial, SP1AirBuilder};
+use crate::bytes::event::ByteRecord;
+use crate::bytes::ByteLookupEvent;
+use crate::memory::{value_as_limbs, MemoryReadCols, MemoryWriteCols};
+use crate::operations::field::field_op::{FieldOpCols, FieldOperation};
+use crate::operations::field::params::{Limbs, NumLimbs, NumWords};
+use crate::operations::field::range::FieldLtCols;
+use crate::runtime::{ExecutionRecord, Program, Syscall, SyscallCode};
+use crate::runtime::{MemoryReadRecord, MemoryWriteRecord};
+use crate::syscall::precompiles::SyscallContext;
+use crate::utils::{limbs_from_prev_access, pad_rows, words_to_bytes_le_vec};
+
+pub const fn num_fp2_mul_cols<P: FpOpField>() -> usize {
+    size_of::<Fp2MulAssignCols<u8, P>>()
+}
+
+/// An `Fp2` multiplication event, computing `x = x * y` in place.
+///
+/// An `Fp2` element `c0 + c1 * u` is laid out in memory as `c0` followed by `c1`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Fp2MulEvent {
+    pub lookup_id: u128,
+    pub shard: u32,
+    pub channel: u8,
+    pub clk: u32,
+    pub x_ptr: u32,
+    pub x: Vec<u32>,
+    pub y_ptr: u32,
+    pub y: Vec<u32>,
+    pub x_memory_records: Vec<MemoryWriteRecord>,
+    pub y_memory_records: Vec<MemoryReadRecord>,
+}
+
+/// Computes `(a0 + a1 * u) * (b0 + b1 * u)` in `Fp[u] / (u^2 + 1)`.
+fn fp2_mul(
+    a0: &BigUint,
+    a1: &BigUint,
+    b0: &BigUint,
+    b1: &BigUint,
+    modulus: &BigUint,
+) -> (BigUint, BigUint) {
+    let c0 = (modulus + (a0 * b0) % modulus - (a1 * b1) % modulus) % modulus;
+    let c1 = (a0 * b1 + a1 * b0) % modulus;
+    (c0, c1)
+}
+
+/// A chip proving `Fp2` multiplication for the quadratic extension of the base field `P`.
+///
+/// The extension is `Fp[u] / (u^2 + 1)`, so `(a0 + a1 * u) * (b0 + b1 * u)` is
+/// `(a0 * b0 - a1 * b1) + (a0 * b1 + a1 * b0) * u`. All coefficients are expected to be reduced
+/// modulo `p`.
+#[derive(Default)]
+pub struct Fp2MulAssignChip<P> {
+    _marker: PhantomData<P>,
+}
+
+impl<P: FpOpField> Fp2MulAssignChip<P> {
+    pub const fn new() -> Self {
+        Self {
+            _marker: PhantomData,
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn populate_field_ops<F: PrimeField32>(
+        blu_events: &mut Vec<ByteLookupEvent>,
+        shard: u32,
+        channel: u8,
+        cols: &mut Fp2MulAssignCols<F, P>,
+        a0: BigUint,
+        a1: BigUint,
+        b0: BigUint,
+        b1: BigUint,
+    ) {
+        let a0_mul_b0 =
+            cols.a0_mul_b0
+                .populate(blu_events, shard, channel, &a0, &b0, FieldOperation::Mul);
+        let a1_mul_b1 =
+            cols.a1_mul_b1
+                .populate(blu_events, shard, channel, &a1, &b1, FieldOperation::Mul);
+        let a0_mul_b1 =
+            cols.a0_mul_b1
+                .populate(blu_events, shard, channel, &a0, &b1, FieldOperation::Mul);
+        let a1_mul_b0 =
+            cols.a1_mul_b0
+                .populate(blu_events, shard, channel, &a1, &b0, FieldOperation::Mul);
+        let c0 = cols.c0.populate(
+            blu_events,
+            shard,
+            channel,
+            &a0_mul_b0,
+            &a1_mul_b1,
+            FieldOperation::Sub,
+        );
+        let c1 = cols.c1.populate(
+            blu_events,
+            shard,
+            channel,
+            &a0_mul_b1,
+            &a1_mul_b0,
+            FieldOperation::Add,
+        );
+        let modulus = P::modulus();
+        cols.c0_range_check
+            .populate(blu_events, shard, channel, &c0, &modulus);
+        cols.c1_range_check
+            .populate(blu_events, shard, channel, &c1, &modulus);
+    }
+}
+
+impl<P: FpOpField> Syscall for Fp2MulAssignChip<P> {
+    fn execute(&self, rt: &mut SyscallContext, arg1: u32, arg2: u32) -> Option<u32> {
+        let clk = rt.clk;
+        let x_ptr = arg1;
+        if x_ptr % 4 != 0 {
+            panic!();
+        }
+        let y_ptr = arg2;
+        if y_ptr % 4 != 0 {
+            panic!();
+        }
+
+        let num_words = <P as NumWords>::WordsCurvePoint::USIZE;
+
+        let x = rt.slice_unsafe(x_ptr, num_words);
+        let (y_memory_records, y) = rt.mr_slice(y_ptr, num_words);
+
+        let (a0, a1) = x.split_at(num_words / 2);
+        let (b0, b1) = y.split_at(num_words / 2);
+        let a0 = BigUint::from_bytes_le(&words_to_bytes_le_vec(a0));
+        let a1 = BigUint::from_bytes_le(&words_to_bytes_le_vec(a1));
+        let b0 = BigUint::from_bytes_le(&words_to_bytes_le_vec(b0));
+        let b1 = BigUint::from_bytes_le(&words_to_bytes_le_vec(b1));
+        let (c0, c1) = fp2_mul(&a0, &a1, &b0, &b1, &P::modulus());
+
+        let mut result_words = c0.to_u32_digits();
+        result_words.resize(num_words / 2, 0);
+        let mut c1_words = c1.to_u32_digits();
+        c1_words.resize(num_words / 2, 0);
+        result_words.extend(c1_words);
+
+        // Increment clk so that the write is not at the same cycle as the read.
+        rt.clk += 1;
+        let x_memory_records = rt.mw_slice(x_ptr, &result_words);
+
+        let event = Fp2MulEvent {
+            lookup_id: rt.syscall_lookup_id,
+            shard: rt.current_shard(),
+            channel: rt.current_channel(),
+            clk,
+            x_ptr,
+            x,
+            y_ptr,
+            y,
+            x_memory_records,
+            y_memory_records,
+        };
+        match P::FIELD_TYPE {
+            FieldType::Bn254 => rt.record_mut().bn254_fp2_mul_events.push(event),
+            FieldType::Bls12381 => rt.record_mut().bls12381_fp2_mul_events.push(event),
+        }
+
+        None
+    }
+
+    fn num_extra_cycles(&self) -> u32 {
+        1
+    }
+}
+
+/// A set of columns for the `Fp2` mul operation.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct Fp2MulAssignCols<T, P: FpOpField> {
+    pub is_real: T,
+    pub shard: T,
+    pub channel: T,
+    pub nonce: T,
+    pub clk: T,
+    pub x_ptr: T,
+    pub y_ptr: T,
+    pub x_access: GenericArray<MemoryWriteCols<T>, P::WordsCurvePoint>,
+    pub y_access: GenericArray<MemoryReadCols<T>, P::WordsCurvePoint>,
+    pub(crate) a0_mul_b0: FieldOpCols<T, P>,
+    pub(crate) a1_mul_b1: FieldOpCols<T, P>,
+    pub(crate) a0_mul_b1: FieldOpCols<T, P>,
+    pub(crate) a1_mul_b0: FieldOpCols<T, P>,
+    pub(crate) c0: FieldOpCols<T, P>,
+    pub(crate) c1: FieldOpCols<T, P>,
+    pub(crate) c0_range_check: FieldLtCols<T, P>,
+    pub(crate) c1_range_check: FieldLtCols<T, P>,
+}
+
+impl<F: PrimeField32, P: FpOpField> MachineAir<F> for Fp2MulAssignChip<P> {
+    type Record = ExecutionRecord;
+    type Program = Program;
+
+    fn name(&self) -> String {
+        match P::FIELD_TYPE {
+            FieldType::Bn254 => "Bn254Fp2MulAssign".to_string(),
+            FieldType::Bls12381 => "Bls12381Fp2MulAssign".to_string(),
+        }
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        output: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let events = match P::FIELD_TYPE {
+            FieldType::Bn254 => &input.bn254_fp2_mul_events,
+            FieldType::Bls12381 => &input.bls12381_fp2_mul_events,
+        };
+
+        let mut rows = Vec::new();
+        let mut new_byte_lookup_events = Vec::new();
+
+        for event in events {
+            let mut row = vec![F::zero(); num_fp2_mul_cols::<P>()];
+            let cols: &mut Fp2MulAssignCols<F, P> = row.as_mut_slice().borrow_mut();
+
+            let num_words = <P as NumWords>::WordsFieldElement::USIZE;
+            let a0 = BigUint::from_bytes_le(&words_to_bytes_le_vec(&event.x[..num_words]));
+            let a1 = BigUint::from_bytes_le(&words_to_bytes_le_vec(&event.x[num_words..]));
+            let b0 = BigUint::from_bytes_le(&words_to_bytes_le_vec(&event.y[..num_words]));
+            let b1 = BigUint::from_bytes_le(&words_to_bytes_le_vec(&event.y[num_words..]));
+
+            cols.is_real = F::one();
+            cols.shard = F::from_canonical_u32(event.shard);
+            cols.channel = F::from_canonical_u8(event.channel);
+            cols.clk = F::from_canonical_u32(event.clk);
+            cols.x_ptr = F::from_canonical_u32(event.x_ptr);
+            cols.y_ptr = F::from_canonical_u32(event.y_ptr);
+
+            Self::populate_field_ops(
+                &mut new_byte_lookup_events,
+                event.shard,
+                event.channel,
+                cols,
+                a0,
+                a1,
+                b0,
+                b1,
+            );
+
+            for i in 0..cols.y_access.len() {
+                cols.y_access[i].populate(
+                    event.channel,
+                    event.y_memory_records[i],
+                    &mut new_byte_lookup_events,
+                );
+            }
+            for i in 0..cols.x_access.len() {
+                cols.x_access[i].populate(
+                    event.channel,
+                    event.x_memory_records[i],
+                    &mut new_byte_lookup_events,
+                );
+            }
+
+            rows.push(row);
+        }
+        output.add_byte_lookup_events(new_byte_lookup_events);
+
+        pad_rows(&mut rows, || {
+            let mut row = vec![F::zero(); num_fp2_mul_cols::<P>()];
+            let cols: &mut Fp2MulAssignCols<F, P> = row.as_mut_slice().borrow_mut();
+            let zero = BigUint::zero();
+            Self::populate_field_ops(
+                &mut vec![],
+                0,
+                0,
+                cols,
+                zero.clone(),
+                zero.clone(),
+                zero.clone(),
+                zero,
+            );
+            row
+        });
+
+        // Convert the trace to a row major matrix.
+        let mut trace = RowMajorMatrix::new(
+            rows.into_iter().flatten().collect::<Vec<_>>(),
+            num_fp2_mul_cols::<P>(),
+        );
+
+        // Write the nonces to the trace.
+        for i in 0..trace.height() {
+            let cols: &mut Fp2MulAssignCols<F, P> = trace.values
+                [i * num_fp2_mul_cols::<P>()..(i + 1) * num_fp2_mul_cols::<P>()]
+                .borrow_mut();
+            cols.nonce = F::from_canonical_usize(i);
+        }
+
+        trace
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        match P::FIELD_TYPE {
+            FieldType::Bn254 => !shard.bn254_fp2_mul_events.is_empty(),
+            FieldType::Bls12381 => !shard.bls12381_fp2_mul_events.is_empty(),
+        }
+    }
+}
+
+impl<F, P: FpOpField> BaseAir<F> for Fp2MulAssignChip<P> {
+    fn width(&self) -> usize {
+        num_fp2_mul_cols::<P>()
+    }
+}
+
+impl<AB, P: FpOpField> Air<AB> for Fp2MulAssignChip<P>
+where
+    AB: SP1AirBuilder,
+    Limbs<AB::Var, <P as NumLimbs>::Limbs>: Copy,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let local = main.row_slice(0);
+        let local: &Fp2MulAssignCols<AB::Var, P> = (*local).borrow();
+        let next = main.row_slice(1);
+        let next: &Fp2MulAssignCols<AB::Var, P> = (*next).borrow();
+
+        // Constrain the incrementing nonce.
+        builder.when_first_row().assert_zero(local.nonce);
+        builder
+            .when_transition()
+            .assert_eq(local.nonce + AB::Expr::one(), next.nonce);
+
+        builder.assert_bool(local.is_real);
+
+        let num_words_field_element = <P as NumWords>::WordsFieldElement::USIZE;
+        let a0: Limbs<AB::Var, <P as NumLimbs>::Limbs> =
+            limbs_from_prev_access(&local.x_access[0..num_words_field_element]);
+        let a1: Limbs<AB::Var, <P as NumLimbs>::Limbs> =
+            limbs_from_prev_access(&local.x_access[num_words_field_element..]);
+        let b0: Limbs<AB::Var, <P as NumLimbs>::Limbs> =
+            limbs_from_prev_access(&local.y_access[0..num_words_field_element]);
+        let b1: Limbs<AB::Var, <P as NumLimbs>::Limbs> =
+            limbs_from_prev_access(&local.y_access[num_words_field_element..]);
+
+        for (cols, a, b) in [
+            (&local.a0_mul_b0, &a0, &b0),
+            (&local.a1_mul_b1, &a1, &b1),
+            (&local.a0_mul_b1, &a0, &b1),
+            (&local.a1_mul_b0, &a1, &b0),
+        ] {
+            cols.eval(
+                builder,
+                a,
+                b,
+                FieldOperation::Mul,
+                local.shard,
+                local.channel,
+                local.is_real,
+            );
+        }
+
+        // c0 = a0 * b0 - a1 * b1.
+        local.c0.eval(
+            builder,
+            &local.a0_mul_b0.result,
+            &local.a1_mul_b1.result,
+            FieldOperation::Sub,
+            local.shard,
+            local.channel,
+            local.is_real,
+        );
+
+        // c1 = a0 * b1 + a1 * b0.
+        local.c1.eval(
+            builder,
+            &local.a0_mul_b1.result,
+            &local.a1_mul_b0.result,
+            FieldOperation::Add,
+            local.shard,
+            local.channel,
+            local.is_real,
+        );
+
+        // Verify that the results are below the modulus.
+        let p_modulus = P::modulus_field_iter::<AB::F>()
+            .map(AB::Expr::from)
+            .collect::<Polynomial<_>>();
+        for (cols, range_check) in [
+            (&local.c0, &local.c0_range_check),
+            (&local.c1, &local.c1_range_check),
+        ] {
+            range_check.eval(
+                builder,
+                &cols.result,
+                &p_modulus,
+                local.shard,
+                local.channel,
+                local.is_real,
+            );
+        }
+
+        // Assert that the result is written to x.
+        builder.when(local.is_real).assert_all_eq(
+            local.c0.result,
+            value_as_limbs(&local.x_access[0..num_words_field_element]),
+        );
+        builder.when(local.is_real).assert_all_eq(
+            local.c1.result,
+            value_as_limbs(&local.x_access[num_words_field_element..]),
+        );
+
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.channel,
+            local.clk.into(),
+            local.y_ptr,
+            &local.y_access,
+            local.is_real,
+        );
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.channel,
+            local.clk + AB::F::from_canonical_u32(1),
+            local.x_ptr,
+            &local.x_access,
+            local.is_real,
+        );
+
+        let syscall_id_felt = match P::FIELD_TYPE {
+            FieldType::Bn254 => AB::F::from_canonical_u32(SyscallCode::BN254_FP2_MUL.syscall_id()),
+            FieldType::Bls12381 => {
+                AB::F::from_canonical_u32(SyscallCode::BLS12381_FP2_MUL.syscall_id())
+            }
+        };
+
+        builder.receive_syscall(
+            local.shard,
+            local.channel,
+            local.clk,
+            local.nonce,
+            syscall_id_felt,
+            local.x_ptr,
+            local.y_ptr,
+            local.is_real,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::borrow::BorrowMut;
+
+    use num::bigint::RandBigInt;
+    use num::{BigUint, One};
+    use p3_baby_bear::BabyBear;
+    use rand::thread_rng;
+
+    use super::super::tests::{debug_tampered_trace, execute, syscall_program, to_words};
+    use super::Fp2MulAssignCols;
+    use crate::operations::field::field_op::FieldOperation;
+    use crate::operations::field::params::FieldParameters;
+    use crate::runtime::SyscallCode;
+    use crate::stark::CpuProver;
+    use crate::utils::ec::weierstrass::bls12_381::Bls12381BaseField;
+    use crate::utils::ec::weierstrass::bn254::Bn254BaseField;
+    use crate::utils::{run_test, setup_logger};
+
+    #[test]
+    fn test_fp2_mul() {
+        setup_logger();
+        let mut rng = thread_rng();
+        let fields = [
+            (Bn254BaseField::modulus(), 8, SyscallCode::BN254_FP2_MUL),
+            (
+                Bls12381BaseField::modulus(),
+                12,
+                SyscallCode::BLS12381_FP2_MUL,
+            ),
+        ];
+
+        let mut calls = Vec::new();
+        let mut expected = Vec::new();
+        let mut num_words = Vec::new();
+        for (modulus, words, code) in fields {
+            let [a0, a1, b0, b1]: [BigUint; 4] =
+                core::array::from_fn(|_| rng.gen_biguint_below(&modulus));
+            let fp2 =
+                |c0: &BigUint, c1: &BigUint| [to_words(c0, words), to_words(c1, words)].concat();
+
+            // (a0 + a1 * u) * (b0 + b1 * u) with u^2 = -1.
+            let c0 = (&modulus * &modulus + &a0 * &b0 - &a1 * &b1) % &modulus;
+            let c1 = (&a0 * &b1 + &a1 * &b0) % &modulus;
+            calls.push((code, fp2(&a0, &a1), fp2(&b0, &b1)));
+            expected.push(fp2(&c0, &c1));
+            num_words.push(2 * words);
+        }
+
+        let (program, x_ptrs) = syscall_program(&calls);
+        assert_eq!(execute(&program, &x_ptrs, &num_words), expected);
+
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+
+    #[test]
+    fn test_fp2_mul_non_canonical_result() {
+        setup_logger();
+        // `a0 * b1 + a1 * b0` is above the modulus, so it is only canonical once reduced.
+        let modulus = Bn254BaseField::modulus();
+        let [a0, a1] = [BigUint::one(), BigUint::one()];
+        let [b0, b1] = [&modulus - 1u32, BigUint::from(2u32)];
+        let fp2 = |c0: &BigUint, c1: &BigUint| [to_words(c0, 8), to_words(c1, 8)].concat();
+        let calls = [(SyscallCode::BN254_FP2_MUL, fp2(&a0, &a1), fp2(&b0, &b1))];
+        let (program, _) = syscall_program(&calls);
+        debug_tampered_trace(program.clone(), "Bn254Fp2MulAssign", |_| {}).unwrap();
+
+        // Write the unreduced `c1` with a zero carry, which satisfies the field operation itself.
+        let error = debug_tampered_trace(program, "Bn254Fp2MulAssign", |row| {
+            let cols: &mut Fp2MulAssignCols<BabyBear, Bn254BaseField> = row.borrow_mut();
+            let c1 = cols.c1.populate_with_modulus(
+                &mut vec![],
+                0,
+                0,
+                &b1,
+                &b0,
+                &(BigUint::one() << 256),
+                FieldOperation::Add,
+            );
+            assert!(c1 >= modulus);
+            for (access, word) in cols.x_access[8..].iter_mut().zip(to_words(&c1, 8)) {
+                access.access.value = word.into();
+            }
+        })
+        .unwrap_err();
+        assert_eq!(error.row, 0);
+    }
+}
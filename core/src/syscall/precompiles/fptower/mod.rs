@@ -0,0 +1,130 @@
+mod fp;
+mod fp2_addsub;
+mod fp2_mul;
+
+pub use fp::*;
+pub use fp2_addsub::*;
+pub use fp2_mul::*;
+
+use crate::operations::field::params::{FieldParameters, NumWords};
+use crate::utils::ec::weierstrass::bls12_381::Bls12381BaseField;
+use crate::utils::ec::weierstrass::bn254::Bn254BaseField;
+
+/// The base fields supported by the `Fp` and `Fp2` precompiles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldType {
+    Bn254,
+    Bls12381,
+}
+
+/// A base field with `Fp` and `Fp2` arithmetic precompiles.
+pub trait FpOpField: FieldParameters + NumWords {
+    const FIELD_TYPE: FieldType;
+}
+
+impl FpOpField for Bn254BaseField {
+    const FIELD_TYPE: FieldType = FieldType::Bn254;
+}
+
+impl FpOpField for Bls12381BaseField {
+    const FIELD_TYPE: FieldType = FieldType::Bls12381;
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::collections::BTreeMap;
+
+    use num::BigUint;
+    use p3_baby_bear::BabyBear;
+    use p3_challenger::FieldChallenger;
+    use p3_matrix::Matrix;
+
+    use crate::air::MachineAir;
+    use crate::runtime::{ExecutionRecord, Instruction, Opcode, Program, Runtime, SyscallCode};
+    use crate::stark::{
+        try_debug_constraints, DebugConstraintError, MachineRecord, RiscvAir, StarkGenericConfig,
+    };
+    use crate::utils::{BabyBearPoseidon2, SP1CoreOpts};
+
+    /// Converts a reduced field element into `num_words` little-endian words.
+    pub(crate) fn to_words(value: &BigUint, num_words: usize) -> Vec<u32> {
+        let mut words = value.to_u32_digits();
+        words.resize(num_words, 0);
+        words
+    }
+
+    /// Builds a program invoking each `(syscall, x, y)` on its own pair of buffers.
+    ///
+    /// Returns the program together with the pointer to each `x` buffer, which holds the result.
+    pub(crate) fn syscall_program(
+        calls: &[(SyscallCode, Vec<u32>, Vec<u32>)],
+    ) -> (Program, Vec<u32>) {
+        let mut memory_image = BTreeMap::new();
+        let mut instructions = Vec::new();
+        let mut x_ptrs = Vec::new();
+        for (i, (code, x, y)) in calls.iter().enumerate() {
+            let x_ptr = 0x1000 + 0x200 * i as u32;
+            let y_ptr = x_ptr + 0x100;
+            for (j, word) in x.iter().enumerate() {
+                memory_image.insert(x_ptr + 4 * j as u32, *word);
+            }
+            for (j, word) in y.iter().enumerate() {
+                memory_image.insert(y_ptr + 4 * j as u32, *word);
+            }
+            instructions.extend([
+                Instruction::new(Opcode::ADD, 5, 0, *code as u32, false, true),
+                Instruction::new(Opcode::ADD, 10, 0, x_ptr, false, true),
+                Instruction::new(Opcode::ADD, 11, 0, y_ptr, false, true),
+                Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            ]);
+            x_ptrs.push(x_ptr);
+        }
+        let mut program = Program::new(instructions, 0, 0);
+        program.memory_image = memory_image;
+        (program, x_ptrs)
+    }
+
+    /// Executes the program and returns the `num_words` words at each pointer.
+    pub(crate) fn execute(program: &Program, ptrs: &[u32], num_words: &[usize]) -> Vec<Vec<u32>> {
+        let mut runtime = Runtime::new(program.clone(), SP1CoreOpts::default());
+        runtime.run().unwrap();
+        ptrs.iter()
+            .zip(num_words)
+            .map(|(ptr, n)| (0..*n as u32).map(|j| runtime.word(ptr + 4 * j)).collect())
+            .collect()
+    }
+
+    /// Executes the program, applies `tamper` to the first row of the trace of the chip named
+    /// `chip_name` and checks the constraints of that chip.
+    pub(crate) fn debug_tampered_trace(
+        program: Program,
+        chip_name: &str,
+        tamper: impl FnOnce(&mut [BabyBear]),
+    ) -> Result<(), DebugConstraintError> {
+        let mut runtime = Runtime::new(program, SP1CoreOpts::default());
+        runtime.run().unwrap();
+        let record = runtime.records.remove(0);
+
+        let machine = RiscvAir::machine(BabyBearPoseidon2::new());
+        let chip = machine
+            .chips()
+            .iter()
+            .find(|chip| chip.name() == chip_name)
+            .unwrap();
+        let mut trace = chip.generate_trace(&record, &mut ExecutionRecord::default());
+        let width = trace.width();
+        tamper(&mut trace.values[..width]);
+
+        let mut challenger = machine.config().challenger();
+        let challenges: [_; 2] = core::array::from_fn(|_| challenger.sample_ext_element());
+        let permutation_trace = chip.generate_permutation_trace(None, &trace, &challenges);
+        try_debug_constraints::<BabyBearPoseidon2, _>(
+            chip,
+            None,
+            &trace,
+            &permutation_trace,
+            &challenges,
+            record.public_values(),
+        )
+    }
+}
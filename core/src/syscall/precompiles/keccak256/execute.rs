@@ -29,59 +29,20 @@ impl Syscall for KeccakPermuteChip {
         let mut state_read_records = Vec::new();
         let mut state_write_records = Vec::new();
 
-        let mut state = Vec::new();
+        let mut state = [0u64; STATE_SIZE];
 
         let (state_records, state_values) = rt.mr_slice(state_ptr, STATE_NUM_WORDS);
         state_read_records.extend_from_slice(&state_records);
 
-        for values in state_values.chunks_exact(2) {
+        for (lane, values) in state.iter_mut().zip(state_values.chunks_exact(2)) {
             let least_sig = values[0];
             let most_sig = values[1];
-            state.push(least_sig as u64 + ((most_sig as u64) << 32));
+            *lane = least_sig as u64 + ((most_sig as u64) << 32);
         }
 
-        let saved_state = state.clone();
+        let saved_state = state;
 
-        for i in 0..NUM_ROUNDS {
-            let mut array: [u64; 5 * 5] = [0; 5 * 5];
-
-            // Theta
-            for x in 0..5 {
-                for y_count in 0..5 {
-                    let y = y_count * 5;
-                    array[x] ^= state[x + y];
-                }
-            }
-
-            for x in 0..5 {
-                for y_count in 0..5 {
-                    let y = y_count * 5;
-                    state[y + x] ^= array[(x + 4) % 5] ^ array[(x + 1) % 5].rotate_left(1);
-                }
-            }
-
-            // Rho and pi
-            let mut last = state[1];
-            for x in 0..24 {
-                array[0] = state[PI[x]];
-                state[PI[x]] = last.rotate_left(RHO[x]);
-                last = array[0];
-            }
-
-            // Chi
-            for y_step in 0..5 {
-                let y = y_step * 5;
-
-                array[..5].copy_from_slice(&state[y..(5 + y)]);
-
-                for x in 0..5 {
-                    state[y + x] = array[x] ^ ((!array[(x + 1) % 5]) & (array[(x + 2) % 5]));
-                }
-            }
-
-            // Iota
-            state[0] ^= RC[i];
-        }
+        keccakf(&mut state);
 
         // Increment the clk by 1 before writing because we read from memory at start_clk.
         rt.clk += 1;
@@ -107,8 +68,8 @@ impl Syscall for KeccakPermuteChip {
                 shard,
                 channel,
                 clk: start_clk,
-                pre_state: saved_state.as_slice().try_into().unwrap(),
-                post_state: state.as_slice().try_into().unwrap(),
+                pre_state: saved_state,
+                post_state: state,
                 state_read_records,
                 state_write_records,
                 state_addr: state_ptr,
@@ -117,3 +78,47 @@ impl Syscall for KeccakPermuteChip {
         None
     }
 }
+
+/// Applies the Keccak-f[1600] permutation to the state in place.
+pub(crate) fn keccakf(state: &mut [u64; STATE_SIZE]) {
+    for i in 0..NUM_ROUNDS {
+        let mut array: [u64; 5 * 5] = [0; 5 * 5];
+
+        // Theta
+        for x in 0..5 {
+            for y_count in 0..5 {
+                let y = y_count * 5;
+                array[x] ^= state[x + y];
+            }
+        }
+
+        for x in 0..5 {
+            for y_count in 0..5 {
+                let y = y_count * 5;
+                state[y + x] ^= array[(x + 4) % 5] ^ array[(x + 1) % 5].rotate_left(1);
+            }
+        }
+
+        // Rho and pi
+        let mut last = state[1];
+        for x in 0..24 {
+            array[0] = state[PI[x]];
+            state[PI[x]] = last.rotate_left(RHO[x]);
+            last = array[0];
+        }
+
+        // Chi
+        for y_step in 0..5 {
+            let y = y_step * 5;
+
+            array[..5].copy_from_slice(&state[y..(5 + y)]);
+
+            for x in 0..5 {
+                state[y + x] = array[x] ^ ((!array[(x + 1) % 5]) & (array[(x + 2) % 5]));
+            }
+        }
+
+        // Iota
+        state[0] ^= RC[i];
+    }
+}
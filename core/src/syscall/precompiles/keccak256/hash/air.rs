@@ -0,0 +1,287 @@
+use core::borrow::Borrow;
+
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::AbstractField;
+use p3_keccak_air::{KeccakAir, NUM_KECCAK_COLS, NUM_ROUNDS, U64_LIMBS};
+use p3_matrix::Matrix;
+
+use super::{
+    columns::{KeccakHashCols, NUM_KECCAK_HASH_COLS},
+    Keccak256HashChip, DIGEST_WORDS, RATE_BYTES, RATE_LANES, RATE_WORDS,
+};
+use crate::{
+    air::{SP1AirBuilder, SubAirBuilder},
+    bytes::ByteOpcode,
+    memory::MemoryCols,
+    runtime::SyscallCode,
+    syscall::precompiles::keccak256::STATE_SIZE,
+};
+
+impl<F> BaseAir<F> for Keccak256HashChip {
+    fn width(&self) -> usize {
+        NUM_KECCAK_HASH_COLS
+    }
+}
+
+impl<AB> Air<AB> for Keccak256HashChip
+where
+    AB: SP1AirBuilder,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+
+        let (local, next) = (main.row_slice(0), main.row_slice(1));
+        let local: &KeccakHashCols<AB::Var> = (*local).borrow();
+        let next: &KeccakHashCols<AB::Var> = (*next).borrow();
+
+        // Constrain the incrementing nonce.
+        builder.when_first_row().assert_zero(local.nonce);
+        builder
+            .when_transition()
+            .assert_eq(local.nonce + AB::Expr::one(), next.nonce);
+
+        let first_step = local.keccak.step_flags[0];
+        let final_step = local.keccak.step_flags[NUM_ROUNDS - 1];
+        let not_final_step = AB::Expr::one() - final_step;
+        let expr_2_pow_8 = AB::Expr::from_canonical_u32(1 << 8);
+
+        // Constrain the row flags.
+        builder.assert_bool(local.is_real);
+        builder.assert_bool(local.is_first_block);
+        builder.assert_bool(local.is_last_block);
+        builder
+            .when_not(local.is_real)
+            .assert_zero(local.is_first_block);
+        builder
+            .when_not(local.is_real)
+            .assert_zero(local.is_last_block);
+        builder.assert_eq(local.absorb, first_step * local.is_real);
+        builder.assert_eq(local.receive_ecall, local.absorb * local.is_first_block);
+        builder.assert_eq(
+            local.write_digest,
+            final_step * local.is_real * local.is_last_block,
+        );
+
+        // Constrain that the block stays the same throughout the 24 rows of its permutation.
+        let mut transition_builder = builder.when_transition();
+        let mut transition_not_final_builder = transition_builder.when(not_final_step);
+        transition_not_final_builder.assert_eq(local.shard, next.shard);
+        transition_not_final_builder.assert_eq(local.channel, next.channel);
+        transition_not_final_builder.assert_eq(local.clk, next.clk);
+        transition_not_final_builder.assert_eq(local.args_ptr, next.args_ptr);
+        transition_not_final_builder.assert_eq(local.output_ptr, next.output_ptr);
+        transition_not_final_builder.assert_eq(local.block_addr, next.block_addr);
+        transition_not_final_builder.assert_eq(local.remaining, next.remaining);
+        transition_not_final_builder.assert_eq(local.is_first_block, next.is_first_block);
+        transition_not_final_builder.assert_eq(local.is_last_block, next.is_last_block);
+        transition_not_final_builder.assert_eq(local.is_real, next.is_real);
+
+        // After the final step of a block, the next row starts a new hash unless this block is
+        // real and not the last one, in which case the next block continues this hash.
+        builder.when_transition().when(final_step).assert_eq(
+            next.is_real - next.is_first_block,
+            local.is_real - local.is_last_block,
+        );
+        builder
+            .when_first_row()
+            .assert_eq(local.is_real, local.is_first_block);
+
+        // The last row must be nonreal because NUM_ROUNDS is not a power of 2. This constraint
+        // ensures that the table does not end abruptly.
+        builder.when_last_row().assert_zero(local.is_real);
+
+        // Constrain a continuation block to follow on from the previous block. This needs no
+        // transition guard, since the first row never continues a hash.
+        let continuation = final_step * (next.is_real - next.is_first_block);
+        let mut continuation_builder = builder.when(continuation);
+        continuation_builder.assert_eq(local.shard, next.shard);
+        continuation_builder.assert_eq(local.channel, next.channel);
+        continuation_builder.assert_eq(local.clk, next.clk);
+        continuation_builder.assert_eq(local.args_ptr, next.args_ptr);
+        continuation_builder.assert_eq(local.output_ptr, next.output_ptr);
+        continuation_builder.assert_eq(
+            local.block_addr + AB::Expr::from_canonical_usize(RATE_BYTES),
+            next.block_addr,
+        );
+        continuation_builder.assert_eq(
+            local.remaining,
+            next.remaining + AB::Expr::from_canonical_usize(RATE_BYTES),
+        );
+        for i in 0..STATE_SIZE {
+            let (y, x) = (i / 5, i % 5);
+            for limb in 0..U64_LIMBS {
+                let output = local.keccak.a_prime_prime_prime(y, x, limb);
+                if i < RATE_LANES {
+                    // The next block is absorbed into the rate lanes of this output.
+                    let byte = 8 * i + 2 * limb;
+                    continuation_builder.assert_eq(
+                        next.prev_state[byte] + next.prev_state[byte + 1] * expr_2_pow_8.clone(),
+                        output,
+                    );
+                } else {
+                    // The capacity lanes are carried over unchanged.
+                    continuation_builder.assert_eq(next.keccak.a[y][x][limb], output);
+                }
+            }
+        }
+
+        // Read the arguments and start the sponge from the zero state in the first block.
+        for i in 0..2 {
+            builder.eval_memory_access(
+                local.shard,
+                local.channel,
+                local.clk,
+                local.args_ptr + AB::Expr::from_canonical_u32(i * 4),
+                &local.args_mem[i as usize],
+                local.receive_ecall,
+            );
+            builder.slice_range_check_u8(
+                &local.args_mem[i as usize].value().0,
+                local.shard,
+                local.channel,
+                local.receive_ecall,
+            );
+        }
+        let mut first_block_builder = builder.when(local.receive_ecall);
+        first_block_builder.assert_eq(local.input_ptr, local.args_mem[0].value().reduce::<AB>());
+        first_block_builder.assert_eq(local.input_len, local.args_mem[1].value().reduce::<AB>());
+        // Bound the input length by `KECCAK256_HASH_MAX_LEN`.
+        first_block_builder.assert_zero(local.args_mem[1].value()[3]);
+        first_block_builder.assert_eq(local.block_addr, local.input_ptr);
+        first_block_builder.assert_eq(local.remaining, local.input_len);
+        for byte in local.prev_state {
+            first_block_builder.assert_zero(byte);
+        }
+        for i in RATE_LANES..STATE_SIZE {
+            for limb in 0..U64_LIMBS {
+                first_block_builder.assert_zero(local.keccak.a[i / 5][i % 5][limb]);
+            }
+        }
+
+        // Constrain the padding. In the last block, `pad_start` flags the offset of the first
+        // padding byte, which is the number of remaining input bytes. Otherwise, at least a full
+        // block of input remains.
+        let mut pad_sum = AB::Expr::zero();
+        let mut pad_offset = AB::Expr::zero();
+        for (i, flag) in local.pad_start.iter().enumerate() {
+            builder.assert_bool(*flag);
+            pad_sum += (*flag).into();
+            pad_offset += AB::Expr::from_canonical_usize(i) * *flag;
+        }
+        builder.assert_eq(pad_sum, local.absorb * local.is_last_block);
+        builder
+            .when(local.absorb)
+            .when(local.is_last_block)
+            .assert_eq(local.remaining, pad_offset);
+        let remaining_minus_rate = local.remaining_minus_rate[0]
+            + local.remaining_minus_rate[1] * expr_2_pow_8.clone()
+            + local.remaining_minus_rate[2] * AB::Expr::from_canonical_u32(1 << 16);
+        let not_last_block = local.absorb - local.absorb * local.is_last_block;
+        builder.when(not_last_block.clone()).assert_eq(
+            local.remaining,
+            remaining_minus_rate + AB::Expr::from_canonical_usize(RATE_BYTES),
+        );
+        builder.slice_range_check_u8(
+            &local.remaining_minus_rate,
+            local.shard,
+            local.channel,
+            local.absorb,
+        );
+
+        // `is_data[i]` is whether byte `i` of the block is input, i.e. lies before the padding.
+        let mut is_data = vec![AB::Expr::zero(); RATE_BYTES];
+        for i in (0..RATE_BYTES - 1).rev() {
+            is_data[i] = is_data[i + 1].clone() + local.pad_start[i + 1];
+        }
+
+        // Read the words of the block that lie in the input.
+        for i in 0..RATE_WORDS {
+            builder.assert_eq(
+                local.word_read[i],
+                not_last_block.clone() + is_data[4 * i].clone(),
+            );
+            builder.eval_memory_access(
+                local.shard,
+                local.channel,
+                local.clk,
+                local.block_addr + AB::Expr::from_canonical_usize(i * 4),
+                &local.block_mem[i],
+                local.word_read[i],
+            );
+        }
+
+        // Absorb the block: input bytes, then `0x01`, zeros and a final `0x80`.
+        for i in 0..RATE_BYTES {
+            let input_byte = local.block_mem[i / 4].value()[i % 4];
+            let mut absorbed = input_byte
+                * (AB::Expr::one() - local.is_last_block + is_data[i].clone())
+                + local.pad_start[i];
+            if i == RATE_BYTES - 1 {
+                absorbed += AB::Expr::from_canonical_u8(0x80) * local.is_last_block;
+            }
+            builder
+                .when(local.absorb)
+                .assert_eq(local.absorbed[i], absorbed);
+            builder.send_byte(
+                AB::F::from_canonical_u32(ByteOpcode::XOR as u32),
+                local.state[i],
+                local.prev_state[i],
+                local.absorbed[i],
+                local.shard,
+                local.channel,
+                local.absorb,
+            );
+        }
+
+        // The permutation input holds the absorbed rate bytes.
+        for i in 0..RATE_LANES {
+            for limb in 0..U64_LIMBS {
+                let byte = 8 * i + 2 * limb;
+                builder.when(local.absorb).assert_eq(
+                    local.keccak.a[i / 5][i % 5][limb],
+                    local.state[byte] + local.state[byte + 1] * expr_2_pow_8.clone(),
+                );
+            }
+        }
+
+        // Write the digest, which is the first four lanes of the final state.
+        for i in 0..DIGEST_WORDS {
+            builder.eval_memory_access(
+                local.shard,
+                local.channel,
+                local.clk + AB::Expr::one(), // The clk increments by 1 after the reads.
+                local.output_ptr + AB::Expr::from_canonical_usize(i * 4),
+                &local.digest_mem[i],
+                local.write_digest,
+            );
+            let word = local.digest_mem[i].value();
+            let lane = i / 2;
+            for half in 0..2 {
+                let limb = 2 * (i % 2) + half;
+                builder.when(local.write_digest).assert_eq(
+                    word[2 * half] + word[2 * half + 1] * expr_2_pow_8.clone(),
+                    local.keccak.a_prime_prime_prime(lane / 5, lane % 5, limb),
+                );
+            }
+            builder.slice_range_check_u8(&word.0, local.shard, local.channel, local.write_digest);
+        }
+
+        // Receive the syscall in the first row of the first block.
+        builder.receive_syscall(
+            local.shard,
+            local.channel,
+            local.clk,
+            local.nonce,
+            AB::F::from_canonical_u32(SyscallCode::KECCAK256_HASH.syscall_id()),
+            local.args_ptr,
+            local.output_ptr,
+            local.receive_ecall,
+        );
+
+        let mut sub_builder =
+            SubAirBuilder::<AB, KeccakAir, AB::Var>::new(builder, 0..NUM_KECCAK_COLS);
+
+        // Eval the plonky3 keccak air
+        self.p3_keccak.eval(&mut sub_builder);
+    }
+}
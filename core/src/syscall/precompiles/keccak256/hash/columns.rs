@@ -0,0 +1,71 @@
+use core::mem::size_of;
+
+use p3_keccak_air::KeccakCols;
+use sp1_derive::AlignedBorrow;
+
+use crate::memory::{MemoryReadCols, MemoryWriteCols};
+
+use super::{DIGEST_WORDS, RATE_BYTES, RATE_WORDS};
+
+/// KeccakHashCols is the column layout for the Keccak-256 sponge.
+///
+/// Each absorbed block takes the 24 rows of one permutation. The per-block columns are held
+/// constant across those rows, and the blocks of a hash are laid out consecutively.
+#[derive(AlignedBorrow)]
+#[repr(C)]
+pub(crate) struct KeccakHashCols<T> {
+    /// Keccak columns from p3_keccak_air. Note it is assumed in trace gen to be the first field.
+    pub keccak: KeccakCols<T>,
+
+    pub shard: T,
+    pub channel: T,
+    pub clk: T,
+    pub nonce: T,
+    pub args_ptr: T,
+    pub output_ptr: T,
+    pub input_ptr: T,
+    pub input_len: T,
+
+    /// The address of the block absorbed by this permutation.
+    pub block_addr: T,
+
+    /// The number of input bytes left to absorb, starting at `block_addr`.
+    pub remaining: T,
+
+    /// The bytes of `remaining - RATE_BYTES`, which bound `remaining` in non-final blocks.
+    pub remaining_minus_rate: [T; 3],
+
+    pub is_first_block: T,
+    pub is_last_block: T,
+
+    /// Memory columns for the `[input_ptr, input_len]` arguments.
+    pub args_mem: [MemoryReadCols<T>; 2],
+
+    /// Memory columns for the block, and whether each word lies in the input.
+    pub block_mem: [MemoryReadCols<T>; RATE_WORDS],
+    pub word_read: [T; RATE_WORDS],
+
+    /// A one-hot flag for the offset of the first padding byte, set only in the last block.
+    pub pad_start: [T; RATE_BYTES],
+
+    /// The rate bytes of the state before and after absorbing the block, and the absorbed bytes.
+    pub prev_state: [T; RATE_BYTES],
+    pub absorbed: [T; RATE_BYTES],
+    pub state: [T; RATE_BYTES],
+
+    /// Memory columns for the digest.
+    pub digest_mem: [MemoryWriteCols<T>; DIGEST_WORDS],
+
+    /// If row is real and the first cycle of a block.
+    pub absorb: T,
+
+    /// If row is real and the first cycle of the first block.
+    pub receive_ecall: T,
+
+    /// If row is real and the final cycle of the last block.
+    pub write_digest: T,
+
+    pub is_real: T,
+}
+
+pub const NUM_KECCAK_HASH_COLS: usize = size_of::<KeccakHashCols<u8>>();
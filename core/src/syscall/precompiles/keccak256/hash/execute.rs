@@ -0,0 +1,83 @@
+use super::{
+    absorb_block, padded_block, Keccak256HashChip, Keccak256HashEvent, DIGEST_WORDS,
+    KECCAK256_HASH_MAX_LEN, RATE_BYTES,
+};
+use crate::{
+    runtime::Syscall,
+    syscall::precompiles::{
+        keccak256::{keccakf, STATE_SIZE},
+        SyscallContext,
+    },
+};
+
+impl Syscall for Keccak256HashChip {
+    fn num_extra_cycles(&self) -> u32 {
+        1
+    }
+
+    fn execute(&self, rt: &mut SyscallContext, arg1: u32, arg2: u32) -> Option<u32> {
+        let start_clk = rt.clk;
+        let args_ptr = arg1;
+        let output_ptr = arg2;
+
+        let (args_read_records, args) = rt.mr_slice(args_ptr, 2);
+        let (input_ptr, input_len) = (args[0], args[1]);
+        if input_ptr % 4 != 0 {
+            panic!("input_ptr must be word aligned, got {}", input_ptr);
+        }
+        if input_len as usize >= KECCAK256_HASH_MAX_LEN {
+            panic!(
+                "input_len must be below {}, got {}",
+                KECCAK256_HASH_MAX_LEN, input_len
+            );
+        }
+        // The arguments are read at the same clk as the input, so they must not share words.
+        let input_end = input_ptr + input_len.div_ceil(4) * 4;
+        if input_len > 0 && args_ptr < input_end && input_ptr < args_ptr + 8 {
+            panic!("The arguments of keccak256_hash must not overlap its input");
+        }
+
+        let (input_read_records, input_words) =
+            rt.mr_slice(input_ptr, input_len.div_ceil(4) as usize);
+        let mut input = input_words
+            .iter()
+            .flat_map(|word| word.to_le_bytes())
+            .collect::<Vec<_>>();
+        input.truncate(input_len as usize);
+
+        let mut state = [0u64; STATE_SIZE];
+        for index in 0..input.len() / RATE_BYTES + 1 {
+            absorb_block(&mut state, &padded_block(&input, index));
+            keccakf(&mut state);
+        }
+
+        // Increment the clk by 1 before writing because we read from memory at start_clk.
+        rt.clk += 1;
+        let digest = state[..DIGEST_WORDS / 2]
+            .iter()
+            .flat_map(|lane| [*lane as u32, (lane >> 32) as u32])
+            .collect::<Vec<_>>();
+        let output_write_records = rt.mw_slice(output_ptr, &digest);
+
+        let shard = rt.current_shard();
+        let channel = rt.current_channel();
+        let lookup_id = rt.syscall_lookup_id;
+        rt.record_mut()
+            .keccak256_hash_events
+            .push(Keccak256HashEvent {
+                lookup_id,
+                shard,
+                channel,
+                clk: start_clk,
+                args_ptr,
+                output_ptr,
+                input_ptr,
+                input_len,
+                args_read_records,
+                input_read_records,
+                output_write_records,
+            });
+
+        None
+    }
+}
@@ -0,0 +1,231 @@
+mod air;
+mod columns;
+mod execute;
+mod trace;
+
+use p3_keccak_air::KeccakAir;
+use serde::{Deserialize, Serialize};
+
+use super::STATE_SIZE;
+use crate::runtime::{MemoryReadRecord, MemoryWriteRecord};
+
+/// The number of bytes absorbed by each permutation, i.e. the rate of Keccak-256.
+pub(crate) const RATE_BYTES: usize = 136;
+
+/// The number of words absorbed by each permutation.
+const RATE_WORDS: usize = RATE_BYTES / 4;
+
+/// The number of 64-bit lanes absorbed by each permutation.
+const RATE_LANES: usize = RATE_BYTES / 8;
+
+/// The digest is the first four lanes of the final state, i.e. 8 words.
+const DIGEST_WORDS: usize = 8;
+
+/// The exclusive upper bound on the length of a hashed input, in bytes.
+///
+/// The bound keeps the byte counts tracked by the AIR far from the field modulus.
+pub const KECCAK256_HASH_MAX_LEN: usize = 1 << 24;
+
+/// A Keccak-256 hash of a memory buffer.
+///
+/// The syscall takes a pointer to the two words `[input_ptr, input_len]` and a pointer to the
+/// 8-word digest. `input_ptr` must be word aligned, and the arguments must not overlap the input.
+/// The final partial word of the input is read whole, but only its leading bytes are absorbed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Keccak256HashEvent {
+    pub lookup_id: u128,
+    pub shard: u32,
+    pub channel: u8,
+    pub clk: u32,
+    pub args_ptr: u32,
+    pub output_ptr: u32,
+    pub input_ptr: u32,
+    pub input_len: u32,
+    pub args_read_records: Vec<MemoryReadRecord>,
+    pub input_read_records: Vec<MemoryReadRecord>,
+    pub output_write_records: Vec<MemoryWriteRecord>,
+}
+
+impl Keccak256HashEvent {
+    /// The number of permutations needed to absorb the padded input.
+    pub fn num_blocks(&self) -> usize {
+        self.input_len as usize / RATE_BYTES + 1
+    }
+
+    /// The absorbed input bytes, without padding.
+    pub fn input(&self) -> Vec<u8> {
+        let mut input = self
+            .input_read_records
+            .iter()
+            .flat_map(|record| record.value.to_le_bytes())
+            .collect::<Vec<_>>();
+        input.truncate(self.input_len as usize);
+        input
+    }
+}
+
+/// Returns whether a hash of `num_blocks` permutations starts a new shard, given the number of
+/// permutations already in the current one.
+///
+/// Hashes vary in length, so unlike other precompiles they are split into shards by their number
+/// of permutations rather than by their count. The runtime and [crate::runtime::ExecutionRecord]
+/// must agree on this rule, since the nonce of a hash is its index within its shard.
+pub(crate) const fn keccak256_hash_starts_shard(
+    shard_blocks: usize,
+    num_blocks: usize,
+    threshold: usize,
+) -> bool {
+    shard_blocks > 0 && shard_blocks + num_blocks > threshold
+}
+
+/// Returns the `index`-th block of the padded `input`.
+///
+/// Keccak-256 pads with the bytes `0x01 .. 0x80`, which merge into `0x81` when only one byte of
+/// the final block is free.
+pub(crate) fn padded_block(input: &[u8], index: usize) -> [u8; RATE_BYTES] {
+    let start = index * RATE_BYTES;
+    let data = &input[start..input.len().min(start + RATE_BYTES)];
+    let mut block = [0u8; RATE_BYTES];
+    block[..data.len()].copy_from_slice(data);
+    if data.len() < RATE_BYTES {
+        block[data.len()] ^= 0x01;
+        block[RATE_BYTES - 1] ^= 0x80;
+    }
+    block
+}
+
+/// XORs a block into the rate lanes of the state.
+pub(crate) fn absorb_block(state: &mut [u64; STATE_SIZE], block: &[u8; RATE_BYTES]) {
+    for (lane, bytes) in state.iter_mut().zip(block.chunks_exact(8)) {
+        *lane ^= u64::from_le_bytes(bytes.try_into().unwrap());
+    }
+}
+
+pub struct Keccak256HashChip {
+    p3_keccak: KeccakAir,
+}
+
+impl Keccak256HashChip {
+    pub const fn new() -> Self {
+        Self {
+            p3_keccak: KeccakAir {},
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod hash_tests {
+    use std::collections::BTreeMap;
+
+    use tiny_keccak::Hasher;
+
+    use crate::io::SP1Stdin;
+    use crate::runtime::{Instruction, Opcode, Program, Runtime, SyscallCode};
+    use crate::stark::{CpuProver, RiscvAir, StarkGenericConfig};
+    use crate::utils::{self, prove, run_test, BabyBearPoseidon2, SP1CoreOpts};
+
+    use super::{padded_block, RATE_BYTES};
+
+    /// Builds a program hashing each input into its own digest buffer.
+    ///
+    /// Returns the program together with the pointer to each digest.
+    pub fn keccak256_hash_program(inputs: &[Vec<u8>]) -> (Program, Vec<u32>) {
+        let mut memory_image = BTreeMap::new();
+        let mut instructions = Vec::new();
+        let mut digest_ptrs = Vec::new();
+        let mut ptr = 0x1000;
+        for input in inputs {
+            let (args_ptr, digest_ptr, input_ptr) = (ptr, ptr + 8, ptr + 40);
+            memory_image.insert(args_ptr, input_ptr);
+            memory_image.insert(args_ptr + 4, input.len() as u32);
+            for (i, chunk) in input.chunks(4).enumerate() {
+                let mut word = [0u8; 4];
+                word[..chunk.len()].copy_from_slice(chunk);
+                memory_image.insert(input_ptr + 4 * i as u32, u32::from_le_bytes(word));
+            }
+            instructions.extend([
+                Instruction::new(
+                    Opcode::ADD,
+                    5,
+                    0,
+                    SyscallCode::KECCAK256_HASH as u32,
+                    false,
+                    true,
+                ),
+                Instruction::new(Opcode::ADD, 10, 0, args_ptr, false, true),
+                Instruction::new(Opcode::ADD, 11, 0, digest_ptr, false, true),
+                Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            ]);
+            digest_ptrs.push(digest_ptr);
+            ptr = (input_ptr + input.len() as u32 + 4) & !3;
+        }
+        let mut program = Program::new(instructions, 0, 0);
+        program.memory_image = memory_image;
+        (program, digest_ptrs)
+    }
+
+    fn test_inputs() -> Vec<Vec<u8>> {
+        [0, 1, 4, 133, 135, 136, 137, 300]
+            .into_iter()
+            .map(|len| (0..len).map(|i| (i * 31 + len) as u8).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_padded_block() {
+        let block = padded_block(&[0xAA; RATE_BYTES - 1], 0);
+        assert_eq!(block[RATE_BYTES - 1], 0x81);
+        let block = padded_block(&[0xAA; RATE_BYTES], 1);
+        assert_eq!(block[0], 0x01);
+        assert_eq!(block[RATE_BYTES - 1], 0x80);
+    }
+
+    #[test]
+    fn test_keccak256_hash_execute() {
+        utils::setup_logger();
+        let inputs = test_inputs();
+        let (program, digest_ptrs) = keccak256_hash_program(&inputs);
+        let mut runtime = Runtime::new(program, SP1CoreOpts::default());
+        runtime.run().unwrap();
+
+        for (input, digest_ptr) in inputs.iter().zip(digest_ptrs) {
+            let mut expected = [0u8; 32];
+            let mut keccak = tiny_keccak::Keccak::v256();
+            keccak.update(input);
+            keccak.finalize(&mut expected);
+
+            let digest = (0..8)
+                .flat_map(|i| runtime.word(digest_ptr + 4 * i).to_le_bytes())
+                .collect::<Vec<_>>();
+            assert_eq!(digest, expected);
+        }
+    }
+
+    #[test]
+    fn test_keccak256_hash_prove() {
+        utils::setup_logger();
+        let (program, _) = keccak256_hash_program(&test_inputs());
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+
+    #[test]
+    fn test_keccak256_hash_prove_split() {
+        utils::setup_logger();
+        let (program, _) = keccak256_hash_program(&test_inputs());
+
+        // Split the hashes across several shards, so that hashes of several blocks start
+        // new shards.
+        let mut opts = SP1CoreOpts::default();
+        opts.split_opts.keccak_split_threshold = 3;
+
+        let config = BabyBearPoseidon2::new();
+        let (proof, _, _) =
+            prove::<_, CpuProver<_, _>>(program.clone(), &SP1Stdin::new(), config, opts).unwrap();
+
+        let config = BabyBearPoseidon2::new();
+        let mut challenger = config.challenger();
+        let machine = RiscvAir::machine(config);
+        let (_, vk) = machine.setup(&program);
+        machine.verify(&vk, &proof, &mut challenger).unwrap();
+    }
+}
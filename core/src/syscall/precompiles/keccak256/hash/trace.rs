@@ -0,0 +1,278 @@
+use std::borrow::BorrowMut;
+
+use p3_field::PrimeField32;
+use p3_keccak_air::{generate_trace_rows, NUM_KECCAK_COLS, NUM_ROUNDS};
+use p3_matrix::dense::RowMajorMatrix;
+use p3_matrix::Matrix;
+use p3_maybe_rayon::prelude::{ParallelIterator, ParallelSlice};
+
+use crate::bytes::event::ByteRecord;
+use crate::bytes::{ByteLookupEvent, ByteOpcode};
+use crate::syscall::precompiles::keccak256::{keccakf, STATE_SIZE};
+use crate::{runtime::Program, stark::MachineRecord};
+
+use crate::{air::MachineAir, runtime::ExecutionRecord};
+
+use super::{
+    absorb_block,
+    columns::{KeccakHashCols, NUM_KECCAK_HASH_COLS},
+    padded_block, Keccak256HashChip, RATE_BYTES, RATE_LANES, RATE_WORDS,
+};
+
+/// Returns the little-endian bytes of the rate lanes of the state.
+fn rate_bytes(state: &[u64; STATE_SIZE]) -> Vec<u8> {
+    state[..RATE_LANES]
+        .iter()
+        .flat_map(|lane| lane.to_le_bytes())
+        .collect()
+}
+
+impl<F: PrimeField32> MachineAir<F> for Keccak256HashChip {
+    type Record = ExecutionRecord;
+    type Program = Program;
+
+    fn name(&self) -> String {
+        "Keccak256Hash".to_string()
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        output: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let num_events = input.keccak256_hash_events.len();
+        let chunk_size = std::cmp::max(num_events / num_cpus::get(), 1);
+
+        // Use par_chunks to generate the trace in parallel.
+        let rows_and_records = (0..num_events)
+            .collect::<Vec<_>>()
+            .par_chunks(chunk_size)
+            .map(|chunk| {
+                let mut record = ExecutionRecord::default();
+                let mut new_byte_lookup_events = Vec::new();
+
+                // Run the sponge over each input, keeping the state before and after absorbing
+                // each block.
+                let sponges = chunk
+                    .iter()
+                    .map(|event_index| {
+                        let event = &input.keccak256_hash_events[*event_index];
+                        let input = event.input();
+                        let mut state = [0u64; STATE_SIZE];
+                        let mut blocks = Vec::new();
+                        for index in 0..event.num_blocks() {
+                            let block = padded_block(&input, index);
+                            let prev_state = state;
+                            absorb_block(&mut state, &block);
+                            blocks.push((block, prev_state, state));
+                            keccakf(&mut state);
+                        }
+                        blocks
+                    })
+                    .collect::<Vec<_>>();
+
+                // Then generate all the p3_keccak_air traces at once.
+                let perm_inputs = sponges
+                    .iter()
+                    .flatten()
+                    .map(|(_, _, state)| *state)
+                    .collect::<Vec<_>>();
+                let p3_keccak_trace = generate_trace_rows::<F>(perm_inputs);
+
+                let mut rows = Vec::new();
+                let mut perm_index = 0;
+                for (event_index, blocks) in chunk.iter().zip(sponges.iter()) {
+                    let event = &input.keccak256_hash_events[*event_index];
+                    let shard = event.shard;
+                    let channel = event.channel;
+                    let num_blocks = blocks.len();
+
+                    for (block_index, (block, prev_state, state)) in blocks.iter().enumerate() {
+                        let is_first_block = block_index == 0;
+                        let is_last_block = block_index == num_blocks - 1;
+                        let block_offset = block_index * RATE_BYTES;
+                        let remaining = event.input_len as usize - block_offset;
+
+                        // Create all the rows for the permutation.
+                        for i in 0..NUM_ROUNDS {
+                            let p3_keccak_row = p3_keccak_trace.row(i + perm_index * NUM_ROUNDS);
+                            let mut row = [F::zero(); NUM_KECCAK_HASH_COLS];
+                            // Copy p3_keccak_row into start of cols
+                            row[..NUM_KECCAK_COLS]
+                                .copy_from_slice(p3_keccak_row.collect::<Vec<_>>().as_slice());
+                            let cols: &mut KeccakHashCols<F> = row.as_mut_slice().borrow_mut();
+
+                            cols.shard = F::from_canonical_u32(shard);
+                            cols.channel = F::from_canonical_u8(channel);
+                            cols.clk = F::from_canonical_u32(event.clk);
+                            cols.args_ptr = F::from_canonical_u32(event.args_ptr);
+                            cols.output_ptr = F::from_canonical_u32(event.output_ptr);
+                            cols.input_ptr = F::from_canonical_u32(event.input_ptr);
+                            cols.input_len = F::from_canonical_u32(event.input_len);
+                            cols.block_addr =
+                                F::from_canonical_usize(event.input_ptr as usize + block_offset);
+                            cols.remaining = F::from_canonical_usize(remaining);
+                            cols.is_first_block = F::from_bool(is_first_block);
+                            cols.is_last_block = F::from_bool(is_last_block);
+                            cols.is_real = F::one();
+
+                            // If this is the first row of the block, then absorb it.
+                            if i == 0 {
+                                cols.absorb = F::one();
+
+                                if is_first_block {
+                                    cols.receive_ecall = F::one();
+                                    for (j, read_record) in
+                                        event.args_read_records.iter().enumerate()
+                                    {
+                                        cols.args_mem[j].populate(
+                                            channel,
+                                            *read_record,
+                                            &mut new_byte_lookup_events,
+                                        );
+                                        new_byte_lookup_events.add_u8_range_checks(
+                                            shard,
+                                            channel,
+                                            &read_record.value.to_le_bytes(),
+                                        );
+                                    }
+                                }
+
+                                // The bytes of `remaining - RATE_BYTES` are range checked in
+                                // every block, and left as zero in the last one.
+                                let mut remaining_minus_rate = [0u8; 4];
+                                if is_last_block {
+                                    cols.pad_start[remaining] = F::one();
+                                } else {
+                                    remaining_minus_rate =
+                                        ((remaining - RATE_BYTES) as u32).to_le_bytes();
+                                }
+                                for (col, byte) in cols
+                                    .remaining_minus_rate
+                                    .iter_mut()
+                                    .zip(remaining_minus_rate)
+                                {
+                                    *col = F::from_canonical_u8(byte);
+                                }
+                                new_byte_lookup_events.add_u8_range_checks(
+                                    shard,
+                                    channel,
+                                    &remaining_minus_rate[..3],
+                                );
+
+                                let first_word = block_index * RATE_WORDS;
+                                for (j, read_record) in event
+                                    .input_read_records
+                                    .iter()
+                                    .skip(first_word)
+                                    .take(RATE_WORDS)
+                                    .enumerate()
+                                {
+                                    cols.block_mem[j].populate(
+                                        channel,
+                                        *read_record,
+                                        &mut new_byte_lookup_events,
+                                    );
+                                    cols.word_read[j] = F::one();
+                                }
+
+                                let prev_bytes = rate_bytes(prev_state);
+                                let state_bytes = rate_bytes(state);
+                                for j in 0..RATE_BYTES {
+                                    cols.prev_state[j] = F::from_canonical_u8(prev_bytes[j]);
+                                    cols.absorbed[j] = F::from_canonical_u8(block[j]);
+                                    cols.state[j] = F::from_canonical_u8(state_bytes[j]);
+                                    new_byte_lookup_events.add_byte_lookup_event(
+                                        ByteLookupEvent::new(
+                                            shard,
+                                            channel,
+                                            ByteOpcode::XOR,
+                                            state_bytes[j] as u16,
+                                            0,
+                                            prev_bytes[j],
+                                            block[j],
+                                        ),
+                                    );
+                                }
+                            }
+
+                            // If this is the last row of the last block, then write the digest.
+                            if i == NUM_ROUNDS - 1 && is_last_block {
+                                cols.write_digest = F::one();
+                                for (j, write_record) in
+                                    event.output_write_records.iter().enumerate()
+                                {
+                                    cols.digest_mem[j].populate(
+                                        channel,
+                                        *write_record,
+                                        &mut new_byte_lookup_events,
+                                    );
+                                    new_byte_lookup_events.add_u8_range_checks(
+                                        shard,
+                                        channel,
+                                        &write_record.value.to_le_bytes(),
+                                    );
+                                }
+                            }
+
+                            rows.push(row);
+                        }
+                        perm_index += 1;
+                    }
+                }
+                record.add_byte_lookup_events(new_byte_lookup_events);
+                (rows, record)
+            })
+            .collect::<Vec<_>>();
+
+        // Generate the trace rows for each event.
+        let mut rows: Vec<[F; NUM_KECCAK_HASH_COLS]> = vec![];
+        for (mut row, mut record) in rows_and_records {
+            rows.append(&mut row);
+            output.append(&mut record);
+        }
+
+        let nb_rows = rows.len();
+        let mut padded_nb_rows = nb_rows.next_power_of_two();
+        if padded_nb_rows == 2 || padded_nb_rows == 1 {
+            padded_nb_rows = 4;
+        }
+        if padded_nb_rows > nb_rows {
+            let dummy_keccak_rows = generate_trace_rows::<F>(vec![[0; STATE_SIZE]]);
+            let mut dummy_rows = Vec::new();
+            for i in 0..NUM_ROUNDS {
+                let dummy_row = dummy_keccak_rows.row(i);
+                let mut row = [F::zero(); NUM_KECCAK_HASH_COLS];
+                row[..NUM_KECCAK_COLS].copy_from_slice(dummy_row.collect::<Vec<_>>().as_slice());
+                dummy_rows.push(row);
+            }
+            rows.append(
+                &mut dummy_rows
+                    .iter()
+                    .cloned()
+                    .cycle()
+                    .take(padded_nb_rows - nb_rows)
+                    .collect::<Vec<_>>(),
+            );
+        }
+
+        // Convert the trace to a row major matrix.
+        let mut trace = RowMajorMatrix::new(
+            rows.into_iter().flatten().collect::<Vec<_>>(),
+            NUM_KECCAK_HASH_COLS,
+        );
+
+        // Write the nonce to the trace.
+        for i in 0..trace.height() {
+            let cols: &mut KeccakHashCols<F> =
+                trace.values[i * NUM_KECCAK_HASH_COLS..(i + 1) * NUM_KECCAK_HASH_COLS].borrow_mut();
+            cols.nonce = F::from_canonical_usize(i);
+        }
+
+        trace
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        !shard.keccak256_hash_events.is_empty()
+    }
+}
@@ -1,8 +1,12 @@
 mod air;
 pub mod columns;
 mod execute;
+mod hash;
 mod trace;
 
+pub(crate) use execute::keccakf;
+pub use hash::*;
+
 use p3_keccak_air::KeccakAir;
 use serde::{Deserialize, Serialize};
 
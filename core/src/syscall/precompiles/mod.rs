@@ -1,20 +1,33 @@
+pub mod blake;
 pub mod edwards;
+pub mod fptower;
 pub mod keccak256;
+pub mod poseidon2;
 pub mod sha256;
-pub mod uint256;
+pub mod sha512;
+pub mod uint;
 pub mod weierstrass;
-use crate::operations::field::params::{NumLimbs, NumWords};
+use crate::air::{BaseAirBuilder, SP1AirBuilder};
+use crate::memory::{value_as_limbs, MemoryReadCols};
+use crate::operations::field::params::{FieldParameters, Limbs, NumLimbs, NumWords};
 use crate::runtime::SyscallContext;
+use crate::utils::ec::scalar_mul::{SCALAR_MUL_SCALAR_BITS, SCALAR_MUL_SCALAR_WORDS};
 use crate::utils::ec::weierstrass::bls12_381::bls12381_decompress;
 use crate::utils::ec::weierstrass::secp256k1::secp256k1_decompress;
+use crate::utils::ec::weierstrass::secp256r1::secp256r1_decompress;
 use crate::utils::ec::CurveType;
-use crate::utils::ec::{AffinePoint, EllipticCurve};
+use crate::utils::ec::{AffinePoint, EllipticCurve, EllipticCurveParameters};
 use crate::utils::{bytes_to_words_le_vec, words_to_bytes_le_vec};
 use crate::{runtime::MemoryReadRecord, runtime::MemoryWriteRecord};
+use p3_air::AirBuilder;
+use p3_field::AbstractField;
 use typenum::Unsigned;
 
 use core::fmt::Debug;
+use num::BigUint;
+use p3_field::PrimeField32;
 use serde::{Deserialize, Serialize};
+use sp1_derive::AlignedBorrow;
 
 /// Elliptic curve add event.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -131,6 +144,175 @@ pub fn create_ec_double_event<E: EllipticCurve>(
     }
 }
 
+/// Elliptic curve scalar multiplication event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ECMulEvent {
+    pub lookup_id: u128,
+    pub shard: u32,
+    pub channel: u8,
+    pub clk: u32,
+    pub p_ptr: u32,
+    pub p: Vec<u32>,
+    pub scalar_ptr: u32,
+    pub scalar: Vec<u32>,
+    pub result: Vec<u32>,
+    pub p_read_records: Vec<MemoryReadRecord>,
+    pub scalar_read_records: Vec<MemoryReadRecord>,
+    pub p_write_records: Vec<MemoryWriteRecord>,
+}
+
+/// Create an elliptic curve scalar multiplication event. It takes a pointer to a point and a
+/// pointer to a little endian scalar of [SCALAR_MUL_SCALAR_BITS] bits, multiplies the point by the
+/// scalar with a fixed window, and writes the result back to the memory location of the point.
+///
+/// Returns `None` without writing the point if the product is the point at infinity, which has no
+/// affine coordinates.
+pub fn create_ec_mul_event<E: EllipticCurve>(
+    rt: &mut SyscallContext,
+    arg1: u32,
+    arg2: u32,
+) -> Option<ECMulEvent> {
+    let start_clk = rt.clk;
+    let p_ptr = arg1;
+    assert!(p_ptr % 4 == 0, "p_ptr must be 4-byte aligned");
+    let scalar_ptr = arg2;
+    assert!(scalar_ptr % 4 == 0, "scalar_ptr must be 4-byte aligned");
+
+    let num_words = <E::BaseField as NumWords>::WordsCurvePoint::USIZE;
+
+    // The point and the scalar are both read at the same clk, so they must not overlap.
+    let p_end = p_ptr + 4 * num_words as u32;
+    let scalar_end = scalar_ptr + 4 * SCALAR_MUL_SCALAR_WORDS as u32;
+    assert!(
+        p_end <= scalar_ptr || scalar_end <= p_ptr,
+        "the point and the scalar must not overlap"
+    );
+
+    let (p_read_records, p) = rt.mr_slice(p_ptr, num_words);
+    let (scalar_read_records, scalar) = rt.mr_slice(scalar_ptr, SCALAR_MUL_SCALAR_WORDS);
+
+    let p_affine = AffinePoint::<E>::from_words_le(&p);
+    let scalar_biguint = BigUint::from_slice(&scalar);
+    let result_affine = p_affine.fixed_window_scalar_mul(&scalar_biguint)?;
+    let result = result_affine.to_words_le();
+
+    // Increment the clk by 1 before writing because we read from memory at start_clk.
+    rt.clk += 1;
+    let p_write_records = rt.mw_slice(p_ptr, &result);
+
+    Some(ECMulEvent {
+        lookup_id: rt.syscall_lookup_id,
+        shard: rt.current_shard(),
+        channel: rt.current_channel(),
+        clk: start_clk,
+        p_ptr,
+        p,
+        scalar_ptr,
+        scalar,
+        result,
+        p_read_records,
+        scalar_read_records,
+        p_write_records,
+    })
+}
+
+/// A set of columns holding the coordinates of an affine point.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct AffinePointCols<T, P: FieldParameters> {
+    pub x: Limbs<T, P::Limbs>,
+    pub y: Limbs<T, P::Limbs>,
+}
+
+impl<T: Copy, P: FieldParameters> AffinePointCols<T, P> {
+    /// Returns the limbs of the x coordinate followed by the limbs of the y coordinate.
+    pub fn limbs(&self) -> Vec<T> {
+        self.x.0.iter().chain(self.y.0.iter()).copied().collect()
+    }
+}
+
+impl<F: PrimeField32, P: FieldParameters> AffinePointCols<F, P> {
+    pub fn populate<E: EllipticCurveParameters>(&mut self, point: &AffinePoint<E>) {
+        self.x = P::to_limbs_field::<F, _>(&point.x);
+        self.y = P::to_limbs_field::<F, _>(&point.y);
+    }
+}
+
+/// Constrains the flags which split the rows of a chip into operations of `num_steps` rows each,
+/// such as the windows of a scalar multiplication, followed by padding rows.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn eval_step_flags<AB: SP1AirBuilder>(
+    builder: &mut AB,
+    num_steps: usize,
+    is_real: AB::Var,
+    is_first_step: AB::Var,
+    is_last_step: AB::Var,
+    step: AB::Var,
+    step_inverse: AB::Var,
+    next_is_real: AB::Var,
+    next_is_first_step: AB::Var,
+) {
+    let last_step = AB::Expr::from_canonical_usize(num_steps - 1);
+
+    builder.assert_bool(is_real);
+    builder.assert_bool(is_first_step);
+    builder.assert_bool(is_last_step);
+    builder.when(is_first_step).assert_one(is_real);
+    builder.when(is_last_step).assert_one(is_real);
+
+    // The step index starts at zero, and the last step is the only one with the last index.
+    builder.when(is_first_step).assert_zero(step);
+    builder
+        .when(is_last_step)
+        .assert_eq(step, last_step.clone());
+    builder
+        .when(is_real - is_last_step)
+        .assert_one((step - last_step) * step_inverse);
+
+    // An operation starts on the first row and after the last step of another one, and padding
+    // rows are only followed by padding rows.
+    builder.when_first_row().assert_eq(is_first_step, is_real);
+    // The table can't end in the middle of an operation, which would skip its write.
+    builder.when_last_row().assert_eq(is_last_step, is_real);
+    builder
+        .when_transition()
+        .when(is_last_step)
+        .assert_eq(next_is_first_step, next_is_real);
+    builder
+        .when_transition()
+        .when_not(is_real)
+        .assert_zero(next_is_real);
+
+    // The other steps continue the operation of the previous row.
+    let mut transition_builder = builder.when_transition();
+    let mut builder = transition_builder.when(is_real - is_last_step);
+    builder.assert_one(next_is_real);
+    builder.assert_zero(next_is_first_step);
+}
+
+/// Constrains the bits of the scalar on the first step of a scalar multiplication, and that the
+/// bits are boolean.
+pub(crate) fn eval_scalar_bits<AB: SP1AirBuilder>(
+    builder: &mut AB,
+    is_first_step: AB::Var,
+    scalar_access: &[MemoryReadCols<AB::Var>; SCALAR_MUL_SCALAR_WORDS],
+    scalar_bits: &[AB::Var; SCALAR_MUL_SCALAR_BITS],
+) {
+    for bit in scalar_bits.iter() {
+        builder.assert_bool(*bit);
+    }
+
+    for (i, byte) in value_as_limbs(scalar_access).into_iter().enumerate() {
+        let bits = &scalar_bits[i * 8..(i + 1) * 8];
+        let value = bits
+            .iter()
+            .enumerate()
+            .map(|(j, bit)| AB::Expr::from_canonical_u32(1 << j) * *bit)
+            .sum::<AB::Expr>();
+        builder.when(is_first_step).assert_eq(byte, value);
+    }
+}
+
 /// Elliptic curve point decompress event.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ECDecompressEvent {
@@ -167,6 +349,7 @@ pub fn create_ec_decompress_event<E: EllipticCurve>(
 
     let decompress_fn = match E::CURVE_TYPE {
         CurveType::Secp256k1 => secp256k1_decompress::<E>,
+        CurveType::Secp256r1 => secp256r1_decompress::<E>,
         CurveType::Bls12381 => bls12381_decompress::<E>,
         _ => panic!("Unsupported curve"),
     };
@@ -0,0 +1,194 @@
+use core::borrow::Borrow;
+
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::AbstractField;
+use p3_matrix::Matrix;
+use sp1_primitives::RC_16_30_U32;
+
+use super::{
+    columns::{Poseidon2PermuteCols, NUM_POSEIDON2_PERMUTE_COLS},
+    external_linear_layer, internal_linear_layer, Poseidon2PermuteChip, NUM_EXTERNAL_ROUNDS,
+    NUM_INTERNAL_ROUNDS, POSEIDON2_WIDTH,
+};
+use crate::{
+    air::SP1AirBuilder, memory::MemoryCols, operations::BabyBearWordRangeChecker,
+    runtime::SyscallCode,
+};
+
+impl<F> BaseAir<F> for Poseidon2PermuteChip {
+    fn width(&self) -> usize {
+        NUM_POSEIDON2_PERMUTE_COLS
+    }
+}
+
+impl<AB> Air<AB> for Poseidon2PermuteChip
+where
+    AB: SP1AirBuilder,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let (local, next) = (main.row_slice(0), main.row_slice(1));
+        let local: &Poseidon2PermuteCols<AB::Var> = (*local).borrow();
+        let next: &Poseidon2PermuteCols<AB::Var> = (*next).borrow();
+
+        // Constrain the incrementing nonce.
+        builder.when_first_row().assert_zero(local.nonce);
+        builder
+            .when_transition()
+            .assert_eq(local.nonce + AB::Expr::one(), next.nonce);
+
+        builder.assert_bool(local.is_real);
+
+        // Read the state at clk and write the permuted state at clk + 1. Both must be canonical
+        // BabyBear elements.
+        for i in 0..POSEIDON2_WIDTH {
+            let addr = local.state_ptr + AB::Expr::from_canonical_usize(i * 4);
+            builder.eval_memory_access(
+                local.shard,
+                local.channel,
+                local.clk,
+                addr.clone(),
+                &local.state_read_mem[i],
+                local.is_real,
+            );
+            builder.eval_memory_access(
+                local.shard,
+                local.channel,
+                local.clk + AB::Expr::one(),
+                addr,
+                &local.state_write_mem[i],
+                local.is_real,
+            );
+
+            for (mem, range_checker) in [
+                (
+                    local.state_read_mem[i].value(),
+                    local.input_range_checkers[i],
+                ),
+                (
+                    local.state_write_mem[i].value(),
+                    local.output_range_checkers[i],
+                ),
+            ] {
+                builder.slice_range_check_u8(&mem.0, local.shard, local.channel, local.is_real);
+                BabyBearWordRangeChecker::<AB::F>::range_check(
+                    builder,
+                    *mem,
+                    range_checker,
+                    local.is_real.into(),
+                );
+            }
+
+            builder.when(local.is_real).assert_eq(
+                local.state_write_mem[i].value().reduce::<AB>(),
+                local.output_state[i],
+            );
+        }
+
+        // The permutation constraints are not gated by `is_real`, since padding rows hold the
+        // permutation of the zero state.
+        let mut initial_state: [AB::Expr; POSEIDON2_WIDTH] =
+            core::array::from_fn(|i| local.state_read_mem[i].value().reduce::<AB>());
+        external_linear_layer(&mut initial_state);
+        for i in 0..POSEIDON2_WIDTH {
+            builder.assert_eq(local.external_rounds_state[0][i], initial_state[i].clone());
+        }
+
+        // Apply the first half of external rounds, the internal rounds and the second half of
+        // external rounds.
+        for r in 0..NUM_EXTERNAL_ROUNDS / 2 {
+            eval_external_round(builder, local, r);
+        }
+        eval_internal_rounds(builder, local);
+        for r in NUM_EXTERNAL_ROUNDS / 2..NUM_EXTERNAL_ROUNDS {
+            eval_external_round(builder, local, r);
+        }
+
+        builder.receive_syscall(
+            local.shard,
+            local.channel,
+            local.clk,
+            local.nonce,
+            AB::F::from_canonical_u32(SyscallCode::POSEIDON2_PERMUTE.syscall_id()),
+            local.state_ptr,
+            AB::Expr::zero(),
+            local.is_real,
+        );
+    }
+}
+
+fn eval_external_round<AB: SP1AirBuilder>(
+    builder: &mut AB,
+    local: &Poseidon2PermuteCols<AB::Var>,
+    r: usize,
+) {
+    let external_state = local.external_rounds_state[r];
+
+    // Add the round constants.
+    let round = if r < NUM_EXTERNAL_ROUNDS / 2 {
+        r
+    } else {
+        r + NUM_INTERNAL_ROUNDS
+    };
+    let add_rc: [AB::Expr; POSEIDON2_WIDTH] = core::array::from_fn(|i| {
+        external_state[i].into() + AB::F::from_wrapped_u32(RC_16_30_U32[round][i])
+    });
+
+    // Apply the sboxes, with a column for the cube of each input to keep the degree at 3.
+    let mut state: [AB::Expr; POSEIDON2_WIDTH] = core::array::from_fn(|i| {
+        let sbox_deg_3 = local.external_rounds_sbox[r][i];
+        builder.assert_eq(
+            sbox_deg_3,
+            add_rc[i].clone() * add_rc[i].clone() * add_rc[i].clone(),
+        );
+        sbox_deg_3 * sbox_deg_3 * add_rc[i].clone()
+    });
+
+    // Apply the linear layer.
+    external_linear_layer(&mut state);
+
+    let next_state = if r == NUM_EXTERNAL_ROUNDS / 2 - 1 {
+        local.internal_rounds_state
+    } else if r == NUM_EXTERNAL_ROUNDS - 1 {
+        local.output_state
+    } else {
+        local.external_rounds_state[r + 1]
+    };
+    for i in 0..POSEIDON2_WIDTH {
+        builder.assert_eq(next_state[i], state[i].clone());
+    }
+}
+
+fn eval_internal_rounds<AB: SP1AirBuilder>(
+    builder: &mut AB,
+    local: &Poseidon2PermuteCols<AB::Var>,
+) {
+    let mut state: [AB::Expr; POSEIDON2_WIDTH] =
+        core::array::from_fn(|i| local.internal_rounds_state[i].into());
+    for r in 0..NUM_INTERNAL_ROUNDS {
+        // Add the round constant to the first element.
+        let round = r + NUM_EXTERNAL_ROUNDS / 2;
+        let add_rc = if r == 0 {
+            state[0].clone()
+        } else {
+            local.internal_rounds_s0[r - 1].into()
+        } + AB::Expr::from_wrapped_u32(RC_16_30_U32[round][0]);
+
+        // Apply the sbox to the first element.
+        let sbox_deg_3 = local.internal_rounds_sbox[r];
+        builder.assert_eq(sbox_deg_3, add_rc.clone() * add_rc.clone() * add_rc.clone());
+        state[0] = sbox_deg_3 * sbox_deg_3 * add_rc;
+
+        // Apply the linear layer. Only the first element gets a column, since the others stay
+        // linear in the columns.
+        internal_linear_layer(&mut state);
+        if r < NUM_INTERNAL_ROUNDS - 1 {
+            builder.assert_eq(local.internal_rounds_s0[r], state[0].clone());
+        }
+    }
+
+    let external_state = local.external_rounds_state[NUM_EXTERNAL_ROUNDS / 2];
+    for i in 0..POSEIDON2_WIDTH {
+        builder.assert_eq(external_state[i], state[i].clone());
+    }
+}
@@ -0,0 +1,50 @@
+use core::mem::size_of;
+
+use sp1_derive::AlignedBorrow;
+
+use crate::memory::{MemoryReadCols, MemoryWriteCols};
+use crate::operations::BabyBearWordRangeChecker;
+
+use super::{NUM_EXTERNAL_ROUNDS, NUM_INTERNAL_ROUNDS, POSEIDON2_WIDTH};
+
+pub const NUM_POSEIDON2_PERMUTE_COLS: usize = size_of::<Poseidon2PermuteCols<u8>>();
+
+/// Poseidon2PermuteCols is the column layout for the Poseidon2 permutation.
+///
+/// Each permutation takes a single row. Round states are kept only at the boundaries where the
+/// constraints would otherwise exceed degree 3.
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct Poseidon2PermuteCols<T> {
+    pub shard: T,
+    pub channel: T,
+    pub clk: T,
+    pub nonce: T,
+    pub state_ptr: T,
+
+    /// Memory columns for the state, read at `clk` and written at `clk + 1`.
+    pub state_read_mem: [MemoryReadCols<T>; POSEIDON2_WIDTH],
+    pub state_write_mem: [MemoryWriteCols<T>; POSEIDON2_WIDTH],
+
+    /// Checks that the input and output words are canonical BabyBear elements.
+    pub input_range_checkers: [BabyBearWordRangeChecker<T>; POSEIDON2_WIDTH],
+    pub output_range_checkers: [BabyBearWordRangeChecker<T>; POSEIDON2_WIDTH],
+
+    /// The state at the start of each external round, after the initial linear layer.
+    pub external_rounds_state: [[T; POSEIDON2_WIDTH]; NUM_EXTERNAL_ROUNDS],
+
+    /// The state at the start of the internal rounds.
+    pub internal_rounds_state: [T; POSEIDON2_WIDTH],
+
+    /// The first element of the state after each internal round but the last.
+    pub internal_rounds_s0: [T; NUM_INTERNAL_ROUNDS - 1],
+
+    /// The cubes of the S-box inputs.
+    pub external_rounds_sbox: [[T; POSEIDON2_WIDTH]; NUM_EXTERNAL_ROUNDS],
+    pub internal_rounds_sbox: [T; NUM_INTERNAL_ROUNDS],
+
+    /// The output of the permutation.
+    pub output_state: [T; POSEIDON2_WIDTH],
+
+    pub is_real: T,
+}
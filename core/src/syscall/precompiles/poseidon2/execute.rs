@@ -0,0 +1,56 @@
+use p3_baby_bear::BabyBear;
+use p3_field::{AbstractField, PrimeField32};
+use p3_symmetric::Permutation;
+
+use super::{Poseidon2PermuteChip, Poseidon2PermuteEvent, POSEIDON2_WIDTH};
+use crate::{runtime::Syscall, syscall::precompiles::SyscallContext, utils::inner_perm};
+
+impl Syscall for Poseidon2PermuteChip {
+    fn num_extra_cycles(&self) -> u32 {
+        1
+    }
+
+    fn execute(&self, rt: &mut SyscallContext, arg1: u32, arg2: u32) -> Option<u32> {
+        let start_clk = rt.clk;
+        let state_ptr = arg1;
+        if arg2 != 0 {
+            panic!("Expected arg2 to be 0, got {}", arg2);
+        }
+
+        let (state_read_records, state_values) = rt.mr_slice(state_ptr, POSEIDON2_WIDTH);
+        let pre_state: [u32; POSEIDON2_WIDTH] = state_values.try_into().unwrap();
+        if let Some(value) = pre_state.iter().find(|x| **x >= BabyBear::ORDER_U32) {
+            panic!(
+                "Poseidon2 state element {} is not a canonical BabyBear element",
+                value
+            );
+        }
+
+        let post_state = inner_perm()
+            .permute(pre_state.map(BabyBear::from_canonical_u32))
+            .map(|x| x.as_canonical_u32());
+
+        // Increment the clk by 1 before writing because we read from memory at start_clk.
+        rt.clk += 1;
+        let state_write_records = rt.mw_slice(state_ptr, &post_state);
+
+        let shard = rt.current_shard();
+        let channel = rt.current_channel();
+        let lookup_id = rt.syscall_lookup_id;
+        rt.record_mut()
+            .poseidon2_permute_events
+            .push(Poseidon2PermuteEvent {
+                lookup_id,
+                shard,
+                channel,
+                clk: start_clk,
+                state_ptr,
+                pre_state,
+                post_state,
+                state_read_records,
+                state_write_records,
+            });
+
+        None
+    }
+}
@@ -0,0 +1,169 @@
+mod air;
+pub mod columns;
+mod execute;
+mod trace;
+
+use p3_baby_bear::{MONTY_INVERSE, POSEIDON2_INTERNAL_MATRIX_DIAG_16_BABYBEAR_MONTY};
+use p3_field::{AbstractField, PrimeField32};
+use p3_poseidon2::matmul_internal;
+use serde::{Deserialize, Serialize};
+
+use crate::runtime::{MemoryReadRecord, MemoryWriteRecord};
+
+/// The width of the permutation, in BabyBear elements. Each element takes one word of memory.
+pub const POSEIDON2_WIDTH: usize = 16;
+
+pub(crate) const NUM_EXTERNAL_ROUNDS: usize = 8;
+pub(crate) const NUM_INTERNAL_ROUNDS: usize = 13;
+
+/// A Poseidon2 permutation of a state of canonical BabyBear elements, in place.
+///
+/// The permutation is the one returned by [sp1_primitives::poseidon2_init], which the recursion
+/// layer uses for hashing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Poseidon2PermuteEvent {
+    pub lookup_id: u128,
+    pub shard: u32,
+    pub channel: u8,
+    pub clk: u32,
+    pub state_ptr: u32,
+    pub pre_state: [u32; POSEIDON2_WIDTH],
+    pub post_state: [u32; POSEIDON2_WIDTH],
+    pub state_read_records: Vec<MemoryReadRecord>,
+    pub state_write_records: Vec<MemoryWriteRecord>,
+}
+
+#[derive(Default)]
+pub struct Poseidon2PermuteChip;
+
+impl Poseidon2PermuteChip {
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+fn apply_m_4<AF: AbstractField>(x: &mut [AF]) {
+    let t01 = x[0].clone() + x[1].clone();
+    let t23 = x[2].clone() + x[3].clone();
+    let t0123 = t01.clone() + t23.clone();
+    let t01123 = t0123.clone() + x[1].clone();
+    let t01233 = t0123.clone() + x[3].clone();
+    // The order here is important. Need to overwrite x[0] and x[2] after x[1] and x[3].
+    x[3] = t01233.clone() + x[0].double(); // 3*x[0] + x[1] + x[2] + 2*x[3]
+    x[1] = t01123.clone() + x[2].double(); // x[0] + 2*x[1] + 3*x[2] + x[3]
+    x[0] = t01123 + t01; // 2*x[0] + 3*x[1] + x[2] + x[3]
+    x[2] = t01233 + t23; // x[0] + x[1] + 2*x[2] + 3*x[3]
+}
+
+pub(crate) fn external_linear_layer<AF: AbstractField>(state: &mut [AF; POSEIDON2_WIDTH]) {
+    for j in (0..POSEIDON2_WIDTH).step_by(4) {
+        apply_m_4(&mut state[j..j + 4]);
+    }
+    let sums: [AF; 4] = core::array::from_fn(|k| {
+        (0..POSEIDON2_WIDTH)
+            .step_by(4)
+            .map(|j| state[j + k].clone())
+            .sum::<AF>()
+    });
+
+    for j in 0..POSEIDON2_WIDTH {
+        state[j] += sums[j % 4].clone();
+    }
+}
+
+pub(crate) fn internal_linear_layer<AF: AbstractField>(state: &mut [AF; POSEIDON2_WIDTH]) {
+    let matmul_constants: [<AF as AbstractField>::F; POSEIDON2_WIDTH] =
+        POSEIDON2_INTERNAL_MATRIX_DIAG_16_BABYBEAR_MONTY
+            .map(|x| <AF as AbstractField>::F::from_wrapped_u32(x.as_canonical_u32()));
+    matmul_internal(state, matmul_constants);
+    let monty_inverse = AF::from_wrapped_u32(MONTY_INVERSE.as_canonical_u32());
+    state.iter_mut().for_each(|i| *i *= monty_inverse.clone());
+}
+
+#[cfg(test)]
+pub mod permute_tests {
+    use std::collections::BTreeMap;
+
+    use p3_baby_bear::BabyBear;
+    use p3_field::{AbstractField, PrimeField32};
+    use p3_symmetric::Permutation;
+
+    use crate::runtime::{Instruction, Opcode, Program, Runtime, SyscallCode};
+    use crate::stark::CpuProver;
+    use crate::utils::{self, inner_perm, run_test, SP1CoreOpts};
+
+    use super::POSEIDON2_WIDTH;
+
+    /// Builds a program permuting each state in place, and returns the pointer to each state.
+    pub fn poseidon2_permute_program(states: &[[u32; POSEIDON2_WIDTH]]) -> (Program, Vec<u32>) {
+        let mut memory_image = BTreeMap::new();
+        let mut instructions = Vec::new();
+        let mut state_ptrs = Vec::new();
+        for (i, state) in states.iter().enumerate() {
+            let state_ptr = 0x1000 + 0x100 * i as u32;
+            for (j, word) in state.iter().enumerate() {
+                memory_image.insert(state_ptr + 4 * j as u32, *word);
+            }
+            instructions.extend([
+                Instruction::new(
+                    Opcode::ADD,
+                    5,
+                    0,
+                    SyscallCode::POSEIDON2_PERMUTE as u32,
+                    false,
+                    true,
+                ),
+                Instruction::new(Opcode::ADD, 10, 0, state_ptr, false, true),
+                Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            ]);
+            state_ptrs.push(state_ptr);
+        }
+        let mut program = Program::new(instructions, 0, 0);
+        program.memory_image = memory_image;
+        (program, state_ptrs)
+    }
+
+    fn test_states() -> Vec<[u32; POSEIDON2_WIDTH]> {
+        let modulus = BabyBear::ORDER_U32;
+        vec![
+            [0; POSEIDON2_WIDTH],
+            core::array::from_fn(|i| i as u32),
+            [modulus - 1; POSEIDON2_WIDTH],
+            core::array::from_fn(|i| (i as u32 + 1).wrapping_mul(0x9E37_79B9) % modulus),
+        ]
+    }
+
+    #[test]
+    fn test_poseidon2_permute_execute() {
+        utils::setup_logger();
+        let states = test_states();
+        let (program, state_ptrs) = poseidon2_permute_program(&states);
+        let mut runtime = Runtime::new(program, SP1CoreOpts::default());
+        runtime.run().unwrap();
+
+        let perm = inner_perm();
+        for (state, state_ptr) in states.iter().zip(state_ptrs) {
+            let expected = perm.permute(state.map(BabyBear::from_canonical_u32));
+            let actual: [u32; POSEIDON2_WIDTH] =
+                core::array::from_fn(|i| runtime.word(state_ptr + 4 * i as u32));
+            assert_eq!(actual, expected.map(|x| x.as_canonical_u32()));
+        }
+    }
+
+    #[test]
+    fn test_poseidon2_permute_prove() {
+        utils::setup_logger();
+        let (program, _) = poseidon2_permute_program(&test_states());
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_poseidon2_permute_non_canonical() {
+        let mut state = [0; POSEIDON2_WIDTH];
+        state[3] = BabyBear::ORDER_U32;
+        let (program, _) = poseidon2_permute_program(&[state]);
+        let mut runtime = Runtime::new(program, SP1CoreOpts::default());
+        runtime.run().unwrap();
+    }
+}
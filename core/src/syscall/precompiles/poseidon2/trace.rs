@@ -0,0 +1,193 @@
+use std::borrow::BorrowMut;
+
+use p3_field::PrimeField32;
+use p3_matrix::dense::RowMajorMatrix;
+use p3_matrix::Matrix;
+use p3_maybe_rayon::prelude::{ParallelIterator, ParallelSlice};
+use sp1_primitives::RC_16_30_U32;
+
+use super::{
+    columns::{Poseidon2PermuteCols, NUM_POSEIDON2_PERMUTE_COLS},
+    external_linear_layer, internal_linear_layer, Poseidon2PermuteChip, NUM_EXTERNAL_ROUNDS,
+    NUM_INTERNAL_ROUNDS, POSEIDON2_WIDTH,
+};
+use crate::{
+    air::MachineAir,
+    bytes::event::ByteRecord,
+    runtime::{ExecutionRecord, Program},
+    stark::MachineRecord,
+    utils::pad_rows,
+};
+
+impl<F: PrimeField32> MachineAir<F> for Poseidon2PermuteChip {
+    type Record = ExecutionRecord;
+    type Program = Program;
+
+    fn name(&self) -> String {
+        "Poseidon2Permute".to_string()
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        output: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let chunk_size = std::cmp::max(input.poseidon2_permute_events.len() / num_cpus::get(), 1);
+
+        let rows_and_records = input
+            .poseidon2_permute_events
+            .par_chunks(chunk_size)
+            .map(|events| {
+                let mut record = ExecutionRecord::default();
+                let mut new_byte_lookup_events = Vec::new();
+                let rows = events
+                    .iter()
+                    .map(|event| {
+                        let mut row = [F::zero(); NUM_POSEIDON2_PERMUTE_COLS];
+                        let cols: &mut Poseidon2PermuteCols<F> = row.as_mut_slice().borrow_mut();
+                        let shard = event.shard;
+                        let channel = event.channel;
+
+                        cols.shard = F::from_canonical_u32(shard);
+                        cols.channel = F::from_canonical_u8(channel);
+                        cols.clk = F::from_canonical_u32(event.clk);
+                        cols.state_ptr = F::from_canonical_u32(event.state_ptr);
+                        cols.is_real = F::one();
+
+                        for i in 0..POSEIDON2_WIDTH {
+                            let read_record = event.state_read_records[i];
+                            cols.state_read_mem[i].populate(
+                                channel,
+                                read_record,
+                                &mut new_byte_lookup_events,
+                            );
+                            cols.input_range_checkers[i].populate(read_record.value);
+                            new_byte_lookup_events.add_u8_range_checks(
+                                shard,
+                                channel,
+                                &read_record.value.to_le_bytes(),
+                            );
+
+                            let write_record = event.state_write_records[i];
+                            cols.state_write_mem[i].populate(
+                                channel,
+                                write_record,
+                                &mut new_byte_lookup_events,
+                            );
+                            cols.output_range_checkers[i].populate(write_record.value);
+                            new_byte_lookup_events.add_u8_range_checks(
+                                shard,
+                                channel,
+                                &write_record.value.to_le_bytes(),
+                            );
+                        }
+
+                        populate_permutation(
+                            cols,
+                            event.pre_state.map(F::from_canonical_u32),
+                            Some(event.post_state.map(F::from_canonical_u32)),
+                        );
+
+                        row
+                    })
+                    .collect::<Vec<_>>();
+                record.add_byte_lookup_events(new_byte_lookup_events);
+                (rows, record)
+            })
+            .collect::<Vec<_>>();
+
+        let mut rows = Vec::new();
+        for (mut chunk_rows, mut record) in rows_and_records {
+            rows.append(&mut chunk_rows);
+            output.append(&mut record);
+        }
+
+        // Padding rows hold the permutation of the zero state.
+        pad_rows(&mut rows, || {
+            let mut row = [F::zero(); NUM_POSEIDON2_PERMUTE_COLS];
+            let cols: &mut Poseidon2PermuteCols<F> = row.as_mut_slice().borrow_mut();
+            populate_permutation(cols, [F::zero(); POSEIDON2_WIDTH], None);
+            row
+        });
+
+        let mut trace = RowMajorMatrix::new(
+            rows.into_iter().flatten().collect::<Vec<_>>(),
+            NUM_POSEIDON2_PERMUTE_COLS,
+        );
+
+        // Write the nonces to the trace.
+        for i in 0..trace.height() {
+            let cols: &mut Poseidon2PermuteCols<F> = trace.values
+                [i * NUM_POSEIDON2_PERMUTE_COLS..(i + 1) * NUM_POSEIDON2_PERMUTE_COLS]
+                .borrow_mut();
+            cols.nonce = F::from_canonical_usize(i);
+        }
+
+        trace
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        !shard.poseidon2_permute_events.is_empty()
+    }
+}
+
+/// Populates the round columns of the permutation of `input`.
+fn populate_permutation<F: PrimeField32>(
+    cols: &mut Poseidon2PermuteCols<F>,
+    input: [F; POSEIDON2_WIDTH],
+    expected_output: Option<[F; POSEIDON2_WIDTH]>,
+) {
+    let mut state = input;
+    external_linear_layer(&mut state);
+
+    for r in 0..NUM_EXTERNAL_ROUNDS {
+        if r == NUM_EXTERNAL_ROUNDS / 2 {
+            cols.internal_rounds_state = state;
+            state = populate_internal_rounds(cols, state);
+        }
+        cols.external_rounds_state[r] = state;
+
+        // Add the round constants and apply the sboxes.
+        let round = if r < NUM_EXTERNAL_ROUNDS / 2 {
+            r
+        } else {
+            r + NUM_INTERNAL_ROUNDS
+        };
+        for i in 0..POSEIDON2_WIDTH {
+            let add_rc = state[i] + F::from_wrapped_u32(RC_16_30_U32[round][i]);
+            let sbox_deg_3 = add_rc * add_rc * add_rc;
+            cols.external_rounds_sbox[r][i] = sbox_deg_3;
+            state[i] = sbox_deg_3 * sbox_deg_3 * add_rc;
+        }
+
+        // Apply the linear layer.
+        external_linear_layer(&mut state);
+    }
+
+    if let Some(expected_output) = expected_output {
+        assert_eq!(state, expected_output);
+    }
+    cols.output_state = state;
+}
+
+/// Populates the internal round columns, and returns the state after the internal rounds.
+fn populate_internal_rounds<F: PrimeField32>(
+    cols: &mut Poseidon2PermuteCols<F>,
+    mut state: [F; POSEIDON2_WIDTH],
+) -> [F; POSEIDON2_WIDTH] {
+    for r in 0..NUM_INTERNAL_ROUNDS {
+        // Add the round constant and apply the sbox to the first element.
+        let round = r + NUM_EXTERNAL_ROUNDS / 2;
+        let add_rc = state[0] + F::from_wrapped_u32(RC_16_30_U32[round][0]);
+        let sbox_deg_3 = add_rc * add_rc * add_rc;
+        cols.internal_rounds_sbox[r] = sbox_deg_3;
+        state[0] = sbox_deg_3 * sbox_deg_3 * add_rc;
+
+        // Apply the linear layer.
+        internal_linear_layer(&mut state);
+        if r < NUM_INTERNAL_ROUNDS - 1 {
+            cols.internal_rounds_s0[r] = state[0];
+        }
+    }
+    state
+}
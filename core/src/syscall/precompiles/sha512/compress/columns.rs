@@ -0,0 +1,109 @@
+use std::mem::size_of;
+
+use sp1_derive::AlignedBorrow;
+
+use crate::air::Word;
+use crate::memory::MemoryReadWriteCols;
+use crate::operations::AddU64Operation;
+use crate::operations::AndOperation;
+use crate::operations::FixedRotateRightU64Operation;
+use crate::operations::NotOperation;
+use crate::operations::XorOperation;
+
+pub const NUM_SHA512_COMPRESS_COLS: usize = size_of::<Sha512CompressCols<u8>>();
+
+/// A set of columns needed to compute the SHA-512 compression function.
+///
+/// Each sha512 compress syscall is processed over 96 rows, split into 12 octets. The first octet is
+/// for initialization, the next 10 octets are for compression, and the last octet is for finalize.
+/// Every 64-bit word is handled as its low and high 32-bit words, in that order.
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct Sha512CompressCols<T> {
+    /// Inputs.
+    pub shard: T,
+    pub channel: T,
+    pub nonce: T,
+    pub clk: T,
+    pub w_ptr: T,
+    pub h_ptr: T,
+
+    pub start: T,
+
+    /// Which cycle within the octet we are currently processing.
+    pub octet: [T; 8],
+
+    /// This will specify which octet we are currently processing.
+    ///  - The first octet is for initialize.
+    ///  - The next 10 octets are for compress.
+    ///  - The last octet is for finalize.
+    pub octet_num: [T; 12],
+
+    /// Memory access to the low and high words of a 64-bit word. During init and compression,
+    /// this is read only. During finalize, this is used to write the result into memory.
+    pub mem: [MemoryReadWriteCols<T>; 2],
+    /// Address of the low word being written/read. During init and finalize, this is A-H. During
+    /// compression, this is w[i] being read only.
+    pub mem_addr: T,
+
+    pub a: [Word<T>; 2],
+    pub b: [Word<T>; 2],
+    pub c: [Word<T>; 2],
+    pub d: [Word<T>; 2],
+    pub e: [Word<T>; 2],
+    pub f: [Word<T>; 2],
+    pub g: [Word<T>; 2],
+    pub h: [Word<T>; 2],
+
+    /// Current value of K[i]. This is a constant array that loops around every 80 iterations.
+    pub k: [Word<T>; 2],
+
+    pub e_rr_14: FixedRotateRightU64Operation<T>,
+    pub e_rr_18: FixedRotateRightU64Operation<T>,
+    pub e_rr_41: FixedRotateRightU64Operation<T>,
+    pub s1_intermediate: [XorOperation<T>; 2],
+    /// `S1 := (e rightrotate 14) xor (e rightrotate 18) xor (e rightrotate 41)`.
+    pub s1: [XorOperation<T>; 2],
+
+    pub e_and_f: [AndOperation<T>; 2],
+    pub e_not: [NotOperation<T>; 2],
+    pub e_not_and_g: [AndOperation<T>; 2],
+    /// `ch := (e and f) xor ((not e) and g)`.
+    pub ch: [XorOperation<T>; 2],
+
+    /// `temp1 := h + S1 + ch + k[i] + w[i]`.
+    pub temp1: AddU64Operation<T>,
+
+    pub a_rr_28: FixedRotateRightU64Operation<T>,
+    pub a_rr_34: FixedRotateRightU64Operation<T>,
+    pub a_rr_39: FixedRotateRightU64Operation<T>,
+    pub s0_intermediate: [XorOperation<T>; 2],
+    /// `S0 := (a rightrotate 28) xor (a rightrotate 34) xor (a rightrotate 39)`.
+    pub s0: [XorOperation<T>; 2],
+
+    pub a_and_b: [AndOperation<T>; 2],
+    pub a_and_c: [AndOperation<T>; 2],
+    pub b_and_c: [AndOperation<T>; 2],
+    pub maj_intermediate: [XorOperation<T>; 2],
+    /// `maj := (a and b) xor (a and c) xor (b and c)`.
+    pub maj: [XorOperation<T>; 2],
+
+    /// `temp2 := S0 + maj`.
+    pub temp2: AddU64Operation<T>,
+
+    /// The next value of `e` is `d + temp1`.
+    pub d_add_temp1: AddU64Operation<T>,
+    /// The next value of `a` is `temp1 + temp2`.
+    pub temp1_add_temp2: AddU64Operation<T>,
+
+    /// During finalize, this is one of a-h and is being written into `mem`.
+    pub finalized_operand: [Word<T>; 2],
+    pub finalize_add: AddU64Operation<T>,
+
+    pub is_initialize: T,
+    pub is_compression: T,
+    pub is_finalize: T,
+    pub is_last_row: T,
+
+    pub is_real: T,
+}
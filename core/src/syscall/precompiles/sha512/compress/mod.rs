@@ -0,0 +1,218 @@
+mod air;
+mod columns;
+mod execute;
+mod trace;
+
+use serde::{Deserialize, Serialize};
+
+use crate::runtime::{MemoryReadRecord, MemoryWriteRecord};
+
+pub const SHA512_COMPRESS_K: [u64; 80] = [
+    0x428a2f98_d728ae22,
+    0x71374491_23ef65cd,
+    0xb5c0fbcf_ec4d3b2f,
+    0xe9b5dba5_8189dbbc,
+    0x3956c25b_f348b538,
+    0x59f111f1_b605d019,
+    0x923f82a4_af194f9b,
+    0xab1c5ed5_da6d8118,
+    0xd807aa98_a3030242,
+    0x12835b01_45706fbe,
+    0x243185be_4ee4b28c,
+    0x550c7dc3_d5ffb4e2,
+    0x72be5d74_f27b896f,
+    0x80deb1fe_3b1696b1,
+    0x9bdc06a7_25c71235,
+    0xc19bf174_cf692694,
+    0xe49b69c1_9ef14ad2,
+    0xefbe4786_384f25e3,
+    0x0fc19dc6_8b8cd5b5,
+    0x240ca1cc_77ac9c65,
+    0x2de92c6f_592b0275,
+    0x4a7484aa_6ea6e483,
+    0x5cb0a9dc_bd41fbd4,
+    0x76f988da_831153b5,
+    0x983e5152_ee66dfab,
+    0xa831c66d_2db43210,
+    0xb00327c8_98fb213f,
+    0xbf597fc7_beef0ee4,
+    0xc6e00bf3_3da88fc2,
+    0xd5a79147_930aa725,
+    0x06ca6351_e003826f,
+    0x14292967_0a0e6e70,
+    0x27b70a85_46d22ffc,
+    0x2e1b2138_5c26c926,
+    0x4d2c6dfc_5ac42aed,
+    0x53380d13_9d95b3df,
+    0x650a7354_8baf63de,
+    0x766a0abb_3c77b2a8,
+    0x81c2c92e_47edaee6,
+    0x92722c85_1482353b,
+    0xa2bfe8a1_4cf10364,
+    0xa81a664b_bc423001,
+    0xc24b8b70_d0f89791,
+    0xc76c51a3_0654be30,
+    0xd192e819_d6ef5218,
+    0xd6990624_5565a910,
+    0xf40e3585_5771202a,
+    0x106aa070_32bbd1b8,
+    0x19a4c116_b8d2d0c8,
+    0x1e376c08_5141ab53,
+    0x2748774c_df8eeb99,
+    0x34b0bcb5_e19b48a8,
+    0x391c0cb3_c5c95a63,
+    0x4ed8aa4a_e3418acb,
+    0x5b9cca4f_7763e373,
+    0x682e6ff3_d6b2b8a3,
+    0x748f82ee_5defb2fc,
+    0x78a5636f_43172f60,
+    0x84c87814_a1f0ab72,
+    0x8cc70208_1a6439ec,
+    0x90befffa_23631e28,
+    0xa4506ceb_de82bde9,
+    0xbef9a3f7_b2c67915,
+    0xc67178f2_e372532b,
+    0xca273ece_ea26619c,
+    0xd186b8c7_21c0c207,
+    0xeada7dd6_cde0eb1e,
+    0xf57d4f7f_ee6ed178,
+    0x06f067aa_72176fba,
+    0x0a637dc5_a2c898a6,
+    0x113f9804_bef90dae,
+    0x1b710b35_131c471b,
+    0x28db77f5_23047d84,
+    0x32caab7b_40c72493,
+    0x3c9ebe0a_15c9bebc,
+    0x431d67c4_9c100d4c,
+    0x4cc5d4be_cb3e42b6,
+    0x597f299c_fc657e2a,
+    0x5fcb6fab_3ad6faec,
+    0x6c44198c_4a475817,
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Sha512CompressEvent {
+    pub lookup_id: u128,
+    pub shard: u32,
+    pub channel: u8,
+    pub clk: u32,
+    pub w_ptr: u32,
+    pub h_ptr: u32,
+    pub w: Vec<u64>,
+    pub h: [u64; 8],
+    pub h_read_records: [[MemoryReadRecord; 2]; 8],
+    pub w_i_read_records: Vec<[MemoryReadRecord; 2]>,
+    pub h_write_records: [[MemoryWriteRecord; 2]; 8],
+}
+
+/// Implements the SHA-512 compress operation which loops over i = [0, 79] and modifies A-H in each
+/// iteration. The inputs to the syscall are a pointer to the 80 word array W and a pointer to the
+/// 8 word array H, where each word is a little-endian 64-bit integer.
+///
+/// In the AIR, each SHA-512 compress syscall takes up 96 rows. The first and last 8 rows are for
+/// initialization and finalize respectively. The middle 80 rows are for compression. Each row
+/// operates over a single 64-bit word.
+#[derive(Default)]
+pub struct Sha512CompressChip;
+
+impl Sha512CompressChip {
+    pub const fn new() -> Self {
+        Self {}
+    }
+}
+
+#[cfg(test)]
+pub mod compress_tests {
+
+    use crate::{
+        runtime::{Instruction, Opcode, Program, Runtime, SyscallCode},
+        stark::CpuProver,
+        utils::{run_test, setup_logger, SP1CoreOpts},
+    };
+
+    /// The SHA-512 initial hash value.
+    const SHA512_IV: [u64; 8] = [
+        0x6a09e667_f3bcc908,
+        0xbb67ae85_84caa73b,
+        0x3c6ef372_fe94f82b,
+        0xa54ff53a_5f1d36f1,
+        0x510e527f_ade682d1,
+        0x9b05688c_2b3e6c1f,
+        0x1f83d9ab_fb41bd6b,
+        0x5be0cd19_137e2179,
+    ];
+
+    /// The SHA-512 digest of `"abc"`.
+    const SHA512_ABC: [u64; 8] = [
+        0xddaf35a1_93617aba,
+        0xcc417349_ae204131,
+        0x12e6fa4e_89a97ea2,
+        0x0a9eeee6_4b55d39a,
+        0x2192992a_274fc1a8,
+        0x36ba3c23_a3feebbd,
+        0x454d4423_643ce80e,
+        0x2a9ac94f_a54ca49f,
+    ];
+
+    /// A program hashing the single padded block of `"abc"` with the extend and compress syscalls.
+    pub fn sha512_program() -> (Program, u32) {
+        let w_ptr = 0x1000;
+        let h_ptr = 0x2000;
+        let mut program = Program::new(
+            vec![
+                Instruction::new(
+                    Opcode::ADD,
+                    5,
+                    0,
+                    SyscallCode::SHA512_EXTEND as u32,
+                    false,
+                    true,
+                ),
+                Instruction::new(Opcode::ADD, 10, 0, w_ptr, false, true),
+                Instruction::new(Opcode::ADD, 11, 0, 0, false, true),
+                Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+                Instruction::new(
+                    Opcode::ADD,
+                    5,
+                    0,
+                    SyscallCode::SHA512_COMPRESS as u32,
+                    false,
+                    true,
+                ),
+                Instruction::new(Opcode::ADD, 10, 0, w_ptr, false, true),
+                Instruction::new(Opcode::ADD, 11, 0, h_ptr, false, true),
+                Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            ],
+            0,
+            0,
+        );
+
+        let mut w = [0u64; 80];
+        w[0] = 0x61626380_00000000;
+        w[15] = 24;
+        for (ptr, words) in [(w_ptr, &w[..]), (h_ptr, &SHA512_IV[..])] {
+            for (i, word) in words.iter().enumerate() {
+                let addr = ptr + 8 * i as u32;
+                program.memory_image.insert(addr, *word as u32);
+                program.memory_image.insert(addr + 4, (*word >> 32) as u32);
+            }
+        }
+        (program, h_ptr)
+    }
+
+    #[test]
+    fn test_sha512_compress_prove() {
+        setup_logger();
+        let (program, h_ptr) = sha512_program();
+
+        let mut runtime = Runtime::new(program.clone(), SP1CoreOpts::default());
+        runtime.run().unwrap();
+        for (i, word) in SHA512_ABC.iter().enumerate() {
+            let addr = h_ptr + 8 * i as u32;
+            let value = (runtime.word(addr + 4) as u64) << 32 | runtime.word(addr) as u64;
+            assert_eq!(value, *word);
+        }
+
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+}
@@ -0,0 +1,253 @@
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::AbstractField;
+use p3_matrix::Matrix;
+
+use super::{Sha512ExtendChip, Sha512ExtendCols, NUM_SHA512_EXTEND_COLS};
+use crate::air::{BaseAirBuilder, SP1AirBuilder, Word};
+use crate::memory::MemoryCols;
+use crate::operations::{
+    AddU64Operation, FixedRotateRightU64Operation, FixedShiftRightU64Operation, XorOperation,
+};
+use crate::runtime::SyscallCode;
+use core::borrow::Borrow;
+
+impl<F> BaseAir<F> for Sha512ExtendChip {
+    fn width(&self) -> usize {
+        NUM_SHA512_EXTEND_COLS
+    }
+}
+
+impl<AB> Air<AB> for Sha512ExtendChip
+where
+    AB: SP1AirBuilder,
+{
+    fn eval(&self, builder: &mut AB) {
+        // Initialize columns.
+        let main = builder.main();
+        let (local, next) = (main.row_slice(0), main.row_slice(1));
+        let local: &Sha512ExtendCols<AB::Var> = (*local).borrow();
+        let next: &Sha512ExtendCols<AB::Var> = (*next).borrow();
+
+        // Constrain the incrementing nonce.
+        builder.when_first_row().assert_zero(local.nonce);
+        builder
+            .when_transition()
+            .assert_eq(local.nonce + AB::Expr::one(), next.nonce);
+
+        let i_start = AB::F::from_canonical_u32(16);
+        let nb_bytes_in_u64 = AB::F::from_canonical_u32(8);
+
+        // Evaluate the control flags.
+        self.eval_flags(builder);
+
+        // Copy over the inputs until the result has been computed (every 64 rows).
+        builder
+            .when_transition()
+            .when_not(local.cycle_64_end.result)
+            .assert_eq(local.shard, next.shard);
+        builder
+            .when_transition()
+            .when_not(local.cycle_64_end.result)
+            .assert_eq(local.clk, next.clk);
+        builder
+            .when_transition()
+            .when_not(local.cycle_64_end.result)
+            .assert_eq(local.channel, next.channel);
+        builder
+            .when_transition()
+            .when_not(local.cycle_64_end.result)
+            .assert_eq(local.w_ptr, next.w_ptr);
+
+        let clk = local.clk + (local.i - i_start);
+
+        // Read w[i-15], w[i-2], w[i-16] and w[i-7].
+        for (offset, access) in [
+            (15, &local.w_i_minus_15),
+            (2, &local.w_i_minus_2),
+            (16, &local.w_i_minus_16),
+            (7, &local.w_i_minus_7),
+        ] {
+            builder.eval_memory_access_slice(
+                local.shard,
+                local.channel,
+                clk.clone(),
+                local.w_ptr + (local.i - AB::F::from_canonical_u32(offset)) * nb_bytes_in_u64,
+                access,
+                local.is_real,
+            );
+        }
+
+        let w_i_minus_15 = [
+            *local.w_i_minus_15[0].value(),
+            *local.w_i_minus_15[1].value(),
+        ];
+        let w_i_minus_2 = [*local.w_i_minus_2[0].value(), *local.w_i_minus_2[1].value()];
+        let w_i_minus_16 = [
+            *local.w_i_minus_16[0].value(),
+            *local.w_i_minus_16[1].value(),
+        ];
+        let w_i_minus_7 = [*local.w_i_minus_7[0].value(), *local.w_i_minus_7[1].value()];
+
+        // Compute `s0`.
+        // w[i-15] rightrotate 1.
+        FixedRotateRightU64Operation::<AB::F>::eval(
+            builder,
+            w_i_minus_15,
+            1,
+            local.w_i_minus_15_rr_1,
+            local.shard,
+            local.channel,
+            local.is_real,
+        );
+        // w[i-15] rightrotate 8.
+        FixedRotateRightU64Operation::<AB::F>::eval(
+            builder,
+            w_i_minus_15,
+            8,
+            local.w_i_minus_15_rr_8,
+            local.shard,
+            local.channel,
+            local.is_real,
+        );
+        // w[i-15] rightshift 7.
+        FixedShiftRightU64Operation::<AB::F>::eval(
+            builder,
+            w_i_minus_15,
+            7,
+            local.w_i_minus_15_rs_7,
+            local.shard,
+            local.channel,
+            local.is_real,
+        );
+        for k in 0..2 {
+            // (w[i-15] rightrotate 1) xor (w[i-15] rightrotate 8)
+            XorOperation::<AB::F>::eval(
+                builder,
+                local.w_i_minus_15_rr_1.value[k],
+                local.w_i_minus_15_rr_8.value[k],
+                local.s0_intermediate[k],
+                local.shard,
+                local.channel,
+                local.is_real,
+            );
+            // s0 := (w[i-15] rightrotate 1) xor (w[i-15] rightrotate 8) xor (w[i-15] rightshift 7)
+            XorOperation::<AB::F>::eval(
+                builder,
+                local.s0_intermediate[k].value,
+                local.w_i_minus_15_rs_7.value[k],
+                local.s0[k],
+                local.shard,
+                local.channel,
+                local.is_real,
+            );
+        }
+
+        // Compute `s1`.
+        // w[i-2] rightrotate 19.
+        FixedRotateRightU64Operation::<AB::F>::eval(
+            builder,
+            w_i_minus_2,
+            19,
+            local.w_i_minus_2_rr_19,
+            local.shard,
+            local.channel,
+            local.is_real,
+        );
+        // w[i-2] rightrotate 61.
+        FixedRotateRightU64Operation::<AB::F>::eval(
+            builder,
+            w_i_minus_2,
+            61,
+            local.w_i_minus_2_rr_61,
+            local.shard,
+            local.channel,
+            local.is_real,
+        );
+        // w[i-2] rightshift 6.
+        FixedShiftRightU64Operation::<AB::F>::eval(
+            builder,
+            w_i_minus_2,
+            6,
+            local.w_i_minus_2_rs_6,
+            local.shard,
+            local.channel,
+            local.is_real,
+        );
+        for k in 0..2 {
+            // (w[i-2] rightrotate 19) xor (w[i-2] rightrotate 61)
+            XorOperation::<AB::F>::eval(
+                builder,
+                local.w_i_minus_2_rr_19.value[k],
+                local.w_i_minus_2_rr_61.value[k],
+                local.s1_intermediate[k],
+                local.shard,
+                local.channel,
+                local.is_real,
+            );
+            // s1 := (w[i-2] rightrotate 19) xor (w[i-2] rightrotate 61) xor (w[i-2] rightshift 6)
+            XorOperation::<AB::F>::eval(
+                builder,
+                local.s1_intermediate[k].value,
+                local.w_i_minus_2_rs_6.value[k],
+                local.s1[k],
+                local.shard,
+                local.channel,
+                local.is_real,
+            );
+        }
+
+        // s2 := w[i-16] + s0 + w[i-7] + s1.
+        let s0: [Word<AB::Var>; 2] = [local.s0[0].value, local.s0[1].value];
+        let s1: [Word<AB::Var>; 2] = [local.s1[0].value, local.s1[1].value];
+        AddU64Operation::<AB::F>::eval(
+            builder,
+            &[w_i_minus_16, s0, w_i_minus_7, s1],
+            local.shard,
+            local.channel,
+            local.is_real,
+            local.s2,
+        );
+
+        // Write `s2` to `w[i]`.
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.channel,
+            clk,
+            local.w_ptr + local.i * nb_bytes_in_u64,
+            &local.w_i,
+            local.is_real,
+        );
+
+        for k in 0..2 {
+            builder.assert_word_eq(*local.w_i[k].value(), local.s2.value[k]);
+        }
+
+        // Receive syscall event in first row of 64-cycle.
+        builder.receive_syscall(
+            local.shard,
+            local.channel,
+            local.clk,
+            local.nonce,
+            AB::F::from_canonical_u32(SyscallCode::SHA512_EXTEND.syscall_id()),
+            local.w_ptr,
+            AB::Expr::zero(),
+            local.is_start,
+        );
+
+        // Assert that is_real is a bool.
+        builder.assert_bool(local.is_real);
+
+        // Ensure that all rows in a 64 row cycle has the same `is_real` values.
+        builder
+            .when_transition()
+            .when_not(local.cycle_64_end.result)
+            .assert_eq(local.is_real, next.is_real);
+
+        // Assert that the table never ends in the middle of a 64 row cycle. The padded height is a
+        // power of two, so this only matters for tables shorter than a full cycle.
+        builder
+            .when_last_row()
+            .when(local.is_real)
+            .assert_one(local.cycle_64_end.result);
+    }
+}
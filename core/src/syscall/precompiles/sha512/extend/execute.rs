@@ -0,0 +1,97 @@
+use crate::{
+    runtime::Syscall,
+    syscall::precompiles::{
+        sha512::{u64_from_u32s, Sha512ExtendEvent},
+        SyscallContext,
+    },
+};
+
+use super::Sha512ExtendChip;
+
+impl Syscall for Sha512ExtendChip {
+    fn num_extra_cycles(&self) -> u32 {
+        64
+    }
+
+    fn execute(&self, rt: &mut SyscallContext, arg1: u32, arg2: u32) -> Option<u32> {
+        let clk_init = rt.clk;
+        let w_ptr = arg1;
+        if arg2 != 0 {
+            panic!("arg2 must be 0")
+        }
+
+        let w_ptr_init = w_ptr;
+        let mut w_i_minus_15_reads = Vec::new();
+        let mut w_i_minus_2_reads = Vec::new();
+        let mut w_i_minus_16_reads = Vec::new();
+        let mut w_i_minus_7_reads = Vec::new();
+        let mut w_i_writes = Vec::new();
+
+        // Reads the 64-bit word at index `i` of the w array.
+        let read = |rt: &mut SyscallContext, i: u32| {
+            let (records, values) = rt.mr_slice(w_ptr + i * 8, 2);
+            (
+                [records[0], records[1]],
+                u64_from_u32s(values[0], values[1]),
+            )
+        };
+
+        for i in 16..80 {
+            // Read w[i-15].
+            let (records, w_i_minus_15) = read(rt, i - 15);
+            w_i_minus_15_reads.push(records);
+
+            // Compute `s0`.
+            let s0 =
+                w_i_minus_15.rotate_right(1) ^ w_i_minus_15.rotate_right(8) ^ (w_i_minus_15 >> 7);
+
+            // Read w[i-2].
+            let (records, w_i_minus_2) = read(rt, i - 2);
+            w_i_minus_2_reads.push(records);
+
+            // Compute `s1`.
+            let s1 =
+                w_i_minus_2.rotate_right(19) ^ w_i_minus_2.rotate_right(61) ^ (w_i_minus_2 >> 6);
+
+            // Read w[i-16].
+            let (records, w_i_minus_16) = read(rt, i - 16);
+            w_i_minus_16_reads.push(records);
+
+            // Read w[i-7].
+            let (records, w_i_minus_7) = read(rt, i - 7);
+            w_i_minus_7_reads.push(records);
+
+            // Compute `w_i`.
+            let w_i = s1
+                .wrapping_add(w_i_minus_16)
+                .wrapping_add(s0)
+                .wrapping_add(w_i_minus_7);
+
+            // Write w[i].
+            let records = rt.mw_slice(w_ptr + i * 8, &[w_i as u32, (w_i >> 32) as u32]);
+            w_i_writes.push([records[0], records[1]]);
+            rt.clk += 1;
+        }
+
+        // Push the SHA-512 extend event.
+        let lookup_id = rt.syscall_lookup_id;
+        let shard = rt.current_shard();
+        let channel = rt.current_channel();
+        rt.record_mut()
+            .sha512_extend_events
+            .push(Sha512ExtendEvent {
+                lookup_id,
+                shard,
+                channel,
+                clk: clk_init,
+                w_ptr: w_ptr_init,
+                w_i_minus_15_reads,
+                w_i_minus_2_reads,
+                w_i_minus_16_reads,
+                w_i_minus_7_reads,
+                w_i_writes,
+            });
+
+        None
+    }
+}
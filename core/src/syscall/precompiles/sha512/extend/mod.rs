@@ -0,0 +1,108 @@
+mod air;
+mod columns;
+mod execute;
+mod flags;
+mod trace;
+
+pub use columns::*;
+
+use crate::runtime::{MemoryReadRecord, MemoryWriteRecord};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Sha512ExtendEvent {
+    pub lookup_id: u128,
+    pub shard: u32,
+    pub channel: u8,
+    pub clk: u32,
+    pub w_ptr: u32,
+    pub w_i_minus_15_reads: Vec<[MemoryReadRecord; 2]>,
+    pub w_i_minus_2_reads: Vec<[MemoryReadRecord; 2]>,
+    pub w_i_minus_16_reads: Vec<[MemoryReadRecord; 2]>,
+    pub w_i_minus_7_reads: Vec<[MemoryReadRecord; 2]>,
+    pub w_i_writes: Vec<[MemoryWriteRecord; 2]>,
+}
+
+/// Implements the SHA-512 extension operation which loops over i = [16, 79] and modifies w[i] in
+/// each iteration. The only input to the syscall is the 4byte-aligned pointer to the w array, which
+/// holds 80 little-endian 64-bit words.
+///
+/// In the AIR, each SHA-512 extend syscall takes up 64 rows, where each row corresponds to a single
+/// iteration of the loop.
+#[derive(Default)]
+pub struct Sha512ExtendChip;
+
+impl Sha512ExtendChip {
+    pub const fn new() -> Self {
+        Self {}
+    }
+}
+
+#[cfg(test)]
+pub mod extend_tests {
+
+    use crate::{
+        runtime::{Instruction, Opcode, Program, Runtime, SyscallCode},
+        stark::CpuProver,
+        utils::{run_test, setup_logger, SP1CoreOpts},
+    };
+
+    pub fn sha512_extend_program(w: &[u64; 80]) -> Program {
+        let w_ptr = 0x1000;
+        let mut program = Program::new(
+            vec![
+                Instruction::new(
+                    Opcode::ADD,
+                    5,
+                    0,
+                    SyscallCode::SHA512_EXTEND as u32,
+                    false,
+                    true,
+                ),
+                Instruction::new(Opcode::ADD, 10, 0, w_ptr, false, true),
+                Instruction::new(Opcode::ADD, 11, 0, 0, false, true),
+                Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            ],
+            0,
+            0,
+        );
+        for (i, word) in w.iter().enumerate() {
+            let addr = w_ptr + 8 * i as u32;
+            program.memory_image.insert(addr, *word as u32);
+            program.memory_image.insert(addr + 4, (*word >> 32) as u32);
+        }
+        program
+    }
+
+    fn sha512_extend(w: &mut [u64]) {
+        for i in 16..80 {
+            let s0 = w[i - 15].rotate_right(1) ^ w[i - 15].rotate_right(8) ^ (w[i - 15] >> 7);
+            let s1 = w[i - 2].rotate_right(19) ^ w[i - 2].rotate_right(61) ^ (w[i - 2] >> 6);
+            w[i] = w[i - 16]
+                .wrapping_add(s0)
+                .wrapping_add(w[i - 7])
+                .wrapping_add(s1);
+        }
+    }
+
+    #[test]
+    fn test_sha512_extend_prove() {
+        setup_logger();
+        let mut w = [0u64; 80];
+        for (i, word) in w.iter_mut().take(16).enumerate() {
+            *word = 0x0123_4567_89ab_cdef_u64.rotate_left(5 * i as u32) ^ i as u64;
+        }
+        let program = sha512_extend_program(&w);
+
+        let mut runtime = Runtime::new(program.clone(), SP1CoreOpts::default());
+        runtime.run().unwrap();
+        sha512_extend(&mut w);
+        for (i, word) in w.iter().enumerate() {
+            let addr = 0x1000 + 8 * i as u32;
+            let value = (runtime.word(addr + 4) as u64) << 32 | runtime.word(addr) as u64;
+            assert_eq!(value, *word);
+        }
+
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+}
@@ -0,0 +1,201 @@
+use hashbrown::HashMap;
+use itertools::Itertools;
+use p3_field::PrimeField32;
+use p3_matrix::dense::RowMajorMatrix;
+use p3_matrix::Matrix;
+use p3_maybe_rayon::prelude::{ParallelIterator, ParallelSlice};
+use std::borrow::BorrowMut;
+
+use crate::{
+    air::MachineAir,
+    bytes::{event::ByteRecord, ByteLookupEvent},
+    runtime::{ExecutionRecord, MemoryReadRecord, Program},
+    syscall::precompiles::sha512::u64_from_u32s,
+};
+
+use super::{Sha512ExtendChip, Sha512ExtendCols, Sha512ExtendEvent, NUM_SHA512_EXTEND_COLS};
+
+impl<F: PrimeField32> MachineAir<F> for Sha512ExtendChip {
+    type Record = ExecutionRecord;
+
+    type Program = Program;
+
+    fn name(&self) -> String {
+        "Sha512Extend".to_string()
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        _: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let rows = Vec::new();
+
+        let mut new_byte_lookup_events = Vec::new();
+        let mut wrapped_rows = Some(rows);
+        for i in 0..input.sha512_extend_events.len() {
+            self.event_to_rows(
+                &input.sha512_extend_events[i],
+                &mut wrapped_rows,
+                &mut new_byte_lookup_events,
+            );
+        }
+
+        let mut rows = wrapped_rows.unwrap();
+        let nb_rows = rows.len();
+        let mut padded_nb_rows = nb_rows.next_power_of_two();
+        if padded_nb_rows == 2 || padded_nb_rows == 1 {
+            padded_nb_rows = 4;
+        }
+        for i in nb_rows..padded_nb_rows {
+            let mut row = [F::zero(); NUM_SHA512_EXTEND_COLS];
+            let cols: &mut Sha512ExtendCols<F> = row.as_mut_slice().borrow_mut();
+            cols.populate_flags(i);
+            rows.push(row);
+        }
+
+        // Convert the trace to a row major matrix.
+        let mut trace = RowMajorMatrix::new(
+            rows.into_iter().flatten().collect::<Vec<_>>(),
+            NUM_SHA512_EXTEND_COLS,
+        );
+
+        // Write the nonces to the trace.
+        for i in 0..trace.height() {
+            let cols: &mut Sha512ExtendCols<F> = trace.values
+                [i * NUM_SHA512_EXTEND_COLS..(i + 1) * NUM_SHA512_EXTEND_COLS]
+                .borrow_mut();
+            cols.nonce = F::from_canonical_usize(i);
+        }
+
+        trace
+    }
+
+    fn generate_dependencies(&self, input: &Self::Record, output: &mut Self::Record) {
+        let chunk_size = std::cmp::max(input.sha512_extend_events.len() / num_cpus::get(), 1);
+
+        let blu_batches = input
+            .sha512_extend_events
+            .par_chunks(chunk_size)
+            .map(|events| {
+                let mut blu: HashMap<u32, HashMap<ByteLookupEvent, usize>> = HashMap::new();
+                events.iter().for_each(|event| {
+                    self.event_to_rows::<F>(event, &mut None, &mut blu);
+                });
+                blu
+            })
+            .collect::<Vec<_>>();
+
+        output.add_sharded_byte_lookup_events(blu_batches.iter().collect_vec());
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        !shard.sha512_extend_events.is_empty()
+    }
+}
+
+impl Sha512ExtendChip {
+    fn event_to_rows<F: PrimeField32>(
+        &self,
+        event: &Sha512ExtendEvent,
+        rows: &mut Option<Vec<[F; NUM_SHA512_EXTEND_COLS]>>,
+        blu: &mut impl ByteRecord,
+    ) {
+        let shard = event.shard;
+        let channel = event.channel;
+        let value = |reads: &[MemoryReadRecord; 2]| u64_from_u32s(reads[0].value, reads[1].value);
+        for j in 0..64usize {
+            let mut row = [F::zero(); NUM_SHA512_EXTEND_COLS];
+            let cols: &mut Sha512ExtendCols<F> = row.as_mut_slice().borrow_mut();
+            cols.is_real = F::one();
+            cols.populate_flags(j);
+            cols.shard = F::from_canonical_u32(event.shard);
+            cols.channel = F::from_canonical_u8(event.channel);
+            cols.clk = F::from_canonical_u32(event.clk);
+            cols.w_ptr = F::from_canonical_u32(event.w_ptr);
+
+            for k in 0..2 {
+                cols.w_i_minus_15[k].populate(channel, event.w_i_minus_15_reads[j][k], blu);
+                cols.w_i_minus_2[k].populate(channel, event.w_i_minus_2_reads[j][k], blu);
+                cols.w_i_minus_16[k].populate(channel, event.w_i_minus_16_reads[j][k], blu);
+                cols.w_i_minus_7[k].populate(channel, event.w_i_minus_7_reads[j][k], blu);
+            }
+
+            // `s0 := (w[i-15] rightrotate 1) xor (w[i-15] rightrotate 8) xor (w[i-15] rightshift 7)`.
+            let w_i_minus_15 = value(&event.w_i_minus_15_reads[j]);
+            let w_i_minus_15_rr_1 =
+                cols.w_i_minus_15_rr_1
+                    .populate(blu, shard, channel, w_i_minus_15, 1);
+            let w_i_minus_15_rr_8 =
+                cols.w_i_minus_15_rr_8
+                    .populate(blu, shard, channel, w_i_minus_15, 8);
+            let w_i_minus_15_rs_7 =
+                cols.w_i_minus_15_rs_7
+                    .populate(blu, shard, channel, w_i_minus_15, 7);
+            let mut s0 = 0u64;
+            for k in 0..2 {
+                let shift = 32 * k;
+                let s0_intermediate = cols.s0_intermediate[k].populate(
+                    blu,
+                    shard,
+                    channel,
+                    (w_i_minus_15_rr_1 >> shift) as u32,
+                    (w_i_minus_15_rr_8 >> shift) as u32,
+                );
+                let s0_k = cols.s0[k].populate(
+                    blu,
+                    shard,
+                    channel,
+                    s0_intermediate,
+                    (w_i_minus_15_rs_7 >> shift) as u32,
+                );
+                s0 |= (s0_k as u64) << shift;
+            }
+
+            // `s1 := (w[i-2] rightrotate 19) xor (w[i-2] rightrotate 61) xor (w[i-2] rightshift 6)`.
+            let w_i_minus_2 = value(&event.w_i_minus_2_reads[j]);
+            let w_i_minus_2_rr_19 =
+                cols.w_i_minus_2_rr_19
+                    .populate(blu, shard, channel, w_i_minus_2, 19);
+            let w_i_minus_2_rr_61 =
+                cols.w_i_minus_2_rr_61
+                    .populate(blu, shard, channel, w_i_minus_2, 61);
+            let w_i_minus_2_rs_6 =
+                cols.w_i_minus_2_rs_6
+                    .populate(blu, shard, channel, w_i_minus_2, 6);
+            let mut s1 = 0u64;
+            for k in 0..2 {
+                let shift = 32 * k;
+                let s1_intermediate = cols.s1_intermediate[k].populate(
+                    blu,
+                    shard,
+                    channel,
+                    (w_i_minus_2_rr_19 >> shift) as u32,
+                    (w_i_minus_2_rr_61 >> shift) as u32,
+                );
+                let s1_k = cols.s1[k].populate(
+                    blu,
+                    shard,
+                    channel,
+                    s1_intermediate,
+                    (w_i_minus_2_rs_6 >> shift) as u32,
+                );
+                s1 |= (s1_k as u64) << shift;
+            }
+
+            // Compute `s2`.
+            let w_i_minus_7 = value(&event.w_i_minus_7_reads[j]);
+            let w_i_minus_16 = value(&event.w_i_minus_16_reads[j]);
+            cols.s2
+                .populate(blu, shard, channel, &[w_i_minus_16, s0, w_i_minus_7, s1]);
+
+            for k in 0..2 {
+                cols.w_i[k].populate(channel, event.w_i_writes[j][k], blu);
+            }
+
+            if rows.as_ref().is_some() {
+                rows.as_mut().unwrap().push(row);
+            }
+        }
+    }
+}
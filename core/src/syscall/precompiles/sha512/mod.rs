@@ -0,0 +1,18 @@
+mod compress;
+mod extend;
+
+pub use compress::*;
+pub use extend::*;
+
+use crate::air::Word;
+use p3_field::Field;
+
+/// Splits a 64-bit word into its low and high 32-bit words.
+pub(crate) fn u64_to_words<F: Field>(value: u64) -> [Word<F>; 2] {
+    [Word::from(value as u32), Word::from((value >> 32) as u32)]
+}
+
+/// Joins the low and high 32-bit words of a 64-bit word.
+pub(crate) const fn u64_from_u32s(lo: u32, hi: u32) -> u64 {
+    (hi as u64) << 32 | lo as u64
+}
@@ -1,3 +1,5 @@
+use std::marker::PhantomData;
+
 use crate::air::{BaseAirBuilder, MachineAir, Polynomial, SP1AirBuilder, WORD_SIZE};
 use crate::bytes::event::ByteRecord;
 use crate::memory::{value_as_limbs, MemoryReadCols, MemoryWriteCols};
@@ -10,11 +12,7 @@ use crate::runtime::{ExecutionRecord, Program, Syscall, SyscallCode};
 use crate::runtime::{MemoryReadRecord, MemoryWriteRecord};
 use crate::stark::MachineRecord;
 use crate::syscall::precompiles::SyscallContext;
-use crate::utils::ec::uint256::U256Field;
-use crate::utils::{
-    bytes_to_words_le, limbs_from_access, limbs_from_prev_access, pad_rows, words_to_bytes_le,
-    words_to_bytes_le_vec,
-};
+use crate::utils::{limbs_from_access, limbs_from_prev_access, pad_rows, words_to_bytes_le_vec};
 use generic_array::GenericArray;
 use num::{BigUint, One, Zero};
 use p3_air::AirBuilder;
@@ -29,15 +27,20 @@ use std::borrow::{Borrow, BorrowMut};
 use std::mem::size_of;
 use typenum::Unsigned;
 
-/// The number of columns in the Uint256MulCols.
-const NUM_COLS: usize = size_of::<Uint256MulCols<u8>>();
+use super::{UintOpField, UintType};
+
+pub const fn num_uint_op_cols<P: UintOpField>() -> usize {
+    size_of::<UintOpCols<u8, P>>()
+}
 
+/// A modular arithmetic event on unsigned integers, computing `x = x op y mod modulus` in place.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Uint256MulEvent {
+pub struct UintOpEvent {
     pub lookup_id: u128,
     pub shard: u32,
     pub channel: u8,
     pub clk: u32,
+    pub op: FieldOperation,
     pub x_ptr: u32,
     pub x: Vec<u32>,
     pub y_ptr: u32,
@@ -48,22 +51,115 @@ pub struct Uint256MulEvent {
     pub modulus_memory_records: Vec<MemoryReadRecord>,
 }
 
-#[derive(Default)]
-pub struct Uint256MulChip;
+/// The syscall computing `x = x op y mod modulus` for the unsigned integers described by `P`.
+///
+/// The modulus is stored in memory right after `y`, and a zero modulus stands for `2^n`, where `n`
+/// is the bit width of `P`. Each operation has its own syscall code, but all of them are proven by
+/// a single [UintOpChip]. Both operands are expected to be reduced modulo the modulus.
+pub struct UintOpSyscall<P> {
+    op: FieldOperation,
+    _marker: PhantomData<P>,
+}
 
-impl Uint256MulChip {
-    pub const fn new() -> Self {
-        Self
+impl<P> UintOpSyscall<P> {
+    pub const fn new(op: FieldOperation) -> Self {
+        Self {
+            op,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<P: UintOpField> Syscall for UintOpSyscall<P> {
+    fn num_extra_cycles(&self) -> u32 {
+        1
+    }
+
+    fn execute(&self, rt: &mut SyscallContext, arg1: u32, arg2: u32) -> Option<u32> {
+        let clk = rt.clk;
+
+        let x_ptr = arg1;
+        if x_ptr % 4 != 0 {
+            panic!();
+        }
+        let y_ptr = arg2;
+        if y_ptr % 4 != 0 {
+            panic!();
+        }
+
+        let num_words = <P as NumWords>::WordsFieldElement::USIZE;
+
+        // First read the words for the x value. We can read a slice_unsafe here because we write
+        // the computed result to x later.
+        let x = rt.slice_unsafe(x_ptr, num_words);
+
+        // Read the y value.
+        let (y_memory_records, y) = rt.mr_slice(y_ptr, num_words);
+
+        // The modulus is stored after the y value. We increment the pointer by the number of words.
+        let modulus_ptr = y_ptr + num_words as u32 * WORD_SIZE as u32;
+        let (modulus_memory_records, modulus) = rt.mr_slice(modulus_ptr, num_words);
+
+        // Get the BigUint values for x, y, and the modulus. The inputs are reduced first, so that
+        // the subtraction can't underflow.
+        let effective_modulus = effective_modulus::<P>(&modulus);
+        let a = BigUint::from_bytes_le(&words_to_bytes_le_vec(&x)) % &effective_modulus;
+        let b = BigUint::from_bytes_le(&words_to_bytes_le_vec(&y)) % &effective_modulus;
+
+        // Perform the operation and take the result modulo the modulus.
+        let result = match self.op {
+            FieldOperation::Add => (a + b) % &effective_modulus,
+            FieldOperation::Sub => (&effective_modulus + a - b) % &effective_modulus,
+            FieldOperation::Mul => (a * b) % &effective_modulus,
+            FieldOperation::Div => panic!("Unsupported operation"),
+        };
+        let mut result_words = result.to_u32_digits();
+        result_words.resize(num_words, 0);
+
+        // Increment clk so that the write is not at the same cycle as the read.
+        rt.clk += 1;
+        // Write the result to x and keep track of the memory records.
+        let x_memory_records = rt.mw_slice(x_ptr, &result_words);
+
+        let event = UintOpEvent {
+            lookup_id: rt.syscall_lookup_id,
+            shard: rt.current_shard(),
+            channel: rt.current_channel(),
+            clk,
+            op: self.op,
+            x_ptr,
+            x,
+            y_ptr,
+            y,
+            modulus,
+            x_memory_records,
+            y_memory_records,
+            modulus_memory_records,
+        };
+        match P::UINT_TYPE {
+            UintType::Uint256 => rt.record_mut().uint256_events.push(event),
+            UintType::Uint384 => rt.record_mut().uint384_events.push(event),
+            UintType::Uint512 => rt.record_mut().uint512_events.push(event),
+        }
+
+        None
     }
 }
 
-type WordsFieldElement = <U256Field as NumWords>::WordsFieldElement;
-const WORDS_FIELD_ELEMENT: usize = WordsFieldElement::USIZE;
+/// Returns the modulus given by `words`, where zero stands for `2^n`.
+fn effective_modulus<P: UintOpField>(words: &[u32]) -> BigUint {
+    let modulus = BigUint::from_bytes_le(&words_to_bytes_le_vec(words));
+    if modulus.is_zero() {
+        BigUint::one() << P::nb_bits()
+    } else {
+        modulus
+    }
+}
 
-/// A set of columns for the Uint256Mul operation.
+/// A set of columns for the modular add, sub and mul operations.
 #[derive(Debug, Clone, AlignedBorrow)]
 #[repr(C)]
-pub struct Uint256MulCols<T> {
+pub struct UintOpCols<T, P: UintOpField> {
     /// The shard number of the syscall.
     pub shard: T,
 
@@ -76,6 +172,11 @@ pub struct Uint256MulCols<T> {
     /// The nonce of the operation.
     pub nonce: T,
 
+    /// The selected operation. Exactly one of these flags is set on real rows.
+    pub is_add: T,
+    pub is_sub: T,
+    pub is_mul: T,
+
     /// The pointer to the first input.
     pub x_ptr: T,
 
@@ -84,30 +185,48 @@ pub struct Uint256MulCols<T> {
 
     // Memory columns.
     // x_memory is written to with the result, which is why it is of type MemoryWriteCols.
-    pub x_memory: GenericArray<MemoryWriteCols<T>, WordsFieldElement>,
-    pub y_memory: GenericArray<MemoryReadCols<T>, WordsFieldElement>,
-    pub modulus_memory: GenericArray<MemoryReadCols<T>, WordsFieldElement>,
+    pub x_memory: GenericArray<MemoryWriteCols<T>, P::WordsFieldElement>,
+    pub y_memory: GenericArray<MemoryReadCols<T>, P::WordsFieldElement>,
+    pub modulus_memory: GenericArray<MemoryReadCols<T>, P::WordsFieldElement>,
 
-    /// Columns for checking if modulus is zero. If it's zero, then use 2^256 as the effective modulus.
+    /// Columns for checking if modulus is zero. If it's zero, then use 2^n as the effective modulus.
     pub modulus_is_zero: IsZeroOperation<T>,
 
     /// Column that is equal to is_real * (1 - modulus_is_zero.result).
     pub modulus_is_not_zero: T,
 
-    // Output values. We compute (x * y) % modulus.
-    pub output: FieldOpCols<T, U256Field>,
+    // Output values. We compute (x op y) % modulus.
+    pub output: FieldOpCols<T, P>,
 
-    pub output_range_check: FieldLtCols<T, U256Field>,
+    pub output_range_check: FieldLtCols<T, P>,
 
     pub is_real: T,
 }
 
-impl<F: PrimeField32> MachineAir<F> for Uint256MulChip {
+/// A chip proving modular add, sub and mul for the unsigned integers described by `P`.
+#[derive(Default)]
+pub struct UintOpChip<P> {
+    _marker: PhantomData<P>,
+}
+
+impl<P: UintOpField> UintOpChip<P> {
+    pub const fn new() -> Self {
+        Self {
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<F: PrimeField32, P: UintOpField> MachineAir<F> for UintOpChip<P> {
     type Record = ExecutionRecord;
     type Program = Program;
 
     fn name(&self) -> String {
-        "Uint256MulMod".to_string()
+        match P::UINT_TYPE {
+            UintType::Uint256 => "Uint256Op".to_string(),
+            UintType::Uint384 => "Uint384Op".to_string(),
+            UintType::Uint512 => "Uint512Op".to_string(),
+        }
     }
 
     fn generate_trace(
@@ -115,9 +234,15 @@ impl<F: PrimeField32> MachineAir<F> for Uint256MulChip {
         input: &ExecutionRecord,
         output: &mut ExecutionRecord,
     ) -> RowMajorMatrix<F> {
+        let events = match P::UINT_TYPE {
+            UintType::Uint256 => &input.uint256_events,
+            UintType::Uint384 => &input.uint384_events,
+            UintType::Uint512 => &input.uint512_events,
+        };
+        let num_cols = num_uint_op_cols::<P>();
+
         // Generate the trace rows & corresponding records for each chunk of events concurrently.
-        let rows_and_records = input
-            .uint256_mul_events
+        let rows_and_records = events
             .chunks(1)
             .map(|events| {
                 let mut records = ExecutionRecord::default();
@@ -126,25 +251,26 @@ impl<F: PrimeField32> MachineAir<F> for Uint256MulChip {
                 let rows = events
                     .iter()
                     .map(|event| {
-                        let mut row: [F; NUM_COLS] = [F::zero(); NUM_COLS];
-                        let cols: &mut Uint256MulCols<F> = row.as_mut_slice().borrow_mut();
+                        let mut row = vec![F::zero(); num_cols];
+                        let cols: &mut UintOpCols<F, P> = row.as_mut_slice().borrow_mut();
 
-                        // Decode uint256 points
-                        let x = BigUint::from_bytes_le(&words_to_bytes_le::<32>(&event.x));
-                        let y = BigUint::from_bytes_le(&words_to_bytes_le::<32>(&event.y));
-                        let modulus =
-                            BigUint::from_bytes_le(&words_to_bytes_le::<32>(&event.modulus));
+                        // Decode the inputs.
+                        let x = BigUint::from_bytes_le(&words_to_bytes_le_vec(&event.x));
+                        let y = BigUint::from_bytes_le(&words_to_bytes_le_vec(&event.y));
 
                         // Assign basic values to the columns.
                         cols.is_real = F::one();
                         cols.shard = F::from_canonical_u32(event.shard);
                         cols.channel = F::from_canonical_u8(event.channel);
                         cols.clk = F::from_canonical_u32(event.clk);
+                        cols.is_add = F::from_bool(event.op == FieldOperation::Add);
+                        cols.is_sub = F::from_bool(event.op == FieldOperation::Sub);
+                        cols.is_mul = F::from_bool(event.op == FieldOperation::Mul);
                         cols.x_ptr = F::from_canonical_u32(event.x_ptr);
                         cols.y_ptr = F::from_canonical_u32(event.y_ptr);
 
                         // Populate memory columns.
-                        for i in 0..WORDS_FIELD_ELEMENT {
+                        for i in 0..cols.x_memory.len() {
                             cols.x_memory[i].populate(
                                 event.channel,
                                 event.x_memory_records[i],
@@ -167,11 +293,7 @@ impl<F: PrimeField32> MachineAir<F> for Uint256MulChip {
                         IsZeroOperation::populate(&mut cols.modulus_is_zero, modulus_byte_sum);
 
                         // Populate the output column.
-                        let effective_modulus = if modulus.is_zero() {
-                            BigUint::one() << 256
-                        } else {
-                            modulus.clone()
-                        };
+                        let effective_modulus = effective_modulus::<P>(&event.modulus);
                         let result = cols.output.populate_with_modulus(
                             &mut new_byte_lookup_events,
                             event.shard,
@@ -179,8 +301,7 @@ impl<F: PrimeField32> MachineAir<F> for Uint256MulChip {
                             &x,
                             &y,
                             &effective_modulus,
-                            // &modulus,
-                            FieldOperation::Mul,
+                            event.op,
                         );
 
                         cols.modulus_is_not_zero = F::one() - cols.modulus_is_zero.result;
@@ -210,8 +331,8 @@ impl<F: PrimeField32> MachineAir<F> for Uint256MulChip {
         }
 
         pad_rows(&mut rows, || {
-            let mut row: [F; NUM_COLS] = [F::zero(); NUM_COLS];
-            let cols: &mut Uint256MulCols<F> = row.as_mut_slice().borrow_mut();
+            let mut row = vec![F::zero(); num_cols];
+            let cols: &mut UintOpCols<F, P> = row.as_mut_slice().borrow_mut();
 
             let x = BigUint::zero();
             let y = BigUint::zero();
@@ -223,12 +344,12 @@ impl<F: PrimeField32> MachineAir<F> for Uint256MulChip {
 
         // Convert the trace to a row major matrix.
         let mut trace =
-            RowMajorMatrix::new(rows.into_iter().flatten().collect::<Vec<_>>(), NUM_COLS);
+            RowMajorMatrix::new(rows.into_iter().flatten().collect::<Vec<_>>(), num_cols);
 
         // Write the nonces to the trace.
         for i in 0..trace.height() {
-            let cols: &mut Uint256MulCols<F> =
-                trace.values[i * NUM_COLS..(i + 1) * NUM_COLS].borrow_mut();
+            let cols: &mut UintOpCols<F, P> =
+                trace.values[i * num_cols..(i + 1) * num_cols].borrow_mut();
             cols.nonce = F::from_canonical_usize(i);
         }
 
@@ -236,101 +357,31 @@ impl<F: PrimeField32> MachineAir<F> for Uint256MulChip {
     }
 
     fn included(&self, shard: &Self::Record) -> bool {
-        !shard.uint256_mul_events.is_empty()
-    }
-}
-
-impl Syscall for Uint256MulChip {
-    fn num_extra_cycles(&self) -> u32 {
-        1
-    }
-
-    fn execute(&self, rt: &mut SyscallContext, arg1: u32, arg2: u32) -> Option<u32> {
-        let clk = rt.clk;
-
-        let x_ptr = arg1;
-        if x_ptr % 4 != 0 {
-            panic!();
+        match P::UINT_TYPE {
+            UintType::Uint256 => !shard.uint256_events.is_empty(),
+            UintType::Uint384 => !shard.uint384_events.is_empty(),
+            UintType::Uint512 => !shard.uint512_events.is_empty(),
         }
-        let y_ptr = arg2;
-        if y_ptr % 4 != 0 {
-            panic!();
-        }
-
-        // First read the words for the x value. We can read a slice_unsafe here because we write
-        // the computed result to x later.
-        let x = rt.slice_unsafe(x_ptr, WORDS_FIELD_ELEMENT);
-
-        // Read the y value.
-        let (y_memory_records, y) = rt.mr_slice(y_ptr, WORDS_FIELD_ELEMENT);
-
-        // The modulus is stored after the y value. We increment the pointer by the number of words.
-        let modulus_ptr = y_ptr + WORDS_FIELD_ELEMENT as u32 * WORD_SIZE as u32;
-        let (modulus_memory_records, modulus) = rt.mr_slice(modulus_ptr, WORDS_FIELD_ELEMENT);
-
-        // Get the BigUint values for x, y, and the modulus.
-        let uint256_x = BigUint::from_bytes_le(&words_to_bytes_le_vec(&x));
-        let uint256_y = BigUint::from_bytes_le(&words_to_bytes_le_vec(&y));
-        let uint256_modulus = BigUint::from_bytes_le(&words_to_bytes_le_vec(&modulus));
-
-        // Perform the multiplication and take the result modulo the modulus.
-        let result: BigUint = if uint256_modulus.is_zero() {
-            let modulus = BigUint::one() << 256;
-            (uint256_x * uint256_y) % modulus
-        } else {
-            (uint256_x * uint256_y) % uint256_modulus
-        };
-
-        let mut result_bytes = result.to_bytes_le();
-        result_bytes.resize(32, 0u8); // Pad the result to 32 bytes.
-
-        // Convert the result to little endian u32 words.
-        let result = bytes_to_words_le::<8>(&result_bytes);
-
-        // Increment clk so that the write is not at the same cycle as the read.
-        rt.clk += 1;
-        // Write the result to x and keep track of the memory records.
-        let x_memory_records = rt.mw_slice(x_ptr, &result);
-
-        let lookup_id = rt.syscall_lookup_id;
-        let shard = rt.current_shard();
-        let channel = rt.current_channel();
-        rt.record_mut().uint256_mul_events.push(Uint256MulEvent {
-            lookup_id,
-            shard,
-            channel,
-            clk,
-            x_ptr,
-            x,
-            y_ptr,
-            y,
-            modulus,
-            x_memory_records,
-            y_memory_records,
-            modulus_memory_records,
-        });
-
-        None
     }
 }
 
-impl<F> BaseAir<F> for Uint256MulChip {
+impl<F, P: UintOpField> BaseAir<F> for UintOpChip<P> {
     fn width(&self) -> usize {
-        NUM_COLS
+        num_uint_op_cols::<P>()
     }
 }
 
-impl<AB> Air<AB> for Uint256MulChip
+impl<AB, P: UintOpField> Air<AB> for UintOpChip<P>
 where
     AB: SP1AirBuilder,
-    Limbs<AB::Var, <U256Field as NumLimbs>::Limbs>: Copy,
+    Limbs<AB::Var, <P as NumLimbs>::Limbs>: Copy,
 {
     fn eval(&self, builder: &mut AB) {
         let main = builder.main();
         let local = main.row_slice(0);
-        let local: &Uint256MulCols<AB::Var> = (*local).borrow();
+        let local: &UintOpCols<AB::Var, P> = (*local).borrow();
         let next = main.row_slice(1);
-        let next: &Uint256MulCols<AB::Var> = (*next).borrow();
+        let next: &UintOpCols<AB::Var, P> = (*next).borrow();
 
         // Constrain the incrementing nonce.
         builder.when_first_row().assert_zero(local.nonce);
@@ -338,15 +389,23 @@ where
             .when_transition()
             .assert_eq(local.nonce + AB::Expr::one(), next.nonce);
 
-        // We are computing (x * y) % modulus. The value of x is stored in the "prev_value" of
+        // A real row performs exactly one operation, and padding rows perform none.
+        builder.assert_bool(local.is_add);
+        builder.assert_bool(local.is_sub);
+        builder.assert_bool(local.is_mul);
+        builder.assert_eq(local.is_add + local.is_sub + local.is_mul, local.is_real);
+
+        // We are computing (x op y) % modulus. The value of x is stored in the "prev_value" of
         // the x_memory, since we write to it later.
-        let x_limbs = limbs_from_prev_access(&local.x_memory);
-        let y_limbs = limbs_from_access(&local.y_memory);
-        let modulus_limbs = limbs_from_access(&local.modulus_memory);
+        let x_limbs: Limbs<AB::Var, <P as NumLimbs>::Limbs> =
+            limbs_from_prev_access(&local.x_memory);
+        let y_limbs: Limbs<AB::Var, <P as NumLimbs>::Limbs> = limbs_from_access(&local.y_memory);
+        let modulus_limbs: Limbs<AB::Var, <P as NumLimbs>::Limbs> =
+            limbs_from_access(&local.modulus_memory);
 
         // If the modulus is zero, then we don't perform the modulus operation.
         // Evaluate the modulus_is_zero operation by summing each byte of the modulus. The sum will
-        // not overflow because we are summing 32 bytes.
+        // not overflow because we are summing at most 64 bytes.
         let modulus_byte_sum = modulus_limbs
             .0
             .iter()
@@ -358,24 +417,27 @@ where
             local.is_real.into(),
         );
 
-        // If the modulus is zero, we'll actually use 2^256 as the modulus, so nothing happens.
+        // If the modulus is zero, we'll actually use 2^n as the modulus, so nothing happens.
         // Otherwise, we use the modulus passed in.
         let modulus_is_zero = local.modulus_is_zero.result;
-        let mut coeff_2_256 = Vec::new();
-        coeff_2_256.resize(32, AB::Expr::zero());
-        coeff_2_256.push(AB::Expr::one());
+        let mut coeff_2_n = Vec::new();
+        coeff_2_n.resize(P::NB_LIMBS, AB::Expr::zero());
+        coeff_2_n.push(AB::Expr::one());
         let modulus_polynomial: Polynomial<AB::Expr> = modulus_limbs.into();
         let p_modulus: Polynomial<AB::Expr> = modulus_polynomial
             * (AB::Expr::one() - modulus_is_zero.into())
-            + Polynomial::from_coefficients(&coeff_2_256) * modulus_is_zero.into();
+            + Polynomial::from_coefficients(&coeff_2_n) * modulus_is_zero.into();
 
-        // Evaluate the uint256 multiplication
-        local.output.eval_with_modulus(
+        // Evaluate the selected operation.
+        local.output.eval_variable(
             builder,
             &x_limbs,
             &y_limbs,
             &p_modulus,
-            FieldOperation::Mul,
+            local.is_add,
+            local.is_sub,
+            local.is_mul,
+            AB::Expr::zero(),
             local.shard,
             local.channel,
             local.is_real,
@@ -418,17 +480,39 @@ where
             local.channel,
             local.clk.into(),
             local.y_ptr,
-            &[local.y_memory, local.modulus_memory].concat(),
+            &[local.y_memory.as_slice(), local.modulus_memory.as_slice()].concat(),
             local.is_real,
         );
 
+        // The syscall id is determined by the selected operation.
+        let (add_id, sub_id, mul_id) = match P::UINT_TYPE {
+            UintType::Uint256 => (
+                SyscallCode::UINT256_ADD,
+                SyscallCode::UINT256_SUB,
+                SyscallCode::UINT256_MUL,
+            ),
+            UintType::Uint384 => (
+                SyscallCode::UINT384_ADD,
+                SyscallCode::UINT384_SUB,
+                SyscallCode::UINT384_MUL,
+            ),
+            UintType::Uint512 => (
+                SyscallCode::UINT512_ADD,
+                SyscallCode::UINT512_SUB,
+                SyscallCode::UINT512_MUL,
+            ),
+        };
+        let syscall_id_felt = local.is_add * AB::F::from_canonical_u32(add_id.syscall_id())
+            + local.is_sub * AB::F::from_canonical_u32(sub_id.syscall_id())
+            + local.is_mul * AB::F::from_canonical_u32(mul_id.syscall_id());
+
         // Receive the arguments.
         builder.receive_syscall(
             local.shard,
             local.channel,
             local.clk,
             local.nonce,
-            AB::F::from_canonical_u32(SyscallCode::UINT256_MUL.syscall_id()),
+            syscall_id_felt,
             local.x_ptr,
             local.y_ptr,
             local.is_real,
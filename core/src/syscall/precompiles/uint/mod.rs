@@ -0,0 +1,148 @@
+mod air;
+
+pub use air::*;
+
+use crate::operations::field::params::{FieldParameters, NumWords};
+use crate::utils::ec::uint256::U256Field;
+use crate::utils::ec::uint384::U384Field;
+use crate::utils::ec::uint512::U512Field;
+
+/// The integer widths supported by the modular arithmetic precompiles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UintType {
+    Uint256,
+    Uint384,
+    Uint512,
+}
+
+/// An unsigned integer type with modular add, sub and mul precompiles.
+pub trait UintOpField: FieldParameters + NumWords {
+    const UINT_TYPE: UintType;
+}
+
+impl UintOpField for U256Field {
+    const UINT_TYPE: UintType = UintType::Uint256;
+}
+
+impl UintOpField for U384Field {
+    const UINT_TYPE: UintType = UintType::Uint384;
+}
+
+impl UintOpField for U512Field {
+    const UINT_TYPE: UintType = UintType::Uint512;
+}
+
+#[cfg(test)]
+mod tests {
+    use num::bigint::RandBigInt;
+    use num::{BigUint, One, Zero};
+    use rand::thread_rng;
+
+    use crate::operations::field::params::FieldParameters;
+    use crate::runtime::SyscallCode;
+    use crate::stark::CpuProver;
+    use crate::syscall::precompiles::fptower::tests::{execute, syscall_program, to_words};
+    use crate::utils::ec::uint384::U384Field;
+    use crate::utils::ec::uint512::U512Field;
+    use crate::{
+        io::SP1Stdin,
+        runtime::Program,
+        utils::{
+            self,
+            ec::{uint256::U256Field, utils::biguint_from_limbs},
+            run_test, run_test_io,
+            tests::UINT256_MUL_ELF,
+        },
+    };
+
+    #[test]
+    fn test_uint256_mul() {
+        utils::setup_logger();
+        let program = Program::from(UINT256_MUL_ELF);
+        run_test_io::<CpuProver<_, _>>(program, SP1Stdin::new()).unwrap();
+    }
+
+    #[test]
+    fn test_uint_ops() {
+        utils::setup_logger();
+        let mut rng = thread_rng();
+        let widths = [
+            (
+                256,
+                SyscallCode::UINT256_ADD,
+                SyscallCode::UINT256_SUB,
+                SyscallCode::UINT256_MUL,
+            ),
+            (
+                384,
+                SyscallCode::UINT384_ADD,
+                SyscallCode::UINT384_SUB,
+                SyscallCode::UINT384_MUL,
+            ),
+            (
+                512,
+                SyscallCode::UINT512_ADD,
+                SyscallCode::UINT512_SUB,
+                SyscallCode::UINT512_MUL,
+            ),
+        ];
+
+        let mut calls = Vec::new();
+        let mut expected = Vec::new();
+        let mut num_words = Vec::new();
+        for (bits, add, sub, mul) in widths {
+            let words = bits / 32;
+            // Exercise both an odd modulus and the zero modulus, which stands for 2^bits.
+            let modulus = rng.gen_biguint(bits as u64) | BigUint::one();
+            for modulus in [modulus, BigUint::zero()] {
+                let effective_modulus = if modulus.is_zero() {
+                    BigUint::one() << bits
+                } else {
+                    modulus.clone()
+                };
+                let x = rng.gen_biguint_below(&effective_modulus);
+                let y = rng.gen_biguint_below(&effective_modulus);
+                let results = [
+                    (add, (&x + &y) % &effective_modulus),
+                    (sub, (&effective_modulus + &x - &y) % &effective_modulus),
+                    (mul, (&x * &y) % &effective_modulus),
+                ];
+                for (code, result) in results {
+                    let y_and_modulus = [to_words(&y, words), to_words(&modulus, words)].concat();
+                    calls.push((code, to_words(&x, words), y_and_modulus));
+                    expected.push(to_words(&result, words));
+                    num_words.push(words);
+                }
+            }
+        }
+
+        let (program, x_ptrs) = syscall_program(&calls);
+        assert_eq!(execute(&program, &x_ptrs, &num_words), expected);
+
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+
+    #[test]
+    fn test_uint_sub_non_canonical() {
+        utils::setup_logger();
+        // `y` is above the modulus, so `modulus + x - y` would underflow without reducing it.
+        let modulus = (BigUint::one() << 255u32) | BigUint::one();
+        let x = BigUint::one();
+        let y = &modulus + 2u32;
+        let y_and_modulus = [to_words(&y, 8), to_words(&modulus, 8)].concat();
+        let calls = [(SyscallCode::UINT256_SUB, to_words(&x, 8), y_and_modulus)];
+        let expected = to_words(&(&modulus - 1u32), 8);
+
+        let (program, x_ptrs) = syscall_program(&calls);
+        assert_eq!(execute(&program, &x_ptrs, &[8]), vec![expected]);
+
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+
+    #[test]
+    fn test_uint_modulus() {
+        assert_eq!(biguint_from_limbs(U256Field::MODULUS), U256Field::modulus());
+        assert_eq!(biguint_from_limbs(U384Field::MODULUS), U384Field::modulus());
+        assert_eq!(biguint_from_limbs(U512Field::MODULUS), U512Field::modulus());
+    }
+}
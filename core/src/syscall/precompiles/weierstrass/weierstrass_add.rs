@@ -79,6 +79,7 @@ impl<E: EllipticCurve> Syscall for WeierstrassAddAssignChip<E> {
         let event = create_ec_add_event::<E>(rt, arg1, arg2);
         match E::CURVE_TYPE {
             CurveType::Secp256k1 => rt.record_mut().secp256k1_add_events.push(event),
+            CurveType::Secp256r1 => rt.record_mut().secp256r1_add_events.push(event),
             CurveType::Bn254 => rt.record_mut().bn254_add_events.push(event),
             CurveType::Bls12381 => rt.record_mut().bls12381_add_events.push(event),
             _ => panic!("Unsupported curve"),
@@ -209,6 +210,7 @@ impl<F: PrimeField32, E: EllipticCurve + WeierstrassParameters> MachineAir<F>
     fn name(&self) -> String {
         match E::CURVE_TYPE {
             CurveType::Secp256k1 => "Secp256k1AddAssign".to_string(),
+            CurveType::Secp256r1 => "Secp256r1AddAssign".to_string(),
             CurveType::Bn254 => "Bn254AddAssign".to_string(),
             CurveType::Bls12381 => "Bls12381AddAssign".to_string(),
             _ => panic!("Unsupported curve"),
@@ -222,6 +224,7 @@ impl<F: PrimeField32, E: EllipticCurve + WeierstrassParameters> MachineAir<F>
     ) -> RowMajorMatrix<F> {
         let events = match E::CURVE_TYPE {
             CurveType::Secp256k1 => &input.secp256k1_add_events,
+            CurveType::Secp256r1 => &input.secp256r1_add_events,
             CurveType::Bn254 => &input.bn254_add_events,
             CurveType::Bls12381 => &input.bls12381_add_events,
             _ => panic!("Unsupported curve"),
@@ -323,6 +326,7 @@ impl<F: PrimeField32, E: EllipticCurve + WeierstrassParameters> MachineAir<F>
     fn included(&self, shard: &Self::Record) -> bool {
         match E::CURVE_TYPE {
             CurveType::Secp256k1 => !shard.secp256k1_add_events.is_empty(),
+            CurveType::Secp256r1 => !shard.secp256r1_add_events.is_empty(),
             CurveType::Bn254 => !shard.bn254_add_events.is_empty(),
             CurveType::Bls12381 => !shard.bls12381_add_events.is_empty(),
             _ => panic!("Unsupported curve"),
@@ -499,6 +503,9 @@ where
             CurveType::Secp256k1 => {
                 AB::F::from_canonical_u32(SyscallCode::SECP256K1_ADD.syscall_id())
             }
+            CurveType::Secp256r1 => {
+                AB::F::from_canonical_u32(SyscallCode::SECP256R1_ADD.syscall_id())
+            }
             CurveType::Bn254 => AB::F::from_canonical_u32(SyscallCode::BN254_ADD.syscall_id()),
             CurveType::Bls12381 => {
                 AB::F::from_canonical_u32(SyscallCode::BLS12381_ADD.syscall_id())
@@ -522,15 +529,19 @@ where
 #[cfg(test)]
 mod tests {
 
+    use std::collections::BTreeMap;
+
     use crate::{
-        runtime::Program,
+        runtime::{Instruction, Opcode, Program, Runtime, SyscallCode},
         stark::CpuProver,
         utils::{
+            ec::weierstrass::secp256r1::Secp256r1,
             run_test, setup_logger,
             tests::{
                 BLS12381_ADD_ELF, BLS12381_DOUBLE_ELF, BLS12381_MUL_ELF, BN254_ADD_ELF,
                 BN254_MUL_ELF, SECP256K1_ADD_ELF, SECP256K1_MUL_ELF,
             },
+            SP1CoreOpts,
         },
     };
 
@@ -541,6 +552,46 @@ mod tests {
         run_test::<CpuProver<_, _>>(program).unwrap();
     }
 
+    #[test]
+    fn test_secp256r1_add_simple() {
+        setup_logger();
+        let p = Secp256r1::generator();
+        let q = p.sw_double();
+        let (p_ptr, q_ptr) = (0x1000, 0x1100);
+
+        let mut memory_image = BTreeMap::new();
+        for (i, word) in p.to_words_le().into_iter().enumerate() {
+            memory_image.insert(p_ptr + 4 * i as u32, word);
+        }
+        for (i, word) in q.to_words_le().into_iter().enumerate() {
+            memory_image.insert(q_ptr + 4 * i as u32, word);
+        }
+        let instructions = vec![
+            Instruction::new(
+                Opcode::ADD,
+                5,
+                0,
+                SyscallCode::SECP256R1_ADD as u32,
+                false,
+                true,
+            ),
+            Instruction::new(Opcode::ADD, 10, 0, p_ptr, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, q_ptr, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+        ];
+        let mut program = Program::new(instructions, 0, 0);
+        program.memory_image = memory_image;
+
+        let mut runtime = Runtime::new(program.clone(), SP1CoreOpts::default());
+        runtime.run().unwrap();
+        let result = (0..16)
+            .map(|i| runtime.word(p_ptr + 4 * i))
+            .collect::<Vec<_>>();
+        assert_eq!(result, p.sw_add(&q).to_words_le());
+
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+
     #[test]
     fn test_bn254_add_simple() {
         setup_logger();
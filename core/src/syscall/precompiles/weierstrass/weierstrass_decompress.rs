@@ -31,6 +31,7 @@ use crate::syscall::precompiles::create_ec_decompress_event;
 use crate::syscall::precompiles::SyscallContext;
 use crate::utils::ec::weierstrass::bls12_381::bls12381_sqrt;
 use crate::utils::ec::weierstrass::secp256k1::secp256k1_sqrt;
+use crate::utils::ec::weierstrass::secp256r1::secp256r1_sqrt;
 use crate::utils::ec::weierstrass::WeierstrassParameters;
 use crate::utils::ec::CurveType;
 use crate::utils::ec::EllipticCurve;
@@ -64,6 +65,15 @@ pub struct WeierstrassDecompressCols<T, P: FieldParameters + NumWords> {
     pub(crate) neg_y: FieldOpCols<T, P>,
 }
 
+/// A set of columns to add the `ax` term of the curve equation, only present for curves with a
+/// nonzero `a` coefficient.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct LinearTermCols<T, P: FieldParameters + NumWords> {
+    pub(crate) ax: FieldOpCols<T, P>,
+    pub(crate) x_3_plus_ax_plus_b: FieldOpCols<T, P>,
+}
+
 /// A set of columns to compute `WeierstrassDecompress` that decompresses a point on a Weierstrass
 /// curve.
 #[derive(Debug, Clone, AlignedBorrow)]
@@ -101,6 +111,7 @@ impl<E: EllipticCurve> Syscall for WeierstrassDecompressChip<E> {
         let event = create_ec_decompress_event::<E>(rt, arg1, arg2);
         match E::CURVE_TYPE {
             CurveType::Secp256k1 => rt.record_mut().k256_decompress_events.push(event),
+            CurveType::Secp256r1 => rt.record_mut().secp256r1_decompress_events.push(event),
             CurveType::Bls12381 => rt.record_mut().bls12381_decompress_events.push(event),
             _ => panic!("Unsupported curve"),
         }
@@ -134,14 +145,24 @@ impl<E: EllipticCurve + WeierstrassParameters> WeierstrassDecompressChip<E> {
         }
     }
 
+    /// The number of [LinearTermCols] columns, which is zero for curves with `a = 0`.
+    fn num_linear_term_cols() -> usize {
+        if E::a_int().is_zero() {
+            0
+        } else {
+            size_of::<LinearTermCols<u8, E::BaseField>>()
+        }
+    }
+
     fn populate_field_ops<F: PrimeField32>(
         record: &mut impl ByteRecord,
         shard: u32,
         channel: u8,
         cols: &mut WeierstrassDecompressCols<F, E::BaseField>,
+        linear_cols: Option<&mut LinearTermCols<F, E::BaseField>>,
         x: BigUint,
     ) {
-        // Y = sqrt(x^3 + b)
+        // Y = sqrt(x^3 + b), or sqrt(x^3 + b + ax) for curves with nonzero `a`.
         cols.range_x
             .populate(record, shard, channel, &x, &E::BaseField::modulus());
         let x_2 = cols.x_2.populate(
@@ -159,15 +180,32 @@ impl<E: EllipticCurve + WeierstrassParameters> WeierstrassDecompressChip<E> {
         let x_3_plus_b =
             cols.x_3_plus_b
                 .populate(record, shard, channel, &x_3, &b, FieldOperation::Add);
+        let y_2 = match linear_cols {
+            Some(linear_cols) => {
+                let a = E::a_int();
+                let ax =
+                    linear_cols
+                        .ax
+                        .populate(record, shard, channel, &a, &x, FieldOperation::Mul);
+                linear_cols.x_3_plus_ax_plus_b.populate(
+                    record,
+                    shard,
+                    channel,
+                    &x_3_plus_b,
+                    &ax,
+                    FieldOperation::Add,
+                )
+            }
+            None => x_3_plus_b,
+        };
 
         let sqrt_fn = match E::CURVE_TYPE {
             CurveType::Secp256k1 => secp256k1_sqrt,
+            CurveType::Secp256r1 => secp256r1_sqrt,
             CurveType::Bls12381 => bls12381_sqrt,
             _ => panic!("Unsupported curve"),
         };
-        let y = cols
-            .y
-            .populate(record, shard, channel, &x_3_plus_b, sqrt_fn);
+        let y = cols.y.populate(record, shard, channel, &y_2, sqrt_fn);
 
         let zero = BigUint::zero();
         cols.neg_y
@@ -184,6 +222,7 @@ impl<F: PrimeField32, E: EllipticCurve + WeierstrassParameters> MachineAir<F>
     fn name(&self) -> String {
         match E::CURVE_TYPE {
             CurveType::Secp256k1 => "Secp256k1Decompress".to_string(),
+            CurveType::Secp256r1 => "Secp256r1Decompress".to_string(),
             CurveType::Bls12381 => "Bls12381Decompress".to_string(),
             _ => panic!("Unsupported curve"),
         }
@@ -196,12 +235,14 @@ impl<F: PrimeField32, E: EllipticCurve + WeierstrassParameters> MachineAir<F>
     ) -> RowMajorMatrix<F> {
         let events = match E::CURVE_TYPE {
             CurveType::Secp256k1 => &input.k256_decompress_events,
+            CurveType::Secp256r1 => &input.secp256r1_decompress_events,
             CurveType::Bls12381 => &input.bls12381_decompress_events,
             _ => panic!("Unsupported curve"),
         };
 
         let mut rows = Vec::new();
         let weierstrass_width = num_weierstrass_decompress_cols::<E::BaseField>();
+        let linear_width = Self::num_linear_term_cols();
         let width = BaseAir::<F>::width(self);
 
         let mut new_byte_lookup_events = Vec::new();
@@ -211,8 +252,10 @@ impl<F: PrimeField32, E: EllipticCurve + WeierstrassParameters> MachineAir<F>
         for i in 0..events.len() {
             let event = events[i].clone();
             let mut row = vec![F::zero(); width];
-            let cols: &mut WeierstrassDecompressCols<F, E::BaseField> =
-                row[0..weierstrass_width].borrow_mut();
+            let (cols, rest) = row.split_at_mut(weierstrass_width);
+            let (linear_cols, choice_cols) = rest.split_at_mut(linear_width);
+            let cols: &mut WeierstrassDecompressCols<F, E::BaseField> = cols.borrow_mut();
+            let linear_cols = (linear_width > 0).then(|| linear_cols.borrow_mut());
 
             cols.is_real = F::from_bool(true);
             cols.shard = F::from_canonical_u32(event.shard);
@@ -228,6 +271,7 @@ impl<F: PrimeField32, E: EllipticCurve + WeierstrassParameters> MachineAir<F>
                 event.shard,
                 event.channel,
                 cols,
+                linear_cols,
                 x,
             );
 
@@ -249,7 +293,7 @@ impl<F: PrimeField32, E: EllipticCurve + WeierstrassParameters> MachineAir<F>
             if matches!(self.sign_rule, SignChoiceRule::Lexicographic) {
                 let lsb = cols.y.lsb;
                 let choice_cols: &mut LexicographicChoiceCols<F, E::BaseField> =
-                    row[weierstrass_width..width].borrow_mut();
+                    choice_cols.borrow_mut();
 
                 let decompressed_y = BigUint::from_bytes_le(&event.decompressed_y_bytes);
                 let neg_y = &modulus - &decompressed_y;
@@ -306,8 +350,9 @@ impl<F: PrimeField32, E: EllipticCurve + WeierstrassParameters> MachineAir<F>
 
         pad_rows(&mut rows, || {
             let mut row = vec![F::zero(); width];
-            let cols: &mut WeierstrassDecompressCols<F, E::BaseField> =
-                row.as_mut_slice()[0..weierstrass_width].borrow_mut();
+            let (cols, rest) = row.split_at_mut(weierstrass_width);
+            let cols: &mut WeierstrassDecompressCols<F, E::BaseField> = cols.borrow_mut();
+            let linear_cols = (linear_width > 0).then(|| rest[..linear_width].borrow_mut());
 
             // take X of the generator as a dummy value to make sure Y^2 = X^3 + b holds
             let dummy_value = E::generator().0;
@@ -317,7 +362,7 @@ impl<F: PrimeField32, E: EllipticCurve + WeierstrassParameters> MachineAir<F>
                 cols.x_access[i].access.value = words[i].into();
             }
 
-            Self::populate_field_ops(&mut vec![], 0, 0, cols, dummy_value);
+            Self::populate_field_ops(&mut vec![], 0, 0, cols, linear_cols, dummy_value);
             row
         });
 
@@ -336,15 +381,17 @@ impl<F: PrimeField32, E: EllipticCurve + WeierstrassParameters> MachineAir<F>
     fn included(&self, shard: &Self::Record) -> bool {
         match E::CURVE_TYPE {
             CurveType::Secp256k1 => !shard.k256_decompress_events.is_empty(),
+            CurveType::Secp256r1 => !shard.secp256r1_decompress_events.is_empty(),
             CurveType::Bls12381 => !shard.bls12381_decompress_events.is_empty(),
             _ => panic!("Unsupported curve"),
         }
     }
 }
 
-impl<F, E: EllipticCurve> BaseAir<F> for WeierstrassDecompressChip<E> {
+impl<F, E: EllipticCurve + WeierstrassParameters> BaseAir<F> for WeierstrassDecompressChip<E> {
     fn width(&self) -> usize {
         num_weierstrass_decompress_cols::<E::BaseField>()
+            + Self::num_linear_term_cols()
             + match self.sign_rule {
                 SignChoiceRule::LeastSignificantBit => 0,
                 SignChoiceRule::Lexicographic => {
@@ -422,6 +469,36 @@ where
             local.is_real,
         );
 
+        // For curves with nonzero `a`, add the `ax` term to get the square of `y`.
+        let linear_width = Self::num_linear_term_cols();
+        let y_2 = if linear_width > 0 {
+            let linear_cols: &LinearTermCols<AB::Var, E::BaseField> =
+                (*local_slice)[weierstrass_cols..weierstrass_cols + linear_width].borrow();
+            let a = E::a_int();
+            let a_const = E::BaseField::to_limbs_field::<AB::F, _>(&a);
+            linear_cols.ax.eval(
+                builder,
+                &a_const,
+                &x,
+                FieldOperation::Mul,
+                local.shard,
+                local.channel,
+                local.is_real,
+            );
+            linear_cols.x_3_plus_ax_plus_b.eval(
+                builder,
+                &local.x_3_plus_b.result,
+                &linear_cols.ax.result,
+                FieldOperation::Add,
+                local.shard,
+                local.channel,
+                local.is_real,
+            );
+            linear_cols.x_3_plus_ax_plus_b.result
+        } else {
+            local.x_3_plus_b.result
+        };
+
         local.neg_y.eval(
             builder,
             &[AB::Expr::zero()].iter(),
@@ -434,7 +511,7 @@ where
 
         local.y.eval(
             builder,
-            &local.x_3_plus_b.result,
+            &y_2,
             local.y.lsb,
             local.shard,
             local.channel,
@@ -469,10 +546,10 @@ where
                 // In order to endorce these constraints, we will use the auxillary choice columns.
 
                 // Get the choice columns from the row slice
+                let choice_offset = weierstrass_cols + linear_width;
                 let choice_cols: &LexicographicChoiceCols<AB::Var, E::BaseField> = (*local_slice)
-                    [weierstrass_cols
-                        ..weierstrass_cols
-                            + size_of::<LexicographicChoiceCols<u8, E::BaseField>>()]
+                    [choice_offset
+                        ..choice_offset + size_of::<LexicographicChoiceCols<u8, E::BaseField>>()]
                     .borrow();
 
                 // Range check the neg_y value since we are now using a lexicographic comparison.
@@ -589,6 +666,9 @@ where
             CurveType::Secp256k1 => {
                 AB::F::from_canonical_u32(SyscallCode::SECP256K1_DECOMPRESS.syscall_id())
             }
+            CurveType::Secp256r1 => {
+                AB::F::from_canonical_u32(SyscallCode::SECP256R1_DECOMPRESS.syscall_id())
+            }
             CurveType::Bls12381 => {
                 AB::F::from_canonical_u32(SyscallCode::BLS12381_DECOMPRESS.syscall_id())
             }
@@ -620,8 +700,13 @@ mod tests {
     use elliptic_curve::sec1::ToEncodedPoint;
     use rand::{thread_rng, Rng};
 
-    use crate::utils::run_test_io;
+    use crate::runtime::{Instruction, Opcode, Runtime, SyscallCode};
+    use crate::utils::ec::weierstrass::secp256r1::Secp256r1;
+    use crate::utils::ec::EllipticCurve;
     use crate::utils::tests::SECP256K1_DECOMPRESS_ELF;
+    use crate::utils::{run_test, run_test_io, SP1CoreOpts};
+    use num::bigint::RandBigInt;
+    use std::collections::BTreeMap;
 
     #[test]
     fn test_weierstrass_bls_decompress() {
@@ -678,4 +763,52 @@ mod tests {
             assert_eq!(result, decompressed);
         }
     }
+
+    #[test]
+    fn test_weierstrass_secp256r1_decompress() {
+        utils::setup_logger();
+        let mut rng = thread_rng();
+
+        // Decompress a random point and its negation so that both sign bits are exercised.
+        let point = Secp256r1::generator().sw_scalar_mul(&rng.gen_biguint(64));
+        let points = [point.clone(), Secp256r1::ec_neg(&point)];
+
+        let mut memory_image = BTreeMap::new();
+        let mut instructions = vec![];
+        for (i, point) in points.iter().enumerate() {
+            let ptr = 0x1000 + 0x100 * i as u32;
+            let x_words = &point.to_words_le()[..8];
+            for (j, word) in x_words.iter().enumerate() {
+                memory_image.insert(ptr + 32 + 4 * j as u32, *word);
+            }
+            let sign_bit = point.y.bit(0) as u32;
+            instructions.extend([
+                Instruction::new(
+                    Opcode::ADD,
+                    5,
+                    0,
+                    SyscallCode::SECP256R1_DECOMPRESS as u32,
+                    false,
+                    true,
+                ),
+                Instruction::new(Opcode::ADD, 10, 0, ptr, false, true),
+                Instruction::new(Opcode::ADD, 11, 0, sign_bit, false, true),
+                Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            ]);
+        }
+        let mut program = Program::new(instructions, 0, 0);
+        program.memory_image = memory_image;
+
+        let mut runtime = Runtime::new(program.clone(), SP1CoreOpts::default());
+        runtime.run().unwrap();
+        for (i, point) in points.iter().enumerate() {
+            let ptr = 0x1000 + 0x100 * i as u32;
+            let y_words = (0..8)
+                .map(|j| runtime.word(ptr + 4 * j))
+                .collect::<Vec<_>>();
+            assert_eq!(y_words, point.to_words_le()[8..]);
+        }
+
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
 }
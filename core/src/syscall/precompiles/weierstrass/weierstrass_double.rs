@@ -17,6 +17,7 @@ use p3_maybe_rayon::prelude::ParallelSlice;
 use sp1_derive::AlignedBorrow;
 
 use crate::air::MachineAir;
+use crate::air::Polynomial;
 use crate::air::SP1AirBuilder;
 use crate::bytes::event::ByteRecord;
 use crate::bytes::ByteLookupEvent;
@@ -80,6 +81,7 @@ impl<E: EllipticCurve + WeierstrassParameters> Syscall for WeierstrassDoubleAssi
         let event = create_ec_double_event::<E>(rt, arg1, arg2);
         match E::CURVE_TYPE {
             CurveType::Secp256k1 => rt.record_mut().secp256k1_double_events.push(event),
+            CurveType::Secp256r1 => rt.record_mut().secp256r1_double_events.push(event),
             CurveType::Bn254 => rt.record_mut().bn254_double_events.push(event),
             CurveType::Bls12381 => rt.record_mut().bls12381_double_events.push(event),
             _ => panic!("Unsupported curve"),
@@ -106,10 +108,12 @@ impl<E: EllipticCurve + WeierstrassParameters> WeierstrassDoubleAssignChip<E> {
         cols: &mut WeierstrassDoubleAssignCols<F, E::BaseField>,
         p_x: BigUint,
         p_y: BigUint,
+        is_real: bool,
     ) {
         // This populates necessary field operations to double a point on a Weierstrass curve.
 
-        let a = E::a_int();
+        // `a` is only added on real rows, see the note in `eval`.
+        let a = if is_real { E::a_int() } else { BigUint::zero() };
 
         // slope = slope_numerator / slope_denominator.
         let slope = {
@@ -228,6 +232,7 @@ impl<F: PrimeField32, E: EllipticCurve + WeierstrassParameters> MachineAir<F>
     fn name(&self) -> String {
         match E::CURVE_TYPE {
             CurveType::Secp256k1 => "Secp256k1DoubleAssign".to_string(),
+            CurveType::Secp256r1 => "Secp256r1DoubleAssign".to_string(),
             CurveType::Bn254 => "Bn254DoubleAssign".to_string(),
             CurveType::Bls12381 => "Bls12381DoubleAssign".to_string(),
             _ => panic!("Unsupported curve"),
@@ -242,6 +247,7 @@ impl<F: PrimeField32, E: EllipticCurve + WeierstrassParameters> MachineAir<F>
         // collects the events based on the curve type.
         let events = match E::CURVE_TYPE {
             CurveType::Secp256k1 => &input.secp256k1_double_events,
+            CurveType::Secp256r1 => &input.secp256r1_double_events,
             CurveType::Bn254 => &input.bn254_double_events,
             CurveType::Bls12381 => &input.bls12381_double_events,
             _ => panic!("Unsupported curve"),
@@ -283,6 +289,7 @@ impl<F: PrimeField32, E: EllipticCurve + WeierstrassParameters> MachineAir<F>
                             cols,
                             p_x,
                             p_y,
+                            true,
                         );
 
                         // Populate the memory access columns.
@@ -313,7 +320,7 @@ impl<F: PrimeField32, E: EllipticCurve + WeierstrassParameters> MachineAir<F>
             let cols: &mut WeierstrassDoubleAssignCols<F, E::BaseField> =
                 row.as_mut_slice().borrow_mut();
             let zero = BigUint::zero();
-            Self::populate_field_ops(&mut vec![], 0, 0, cols, zero.clone(), zero.clone());
+            Self::populate_field_ops(&mut vec![], 0, 0, cols, zero.clone(), zero, false);
             row
         });
 
@@ -338,6 +345,7 @@ impl<F: PrimeField32, E: EllipticCurve + WeierstrassParameters> MachineAir<F>
     fn included(&self, shard: &Self::Record) -> bool {
         match E::CURVE_TYPE {
             CurveType::Secp256k1 => !shard.secp256k1_double_events.is_empty(),
+            CurveType::Secp256r1 => !shard.secp256r1_double_events.is_empty(),
             CurveType::Bn254 => !shard.bn254_double_events.is_empty(),
             CurveType::Bls12381 => !shard.bls12381_double_events.is_empty(),
             _ => panic!("Unsupported curve"),
@@ -374,7 +382,14 @@ where
         let p_y = limbs_from_prev_access(&local.p_access[num_words_field_element..]);
 
         // `a` in the Weierstrass form: y^2 = x^3 + a * x + b.
-        let a = E::BaseField::to_limbs_field::<AB::Expr, _>(&E::a_int());
+        //
+        // It is multiplied by `is_real` so that padding rows, which double the all-zero point, have
+        // a zero slope numerator even on curves where `a` is non-zero.
+        let a = E::BaseField::to_limbs_field::<AB::Expr, _>(&E::a_int())
+            .0
+            .into_iter()
+            .map(|limb| limb * local.is_real)
+            .collect::<Polynomial<_>>();
 
         // slope = slope_numerator / slope_denominator.
         let slope = {
@@ -524,6 +539,9 @@ where
             CurveType::Secp256k1 => {
                 AB::F::from_canonical_u32(SyscallCode::SECP256K1_DOUBLE.syscall_id())
             }
+            CurveType::Secp256r1 => {
+                AB::F::from_canonical_u32(SyscallCode::SECP256R1_DOUBLE.syscall_id())
+            }
             CurveType::Bn254 => AB::F::from_canonical_u32(SyscallCode::BN254_DOUBLE.syscall_id()),
             CurveType::Bls12381 => {
                 AB::F::from_canonical_u32(SyscallCode::BLS12381_DOUBLE.syscall_id())
@@ -547,12 +565,16 @@ where
 #[cfg(test)]
 pub mod tests {
 
+    use std::collections::BTreeMap;
+
     use crate::{
-        runtime::Program,
+        runtime::{Instruction, Opcode, Program, Runtime, SyscallCode},
         stark::CpuProver,
         utils::{
+            ec::weierstrass::secp256r1::Secp256r1,
             run_test, setup_logger,
             tests::{BLS12381_DOUBLE_ELF, BN254_DOUBLE_ELF, SECP256K1_DOUBLE_ELF},
+            SP1CoreOpts,
         },
     };
 
@@ -563,6 +585,44 @@ pub mod tests {
         run_test::<CpuProver<_, _>>(program).unwrap();
     }
 
+    #[test]
+    fn test_secp256r1_double_simple() {
+        setup_logger();
+        let p = Secp256r1::generator();
+        let p_ptr = 0x1000;
+
+        let memory_image = p
+            .to_words_le()
+            .into_iter()
+            .enumerate()
+            .map(|(i, word)| (p_ptr + 4 * i as u32, word))
+            .collect::<BTreeMap<_, _>>();
+        let instructions = vec![
+            Instruction::new(
+                Opcode::ADD,
+                5,
+                0,
+                SyscallCode::SECP256R1_DOUBLE as u32,
+                false,
+                true,
+            ),
+            Instruction::new(Opcode::ADD, 10, 0, p_ptr, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, 0, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+        ];
+        let mut program = Program::new(instructions, 0, 0);
+        program.memory_image = memory_image;
+
+        let mut runtime = Runtime::new(program.clone(), SP1CoreOpts::default());
+        runtime.run().unwrap();
+        let result = (0..16)
+            .map(|i| runtime.word(p_ptr + 4 * i))
+            .collect::<Vec<_>>();
+        assert_eq!(result, p.sw_double().to_words_le());
+
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+
     #[test]
     fn test_bn254_double_simple() {
         setup_logger();
@@ -0,0 +1,1224 @@
+use core::borrow::{Borrow, BorrowMut};
+use core::mem::size_of;
+use std::marker::PhantomData;
+
+use generic_array::GenericArray;
+use num::BigUint;
+use num::Zero;
+use p3_air::AirBuilder;
+use p3_air::{Air, BaseAir};
+use p3_field::AbstractField;
+use p3_field::PrimeField32;
+use p3_matrix::dense::RowMajorMatrix;
+use p3_matrix::Matrix;
+use p3_maybe_rayon::prelude::{ParallelIterator, ParallelSlice};
+use sp1_derive::AlignedBorrow;
+
+use crate::air::BaseAirBuilder;
+use crate::air::MachineAir;
+use crate::air::Polynomial;
+use crate::air::SP1AirBuilder;
+use crate::bytes::event::ByteRecord;
+use crate::memory::value_as_limbs;
+use crate::memory::MemoryReadCols;
+use crate::memory::MemoryWriteCols;
+use crate::operations::field::field_op::FieldOpCols;
+use crate::operations::field::field_op::FieldOperation;
+use crate::operations::field::params::{
+    limbs_from_vec, FieldParameters, Limbs, NumLimbs, NumWords,
+};
+use crate::operations::field::range::FieldLtCols;
+use crate::runtime::ExecutionRecord;
+use crate::runtime::Program;
+use crate::runtime::Syscall;
+use crate::runtime::SyscallCode;
+use crate::stark::MachineRecord;
+use crate::syscall::precompiles::SyscallContext;
+use crate::syscall::precompiles::{
+    create_ec_mul_event, eval_scalar_bits, eval_step_flags, AffinePointCols, ECMulEvent,
+};
+use crate::utils::ec::scalar_mul::{
+    scalar_windows_be, SCALAR_MUL_NUM_WINDOWS, SCALAR_MUL_SCALAR_BITS, SCALAR_MUL_SCALAR_WORDS,
+    SCALAR_MUL_WINDOW_BITS,
+};
+use crate::utils::ec::utils::biguint_to_bits_le;
+use crate::utils::ec::weierstrass::WeierstrassParameters;
+use crate::utils::ec::AffinePoint;
+use crate::utils::ec::CurveType;
+use crate::utils::ec::EllipticCurve;
+use crate::utils::limbs_from_access;
+use crate::utils::pad_rows;
+
+pub const fn num_weierstrass_mul_cols<P: FieldParameters + NumWords>() -> usize {
+    size_of::<WeierstrassMulAssignCols<u8, P>>()
+}
+
+/// A set of columns to add two different points on a Weierstrass curve.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct WeierstrassAddCols<T, P: FieldParameters> {
+    pub(crate) slope_denominator: FieldOpCols<T, P>,
+    pub(crate) slope_numerator: FieldOpCols<T, P>,
+    pub(crate) slope: FieldOpCols<T, P>,
+    pub(crate) slope_squared: FieldOpCols<T, P>,
+    pub(crate) p_x_plus_q_x: FieldOpCols<T, P>,
+    pub(crate) x3_ins: FieldOpCols<T, P>,
+    pub(crate) p_x_minus_x: FieldOpCols<T, P>,
+    pub(crate) y3_ins: FieldOpCols<T, P>,
+    pub(crate) slope_times_p_x_minus_x: FieldOpCols<T, P>,
+}
+
+/// A set of columns to double a point on a Weierstrass curve.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct WeierstrassDoubleCols<T, P: FieldParameters> {
+    pub(crate) slope_denominator: FieldOpCols<T, P>,
+    pub(crate) slope_numerator: FieldOpCols<T, P>,
+    pub(crate) slope: FieldOpCols<T, P>,
+    pub(crate) p_x_squared: FieldOpCols<T, P>,
+    pub(crate) p_x_squared_times_3: FieldOpCols<T, P>,
+    pub(crate) slope_squared: FieldOpCols<T, P>,
+    pub(crate) p_x_plus_p_x: FieldOpCols<T, P>,
+    pub(crate) x3_ins: FieldOpCols<T, P>,
+    pub(crate) p_x_minus_x: FieldOpCols<T, P>,
+    pub(crate) y3_ins: FieldOpCols<T, P>,
+    pub(crate) slope_times_p_x_minus_x: FieldOpCols<T, P>,
+}
+
+/// A set of columns for one step of a fixed-window scalar multiplication on a Weierstrass curve.
+///
+/// A scalar multiplication takes [SCALAR_MUL_NUM_WINDOWS] consecutive rows, one per window of the
+/// scalar starting from the most significant one. Each step computes `4 * acc + q`, where `q` is
+/// the multiple of `P` selected by the window. Since the point at infinity has no affine
+/// coordinates, the accumulator starts at infinity and is tracked by `acc_is_infinity`, and the
+/// addition is replaced by `2P + P` whenever its result is not used, which also computes the
+/// window table `[2P, 3P]` on the first step.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct WeierstrassMulAssignCols<T, P: FieldParameters + NumWords> {
+    pub is_real: T,
+    pub shard: T,
+    pub channel: T,
+    pub clk: T,
+    pub nonce: T,
+    pub p_ptr: T,
+    pub scalar_ptr: T,
+
+    /// Whether this row is the first step of a scalar multiplication.
+    pub is_first_step: T,
+    /// Whether this row is the last step of a scalar multiplication.
+    pub is_last_step: T,
+    /// The index of the step within the scalar multiplication.
+    pub step: T,
+    /// The inverse of `step - (SCALAR_MUL_NUM_WINDOWS - 1)`, or zero on the last step.
+    pub step_inverse: T,
+
+    /// The point and the scalar, read on the first step.
+    pub p_access: GenericArray<MemoryReadCols<T>, P::WordsCurvePoint>,
+    pub scalar_access: [MemoryReadCols<T>; SCALAR_MUL_SCALAR_WORDS],
+    /// The product, written on the last step.
+    pub result_access: GenericArray<MemoryWriteCols<T>, P::WordsCurvePoint>,
+
+    /// The bits of the scalar, shifted up by [SCALAR_MUL_WINDOW_BITS] at every step so that the
+    /// current window is held by the most significant bits.
+    pub scalar_bits: [T; SCALAR_MUL_SCALAR_BITS],
+    /// Checks that the scalar is less than the group order on the first step.
+    pub scalar_range: FieldLtCols<T, P>,
+
+    /// The window table.
+    pub p: AffinePointCols<T, P>,
+    pub p_times_2: AffinePointCols<T, P>,
+    pub p_times_3: AffinePointCols<T, P>,
+
+    /// The accumulator before this step, which holds `P` while it is the point at infinity.
+    pub acc: AffinePointCols<T, P>,
+    pub acc_is_infinity: T,
+    pub window_is_zero: T,
+    /// The multiple of `P` selected by the current window, or `P` if the window is zero.
+    pub q: AffinePointCols<T, P>,
+    pub first_double: WeierstrassDoubleCols<T, P>,
+    pub second_double: WeierstrassDoubleCols<T, P>,
+    /// The operands of `add`, which are `4 * acc` and `q` if the sum is used, and `2P` and `P`
+    /// otherwise.
+    pub add_lhs: AffinePointCols<T, P>,
+    pub add_rhs: AffinePointCols<T, P>,
+    pub add: WeierstrassAddCols<T, P>,
+    /// The accumulator after this step.
+    pub result: AffinePointCols<T, P>,
+    pub result_is_infinity: T,
+}
+
+#[derive(Default)]
+pub struct WeierstrassMulAssignChip<E> {
+    _marker: PhantomData<E>,
+}
+
+impl<E: EllipticCurve + WeierstrassParameters> Syscall for WeierstrassMulAssignChip<E> {
+    fn execute(&self, rt: &mut SyscallContext, arg1: u32, arg2: u32) -> Option<u32> {
+        // The incomplete addition formula only handles the intermediate points of scalars below
+        // the group order.
+        let scalar = BigUint::from_slice(&rt.slice_unsafe(arg2, SCALAR_MUL_SCALAR_WORDS));
+        if scalar >= E::prime_group_order() {
+            rt.fail("the scalar must be less than the group order");
+            return None;
+        }
+        let Some(event) = create_ec_mul_event::<E>(rt, arg1, arg2) else {
+            rt.fail("the product is the point at infinity");
+            return None;
+        };
+        match E::CURVE_TYPE {
+            CurveType::Secp256k1 => rt.record_mut().secp256k1_mul_events.push(event),
+            CurveType::Bn254 => rt.record_mut().bn254_mul_events.push(event),
+            _ => panic!("Unsupported curve"),
+        }
+        None
+    }
+
+    fn num_extra_cycles(&self) -> u32 {
+        1
+    }
+}
+
+impl<E: EllipticCurve + WeierstrassParameters> WeierstrassMulAssignChip<E> {
+    pub const fn new() -> Self {
+        Self {
+            _marker: PhantomData,
+        }
+    }
+
+    /// Populates the columns of a step, and returns the accumulator after the step and whether it
+    /// is the point at infinity.
+    #[allow(clippy::too_many_arguments)]
+    fn populate_step<F: PrimeField32>(
+        record: &mut impl ByteRecord,
+        shard: u32,
+        channel: u8,
+        cols: &mut WeierstrassMulAssignCols<F, E::BaseField>,
+        table: &[AffinePoint<E>],
+        acc: &AffinePoint<E>,
+        acc_is_infinity: bool,
+        window: usize,
+        is_real: bool,
+    ) -> (AffinePoint<E>, bool) {
+        cols.p.populate(&table[0]);
+        cols.p_times_2.populate(&table[1]);
+        cols.p_times_3.populate(&table[2]);
+        cols.acc.populate(acc);
+
+        let window_is_zero = window == 0;
+        let result_is_infinity = acc_is_infinity && window_is_zero;
+        let use_add = !acc_is_infinity && !window_is_zero;
+        cols.acc_is_infinity = F::from_bool(acc_is_infinity);
+        cols.window_is_zero = F::from_bool(window_is_zero);
+        cols.result_is_infinity = F::from_bool(result_is_infinity);
+
+        let acc_times_2 = cols
+            .first_double
+            .populate::<E>(record, shard, channel, acc, is_real);
+        let acc_times_4 =
+            cols.second_double
+                .populate::<E>(record, shard, channel, &acc_times_2, is_real);
+
+        let q = table[window.saturating_sub(1)].clone();
+        cols.q.populate(&q);
+        let (add_lhs, add_rhs) = if use_add {
+            (acc_times_4.clone(), q.clone())
+        } else {
+            (table[1].clone(), table[0].clone())
+        };
+        cols.add_lhs.populate(&add_lhs);
+        cols.add_rhs.populate(&add_rhs);
+        let sum = cols
+            .add
+            .populate(record, shard, channel, &add_lhs, &add_rhs);
+
+        let result = if result_is_infinity {
+            table[0].clone()
+        } else if acc_is_infinity {
+            q
+        } else if window_is_zero {
+            acc_times_4
+        } else {
+            sum
+        };
+        cols.result.populate(&result);
+        (result, result_is_infinity)
+    }
+
+    /// Creates the rows of an event.
+    fn event_to_rows<F: PrimeField32>(
+        &self,
+        event: &ECMulEvent,
+        record: &mut impl ByteRecord,
+    ) -> Vec<Vec<F>> {
+        let num_cols = num_weierstrass_mul_cols::<E::BaseField>();
+        let p = AffinePoint::<E>::from_words_le(&event.p);
+        let table = p.window_table();
+        let scalar = BigUint::from_slice(&event.scalar);
+        let mut scalar_bits = biguint_to_bits_le(&scalar, SCALAR_MUL_SCALAR_BITS);
+
+        let mut acc = p;
+        let mut acc_is_infinity = true;
+        let mut rows = Vec::with_capacity(SCALAR_MUL_NUM_WINDOWS);
+        for (step, window) in scalar_windows_be(&scalar).into_iter().enumerate() {
+            let mut row = vec![F::zero(); num_cols];
+            let cols: &mut WeierstrassMulAssignCols<F, E::BaseField> =
+                row.as_mut_slice().borrow_mut();
+            let is_first_step = step == 0;
+            let is_last_step = step == SCALAR_MUL_NUM_WINDOWS - 1;
+
+            cols.is_real = F::one();
+            cols.shard = F::from_canonical_u32(event.shard);
+            cols.channel = F::from_canonical_u8(event.channel);
+            cols.clk = F::from_canonical_u32(event.clk);
+            cols.p_ptr = F::from_canonical_u32(event.p_ptr);
+            cols.scalar_ptr = F::from_canonical_u32(event.scalar_ptr);
+            cols.is_first_step = F::from_bool(is_first_step);
+            cols.is_last_step = F::from_bool(is_last_step);
+            cols.step = F::from_canonical_usize(step);
+            cols.step_inverse = (cols.step - F::from_canonical_usize(SCALAR_MUL_NUM_WINDOWS - 1))
+                .try_inverse()
+                .unwrap_or(F::zero());
+
+            if is_first_step {
+                for i in 0..cols.p_access.len() {
+                    cols.p_access[i].populate(event.channel, event.p_read_records[i], record);
+                }
+                for i in 0..SCALAR_MUL_SCALAR_WORDS {
+                    cols.scalar_access[i].populate(
+                        event.channel,
+                        event.scalar_read_records[i],
+                        record,
+                    );
+                }
+                cols.scalar_range.populate(
+                    record,
+                    event.shard,
+                    event.channel,
+                    &scalar,
+                    &E::prime_group_order(),
+                );
+            }
+            if is_last_step {
+                for i in 0..cols.result_access.len() {
+                    cols.result_access[i].populate(event.channel, event.p_write_records[i], record);
+                }
+            }
+
+            cols.scalar_bits = scalar_bits
+                .iter()
+                .map(|bit| F::from_bool(*bit))
+                .collect::<Vec<_>>()
+                .try_into()
+                .unwrap();
+            scalar_bits.rotate_right(SCALAR_MUL_WINDOW_BITS);
+            scalar_bits[..SCALAR_MUL_WINDOW_BITS].fill(false);
+
+            (acc, acc_is_infinity) = Self::populate_step(
+                record,
+                event.shard,
+                event.channel,
+                cols,
+                &table,
+                &acc,
+                acc_is_infinity,
+                window,
+                true,
+            );
+            rows.push(row);
+        }
+        assert!(!acc_is_infinity);
+        assert_eq!(acc.to_words_le(), event.result);
+
+        rows
+    }
+}
+
+impl<F: PrimeField32, P: FieldParameters> WeierstrassAddCols<F, P> {
+    /// Populates the columns to add `p` and `q`, and returns the sum.
+    pub fn populate<E: EllipticCurve>(
+        &mut self,
+        record: &mut impl ByteRecord,
+        shard: u32,
+        channel: u8,
+        p: &AffinePoint<E>,
+        q: &AffinePoint<E>,
+    ) -> AffinePoint<E> {
+        // slope = (q.y - p.y) / (q.x - p.x).
+        let slope = {
+            let slope_numerator = self.slope_numerator.populate(
+                record,
+                shard,
+                channel,
+                &q.y,
+                &p.y,
+                FieldOperation::Sub,
+            );
+            let slope_denominator = self.slope_denominator.populate(
+                record,
+                shard,
+                channel,
+                &q.x,
+                &p.x,
+                FieldOperation::Sub,
+            );
+            self.slope.populate(
+                record,
+                shard,
+                channel,
+                &slope_numerator,
+                &slope_denominator,
+                FieldOperation::Div,
+            )
+        };
+
+        // x = slope * slope - (p.x + q.x).
+        let x = {
+            let slope_squared = self.slope_squared.populate(
+                record,
+                shard,
+                channel,
+                &slope,
+                &slope,
+                FieldOperation::Mul,
+            );
+            let p_x_plus_q_x =
+                self.p_x_plus_q_x
+                    .populate(record, shard, channel, &p.x, &q.x, FieldOperation::Add);
+            self.x3_ins.populate(
+                record,
+                shard,
+                channel,
+                &slope_squared,
+                &p_x_plus_q_x,
+                FieldOperation::Sub,
+            )
+        };
+
+        // y = slope * (p.x - x) - p.y.
+        let y = {
+            let p_x_minus_x =
+                self.p_x_minus_x
+                    .populate(record, shard, channel, &p.x, &x, FieldOperation::Sub);
+            let slope_times_p_x_minus_x = self.slope_times_p_x_minus_x.populate(
+                record,
+                shard,
+                channel,
+                &slope,
+                &p_x_minus_x,
+                FieldOperation::Mul,
+            );
+            self.y3_ins.populate(
+                record,
+                shard,
+                channel,
+                &slope_times_p_x_minus_x,
+                &p.y,
+                FieldOperation::Sub,
+            )
+        };
+
+        AffinePoint::new(x, y)
+    }
+}
+
+impl<V: Copy, P: FieldParameters> WeierstrassAddCols<V, P> {
+    /// Evaluates the addition of `p` and `q`. The sum is held by the results of `x3_ins` and
+    /// `y3_ins`.
+    pub fn eval<AB: SP1AirBuilder<Var = V>>(
+        &self,
+        builder: &mut AB,
+        p: &AffinePointCols<V, P>,
+        q: &AffinePointCols<V, P>,
+        shard: impl Into<AB::Expr> + Clone,
+        channel: impl Into<AB::Expr> + Clone,
+        is_real: impl Into<AB::Expr> + Clone,
+    ) where
+        V: Into<AB::Expr>,
+        Limbs<V, P::Limbs>: Copy,
+    {
+        // slope = (q.y - p.y) / (q.x - p.x).
+        self.slope_numerator.eval(
+            builder,
+            &q.y,
+            &p.y,
+            FieldOperation::Sub,
+            shard.clone(),
+            channel.clone(),
+            is_real.clone(),
+        );
+        self.slope_denominator.eval(
+            builder,
+            &q.x,
+            &p.x,
+            FieldOperation::Sub,
+            shard.clone(),
+            channel.clone(),
+            is_real.clone(),
+        );
+        self.slope.eval(
+            builder,
+            &self.slope_numerator.result,
+            &self.slope_denominator.result,
+            FieldOperation::Div,
+            shard.clone(),
+            channel.clone(),
+            is_real.clone(),
+        );
+        let slope = &self.slope.result;
+
+        // x = slope * slope - (p.x + q.x).
+        self.slope_squared.eval(
+            builder,
+            slope,
+            slope,
+            FieldOperation::Mul,
+            shard.clone(),
+            channel.clone(),
+            is_real.clone(),
+        );
+        self.p_x_plus_q_x.eval(
+            builder,
+            &p.x,
+            &q.x,
+            FieldOperation::Add,
+            shard.clone(),
+            channel.clone(),
+            is_real.clone(),
+        );
+        self.x3_ins.eval(
+            builder,
+            &self.slope_squared.result,
+            &self.p_x_plus_q_x.result,
+            FieldOperation::Sub,
+            shard.clone(),
+            channel.clone(),
+            is_real.clone(),
+        );
+
+        // y = slope * (p.x - x) - p.y.
+        self.p_x_minus_x.eval(
+            builder,
+            &p.x,
+            &self.x3_ins.result,
+            FieldOperation::Sub,
+            shard.clone(),
+            channel.clone(),
+            is_real.clone(),
+        );
+        self.slope_times_p_x_minus_x.eval(
+            builder,
+            slope,
+            &self.p_x_minus_x.result,
+            FieldOperation::Mul,
+            shard.clone(),
+            channel.clone(),
+            is_real.clone(),
+        );
+        self.y3_ins.eval(
+            builder,
+            &self.slope_times_p_x_minus_x.result,
+            &p.y,
+            FieldOperation::Sub,
+            shard,
+            channel,
+            is_real,
+        );
+    }
+
+    /// Returns the sum as point columns.
+    pub fn result(&self) -> AffinePointCols<V, P> {
+        AffinePointCols {
+            x: self.x3_ins.result.clone(),
+            y: self.y3_ins.result.clone(),
+        }
+    }
+}
+
+impl<F: PrimeField32, P: FieldParameters> WeierstrassDoubleCols<F, P> {
+    /// Populates the columns to double `p`, and returns the result.
+    ///
+    /// `a` is only added on real rows, see the note in [WeierstrassDoubleCols::eval].
+    pub fn populate<E: EllipticCurve + WeierstrassParameters>(
+        &mut self,
+        record: &mut impl ByteRecord,
+        shard: u32,
+        channel: u8,
+        p: &AffinePoint<E>,
+        is_real: bool,
+    ) -> AffinePoint<E> {
+        let a = if is_real { E::a_int() } else { BigUint::zero() };
+
+        // slope = slope_numerator / slope_denominator.
+        let slope = {
+            // slope_numerator = a + (p.x * p.x) * 3.
+            let p_x_squared =
+                self.p_x_squared
+                    .populate(record, shard, channel, &p.x, &p.x, FieldOperation::Mul);
+            let p_x_squared_times_3 = self.p_x_squared_times_3.populate(
+                record,
+                shard,
+                channel,
+                &p_x_squared,
+                &BigUint::from(3u32),
+                FieldOperation::Mul,
+            );
+            let slope_numerator = self.slope_numerator.populate(
+                record,
+                shard,
+                channel,
+                &a,
+                &p_x_squared_times_3,
+                FieldOperation::Add,
+            );
+
+            // slope_denominator = 2 * y.
+            let slope_denominator = self.slope_denominator.populate(
+                record,
+                shard,
+                channel,
+                &BigUint::from(2u32),
+                &p.y,
+                FieldOperation::Mul,
+            );
+
+            self.slope.populate(
+                record,
+                shard,
+                channel,
+                &slope_numerator,
+                &slope_denominator,
+                FieldOperation::Div,
+            )
+        };
+
+        // x = slope * slope - (p.x + p.x).
+        let x = {
+            let slope_squared = self.slope_squared.populate(
+                record,
+                shard,
+                channel,
+                &slope,
+                &slope,
+                FieldOperation::Mul,
+            );
+            let p_x_plus_p_x =
+                self.p_x_plus_p_x
+                    .populate(record, shard, channel, &p.x, &p.x, FieldOperation::Add);
+            self.x3_ins.populate(
+                record,
+                shard,
+                channel,
+                &slope_squared,
+                &p_x_plus_p_x,
+                FieldOperation::Sub,
+            )
+        };
+
+        // y = slope * (p.x - x) - p.y.
+        let y = {
+            let p_x_minus_x =
+                self.p_x_minus_x
+                    .populate(record, shard, channel, &p.x, &x, FieldOperation::Sub);
+            let slope_times_p_x_minus_x = self.slope_times_p_x_minus_x.populate(
+                record,
+                shard,
+                channel,
+                &slope,
+                &p_x_minus_x,
+                FieldOperation::Mul,
+            );
+            self.y3_ins.populate(
+                record,
+                shard,
+                channel,
+                &slope_times_p_x_minus_x,
+                &p.y,
+                FieldOperation::Sub,
+            )
+        };
+
+        AffinePoint::new(x, y)
+    }
+}
+
+impl<V: Copy, P: FieldParameters> WeierstrassDoubleCols<V, P> {
+    /// Evaluates the doubling of `p`. The result is held by the results of `x3_ins` and `y3_ins`.
+    ///
+    /// `a` is multiplied by `is_real` so that padding rows, which double the all-zero point, have
+    /// a zero slope numerator even on curves where `a` is non-zero.
+    pub fn eval<AB: SP1AirBuilder<Var = V>, E: WeierstrassParameters<BaseField = P>>(
+        &self,
+        builder: &mut AB,
+        p: &AffinePointCols<V, P>,
+        shard: impl Into<AB::Expr> + Clone,
+        channel: impl Into<AB::Expr> + Clone,
+        is_real: impl Into<AB::Expr> + Clone,
+    ) where
+        V: Into<AB::Expr>,
+        Limbs<V, P::Limbs>: Copy,
+    {
+        let a = P::to_limbs_field::<AB::Expr, _>(&E::a_int())
+            .0
+            .into_iter()
+            .map(|limb| limb * is_real.clone().into())
+            .collect::<Polynomial<_>>();
+
+        // slope_numerator = a + (p.x * p.x) * 3.
+        self.p_x_squared.eval(
+            builder,
+            &p.x,
+            &p.x,
+            FieldOperation::Mul,
+            shard.clone(),
+            channel.clone(),
+            is_real.clone(),
+        );
+        self.p_x_squared_times_3.eval(
+            builder,
+            &self.p_x_squared.result,
+            &P::to_limbs_field::<AB::Expr, _>(&BigUint::from(3u32)),
+            FieldOperation::Mul,
+            shard.clone(),
+            channel.clone(),
+            is_real.clone(),
+        );
+        self.slope_numerator.eval(
+            builder,
+            &a,
+            &self.p_x_squared_times_3.result,
+            FieldOperation::Add,
+            shard.clone(),
+            channel.clone(),
+            is_real.clone(),
+        );
+
+        // slope_denominator = 2 * y.
+        self.slope_denominator.eval(
+            builder,
+            &P::to_limbs_field::<AB::Expr, _>(&BigUint::from(2u32)),
+            &p.y,
+            FieldOperation::Mul,
+            shard.clone(),
+            channel.clone(),
+            is_real.clone(),
+        );
+
+        // slope = slope_numerator / slope_denominator.
+        self.slope.eval(
+            builder,
+            &self.slope_numerator.result,
+            &self.slope_denominator.result,
+            FieldOperation::Div,
+            shard.clone(),
+            channel.clone(),
+            is_real.clone(),
+        );
+        let slope = &self.slope.result;
+
+        // x = slope * slope - (p.x + p.x).
+        self.slope_squared.eval(
+            builder,
+            slope,
+            slope,
+            FieldOperation::Mul,
+            shard.clone(),
+            channel.clone(),
+            is_real.clone(),
+        );
+        self.p_x_plus_p_x.eval(
+            builder,
+            &p.x,
+            &p.x,
+            FieldOperation::Add,
+            shard.clone(),
+            channel.clone(),
+            is_real.clone(),
+        );
+        self.x3_ins.eval(
+            builder,
+            &self.slope_squared.result,
+            &self.p_x_plus_p_x.result,
+            FieldOperation::Sub,
+            shard.clone(),
+            channel.clone(),
+            is_real.clone(),
+        );
+
+        // y = slope * (p.x - x) - p.y.
+        self.p_x_minus_x.eval(
+            builder,
+            &p.x,
+            &self.x3_ins.result,
+            FieldOperation::Sub,
+            shard.clone(),
+            channel.clone(),
+            is_real.clone(),
+        );
+        self.slope_times_p_x_minus_x.eval(
+            builder,
+            slope,
+            &self.p_x_minus_x.result,
+            FieldOperation::Mul,
+            shard.clone(),
+            channel.clone(),
+            is_real.clone(),
+        );
+        self.y3_ins.eval(
+            builder,
+            &self.slope_times_p_x_minus_x.result,
+            &p.y,
+            FieldOperation::Sub,
+            shard,
+            channel,
+            is_real,
+        );
+    }
+
+    /// Returns the double as point columns.
+    pub fn result(&self) -> AffinePointCols<V, P> {
+        AffinePointCols {
+            x: self.x3_ins.result.clone(),
+            y: self.y3_ins.result.clone(),
+        }
+    }
+}
+
+impl<F: PrimeField32, E: EllipticCurve + WeierstrassParameters> MachineAir<F>
+    for WeierstrassMulAssignChip<E>
+{
+    type Record = ExecutionRecord;
+    type Program = Program;
+
+    fn name(&self) -> String {
+        match E::CURVE_TYPE {
+            CurveType::Secp256k1 => "Secp256k1MulAssign".to_string(),
+            CurveType::Bn254 => "Bn254MulAssign".to_string(),
+            _ => panic!("Unsupported curve"),
+        }
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        output: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let events = match E::CURVE_TYPE {
+            CurveType::Secp256k1 => &input.secp256k1_mul_events,
+            CurveType::Bn254 => &input.bn254_mul_events,
+            _ => panic!("Unsupported curve"),
+        };
+        let num_cols = num_weierstrass_mul_cols::<E::BaseField>();
+
+        let chunk_size = std::cmp::max(events.len() / num_cpus::get(), 1);
+
+        let rows_and_records = events
+            .par_chunks(chunk_size)
+            .map(|events| {
+                let mut record = ExecutionRecord::default();
+                let mut new_byte_lookup_events = Vec::new();
+                let rows = events
+                    .iter()
+                    .flat_map(|event| self.event_to_rows(event, &mut new_byte_lookup_events))
+                    .collect::<Vec<_>>();
+                record.add_byte_lookup_events(new_byte_lookup_events);
+                (rows, record)
+            })
+            .collect::<Vec<_>>();
+
+        let mut rows = Vec::new();
+        for (mut chunk_rows, mut record) in rows_and_records {
+            rows.append(&mut chunk_rows);
+            output.append(&mut record);
+        }
+
+        pad_rows(&mut rows, || {
+            let mut row = vec![F::zero(); num_cols];
+            let cols: &mut WeierstrassMulAssignCols<F, E::BaseField> =
+                row.as_mut_slice().borrow_mut();
+            let zero = AffinePoint::<E>::new(BigUint::zero(), BigUint::zero());
+            let table = vec![zero.clone(); 3];
+            Self::populate_step(&mut vec![], 0, 0, cols, &table, &zero, false, 0, false);
+            row
+        });
+
+        // Convert the trace to a row major matrix.
+        let mut trace =
+            RowMajorMatrix::new(rows.into_iter().flatten().collect::<Vec<_>>(), num_cols);
+
+        // Write the nonces to the trace.
+        for i in 0..trace.height() {
+            let cols: &mut WeierstrassMulAssignCols<F, E::BaseField> =
+                trace.values[i * num_cols..(i + 1) * num_cols].borrow_mut();
+            cols.nonce = F::from_canonical_usize(i);
+        }
+
+        trace
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        match E::CURVE_TYPE {
+            CurveType::Secp256k1 => !shard.secp256k1_mul_events.is_empty(),
+            CurveType::Bn254 => !shard.bn254_mul_events.is_empty(),
+            _ => panic!("Unsupported curve"),
+        }
+    }
+}
+
+impl<F, E: EllipticCurve + WeierstrassParameters> BaseAir<F> for WeierstrassMulAssignChip<E> {
+    fn width(&self) -> usize {
+        num_weierstrass_mul_cols::<E::BaseField>()
+    }
+}
+
+impl<AB, E: EllipticCurve + WeierstrassParameters> Air<AB> for WeierstrassMulAssignChip<E>
+where
+    AB: SP1AirBuilder,
+    Limbs<AB::Var, <E::BaseField as NumLimbs>::Limbs>: Copy,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let local = main.row_slice(0);
+        let local: &WeierstrassMulAssignCols<AB::Var, E::BaseField> = (*local).borrow();
+        let next = main.row_slice(1);
+        let next: &WeierstrassMulAssignCols<AB::Var, E::BaseField> = (*next).borrow();
+
+        // Constrain the incrementing nonce.
+        builder.when_first_row().assert_zero(local.nonce);
+        builder
+            .when_transition()
+            .assert_eq(local.nonce + AB::Expr::one(), next.nonce);
+
+        eval_step_flags(
+            builder,
+            SCALAR_MUL_NUM_WINDOWS,
+            local.is_real,
+            local.is_first_step,
+            local.is_last_step,
+            local.step,
+            local.step_inverse,
+            next.is_real,
+            next.is_first_step,
+        );
+
+        // The columns which stay the same across the steps of a scalar multiplication.
+        let carried = |cols: &WeierstrassMulAssignCols<AB::Var, E::BaseField>| {
+            [
+                cols.shard,
+                cols.channel,
+                cols.clk,
+                cols.p_ptr,
+                cols.scalar_ptr,
+            ]
+            .into_iter()
+            .chain(cols.p.limbs())
+            .chain(cols.p_times_2.limbs())
+            .chain(cols.p_times_3.limbs())
+            .collect::<Vec<_>>()
+        };
+
+        // Constrain the transition to the next step of the same scalar multiplication.
+        {
+            let mut transition_builder = builder.when_transition();
+            let mut builder = transition_builder.when(local.is_real - local.is_last_step);
+            builder.assert_eq(next.step, local.step + AB::Expr::one());
+            builder.assert_all_eq(carried(next), carried(local));
+            builder.assert_all_eq(next.acc.limbs(), local.result.limbs());
+            builder.assert_eq(next.acc_is_infinity, local.result_is_infinity);
+            builder.assert_all_eq(
+                next.scalar_bits[SCALAR_MUL_WINDOW_BITS..].iter().copied(),
+                local.scalar_bits[..SCALAR_MUL_SCALAR_BITS - SCALAR_MUL_WINDOW_BITS]
+                    .iter()
+                    .copied(),
+            );
+        }
+
+        // Read the point and the scalar on the first step, where the accumulator is the point at
+        // infinity.
+        eval_scalar_bits(
+            builder,
+            local.is_first_step,
+            &local.scalar_access,
+            &local.scalar_bits,
+        );
+        // The incomplete addition formula is only sound for scalars below the group order.
+        let scalar: Limbs<AB::Var, <E::BaseField as NumLimbs>::Limbs> =
+            limbs_from_access(&local.scalar_access);
+        let group_order = E::BaseField::to_limbs_field_vec(&E::prime_group_order());
+        local.scalar_range.eval(
+            builder,
+            &scalar,
+            &limbs_from_vec::<AB::Expr, <E::BaseField as NumLimbs>::Limbs, AB::F>(group_order),
+            local.shard,
+            local.channel,
+            local.is_first_step,
+        );
+        builder
+            .when(local.is_first_step)
+            .assert_all_eq(local.p.limbs(), value_as_limbs(&local.p_access));
+        builder
+            .when(local.is_first_step)
+            .assert_all_eq(local.acc.limbs(), local.p.limbs());
+        builder
+            .when(local.is_first_step)
+            .assert_one(local.acc_is_infinity);
+        builder
+            .when(local.is_first_step)
+            .assert_all_eq(local.p_times_2.limbs(), local.first_double.result().limbs());
+        builder
+            .when(local.is_first_step)
+            .assert_all_eq(local.p_times_3.limbs(), local.add.result().limbs());
+
+        // Compute `4 * acc`.
+        local.first_double.eval::<AB, E>(
+            builder,
+            &local.acc,
+            local.shard,
+            local.channel,
+            local.is_real,
+        );
+        local.second_double.eval::<AB, E>(
+            builder,
+            &local.first_double.result(),
+            local.shard,
+            local.channel,
+            local.is_real,
+        );
+
+        // Select `q = window * P` with the two bits of the current window, or `P` if the window
+        // is zero.
+        let bit_low = local.scalar_bits[SCALAR_MUL_SCALAR_BITS - 2];
+        let bit_high = local.scalar_bits[SCALAR_MUL_SCALAR_BITS - 1];
+        builder.assert_bool(local.acc_is_infinity);
+        builder.assert_eq(
+            local.window_is_zero,
+            (AB::Expr::one() - bit_low) * (AB::Expr::one() - bit_high),
+        );
+        builder.assert_eq(
+            local.result_is_infinity,
+            local.acc_is_infinity * local.window_is_zero,
+        );
+        let q = local
+            .p
+            .limbs()
+            .into_iter()
+            .zip(local.p_times_2.limbs())
+            .zip(local.p_times_3.limbs())
+            .map(|((p, p_times_2), p_times_3)| {
+                p + (p_times_2 - p) * bit_high + (p_times_3 - p_times_2) * bit_low * bit_high
+            })
+            .collect::<Vec<_>>();
+        builder.assert_all_eq(local.q.limbs(), q);
+
+        // The sum `4 * acc + q` is used if the accumulator is not the point at infinity and the
+        // window is not zero. Otherwise, `add` computes `2P + P`.
+        let use_add = AB::Expr::one() - local.acc_is_infinity - local.window_is_zero
+            + local.result_is_infinity;
+        let select = |a: Vec<AB::Var>, b: Vec<AB::Var>| {
+            a.into_iter()
+                .zip(b)
+                .map(|(a, b)| use_add.clone() * a + (AB::Expr::one() - use_add.clone()) * b)
+                .collect::<Vec<_>>()
+        };
+        builder.assert_all_eq(
+            local.add_lhs.limbs(),
+            select(
+                local.second_double.result().limbs(),
+                local.p_times_2.limbs(),
+            ),
+        );
+        builder.assert_all_eq(
+            local.add_rhs.limbs(),
+            select(local.q.limbs(), local.p.limbs()),
+        );
+        local.add.eval(
+            builder,
+            &local.add_lhs,
+            &local.add_rhs,
+            local.shard,
+            local.channel,
+            local.is_real,
+        );
+
+        // The result is `P` if it is the point at infinity, `q` if only the accumulator is, `4 *
+        // acc` if the window is zero, and `4 * acc + q` otherwise.
+        let result = local
+            .p
+            .limbs()
+            .into_iter()
+            .zip(local.q.limbs())
+            .zip(local.second_double.result().limbs())
+            .zip(local.add.result().limbs())
+            .map(|(((p, q), acc_times_4), sum)| {
+                p * local.result_is_infinity
+                    + q * (local.acc_is_infinity - local.result_is_infinity)
+                    + acc_times_4 * (local.window_is_zero - local.result_is_infinity)
+                    + sum * use_add.clone()
+            })
+            .collect::<Vec<_>>();
+        builder.assert_all_eq(local.result.limbs(), result);
+        builder
+            .when(local.is_last_step)
+            .assert_zero(local.result_is_infinity);
+        builder
+            .when(local.is_last_step)
+            .assert_all_eq(local.result.limbs(), value_as_limbs(&local.result_access));
+
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.channel,
+            local.clk,
+            local.p_ptr,
+            &local.p_access,
+            local.is_first_step,
+        );
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.channel,
+            local.clk,
+            local.scalar_ptr,
+            &local.scalar_access,
+            local.is_first_step,
+        );
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.channel,
+            local.clk + AB::F::from_canonical_u32(1),
+            local.p_ptr,
+            &local.result_access,
+            local.is_last_step,
+        );
+
+        // Fetch the syscall id for the curve type.
+        let syscall_id_felt = match E::CURVE_TYPE {
+            CurveType::Secp256k1 => {
+                AB::F::from_canonical_u32(SyscallCode::SECP256K1_MUL.syscall_id())
+            }
+            CurveType::Bn254 => AB::F::from_canonical_u32(SyscallCode::BN254_MUL.syscall_id()),
+            _ => panic!("Unsupported curve"),
+        };
+
+        builder.receive_syscall(
+            local.shard,
+            local.channel,
+            local.clk,
+            local.nonce,
+            syscall_id_felt,
+            local.p_ptr,
+            local.scalar_ptr,
+            local.is_first_step,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::collections::BTreeMap;
+
+    use num::{BigUint, Num, One, Zero};
+
+    use crate::{
+        runtime::{ExecutionError, Instruction, Opcode, Program, Runtime, SyscallCode},
+        stark::CpuProver,
+        utils::{
+            ec::{
+                weierstrass::{bn254::Bn254, secp256k1::Secp256k1, WeierstrassParameters},
+                AffinePoint, EllipticCurve,
+            },
+            run_test, setup_logger, SP1CoreOpts,
+        },
+    };
+
+    /// A program multiplying the generator of `E` by `scalar` with the precompile of
+    /// `syscall_code`, with the point at `p_ptr`.
+    fn mul_program<E: EllipticCurve>(
+        syscall_code: SyscallCode,
+        p_ptr: u32,
+        scalar: &BigUint,
+    ) -> Program {
+        let scalar_ptr = 0x2000;
+
+        let mut scalar_words = scalar.to_u32_digits();
+        scalar_words.resize(8, 0);
+        let memory_image = E::ec_generator()
+            .to_words_le()
+            .into_iter()
+            .enumerate()
+            .map(|(i, word)| (p_ptr + 4 * i as u32, word))
+            .chain(
+                scalar_words
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, word)| (scalar_ptr + 4 * i as u32, word)),
+            )
+            .collect::<BTreeMap<_, _>>();
+        let instructions = vec![
+            Instruction::new(Opcode::ADD, 5, 0, syscall_code as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, p_ptr, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, scalar_ptr, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+        ];
+        let mut program = Program::new(instructions, 0, 0);
+        program.memory_image = memory_image;
+        program
+    }
+
+    /// Multiplies the generator of `E` by `scalar` with the precompile of `syscall_code`, checks
+    /// the product in memory, and proves the program.
+    fn test_mul<E: EllipticCurve>(syscall_code: SyscallCode, scalar: BigUint) {
+        let p_ptr = 0x1000;
+        let program = mul_program::<E>(syscall_code, p_ptr, &scalar);
+
+        let mut runtime = Runtime::new(program.clone(), SP1CoreOpts::default());
+        runtime.run().unwrap();
+        let result = (0..16)
+            .map(|i| runtime.word(p_ptr + 4 * i))
+            .collect::<Vec<_>>();
+        let expected: AffinePoint<E> = E::ec_generator().fixed_window_scalar_mul(&scalar).unwrap();
+        assert_eq!(result, expected.to_words_le());
+
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+
+    /// Checks that the execution fails with an error instead of panicking.
+    fn test_mul_rejected<E: EllipticCurve>(syscall_code: SyscallCode, scalar: BigUint) {
+        let program = mul_program::<E>(syscall_code, 0x1000, &scalar);
+        let mut runtime = Runtime::new(program, SP1CoreOpts::default());
+        assert!(matches!(
+            runtime.run(),
+            Err(ExecutionError::InvalidSyscallInput(code, _)) if code == syscall_code as u32
+        ));
+    }
+
+    #[test]
+    fn test_secp256k1_mul_simple() {
+        setup_logger();
+        // Leading and inner zero windows exercise the point at infinity and the skipped additions.
+        let scalar = BigUint::from_str_radix(
+            "00000000000000000000000000000000fffffffffffffffe0000000000000003",
+            16,
+        )
+        .unwrap();
+        test_mul::<Secp256k1>(SyscallCode::SECP256K1_MUL, scalar);
+    }
+
+    #[test]
+    fn test_bn254_mul_simple() {
+        setup_logger();
+        let scalar = Bn254::prime_group_order() - BigUint::one();
+        test_mul::<Bn254>(SyscallCode::BN254_MUL, scalar);
+    }
+
+    #[test]
+    fn test_secp256k1_mul_rejects_scalar_above_group_order() {
+        setup_logger();
+        test_mul_rejected::<Secp256k1>(SyscallCode::SECP256K1_MUL, Secp256k1::prime_group_order());
+    }
+
+    #[test]
+    fn test_bn254_mul_rejects_zero_scalar() {
+        setup_logger();
+        test_mul_rejected::<Bn254>(SyscallCode::BN254_MUL, BigUint::zero());
+    }
+}
@@ -34,7 +34,7 @@ impl Syscall for SyscallWrite {
                     .trim_start();
                 let depth = rt.cycle_tracker.len() as u32;
                 rt.cycle_tracker
-                    .insert(fn_name.to_string(), (rt.state.global_clk, depth));
+                    .push((fn_name.to_string(), rt.state.global_clk));
                 let padding = (0..depth).map(|_| "│ ").collect::<String>();
                 log::info!("{}┌╴{}", padding, fn_name);
             } else if s.contains("cycle-tracker-end:") {
@@ -44,14 +44,24 @@ impl Syscall for SyscallWrite {
                     .unwrap()
                     .trim_end()
                     .trim_start();
-                let (start, depth) = rt.cycle_tracker.remove(fn_name).unwrap_or((0, 0));
+                // End the innermost open region with this name, so recursive regions nest.
+                let Some(depth) = rt
+                    .cycle_tracker
+                    .iter()
+                    .rposition(|(name, _)| name == fn_name)
+                else {
+                    log::warn!("cycle tracker region {} ended without starting", fn_name);
+                    return None;
+                };
+                let (_, start) = rt.cycle_tracker.remove(depth);
+                let cycles = rt.state.global_clk - start;
+                if rt.print_report && !rt.unconstrained {
+                    let parent = depth.checked_sub(1).map(|i| rt.cycle_tracker[i].0.as_str());
+                    rt.report.track_cycles(fn_name, parent, cycles);
+                }
                 // Leftpad by 2 spaces for each depth.
                 let padding = (0..depth).map(|_| "│ ").collect::<String>();
-                log::info!(
-                    "{}└╴{} cycles",
-                    padding,
-                    num_to_comma_separated(rt.state.global_clk - start as u64)
-                );
+                log::info!("{}└╴{} cycles", padding, num_to_comma_separated(cycles));
             } else {
                 let flush_s = update_io_buf(ctx, fd, s);
                 if !flush_s.is_empty() {
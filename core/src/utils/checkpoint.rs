@@ -0,0 +1,238 @@
+use std::collections::BTreeSet;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::io::SP1Stdin;
+use crate::runtime::{ExecutionState, Program};
+use crate::stark::{ShardProof, StarkGenericConfig, StarkProvingKey};
+use crate::utils::SP1CoreOpts;
+
+const MANIFEST_FILE: &str = "manifest.bin";
+const STDIN_FILE: &str = "stdin.bin";
+
+/// Describes the progress of a proof whose checkpoints are persisted in a [CheckpointStore].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckpointManifest {
+    /// A hash of the program, stdin, and sharding options the checkpoints were generated with.
+    pub fingerprint: [u8; 32],
+
+    /// The number of execution checkpoints written to disk.
+    pub num_checkpoints: usize,
+
+    /// Whether execution reached the end of the program.
+    pub execution_done: bool,
+
+    /// The public values stream of the program, set once execution is done.
+    pub public_values_stream: Vec<u8>,
+
+    /// The checkpoints whose shard proofs have been written to disk.
+    pub proven_checkpoints: BTreeSet<usize>,
+}
+
+/// A directory holding execution checkpoints and finished shard proofs, so that an interrupted
+/// core proof can be resumed from the last completed checkpoint.
+///
+/// The directory contains a manifest, the stdin of the program, one `checkpoint_{i}.bin` file per
+/// execution checkpoint, and one `shard_proofs_{i}.bin` file per proven checkpoint.
+#[derive(Debug)]
+pub struct CheckpointStore {
+    dir: PathBuf,
+    manifest: CheckpointManifest,
+}
+
+impl CheckpointStore {
+    /// Opens the checkpoint directory for the given program, stdin and proving key, creating it if
+    /// needed.
+    ///
+    /// Returns an error if the directory holds checkpoints of a different program, stdin, proving
+    /// key, or sharding configuration.
+    pub fn open<SC: StarkGenericConfig>(
+        dir: &Path,
+        program: &Program,
+        stdin: &SP1Stdin,
+        opts: &SP1CoreOpts,
+        pk: &StarkProvingKey<SC>,
+    ) -> io::Result<Self> {
+        let fingerprint = Self::fingerprint(program, stdin, opts, pk);
+        let manifest_path = dir.join(MANIFEST_FILE);
+        if manifest_path.exists() {
+            let manifest: CheckpointManifest = read_bincode(&manifest_path)?;
+            if manifest.fingerprint != fingerprint {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "checkpoint directory {} belongs to a different program, stdin, \
+                         proving key, or sharding configuration",
+                        dir.display()
+                    ),
+                ));
+            }
+            return Ok(Self {
+                dir: dir.to_path_buf(),
+                manifest,
+            });
+        }
+
+        fs::create_dir_all(dir)?;
+        write_bincode(&dir.join(STDIN_FILE), stdin)?;
+        let store = Self {
+            dir: dir.to_path_buf(),
+            manifest: CheckpointManifest {
+                fingerprint,
+                ..Default::default()
+            },
+        };
+        store.save_manifest()?;
+        Ok(store)
+    }
+
+    /// Reads the stdin that the checkpoints in `dir` were generated with.
+    pub fn read_stdin(dir: &Path) -> io::Result<SP1Stdin> {
+        read_bincode(&dir.join(STDIN_FILE))
+    }
+
+    /// The manifest describing the progress stored in this directory.
+    pub fn manifest(&self) -> &CheckpointManifest {
+        &self.manifest
+    }
+
+    /// Writes the checkpoint with the given index and returns a handle to the written file.
+    pub fn save_checkpoint(&mut self, index: usize, state: &ExecutionState) -> io::Result<File> {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(self.checkpoint_path(index))?;
+        state.save(&mut file)?;
+        file.sync_all()?;
+        if index >= self.manifest.num_checkpoints {
+            self.manifest.num_checkpoints = index + 1;
+            self.save_manifest()?;
+        }
+        Ok(file)
+    }
+
+    /// Opens the checkpoint with the given index.
+    pub fn open_checkpoint(&self, index: usize) -> io::Result<File> {
+        File::open(self.checkpoint_path(index))
+    }
+
+    /// Reads the execution state of the checkpoint with the given index.
+    pub fn load_checkpoint(&self, index: usize) -> io::Result<ExecutionState> {
+        read_bincode(&self.checkpoint_path(index))
+    }
+
+    /// Marks execution as done and records the public values stream of the program.
+    pub fn finish_execution(&mut self, public_values_stream: Vec<u8>) -> io::Result<()> {
+        self.manifest.execution_done = true;
+        self.manifest.public_values_stream = public_values_stream;
+        self.save_manifest()
+    }
+
+    /// Writes the shard proofs generated from the checkpoint with the given index.
+    pub fn save_shard_proofs<SC: StarkGenericConfig>(
+        &mut self,
+        index: usize,
+        proofs: &[ShardProof<SC>],
+    ) -> io::Result<()> {
+        write_bincode(&self.shard_proofs_path(index), &proofs)?;
+        self.manifest.proven_checkpoints.insert(index);
+        self.save_manifest()
+    }
+
+    /// Reads the shard proofs generated from the checkpoint with the given index.
+    pub fn load_shard_proofs<SC: StarkGenericConfig>(
+        &self,
+        index: usize,
+    ) -> io::Result<Vec<ShardProof<SC>>> {
+        read_bincode(&self.shard_proofs_path(index))
+    }
+
+    fn checkpoint_path(&self, index: usize) -> PathBuf {
+        self.dir.join(format!("checkpoint_{}.bin", index))
+    }
+
+    fn shard_proofs_path(&self, index: usize) -> PathBuf {
+        self.dir.join(format!("shard_proofs_{}.bin", index))
+    }
+
+    /// Atomically replaces the manifest on disk, so that a crash never leaves it half-written.
+    fn save_manifest(&self) -> io::Result<()> {
+        let tmp_path = self.dir.join(format!("{}.tmp", MANIFEST_FILE));
+        write_bincode(&tmp_path, &self.manifest)?;
+        fs::rename(tmp_path, self.dir.join(MANIFEST_FILE))
+    }
+
+    fn fingerprint<SC: StarkGenericConfig>(
+        program: &Program,
+        stdin: &SP1Stdin,
+        opts: &SP1CoreOpts,
+        pk: &StarkProvingKey<SC>,
+    ) -> [u8; 32] {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(&bincode::serialize(program).unwrap());
+        hasher.update(&bincode::serialize(stdin).unwrap());
+        // The shard proofs are only valid for the proving key they were generated with.
+        hasher.update(
+            &bincode::serialize(&(&pk.commit, pk.pc_start, &pk.extension_chip_set)).unwrap(),
+        );
+        hasher.update(&bincode::serialize(&(opts.shard_size, opts.shard_batch_size)).unwrap());
+        hasher.update(&bincode::serialize(&opts.split_opts).unwrap());
+        hasher.finalize().into()
+    }
+}
+
+fn read_bincode<T: serde::de::DeserializeOwned>(path: &Path) -> io::Result<T> {
+    let reader = BufReader::new(File::open(path)?);
+    bincode::deserialize_from(reader).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+fn write_bincode<T: Serialize>(path: &Path, value: &T) -> io::Result<()> {
+    let file = File::create(path)?;
+    let mut writer = BufWriter::new(&file);
+    bincode::serialize_into(&mut writer, value)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    drop(writer);
+    file.sync_all()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stark::RiscvAir;
+    use crate::utils::tests::FIBONACCI_ELF;
+    use crate::utils::BabyBearPoseidon2;
+
+    #[test]
+    fn test_checkpoint_store_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let program = Program::from(FIBONACCI_ELF);
+        let mut stdin = SP1Stdin::new();
+        stdin.write(&10u32);
+        let opts = SP1CoreOpts::default();
+        let (pk, _) = RiscvAir::machine(BabyBearPoseidon2::new()).setup(&program);
+
+        let mut store = CheckpointStore::open(dir.path(), &program, &stdin, &opts, &pk).unwrap();
+        let state = ExecutionState::new(program.pc_start);
+        store.save_checkpoint(0, &state).unwrap();
+        store.finish_execution(vec![1, 2, 3]).unwrap();
+
+        let store = CheckpointStore::open(dir.path(), &program, &stdin, &opts, &pk).unwrap();
+        assert_eq!(store.manifest().num_checkpoints, 1);
+        assert!(store.manifest().execution_done);
+        assert_eq!(store.manifest().public_values_stream, vec![1, 2, 3]);
+        assert_eq!(store.load_checkpoint(0).unwrap().pc, program.pc_start);
+        assert_eq!(
+            CheckpointStore::read_stdin(dir.path()).unwrap().buffer,
+            stdin.buffer
+        );
+
+        let mut other_stdin = SP1Stdin::new();
+        other_stdin.write(&11u32);
+        assert!(CheckpointStore::open(dir.path(), &program, &other_stdin, &opts, &pk).is_err());
+    }
+}
@@ -0,0 +1,374 @@
+use std::sync::Arc;
+
+use p3_challenger::CanObserve;
+use p3_field::PrimeField32;
+use p3_maybe_rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::air::PublicValues;
+use crate::io::SP1Stdin;
+use crate::runtime::{
+    ExecutionRecord, ExecutionState, PrecompileRegistry, Program, Runtime, SP1Context,
+};
+use crate::stark::{
+    Com, MachineProver, MachineRecord, OpeningProof, PcsProverData, RiscvAir, ShardProof,
+    StarkGenericConfig, StarkProvingKey, Val,
+};
+use crate::utils::{trace_state, update_shard_state, SP1CoreOpts, SP1CoreProverError};
+
+/// The state threaded from one checkpoint to the next while splitting the execution records into
+/// shards.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShardingState {
+    /// The public values of the last shard generated before the checkpoint.
+    pub public_values: PublicValues<u32, u32>,
+
+    /// The deferred events which have not been split into shards yet.
+    ///
+    /// Before the last checkpoint, fewer events of each kind than their split threshold in
+    /// [crate::utils::SplitOpts] are left over, so this stays small however long the execution is.
+    pub deferred: ExecutionRecord,
+}
+
+impl Default for ShardingState {
+    fn default() -> Self {
+        Self {
+            public_values: PublicValues::<u32, u32>::default().reset(),
+            deferred: ExecutionRecord::default(),
+        }
+    }
+}
+
+/// A checkpoint of the execution whose shards can be proven independently of the others.
+///
+/// Jobs are emitted by [generate_checkpoint_jobs] and proven with [commit_checkpoint_job] and
+/// [prove_checkpoint_job], possibly on different machines.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckpointJob {
+    /// The position of the checkpoint in the execution.
+    pub index: usize,
+
+    /// Whether this is the last checkpoint of the execution.
+    pub done: bool,
+
+    /// The state of the runtime at the start of the checkpoint.
+    pub checkpoint: ExecutionState,
+
+    /// The sharding state left by the previous checkpoints.
+    pub state: ShardingState,
+}
+
+/// The main commitment and public values of a shard, which every shard is opened against.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(bound(serialize = "Com<SC>: Serialize, Val<SC>: Serialize"))]
+#[serde(bound(deserialize = "Com<SC>: Deserialize<'de>, Val<SC>: Deserialize<'de>"))]
+pub struct ShardCommitment<SC: StarkGenericConfig> {
+    pub main_commit: Com<SC>,
+    pub public_values: Vec<Val<SC>>,
+}
+
+/// Executes the program and emits one [CheckpointJob] per execution checkpoint, in order.
+///
+/// The sharding state of a checkpoint depends on the events of every previous checkpoint, so the
+/// events of each checkpoint are collected while executing it, to advance the state. No traces are
+/// generated and nothing is committed to. Returns the public values stream and the number of
+/// cycles of the program.
+pub fn generate_checkpoint_jobs(
+    program: Program,
+    stdin: &SP1Stdin,
+    opts: &SP1CoreOpts,
+    context: SP1Context,
+    mut emit: impl FnMut(CheckpointJob),
+) -> Result<(Vec<u8>, u64), SP1CoreProverError> {
+    // Setup the runtime.
+    let mut runtime = Runtime::with_context(program.clone(), *opts, context);
+    runtime.write_vecs(&stdin.buffer);
+    for proof in stdin.proofs.iter() {
+        runtime.write_proof(proof.0.clone(), proof.1.clone());
+    }
+
+    let mut state = ShardingState::default();
+    state.deferred.program = Arc::new(program);
+    for index in 0.. {
+        // Execute the runtime until we reach a checkpoint, keeping the state it started from.
+        let checkpoint = runtime.state.clone();
+        let (mut records, done) = runtime
+            .execute_record()
+            .map_err(SP1CoreProverError::ExecutionError)?;
+
+        // The program is known to every prover, so it is not sent with the deferred events.
+        let mut job_state = state.clone();
+        job_state.deferred.program = Arc::default();
+
+        // Advance the sharding state past the shards of this checkpoint.
+        update_shard_state(
+            &mut records,
+            &mut state.public_values,
+            &mut state.deferred,
+            done,
+            opts,
+        );
+
+        emit(CheckpointJob {
+            index,
+            done,
+            checkpoint,
+            state: job_state,
+        });
+
+        if done {
+            break;
+        }
+    }
+
+    Ok((
+        runtime.state.public_values_stream,
+        runtime.report.total_instruction_count(),
+    ))
+}
+
+/// Reconstructs the records of the shards generated from a checkpoint job.
+///
+/// The precompiles must be the ones registered in the [SP1Context] the jobs were generated with.
+pub fn checkpoint_job_records<SC, P>(
+    prover: &P,
+    program: Program,
+    job: &CheckpointJob,
+    opts: &SP1CoreOpts,
+    precompiles: &PrecompileRegistry,
+) -> Vec<ExecutionRecord>
+where
+    SC: StarkGenericConfig,
+    P: MachineProver<SC, RiscvAir<SC::Val>>,
+    SC::Val: PrimeField32,
+{
+    let (mut records, _) = trace_state(program.clone(), job.checkpoint.clone(), opts, precompiles);
+    prover.machine().generate_dependencies(&mut records, opts);
+
+    let mut state = job.state.clone();
+    state.deferred.program = Arc::new(program);
+    update_shard_state(
+        &mut records,
+        &mut state.public_values,
+        &mut state.deferred,
+        job.done,
+        opts,
+    );
+    records
+}
+
+/// Executes the program once, and emits the records of its shards in order, with their
+/// dependencies generated. Returns the public values stream and the number of cycles of the
+/// program.
+///
+/// Unlike [generate_checkpoint_jobs], the records are emitted with their dependencies, rather than
+/// as checkpoints to be traced again on another machine.
+pub fn execute_shard_records<SC, P>(
+    prover: &P,
+    program: Program,
+    stdin: &SP1Stdin,
+    opts: &SP1CoreOpts,
+    context: SP1Context,
+    mut emit: impl FnMut(Vec<ExecutionRecord>),
+) -> Result<(Vec<u8>, u64), SP1CoreProverError>
+where
+    SC: StarkGenericConfig,
+    P: MachineProver<SC, RiscvAir<SC::Val>>,
+    SC::Val: PrimeField32,
+{
+    let mut runtime = Runtime::with_context(program.clone(), *opts, context);
+    runtime.write_vecs(&stdin.buffer);
+    for proof in stdin.proofs.iter() {
+        runtime.write_proof(proof.0.clone(), proof.1.clone());
+    }
+
+    let mut state = ShardingState::default();
+    state.deferred.program = Arc::new(program);
+    loop {
+        let (mut records, done) = runtime
+            .execute_record()
+            .map_err(SP1CoreProverError::ExecutionError)?;
+        prover.machine().generate_dependencies(&mut records, opts);
+        update_shard_state(
+            &mut records,
+            &mut state.public_values,
+            &mut state.deferred,
+            done,
+            opts,
+        );
+        emit(records);
+
+        if done {
+            break;
+        }
+    }
+
+    Ok((
+        runtime.state.public_values_stream,
+        runtime.report.total_instruction_count(),
+    ))
+}
+
+/// Commits to the shards of a checkpoint job, with the precompiles registered in the
+/// [SP1Context] the jobs were generated with.
+///
+/// The commitments of every job, in order, are needed to build the challenger with
+/// [core_challenger] before any shard can be opened.
+pub fn commit_checkpoint_job<SC, P>(
+    prover: &P,
+    program: Program,
+    job: &CheckpointJob,
+    opts: &SP1CoreOpts,
+    precompiles: &PrecompileRegistry,
+) -> Vec<ShardCommitment<SC>>
+where
+    SC: StarkGenericConfig,
+    P: MachineProver<SC, RiscvAir<SC::Val>>,
+    SC::Val: PrimeField32,
+    Com<SC>: Send + Sync,
+    PcsProverData<SC>: Send + Sync,
+{
+    let records = checkpoint_job_records(prover, program, job, opts, precompiles);
+    records
+        .into_par_iter()
+        .map(|record| {
+            let public_values =
+                record.public_values::<SC::Val>()[0..prover.machine().num_pv_elts()].to_vec();
+            let traces = prover.generate_traces(&record);
+            let data = prover.commit(record, traces);
+            ShardCommitment {
+                main_commit: data.main_commit.clone(),
+                public_values,
+            }
+        })
+        .collect()
+}
+
+/// Creates the challenger that every shard is opened with, from the commitments of all the
+/// checkpoint jobs of the execution in order.
+pub fn core_challenger<SC, P>(
+    prover: &P,
+    pk: &StarkProvingKey<SC>,
+    commitments: &[ShardCommitment<SC>],
+) -> SC::Challenger
+where
+    SC: StarkGenericConfig,
+    P: MachineProver<SC, RiscvAir<SC::Val>>,
+    SC::Val: PrimeField32,
+{
+    let mut challenger = prover.config().challenger();
+    challenger.observe(pk.commit.clone());
+    challenger.observe(pk.pc_start);
+    for commitment in commitments {
+        prover.observe(
+            &mut challenger,
+            commitment.main_commit.clone(),
+            &commitment.public_values,
+        );
+    }
+    challenger
+}
+
+/// Proves the shards of a checkpoint job using the challenger built by [core_challenger], with the
+/// precompiles registered in the [SP1Context] the jobs were generated with.
+pub fn prove_checkpoint_job<SC, P>(
+    prover: &P,
+    pk: &StarkProvingKey<SC>,
+    program: Program,
+    job: &CheckpointJob,
+    challenger: &SC::Challenger,
+    opts: &SP1CoreOpts,
+    precompiles: &PrecompileRegistry,
+) -> Result<Vec<ShardProof<SC>>, SP1CoreProverError>
+where
+    SC: StarkGenericConfig,
+    P: MachineProver<SC, RiscvAir<SC::Val>>,
+    SC::Val: PrimeField32,
+    SC::Challenger: Clone + Send + Sync,
+    OpeningProof<SC>: Send,
+    Com<SC>: Send + Sync,
+    PcsProverData<SC>: Send + Sync,
+{
+    let records = checkpoint_job_records(prover, program, job, opts, precompiles);
+    records
+        .into_par_iter()
+        .map(|record| {
+            let traces = prover.generate_traces(&record);
+            let data = prover.commit(record, traces);
+            prover
+                .open(pk, data, &mut challenger.clone())
+                .map_err(|e| SP1CoreProverError::ShardProofError(e.to_string()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stark::{CpuProver, MachineProof};
+    use crate::utils::tests::FIBONACCI_ELF;
+    use crate::utils::{setup_logger, BabyBearPoseidon2};
+
+    #[test]
+    fn test_fibonacci_prove_checkpoint_jobs() {
+        setup_logger();
+        let program = Program::from(FIBONACCI_ELF);
+        let mut opts = SP1CoreOpts::default();
+        opts.shard_size = 1024;
+        opts.shard_batch_size = 2;
+
+        let machine = RiscvAir::machine(BabyBearPoseidon2::new());
+        let prover = CpuProver::new(machine);
+        let (pk, vk) = prover.setup(&program);
+        let precompiles = PrecompileRegistry::default();
+
+        // Round-trip the jobs through bincode, as a coordinator would when sending them out.
+        let mut jobs = Vec::new();
+        generate_checkpoint_jobs(
+            program.clone(),
+            &SP1Stdin::new(),
+            &opts,
+            SP1Context::default(),
+            |job| jobs.push(bincode::serialize(&job).unwrap()),
+        )
+        .unwrap();
+        let jobs = jobs
+            .iter()
+            .map(|job| bincode::deserialize::<CheckpointJob>(job).unwrap())
+            .collect::<Vec<_>>();
+        assert!(jobs.len() > 1);
+        assert!(jobs.last().unwrap().done);
+
+        let commitments = jobs
+            .iter()
+            .flat_map(|job| {
+                commit_checkpoint_job(&prover, program.clone(), job, &opts, &precompiles)
+            })
+            .collect::<Vec<_>>();
+        let challenger = core_challenger(&prover, &pk, &commitments);
+        let shard_proofs = jobs
+            .iter()
+            .map(|job| {
+                prove_checkpoint_job(
+                    &prover,
+                    &pk,
+                    program.clone(),
+                    job,
+                    &challenger,
+                    &opts,
+                    &precompiles,
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap()
+            .concat();
+        assert_eq!(shard_proofs.len(), commitments.len());
+
+        let proof = MachineProof { shard_proofs };
+        let mut challenger = prover.config().challenger();
+        prover
+            .machine()
+            .verify(&vk, &proof, &mut challenger)
+            .unwrap();
+    }
+}
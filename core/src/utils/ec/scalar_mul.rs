@@ -6,6 +6,37 @@ use super::utils::biguint_to_bits_le;
 use super::AffinePoint;
 use super::EllipticCurve;
 
+/// The number of scalar bits consumed by each step of [AffinePoint::fixed_window_scalar_mul].
+pub const SCALAR_MUL_WINDOW_BITS: usize = 2;
+
+/// The maximum number of bits of a scalar in [AffinePoint::fixed_window_scalar_mul].
+pub const SCALAR_MUL_SCALAR_BITS: usize = 256;
+
+/// The number of 32-bit words of a scalar in [AffinePoint::fixed_window_scalar_mul].
+pub const SCALAR_MUL_SCALAR_WORDS: usize = SCALAR_MUL_SCALAR_BITS / 32;
+
+/// The number of windows of a scalar in [AffinePoint::fixed_window_scalar_mul].
+pub const SCALAR_MUL_NUM_WINDOWS: usize = SCALAR_MUL_SCALAR_BITS / SCALAR_MUL_WINDOW_BITS;
+
+/// Splits a scalar into windows of [SCALAR_MUL_WINDOW_BITS] bits, starting from the most
+/// significant one.
+pub fn scalar_windows_be(scalar: &BigUint) -> Vec<usize> {
+    assert!(
+        scalar.bits() <= SCALAR_MUL_SCALAR_BITS as u64,
+        "scalar does not fit in {SCALAR_MUL_SCALAR_BITS} bits"
+    );
+    biguint_to_bits_le(scalar, SCALAR_MUL_SCALAR_BITS)
+        .chunks(SCALAR_MUL_WINDOW_BITS)
+        .rev()
+        .map(|window| {
+            window
+                .iter()
+                .rev()
+                .fold(0, |acc, &bit| (acc << 1) | bit as usize)
+        })
+        .collect()
+}
+
 impl<E: EllipticCurve> AffinePoint<E> {
     pub fn scalar_mul(&self, scalar: &BigUint) -> Self {
         let power_two_modulus = BigUint::one() << E::nb_scalar_bits();
@@ -21,6 +52,37 @@ impl<E: EllipticCurve> AffinePoint<E> {
         }
         result.expect("Scalar multiplication failed")
     }
+
+    /// Returns the multiples `[P, 2P, ..., (2^w - 1)P]` of the point, where `w` is
+    /// [SCALAR_MUL_WINDOW_BITS].
+    pub fn window_table(&self) -> Vec<Self> {
+        let mut table = vec![self.clone(), E::ec_double(self)];
+        while table.len() < (1 << SCALAR_MUL_WINDOW_BITS) - 1 {
+            let next = table.last().unwrap() + self;
+            table.push(next);
+        }
+        table
+    }
+
+    /// Multiplies the point by a scalar of at most [SCALAR_MUL_SCALAR_BITS] bits with a fixed
+    /// window.
+    ///
+    /// The windows are processed from the most significant one: each step doubles the accumulator
+    /// [SCALAR_MUL_WINDOW_BITS] times and adds the multiple of the point selected by the window.
+    /// Returns `None` if the result is the point at infinity of a curve without an affine neutral
+    /// element.
+    pub fn fixed_window_scalar_mul(&self, scalar: &BigUint) -> Option<Self> {
+        let table = self.window_table();
+        let mut result: Option<Self> = None;
+        for window in scalar_windows_be(scalar) {
+            result = result.map(|r| (0..SCALAR_MUL_WINDOW_BITS).fold(r, |r, _| E::ec_double(&r)));
+            if window != 0 {
+                let q = &table[window - 1];
+                result = Some(result.map_or_else(|| q.clone(), |r| &r + q));
+            }
+        }
+        result.or_else(E::ec_neutral)
+    }
 }
 
 impl<E: EllipticCurve> Mul<&BigUint> for &AffinePoint<E> {
@@ -46,3 +108,45 @@ impl<E: EllipticCurve> Mul<BigUint> for AffinePoint<E> {
         self.scalar_mul(&scalar)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use num::bigint::RandBigInt;
+    use num::BigUint;
+    use rand::thread_rng;
+
+    use super::{scalar_windows_be, SCALAR_MUL_NUM_WINDOWS, SCALAR_MUL_SCALAR_BITS};
+    use crate::utils::ec::edwards::ed25519::Ed25519;
+    use crate::utils::ec::weierstrass::secp256k1::Secp256k1;
+    use crate::utils::ec::EllipticCurve;
+
+    #[test]
+    fn test_scalar_windows_be() {
+        let windows = scalar_windows_be(&BigUint::from(0b10_01_11u32));
+        assert_eq!(windows.len(), SCALAR_MUL_NUM_WINDOWS);
+        assert_eq!(windows[SCALAR_MUL_NUM_WINDOWS - 3..], [2, 1, 3]);
+        assert!(windows[..SCALAR_MUL_NUM_WINDOWS - 3]
+            .iter()
+            .all(|&w| w == 0));
+    }
+
+    #[test]
+    fn test_fixed_window_scalar_mul() {
+        let mut rng = thread_rng();
+        for _ in 0..10 {
+            let scalar = rng.gen_biguint(SCALAR_MUL_SCALAR_BITS as u64 - 4);
+
+            let ed_base = Ed25519::ec_generator();
+            assert_eq!(
+                ed_base.fixed_window_scalar_mul(&scalar),
+                Some(ed_base.scalar_mul(&scalar))
+            );
+
+            let sw_base = Secp256k1::ec_generator();
+            assert_eq!(
+                sw_base.fixed_window_scalar_mul(&scalar),
+                Some(sw_base.sw_scalar_mul(&scalar))
+            );
+        }
+    }
+}
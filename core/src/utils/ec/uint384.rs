@@ -0,0 +1,33 @@
+use typenum::{U48, U95};
+
+use num::{BigUint, One};
+use serde::{Deserialize, Serialize};
+
+use crate::operations::field::params::{FieldParameters, NumLimbs};
+
+/// As with [super::uint256::U256Field], `U384` is not a field. These parameters only provide the
+/// limb layout and the default modulus 2^384 to the `FieldOps` operations.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct U384Field;
+
+impl FieldParameters for U384Field {
+    /// The modulus of the field. It is represented as a little-endian array of 49 bytes.
+    const MODULUS: &'static [u8] = &[
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1,
+    ];
+
+    /// A rough witness-offset estimate given the size of the limbs and the size of the field.
+    const WITNESS_OFFSET: usize = 1usize << 15;
+
+    /// The modulus of Uint384 is 2^384.
+    fn modulus() -> BigUint {
+        BigUint::one() << 384
+    }
+}
+
+impl NumLimbs for U384Field {
+    type Limbs = U48;
+    // As with `U256Field`, we use one more witness limb than usual to allow a modulus of 2^384.
+    type Witness = U95;
+}
@@ -0,0 +1,34 @@
+use typenum::{U127, U64};
+
+use num::{BigUint, One};
+use serde::{Deserialize, Serialize};
+
+use crate::operations::field::params::{FieldParameters, NumLimbs};
+
+/// As with [super::uint256::U256Field], `U512` is not a field. These parameters only provide the
+/// limb layout and the default modulus 2^512 to the `FieldOps` operations.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct U512Field;
+
+impl FieldParameters for U512Field {
+    /// The modulus of the field. It is represented as a little-endian array of 65 bytes.
+    const MODULUS: &'static [u8] = &[
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0, 1,
+    ];
+
+    /// A rough witness-offset estimate given the size of the limbs and the size of the field.
+    const WITNESS_OFFSET: usize = 1usize << 15;
+
+    /// The modulus of Uint512 is 2^512.
+    fn modulus() -> BigUint {
+        BigUint::one() << 512
+    }
+}
+
+impl NumLimbs for U512Field {
+    type Limbs = U64;
+    // As with `U256Field`, we use one more witness limb than usual to allow a modulus of 2^512.
+    type Witness = U127;
+}
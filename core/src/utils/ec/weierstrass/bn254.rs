@@ -85,11 +85,23 @@ impl WeierstrassParameters for Bn254Parameters {
     }
 }
 
+/// Computes a square root of `n` in the base field.
+///
+/// Since `p = 3 mod 4`, a square root is given by `n^((p + 1) / 4)`. If `n` is not a quadratic
+/// residue, the result is not a square root of `n`.
+pub fn bn254_sqrt(n: &BigUint) -> BigUint {
+    let modulus = Bn254BaseField::modulus();
+    let exponent = (&modulus + 1u32) >> 2;
+    n.modpow(&exponent, &modulus)
+}
+
 #[cfg(test)]
 mod tests {
 
     use super::*;
     use crate::utils::ec::utils::biguint_from_limbs;
+    use num::bigint::RandBigInt;
+    use rand::thread_rng;
 
     #[test]
     fn test_weierstrass_biguint_scalar_mul() {
@@ -98,4 +110,20 @@ mod tests {
             Bn254BaseField::modulus()
         );
     }
+
+    #[test]
+    fn test_bn254_sqrt() {
+        let mut rng = thread_rng();
+        for _ in 0..10 {
+            // Check that sqrt(x^2)^2 == x^2
+            // We use x^2 since not all field elements have a square root
+            let x = rng.gen_biguint(256) % Bn254BaseField::modulus();
+            let x_2 = (&x * &x) % Bn254BaseField::modulus();
+            let sqrt = bn254_sqrt(&x_2);
+
+            let sqrt_2 = (&sqrt * &sqrt) % Bn254BaseField::modulus();
+
+            assert_eq!(sqrt_2, x_2);
+        }
+    }
 }
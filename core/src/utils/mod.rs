@@ -1,21 +1,37 @@
 mod buffer;
+#[cfg(feature = "prover")]
+mod checkpoint;
 pub mod concurrency;
 mod config;
+#[cfg(feature = "prover")]
+mod distributed;
 pub mod ec;
+#[cfg(feature = "prover")]
 mod logger;
 mod options;
 #[cfg(any(test, feature = "programs"))]
 mod programs;
+#[cfg(feature = "prover")]
 mod prove;
 mod serde;
+mod stark_configs;
+#[cfg(feature = "prover")]
 mod tracer;
 
 pub use buffer::*;
+#[cfg(feature = "prover")]
+pub use checkpoint::*;
 pub use config::*;
+#[cfg(feature = "prover")]
+pub use distributed::*;
+#[cfg(feature = "prover")]
 pub use logger::*;
 pub use options::*;
+#[cfg(feature = "prover")]
 pub use prove::*;
 pub use serde::*;
+pub use stark_configs::*;
+#[cfg(feature = "prover")]
 pub use tracer::*;
 
 #[cfg(any(test, feature = "programs"))]
@@ -23,7 +39,7 @@ pub use programs::*;
 
 use crate::{memory::MemoryCols, operations::field::params::Limbs};
 use generic_array::ArrayLength;
-use p3_maybe_rayon::prelude::{ParallelBridge, ParallelIterator};
+use p3_maybe_rayon::prelude::*;
 
 pub const fn indices_arr<const N: usize>() -> [usize; N] {
     let mut indices_arr = [0; N];
@@ -207,9 +223,8 @@ where
     let ceil_div = (len + cpus - 1) / cpus;
     let chunk_size = std::cmp::max(ceil_div, cpus);
 
-    vec.chunks_mut(chunk_size * num_elements_per_event)
+    vec.par_chunks_mut(chunk_size * num_elements_per_event)
         .enumerate()
-        .par_bridge()
         .for_each(|(i, chunk)| {
             chunk
                 .chunks_mut(num_elements_per_event)
@@ -9,12 +9,14 @@ const DEFAULT_SHARD_BATCH_SIZE: usize = 16;
 const DEFAULT_TRACE_GEN_WORKERS: usize = 1;
 const DEFAULT_CHECKPOINTS_CHANNEL_CAPACITY: usize = 128;
 const DEFAULT_RECORDS_AND_TRACES_CHANNEL_CAPACITY: usize = 1;
+const DEFAULT_REDUCE_BATCH_SIZE: usize = 2;
 
 /// Options to configure the SP1 prover for core and recursive proofs.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SP1ProverOpts {
     pub core_opts: SP1CoreOpts,
     pub recursion_opts: SP1CoreOpts,
+    reduce_batch_size: usize,
 }
 
 impl Default for SP1ProverOpts {
@@ -22,10 +24,29 @@ impl Default for SP1ProverOpts {
         Self {
             core_opts: SP1CoreOpts::default(),
             recursion_opts: SP1CoreOpts::recursion(),
+            reduce_batch_size: env::var("REDUCE_BATCH_SIZE").map_or_else(
+                |_| DEFAULT_REDUCE_BATCH_SIZE,
+                |s| s.parse::<usize>().unwrap_or(DEFAULT_REDUCE_BATCH_SIZE),
+            ),
         }
     }
 }
 
+impl SP1ProverOpts {
+    /// The arity of the reduction tree, i.e. the number of proofs verified by each recursive
+    /// proof.
+    pub fn reduce_batch_size(&self) -> usize {
+        self.reduce_batch_size
+    }
+
+    /// Sets the arity of the reduction tree. Larger values mean fewer layers but more work per
+    /// proof. Must be at least 2.
+    pub fn with_reduce_batch_size(mut self, reduce_batch_size: usize) -> Self {
+        self.reduce_batch_size = reduce_batch_size;
+        self
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SP1CoreOpts {
     pub shard_size: usize,
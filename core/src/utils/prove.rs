@@ -2,7 +2,7 @@ use std::collections::VecDeque;
 use std::fs::File;
 use std::io::Seek;
 use std::io::{self};
-use std::sync::mpsc::sync_channel;
+use std::sync::mpsc::{sync_channel, SyncSender};
 use std::sync::Arc;
 use std::sync::Mutex;
 use web_time::Instant;
@@ -15,19 +15,25 @@ use size::Size;
 use std::thread::ScopedJoinHandle;
 use thiserror::Error;
 
-pub use baby_bear_blake3::BabyBearBlake3;
+use p3_air::Air;
 use p3_baby_bear::BabyBear;
 use p3_field::PrimeField32;
+use p3_matrix::dense::RowMajorMatrix;
+use p3_uni_stark::Proof;
 
 use crate::air::{MachineAir, PublicValues};
 use crate::io::{SP1PublicValues, SP1Stdin};
 use crate::lookup::InteractionBuilder;
-use crate::runtime::{ExecutionError, NoOpSubproofVerifier, SP1Context};
+use crate::runtime::{
+    ExecutionError, ExecutionState, NoOpSubproofVerifier, PrecompileRegistry, ProgressEvent,
+    SP1Context,
+};
 use crate::runtime::{ExecutionRecord, ExecutionReport};
 use crate::stark::DebugConstraintBuilder;
 use crate::stark::MachineProof;
 use crate::stark::MachineProver;
 use crate::stark::ProverConstraintFolder;
+use crate::stark::ShardProof;
 use crate::stark::StarkVerifyingKey;
 use crate::stark::Val;
 use crate::stark::VerifierConstraintFolder;
@@ -35,15 +41,13 @@ use crate::stark::{Com, PcsProverData, RiscvAir, StarkProvingKey, UniConfig};
 use crate::stark::{MachineRecord, StarkMachine};
 use crate::utils::chunk_vec;
 use crate::utils::concurrency::TurnBasedSync;
-use crate::utils::SP1CoreOpts;
+use crate::utils::{BabyBearPoseidon2, CheckpointStore, SP1CoreOpts};
 use crate::{
     runtime::{Program, Runtime},
     stark::StarkGenericConfig,
     stark::{CpuProver, OpeningProof},
 };
 
-const LOG_DEGREE_BOUND: usize = 31;
-
 #[derive(Error, Debug)]
 pub enum SP1CoreProverError {
     #[error("failed to execute program: {0}")]
@@ -52,6 +56,27 @@ pub enum SP1CoreProverError {
     IoError(io::Error),
     #[error("serialization error: {0}")]
     SerializationError(bincode::Error),
+    #[error("no checkpoint directory is configured")]
+    MissingCheckpointDir,
+    #[error("failed to prove shard: {0}")]
+    ShardProofError(String),
+    #[error("proving was cancelled")]
+    Cancelled,
+    #[error("precompile extensions can't be proven by the recursive prover")]
+    UnsupportedPrecompiles,
+}
+
+/// A batch of shards sent from the trace generation workers to a prover thread.
+enum ShardBatch<SC: StarkGenericConfig> {
+    /// Records and traces of shards generated from the checkpoint with the given index.
+    Traced {
+        index: usize,
+        last: bool,
+        records: Vec<ExecutionRecord>,
+        traces: Vec<Vec<(String, RowMajorMatrix<Val<SC>>)>>,
+    },
+    /// The shards of a checkpoint whose proofs are stored in the checkpoint directory.
+    Restored(usize),
 }
 
 pub fn prove_simple<SC: StarkGenericConfig, P: MachineProver<SC, RiscvAir<SC::Val>>>(
@@ -122,10 +147,14 @@ where
     let machine = RiscvAir::machine(config);
     let prover = P::new(machine);
     let (pk, _) = prover.setup(&program);
-    prove_with_context::<SC, _>(&prover, &pk, program, stdin, opts, Default::default())
+    prove_with_context::<SC, _, _>(&prover, &pk, program, stdin, opts, Default::default())
 }
 
-pub fn prove_with_context<SC: StarkGenericConfig, P: MachineProver<SC, RiscvAir<SC::Val>>>(
+/// Proves the execution of the program with the given context.
+///
+/// The machine of the prover may extend [RiscvAir] with the chips of the precompiles registered in
+/// the context, see [ExtendedRiscvAir](crate::stark::ExtendedRiscvAir).
+pub fn prove_with_context<SC, A, P>(
     prover: &P,
     pk: &StarkProvingKey<SC>,
     program: Program,
@@ -134,19 +163,49 @@ pub fn prove_with_context<SC: StarkGenericConfig, P: MachineProver<SC, RiscvAir<
     context: SP1Context,
 ) -> Result<(MachineProof<SC>, Vec<u8>, u64), SP1CoreProverError>
 where
+    SC: StarkGenericConfig,
+    A: MachineAir<SC::Val, Record = ExecutionRecord, Program = Program>,
+    P: MachineProver<SC, A>,
     SC::Val: PrimeField32,
     SC::Challenger: 'static + Clone + Send,
     OpeningProof<SC>: Send,
     Com<SC>: Send + Sync,
     PcsProverData<SC>: Send + Sync,
 {
+    // Keep the progress callback and cancellation token for the prover threads, and the
+    // registered precompiles for re-executing the checkpoints.
+    let control = SP1Context {
+        progress: context.progress.clone(),
+        cancellation_token: context.cancellation_token.clone(),
+        ..Default::default()
+    };
+    let control = &control;
+    let precompiles = context.precompiles.clone().unwrap_or_default();
+    let precompiles = &precompiles;
+
     // Setup the runtime.
+    let checkpoint_dir = context.checkpoint_dir.clone();
     let mut runtime = Runtime::with_context(program.clone(), opts, context);
     runtime.write_vecs(&stdin.buffer);
     for proof in stdin.proofs.iter() {
         runtime.write_proof(proof.0.clone(), proof.1.clone());
     }
 
+    // Open the checkpoint directory, if any, and find the checkpoints proven by a previous run.
+    let store = checkpoint_dir
+        .as_ref()
+        .map(|dir| CheckpointStore::open(dir, &program, stdin, &opts, pk))
+        .transpose()
+        .map_err(SP1CoreProverError::IoError)?;
+    let proven = store
+        .as_ref()
+        .map(|store| store.manifest().proven_checkpoints.clone())
+        .unwrap_or_default();
+    let store = store.map(Mutex::new);
+    let store = store.as_ref();
+    let proven = &proven;
+    let opts = &opts;
+
     // Record the start of the process.
     let proving_start = Instant::now();
     let span = tracing::Span::current().clone();
@@ -162,28 +221,79 @@ where
                 let _span = checkpoint_generator_span.enter();
                 tracing::debug_span!("checkpoint generator").in_scope(|| {
                     let mut index = 0;
+
+                    // Replay the checkpoints saved by a previous run. If execution did not finish,
+                    // continue executing from the last saved checkpoint.
+                    if let Some(store) = store {
+                        let manifest = store.lock().unwrap().manifest().clone();
+                        let num_replayed = if manifest.execution_done {
+                            manifest.num_checkpoints
+                        } else {
+                            manifest.num_checkpoints.saturating_sub(1)
+                        };
+                        for i in 0..num_replayed {
+                            let checkpoint_file = store
+                                .lock()
+                                .unwrap()
+                                .open_checkpoint(i)
+                                .map_err(SP1CoreProverError::IoError)?;
+                            let done = manifest.execution_done && i + 1 == num_replayed;
+                            checkpoints_tx.send((i, checkpoint_file, done)).unwrap();
+                        }
+                        if manifest.execution_done {
+                            return Ok(manifest.public_values_stream);
+                        }
+                        if manifest.num_checkpoints > 0 {
+                            index = num_replayed;
+                            runtime.state = store
+                                .lock()
+                                .unwrap()
+                                .load_checkpoint(index)
+                                .map_err(SP1CoreProverError::IoError)?;
+                        }
+                    }
+
                     loop {
                         // Enter the span.
                         let span = tracing::debug_span!("batch");
                         let _span = span.enter();
 
+                        // Stop executing once proving is cancelled. Dropping the sender lets the
+                        // workers drain the checkpoints already sent and exit.
+                        if control.is_cancelled() {
+                            return Err(SP1CoreProverError::Cancelled);
+                        }
+
                         // Execute the runtime until we reach a checkpoint.
                         let (checkpoint, done) = runtime
                             .execute_state()
                             .map_err(SP1CoreProverError::ExecutionError)?;
-
-                        // Save the checkpoint to a temp file.
-                        let mut checkpoint_file =
-                            tempfile::tempfile().map_err(SP1CoreProverError::IoError)?;
-                        checkpoint
-                            .save(&mut checkpoint_file)
-                            .map_err(SP1CoreProverError::IoError)?;
+                        control.report(ProgressEvent::Executed {
+                            cycles: runtime.state.global_clk,
+                        });
+
+                        // Save the checkpoint to the checkpoint directory or to a temp file.
+                        let checkpoint_file = match store {
+                            Some(store) => {
+                                store.lock().unwrap().save_checkpoint(index, &checkpoint)
+                            }
+                            None => tempfile::tempfile()
+                                .and_then(|mut file| checkpoint.save(&mut file).map(|_| file)),
+                        }
+                        .map_err(SP1CoreProverError::IoError)?;
 
                         // Send the checkpoint.
                         checkpoints_tx.send((index, checkpoint_file, done)).unwrap();
 
                         // If we've reached the final checkpoint, break out of the loop.
                         if done {
+                            if let Some(store) = store {
+                                store
+                                    .lock()
+                                    .unwrap()
+                                    .finish_execution(runtime.state.public_values_stream.clone())
+                                    .map_err(SP1CoreProverError::IoError)?;
+                            }
                             break Ok(runtime.state.public_values_stream);
                         }
 
@@ -197,10 +307,7 @@ where
         let p1_record_gen_sync = Arc::new(TurnBasedSync::new());
         let p1_trace_gen_sync = Arc::new(TurnBasedSync::new());
         let (p1_records_and_traces_tx, p1_records_and_traces_rx) =
-            sync_channel::<(
-                Vec<ExecutionRecord>,
-                Vec<Vec<(String, RowMajorMatrix<Val<SC>>)>>,
-            )>(opts.records_and_traces_channel_capacity);
+            sync_channel::<ShardBatch<SC>>(opts.records_and_traces_channel_capacity);
         let p1_records_and_traces_tx = Arc::new(Mutex::new(p1_records_and_traces_tx));
         let checkpoints_rx = Arc::new(Mutex::new(checkpoints_rx));
 
@@ -227,62 +334,46 @@ where
                         // Receive the latest checkpoint.
                         let received = { checkpoints_rx.lock().unwrap().recv() };
                         if let Ok((index, mut checkpoint, done)) = received {
+                            // Once cancelled, only take our turns so the other workers can exit.
+                            if control.is_cancelled() {
+                                skip_turns(index, &[&record_gen_sync, &trace_gen_sync]);
+                                continue;
+                            }
+
                             // Trace the checkpoint and reconstruct the execution records.
                             let (mut records, _) = tracing::debug_span!("trace checkpoint")
-                                .in_scope(|| trace_checkpoint(program.clone(), &checkpoint, opts));
+                                .in_scope(|| {
+                                    trace_checkpoint(
+                                        program.clone(),
+                                        &checkpoint,
+                                        opts,
+                                        precompiles,
+                                    )
+                                });
                             reset_seek(&mut checkpoint);
 
                             // Generate the dependencies.
                             tracing::debug_span!("generate dependencies").in_scope(|| {
-                                prover.machine().generate_dependencies(&mut records, &opts)
+                                prover.machine().generate_dependencies(&mut records, opts)
                             });
 
                             // Wait for our turn to update the state.
                             record_gen_sync.wait_for_turn(index);
 
-                            // Update the public values & prover state for the shards which contain
-                            // "cpu events".
-                            let mut state = state.lock().unwrap();
-                            for record in records.iter_mut() {
-                                state.shard += 1;
-                                state.execution_shard = record.public_values.execution_shard;
-                                state.start_pc = record.public_values.start_pc;
-                                state.next_pc = record.public_values.next_pc;
-                                state.committed_value_digest =
-                                    record.public_values.committed_value_digest;
-                                state.deferred_proofs_digest =
-                                    record.public_values.deferred_proofs_digest;
-                                record.public_values = *state;
-                            }
-
-                            // Defer events that are too expensive to include in every shard.
-                            let mut deferred = deferred.lock().unwrap();
-                            for record in records.iter_mut() {
-                                deferred.append(&mut record.defer());
-                            }
-
-                            // See if any deferred shards are ready to be commited to.
-                            let mut deferred = deferred.split(done, opts.split_opts);
-
-                            // Update the public values & prover state for the shards which do not
-                            // contain "cpu events" before committing to them.
-                            if !done {
-                                state.execution_shard += 1;
-                            }
-                            for record in deferred.iter_mut() {
-                                state.shard += 1;
-                                state.previous_init_addr_bits =
-                                    record.public_values.previous_init_addr_bits;
-                                state.last_init_addr_bits =
-                                    record.public_values.last_init_addr_bits;
-                                state.previous_finalize_addr_bits =
-                                    record.public_values.previous_finalize_addr_bits;
-                                state.last_finalize_addr_bits =
-                                    record.public_values.last_finalize_addr_bits;
-                                state.start_pc = state.next_pc;
-                                record.public_values = *state;
-                            }
-                            records.append(&mut deferred);
+                            // Update the public values & prover state and split off the shards
+                            // of deferred events.
+                            let shards = {
+                                let mut state = state.lock().unwrap();
+                                update_shard_state(
+                                    &mut records,
+                                    &mut state,
+                                    &mut deferred.lock().unwrap(),
+                                    done,
+                                    opts,
+                                );
+                                state.shard as usize
+                            };
+                            control.report(ProgressEvent::ShardsExecuted { shards });
 
                             // Collect the checkpoints to be used again in the phase 2 prover.
                             let mut checkpoints = checkpoints.lock().unwrap();
@@ -291,6 +382,19 @@ where
                             // Let another worker update the state.
                             record_gen_sync.advance_turn();
 
+                            // The shards of a checkpoint proven by a previous run are restored
+                            // from the checkpoint directory instead of being traced again.
+                            if proven.contains(&index) {
+                                trace_gen_sync.wait_for_turn(index);
+                                records_and_traces_tx
+                                    .lock()
+                                    .unwrap()
+                                    .send(ShardBatch::Restored(index))
+                                    .unwrap();
+                                trace_gen_sync.advance_turn();
+                                continue;
+                            }
+
                             // Generate the traces.
                             let traces = records
                                 .par_iter()
@@ -301,16 +405,12 @@ where
                             trace_gen_sync.wait_for_turn(index);
 
                             // Send the records to the phase 1 prover.
-                            let chunked_records = chunk_vec(records, opts.shard_batch_size);
-                            let chunked_traces = chunk_vec(traces, opts.shard_batch_size);
-                            chunked_records.into_iter().zip(chunked_traces).for_each(
-                                |(records, traces)| {
-                                    records_and_traces_tx
-                                        .lock()
-                                        .unwrap()
-                                        .send((records, traces))
-                                        .unwrap();
-                                },
+                            send_shard_batches(
+                                &records_and_traces_tx,
+                                index,
+                                records,
+                                traces,
+                                opts.shard_batch_size,
                             );
 
                             trace_gen_sync.advance_turn();
@@ -333,8 +433,37 @@ where
         let phase_1_prover_span = tracing::Span::current().clone();
         let phase_1_prover_handle = s.spawn(move || {
             let _span = phase_1_prover_span.enter();
+            let mut error = None;
             tracing::debug_span!("phase 1 prover").in_scope(|| {
-                for (records, traces) in p1_records_and_traces_rx.iter() {
+                for batch in p1_records_and_traces_rx.iter() {
+                    // Keep draining the batches after an error, so that the workers can exit.
+                    if control.is_cancelled() || error.is_some() {
+                        continue;
+                    }
+                    let (records, traces) = match batch {
+                        ShardBatch::Traced {
+                            records, traces, ..
+                        } => (records, traces),
+                        ShardBatch::Restored(index) => {
+                            // Observe the commitments of the restored shard proofs.
+                            let shard_proofs = match load_shard_proofs::<SC>(store, index) {
+                                Ok(shard_proofs) => shard_proofs,
+                                Err(e) => {
+                                    error = Some(e);
+                                    continue;
+                                }
+                            };
+                            for proof in shard_proofs {
+                                prover.observe(
+                                    &mut challenger,
+                                    proof.commitment.main_commit,
+                                    &proof.public_values[0..prover.machine().num_pv_elts()],
+                                );
+                            }
+                            continue;
+                        }
+                    };
+
                     tracing::debug_span!("batch").in_scope(|| {
                         let span = tracing::Span::current().clone();
 
@@ -370,11 +499,11 @@ where
                 }
             });
 
-            challenger
+            error.map_or(Ok(challenger), Err)
         });
 
         // Wait until the checkpoint generator handle has fully finished.
-        let public_values_stream = checkpoint_generator_handle.join().unwrap().unwrap();
+        let public_values_stream = checkpoint_generator_handle.join().unwrap()?;
 
         // Wait until the records and traces have been fully generated.
         p1_record_and_trace_gen_handles
@@ -383,15 +512,16 @@ where
 
         // Wait until the phase 1 prover has completely finished.
         let challenger = phase_1_prover_handle.join().unwrap();
+        if control.is_cancelled() {
+            return Err(SP1CoreProverError::Cancelled);
+        }
+        let challenger = challenger?;
 
         // Spawn the phase 2 record generator thread.
         let p2_record_gen_sync = Arc::new(TurnBasedSync::new());
         let p2_trace_gen_sync = Arc::new(TurnBasedSync::new());
         let (p2_records_and_traces_tx, p2_records_and_traces_rx) =
-            sync_channel::<(
-                Vec<ExecutionRecord>,
-                Vec<Vec<(String, RowMajorMatrix<Val<SC>>)>>,
-            )>(opts.records_and_traces_channel_capacity);
+            sync_channel::<ShardBatch<SC>>(opts.records_and_traces_channel_capacity);
         let p2_records_and_traces_tx = Arc::new(Mutex::new(p2_records_and_traces_tx));
 
         let report_aggregate = Arc::new(Mutex::new(ExecutionReport::default()));
@@ -417,67 +547,59 @@ where
                         // Receive the latest checkpoint.
                         let received = { checkpoints.lock().unwrap().pop_front() };
                         if let Some((index, mut checkpoint, done)) = received {
+                            // Once cancelled, only take our turns so the other workers can exit.
+                            if control.is_cancelled() {
+                                skip_turns(index, &[&record_gen_sync, &trace_gen_sync]);
+                                continue;
+                            }
+
                             // Trace the checkpoint and reconstruct the execution records.
                             let (mut records, report) = tracing::debug_span!("trace checkpoint")
-                                .in_scope(|| trace_checkpoint(program.clone(), &checkpoint, opts));
+                                .in_scope(|| {
+                                    trace_checkpoint(
+                                        program.clone(),
+                                        &checkpoint,
+                                        opts,
+                                        precompiles,
+                                    )
+                                });
                             *report_aggregate.lock().unwrap() += report;
                             reset_seek(&mut checkpoint);
 
                             // Generate the dependencies.
                             tracing::debug_span!("generate dependencies").in_scope(|| {
-                                prover.machine().generate_dependencies(&mut records, &opts)
+                                prover.machine().generate_dependencies(&mut records, opts)
                             });
 
                             // Wait for our turn to update the state.
                             record_gen_sync.wait_for_turn(index);
 
-                            // Update the public values & prover state for the shards which contain
-                            // "cpu events".
-                            let mut state = state.lock().unwrap();
-                            for record in records.iter_mut() {
-                                state.shard += 1;
-                                state.execution_shard = record.public_values.execution_shard;
-                                state.start_pc = record.public_values.start_pc;
-                                state.next_pc = record.public_values.next_pc;
-                                state.committed_value_digest =
-                                    record.public_values.committed_value_digest;
-                                state.deferred_proofs_digest =
-                                    record.public_values.deferred_proofs_digest;
-                                record.public_values = *state;
-                            }
-
-                            // Defer events that are too expensive to include in every shard.
-                            let mut deferred = deferred.lock().unwrap();
-                            for record in records.iter_mut() {
-                                deferred.append(&mut record.defer());
-                            }
-
-                            // See if any deferred shards are ready to be commited to.
-                            let mut deferred = deferred.split(done, opts.split_opts);
-
-                            // Update the public values & prover state for the shards which do not
-                            // contain "cpu events" before committing to them.
-                            if !done {
-                                state.execution_shard += 1;
-                            }
-                            for record in deferred.iter_mut() {
-                                state.shard += 1;
-                                state.previous_init_addr_bits =
-                                    record.public_values.previous_init_addr_bits;
-                                state.last_init_addr_bits =
-                                    record.public_values.last_init_addr_bits;
-                                state.previous_finalize_addr_bits =
-                                    record.public_values.previous_finalize_addr_bits;
-                                state.last_finalize_addr_bits =
-                                    record.public_values.last_finalize_addr_bits;
-                                state.start_pc = state.next_pc;
-                                record.public_values = *state;
-                            }
-                            records.append(&mut deferred);
+                            // Update the public values & prover state and split off the shards
+                            // of deferred events.
+                            update_shard_state(
+                                &mut records,
+                                &mut state.lock().unwrap(),
+                                &mut deferred.lock().unwrap(),
+                                done,
+                                opts,
+                            );
 
                             // Let another worker update the state.
                             record_gen_sync.advance_turn();
 
+                            // The shards of a checkpoint proven by a previous run are restored
+                            // from the checkpoint directory instead of being proven again.
+                            if proven.contains(&index) {
+                                trace_gen_sync.wait_for_turn(index);
+                                records_and_traces_tx
+                                    .lock()
+                                    .unwrap()
+                                    .send(ShardBatch::Restored(index))
+                                    .unwrap();
+                                trace_gen_sync.advance_turn();
+                                continue;
+                            }
+
                             // Generate the traces.
                             let traces = records
                                 .par_iter()
@@ -486,17 +608,13 @@ where
 
                             trace_gen_sync.wait_for_turn(index);
 
-                            // Send the records to the phase 1 prover.
-                            let chunked_records = chunk_vec(records, opts.shard_batch_size);
-                            let chunked_traces = chunk_vec(traces, opts.shard_batch_size);
-                            chunked_records.into_iter().zip(chunked_traces).for_each(
-                                |(records, traces)| {
-                                    records_and_traces_tx
-                                        .lock()
-                                        .unwrap()
-                                        .send((records, traces))
-                                        .unwrap();
-                                },
+                            // Send the records to the phase 2 prover.
+                            send_shard_batches(
+                                &records_and_traces_tx,
+                                index,
+                                records,
+                                traces,
+                                opts.shard_batch_size,
                             );
 
                             trace_gen_sync.advance_turn();
@@ -515,23 +633,65 @@ where
         let p2_prover_handle = s.spawn(move || {
             let _span = p2_prover_span.enter();
             let mut shard_proofs = Vec::new();
+            let mut checkpoint_shard_proofs = Vec::new();
+            let mut error = None;
             tracing::debug_span!("phase 2 prover").in_scope(|| {
-                for (records, traces) in p2_records_and_traces_rx.into_iter() {
-                    tracing::debug_span!("batch").in_scope(|| {
-                        let span = tracing::Span::current().clone();
-                        shard_proofs.par_extend(
-                            records.into_par_iter().zip(traces.into_par_iter()).map(
-                                |(record, traces)| {
-                                    let _span = span.enter();
-                                    let data = prover.commit(record, traces);
-                                    prover.open(pk, data, &mut challenger.clone()).unwrap()
-                                },
-                            ),
-                        );
+                for batch in p2_records_and_traces_rx.into_iter() {
+                    // Keep draining the batches after an error, so that the workers can exit.
+                    if control.is_cancelled() || error.is_some() {
+                        continue;
+                    }
+                    match batch {
+                        ShardBatch::Traced {
+                            index,
+                            last,
+                            records,
+                            traces,
+                        } => {
+                            tracing::debug_span!("batch").in_scope(|| {
+                                let span = tracing::Span::current().clone();
+                                checkpoint_shard_proofs.par_extend(
+                                    records.into_par_iter().zip(traces.into_par_iter()).map(
+                                        |(record, traces)| {
+                                            let _span = span.enter();
+                                            let data = prover.commit(record, traces);
+                                            prover.open(pk, data, &mut challenger.clone()).unwrap()
+                                        },
+                                    ),
+                                );
+                            });
+
+                            // Once all shards of the checkpoint are proven, persist their proofs.
+                            if last {
+                                if let Some(store) = store {
+                                    if let Err(e) = store
+                                        .lock()
+                                        .unwrap()
+                                        .save_shard_proofs(index, &checkpoint_shard_proofs)
+                                    {
+                                        error = Some(SP1CoreProverError::IoError(e));
+                                        continue;
+                                    }
+                                }
+                                shard_proofs.append(&mut checkpoint_shard_proofs);
+                            }
+                        }
+                        ShardBatch::Restored(index) => {
+                            match load_shard_proofs::<SC>(store, index) {
+                                Ok(restored) => shard_proofs.extend(restored),
+                                Err(e) => {
+                                    error = Some(e);
+                                    continue;
+                                }
+                            }
+                        }
+                    }
+                    control.report(ProgressEvent::ShardsProven {
+                        shards: shard_proofs.len() + checkpoint_shard_proofs.len(),
                     });
                 }
             });
-            shard_proofs
+            error.map_or(Ok(shard_proofs), Err)
         });
 
         // Wait until the records and traces have been fully generated for phase 2.
@@ -541,6 +701,10 @@ where
 
         // Wait until the phase 2 prover has finished.
         let shard_proofs = p2_prover_handle.join().unwrap();
+        if control.is_cancelled() {
+            return Err(SP1CoreProverError::Cancelled);
+        }
+        let shard_proofs = shard_proofs?;
 
         // Log some of the `ExecutionReport` information.
         let report_aggregate = report_aggregate.lock().unwrap();
@@ -675,14 +839,42 @@ where
     Ok(proof)
 }
 
+/// Takes the turns of a skipped checkpoint, so that the workers handling the next checkpoints are
+/// not left waiting for it.
+fn skip_turns(index: usize, syncs: &[&TurnBasedSync]) {
+    for sync in syncs {
+        sync.wait_for_turn(index);
+        sync.advance_turn();
+    }
+}
+
 fn trace_checkpoint(
     program: Program,
     file: &File,
-    opts: SP1CoreOpts,
+    opts: &SP1CoreOpts,
+    precompiles: &PrecompileRegistry,
 ) -> (Vec<ExecutionRecord>, ExecutionReport) {
     let mut reader = std::io::BufReader::new(file);
     let state = bincode::deserialize_from(&mut reader).expect("failed to deserialize state");
-    let mut runtime = Runtime::recover(program.clone(), state, opts);
+    trace_state(program, state, opts, precompiles)
+}
+
+/// Re-executes the program from a checkpoint and returns the records of the shards it produces.
+///
+/// The precompiles must be the ones registered for the original execution, as they determine the
+/// syscalls available to the program and the shard boundaries.
+pub(crate) fn trace_state(
+    program: Program,
+    state: ExecutionState,
+    opts: &SP1CoreOpts,
+    precompiles: &PrecompileRegistry,
+) -> (Vec<ExecutionRecord>, ExecutionReport) {
+    let context = SP1Context {
+        precompiles: Some(precompiles.clone()),
+        ..Default::default()
+    };
+    let mut runtime = Runtime::with_context(program, *opts, context);
+    runtime.state = state;
     // We already passed the deferred proof verifier when creating checkpoints, so the proofs were
     // already verified. So here we use a noop verifier to not print any warnings.
     runtime.subproof_verifier = Arc::new(NoOpSubproofVerifier);
@@ -690,6 +882,94 @@ fn trace_checkpoint(
     (events, runtime.report)
 }
 
+/// Updates the public values of the records traced from a checkpoint, defers the events that are
+/// too expensive to include in every shard, and appends the deferred shards that are ready to be
+/// proven.
+///
+/// The `state` and `deferred` accumulators must be threaded through the checkpoints in order.
+pub(crate) fn update_shard_state(
+    records: &mut Vec<ExecutionRecord>,
+    state: &mut PublicValues<u32, u32>,
+    deferred: &mut ExecutionRecord,
+    done: bool,
+    opts: &SP1CoreOpts,
+) {
+    // Update the public values & prover state for the shards which contain "cpu events".
+    for record in records.iter_mut() {
+        state.shard += 1;
+        state.execution_shard = record.public_values.execution_shard;
+        state.start_pc = record.public_values.start_pc;
+        state.next_pc = record.public_values.next_pc;
+        state.committed_value_digest = record.public_values.committed_value_digest;
+        state.deferred_proofs_digest = record.public_values.deferred_proofs_digest;
+        record.public_values = *state;
+    }
+
+    // Defer events that are too expensive to include in every shard.
+    for record in records.iter_mut() {
+        deferred.append(&mut record.defer());
+    }
+
+    // See if any deferred shards are ready to be commited to.
+    let mut deferred = deferred.split(done, opts.split_opts);
+
+    // Update the public values & prover state for the shards which do not contain "cpu events"
+    // before committing to them.
+    if !done {
+        state.execution_shard += 1;
+    }
+    for record in deferred.iter_mut() {
+        state.shard += 1;
+        state.previous_init_addr_bits = record.public_values.previous_init_addr_bits;
+        state.last_init_addr_bits = record.public_values.last_init_addr_bits;
+        state.previous_finalize_addr_bits = record.public_values.previous_finalize_addr_bits;
+        state.last_finalize_addr_bits = record.public_values.last_finalize_addr_bits;
+        state.start_pc = state.next_pc;
+        record.public_values = *state;
+    }
+    records.append(&mut deferred);
+}
+
+/// Sends the records and traces of a checkpoint to a prover thread in batches of `batch_size`,
+/// flagging the last batch of the checkpoint.
+fn send_shard_batches<SC: StarkGenericConfig>(
+    tx: &Mutex<SyncSender<ShardBatch<SC>>>,
+    index: usize,
+    records: Vec<ExecutionRecord>,
+    traces: Vec<Vec<(String, RowMajorMatrix<Val<SC>>)>>,
+    batch_size: usize,
+) {
+    let mut batches = chunk_vec(records, batch_size)
+        .into_iter()
+        .zip(chunk_vec(traces, batch_size))
+        .collect::<Vec<_>>();
+    if batches.is_empty() {
+        batches.push((Vec::new(), Vec::new()));
+    }
+    let num_batches = batches.len();
+    for (i, (records, traces)) in batches.into_iter().enumerate() {
+        let batch = ShardBatch::Traced {
+            index,
+            last: i + 1 == num_batches,
+            records,
+            traces,
+        };
+        tx.lock().unwrap().send(batch).unwrap();
+    }
+}
+
+fn load_shard_proofs<SC: StarkGenericConfig>(
+    store: Option<&Mutex<CheckpointStore>>,
+    index: usize,
+) -> Result<Vec<ShardProof<SC>>, SP1CoreProverError> {
+    store
+        .ok_or(SP1CoreProverError::MissingCheckpointDir)?
+        .lock()
+        .unwrap()
+        .load_shard_proofs(index)
+        .map_err(SP1CoreProverError::IoError)
+}
+
 fn reset_seek(file: &mut File) {
     file.seek(std::io::SeekFrom::Start(0))
         .expect("failed to seek to start of tempfile");
@@ -758,424 +1038,3 @@ where
 {
     p3_uni_stark::verify(&UniConfig(config.clone()), air, challenger, proof, &vec![])
 }
-
-pub use baby_bear_keccak::BabyBearKeccak;
-pub use baby_bear_poseidon2::BabyBearPoseidon2;
-use p3_air::Air;
-use p3_matrix::dense::RowMajorMatrix;
-use p3_uni_stark::Proof;
-
-pub mod baby_bear_poseidon2 {
-
-    use p3_baby_bear::{BabyBear, DiffusionMatrixBabyBear};
-    use p3_challenger::DuplexChallenger;
-    use p3_commit::ExtensionMmcs;
-    use p3_dft::Radix2DitParallel;
-    use p3_field::{extension::BinomialExtensionField, Field};
-    use p3_fri::{FriConfig, TwoAdicFriPcs};
-    use p3_merkle_tree::FieldMerkleTreeMmcs;
-    use p3_poseidon2::Poseidon2;
-    use p3_poseidon2::Poseidon2ExternalMatrixGeneral;
-    use p3_symmetric::{PaddingFreeSponge, TruncatedPermutation};
-    use serde::{Deserialize, Serialize};
-    use sp1_primitives::RC_16_30;
-
-    use crate::stark::StarkGenericConfig;
-
-    pub type Val = BabyBear;
-    pub type Challenge = BinomialExtensionField<Val, 4>;
-
-    pub type Perm = Poseidon2<Val, Poseidon2ExternalMatrixGeneral, DiffusionMatrixBabyBear, 16, 7>;
-    pub type MyHash = PaddingFreeSponge<Perm, 16, 8, 8>;
-    pub type MyCompress = TruncatedPermutation<Perm, 2, 8, 16>;
-    pub type ValMmcs = FieldMerkleTreeMmcs<
-        <Val as Field>::Packing,
-        <Val as Field>::Packing,
-        MyHash,
-        MyCompress,
-        8,
-    >;
-    pub type ChallengeMmcs = ExtensionMmcs<Val, Challenge, ValMmcs>;
-    pub type Dft = Radix2DitParallel;
-    pub type Challenger = DuplexChallenger<Val, Perm, 16, 8>;
-    type Pcs = TwoAdicFriPcs<Val, Dft, ValMmcs, ChallengeMmcs>;
-
-    pub fn my_perm() -> Perm {
-        const ROUNDS_F: usize = 8;
-        const ROUNDS_P: usize = 13;
-        let mut round_constants = RC_16_30.to_vec();
-        let internal_start = ROUNDS_F / 2;
-        let internal_end = (ROUNDS_F / 2) + ROUNDS_P;
-        let internal_round_constants = round_constants
-            .drain(internal_start..internal_end)
-            .map(|vec| vec[0])
-            .collect::<Vec<_>>();
-        let external_round_constants = round_constants;
-        Perm::new(
-            ROUNDS_F,
-            external_round_constants,
-            Poseidon2ExternalMatrixGeneral,
-            ROUNDS_P,
-            internal_round_constants,
-            DiffusionMatrixBabyBear,
-        )
-    }
-
-    pub fn default_fri_config() -> FriConfig<ChallengeMmcs> {
-        let perm = my_perm();
-        let hash = MyHash::new(perm.clone());
-        let compress = MyCompress::new(perm.clone());
-        let challenge_mmcs = ChallengeMmcs::new(ValMmcs::new(hash, compress));
-        let num_queries = match std::env::var("FRI_QUERIES") {
-            Ok(value) => value.parse().unwrap(),
-            Err(_) => 100,
-        };
-        FriConfig {
-            log_blowup: 1,
-            num_queries,
-            proof_of_work_bits: 16,
-            mmcs: challenge_mmcs,
-        }
-    }
-
-    pub fn compressed_fri_config() -> FriConfig<ChallengeMmcs> {
-        let perm = my_perm();
-        let hash = MyHash::new(perm.clone());
-        let compress = MyCompress::new(perm.clone());
-        let challenge_mmcs = ChallengeMmcs::new(ValMmcs::new(hash, compress));
-        let num_queries = match std::env::var("FRI_QUERIES") {
-            Ok(value) => value.parse().unwrap(),
-            Err(_) => 33,
-        };
-        FriConfig {
-            log_blowup: 3,
-            num_queries,
-            proof_of_work_bits: 16,
-            mmcs: challenge_mmcs,
-        }
-    }
-
-    enum BabyBearPoseidon2Type {
-        Default,
-        Compressed,
-    }
-
-    #[derive(Deserialize)]
-    #[serde(from = "std::marker::PhantomData<BabyBearPoseidon2>")]
-    pub struct BabyBearPoseidon2 {
-        pub perm: Perm,
-        pcs: Pcs,
-        config_type: BabyBearPoseidon2Type,
-    }
-
-    impl BabyBearPoseidon2 {
-        pub fn new() -> Self {
-            let perm = my_perm();
-            let hash = MyHash::new(perm.clone());
-            let compress = MyCompress::new(perm.clone());
-            let val_mmcs = ValMmcs::new(hash, compress);
-            let dft = Dft {};
-            let fri_config = default_fri_config();
-            let pcs = Pcs::new(27, dft, val_mmcs, fri_config);
-            Self {
-                pcs,
-                perm,
-                config_type: BabyBearPoseidon2Type::Default,
-            }
-        }
-
-        pub fn compressed() -> Self {
-            let perm = my_perm();
-            let hash = MyHash::new(perm.clone());
-            let compress = MyCompress::new(perm.clone());
-            let val_mmcs = ValMmcs::new(hash, compress);
-            let dft = Dft {};
-            let fri_config = compressed_fri_config();
-            let pcs = Pcs::new(27, dft, val_mmcs, fri_config);
-            Self {
-                pcs,
-                perm,
-                config_type: BabyBearPoseidon2Type::Compressed,
-            }
-        }
-    }
-
-    impl Clone for BabyBearPoseidon2 {
-        fn clone(&self) -> Self {
-            match self.config_type {
-                BabyBearPoseidon2Type::Default => Self::new(),
-                BabyBearPoseidon2Type::Compressed => Self::compressed(),
-            }
-        }
-    }
-
-    impl Default for BabyBearPoseidon2 {
-        fn default() -> Self {
-            Self::new()
-        }
-    }
-
-    /// Implement serialization manually instead of using serde to avoid cloing the config.
-    impl Serialize for BabyBearPoseidon2 {
-        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-        where
-            S: serde::Serializer,
-        {
-            std::marker::PhantomData::<BabyBearPoseidon2>.serialize(serializer)
-        }
-    }
-
-    impl From<std::marker::PhantomData<BabyBearPoseidon2>> for BabyBearPoseidon2 {
-        fn from(_: std::marker::PhantomData<BabyBearPoseidon2>) -> Self {
-            Self::new()
-        }
-    }
-
-    impl StarkGenericConfig for BabyBearPoseidon2 {
-        type Val = BabyBear;
-        type Domain = <Pcs as p3_commit::Pcs<Challenge, Challenger>>::Domain;
-        type Pcs = Pcs;
-        type Challenge = Challenge;
-        type Challenger = Challenger;
-
-        fn pcs(&self) -> &Self::Pcs {
-            &self.pcs
-        }
-
-        fn challenger(&self) -> Self::Challenger {
-            Challenger::new(self.perm.clone())
-        }
-    }
-}
-
-pub(super) mod baby_bear_keccak {
-
-    use p3_baby_bear::BabyBear;
-    use p3_challenger::{HashChallenger, SerializingChallenger32};
-    use p3_commit::ExtensionMmcs;
-    use p3_dft::Radix2DitParallel;
-    use p3_field::extension::BinomialExtensionField;
-    use p3_fri::{FriConfig, TwoAdicFriPcs};
-    use p3_keccak::Keccak256Hash;
-    use p3_merkle_tree::FieldMerkleTreeMmcs;
-    use p3_symmetric::{CompressionFunctionFromHasher, SerializingHasher32};
-    use serde::{Deserialize, Serialize};
-
-    use crate::stark::StarkGenericConfig;
-
-    use super::LOG_DEGREE_BOUND;
-
-    pub type Val = BabyBear;
-
-    pub type Challenge = BinomialExtensionField<Val, 4>;
-
-    type ByteHash = Keccak256Hash;
-    type FieldHash = SerializingHasher32<ByteHash>;
-
-    type MyCompress = CompressionFunctionFromHasher<u8, ByteHash, 2, 32>;
-
-    pub type ValMmcs = FieldMerkleTreeMmcs<Val, u8, FieldHash, MyCompress, 32>;
-    pub type ChallengeMmcs = ExtensionMmcs<Val, Challenge, ValMmcs>;
-
-    pub type Dft = Radix2DitParallel;
-
-    type Challenger = SerializingChallenger32<Val, HashChallenger<u8, ByteHash, 32>>;
-
-    type Pcs = TwoAdicFriPcs<Val, Dft, ValMmcs, ChallengeMmcs>;
-
-    #[derive(Deserialize)]
-    #[serde(from = "std::marker::PhantomData<BabyBearKeccak>")]
-    pub struct BabyBearKeccak {
-        pcs: Pcs,
-    }
-    // Implement serialization manually instead of using serde(into) to avoid cloing the config
-    impl Serialize for BabyBearKeccak {
-        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-        where
-            S: serde::Serializer,
-        {
-            std::marker::PhantomData::<BabyBearKeccak>.serialize(serializer)
-        }
-    }
-
-    impl From<std::marker::PhantomData<BabyBearKeccak>> for BabyBearKeccak {
-        fn from(_: std::marker::PhantomData<BabyBearKeccak>) -> Self {
-            Self::new()
-        }
-    }
-
-    impl BabyBearKeccak {
-        #[allow(dead_code)]
-        pub fn new() -> Self {
-            let byte_hash = ByteHash {};
-            let field_hash = FieldHash::new(byte_hash);
-
-            let compress = MyCompress::new(byte_hash);
-
-            let val_mmcs = ValMmcs::new(field_hash, compress);
-
-            let challenge_mmcs = ChallengeMmcs::new(val_mmcs.clone());
-
-            let dft = Dft {};
-
-            let fri_config = FriConfig {
-                log_blowup: 1,
-                num_queries: 100,
-                proof_of_work_bits: 16,
-                mmcs: challenge_mmcs,
-            };
-            let pcs = Pcs::new(LOG_DEGREE_BOUND, dft, val_mmcs, fri_config);
-
-            Self { pcs }
-        }
-    }
-
-    impl Default for BabyBearKeccak {
-        fn default() -> Self {
-            Self::new()
-        }
-    }
-
-    impl Clone for BabyBearKeccak {
-        fn clone(&self) -> Self {
-            Self::new()
-        }
-    }
-
-    impl StarkGenericConfig for BabyBearKeccak {
-        type Val = Val;
-        type Challenge = Challenge;
-
-        type Domain = <Pcs as p3_commit::Pcs<Challenge, Challenger>>::Domain;
-
-        type Pcs = Pcs;
-        type Challenger = Challenger;
-
-        fn pcs(&self) -> &Self::Pcs {
-            &self.pcs
-        }
-
-        fn challenger(&self) -> Self::Challenger {
-            let byte_hash = ByteHash {};
-            Challenger::from_hasher(vec![], byte_hash)
-        }
-    }
-}
-
-pub(super) mod baby_bear_blake3 {
-
-    use p3_baby_bear::BabyBear;
-    use p3_blake3::Blake3;
-    use p3_challenger::{HashChallenger, SerializingChallenger32};
-    use p3_commit::ExtensionMmcs;
-    use p3_dft::Radix2DitParallel;
-    use p3_field::extension::BinomialExtensionField;
-    use p3_fri::{FriConfig, TwoAdicFriPcs};
-    use p3_merkle_tree::FieldMerkleTreeMmcs;
-    use p3_symmetric::{CompressionFunctionFromHasher, SerializingHasher32};
-    use serde::{Deserialize, Serialize};
-
-    use crate::stark::StarkGenericConfig;
-
-    use super::LOG_DEGREE_BOUND;
-
-    pub type Val = BabyBear;
-
-    pub type Challenge = BinomialExtensionField<Val, 4>;
-
-    type ByteHash = Blake3;
-    type FieldHash = SerializingHasher32<ByteHash>;
-
-    type MyCompress = CompressionFunctionFromHasher<u8, ByteHash, 2, 32>;
-
-    pub type ValMmcs = FieldMerkleTreeMmcs<Val, u8, FieldHash, MyCompress, 32>;
-    pub type ChallengeMmcs = ExtensionMmcs<Val, Challenge, ValMmcs>;
-
-    pub type Dft = Radix2DitParallel;
-
-    type Challenger = SerializingChallenger32<Val, HashChallenger<u8, ByteHash, 32>>;
-
-    type Pcs = TwoAdicFriPcs<Val, Dft, ValMmcs, ChallengeMmcs>;
-
-    #[derive(Deserialize)]
-    #[serde(from = "std::marker::PhantomData<BabyBearBlake3>")]
-    pub struct BabyBearBlake3 {
-        pcs: Pcs,
-    }
-
-    // Implement serialization manually instead of using serde(into) to avoid cloing the config
-    impl Serialize for BabyBearBlake3 {
-        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-        where
-            S: serde::Serializer,
-        {
-            std::marker::PhantomData::<Self>.serialize(serializer)
-        }
-    }
-
-    impl From<std::marker::PhantomData<BabyBearBlake3>> for BabyBearBlake3 {
-        fn from(_: std::marker::PhantomData<BabyBearBlake3>) -> Self {
-            Self::new()
-        }
-    }
-
-    impl Clone for BabyBearBlake3 {
-        fn clone(&self) -> Self {
-            Self::new()
-        }
-    }
-
-    impl BabyBearBlake3 {
-        pub fn new() -> Self {
-            let byte_hash = ByteHash {};
-            let field_hash = FieldHash::new(byte_hash);
-
-            let compress = MyCompress::new(byte_hash);
-
-            let val_mmcs = ValMmcs::new(field_hash, compress);
-
-            let challenge_mmcs = ChallengeMmcs::new(val_mmcs.clone());
-
-            let dft = Dft {};
-
-            let num_queries = match std::env::var("FRI_QUERIES") {
-                Ok(value) => value.parse().unwrap(),
-                Err(_) => 100,
-            };
-            let fri_config = FriConfig {
-                log_blowup: 1,
-                num_queries,
-                proof_of_work_bits: 16,
-                mmcs: challenge_mmcs,
-            };
-            let pcs = Pcs::new(LOG_DEGREE_BOUND, dft, val_mmcs, fri_config);
-
-            Self { pcs }
-        }
-    }
-
-    impl Default for BabyBearBlake3 {
-        fn default() -> Self {
-            Self::new()
-        }
-    }
-
-    impl StarkGenericConfig for BabyBearBlake3 {
-        type Val = Val;
-        type Challenge = Challenge;
-
-        type Domain = <Pcs as p3_commit::Pcs<Challenge, Challenger>>::Domain;
-
-        type Pcs = Pcs;
-        type Challenger = Challenger;
-
-        fn pcs(&self) -> &Self::Pcs {
-            &self.pcs
-        }
-
-        fn challenger(&self) -> Self::Challenger {
-            let byte_hash = ByteHash {};
-            Challenger::from_hasher(vec![], byte_hash)
-        }
-    }
-}
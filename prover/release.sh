@@ -4,8 +4,12 @@ set -e
 # Get the version from the command line.
 VERSION=$1
 
+# Get the optional circuit kind from the command line (ex. groth16). The plonk artifacts are
+# uploaded without a suffix.
+KIND=$2
+
 # Specify the file to upload and the S3 bucket name
-FILE_TO_UPLOAD="./build"
+FILE_TO_UPLOAD="./build${KIND:+_$KIND}"
 S3_BUCKET="sp1-circuits"
 
 # Check for unstaged changes in the Git repository
@@ -22,10 +26,10 @@ if [ $? -ne 0 ]; then
 fi
 
 # Put the version in the build directory
-echo "$COMMIT_HASH $VERSION" > ./build/SP1_COMMIT
+echo "$COMMIT_HASH $VERSION" > $FILE_TO_UPLOAD/SP1_COMMIT
 
 # Create archive named after the commit hash
-ARCHIVE_NAME="${VERSION}.tar.gz"
+ARCHIVE_NAME="${VERSION}${KIND:+-$KIND}.tar.gz"
 cd $FILE_TO_UPLOAD
 tar --exclude='srs.bin' --exclude='srs_lagrange.bin' -czvf "../$ARCHIVE_NAME" .
 cd -
@@ -0,0 +1,369 @@
+//! A content-addressed cache of proving and verifying keys on disk.
+//!
+//! Generating the keys of a program, and of the recursion programs, commits to their preprocessed
+//! traces, which takes minutes for the recursion programs. The cache stores the keys under
+//! `~/.sp1/keys/<SP1_CIRCUIT_VERSION>`, so that they are only generated once.
+//!
+//! Entries are named after the SHA-256 hash of everything the keys are derived from: the
+//! [StarkMachine::chip_set] of the machine and the program, which is the ELF for the keys of a
+//! program and the compiled recursion program for the keys of the recursion steps. A change to
+//! either never loads stale keys, even within a circuit version.
+//!
+//! Every entry starts with the SHA-256 digest of its `bincode` payload, which is checked on load.
+//! Entries that fail the check, or that can't be decoded, are removed and generated again. The
+//! least recently used entries are evicted once the cache exceeds its maximum size.
+//! [SP1KeyCache::evict] also removes the directories of other circuit versions, but only those
+//! created by the cache, which hold a [KEY_CACHE_MARKER] file.
+
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use p3_field::PrimeField32;
+use serde::{de::DeserializeOwned, Serialize};
+use sha2::{Digest, Sha256};
+use sp1_core::air::MachineAir;
+use sp1_core::stark::{StarkGenericConfig, StarkMachine, Val};
+use sp1_core::SP1_CIRCUIT_VERSION;
+use tempfile::NamedTempFile;
+use thiserror::Error;
+
+/// The default maximum size of the cache, in bytes.
+pub const DEFAULT_KEY_CACHE_MAX_SIZE: u64 = 16 << 30;
+
+/// The size of the digest at the start of every entry, in bytes.
+const DIGEST_SIZE: usize = 32;
+
+/// The extension of the files of the entries.
+const ENTRY_EXTENSION: &str = "bin";
+
+/// The file marking the directories created by the cache, which are the only ones it removes.
+pub const KEY_CACHE_MARKER: &str = ".sp1-key-cache";
+
+#[derive(Error, Debug)]
+pub enum SP1KeyCacheError {
+    #[error("io error: {0}")]
+    Io(#[from] io::Error),
+    #[error("bincode error: {0}")]
+    Bincode(#[from] bincode::Error),
+    #[error("the entry is corrupted")]
+    Corrupted,
+}
+
+/// An on-disk cache of keys, keyed by name within the directory of the current circuit version.
+#[derive(Debug, Clone)]
+pub struct SP1KeyCache {
+    root: PathBuf,
+    max_size: u64,
+}
+
+impl SP1KeyCache {
+    /// Creates a cache in the given root directory, with the default maximum size.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            max_size: DEFAULT_KEY_CACHE_MAX_SIZE,
+        }
+    }
+
+    /// Sets the maximum size of the cache, in bytes.
+    pub fn with_max_size(mut self, max_size: u64) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// Creates the cache configured by the environment, if it is enabled.
+    ///
+    /// The cache is enabled by setting `SP1_KEY_CACHE` to `true`. Its root directory defaults to
+    /// `~/.sp1/keys` and can be set with `SP1_KEY_CACHE_DIR`, and its maximum size in bytes can be
+    /// set with `SP1_KEY_CACHE_MAX_SIZE`.
+    pub fn from_env() -> Option<Self> {
+        let enabled = env::var("SP1_KEY_CACHE").map_or(false, |s| s == "true" || s == "1");
+        if !enabled {
+            return None;
+        }
+        let root =
+            env::var("SP1_KEY_CACHE_DIR").map_or_else(|_| Self::default_root(), PathBuf::from);
+        let max_size = env::var("SP1_KEY_CACHE_MAX_SIZE").map_or(DEFAULT_KEY_CACHE_MAX_SIZE, |s| {
+            s.parse().unwrap_or(DEFAULT_KEY_CACHE_MAX_SIZE)
+        });
+        Some(Self::new(root).with_max_size(max_size))
+    }
+
+    /// The default root directory of the cache, `~/.sp1/keys`.
+    pub fn default_root() -> PathBuf {
+        dirs::home_dir()
+            .expect("failed to get home directory")
+            .join(".sp1")
+            .join("keys")
+    }
+
+    /// The directory of the entries of the current circuit version.
+    pub fn dir(&self) -> PathBuf {
+        self.root.join(SP1_CIRCUIT_VERSION)
+    }
+
+    /// The name of an entry, from its kind and the SHA-256 hash of the machine and the program
+    /// its keys are derived from.
+    pub fn entry<SC, A>(kind: &str, machine: &StarkMachine<SC, A>, program: &[u8]) -> String
+    where
+        SC: StarkGenericConfig,
+        Val<SC>: PrimeField32,
+        A: MachineAir<Val<SC>>,
+    {
+        let mut hasher = Sha256::new();
+        let chip_set = machine.chip_set();
+        hasher.update((chip_set.len() as u64).to_le_bytes());
+        for value in chip_set {
+            hasher.update(value.as_canonical_u32().to_le_bytes());
+        }
+        hasher.update(program);
+        format!("{}-{}", kind, hex::encode(hasher.finalize()))
+    }
+
+    /// The name of the entry of the keys of a program, from its ELF and the core machine.
+    pub fn program_entry<SC, A>(machine: &StarkMachine<SC, A>, elf: &[u8]) -> String
+    where
+        SC: StarkGenericConfig,
+        Val<SC>: PrimeField32,
+        A: MachineAir<Val<SC>>,
+    {
+        Self::entry("program", machine, elf)
+    }
+
+    fn path(&self, name: &str) -> PathBuf {
+        self.dir().join(name).with_extension(ENTRY_EXTENSION)
+    }
+
+    /// Loads an entry, or returns `None` if it is missing.
+    ///
+    /// An entry that fails the integrity check, or can't be decoded, is removed.
+    pub fn get<T: DeserializeOwned>(&self, name: &str) -> Option<T> {
+        let path = self.path(name);
+        let bytes = match fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return None,
+            Err(e) => {
+                tracing::warn!("failed to read key cache entry {}: {}", path.display(), e);
+                return None;
+            }
+        };
+        match decode_entry(&bytes) {
+            Ok(value) => {
+                // Mark the entry as recently used, for the eviction.
+                if let Err(e) = File::options()
+                    .write(true)
+                    .open(&path)
+                    .and_then(|file| file.set_modified(SystemTime::now()))
+                {
+                    tracing::debug!("failed to touch key cache entry {}: {}", path.display(), e);
+                }
+                Some(value)
+            }
+            Err(e) => {
+                tracing::warn!("removing key cache entry {}: {}", path.display(), e);
+                let _ = fs::remove_file(&path);
+                None
+            }
+        }
+    }
+
+    /// Stores an entry, then evicts the least recently used entries if the cache is too large.
+    pub fn insert<T: Serialize>(&self, name: &str, value: &T) -> Result<(), SP1KeyCacheError> {
+        let dir = self.dir();
+        fs::create_dir_all(&dir)?;
+        let marker = dir.join(KEY_CACHE_MARKER);
+        if !marker.exists() {
+            File::create(marker)?;
+        }
+        let payload = bincode::serialize(value)?;
+
+        // Write to a temporary file first, so that concurrent readers never see a partial entry.
+        let mut file = NamedTempFile::new_in(&dir)?;
+        {
+            let mut writer = BufWriter::new(file.as_file_mut());
+            writer.write_all(&Sha256::digest(&payload))?;
+            writer.write_all(&payload)?;
+            writer.flush()?;
+        }
+        file.persist(self.path(name)).map_err(|e| e.error)?;
+
+        self.evict_entries()
+    }
+
+    /// Loads an entry, or generates and stores it if it is missing.
+    ///
+    /// Errors of the cache are logged, and never prevent generating the value.
+    pub fn get_or_insert_with<T: Serialize + DeserializeOwned>(
+        &self,
+        name: &str,
+        f: impl FnOnce() -> T,
+    ) -> T {
+        if let Some(value) = self.get(name) {
+            tracing::debug!("loaded {} from the key cache", name);
+            return value;
+        }
+        let value = f();
+        if let Err(e) = self.insert(name, &value) {
+            tracing::warn!("failed to store {} in the key cache: {}", name, e);
+        }
+        value
+    }
+
+    /// Removes the directories of the other circuit versions that were created by the cache, then
+    /// the least recently used entries until the cache fits in its maximum size.
+    ///
+    /// Other directories in the root are left alone, and other circuit versions are only removed
+    /// here, never when inserting, since another version of SP1 may be using them.
+    pub fn evict(&self) -> Result<(), SP1KeyCacheError> {
+        for dir in fs::read_dir(&self.root)? {
+            let dir = dir?;
+            if dir.file_type()?.is_dir()
+                && dir.file_name() != SP1_CIRCUIT_VERSION
+                && dir.path().join(KEY_CACHE_MARKER).is_file()
+            {
+                tracing::debug!("removing key cache of {}", dir.path().display());
+                fs::remove_dir_all(dir.path())?;
+            }
+        }
+        self.evict_entries()
+    }
+
+    /// Removes the least recently used entries of the current circuit version until the cache fits
+    /// in its maximum size. The most recently used entry is kept.
+    fn evict_entries(&self) -> Result<(), SP1KeyCacheError> {
+        let mut entries = Vec::new();
+        for entry in fs::read_dir(self.dir())? {
+            let entry = entry?;
+            let path = entry.path();
+            if path.extension().map_or(true, |ext| ext != ENTRY_EXTENSION) {
+                continue;
+            }
+            let metadata = entry.metadata()?;
+            entries.push((metadata.modified()?, metadata.len(), path));
+        }
+        entries.sort();
+
+        let mut size = entries.iter().map(|(_, len, _)| len).sum::<u64>();
+        for (_, len, path) in entries.iter().take(entries.len().saturating_sub(1)) {
+            if size <= self.max_size {
+                break;
+            }
+            tracing::debug!("evicting key cache entry {}", path.display());
+            remove_entry(path)?;
+            size -= len;
+        }
+        Ok(())
+    }
+}
+
+/// Checks the digest of an entry and decodes its payload.
+fn decode_entry<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, SP1KeyCacheError> {
+    if bytes.len() < DIGEST_SIZE {
+        return Err(SP1KeyCacheError::Corrupted);
+    }
+    let (digest, payload) = bytes.split_at(DIGEST_SIZE);
+    if Sha256::digest(payload).as_slice() != digest {
+        return Err(SP1KeyCacheError::Corrupted);
+    }
+    Ok(bincode::deserialize(payload)?)
+}
+
+/// Removes an entry, which may have been removed concurrently by another process.
+fn remove_entry(path: &Path) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use sp1_core::stark::RiscvAir;
+
+    use super::*;
+    use crate::{CompressAir, CoreSC, InnerSC};
+
+    #[test]
+    fn test_key_cache_round_trip() {
+        let root = tempfile::tempdir().unwrap();
+        let cache = SP1KeyCache::new(root.path());
+        assert_eq!(cache.get::<Vec<u32>>("keys"), None);
+
+        let mut calls = 0;
+        let value = cache.get_or_insert_with("keys", || {
+            calls += 1;
+            vec![1u32, 2, 3]
+        });
+        let cached: Vec<u32> = cache.get_or_insert_with("keys", || {
+            calls += 1;
+            vec![]
+        });
+        assert_eq!(value, cached);
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn test_key_cache_integrity() {
+        let root = tempfile::tempdir().unwrap();
+        let cache = SP1KeyCache::new(root.path());
+        cache.insert("keys", &vec![1u32, 2, 3]).unwrap();
+
+        let path = cache.path("keys");
+        let mut bytes = fs::read(&path).unwrap();
+        *bytes.last_mut().unwrap() ^= 1;
+        fs::write(&path, bytes).unwrap();
+
+        assert_eq!(cache.get::<Vec<u32>>("keys"), None);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_key_cache_eviction() {
+        let root = tempfile::tempdir().unwrap();
+        let stale = root.path().join("v0.0.0");
+        fs::create_dir_all(&stale).unwrap();
+        File::create(stale.join(KEY_CACHE_MARKER)).unwrap();
+        let unrelated = root.path().join("v0.0.1");
+        fs::create_dir_all(&unrelated).unwrap();
+
+        // Each entry holds a digest, a length prefix and 100 bytes.
+        let cache = SP1KeyCache::new(root.path()).with_max_size(300);
+        let value = vec![0u8; 100];
+        for name in ["a", "b", "c"] {
+            cache.insert(name, &value).unwrap();
+            // Entries inserted within the resolution of the file times would tie.
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert!(stale.exists());
+        assert!(!cache.path("a").exists());
+
+        // Using an entry makes it the most recently used one.
+        cache.get::<Vec<u8>>("b").unwrap();
+        std::thread::sleep(Duration::from_millis(10));
+        cache.insert("d", &value).unwrap();
+        assert!(cache.path("b").exists());
+        assert!(!cache.path("c").exists());
+        assert!(cache.path("d").exists());
+
+        // Only the other versions created by the cache are removed, and only by `evict`.
+        cache.evict().unwrap();
+        assert!(!stale.exists());
+        assert!(unrelated.exists());
+        assert!(cache.path("d").exists());
+    }
+
+    #[test]
+    fn test_key_cache_entry_names() {
+        let riscv = RiscvAir::machine(CoreSC::default());
+        let compress = CompressAir::machine(InnerSC::default());
+        let name = SP1KeyCache::entry("keys", &riscv, b"program");
+        assert_eq!(name, SP1KeyCache::entry("keys", &riscv, b"program"));
+        assert_ne!(name, SP1KeyCache::entry("keys", &riscv, b"other program"));
+        assert_ne!(name, SP1KeyCache::entry("keys", &compress, b"program"));
+    }
+}
@@ -0,0 +1,485 @@
+//! A self-describing, versioned container format for proofs and keys.
+//!
+//! Every artifact starts with a header, made of the magic bytes [SP1_ARTIFACT_MAGIC], the format
+//! version, the [SP1Encoding] of the payload, the [SP1ArtifactKind] of the artifact and the
+//! [SP1PayloadSchema] the payload was written with. The header is checked on load, so that files
+//! holding another kind of artifact are rejected with an error instead of being misread.
+//!
+//! Payloads of any schema are decoded, and their schema is reported to the caller, who decides
+//! whether the proofs and keys of that circuit version are usable. Headers of older format
+//! versions are migrated to the current header on load. Files written before the container format
+//! existed hold the raw `bincode` encoding of the artifact, and are decoded with
+//! [SP1Artifact::decode_legacy], which migrates the types that changed since then.
+
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use serde::{de::DeserializeOwned, Serialize};
+use sp1_core::SP1_CIRCUIT_VERSION;
+use thiserror::Error;
+
+/// The magic bytes at the start of every artifact.
+pub const SP1_ARTIFACT_MAGIC: [u8; 4] = *b"SP1A";
+
+/// The current version of the container format.
+///
+/// Bump this version, and migrate the older versions in [SP1ArtifactHeader::from_bytes], whenever
+/// the layout of the header changes. Changes to the payload are tracked by [SP1PayloadSchema].
+///
+/// - Version 1 has no payload schema.
+/// - Version 2 adds the payload schema after the kind of the artifact.
+pub const SP1_ARTIFACT_VERSION: u16 = 2;
+
+/// The size of the fixed part of the header, in bytes.
+const FIXED_HEADER_SIZE: usize = 8;
+
+/// The encoding of the payload of an artifact.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum SP1Encoding {
+    /// Compact binary encoding with `bincode`. Not self-describing.
+    #[default]
+    Bincode = 0,
+    /// Human-readable JSON encoding.
+    Json = 1,
+    /// Self-describing binary encoding with CBOR.
+    Cbor = 2,
+}
+
+impl TryFrom<u8> for SP1Encoding {
+    type Error = SP1FormatError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(SP1Encoding::Bincode),
+            1 => Ok(SP1Encoding::Json),
+            2 => Ok(SP1Encoding::Cbor),
+            _ => Err(SP1FormatError::UnknownEncoding(value)),
+        }
+    }
+}
+
+/// The kind of artifact held in a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum SP1ArtifactKind {
+    CoreProof = 0,
+    CompressedProof = 1,
+    PlonkProof = 2,
+    Groth16Proof = 3,
+    ProvingKey = 4,
+    VerifyingKey = 5,
+}
+
+impl TryFrom<u8> for SP1ArtifactKind {
+    type Error = SP1FormatError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(SP1ArtifactKind::CoreProof),
+            1 => Ok(SP1ArtifactKind::CompressedProof),
+            2 => Ok(SP1ArtifactKind::PlonkProof),
+            3 => Ok(SP1ArtifactKind::Groth16Proof),
+            4 => Ok(SP1ArtifactKind::ProvingKey),
+            5 => Ok(SP1ArtifactKind::VerifyingKey),
+            _ => Err(SP1FormatError::UnknownKind(value)),
+        }
+    }
+}
+
+/// The versions of the SDK and of the circuits a payload was written with, which determine how it
+/// is laid out and which proofs and keys it is compatible with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SP1PayloadSchema {
+    /// The version of `sp1-prover`.
+    pub sdk_version: String,
+    /// The [SP1_CIRCUIT_VERSION].
+    pub circuit_version: String,
+}
+
+impl SP1PayloadSchema {
+    /// The schema of the payloads written by this version.
+    pub fn current() -> Self {
+        Self {
+            sdk_version: env!("CARGO_PKG_VERSION").to_string(),
+            circuit_version: SP1_CIRCUIT_VERSION.to_string(),
+        }
+    }
+
+    /// The schema reported for payloads written before it was recorded in the header, which is that
+    /// of the last release that did not record it.
+    ///
+    /// Older releases wrote the same layout, but the circuit version of such a payload is not
+    /// known for certain.
+    pub fn legacy() -> Self {
+        Self {
+            sdk_version: "1.1.1".to_string(),
+            circuit_version: "v1.1.0".to_string(),
+        }
+    }
+
+    /// Whether the proofs and keys of this schema can be used with those of the `other` schema.
+    ///
+    /// The circuit versions must match, since the proofs and keys of different circuits are not
+    /// compatible. The SDK versions must have the same major and minor version, since the circuits
+    /// only change in minor releases.
+    pub fn is_compatible(&self, other: &Self) -> bool {
+        fn major_minor(version: &str) -> Option<(&str, &str)> {
+            let mut parts = version.split('.');
+            Some((parts.next()?, parts.next()?))
+        }
+        self.circuit_version == other.circuit_version
+            && major_minor(&self.sdk_version).is_some()
+            && major_minor(&self.sdk_version) == major_minor(&other.sdk_version)
+    }
+}
+
+impl std::fmt::Display for SP1PayloadSchema {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "sdk {} with circuit {}",
+            self.sdk_version, self.circuit_version
+        )
+    }
+}
+
+/// The header of an artifact.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SP1ArtifactHeader {
+    pub version: u16,
+    pub encoding: SP1Encoding,
+    pub kind: SP1ArtifactKind,
+    pub schema: SP1PayloadSchema,
+}
+
+impl SP1ArtifactHeader {
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = SP1_ARTIFACT_MAGIC.to_vec();
+        bytes.extend_from_slice(&self.version.to_le_bytes());
+        bytes.push(self.encoding as u8);
+        bytes.push(self.kind as u8);
+        for field in [&self.schema.sdk_version, &self.schema.circuit_version] {
+            let len = u8::try_from(field.len()).expect("the schema versions are short");
+            bytes.push(len);
+            bytes.extend_from_slice(field.as_bytes());
+        }
+        bytes
+    }
+
+    /// Reads the header at the start of `bytes`, returning `None` if there is no magic, and the
+    /// size of the header in bytes.
+    ///
+    /// Headers of older format versions are migrated to the current version.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Option<(Self, usize)>, SP1FormatError> {
+        if !bytes.starts_with(&SP1_ARTIFACT_MAGIC) {
+            return Ok(None);
+        }
+        if bytes.len() < FIXED_HEADER_SIZE {
+            return Err(SP1FormatError::Truncated);
+        }
+        let version = u16::from_le_bytes([bytes[4], bytes[5]]);
+        let encoding = bytes[6].try_into()?;
+        let kind = bytes[7].try_into()?;
+        let (schema, size) = match version {
+            1 => (SP1PayloadSchema::legacy(), FIXED_HEADER_SIZE),
+            2 => {
+                let mut offset = FIXED_HEADER_SIZE;
+                let mut read_field = || {
+                    let len = *bytes.get(offset).ok_or(SP1FormatError::Truncated)? as usize;
+                    let field = bytes
+                        .get(offset + 1..offset + 1 + len)
+                        .ok_or(SP1FormatError::Truncated)?;
+                    offset += 1 + len;
+                    String::from_utf8(field.to_vec()).map_err(|_| SP1FormatError::InvalidSchema)
+                };
+                let schema = SP1PayloadSchema {
+                    sdk_version: read_field()?,
+                    circuit_version: read_field()?,
+                };
+                (schema, offset)
+            }
+            _ => return Err(SP1FormatError::UnsupportedVersion(version)),
+        };
+        let header = Self {
+            version: SP1_ARTIFACT_VERSION,
+            encoding,
+            kind,
+            schema,
+        };
+        Ok(Some((header, size)))
+    }
+}
+
+/// An error that occurs when encoding or decoding an artifact.
+#[derive(Error, Debug)]
+pub enum SP1FormatError {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("artifact is truncated")]
+    Truncated,
+    #[error("unsupported artifact format version {0}, the latest supported version is {SP1_ARTIFACT_VERSION}")]
+    UnsupportedVersion(u16),
+    #[error("unknown artifact encoding {0}")]
+    UnknownEncoding(u8),
+    #[error("unknown artifact kind {0}")]
+    UnknownKind(u8),
+    #[error("artifact has an invalid payload schema")]
+    InvalidSchema,
+    #[error("artifact was written by {found}, whose payload can not be migrated to {expected}")]
+    SchemaMismatch {
+        expected: SP1PayloadSchema,
+        found: SP1PayloadSchema,
+    },
+    #[error("expected a {expected} artifact, found {found:?}")]
+    KindMismatch {
+        expected: &'static str,
+        found: SP1ArtifactKind,
+    },
+    #[error("bincode error: {0}")]
+    Bincode(#[from] bincode::Error),
+    #[error("json error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("cbor error: {0}")]
+    Cbor(String),
+}
+
+/// A proof or key which can be stored in the container format.
+pub trait SP1Artifact: Serialize + DeserializeOwned {
+    /// A description of the artifact, used in errors.
+    const NAME: &'static str;
+
+    /// The kind of the artifact, written in the header.
+    fn kind(&self) -> SP1ArtifactKind;
+
+    /// Whether a file of the given kind may hold this artifact.
+    fn accepts(kind: SP1ArtifactKind) -> bool;
+
+    /// Decodes the raw `bincode` encoding of the artifact written before the container format
+    /// existed, with the [SP1PayloadSchema::legacy] schema.
+    ///
+    /// Artifacts whose types changed since then migrate the legacy layout here, or return
+    /// [SP1FormatError::SchemaMismatch] if they can not.
+    fn decode_legacy(bytes: &[u8]) -> Result<Self, SP1FormatError> {
+        Ok(bincode::deserialize(bytes)?)
+    }
+}
+
+/// Encodes the artifact in the container format.
+pub fn encode_artifact<T: SP1Artifact>(
+    artifact: &T,
+    encoding: SP1Encoding,
+) -> Result<Vec<u8>, SP1FormatError> {
+    let header = SP1ArtifactHeader {
+        version: SP1_ARTIFACT_VERSION,
+        encoding,
+        kind: artifact.kind(),
+        schema: SP1PayloadSchema::current(),
+    };
+    let mut bytes = header.to_bytes();
+    match encoding {
+        SP1Encoding::Bincode => bincode::serialize_into(&mut bytes, artifact)?,
+        SP1Encoding::Json => serde_json::to_writer(&mut bytes, artifact)?,
+        SP1Encoding::Cbor => ciborium::into_writer(artifact, &mut bytes)
+            .map_err(|e| SP1FormatError::Cbor(e.to_string()))?,
+    }
+    Ok(bytes)
+}
+
+/// Decodes an artifact written with [encode_artifact], or the raw `bincode` encoding of the
+/// artifact written before the container format existed, see [decode_artifact_with_schema].
+pub fn decode_artifact<T: SP1Artifact>(bytes: &[u8]) -> Result<T, SP1FormatError> {
+    decode_artifact_with_schema(bytes).map(|(artifact, _)| artifact)
+}
+
+/// Decodes an artifact, and returns it together with the [SP1PayloadSchema] it was written with.
+///
+/// The schema is not checked against the current one, use [SP1PayloadSchema::is_compatible] to
+/// find out whether the artifact was written for the current circuits.
+pub fn decode_artifact_with_schema<T: SP1Artifact>(
+    bytes: &[u8],
+) -> Result<(T, SP1PayloadSchema), SP1FormatError> {
+    let Some((header, header_size)) = SP1ArtifactHeader::from_bytes(bytes)? else {
+        return Ok((T::decode_legacy(bytes)?, SP1PayloadSchema::legacy()));
+    };
+    if !T::accepts(header.kind) {
+        return Err(SP1FormatError::KindMismatch {
+            expected: T::NAME,
+            found: header.kind,
+        });
+    }
+
+    let payload = &bytes[header_size..];
+    let artifact: T = match header.encoding {
+        SP1Encoding::Bincode => bincode::deserialize(payload)?,
+        SP1Encoding::Json => serde_json::from_slice(payload)?,
+        SP1Encoding::Cbor => {
+            ciborium::from_reader(payload).map_err(|e| SP1FormatError::Cbor(e.to_string()))?
+        }
+    };
+
+    // The header and the payload must agree on the kind of the artifact.
+    if artifact.kind() != header.kind {
+        return Err(SP1FormatError::KindMismatch {
+            expected: T::NAME,
+            found: header.kind,
+        });
+    }
+    Ok((artifact, header.schema))
+}
+
+/// Saves the artifact to a path in the container format.
+pub fn save_artifact<T: SP1Artifact>(
+    artifact: &T,
+    path: impl AsRef<Path>,
+    encoding: SP1Encoding,
+) -> Result<(), SP1FormatError> {
+    let bytes = encode_artifact(artifact, encoding)?;
+    let mut writer = BufWriter::new(File::create(path)?);
+    writer.write_all(&bytes)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Loads an artifact from a path, see [decode_artifact].
+pub fn load_artifact<T: SP1Artifact>(path: impl AsRef<Path>) -> Result<T, SP1FormatError> {
+    let mut bytes = Vec::new();
+    BufReader::new(File::open(path)?).read_to_end(&mut bytes)?;
+    decode_artifact(&bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct TestKey {
+        commit: Vec<u32>,
+        chip_ordering: Vec<(String, usize)>,
+    }
+
+    impl SP1Artifact for TestKey {
+        const NAME: &'static str = "verifying key";
+
+        fn kind(&self) -> SP1ArtifactKind {
+            SP1ArtifactKind::VerifyingKey
+        }
+
+        fn accepts(kind: SP1ArtifactKind) -> bool {
+            kind == SP1ArtifactKind::VerifyingKey
+        }
+    }
+
+    fn test_key() -> TestKey {
+        TestKey {
+            commit: vec![1, 2, 3],
+            chip_ordering: vec![("Cpu".to_string(), 0), ("Program".to_string(), 1)],
+        }
+    }
+
+    #[test]
+    fn test_artifact_round_trip() {
+        for encoding in [SP1Encoding::Bincode, SP1Encoding::Json, SP1Encoding::Cbor] {
+            let bytes = encode_artifact(&test_key(), encoding).unwrap();
+            let (header, _) = SP1ArtifactHeader::from_bytes(&bytes).unwrap().unwrap();
+            assert_eq!(header.version, SP1_ARTIFACT_VERSION);
+            assert_eq!(header.schema, SP1PayloadSchema::current());
+            assert_eq!(header.encoding, encoding);
+            assert_eq!(header.kind, SP1ArtifactKind::VerifyingKey);
+            assert_eq!(decode_artifact::<TestKey>(&bytes).unwrap(), test_key());
+        }
+    }
+
+    #[test]
+    fn test_artifact_legacy() {
+        // Raw `bincode` files and version 1 headers were written with the legacy schema.
+        let bytes = bincode::serialize(&test_key()).unwrap();
+        let (key, schema) = decode_artifact_with_schema::<TestKey>(&bytes).unwrap();
+        assert_eq!(key, test_key());
+        assert_eq!(schema, SP1PayloadSchema::legacy());
+
+        let mut bytes = SP1_ARTIFACT_MAGIC.to_vec();
+        bytes.extend_from_slice(&1u16.to_le_bytes());
+        bytes.extend_from_slice(&[
+            SP1Encoding::Bincode as u8,
+            SP1ArtifactKind::VerifyingKey as u8,
+        ]);
+        bincode::serialize_into(&mut bytes, &test_key()).unwrap();
+        let (header, size) = SP1ArtifactHeader::from_bytes(&bytes).unwrap().unwrap();
+        assert_eq!(header.version, SP1_ARTIFACT_VERSION);
+        assert_eq!(header.schema, SP1PayloadSchema::legacy());
+        assert_eq!(size, FIXED_HEADER_SIZE);
+        assert_eq!(decode_artifact::<TestKey>(&bytes).unwrap(), test_key());
+    }
+
+    #[test]
+    fn test_artifact_schema() {
+        let current = SP1PayloadSchema::current();
+        let patch = SP1PayloadSchema {
+            sdk_version: format!("{}.999", current.sdk_version.rsplit_once('.').unwrap().0),
+            ..current.clone()
+        };
+        assert!(patch.is_compatible(&current));
+
+        let circuit = SP1PayloadSchema {
+            circuit_version: "v0.0.0".to_string(),
+            ..current.clone()
+        };
+        assert!(!circuit.is_compatible(&current));
+
+        let header = SP1ArtifactHeader {
+            version: SP1_ARTIFACT_VERSION,
+            encoding: SP1Encoding::Json,
+            kind: SP1ArtifactKind::VerifyingKey,
+            schema: circuit,
+        };
+        let mut bytes = header.to_bytes();
+        serde_json::to_writer(&mut bytes, &test_key()).unwrap();
+        let (key, schema) = decode_artifact_with_schema::<TestKey>(&bytes).unwrap();
+        assert_eq!(key, test_key());
+        assert_eq!(schema, header.schema);
+    }
+
+    #[test]
+    fn test_artifact_incompatible() {
+        let mut bytes = encode_artifact(&test_key(), SP1Encoding::Json).unwrap();
+
+        bytes[7] = SP1ArtifactKind::CoreProof as u8;
+        assert!(matches!(
+            decode_artifact::<TestKey>(&bytes),
+            Err(SP1FormatError::KindMismatch { .. })
+        ));
+
+        assert!(matches!(
+            decode_artifact::<TestKey>(&bytes[..FIXED_HEADER_SIZE + 2]),
+            Err(SP1FormatError::Truncated)
+        ));
+
+        bytes[4] = 3;
+        assert!(matches!(
+            decode_artifact::<TestKey>(&bytes),
+            Err(SP1FormatError::UnsupportedVersion(3))
+        ));
+
+        assert!(matches!(
+            decode_artifact::<TestKey>(&bytes[..6]),
+            Err(SP1FormatError::Truncated)
+        ));
+    }
+
+    #[test]
+    fn test_artifact_missing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("key.bin");
+        assert!(matches!(
+            load_artifact::<TestKey>(&path),
+            Err(SP1FormatError::Io(_))
+        ));
+
+        save_artifact(&test_key(), &path, SP1Encoding::Cbor).unwrap();
+        assert_eq!(load_artifact::<TestKey>(&path).unwrap(), test_key());
+    }
+}
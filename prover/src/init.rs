@@ -1,12 +1,17 @@
 use crate::components::SP1ProverComponents;
 use p3_baby_bear::BabyBear;
+use serde::{de::DeserializeOwned, Serialize};
+use sp1_core::air::MachineAir;
 pub use sp1_core::io::{SP1PublicValues, SP1Stdin};
 use sp1_core::stark::MachineProver;
+use sp1_core::stark::StarkGenericConfig;
+use sp1_core::stark::StarkMachine;
 use sp1_core::stark::StarkProvingKey;
 use sp1_core::stark::StarkVerifyingKey;
 use sp1_primitives::types::RecursionProgramType;
 use sp1_recursion_compiler::config::InnerConfig;
 use sp1_recursion_core::runtime::RecursionProgram;
+pub use sp1_recursion_gnark_ffi::groth16_bn254::Groth16Bn254Proof;
 pub use sp1_recursion_gnark_ffi::plonk_bn254::PlonkBn254Proof;
 pub use sp1_recursion_program::machine::ReduceProgramType;
 pub use sp1_recursion_program::machine::{
@@ -17,9 +22,38 @@ use sp1_recursion_program::machine::{
 };
 use tracing::debug_span;
 
+use crate::cache::SP1KeyCache;
 use crate::{InnerSC, OuterSC, SP1Prover};
 
 impl<C: SP1ProverComponents> SP1Prover<C> {
+    /// Loads keys from the key cache, or generates them with `setup` if the cache is disabled or
+    /// doesn't hold them. The name of the entry is only computed if the cache is enabled.
+    pub(crate) fn with_cached_keys<T: Serialize + DeserializeOwned>(
+        &self,
+        entry: impl FnOnce() -> String,
+        setup: impl FnOnce() -> T,
+    ) -> T {
+        match &self.key_cache {
+            Some(cache) => cache.get_or_insert_with(&entry(), setup),
+            None => setup(),
+        }
+    }
+
+    /// The name of the key cache entry of the keys of a recursion program, from the machine of its
+    /// prover and the compiled program.
+    fn recursion_entry<SC, A>(
+        kind: &str,
+        machine: &StarkMachine<SC, A>,
+        program: &RecursionProgram<BabyBear>,
+    ) -> String
+    where
+        SC: StarkGenericConfig<Val = BabyBear>,
+        A: MachineAir<BabyBear>,
+    {
+        let program = bincode::serialize(program).expect("failed to serialize the program");
+        SP1KeyCache::entry(kind, machine, &program)
+    }
+
     /// The program that can recursively verify a set of proofs into a single proof.
     pub fn recursion_program(&self) -> &RecursionProgram<BabyBear> {
         self.recursion_program.get_or_init(|| {
@@ -80,8 +114,18 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
     /// The proving and verifying keys for the recursion step.
     pub fn recursion_keys(&self) -> &(StarkProvingKey<InnerSC>, StarkVerifyingKey<InnerSC>) {
         self.recursion_keys.get_or_init(|| {
-            debug_span!("init recursion keys")
-                .in_scope(|| self.compress_prover.setup(self.recursion_program()))
+            debug_span!("init recursion keys").in_scope(|| {
+                self.with_cached_keys(
+                    || {
+                        Self::recursion_entry(
+                            "recursion",
+                            self.compress_prover.machine(),
+                            self.recursion_program(),
+                        )
+                    },
+                    || self.compress_prover.setup(self.recursion_program()),
+                )
+            })
         })
     }
 
@@ -98,8 +142,18 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
     /// The proving and verifying keys for the deferred step.
     pub fn deferred_keys(&self) -> &(StarkProvingKey<InnerSC>, StarkVerifyingKey<InnerSC>) {
         self.deferred_keys.get_or_init(|| {
-            debug_span!("init deferred keys")
-                .in_scope(|| self.compress_prover.setup(self.deferred_program()))
+            debug_span!("init deferred keys").in_scope(|| {
+                self.with_cached_keys(
+                    || {
+                        Self::recursion_entry(
+                            "deferred",
+                            self.compress_prover.machine(),
+                            self.deferred_program(),
+                        )
+                    },
+                    || self.compress_prover.setup(self.deferred_program()),
+                )
+            })
         })
     }
 
@@ -116,8 +170,18 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
     /// The proving and verifying keys for the compress step.
     pub fn compress_keys(&self) -> &(StarkProvingKey<InnerSC>, StarkVerifyingKey<InnerSC>) {
         self.compress_keys.get_or_init(|| {
-            debug_span!("init compress keys")
-                .in_scope(|| self.compress_prover.setup(self.compress_program()))
+            debug_span!("init compress keys").in_scope(|| {
+                self.with_cached_keys(
+                    || {
+                        Self::recursion_entry(
+                            "compress",
+                            self.compress_prover.machine(),
+                            self.compress_program(),
+                        )
+                    },
+                    || self.compress_prover.setup(self.compress_program()),
+                )
+            })
         })
     }
 
@@ -134,8 +198,18 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
     /// The proving and verifying keys for the shrink step.
     pub fn shrink_keys(&self) -> &(StarkProvingKey<InnerSC>, StarkVerifyingKey<InnerSC>) {
         self.shrink_keys.get_or_init(|| {
-            debug_span!("init shrink keys")
-                .in_scope(|| self.shrink_prover.setup(self.shrink_program()))
+            debug_span!("init shrink keys").in_scope(|| {
+                self.with_cached_keys(
+                    || {
+                        Self::recursion_entry(
+                            "shrink",
+                            self.shrink_prover.machine(),
+                            self.shrink_program(),
+                        )
+                    },
+                    || self.shrink_prover.setup(self.shrink_program()),
+                )
+            })
         })
     }
 
@@ -152,7 +226,18 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
     /// The proving and verifying keys for the wrap step.
     pub fn wrap_keys(&self) -> &(StarkProvingKey<OuterSC>, StarkVerifyingKey<OuterSC>) {
         self.wrap_keys.get_or_init(|| {
-            debug_span!("init wrap keys").in_scope(|| self.wrap_prover.setup(self.wrap_program()))
+            debug_span!("init wrap keys").in_scope(|| {
+                self.with_cached_keys(
+                    || {
+                        Self::recursion_entry(
+                            "wrap",
+                            self.wrap_prover.machine(),
+                            self.wrap_program(),
+                        )
+                    },
+                    || self.wrap_prover.setup(self.wrap_program()),
+                )
+            })
         })
     }
 
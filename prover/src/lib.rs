@@ -5,14 +5,17 @@
 //! 1. Generate shard proofs which split up and prove the valid execution of a RISC-V program.
 //! 2. Compress shard proofs into a single shard proof.
 //! 3. Wrap the shard proof into a SNARK-friendly field.
-//! 4. Wrap the last shard proof, proven over the SNARK-friendly field, into a PLONK proof.
+//! 4. Wrap the last shard proof, proven over the SNARK-friendly field, into a PLONK or Groth16
+//!    proof.
 
 #![allow(clippy::too_many_arguments)]
 #![allow(clippy::new_without_default)]
 #![allow(clippy::collapsible_else_if)]
 
 pub mod build;
+pub mod cache;
 pub mod components;
+pub mod format;
 pub mod init;
 pub mod types;
 pub mod utils;
@@ -20,10 +23,12 @@ pub mod verify;
 
 use std::borrow::Borrow;
 use std::path::Path;
-use std::sync::mpsc::sync_channel;
+use std::sync::mpsc::{sync_channel, RecvTimeoutError};
 use std::sync::{Arc, Mutex, OnceLock};
 use std::thread;
+use std::time::Duration;
 
+use cache::SP1KeyCache;
 use components::{DefaultProverComponents, SP1ProverComponents};
 use p3_baby_bear::BabyBear;
 use p3_challenger::CanObserve;
@@ -31,12 +36,18 @@ use p3_field::{AbstractField, PrimeField};
 use p3_matrix::dense::RowMajorMatrix;
 use sp1_core::air::{PublicValues, Word};
 pub use sp1_core::io::{SP1PublicValues, SP1Stdin};
-use sp1_core::runtime::{ExecutionError, ExecutionReport, Runtime, SP1Context};
+use sp1_core::runtime::{
+    ExecutionError, ExecutionReport, ExecutionSnapshot, PauseCondition, PrecompileRegistry,
+    ProgressEvent, Runtime, SP1Context,
+};
 use sp1_core::stark::MachineProver;
 use sp1_core::stark::{Challenge, StarkProvingKey};
 use sp1_core::stark::{Challenger, MachineVerificationError};
 use sp1_core::utils::concurrency::TurnBasedSync;
-use sp1_core::utils::{SP1CoreOpts, SP1ProverOpts, DIGEST_SIZE};
+use sp1_core::utils::{
+    commit_checkpoint_job, generate_checkpoint_jobs, prove_checkpoint_job, CheckpointJob,
+    CheckpointStore, SP1CoreOpts, SP1ProverOpts, ShardCommitment, DIGEST_SIZE,
+};
 use sp1_core::{
     runtime::Program,
     stark::{RiscvAir, ShardProof, StarkGenericConfig, StarkVerifyingKey, Val},
@@ -52,6 +63,8 @@ use sp1_recursion_core::{
     runtime::{RecursionProgram, Runtime as RecursionRuntime},
     stark::{config::BabyBearPoseidon2Outer, RecursionAir},
 };
+pub use sp1_recursion_gnark_ffi::groth16_bn254::Groth16Bn254Proof;
+use sp1_recursion_gnark_ffi::groth16_bn254::Groth16Bn254Prover;
 pub use sp1_recursion_gnark_ffi::plonk_bn254::PlonkBn254Proof;
 use sp1_recursion_gnark_ffi::plonk_bn254::PlonkBn254Prover;
 use sp1_recursion_program::hints::Hintable;
@@ -65,20 +78,17 @@ use utils::words_to_bytes;
 
 pub use sp1_core::SP1_CIRCUIT_VERSION;
 
-/// The configuration for the core prover.
-pub type CoreSC = BabyBearPoseidon2;
-
-/// The configuration for the inner prover.
-pub type InnerSC = BabyBearPoseidon2;
+pub use sp1_verifier::{CompressAir, CoreSC, InnerSC};
 
 /// The configuration for the outer prover.
 pub type OuterSC = BabyBearPoseidon2Outer;
 
-const COMPRESS_DEGREE: usize = 3;
 const SHRINK_DEGREE: usize = 9;
 const WRAP_DEGREE: usize = 17;
 
-pub type CompressAir<F> = RecursionAir<F, COMPRESS_DEGREE>;
+/// How often the compress tree checks for cancellation while waiting for proofs.
+const CANCELLATION_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
 pub type ShrinkAir<F> = RecursionAir<F, SHRINK_DEGREE>;
 pub type WrapAir<F> = RecursionAir<F, WRAP_DEGREE>;
 
@@ -125,6 +135,9 @@ pub struct SP1Prover<C: SP1ProverComponents = DefaultProverComponents> {
 
     /// The machine used for proving the wrapping step.
     pub wrap_prover: C::WrapProver,
+
+    /// The on-disk cache of the proving and verifying keys, if enabled.
+    pub key_cache: Option<SP1KeyCache>,
 }
 
 impl<C: SP1ProverComponents> SP1Prover<C> {
@@ -175,9 +188,16 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
             compress_prover,
             shrink_prover,
             wrap_prover,
+            key_cache: SP1KeyCache::from_env(),
         }
     }
 
+    /// Uses the given on-disk cache for the proving and verifying keys.
+    pub fn with_key_cache(mut self, key_cache: SP1KeyCache) -> Self {
+        self.key_cache = Some(key_cache);
+        self
+    }
+
     /// Fully initializes the programs, proving keys, and verifying keys that are normally
     /// lazily initialized.
     pub fn initialize(&mut self) {
@@ -196,14 +216,17 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
     /// Creates a proving key and a verifying key for a given RISC-V ELF.
     #[instrument(name = "setup", level = "debug", skip_all)]
     pub fn setup(&self, elf: &[u8]) -> (SP1ProvingKey, SP1VerifyingKey) {
-        let program = Program::from(elf);
-        let (pk, vk) = self.core_prover.setup(&program);
-        let vk = SP1VerifyingKey { vk };
-        let pk = SP1ProvingKey {
-            pk,
-            elf: elf.to_vec(),
-            vk: vk.clone(),
-        };
+        let entry = || SP1KeyCache::program_entry(self.core_prover.machine(), elf);
+        let pk = self.with_cached_keys(entry, || {
+            let program = Program::from(elf);
+            let (pk, vk) = self.core_prover.setup(&program);
+            SP1ProvingKey {
+                pk,
+                elf: elf.to_vec(),
+                vk: SP1VerifyingKey { vk },
+            }
+        });
+        let vk = pk.vk.clone();
         (pk, vk)
     }
 
@@ -224,14 +247,93 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
             runtime.write_proof(proof.clone(), vkey.clone());
         }
         runtime.run_untraced()?;
+        Self::write_profile(&runtime, elf);
         Ok((
             SP1PublicValues::from(&runtime.state.public_values_stream),
             runtime.report,
         ))
     }
 
+    /// Execute an SP1 program until the given condition is reached or the program halts.
+    #[instrument(name = "execute_until", level = "info", skip_all)]
+    pub fn execute_until<'a>(
+        &'a self,
+        elf: &[u8],
+        stdin: &SP1Stdin,
+        condition: PauseCondition,
+        mut context: SP1Context<'a>,
+    ) -> Result<SP1ExecutionStatus, ExecutionError> {
+        context.subproof_verifier.replace(Arc::new(self));
+        let program = Program::from(elf);
+        let opts = SP1CoreOpts::default();
+        let mut runtime = Runtime::with_context(program, opts, context);
+        runtime.write_vecs(&stdin.buffer);
+        for (proof, vkey) in stdin.proofs.iter() {
+            runtime.write_proof(proof.clone(), vkey.clone());
+        }
+        Self::run_until(runtime, elf, Some(condition))
+    }
+
+    /// Resume the execution of an SP1 program from a snapshot, until the given condition is
+    /// reached or, if there is none, until the program halts.
+    ///
+    /// The hooks of the context replace those the snapshot was taken with.
+    #[instrument(name = "resume", level = "info", skip_all)]
+    pub fn resume<'a>(
+        &'a self,
+        elf: &[u8],
+        snapshot: ExecutionSnapshot,
+        condition: Option<PauseCondition>,
+        mut context: SP1Context<'a>,
+    ) -> Result<SP1ExecutionStatus, ExecutionError> {
+        context.subproof_verifier.replace(Arc::new(self));
+        let program = Program::from(elf);
+        let opts = SP1CoreOpts::default();
+        let runtime = Runtime::from_snapshot(program, snapshot, opts, context);
+        Self::run_until(runtime, elf, condition)
+    }
+
+    fn run_until(
+        mut runtime: Runtime,
+        elf: &[u8],
+        condition: Option<PauseCondition>,
+    ) -> Result<SP1ExecutionStatus, ExecutionError> {
+        let halted = match condition {
+            Some(condition) => runtime.run_until(condition)?,
+            None => {
+                runtime.run_untraced()?;
+                true
+            }
+        };
+        if !halted {
+            return Ok(SP1ExecutionStatus::Paused(runtime.snapshot()));
+        }
+        Self::write_profile(&runtime, elf);
+        Ok(SP1ExecutionStatus::Halted(
+            SP1PublicValues::from(&runtime.state.public_values_stream),
+            runtime.report,
+        ))
+    }
+
+    /// Write the profile of a finished execution, if profiling was enabled.
+    fn write_profile(runtime: &Runtime, elf: &[u8]) {
+        let Some(profiler) = &runtime.profiler else {
+            return;
+        };
+        match profiler.write(elf) {
+            Ok(()) => tracing::info!("wrote profile to {}", profiler.path().display()),
+            Err(e) => tracing::error!(
+                "failed to write profile to {}: {}",
+                profiler.path().display(),
+                e
+            ),
+        }
+    }
+
     /// Generate shard proofs which split up and prove the valid execution of a RISC-V program with
     /// the core prover. Uses the provided context.
+    ///
+    /// The core machine has no extension chips, so the context must not register precompiles.
     #[instrument(name = "prove_core", level = "info", skip_all)]
     pub fn prove_core<'a>(
         &'a self,
@@ -240,10 +342,11 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
         opts: SP1ProverOpts,
         mut context: SP1Context<'a>,
     ) -> Result<SP1CoreProof, SP1CoreProverError> {
+        Self::check_no_precompiles(&context)?;
         context.subproof_verifier.replace(Arc::new(self));
         let program = Program::from(&pk.elf);
         let (proof, public_values_stream, cycles) =
-            sp1_core::utils::prove_with_context::<_, C::CoreProver>(
+            sp1_core::utils::prove_with_context::<_, _, C::CoreProver>(
                 &self.core_prover,
                 &pk.pk,
                 program,
@@ -261,6 +364,126 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
         })
     }
 
+    /// Resume a core proof from the checkpoint directory set in `context`, reusing the checkpoints
+    /// and shard proofs saved by a previous, interrupted call to [Self::prove_core].
+    #[instrument(name = "resume_core", level = "info", skip_all)]
+    pub fn resume_core<'a>(
+        &'a self,
+        pk: &SP1ProvingKey,
+        opts: SP1ProverOpts,
+        context: SP1Context<'a>,
+    ) -> Result<SP1CoreProof, SP1CoreProverError> {
+        let dir = context
+            .checkpoint_dir
+            .as_ref()
+            .ok_or(SP1CoreProverError::MissingCheckpointDir)?;
+        let stdin = CheckpointStore::read_stdin(dir).map_err(SP1CoreProverError::IoError)?;
+        self.prove_core(pk, &stdin, opts, context)
+    }
+
+    /// Execute a program and emit the checkpoint jobs of its core proof in order, so that their
+    /// shards can be proven on separate machines with [Self::commit_checkpoint] and
+    /// [Self::prove_checkpoint]. Returns the public values and the number of cycles.
+    #[instrument(name = "execute_checkpoints", level = "info", skip_all)]
+    pub fn execute_checkpoints<'a>(
+        &'a self,
+        pk: &SP1ProvingKey,
+        stdin: &SP1Stdin,
+        opts: SP1ProverOpts,
+        mut context: SP1Context<'a>,
+        emit: impl FnMut(CheckpointJob),
+    ) -> Result<(SP1PublicValues, u64), SP1CoreProverError> {
+        Self::check_no_precompiles(&context)?;
+        context.subproof_verifier.replace(Arc::new(self));
+        let program = Program::from(&pk.elf);
+        let (public_values_stream, cycles) =
+            generate_checkpoint_jobs(program, stdin, &opts.core_opts, context, emit)?;
+        Self::check_for_high_cycles(cycles);
+        Ok((SP1PublicValues::from(&public_values_stream), cycles))
+    }
+
+    /// Commit to the shards of a checkpoint job emitted by [Self::execute_checkpoints].
+    ///
+    /// Like the context the job was emitted with, the context must not register precompiles.
+    #[instrument(name = "commit_checkpoint", level = "info", skip_all)]
+    pub fn commit_checkpoint(
+        &self,
+        pk: &SP1ProvingKey,
+        job: &CheckpointJob,
+        opts: SP1ProverOpts,
+        context: SP1Context,
+    ) -> Result<Vec<ShardCommitment<CoreSC>>, SP1CoreProverError> {
+        Self::check_no_precompiles(&context)?;
+        let program = Program::from(&pk.elf);
+        Ok(commit_checkpoint_job::<_, C::CoreProver>(
+            &self.core_prover,
+            program,
+            job,
+            &opts.core_opts,
+            &PrecompileRegistry::default(),
+        ))
+    }
+
+    /// Get the challenger that the core shards are opened with, from the commitments of every
+    /// checkpoint job in order.
+    pub fn core_challenger(
+        &self,
+        pk: &SP1ProvingKey,
+        commitments: &[ShardCommitment<CoreSC>],
+    ) -> Challenger<CoreSC> {
+        sp1_core::utils::core_challenger::<_, C::CoreProver>(&self.core_prover, &pk.pk, commitments)
+    }
+
+    /// Prove the shards of a checkpoint job with the challenger from [Self::core_challenger].
+    ///
+    /// Like the context the job was emitted with, the context must not register precompiles.
+    #[instrument(name = "prove_checkpoint", level = "info", skip_all)]
+    pub fn prove_checkpoint(
+        &self,
+        pk: &SP1ProvingKey,
+        job: &CheckpointJob,
+        challenger: &Challenger<CoreSC>,
+        opts: SP1ProverOpts,
+        context: SP1Context,
+    ) -> Result<Vec<ShardProof<CoreSC>>, SP1CoreProverError> {
+        Self::check_no_precompiles(&context)?;
+        let program = Program::from(&pk.elf);
+        prove_checkpoint_job::<_, C::CoreProver>(
+            &self.core_prover,
+            &pk.pk,
+            program,
+            job,
+            challenger,
+            &opts.core_opts,
+            &PrecompileRegistry::default(),
+        )
+    }
+
+    /// Rejects a context with precompile extensions, since neither the core machine nor the
+    /// recursion programs include their chips.
+    fn check_no_precompiles(context: &SP1Context) -> Result<(), SP1CoreProverError> {
+        match context.precompiles {
+            Some(_) => Err(SP1CoreProverError::UnsupportedPrecompiles),
+            None => Ok(()),
+        }
+    }
+
+    /// Assemble the shard proofs of every checkpoint job, in order, into a core proof.
+    pub fn assemble_core_proof(
+        &self,
+        stdin: &SP1Stdin,
+        public_values: SP1PublicValues,
+        cycles: u64,
+        shard_proofs: Vec<Vec<ShardProof<CoreSC>>>,
+    ) -> SP1CoreProof {
+        SP1CoreProof {
+            proof: SP1CoreProofData(shard_proofs.into_iter().flatten().collect()),
+            stdin: stdin.clone(),
+            public_values,
+            cycles,
+        }
+    }
+
     pub fn get_recursion_core_inputs<'a>(
         &'a self,
         vk: &'a StarkVerifyingKey<CoreSC>,
@@ -389,6 +612,146 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
         inputs
     }
 
+    /// Get the challenger that the leaves of the reduction tree verify the core shard proofs with.
+    pub fn leaf_challenger(
+        &self,
+        vk: &SP1VerifyingKey,
+        shard_proofs: &[ShardProof<CoreSC>],
+    ) -> Challenger<CoreSC> {
+        let mut leaf_challenger = self.core_prover.config().challenger();
+        vk.vk.observe_into(&mut leaf_challenger);
+        shard_proofs.iter().for_each(|proof| {
+            leaf_challenger.observe(proof.commitment.main_commit);
+            leaf_challenger.observe_slice(&proof.public_values[0..self.core_prover.num_pv_elts()]);
+        });
+        leaf_challenger
+    }
+
+    /// The number of leaves in the reduction tree of the given core and deferred proofs.
+    pub fn num_leaves(
+        num_shard_proofs: usize,
+        num_deferred_proofs: usize,
+        batch_size: usize,
+    ) -> usize {
+        num_shard_proofs.div_ceil(batch_size) + num_deferred_proofs.div_ceil(batch_size)
+    }
+
+    /// Generate the inputs reducing a layer of the reduction tree, in order, into the next layer.
+    ///
+    /// If the layer fits into a single batch, the only input is the root of the tree.
+    pub fn get_compress_layer_inputs(
+        &self,
+        layer: &[(ShardProof<InnerSC>, ReduceProgramType)],
+        batch_size: usize,
+    ) -> Vec<SP1CompressMemoryLayouts<'_>> {
+        let is_complete = layer.len() <= batch_size;
+        layer
+            .chunks(batch_size)
+            .map(|batch| {
+                SP1CompressMemoryLayouts::Compress(SP1CompressMemoryLayout {
+                    compress_vk: self.compress_vk(),
+                    recursive_machine: self.compress_prover.machine(),
+                    shard_proofs: batch.iter().map(|(proof, _)| proof.clone()).collect(),
+                    kinds: batch.iter().map(|(_, kind)| *kind).collect(),
+                    is_complete,
+                })
+            })
+            .collect()
+    }
+
+    /// Prove a single step of the reduction tree, so that compression can be scheduled across
+    /// workers. The kind of the resulting proof is given by
+    /// [SP1CompressMemoryLayouts::program_type].
+    ///
+    /// The recursion programs read the number of proofs they verify from their input, so the same
+    /// programs and keys serve every arity of the tree.
+    #[instrument(name = "prove_reduce", level = "info", skip_all)]
+    pub fn prove_reduce(
+        &self,
+        input: SP1CompressMemoryLayouts,
+        opts: SP1ProverOpts,
+    ) -> Result<SP1ReduceProof<InnerSC>, SP1RecursionProverError> {
+        let proof = match input {
+            SP1CompressMemoryLayouts::Core(input) => self.compress_machine_proof(
+                input,
+                self.recursion_program(),
+                self.recursion_pk(),
+                opts,
+            )?,
+            SP1CompressMemoryLayouts::Deferred(input) => self.compress_machine_proof(
+                input,
+                self.deferred_program(),
+                self.deferred_pk(),
+                opts,
+            )?,
+            SP1CompressMemoryLayouts::Compress(input) => self.compress_machine_proof(
+                input,
+                self.compress_program(),
+                self.compress_pk(),
+                opts,
+            )?,
+        };
+        Ok(SP1ReduceProof { proof })
+    }
+
+    /// Prove the leaf with the given index of the reduction tree of the given core and deferred
+    /// proofs, so that the leaves can be proven on separate machines and then reduced with
+    /// [Self::compress_leaves].
+    #[instrument(name = "prove_leaf", level = "info", skip_all)]
+    pub fn prove_leaf(
+        &self,
+        vk: &SP1VerifyingKey,
+        shard_proofs: &[ShardProof<CoreSC>],
+        deferred_proofs: &[ShardProof<InnerSC>],
+        index: usize,
+        opts: SP1ProverOpts,
+    ) -> Result<(ShardProof<InnerSC>, ReduceProgramType), SP1RecursionProverError> {
+        let leaf_challenger = self.leaf_challenger(vk, shard_proofs);
+        let input = self
+            .get_first_layer_inputs(
+                vk,
+                &leaf_challenger,
+                shard_proofs,
+                deferred_proofs,
+                opts.reduce_batch_size(),
+            )
+            .into_iter()
+            .nth(index)
+            .expect("leaf index out of bounds");
+        let program_type = input.program_type();
+        let proof = self.prove_reduce(input, opts)?;
+        Ok((proof.proof, program_type))
+    }
+
+    /// Reduce the leaves of a reduction tree, in order, into a single shard proof.
+    #[instrument(name = "compress_leaves", level = "info", skip_all)]
+    pub fn compress_leaves(
+        &self,
+        leaves: Vec<(ShardProof<InnerSC>, ReduceProgramType)>,
+        opts: SP1ProverOpts,
+    ) -> Result<SP1ReduceProof<InnerSC>, SP1RecursionProverError> {
+        assert!(!leaves.is_empty(), "no leaves to compress");
+        assert!(
+            opts.reduce_batch_size() >= 2,
+            "reduce batch size must be at least 2"
+        );
+        let mut layer = leaves;
+        loop {
+            let inputs = self.get_compress_layer_inputs(&layer, opts.reduce_batch_size());
+            let is_complete = layer.len() <= opts.reduce_batch_size();
+            let mut next_layer = Vec::with_capacity(inputs.len());
+            for input in inputs {
+                let proof = self.prove_reduce(input, opts)?;
+                next_layer.push((proof.proof, ReduceProgramType::Reduce));
+            }
+            if is_complete {
+                let (proof, _) = next_layer.pop().unwrap();
+                return Ok(SP1ReduceProof { proof });
+            }
+            layer = next_layer;
+        }
+    }
+
     /// Reduce shards proofs to a single shard proof using the recursion prover.
     pub fn compress(
         &self,
@@ -396,18 +759,28 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
         proof: SP1CoreProof,
         deferred_proofs: Vec<ShardProof<InnerSC>>,
         opts: SP1ProverOpts,
+    ) -> Result<SP1ReduceProof<InnerSC>, SP1RecursionProverError> {
+        self.compress_with_context(vk, proof, deferred_proofs, opts, &SP1Context::default())
+    }
+
+    /// Reduce shards proofs to a single shard proof using the recursion prover, reporting the
+    /// finished layers of the tree to the progress callback of the context and stopping early once
+    /// its cancellation token is cancelled.
+    pub fn compress_with_context(
+        &self,
+        vk: &SP1VerifyingKey,
+        proof: SP1CoreProof,
+        deferred_proofs: Vec<ShardProof<InnerSC>>,
+        opts: SP1ProverOpts,
+        context: &SP1Context,
     ) -> Result<SP1ReduceProof<InnerSC>, SP1RecursionProverError> {
         // Set the batch size for the reduction tree.
-        let batch_size = 2;
+        let batch_size = opts.reduce_batch_size();
+        assert!(batch_size >= 2, "reduce batch size must be at least 2");
         let shard_proofs = &proof.proof.0;
 
         // Get the leaf challenger.
-        let mut leaf_challenger = self.core_prover.config().challenger();
-        vk.vk.observe_into(&mut leaf_challenger);
-        shard_proofs.iter().for_each(|proof| {
-            leaf_challenger.observe(proof.commitment.main_commit);
-            leaf_challenger.observe_slice(&proof.public_values[0..self.core_prover.num_pv_elts()]);
-        });
+        let leaf_challenger = self.leaf_challenger(vk, shard_proofs);
 
         // Generate the first layer inputs.
         let first_layer_inputs = self.get_first_layer_inputs(
@@ -418,16 +791,17 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
             batch_size,
         );
 
-        // Calculate the expected height of the tree.
-        let mut expected_height = 1;
+        // Calculate the number of proofs in each layer of the tree. The root reduces the last
+        // layer, which fits into a single batch.
         let num_first_layer_inputs = first_layer_inputs.len();
-        let mut num_layer_inputs = num_first_layer_inputs;
-        while num_layer_inputs > batch_size {
-            num_layer_inputs = (num_layer_inputs + 1) / 2;
-            expected_height += 1;
+        let mut layer_sizes = vec![num_first_layer_inputs];
+        while *layer_sizes.last().unwrap() > batch_size {
+            layer_sizes.push(layer_sizes.last().unwrap().div_ceil(batch_size));
         }
+        let expected_height = layer_sizes.len();
 
         // Generate the proofs.
+        let opts = &opts;
         let span = tracing::Span::current().clone();
         let proof = thread::scope(|s| {
             let _span = span.enter();
@@ -444,7 +818,9 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
                 s.spawn(move || {
                     for (index, input) in first_layer_inputs.into_iter().enumerate() {
                         input_sync.wait_for_turn(index);
-                        input_tx.lock().unwrap().send((index, 0, input)).unwrap();
+                        if !context.is_cancelled() {
+                            input_tx.lock().unwrap().send((index, 0, input)).unwrap();
+                        }
                         input_sync.advance_turn();
                     }
                 });
@@ -473,6 +849,13 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
                     loop {
                         let received = { input_rx.lock().unwrap().recv() };
                         if let Ok((index, height, input)) = received {
+                            // Once cancelled, only take our turn so the other workers can exit.
+                            if context.is_cancelled() {
+                                record_and_trace_sync.wait_for_turn(index);
+                                record_and_trace_sync.advance_turn();
+                                continue;
+                            }
+
                             // Get the program and witness stream.
                             let (program, witness_stream, program_type) = tracing::debug_span!(
                                 "write witness stream"
@@ -577,6 +960,13 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
                     loop {
                         let received = { record_and_trace_rx.lock().unwrap().recv() };
                         if let Ok((index, height, record, traces, program_type)) = received {
+                            // Once cancelled, only take our turn so the other workers can exit.
+                            if context.is_cancelled() {
+                                prover_sync.wait_for_turn(index);
+                                prover_sync.advance_turn();
+                                continue;
+                            }
+
                             tracing::debug_span!("batch").in_scope(|| {
                                 // Get the proving key.
                                 let pk = if program_type == ReduceProgramType::Core {
@@ -635,83 +1025,75 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
 
             // Spawn a worker that generates inputs for the next layer.
             let handle = {
-                let input_tx = Arc::clone(&input_tx);
+                let mut input_tx = Some(Arc::clone(&input_tx));
                 let proofs_rx = Arc::clone(&proofs_rx);
                 let span = tracing::debug_span!("generate next layer inputs");
                 s.spawn(move || {
                     let _span = span.enter();
                     let mut count = num_first_layer_inputs;
-                    let mut batch: Vec<(
-                        usize,
-                        usize,
-                        ShardProof<BabyBearPoseidon2>,
-                        ReduceProgramType,
-                    )> = Vec::new();
+                    let mut num_received = vec![0; expected_height];
+                    let mut batch: Vec<(ShardProof<BabyBearPoseidon2>, ReduceProgramType)> =
+                        Vec::new();
                     loop {
-                        let received = { proofs_rx.lock().unwrap().recv() };
-                        if let Ok((index, height, proof, program_type)) = received {
-                            batch.push((index, height, proof, program_type));
-
-                            // Compute whether we've reached the root of the tree.
-                            let is_complete = height == expected_height;
-
-                            // If it's not complete, and we haven't reached the batch size, continue.
-                            if !is_complete && batch.len() < batch_size {
-                                continue;
-                            }
-
-                            // Compute whether we're at the last input of a layer.
-                            let mut is_last = false;
-                            if let Some(first) = batch.first() {
-                                is_last = first.1 != height;
-                            }
-
-                            // If we're at the last input of a layer, we need to only include the
-                            // first input, otherwise we include all inputs.
-                            let inputs = if is_last {
-                                vec![batch[0].clone()]
-                            } else {
-                                batch.clone()
-                            };
-                            let shard_proofs = inputs
-                                .iter()
-                                .map(|(_, _, proof, _)| proof.clone())
-                                .collect();
-                            let kinds = inputs
-                                .iter()
-                                .map(|(_, _, _, program_type)| *program_type)
-                                .collect();
-                            let input =
-                                SP1CompressMemoryLayouts::Compress(SP1CompressMemoryLayout {
-                                    compress_vk: self.compress_vk(),
-                                    recursive_machine: self.compress_prover.machine(),
-                                    shard_proofs,
-                                    kinds,
-                                    is_complete,
-                                });
-
-                            input_sync.wait_for_turn(count);
-                            input_tx
+                        // Once cancelled, stop sending inputs so that the workers run dry, and
+                        // drain the proofs still in flight until they all exit.
+                        if context.is_cancelled() {
+                            input_tx = None;
+                        }
+                        let received = {
+                            proofs_rx
                                 .lock()
                                 .unwrap()
-                                .send((count, inputs[0].1 + 1, input))
-                                .unwrap();
-                            input_sync.advance_turn();
-                            count += 1;
+                                .recv_timeout(CANCELLATION_POLL_INTERVAL)
+                        };
+                        let (height, proof, program_type) = match received {
+                            Ok((_, height, proof, program_type)) => (height, proof, program_type),
+                            Err(RecvTimeoutError::Timeout) => continue,
+                            Err(RecvTimeoutError::Disconnected) => break,
+                        };
+                        let Some(input_tx) = &input_tx else {
+                            continue;
+                        };
+
+                        batch.push((proof, program_type));
+                        num_received[height] += 1;
+
+                        // Proofs arrive in order, so a layer never shares a batch with the
+                        // next one. Wait until the batch is full or the layer is finished.
+                        let is_layer_done = num_received[height] == layer_sizes[height];
+                        if is_layer_done {
+                            context.report(ProgressEvent::CompressLayerFinished {
+                                layer: height,
+                                num_layers: expected_height + 1,
+                            });
+                        }
+                        if !is_layer_done && batch.len() < batch_size {
+                            continue;
+                        }
 
-                            // If we're at the root of the tree, stop generating inputs.
-                            if is_complete {
-                                break;
-                            }
+                        // Compute whether we've reached the root of the tree.
+                        let is_complete = height + 1 == expected_height;
+
+                        let input = SP1CompressMemoryLayouts::Compress(SP1CompressMemoryLayout {
+                            compress_vk: self.compress_vk(),
+                            recursive_machine: self.compress_prover.machine(),
+                            shard_proofs: batch.iter().map(|(p, _)| p.clone()).collect(),
+                            kinds: batch.iter().map(|(_, kind)| *kind).collect(),
+                            is_complete,
+                        });
+                        batch.clear();
+
+                        input_sync.wait_for_turn(count);
+                        input_tx
+                            .lock()
+                            .unwrap()
+                            .send((count, height + 1, input))
+                            .unwrap();
+                        input_sync.advance_turn();
+                        count += 1;
 
-                            // If we were at the last input of a layer, we keep everything but the
-                            // first input. Otherwise, we empty the batch.
-                            if is_last {
-                                batch = vec![batch[1].clone()];
-                            } else {
-                                batch = Vec::new();
-                            }
-                        } else {
+                        // If we're at the root of the tree, stop generating inputs.
+                        if is_complete {
                             break;
                         }
                     }
@@ -727,9 +1109,16 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
             }
             handle.join().unwrap();
 
+            if context.is_cancelled() {
+                return Err(SP1RecursionProverError::Cancelled);
+            }
             let output = proofs_rx.lock().unwrap().recv().unwrap();
-            output.2
-        });
+            context.report(ProgressEvent::CompressLayerFinished {
+                layer: expected_height,
+                num_layers: expected_height + 1,
+            });
+            Ok(output.2)
+        })?;
 
         Ok(SP1ReduceProof { proof })
     }
@@ -920,6 +1309,35 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
         proof
     }
 
+    /// Wrap the STARK proven over a SNARK-friendly field into a Groth16 proof.
+    #[instrument(name = "wrap_groth16_bn254", level = "info", skip_all)]
+    pub fn wrap_groth16_bn254(
+        &self,
+        proof: SP1ReduceProof<OuterSC>,
+        build_dir: &Path,
+    ) -> Groth16Bn254Proof {
+        let vkey_digest = proof.sp1_vkey_digest_bn254();
+        let commited_values_digest = proof.sp1_commited_values_digest_bn254();
+
+        let mut witness = Witness::default();
+        proof.proof.write(&mut witness);
+        witness.write_commited_values_digest(commited_values_digest);
+        witness.write_vkey_hash(vkey_digest);
+
+        let prover = Groth16Bn254Prover::new();
+        let proof = prover.prove(witness, build_dir.to_path_buf());
+
+        // Verify the proof.
+        prover.verify(
+            &proof,
+            &vkey_digest.as_canonical_biguint(),
+            &commited_values_digest.as_canonical_biguint(),
+            build_dir,
+        );
+
+        proof
+    }
+
     /// Accumulate deferred proofs into a single digest.
     pub fn hash_deferred_proofs(
         prev_digest: [Val<CoreSC>; DIGEST_SIZE],
@@ -956,9 +1374,10 @@ pub mod tests {
     use super::*;
 
     use anyhow::Result;
-    use build::try_build_plonk_bn254_artifacts_dev;
+    use build::{try_build_groth16_bn254_artifacts_dev, try_build_plonk_bn254_artifacts_dev};
     use p3_field::PrimeField32;
     use sp1_core::io::SP1Stdin;
+    use sp1_core::runtime::{CancellationToken, PrecompileExtension, Syscall, SyscallContext};
 
     #[cfg(test)]
     use serial_test::serial;
@@ -972,6 +1391,7 @@ pub mod tests {
         Shrink,
         Wrap,
         Plonk,
+        Groth16,
     }
 
     pub fn test_e2e_prover<C: SP1ProverComponents>(
@@ -1048,6 +1468,24 @@ pub mod tests {
         let vk_digest_bn254 = wrapped_bn254_proof.sp1_vkey_digest_bn254();
         assert_eq!(vk_digest_bn254, vk.hash_bn254());
 
+        if test_kind == Test::Groth16 {
+            tracing::info!("generate groth16 bn254 proof");
+            let artifacts_dir =
+                try_build_groth16_bn254_artifacts_dev(prover.wrap_vk(), &wrapped_bn254_proof.proof);
+            let groth16_bn254_proof =
+                prover.wrap_groth16_bn254(wrapped_bn254_proof, &artifacts_dir);
+            println!("{:?}", groth16_bn254_proof);
+
+            prover.verify_groth16_bn254(
+                &groth16_bn254_proof,
+                &vk,
+                &public_values,
+                &artifacts_dir,
+            )?;
+
+            return Ok(());
+        }
+
         tracing::info!("generate plonk bn254 proof");
         let artifacts_dir =
             try_build_plonk_bn254_artifacts_dev(prover.wrap_vk(), &wrapped_bn254_proof.proof);
@@ -1157,6 +1595,179 @@ pub mod tests {
         test_e2e_prover::<DefaultProverComponents>(elf, opts, Test::Plonk)
     }
 
+    /// Tests an end-to-end workflow of proving a program across the entire proof generation
+    /// pipeline, wrapping the final proof into a Groth16 proof.
+    ///
+    /// Note: This test always re-builds the groth16 bn254 artifacts, so setting SP1_DEV is not
+    /// needed.
+    #[test]
+    #[serial]
+    fn test_e2e_groth16() -> Result<()> {
+        let elf = include_bytes!("../../tests/fibonacci/elf/riscv32im-succinct-zkvm-elf");
+        setup_logger();
+        let opts = SP1ProverOpts::default();
+        test_e2e_prover::<DefaultProverComponents>(elf, opts, Test::Groth16)
+    }
+
+    /// Tests that a core proof can be resumed from the checkpoint directory of a previous run.
+    #[test]
+    #[serial]
+    fn test_resume_core() -> Result<()> {
+        let elf = include_bytes!("../../tests/fibonacci/elf/riscv32im-succinct-zkvm-elf");
+        setup_logger();
+        let prover: SP1Prover = SP1Prover::new();
+        let (pk, vk) = prover.setup(elf);
+
+        let checkpoint_dir = tempfile::tempdir()?;
+        let opts = SP1ProverOpts::default();
+        let context = || {
+            SP1Context::builder()
+                .checkpoint_dir(checkpoint_dir.path())
+                .build()
+        };
+
+        let stdin = SP1Stdin::new();
+        let core_proof = prover.prove_core(&pk, &stdin, opts, context())?;
+
+        let resumed_proof = prover.resume_core(&pk, opts, context())?;
+        prover.verify(&resumed_proof.proof, &vk)?;
+        assert_eq!(resumed_proof.proof.0.len(), core_proof.proof.0.len());
+        assert_eq!(
+            resumed_proof.public_values.as_slice(),
+            core_proof.public_values.as_slice()
+        );
+
+        Ok(())
+    }
+
+    /// Tests that a core proof generated from independently proven checkpoint jobs can be
+    /// verified and compressed from independently proven leaves.
+    #[test]
+    #[serial]
+    fn test_distributed_core_and_compress() -> Result<()> {
+        let elf = include_bytes!("../../tests/fibonacci/elf/riscv32im-succinct-zkvm-elf");
+        setup_logger();
+        let prover: SP1Prover = SP1Prover::new();
+        let (pk, vk) = prover.setup(elf);
+        let opts = SP1ProverOpts::default();
+
+        let stdin = SP1Stdin::new();
+        let mut jobs = Vec::new();
+        let (public_values, cycles) =
+            prover.execute_checkpoints(&pk, &stdin, opts, SP1Context::default(), |job| {
+                jobs.push(job)
+            })?;
+
+        let commitments = jobs
+            .iter()
+            .map(|job| prover.commit_checkpoint(&pk, job, opts, SP1Context::default()))
+            .collect::<Result<Vec<_>, _>>()?
+            .concat();
+        let challenger = prover.core_challenger(&pk, &commitments);
+        let shard_proofs = jobs
+            .iter()
+            .map(|job| prover.prove_checkpoint(&pk, job, &challenger, opts, SP1Context::default()))
+            .collect::<Result<_, _>>()?;
+        let core_proof = prover.assemble_core_proof(&stdin, public_values, cycles, shard_proofs);
+        prover.verify(&core_proof.proof, &vk)?;
+
+        let shard_proofs = &core_proof.proof.0;
+        let leaves = (0..SP1Prover::<DefaultProverComponents>::num_leaves(
+            shard_proofs.len(),
+            0,
+            opts.reduce_batch_size(),
+        ))
+            .map(|index| prover.prove_leaf(&vk, shard_proofs, &[], index, opts))
+            .collect::<Result<Vec<_>, _>>()?;
+        let compressed_proof = prover.compress_leaves(leaves, opts)?;
+        prover.verify_compressed(&compressed_proof, &vk)?;
+
+        Ok(())
+    }
+
+    /// Tests that compression works with a reduction tree of a larger arity.
+    #[test]
+    #[serial]
+    fn test_compress_reduce_batch_size() -> Result<()> {
+        let elf = include_bytes!("../../tests/fibonacci/elf/riscv32im-succinct-zkvm-elf");
+        setup_logger();
+        let prover: SP1Prover = SP1Prover::new();
+        let (pk, vk) = prover.setup(elf);
+        let mut opts = SP1ProverOpts::default().with_reduce_batch_size(4);
+        opts.core_opts.shard_size = 1 << 12;
+
+        let core_proof = prover.prove_core(&pk, &SP1Stdin::new(), opts, SP1Context::default())?;
+        let compressed_proof = prover.compress(&vk, core_proof, vec![], opts)?;
+        prover.verify_compressed(&compressed_proof, &vk)?;
+
+        Ok(())
+    }
+
+    /// Tests that compression stops once cancelled from the progress callback.
+    #[test]
+    #[serial]
+    fn test_compress_cancel() -> Result<()> {
+        let elf = include_bytes!("../../tests/fibonacci/elf/riscv32im-succinct-zkvm-elf");
+        setup_logger();
+        let prover: SP1Prover = SP1Prover::new();
+        let (pk, vk) = prover.setup(elf);
+        let mut opts = SP1ProverOpts::default();
+        opts.core_opts.shard_size = 1 << 12;
+
+        let core_proof = prover.prove_core(&pk, &SP1Stdin::new(), opts, SP1Context::default())?;
+        let token = CancellationToken::new();
+        let context = SP1Context::builder()
+            .on_progress(|event| {
+                if let ProgressEvent::CompressLayerFinished { layer: 0, .. } = event {
+                    token.cancel();
+                }
+            })
+            .cancellation_token(token.clone())
+            .build();
+        let result = prover.compress_with_context(&vk, core_proof, vec![], opts, &context);
+        assert!(matches!(result, Err(SP1RecursionProverError::Cancelled)));
+
+        Ok(())
+    }
+
+    /// Tests that a context with precompile extensions is rejected, since the core machine does
+    /// not include their chips.
+    #[test]
+    #[serial]
+    fn test_prove_core_rejects_precompiles() {
+        struct Echo;
+
+        impl Syscall for Echo {
+            fn execute(&self, _: &mut SyscallContext, _: u32, _: u32) -> Option<u32> {
+                None
+            }
+        }
+
+        impl PrecompileExtension for Echo {
+            type Event = ();
+            const SYSCALL_CODE: u32 = 0x00_00_01_80;
+        }
+
+        let elf = include_bytes!("../../tests/fibonacci/elf/riscv32im-succinct-zkvm-elf");
+        setup_logger();
+        let prover: SP1Prover = SP1Prover::new();
+        let (pk, _) = prover.setup(elf);
+        let opts = SP1ProverOpts::default();
+
+        let context = SP1Context::builder().precompile(Echo).build();
+        let result = prover.prove_core(&pk, &SP1Stdin::new(), opts, context);
+        assert!(matches!(
+            result,
+            Err(SP1CoreProverError::UnsupportedPrecompiles)
+        ));
+        let context = SP1Context::builder().precompile(Echo).build();
+        let result = prover.execute_checkpoints(&pk, &SP1Stdin::new(), opts, context, |_| {});
+        assert!(matches!(
+            result,
+            Err(SP1CoreProverError::UnsupportedPrecompiles)
+        ));
+    }
+
     /// Tests an end-to-end workflow of proving a program across the entire proof generation
     /// pipeline in addition to verifying deferred proofs.
     #[test]
@@ -1,27 +1,35 @@
 use std::borrow::Borrow;
-use std::{fs::File, path::Path};
+use std::collections::HashMap;
+use std::path::Path;
 
 use anyhow::Result;
 use p3_baby_bear::BabyBear;
 use p3_bn254_fr::Bn254Fr;
 use p3_commit::{Pcs, TwoAdicMultiplicativeCoset};
 use p3_field::PrimeField;
-use p3_field::{AbstractField, PrimeField32, TwoAdicField};
+use p3_field::PrimeField32;
+use p3_matrix::{dense::RowMajorMatrix, Dimensions};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sp1_core::runtime::{ExecutionReport, ExecutionSnapshot};
 use sp1_core::stark::RiscvAir;
 use sp1_core::{
     io::{SP1PublicValues, SP1Stdin},
-    stark::{ShardProof, StarkGenericConfig, StarkProvingKey, StarkVerifyingKey},
+    stark::{
+        Com, Dom, PcsProverData, ShardProof, StarkGenericConfig, StarkProvingKey, StarkVerifyingKey,
+    },
     utils::DIGEST_SIZE,
 };
-use sp1_primitives::poseidon2_hash;
 use sp1_recursion_core::{air::RecursionPublicValues, stark::config::BabyBearPoseidon2Outer};
+use sp1_recursion_gnark_ffi::groth16_bn254::Groth16Bn254Proof;
 use sp1_recursion_gnark_ffi::plonk_bn254::PlonkBn254Proof;
 use sp1_recursion_program::machine::{
-    SP1CompressMemoryLayout, SP1DeferredMemoryLayout, SP1RecursionMemoryLayout,
+    ReduceProgramType, SP1CompressMemoryLayout, SP1DeferredMemoryLayout, SP1RecursionMemoryLayout,
 };
 use thiserror::Error;
 
+use crate::format::{
+    load_artifact, save_artifact, SP1Artifact, SP1ArtifactKind, SP1Encoding, SP1FormatError,
+};
 use crate::utils::words_to_bytes_be;
 use crate::CompressAir;
 use crate::{utils::babybear_bytes_to_bn254, words_to_bytes};
@@ -42,6 +50,146 @@ pub struct SP1VerifyingKey {
     pub vk: StarkVerifyingKey<CoreSC>,
 }
 
+impl SP1ProvingKey {
+    /// Saves the key to a path, encoded with `bincode`.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), SP1FormatError> {
+        self.save_with_encoding(path, SP1Encoding::Bincode)
+    }
+
+    /// Saves the key to a path with the given encoding.
+    pub fn save_with_encoding(
+        &self,
+        path: impl AsRef<Path>,
+        encoding: SP1Encoding,
+    ) -> Result<(), SP1FormatError> {
+        save_artifact(self, path, encoding)
+    }
+
+    /// Loads a key from a path.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, SP1FormatError> {
+        load_artifact(path)
+    }
+}
+
+impl SP1Artifact for SP1ProvingKey {
+    const NAME: &'static str = "proving key";
+
+    fn kind(&self) -> SP1ArtifactKind {
+        SP1ArtifactKind::ProvingKey
+    }
+
+    fn accepts(kind: SP1ArtifactKind) -> bool {
+        kind == SP1ArtifactKind::ProvingKey
+    }
+
+    fn decode_legacy(bytes: &[u8]) -> Result<Self, SP1FormatError> {
+        let key: LegacySP1ProvingKey = bincode::deserialize(bytes)?;
+        Ok(key.into())
+    }
+}
+
+impl SP1VerifyingKey {
+    /// Saves the key to a path, encoded with `bincode`.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), SP1FormatError> {
+        self.save_with_encoding(path, SP1Encoding::Bincode)
+    }
+
+    /// Saves the key to a path with the given encoding.
+    pub fn save_with_encoding(
+        &self,
+        path: impl AsRef<Path>,
+        encoding: SP1Encoding,
+    ) -> Result<(), SP1FormatError> {
+        save_artifact(self, path, encoding)
+    }
+
+    /// Loads a key from a path.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, SP1FormatError> {
+        load_artifact(path)
+    }
+}
+
+impl SP1Artifact for SP1VerifyingKey {
+    const NAME: &'static str = "verifying key";
+
+    fn kind(&self) -> SP1ArtifactKind {
+        SP1ArtifactKind::VerifyingKey
+    }
+
+    fn accepts(kind: SP1ArtifactKind) -> bool {
+        kind == SP1ArtifactKind::VerifyingKey
+    }
+
+    fn decode_legacy(bytes: &[u8]) -> Result<Self, SP1FormatError> {
+        let key: LegacySP1VerifyingKey = bincode::deserialize(bytes)?;
+        Ok(key.into())
+    }
+}
+
+/// The layout of [SP1ProvingKey] before the keys recorded the extension chip set.
+#[derive(Deserialize)]
+struct LegacySP1ProvingKey {
+    pk: LegacyStarkProvingKey,
+    elf: Vec<u8>,
+    vk: LegacySP1VerifyingKey,
+}
+
+#[derive(Deserialize)]
+struct LegacyStarkProvingKey {
+    commit: Com<CoreSC>,
+    pc_start: BabyBear,
+    traces: Vec<RowMajorMatrix<BabyBear>>,
+    data: PcsProverData<CoreSC>,
+    chip_ordering: HashMap<String, usize>,
+}
+
+/// The layout of [SP1VerifyingKey] before the keys recorded the extension chip set.
+#[derive(Deserialize)]
+struct LegacySP1VerifyingKey {
+    vk: LegacyStarkVerifyingKey,
+}
+
+#[derive(Deserialize)]
+struct LegacyStarkVerifyingKey {
+    commit: Com<CoreSC>,
+    pc_start: BabyBear,
+    chip_information: Vec<(String, Dom<CoreSC>, Dimensions)>,
+    chip_ordering: HashMap<String, usize>,
+}
+
+impl From<LegacySP1ProvingKey> for SP1ProvingKey {
+    fn from(key: LegacySP1ProvingKey) -> Self {
+        // Keys written before the extension chip set was recorded are for machines without
+        // extensions.
+        let pk = StarkProvingKey {
+            commit: key.pk.commit,
+            pc_start: key.pk.pc_start,
+            traces: key.pk.traces,
+            data: key.pk.data,
+            chip_ordering: key.pk.chip_ordering.into_iter().collect(),
+            extension_chip_set: Vec::new(),
+        };
+        Self {
+            pk,
+            elf: key.elf,
+            vk: key.vk.into(),
+        }
+    }
+}
+
+impl From<LegacySP1VerifyingKey> for SP1VerifyingKey {
+    fn from(key: LegacySP1VerifyingKey) -> Self {
+        let vk = StarkVerifyingKey {
+            commit: key.vk.commit,
+            pc_start: key.vk.pc_start,
+            chip_information: key.vk.chip_information,
+            chip_ordering: key.vk.chip_ordering.into_iter().collect(),
+            extension_chip_set: Vec::new(),
+        };
+        Self { vk }
+    }
+}
+
 /// A trait for keys that can be hashed into a digest.
 pub trait HashableKey {
     /// Hash the key into a digest of BabyBear elements.
@@ -84,21 +232,7 @@ where
     <SC::Pcs as Pcs<SC::Challenge, SC::Challenger>>::Commitment: AsRef<[BabyBear; DIGEST_SIZE]>,
 {
     fn hash_babybear(&self) -> [BabyBear; DIGEST_SIZE] {
-        let prep_domains = self.chip_information.iter().map(|(_, domain, _)| domain);
-        let num_inputs = DIGEST_SIZE + 1 + (4 * prep_domains.len());
-        let mut inputs = Vec::with_capacity(num_inputs);
-        inputs.extend(self.commit.as_ref());
-        inputs.push(self.pc_start);
-        for domain in prep_domains {
-            inputs.push(BabyBear::from_canonical_usize(domain.log_n));
-            let size = 1 << domain.log_n;
-            inputs.push(BabyBear::from_canonical_usize(size));
-            let g = BabyBear::two_adic_generator(domain.log_n);
-            inputs.push(domain.shift);
-            inputs.push(g);
-        }
-
-        poseidon2_hash(inputs)
+        sp1_verifier::vk_digest(self)
     }
 
     fn hash_u32(&self) -> [u32; 8] {
@@ -122,15 +256,32 @@ pub struct SP1ProofWithMetadata<P: Clone> {
     pub cycles: u64,
 }
 
-impl<P: Serialize + DeserializeOwned + Clone> SP1ProofWithMetadata<P> {
+impl<P: SP1ProofData> SP1ProofWithMetadata<P> {
+    /// Saves the proof to a path, encoded with `bincode`.
     pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
-        bincode::serialize_into(File::create(path).expect("failed to open file"), self)
-            .map_err(Into::into)
+        self.save_with_encoding(path, SP1Encoding::Bincode)
     }
 
+    /// Saves the proof to a path with the given encoding.
+    pub fn save_with_encoding(&self, path: impl AsRef<Path>, encoding: SP1Encoding) -> Result<()> {
+        save_artifact(self, path, encoding).map_err(Into::into)
+    }
+
+    /// Loads a proof from a path.
     pub fn load(path: impl AsRef<Path>) -> Result<Self> {
-        bincode::deserialize_from(File::open(path).expect("failed to open file"))
-            .map_err(Into::into)
+        load_artifact(path).map_err(Into::into)
+    }
+}
+
+impl<P: SP1ProofData> SP1Artifact for SP1ProofWithMetadata<P> {
+    const NAME: &'static str = P::NAME;
+
+    fn kind(&self) -> SP1ArtifactKind {
+        P::KIND
+    }
+
+    fn accepts(kind: SP1ArtifactKind) -> bool {
+        kind == P::KIND
     }
 }
 
@@ -155,6 +306,18 @@ pub type SP1PlonkBn254Proof = SP1ProofWithMetadata<SP1PlonkBn254ProofData>;
 /// An SP1 proof that has been wrapped into a single PLONK proof and can be verified onchain.
 pub type SP1PlonkProof = SP1ProofWithMetadata<SP1PlonkProofData>;
 
+/// An SP1 proof that has been wrapped into a single Groth16 proof and can be verified onchain.
+pub type SP1Groth16Bn254Proof = SP1ProofWithMetadata<SP1Groth16Bn254ProofData>;
+
+/// The proof held by a [SP1ProofWithMetadata].
+pub trait SP1ProofData: Serialize + DeserializeOwned + Clone {
+    /// A description of the proof, used in errors.
+    const NAME: &'static str;
+
+    /// The kind of the proof in the container format.
+    const KIND: SP1ArtifactKind;
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct SP1CoreProofData(pub Vec<ShardProof<CoreSC>>);
 #[derive(Serialize, Deserialize, Clone)]
@@ -166,6 +329,34 @@ pub struct SP1PlonkBn254ProofData(pub PlonkBn254Proof);
 #[derive(Serialize, Deserialize, Clone)]
 pub struct SP1PlonkProofData(pub PlonkBn254Proof);
 
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SP1Groth16Bn254ProofData(pub Groth16Bn254Proof);
+
+impl SP1ProofData for SP1CoreProofData {
+    const NAME: &'static str = "core proof";
+    const KIND: SP1ArtifactKind = SP1ArtifactKind::CoreProof;
+}
+
+impl SP1ProofData for SP1ReducedProofData {
+    const NAME: &'static str = "compressed proof";
+    const KIND: SP1ArtifactKind = SP1ArtifactKind::CompressedProof;
+}
+
+impl SP1ProofData for SP1PlonkBn254ProofData {
+    const NAME: &'static str = "plonk proof";
+    const KIND: SP1ArtifactKind = SP1ArtifactKind::PlonkProof;
+}
+
+impl SP1ProofData for SP1PlonkProofData {
+    const NAME: &'static str = "plonk proof";
+    const KIND: SP1ArtifactKind = SP1ArtifactKind::PlonkProof;
+}
+
+impl SP1ProofData for SP1Groth16Bn254ProofData {
+    const NAME: &'static str = "groth16 proof";
+    const KIND: SP1ArtifactKind = SP1ArtifactKind::Groth16Proof;
+}
+
 /// An intermediate proof which proves the execution over a range of shards.
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(bound(serialize = "ShardProof<SC>: Serialize"))]
@@ -203,10 +394,23 @@ pub enum SP1ReduceProofWrapper {
     Recursive(SP1ReduceProof<InnerSC>),
 }
 
+/// The outcome of an execution which may pause before the program halts.
+#[derive(Debug, Clone)]
+#[allow(clippy::large_enum_variant)]
+pub enum SP1ExecutionStatus {
+    /// Execution reached its pause condition. The snapshot can be saved, resumed, or forked.
+    Paused(ExecutionSnapshot),
+
+    /// The program halted, with the given public values and execution report.
+    Halted(SP1PublicValues, ExecutionReport),
+}
+
 #[derive(Error, Debug)]
 pub enum SP1RecursionProverError {
     #[error("Runtime error: {0}")]
     RuntimeError(String),
+    #[error("proving was cancelled")]
+    Cancelled,
 }
 
 #[allow(clippy::large_enum_variant)]
@@ -215,3 +419,41 @@ pub enum SP1CompressMemoryLayouts<'a> {
     Deferred(SP1DeferredMemoryLayout<'a, InnerSC, CompressAir<BabyBear>>),
     Compress(SP1CompressMemoryLayout<'a, InnerSC, CompressAir<BabyBear>>),
 }
+
+impl<'a> SP1CompressMemoryLayouts<'a> {
+    /// The kind of program that proves this input.
+    pub fn program_type(&self) -> ReduceProgramType {
+        match self {
+            SP1CompressMemoryLayouts::Core(_) => ReduceProgramType::Core,
+            SP1CompressMemoryLayouts::Deferred(_) => ReduceProgramType::Deferred,
+            SP1CompressMemoryLayouts::Compress(_) => ReduceProgramType::Reduce,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sp1_core::runtime::Program;
+
+    use super::*;
+    use crate::format::decode_artifact;
+
+    #[test]
+    fn test_legacy_verifying_key() {
+        let elf = include_bytes!("../../tests/fibonacci/elf/riscv32im-succinct-zkvm-elf");
+        let machine = RiscvAir::machine(CoreSC::default());
+        let (_, vk) = machine.setup(&Program::from(elf));
+
+        // Keys written before the extension chip set was recorded end after the chip ordering.
+        let bytes = bincode::serialize(&(
+            &vk.commit,
+            vk.pc_start,
+            &vk.chip_information,
+            &vk.chip_ordering,
+        ))
+        .unwrap();
+        let key = decode_artifact::<SP1VerifyingKey>(&bytes).unwrap();
+        assert!(key.vk.extension_chip_set.is_empty());
+        assert_eq!(key.vk.hash_u32(), vk.hash_u32());
+    }
+}
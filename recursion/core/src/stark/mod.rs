@@ -1,4 +1,6 @@
+#[cfg(feature = "prover")]
 pub mod config;
+#[cfg(feature = "prover")]
 pub mod poseidon2;
 pub mod utils;
 
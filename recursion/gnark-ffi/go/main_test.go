@@ -4,6 +4,10 @@ import (
 	"testing"
 )
 
-func TestCircuit(t *testing.T) {
-	TestMain()
+func TestPlonkCircuit(t *testing.T) {
+	TestPlonkMain()
+}
+
+func TestGroth16Circuit(t *testing.T) {
+	TestGroth16Main()
 }
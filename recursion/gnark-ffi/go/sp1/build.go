@@ -1,6 +1,7 @@
 package sp1
 
 import (
+	"bufio"
 	"encoding/json"
 	"fmt"
 	"log"
@@ -9,14 +10,16 @@ import (
 
 	"github.com/consensys/gnark-crypto/ecc"
 	"github.com/consensys/gnark-crypto/kzg"
+	"github.com/consensys/gnark/backend/groth16"
 	"github.com/consensys/gnark/backend/plonk"
 	"github.com/consensys/gnark/frontend"
+	"github.com/consensys/gnark/frontend/cs/r1cs"
 	"github.com/consensys/gnark/frontend/cs/scs"
 	"github.com/consensys/gnark/test/unsafekzg"
 	"github.com/succinctlabs/sp1-recursion-gnark/sp1/trusted_setup"
 )
 
-func Build(dataDir string) {
+func BuildPlonk(dataDir string) {
 	// Set the enviroment variable for the constraints file.
 	//
 	// TODO: There might be some non-determinism if a single process is running this command
@@ -152,7 +155,7 @@ func Build(dataDir string) {
 	os.MkdirAll(dataDir, 0755)
 
 	// Write the solidity verifier.
-	solidityVerifierFile, err := os.Create(dataDir + "/" + verifierContractPath)
+	solidityVerifierFile, err := os.Create(dataDir + "/" + plonkVerifierContractPath)
 	if err != nil {
 		panic(err)
 	}
@@ -192,3 +195,149 @@ func Build(dataDir string) {
 		panic(err)
 	}
 }
+
+func BuildGroth16(dataDir string) {
+	// Set the enviroment variable for the constraints file.
+	//
+	// TODO: There might be some non-determinism if a single process is running this command
+	// multiple times.
+	os.Setenv("CONSTRAINTS_JSON", dataDir+"/"+constraintsJsonFile)
+
+	// Read the file.
+	witnessInputPath := dataDir + "/witness.json"
+	data, err := os.ReadFile(witnessInputPath)
+	if err != nil {
+		panic(err)
+	}
+
+	// Deserialize the JSON data into a slice of Instruction structs
+	var witnessInput WitnessInput
+	err = json.Unmarshal(data, &witnessInput)
+	if err != nil {
+		panic(err)
+	}
+
+	// Initialize the circuit.
+	circuit := NewCircuit(witnessInput)
+
+	// Compile the circuit.
+	r1cs, err := frontend.Compile(ecc.BN254.ScalarField(), r1cs.NewBuilder, &circuit)
+	if err != nil {
+		panic(err)
+	}
+
+	// Generate the proving and verifying key.
+	var pk groth16.ProvingKey
+	var vk groth16.VerifyingKey
+	if strings.Contains(dataDir, "dev") {
+		// A local single-party setup knows its toxic waste, so it is only fit for development.
+		pk, vk, err = groth16.Setup(r1cs)
+		if err != nil {
+			panic(err)
+		}
+	} else {
+		// Release builds use the keys from the phase-2 ceremony for this circuit. A key that does
+		// not match the circuit fails the proof check below.
+		pk, vk = readGroth16CeremonyKeys()
+	}
+
+	// Generate proof.
+	assignment := NewCircuit(witnessInput)
+	witness, err := frontend.NewWitness(&assignment, ecc.BN254.ScalarField())
+	if err != nil {
+		panic(err)
+	}
+	proof, err := groth16.Prove(r1cs, pk, witness)
+	if err != nil {
+		panic(err)
+	}
+
+	// Verify proof.
+	publicWitness, err := witness.Public()
+	if err != nil {
+		panic(err)
+	}
+	err = groth16.Verify(proof, vk, publicWitness)
+	if err != nil {
+		panic(err)
+	}
+
+	// Create the build directory.
+	os.MkdirAll(dataDir, 0755)
+
+	// Write the solidity verifier.
+	solidityVerifierFile, err := os.Create(dataDir + "/" + groth16VerifierContractPath)
+	if err != nil {
+		panic(err)
+	}
+	vk.ExportSolidity(solidityVerifierFile)
+	defer solidityVerifierFile.Close()
+
+	// Write the R1CS.
+	r1csFile, err := os.Create(dataDir + "/" + circuitPath)
+	if err != nil {
+		panic(err)
+	}
+	defer r1csFile.Close()
+	_, err = r1cs.WriteTo(r1csFile)
+	if err != nil {
+		panic(err)
+	}
+
+	// Write the verifier key.
+	vkFile, err := os.Create(dataDir + "/" + vkPath)
+	if err != nil {
+		panic(err)
+	}
+	defer vkFile.Close()
+	_, err = vk.WriteTo(vkFile)
+	if err != nil {
+		panic(err)
+	}
+
+	// Write the proving key.
+	pkFile, err := os.Create(dataDir + "/" + pkPath)
+	if err != nil {
+		panic(err)
+	}
+	defer pkFile.Close()
+	_, err = pk.WriteTo(pkFile)
+	if err != nil {
+		panic(err)
+	}
+}
+
+// readGroth16CeremonyKeys reads the proving and verifying key produced by the phase-2 ceremony
+// from the directory in GROTH16_CEREMONY_DIR.
+func readGroth16CeremonyKeys() (groth16.ProvingKey, groth16.VerifyingKey) {
+	ceremonyDir := os.Getenv("GROTH16_CEREMONY_DIR")
+	if ceremonyDir == "" {
+		panic("GROTH16_CEREMONY_DIR must point to the phase-2 ceremony keys for a release build")
+	}
+
+	// Read the proving key.
+	pkFile, err := os.Open(ceremonyDir + "/" + pkPath)
+	if err != nil {
+		panic(err)
+	}
+	defer pkFile.Close()
+	pk := groth16.NewProvingKey(ecc.BN254)
+	_, err = pk.ReadFrom(bufio.NewReaderSize(pkFile, 1024*1024))
+	if err != nil {
+		panic(err)
+	}
+
+	// Read the verifier key.
+	vkFile, err := os.Open(ceremonyDir + "/" + vkPath)
+	if err != nil {
+		panic(err)
+	}
+	defer vkFile.Close()
+	vk := groth16.NewVerifyingKey(ecc.BN254)
+	_, err = vk.ReadFrom(vkFile)
+	if err != nil {
+		panic(err)
+	}
+
+	return pk, vk
+}
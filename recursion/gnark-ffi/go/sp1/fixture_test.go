@@ -0,0 +1,100 @@
+package sp1
+
+import (
+	"encoding/hex"
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/consensys/gnark-crypto/ecc"
+	"github.com/consensys/gnark/backend/plonk"
+	plonk_bn254 "github.com/consensys/gnark/backend/plonk/bn254"
+	"github.com/consensys/gnark/frontend"
+	"github.com/consensys/gnark/frontend/cs/scs"
+	"github.com/consensys/gnark/test/unsafekzg"
+)
+
+// The directory of the fixture that the native Plonk verifier in sp1-verifier is tested against.
+const plonkFixtureDir = "../../../../verifier/src/plonk/testdata"
+
+// A circuit with two public inputs, like the SP1 circuit, and a BSB22 commitment, so that the
+// fixture exercises every step of the verifier.
+type PlonkFixtureCircuit struct {
+	VkeyHash             frontend.Variable `gnark:",public"`
+	CommitedValuesDigest frontend.Variable `gnark:",public"`
+	X                    frontend.Variable
+}
+
+func (circuit *PlonkFixtureCircuit) Define(api frontend.API) error {
+	api.AssertIsEqual(api.Mul(circuit.X, circuit.X), circuit.VkeyHash)
+
+	committer, ok := api.(frontend.Committer)
+	if !ok {
+		panic("the builder does not support commitments")
+	}
+	commitment, err := committer.Commit(circuit.X, circuit.CommitedValuesDigest)
+	if err != nil {
+		return err
+	}
+	api.AssertIsDifferent(commitment, circuit.X)
+	return nil
+}
+
+// Proves the fixture circuit and writes the verifying key, the Solidity encoding of the proof and
+// the public inputs to the fixture directory. Set SP1_WRITE_PLONK_FIXTURE=1 to regenerate them.
+func TestPlonkFixture(t *testing.T) {
+	if os.Getenv("SP1_WRITE_PLONK_FIXTURE") != "1" {
+		t.Skip("set SP1_WRITE_PLONK_FIXTURE=1 to write the plonk fixture")
+	}
+
+	ccs, err := frontend.Compile(ecc.BN254.ScalarField(), scs.NewBuilder, &PlonkFixtureCircuit{})
+	if err != nil {
+		t.Fatal(err)
+	}
+	srs, srsLagrange, err := unsafekzg.NewSRS(ccs)
+	if err != nil {
+		t.Fatal(err)
+	}
+	pk, vk, err := plonk.Setup(ccs, srs, srsLagrange)
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	assignment := PlonkFixtureCircuit{VkeyHash: 9, CommitedValuesDigest: 5, X: 3}
+	witness, err := frontend.NewWitness(&assignment, ecc.BN254.ScalarField())
+	if err != nil {
+		t.Fatal(err)
+	}
+	publicWitness, err := witness.Public()
+	if err != nil {
+		t.Fatal(err)
+	}
+	proof, err := plonk.Prove(ccs, pk, witness)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if err := plonk.Verify(proof, vk, publicWitness); err != nil {
+		t.Fatal(err)
+	}
+
+	if err := os.MkdirAll(plonkFixtureDir, 0755); err != nil {
+		t.Fatal(err)
+	}
+	vkFile, err := os.Create(filepath.Join(plonkFixtureDir, vkPath))
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer vkFile.Close()
+	if _, err := vk.WriteTo(vkFile); err != nil {
+		t.Fatal(err)
+	}
+	encodedProof := proof.(*plonk_bn254.Proof).MarshalSolidity()
+	err = os.WriteFile(filepath.Join(plonkFixtureDir, "proof.hex"), []byte(hex.EncodeToString(encodedProof)), 0644)
+	if err != nil {
+		t.Fatal(err)
+	}
+	err = os.WriteFile(filepath.Join(plonkFixtureDir, "public_inputs.txt"), []byte("9\n5\n"), 0644)
+	if err != nil {
+		t.Fatal(err)
+	}
+}
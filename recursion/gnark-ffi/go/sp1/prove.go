@@ -6,11 +6,12 @@ import (
 	"os"
 
 	"github.com/consensys/gnark-crypto/ecc"
+	"github.com/consensys/gnark/backend/groth16"
 	"github.com/consensys/gnark/backend/plonk"
 	"github.com/consensys/gnark/frontend"
 )
 
-func Prove(dataDir string, witnessPath string) Proof {
+func ProvePlonk(dataDir string, witnessPath string) Proof {
 	// Sanity check the required arguments have been provided.
 	if dataDir == "" {
 		panic("dataDirStr is required")
@@ -83,3 +84,77 @@ func Prove(dataDir string, witnessPath string) Proof {
 
 	return NewSP1PlonkBn254Proof(&proof, witnessInput)
 }
+
+func ProveGroth16(dataDir string, witnessPath string) Proof {
+	// Sanity check the required arguments have been provided.
+	if dataDir == "" {
+		panic("dataDirStr is required")
+	}
+	os.Setenv("CONSTRAINTS_JSON", dataDir+"/"+constraintsJsonFile)
+
+	// Read the R1CS.
+	r1csFile, err := os.Open(dataDir + "/" + circuitPath)
+	if err != nil {
+		panic(err)
+	}
+	r1cs := groth16.NewCS(ecc.BN254)
+	r1cs.ReadFrom(r1csFile)
+	defer r1csFile.Close()
+
+	// Read the proving key.
+	pkFile, err := os.Open(dataDir + "/" + pkPath)
+	if err != nil {
+		panic(err)
+	}
+	pk := groth16.NewProvingKey(ecc.BN254)
+	bufReader := bufio.NewReaderSize(pkFile, 1024*1024)
+	pk.UnsafeReadFrom(bufReader)
+	defer pkFile.Close()
+
+	// Read the verifier key.
+	vkFile, err := os.Open(dataDir + "/" + vkPath)
+	if err != nil {
+		panic(err)
+	}
+	vk := groth16.NewVerifyingKey(ecc.BN254)
+	vk.ReadFrom(vkFile)
+	defer vkFile.Close()
+
+	// Read the file.
+	data, err := os.ReadFile(witnessPath)
+	if err != nil {
+		panic(err)
+	}
+
+	// Deserialize the JSON data into a slice of Instruction structs
+	var witnessInput WitnessInput
+	err = json.Unmarshal(data, &witnessInput)
+	if err != nil {
+		panic(err)
+	}
+
+	// Generate the witness.
+	assignment := NewCircuit(witnessInput)
+	witness, err := frontend.NewWitness(&assignment, ecc.BN254.ScalarField())
+	if err != nil {
+		panic(err)
+	}
+	publicWitness, err := witness.Public()
+	if err != nil {
+		panic(err)
+	}
+
+	// Generate the proof.
+	proof, err := groth16.Prove(r1cs, pk, witness)
+	if err != nil {
+		panic(err)
+	}
+
+	// Verify proof.
+	err = groth16.Verify(proof, vk, publicWitness)
+	if err != nil {
+		panic(err)
+	}
+
+	return NewSP1Groth16Bn254Proof(&proof, witnessInput)
+}
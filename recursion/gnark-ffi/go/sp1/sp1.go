@@ -14,7 +14,8 @@ import (
 var srsFile string = "srs.bin"
 var srsLagrangeFile string = "srs_lagrange.bin"
 var constraintsJsonFile string = "constraints.json"
-var verifierContractPath string = "PlonkVerifier.sol"
+var plonkVerifierContractPath string = "PlonkVerifier.sol"
+var groth16VerifierContractPath string = "Groth16Verifier.sol"
 var circuitPath string = "circuit.bin"
 var vkPath string = "vk.bin"
 var pkPath string = "pk.bin"
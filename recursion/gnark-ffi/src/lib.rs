@@ -2,8 +2,10 @@ mod babybear;
 
 pub mod ffi;
 
+pub mod groth16_bn254;
 pub mod plonk_bn254;
 pub mod witness;
 
+pub use groth16_bn254::*;
 pub use plonk_bn254::*;
 pub use witness::*;
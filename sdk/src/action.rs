@@ -1,11 +1,17 @@
 use sp1_core::{
-    runtime::{ExecutionReport, HookEnv, SP1ContextBuilder},
+    runtime::{
+        CancellationToken, ExecutionReport, ExecutionSnapshot, HookEnv, PauseCondition,
+        ProgressEvent, SP1ContextBuilder,
+    },
     utils::{SP1CoreOpts, SP1ProverOpts},
 };
-use sp1_prover::{components::DefaultProverComponents, SP1ProvingKey, SP1PublicValues, SP1Stdin};
+use sp1_prover::{
+    components::DefaultProverComponents, SP1ExecutionStatus, SP1ProvingKey, SP1PublicValues,
+    SP1Stdin,
+};
 
 use anyhow::{Ok, Result};
-use std::time::Duration;
+use std::{path::PathBuf, time::Duration};
 
 use crate::{provers::ProofOpts, Prover, SP1ProofKind, SP1ProofWithPublicValues};
 
@@ -16,6 +22,7 @@ pub struct Execute<'a> {
     context_builder: SP1ContextBuilder<'a>,
     elf: &'a [u8],
     stdin: SP1Stdin,
+    snapshot: Option<ExecutionSnapshot>,
 }
 
 impl<'a> Execute<'a> {
@@ -33,6 +40,22 @@ impl<'a> Execute<'a> {
             elf,
             stdin,
             context_builder: Default::default(),
+            snapshot: None,
+        }
+    }
+
+    /// Prepare to resume the execution of the given program from a snapshot.
+    ///
+    /// Prefer using [ProverClient::resume](super::ProverClient::resume).
+    /// See there for more documentation.
+    pub fn resume(
+        prover: &'a dyn Prover<DefaultProverComponents>,
+        elf: &'a [u8],
+        snapshot: ExecutionSnapshot,
+    ) -> Self {
+        Self {
+            snapshot: Some(snapshot),
+            ..Self::new(prover, elf, SP1Stdin::new())
         }
     }
 
@@ -43,9 +66,43 @@ impl<'a> Execute<'a> {
             elf,
             stdin,
             mut context_builder,
+            snapshot,
+        } = self;
+        let context = context_builder.build();
+        let Some(snapshot) = snapshot else {
+            return Ok(prover.sp1_prover().execute(elf, &stdin, context)?);
+        };
+        match prover.sp1_prover().resume(elf, snapshot, None, context)? {
+            SP1ExecutionStatus::Halted(public_values, report) => Ok((public_values, report)),
+            SP1ExecutionStatus::Paused(_) => unreachable!("execution without a pause condition"),
+        }
+    }
+
+    /// Execute the program until the given condition is reached or the program halts, consuming
+    /// the built action `self`.
+    ///
+    /// A paused execution returns a snapshot, which can be serialized and later resumed with
+    /// [ProverClient::resume](super::ProverClient::resume).
+    pub fn run_until(self, condition: PauseCondition) -> Result<SP1ExecutionStatus> {
+        let Self {
+            prover,
+            elf,
+            stdin,
+            mut context_builder,
+            snapshot,
         } = self;
         let context = context_builder.build();
-        Ok(prover.sp1_prover().execute(elf, &stdin, context)?)
+        let status = match snapshot {
+            Some(snapshot) => {
+                prover
+                    .sp1_prover()
+                    .resume(elf, snapshot, Some(condition), context)?
+            }
+            None => prover
+                .sp1_prover()
+                .execute_until(elf, &stdin, condition, context)?,
+        };
+        Ok(status)
     }
 
     /// Add a runtime [Hook](super::Hook) into the context.
@@ -78,6 +135,17 @@ impl<'a> Execute<'a> {
         self.context_builder.max_cycles(max_cycles);
         self
     }
+
+    /// Profile the call stacks of the program and write the profile to the given path once it
+    /// halts.
+    ///
+    /// The profile is written as folded stacks, which can be rendered as a flamegraph with
+    /// `inferno-flamegraph`, or as a [speedscope](https://www.speedscope.app) profile if the path
+    /// has a `.json` extension.
+    pub fn with_profiler(mut self, path: impl Into<PathBuf>) -> Self {
+        self.context_builder.profiler(path);
+        self
+    }
 }
 
 /// Builder to prepare and configure proving execution of a program on an input.
@@ -90,6 +158,7 @@ pub struct Prove<'a> {
     stdin: SP1Stdin,
     core_opts: SP1CoreOpts,
     recursion_opts: SP1CoreOpts,
+    reduce_batch_size: usize,
     timeout: Option<Duration>,
 }
 
@@ -111,6 +180,7 @@ impl<'a> Prove<'a> {
             context_builder: Default::default(),
             core_opts: SP1CoreOpts::default(),
             recursion_opts: SP1CoreOpts::recursion(),
+            reduce_batch_size: SP1ProverOpts::default().reduce_batch_size(),
             timeout: None,
         }
     }
@@ -125,12 +195,12 @@ impl<'a> Prove<'a> {
             mut context_builder,
             core_opts,
             recursion_opts,
+            reduce_batch_size,
             timeout,
         } = self;
-        let opts = SP1ProverOpts {
-            core_opts,
-            recursion_opts,
-        };
+        let mut opts = SP1ProverOpts::default().with_reduce_batch_size(reduce_batch_size);
+        opts.core_opts = core_opts;
+        opts.recursion_opts = recursion_opts;
         let proof_opts = ProofOpts {
             sp1_prover_opts: opts,
             timeout,
@@ -158,6 +228,12 @@ impl<'a> Prove<'a> {
         self
     }
 
+    /// Set the proof mode to the groth16 bn254 mode.
+    pub fn groth16(mut self) -> Self {
+        self.kind = SP1ProofKind::Groth16;
+        self
+    }
+
     /// Add a runtime [Hook](super::Hook) into the context.
     ///
     /// Hooks may be invoked from within SP1 by writing to the specified file descriptor `fd`
@@ -193,12 +269,27 @@ impl<'a> Prove<'a> {
         self
     }
 
+    /// Set the number of proofs verified by each recursive proof when compressing.
+    pub fn reduce_batch_size(mut self, value: usize) -> Self {
+        self.reduce_batch_size = value;
+        self
+    }
+
     /// Set whether we should reconstruct commitments while proving.
     pub fn reconstruct_commitments(mut self, value: bool) -> Self {
         self.core_opts.reconstruct_commitments = value;
         self
     }
 
+    /// Set a directory in which to keep execution checkpoints and finished shard proofs.
+    ///
+    /// If proving is interrupted, running it again with the same directory resumes from the last
+    /// completed checkpoint.
+    pub fn checkpoint_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.context_builder.checkpoint_dir(dir);
+        self
+    }
+
     /// Set the maximum number of cpu cycles to use for execution.
     ///
     /// If the cycle limit is exceeded, execution will return [sp1_core::runtime::ExecutionError::ExceededCycleLimit].
@@ -214,4 +305,22 @@ impl<'a> Prove<'a> {
         self.timeout = Some(timeout);
         self
     }
+
+    /// Set a callback notified as the proof makes progress.
+    ///
+    /// This parameter is only used when the prover is run locally.
+    pub fn on_progress(mut self, f: impl Fn(ProgressEvent) + Send + Sync + 'a) -> Self {
+        self.context_builder.on_progress(f);
+        self
+    }
+
+    /// Set a token which stops the proof's generation once cancelled.
+    ///
+    /// A cancelled proof returns [sp1_core::utils::SP1CoreProverError::Cancelled] or
+    /// [sp1_prover::SP1RecursionProverError::Cancelled], depending on the stage it was stopped
+    /// in. This parameter is only used when the prover is run locally.
+    pub fn cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.context_builder.cancellation_token(token);
+        self
+    }
 }
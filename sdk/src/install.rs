@@ -3,13 +3,20 @@ use std::{cmp::min, io::Write, path::PathBuf, process::Command};
 use futures::{Future, StreamExt};
 use indicatif::{ProgressBar, ProgressStyle};
 use reqwest::Client;
-use tokio::{runtime, task::block_in_place};
+use tokio::{
+    runtime::{self, RuntimeFlavor},
+    task::block_in_place,
+};
 
 use crate::SP1_CIRCUIT_VERSION;
 
 /// The base URL for the S3 bucket containing the plonk bn254 artifacts.
 pub const PLONK_BN254_ARTIFACTS_URL_BASE: &str = "https://sp1-circuits.s3-us-east-2.amazonaws.com";
 
+/// The base URL for the S3 bucket containing the groth16 bn254 artifacts.
+pub const GROTH16_BN254_ARTIFACTS_URL_BASE: &str =
+    "https://sp1-circuits.s3-us-east-2.amazonaws.com";
+
 /// Gets the directory where the PLONK artifacts are installed.
 fn plonk_bn254_artifacts_dir() -> PathBuf {
     dirs::home_dir()
@@ -40,19 +47,66 @@ pub fn try_install_plonk_bn254_artifacts() -> PathBuf {
     build_dir
 }
 
+/// Gets the directory where the Groth16 artifacts are installed.
+fn groth16_bn254_artifacts_dir() -> PathBuf {
+    dirs::home_dir()
+        .unwrap()
+        .join(".sp1")
+        .join("circuits")
+        .join("groth16_bn254")
+        .join(SP1_CIRCUIT_VERSION)
+}
+
+/// Tries to install the Groth16 artifacts if they are not already installed.
+pub fn try_install_groth16_bn254_artifacts() -> PathBuf {
+    let build_dir = groth16_bn254_artifacts_dir();
+
+    if build_dir.exists() {
+        println!(
+            "[sp1] groth16 bn254 artifacts already seem to exist at {}. if you want to re-download them, delete the directory",
+            build_dir.display()
+        );
+    } else {
+        println!(
+            "[sp1] groth16 bn254 artifacts for version {} do not exist at {}. downloading...",
+            SP1_CIRCUIT_VERSION,
+            build_dir.display()
+        );
+        install_groth16_bn254_artifacts(build_dir.clone());
+    }
+    build_dir
+}
+
 /// Install the latest plonk bn254 artifacts.
 ///
 /// This function will download the latest plonk bn254 artifacts from the S3 bucket and extract them to
 /// the directory specified by [plonk_bn254_artifacts_dir()].
 pub fn install_plonk_bn254_artifacts(build_dir: PathBuf) {
-    // Create the build directory.
-    std::fs::create_dir_all(&build_dir).expect("failed to create build directory");
-
-    // Download the artifacts.
     let download_url = format!(
         "{}/{}.tar.gz",
         PLONK_BN254_ARTIFACTS_URL_BASE, SP1_CIRCUIT_VERSION
     );
+    install_artifacts(&download_url, build_dir);
+}
+
+/// Install the latest groth16 bn254 artifacts.
+///
+/// This function will download the latest groth16 bn254 artifacts from the S3 bucket and extract
+/// them to the directory specified by [groth16_bn254_artifacts_dir()].
+pub fn install_groth16_bn254_artifacts(build_dir: PathBuf) {
+    let download_url = format!(
+        "{}/{}-groth16.tar.gz",
+        GROTH16_BN254_ARTIFACTS_URL_BASE, SP1_CIRCUIT_VERSION
+    );
+    install_artifacts(&download_url, build_dir);
+}
+
+/// Downloads the artifacts tarball at `download_url` and extracts it to `build_dir`.
+fn install_artifacts(download_url: &str, build_dir: PathBuf) {
+    // Create the build directory.
+    std::fs::create_dir_all(&build_dir).expect("failed to create build directory");
+
+    // Download the artifacts.
     let mut artifacts_tar_gz_file =
         tempfile::NamedTempFile::new().expect("failed to create tempfile");
     let client = Client::builder()
@@ -60,7 +114,7 @@ pub fn install_plonk_bn254_artifacts(build_dir: PathBuf) {
         .expect("failed to create reqwest client");
     block_on(download_file(
         &client,
-        &download_url,
+        download_url,
         &mut artifacts_tar_gz_file,
     ))
     .expect("failed to download file");
@@ -130,15 +184,28 @@ pub async fn download_file(
     Ok(())
 }
 
-/// Utility method for blocking on an async function. If we're already in a tokio runtime, we'll
-/// block in place. Otherwise, we'll create a new runtime.
-pub fn block_on<T>(fut: impl Future<Output = T>) -> T {
-    // Handle case if we're already in an tokio runtime.
-    if let Ok(handle) = runtime::Handle::try_current() {
-        block_in_place(|| handle.block_on(fut))
-    } else {
+/// Utility method for blocking on an async function. If we're already in a multi-threaded tokio
+/// runtime, we'll block in place. Otherwise, we'll create a new runtime, on a separate thread if
+/// the current thread is driving a single-threaded runtime.
+///
+/// Prefer the `_async` methods of [crate::ProverClient] from async code.
+pub fn block_on<T: Send>(fut: impl Future<Output = T> + Send) -> T {
+    match runtime::Handle::try_current() {
+        // Handle case if we're already in a multi-threaded tokio runtime.
+        Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+            block_in_place(|| handle.block_on(fut))
+        }
+        // A single-threaded runtime can't block in place, so block on another thread.
+        Ok(_) => std::thread::scope(|s| {
+            s.spawn(|| new_runtime().block_on(fut))
+                .join()
+                .unwrap_or_else(|err| std::panic::resume_unwind(err))
+        }),
         // Otherwise create a new runtime.
-        let rt = runtime::Runtime::new().expect("Failed to create a new runtime");
-        rt.block_on(fut)
+        Err(_) => new_runtime().block_on(fut),
     }
 }
+
+fn new_runtime() -> runtime::Runtime {
+    runtime::Runtime::new().expect("Failed to create a new runtime")
+}
@@ -28,20 +28,25 @@ pub use proof::*;
 pub use provers::SP1VerificationError;
 use sp1_prover::components::DefaultProverComponents;
 use std::env;
+use std::sync::Arc;
 
 pub use provers::{LocalProver, MockProver, Prover};
 
-pub use sp1_core::runtime::{ExecutionReport, Hook, HookEnv, SP1Context, SP1ContextBuilder};
+pub use sp1_core::runtime::{
+    CancellationToken, CycleTrackerReport, ExecutionReport, ExecutionSnapshot, Hook, HookEnv,
+    PauseCondition, ProgressEvent, SP1Context, SP1ContextBuilder, WrapStage,
+};
 use sp1_core::SP1_CIRCUIT_VERSION;
+pub use sp1_prover::format::{SP1Encoding, SP1FormatError};
 pub use sp1_prover::{
-    CoreSC, HashableKey, InnerSC, OuterSC, PlonkBn254Proof, SP1Prover, SP1ProvingKey,
-    SP1PublicValues, SP1Stdin, SP1VerifyingKey,
+    CoreSC, Groth16Bn254Proof, HashableKey, InnerSC, OuterSC, PlonkBn254Proof, SP1ExecutionStatus,
+    SP1Prover, SP1ProvingKey, SP1PublicValues, SP1Stdin, SP1VerifyingKey,
 };
 
 /// A client for interacting with SP1.
 pub struct ProverClient {
     /// The underlying prover implementation.
-    pub prover: Box<dyn Prover<DefaultProverComponents>>,
+    pub prover: Arc<dyn Prover<DefaultProverComponents>>,
 }
 
 impl ProverClient {
@@ -67,16 +72,16 @@ impl ProverClient {
             .as_str()
         {
             "mock" => Self {
-                prover: Box::new(MockProver::new()),
+                prover: Arc::new(MockProver::new()),
             },
             "local" => Self {
-                prover: Box::new(LocalProver::new()),
+                prover: Arc::new(LocalProver::new()),
             },
             "network" => {
                 cfg_if! {
                     if #[cfg(feature = "network")] {
                         Self {
-                            prover: Box::new(NetworkProver::new()),
+                            prover: Arc::new(NetworkProver::new()),
                         }
                     } else {
                         panic!("network feature is not enabled")
@@ -103,7 +108,7 @@ impl ProverClient {
     /// ```
     pub fn mock() -> Self {
         Self {
-            prover: Box::new(MockProver::new()),
+            prover: Arc::new(MockProver::new()),
         }
     }
 
@@ -121,7 +126,7 @@ impl ProverClient {
     /// ```
     pub fn local() -> Self {
         Self {
-            prover: Box::new(LocalProver::new()),
+            prover: Arc::new(LocalProver::new()),
         }
     }
 
@@ -141,7 +146,7 @@ impl ProverClient {
         cfg_if! {
             if #[cfg(feature = "network")] {
                 Self {
-                    prover: Box::new(NetworkProver::new()),
+                    prover: Arc::new(NetworkProver::new()),
                 }
             } else {
                 panic!("network feature is not enabled")
@@ -177,14 +182,44 @@ impl ProverClient {
         action::Execute::new(self.prover.as_ref(), elf, stdin)
     }
 
+    /// Prepare to resume the execution of the given program from a snapshot taken by
+    /// [action::Execute::run_until].
+    ///
+    /// The returned [action::Execute] may register different hooks than the original execution.
+    /// A snapshot can be cloned and resumed several times to run different continuations.
+    ///
+    /// ### Examples
+    /// ```no_run
+    /// use sp1_sdk::{PauseCondition, ProverClient, SP1ExecutionStatus, SP1Stdin};
+    ///
+    /// let elf = include_bytes!("../../examples/fibonacci/program/elf/riscv32im-succinct-zkvm-elf");
+    /// let client = ProverClient::new();
+    /// let mut stdin = SP1Stdin::new();
+    /// stdin.write(&10usize);
+    ///
+    /// // Pause the execution after 1000 cycles.
+    /// let status = client
+    ///     .execute(elf, stdin)
+    ///     .run_until(PauseCondition::Cycle(1000))
+    ///     .unwrap();
+    ///
+    /// // Resume it from the snapshot.
+    /// if let SP1ExecutionStatus::Paused(snapshot) = status {
+    ///     let (public_values, report) = client.resume(elf, snapshot).run().unwrap();
+    /// }
+    /// ```
+    pub fn resume<'a>(&'a self, elf: &'a [u8], snapshot: ExecutionSnapshot) -> action::Execute<'a> {
+        action::Execute::resume(self.prover.as_ref(), elf, snapshot)
+    }
+
     /// Prepare to prove the execution of the given program with the given input in the default mode.
     /// The returned [action::Prove] may be configured via its methods before running.
     /// For example, calling [action::Prove::compress] sets the mode to compressed mode.
     ///
     /// To prove, call [action::Prove::run], which returns a proof of the program's execution.
     /// By default the proof generated will not be compressed to constant size.
-    /// To create a more succinct proof, use the [Self::prove_compressed],
-    /// [Self::prove_plonk], or [Self::prove_plonk] methods.
+    /// To create a more succinct proof, use the [action::Prove::compressed],
+    /// [action::Prove::plonk], or [action::Prove::groth16] methods.
     ///
     /// ### Examples
     /// ```no_run
@@ -233,6 +268,86 @@ impl ProverClient {
         self.prover.verify(proof, vk)
     }
 
+    /// Executes the given program on the given input without blocking the async runtime, by
+    /// running the executor on tokio's blocking thread pool.
+    ///
+    /// Unlike [Self::execute], hooks and other context options can't be set, since the program
+    /// runs on another thread.
+    ///
+    /// ### Examples
+    /// ```no_run
+    /// use sp1_sdk::{ProverClient, SP1Stdin};
+    ///
+    /// # async fn run() {
+    /// let elf = include_bytes!("../../examples/fibonacci/program/elf/riscv32im-succinct-zkvm-elf");
+    /// let client = ProverClient::new();
+    /// let mut stdin = SP1Stdin::new();
+    /// stdin.write(&10usize);
+    /// let (public_values, report) = client.execute_async(elf, stdin).await.unwrap();
+    /// # }
+    /// ```
+    pub async fn execute_async(
+        &self,
+        elf: &[u8],
+        stdin: SP1Stdin,
+    ) -> anyhow::Result<(SP1PublicValues, ExecutionReport)> {
+        let prover = Arc::clone(&self.prover);
+        let elf = elf.to_vec();
+        provers::spawn_blocking(move || {
+            Ok(prover
+                .sp1_prover()
+                .execute(&elf, &stdin, SP1Context::default())?)
+        })
+        .await?
+    }
+
+    /// Proves the execution of the given program with the given input in the given mode, without
+    /// blocking the async runtime.
+    ///
+    /// Dispatches to [Prover::prove_async] with the default [provers::ProofOpts]: the local
+    /// prover runs on tokio's blocking thread pool, and the network prover awaits its requests
+    /// directly.
+    ///
+    /// ### Examples
+    /// ```no_run
+    /// use sp1_sdk::{ProverClient, SP1ProofKind, SP1Stdin};
+    ///
+    /// # async fn run() {
+    /// let elf = include_bytes!("../../examples/fibonacci/program/elf/riscv32im-succinct-zkvm-elf");
+    /// let client = ProverClient::new();
+    /// let (pk, vk) = client.setup(elf);
+    /// let mut stdin = SP1Stdin::new();
+    /// stdin.write(&10usize);
+    /// let proof = client
+    ///     .prove_async(&pk, stdin, SP1ProofKind::Compressed)
+    ///     .await
+    ///     .unwrap();
+    /// client.verify_async(&proof, &vk).await.unwrap();
+    /// # }
+    /// ```
+    pub async fn prove_async(
+        &self,
+        pk: &SP1ProvingKey,
+        stdin: SP1Stdin,
+        kind: SP1ProofKind,
+    ) -> anyhow::Result<SP1ProofWithPublicValues> {
+        Arc::clone(&self.prover)
+            .prove_async(pk.clone(), stdin, Default::default(), kind)
+            .await
+    }
+
+    /// Verifies a proof like [Self::verify], without blocking the async runtime.
+    pub async fn verify_async(
+        &self,
+        proof: &SP1ProofWithPublicValues,
+        vk: &SP1VerifyingKey,
+    ) -> anyhow::Result<()> {
+        let prover = Arc::clone(&self.prover);
+        let (proof, vk) = (proof.clone(), vk.clone());
+        provers::spawn_blocking(move || prover.verify(&proof, &vk)).await??;
+        Ok(())
+    }
+
     /// Gets the current version of the SP1 zkVM.
     ///
     /// Note: This is not the same as the version of the SP1 SDK.
@@ -271,10 +386,14 @@ impl Default for ProverClient {
 mod tests {
 
     use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
 
     use sp1_core::runtime::{hook_ecrecover, FD_ECRECOVER_HOOK};
 
-    use crate::{utils, ProverClient, SP1Stdin};
+    use crate::{
+        utils, ExecutionSnapshot, MockProver, PauseCondition, ProverClient, SP1ExecutionStatus,
+        SP1Proof, SP1ProofKind, SP1Stdin,
+    };
 
     #[test]
     fn test_execute() {
@@ -306,6 +425,63 @@ mod tests {
         assert_ne!(call_ct.into_inner(), 0);
     }
 
+    #[test]
+    fn test_execute_resume() {
+        utils::setup_logger();
+        let client = ProverClient::local();
+        let elf = include_bytes!("../../tests/ecrecover/elf/riscv32im-succinct-zkvm-elf");
+        let (expected, _) = client.execute(elf, SP1Stdin::new()).run().unwrap();
+
+        // Pause before the hook is called and round-trip the snapshot through bincode.
+        let SP1ExecutionStatus::Paused(snapshot) = client
+            .execute(elf, SP1Stdin::new())
+            .run_until(PauseCondition::Cycle(100))
+            .unwrap()
+        else {
+            panic!("execution halted before the pause condition");
+        };
+        let snapshot = bincode::serialize(&snapshot).unwrap();
+        let snapshot: ExecutionSnapshot = bincode::deserialize(&snapshot).unwrap();
+
+        // Fork the execution: once with the default hooks and once with a wrapped hook.
+        let (public_values, _) = client.resume(elf, snapshot.clone()).run().unwrap();
+        assert_eq!(public_values.as_slice(), expected.as_slice());
+
+        let call_ct = AtomicU32::new(0);
+        let (public_values, _) = client
+            .resume(elf, snapshot)
+            .with_hook(FD_ECRECOVER_HOOK, |env, buf| {
+                call_ct.fetch_add(1, Ordering::Relaxed);
+                hook_ecrecover(env, buf)
+            })
+            .run()
+            .unwrap();
+        assert_eq!(public_values.as_slice(), expected.as_slice());
+        assert_ne!(call_ct.into_inner(), 0);
+    }
+
+    #[test]
+    fn test_execute_profile() {
+        utils::setup_logger();
+        let client = ProverClient::local();
+        let elf = include_bytes!("../../tests/fibonacci/elf/riscv32im-succinct-zkvm-elf");
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("profile.folded");
+        let (_, report) = client
+            .execute(elf, SP1Stdin::new())
+            .with_profiler(&path)
+            .run()
+            .unwrap();
+
+        let profile = std::fs::read_to_string(&path).unwrap();
+        let cycles = profile
+            .lines()
+            .map(|line| line.rsplit_once(' ').unwrap().1.parse::<u64>().unwrap())
+            .sum::<u64>();
+        assert_eq!(cycles, report.total_instruction_count());
+        assert!(profile.contains(";main;"));
+    }
+
     #[test]
     fn test_prove_new() {
         // Wrap the hook and check that it was called.
@@ -361,6 +537,77 @@ mod tests {
         client.verify(&proof, &vk).unwrap();
     }
 
+    #[test]
+    fn test_e2e_prove_groth16() {
+        utils::setup_logger();
+        let client = ProverClient::local();
+        let elf =
+            include_bytes!("../../examples/fibonacci/program/elf/riscv32im-succinct-zkvm-elf");
+        let (pk, vk) = client.setup(elf);
+        let mut stdin = SP1Stdin::new();
+        stdin.write(&10usize);
+        let proof = client.prove(&pk, stdin).groth16().run().unwrap();
+        client.verify(&proof, &vk).unwrap();
+    }
+
+    #[test]
+    fn test_e2e_prove_groth16_mock() {
+        utils::setup_logger();
+        let client = ProverClient::mock();
+        let elf =
+            include_bytes!("../../examples/fibonacci/program/elf/riscv32im-succinct-zkvm-elf");
+        let (pk, vk) = client.setup(elf);
+        let mut stdin = SP1Stdin::new();
+        stdin.write(&10usize);
+        let proof = client.prove(&pk, stdin).groth16().run().unwrap();
+        client.verify(&proof, &vk).unwrap();
+    }
+
+    #[test]
+    fn test_e2e_prove_core_hybrid_mock() {
+        utils::setup_logger();
+        let client = ProverClient {
+            prover: Arc::new(MockProver::hybrid(1.0).with_seed(0)),
+        };
+        let elf =
+            include_bytes!("../../examples/fibonacci/program/elf/riscv32im-succinct-zkvm-elf");
+        let (pk, vk) = client.setup(elf);
+        let mut stdin = SP1Stdin::new();
+        stdin.write(&10usize);
+        let proof = client.prove(&pk, stdin).run().unwrap();
+        assert!(matches!(&proof.proof, SP1Proof::Core(shards) if !shards.is_empty()));
+        client.verify(&proof, &vk).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_execute_async() {
+        utils::setup_logger();
+        let client = ProverClient::local();
+        let elf =
+            include_bytes!("../../examples/fibonacci/program/elf/riscv32im-succinct-zkvm-elf");
+        let mut stdin = SP1Stdin::new();
+        stdin.write(&10usize);
+        let (public_values, _) = client.execute_async(elf, stdin.clone()).await.unwrap();
+        let (expected, _) = client.execute(elf, stdin).run().unwrap();
+        assert_eq!(public_values.as_slice(), expected.as_slice());
+    }
+
+    #[tokio::test]
+    async fn test_e2e_prove_plonk_mock_async() {
+        utils::setup_logger();
+        let client = ProverClient::mock();
+        let elf =
+            include_bytes!("../../examples/fibonacci/program/elf/riscv32im-succinct-zkvm-elf");
+        let (pk, vk) = client.setup(elf);
+        let mut stdin = SP1Stdin::new();
+        stdin.write(&10usize);
+        let proof = client
+            .prove_async(&pk, stdin, SP1ProofKind::Plonk)
+            .await
+            .unwrap();
+        client.verify_async(&proof, &vk).await.unwrap();
+    }
+
     #[test]
     fn test_e2e_prove_plonk_mock() {
         utils::setup_logger();
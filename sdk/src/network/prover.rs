@@ -1,3 +1,4 @@
+use std::sync::Arc;
 use std::time::Instant;
 use std::{env, time::Duration};
 
@@ -10,13 +11,14 @@ use crate::{
 };
 use crate::{SP1Context, SP1ProofKind, SP1ProofWithPublicValues, SP1ProvingKey, SP1VerifyingKey};
 use anyhow::Result;
+use async_trait::async_trait;
 use serde::de::DeserializeOwned;
-use sp1_core::utils::SP1ProverOpts;
+use sp1_core::utils::{SP1CoreProverError, SP1ProverOpts};
 use sp1_prover::components::DefaultProverComponents;
 use sp1_prover::{SP1Prover, SP1Stdin, SP1_CIRCUIT_VERSION};
 use tokio::time::sleep;
 
-use crate::provers::{LocalProver, ProofOpts, ProverType};
+use crate::provers::{spawn_blocking, LocalProver, ProofOpts, ProverType};
 
 /// An implementation of [crate::ProverClient] that can generate proofs on a remote RPC server.
 pub struct NetworkProver {
@@ -51,8 +53,13 @@ impl NetworkProver {
         stdin: SP1Stdin,
         mode: ProofMode,
     ) -> Result<String> {
-        let client = &self.client;
+        self.simulate(elf, &stdin)?;
+        self.create_proof_request(elf, &stdin, mode).await
+    }
 
+    /// Executes the program locally to catch errors before paying for a proof, unless
+    /// `SKIP_SIMULATION` is set to `true`.
+    fn simulate(&self, elf: &[u8], stdin: &SP1Stdin) -> Result<()> {
         let skip_simulation = env::var("SKIP_SIMULATION")
             .map(|val| val == "true")
             .unwrap_or(false);
@@ -61,7 +68,7 @@ impl NetworkProver {
             let (_, report) =
                 self.local_prover
                     .sp1_prover()
-                    .execute(elf, &stdin, Default::default())?;
+                    .execute(elf, stdin, Default::default())?;
             log::info!(
                 "Simulation complete, cycles: {}",
                 report.total_instruction_count()
@@ -69,9 +76,19 @@ impl NetworkProver {
         } else {
             log::info!("Skipping simulation");
         }
+        Ok(())
+    }
 
+    /// Creates a proof request on the prover network, returning the proof ID.
+    async fn create_proof_request(
+        &self,
+        elf: &[u8],
+        stdin: &SP1Stdin,
+        mode: ProofMode,
+    ) -> Result<String> {
+        let client = &self.client;
         let version = SP1_CIRCUIT_VERSION;
-        let proof_id = client.create_proof(elf, &stdin, mode, version).await?;
+        let proof_id = client.create_proof(elf, stdin, mode, version).await?;
         log::info!("Created {}", proof_id);
 
         if NetworkClient::rpc_url() == DEFAULT_PROVER_NETWORK_RPC {
@@ -137,6 +154,7 @@ impl NetworkProver {
     }
 }
 
+#[async_trait]
 impl Prover<DefaultProverComponents> for NetworkProver {
     fn id(&self) -> ProverType {
         ProverType::Network
@@ -158,9 +176,31 @@ impl Prover<DefaultProverComponents> for NetworkProver {
         context: SP1Context<'a>,
         kind: SP1ProofKind,
     ) -> Result<SP1ProofWithPublicValues> {
+        if context.precompiles.is_some() {
+            return Err(SP1CoreProverError::UnsupportedPrecompiles.into());
+        }
         warn_if_not_default(&opts.sp1_prover_opts, &context);
         block_on(self.prove(&pk.elf, stdin, kind.into(), opts.timeout))
     }
+
+    async fn prove_async(
+        self: Arc<Self>,
+        pk: SP1ProvingKey,
+        stdin: SP1Stdin,
+        opts: ProofOpts,
+        kind: SP1ProofKind,
+    ) -> Result<SP1ProofWithPublicValues> {
+        warn_if_not_default(&opts.sp1_prover_opts, &SP1Context::default());
+
+        // Only the simulation is CPU-bound, the requests to the network are awaited directly.
+        let prover = Arc::clone(&self);
+        let (elf, simulation_stdin) = (pk.elf.clone(), stdin.clone());
+        spawn_blocking(move || prover.simulate(&elf, &simulation_stdin)).await??;
+        let proof_id = self
+            .create_proof_request(&pk.elf, &stdin, kind.into())
+            .await?;
+        self.wait_proof(&proof_id, opts.timeout).await
+    }
 }
 
 impl Default for NetworkProver {
@@ -180,6 +220,8 @@ fn warn_if_not_default(opts: &SP1ProverOpts, context: &SP1Context) {
     let SP1Context {
         hook_registry,
         subproof_verifier,
+        progress,
+        cancellation_token,
         ..
     } = context;
     if hook_registry.is_some() {
@@ -194,6 +236,12 @@ fn warn_if_not_default(opts: &SP1ProverOpts, context: &SP1Context) {
         tracing::warn!("non-default context.subproof_verifier will be ignored");
         tracing::warn!("custom subproof verifiers are currently unsupported by the network prover");
     }
+    if progress.is_some() {
+        tracing::warn!("non-default context.progress will be ignored");
+    }
+    if cancellation_token.is_some() {
+        tracing::warn!("non-default context.cancellation_token will be ignored");
+    }
 }
 
 impl From<SP1ProofKind> for ProofMode {
@@ -202,6 +250,7 @@ impl From<SP1ProofKind> for ProofMode {
             SP1ProofKind::Core => Self::Core,
             SP1ProofKind::Compressed => Self::Compressed,
             SP1ProofKind::Plonk => Self::Plonk,
+            SP1ProofKind::Groth16 => Self::Groth16,
         }
     }
 }
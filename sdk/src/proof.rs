@@ -1,11 +1,15 @@
-use std::{fmt::Debug, fs::File, path::Path};
+use std::{fmt::Debug, path::Path};
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use strum_macros::{EnumDiscriminants, EnumTryAs};
 
 use sp1_core::stark::{MachineVerificationError, ShardProof};
-use sp1_prover::{CoreSC, InnerSC, PlonkBn254Proof, SP1PublicValues, SP1Stdin};
+use sp1_prover::format::{
+    decode_artifact, decode_artifact_with_schema, encode_artifact, load_artifact, save_artifact,
+    SP1Artifact, SP1ArtifactKind, SP1Encoding, SP1PayloadSchema,
+};
+use sp1_prover::{CoreSC, Groth16Bn254Proof, InnerSC, PlonkBn254Proof, SP1PublicValues, SP1Stdin};
 
 /// A proof generated with SP1 of a particular proof mode.
 #[derive(Debug, Clone, Serialize, Deserialize, EnumDiscriminants, EnumTryAs)]
@@ -16,6 +20,7 @@ pub enum SP1Proof {
     Core(Vec<ShardProof<CoreSC>>),
     Compressed(ShardProof<InnerSC>),
     Plonk(PlonkBn254Proof),
+    Groth16(Groth16Bn254Proof),
 }
 
 /// A proof generated with SP1, bundled together with stdin, public values, and the SP1 version.
@@ -28,28 +33,49 @@ pub struct SP1ProofWithPublicValues {
 }
 
 impl SP1ProofWithPublicValues {
-    /// Saves the proof to a path.
+    /// Saves the proof to a path, encoded with `bincode`.
     pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
-        bincode::serialize_into(File::create(path).expect("failed to open file"), self)
-            .map_err(Into::into)
+        self.save_with_encoding(path, SP1Encoding::Bincode)
     }
 
-    /// Loads a proof from a path.
+    /// Saves the proof to a path with the given encoding.
+    pub fn save_with_encoding(&self, path: impl AsRef<Path>, encoding: SP1Encoding) -> Result<()> {
+        save_artifact(self, path, encoding).map_err(Into::into)
+    }
+
+    /// Loads a proof from a path, whatever the encoding it was saved with.
     pub fn load(path: impl AsRef<Path>) -> Result<Self> {
-        bincode::deserialize_from(File::open(path).expect("failed to open file"))
-            .map_err(Into::into)
+        load_artifact(path).map_err(Into::into)
+    }
+
+    /// Encodes the proof in the versioned container format, for storing it outside of a file.
+    pub fn to_bytes(&self, encoding: SP1Encoding) -> Result<Vec<u8>> {
+        encode_artifact(self, encoding).map_err(Into::into)
+    }
+
+    /// Decodes a proof encoded with [SP1ProofWithPublicValues::to_bytes].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        decode_artifact(bytes).map_err(Into::into)
+    }
+
+    /// Decodes a proof like [SP1ProofWithPublicValues::from_bytes], and returns the schema it was
+    /// written with, which tells whether it is for the current circuits.
+    pub fn from_bytes_with_schema(bytes: &[u8]) -> Result<(Self, SP1PayloadSchema)> {
+        decode_artifact_with_schema(bytes).map_err(Into::into)
     }
 
     /// Returns the raw proof as a string.
     pub fn raw(&self) -> String {
         match &self.proof {
             SP1Proof::Plonk(plonk) => plonk.raw_proof.clone(),
+            SP1Proof::Groth16(groth16) => groth16.raw_proof.clone(),
             _ => unimplemented!(),
         }
     }
 
-    /// For Plonk proofs, returns the proof in a byte encoding the onchain verifier accepts.
-    /// The bytes consist of the first four bytes of Plonk vkey hash followed by the encoded proof.
+    /// For Plonk and Groth16 proofs, returns the proof in a byte encoding the onchain verifier
+    /// accepts. The bytes consist of the first four bytes of the circuit's vkey hash followed by
+    /// the encoded proof.
     pub fn bytes(&self) -> Vec<u8> {
         match &self.proof {
             SP1Proof::Plonk(plonk_proof) => {
@@ -60,11 +86,39 @@ impl SP1ProofWithPublicValues {
                 );
                 bytes
             }
-            _ => unimplemented!("only Plonk proofs are verifiable onchain"),
+            SP1Proof::Groth16(groth16_proof) => {
+                let mut bytes = Vec::with_capacity(4 + groth16_proof.encoded_proof.len());
+                bytes.extend_from_slice(&groth16_proof.groth16_vkey_hash[..4]);
+                bytes.extend_from_slice(
+                    &hex::decode(&groth16_proof.encoded_proof).expect("Invalid Groth16 proof"),
+                );
+                bytes
+            }
+            _ => unimplemented!("only Plonk and Groth16 proofs are verifiable onchain"),
         }
     }
 }
 
+impl SP1Artifact for SP1ProofWithPublicValues {
+    const NAME: &'static str = "proof";
+
+    fn kind(&self) -> SP1ArtifactKind {
+        match self.proof {
+            SP1Proof::Core(_) => SP1ArtifactKind::CoreProof,
+            SP1Proof::Compressed(_) => SP1ArtifactKind::CompressedProof,
+            SP1Proof::Plonk(_) => SP1ArtifactKind::PlonkProof,
+            SP1Proof::Groth16(_) => SP1ArtifactKind::Groth16Proof,
+        }
+    }
+
+    fn accepts(kind: SP1ArtifactKind) -> bool {
+        !matches!(
+            kind,
+            SP1ArtifactKind::ProvingKey | SP1ArtifactKind::VerifyingKey
+        )
+    }
+}
+
 pub type SP1CoreProofVerificationError = MachineVerificationError<CoreSC>;
 
 pub type SP1CompressedProofVerificationError = MachineVerificationError<InnerSC>;
@@ -1,11 +1,12 @@
 use anyhow::Result;
-use sp1_core::runtime::SP1Context;
-use sp1_prover::{components::SP1ProverComponents, SP1Prover, SP1Stdin};
+use sp1_core::runtime::{ProgressEvent, SP1Context, WrapStage};
+use sp1_prover::{components::SP1ProverComponents, SP1Prover, SP1RecursionProverError, SP1Stdin};
 use sysinfo::System;
 
 use crate::{
-    install::try_install_plonk_bn254_artifacts, provers::ProofOpts, Prover, SP1Proof, SP1ProofKind,
-    SP1ProofWithPublicValues, SP1ProvingKey, SP1VerifyingKey,
+    install::{try_install_groth16_bn254_artifacts, try_install_plonk_bn254_artifacts},
+    provers::ProofOpts,
+    Prover, SP1Proof, SP1ProofKind, SP1ProofWithPublicValues, SP1ProvingKey, SP1VerifyingKey,
 };
 
 use super::ProverType;
@@ -50,12 +51,27 @@ impl<C: SP1ProverComponents> Prover<C> for LocalProver<C> {
         kind: SP1ProofKind,
     ) -> Result<SP1ProofWithPublicValues> {
         let total_ram_gb = System::new_all().total_memory() / 1_000_000_000;
-        if kind == SP1ProofKind::Plonk && total_ram_gb <= 120 {
+        if (kind == SP1ProofKind::Plonk || kind == SP1ProofKind::Groth16) && total_ram_gb <= 120 {
             return Err(anyhow::anyhow!(
-                "not enough memory to generate plonk proof. at least 128GB is required."
+                "not enough memory to generate {:?} proof. at least 128GB is required.",
+                kind
             ));
         }
 
+        // The core prover consumes the context, so keep what the later stages need.
+        let control = SP1Context {
+            progress: context.progress.clone(),
+            cancellation_token: context.cancellation_token.clone(),
+            ..Default::default()
+        };
+        let start_stage = |stage| {
+            if control.is_cancelled() {
+                return Err(SP1RecursionProverError::Cancelled);
+            }
+            control.report(ProgressEvent::Wrap(stage));
+            Ok(())
+        };
+
         let proof = self
             .prover
             .prove_core(pk, &stdin, opts.sp1_prover_opts, context)?;
@@ -69,9 +85,13 @@ impl<C: SP1ProverComponents> Prover<C> for LocalProver<C> {
         }
         let deferred_proofs = stdin.proofs.iter().map(|p| p.0.clone()).collect();
         let public_values = proof.public_values.clone();
-        let reduce_proof =
-            self.prover
-                .compress(&pk.vk, proof, deferred_proofs, opts.sp1_prover_opts)?;
+        let reduce_proof = self.prover.compress_with_context(
+            &pk.vk,
+            proof,
+            deferred_proofs,
+            opts.sp1_prover_opts,
+            &control,
+        )?;
         if kind == SP1ProofKind::Compressed {
             return Ok(SP1ProofWithPublicValues {
                 proof: SP1Proof::Compressed(reduce_proof.proof),
@@ -80,11 +100,34 @@ impl<C: SP1ProverComponents> Prover<C> for LocalProver<C> {
                 sp1_version: self.version().to_string(),
             });
         }
+        start_stage(WrapStage::Shrink)?;
         let compress_proof = self.prover.shrink(reduce_proof, opts.sp1_prover_opts)?;
+        start_stage(WrapStage::WrapBn254)?;
         let outer_proof = self
             .prover
             .wrap_bn254(compress_proof, opts.sp1_prover_opts)?;
 
+        if kind == SP1ProofKind::Groth16 {
+            let groth16_bn254_artifacts = if sp1_prover::build::sp1_dev_mode() {
+                sp1_prover::build::try_build_groth16_bn254_artifacts_dev(
+                    self.prover.wrap_vk(),
+                    &outer_proof.proof,
+                )
+            } else {
+                try_install_groth16_bn254_artifacts()
+            };
+            start_stage(WrapStage::Groth16)?;
+            let proof = self
+                .prover
+                .wrap_groth16_bn254(outer_proof, &groth16_bn254_artifacts);
+            return Ok(SP1ProofWithPublicValues {
+                proof: SP1Proof::Groth16(proof),
+                stdin,
+                public_values,
+                sp1_version: self.version().to_string(),
+            });
+        }
+
         let plonk_bn254_aritfacts = if sp1_prover::build::sp1_dev_mode() {
             sp1_prover::build::try_build_plonk_bn254_artifacts_dev(
                 self.prover.wrap_vk(),
@@ -93,6 +136,7 @@ impl<C: SP1ProverComponents> Prover<C> for LocalProver<C> {
         } else {
             try_install_plonk_bn254_artifacts()
         };
+        start_stage(WrapStage::Plonk)?;
         let proof = self
             .prover
             .wrap_plonk_bn254(outer_proof, &plonk_bn254_aritfacts);
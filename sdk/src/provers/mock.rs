@@ -1,35 +1,172 @@
 #![allow(unused_variables)]
+use std::env;
+use std::sync::Arc;
+
 use hashbrown::HashMap;
 
 use crate::{
     Prover, SP1Proof, SP1ProofKind, SP1ProofWithPublicValues, SP1ProvingKey, SP1VerificationError,
     SP1VerifyingKey,
 };
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use p3_baby_bear::BabyBear;
 use p3_field::{AbstractField, PrimeField};
 use p3_fri::{FriProof, TwoAdicFriPcsProof};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use sp1_core::{
-    runtime::SP1Context,
-    stark::{ShardCommitment, ShardOpenedValues, ShardProof},
+    runtime::{Program, SP1Context},
+    stark::{
+        MachineProver, MachineVerificationError, ShardCommitment, ShardOpenedValues, ShardProof,
+        StarkGenericConfig,
+    },
+    utils::{execute_shard_records, SP1CoreOpts, SP1CoreProverError},
 };
 use sp1_prover::{
-    components::DefaultProverComponents, verify::verify_plonk_bn254_public_inputs, HashableKey,
-    PlonkBn254Proof, SP1Prover, SP1Stdin,
+    components::DefaultProverComponents,
+    verify::{verify_groth16_bn254_public_inputs, verify_plonk_bn254_public_inputs},
+    CoreSC, Groth16Bn254Proof, HashableKey, PlonkBn254Proof, SP1Prover, SP1PublicValues, SP1Stdin,
 };
 
 use super::{ProofOpts, ProverType};
 
 /// An implementation of [crate::ProverClient] that can generate mock proofs.
+///
+/// In hybrid mode, the mock prover also generates real core proofs of a random sample of the
+/// shards, after checking their constraints. A hybrid core proof holds the proofs of the sampled
+/// shards, so it is only accepted by [MockProver::verify], which verifies each of them.
 pub struct MockProver {
     pub(crate) prover: SP1Prover,
+    sample_rate: f64,
+    seed: Option<u64>,
 }
 
 impl MockProver {
     /// Creates a new [MockProver].
+    ///
+    /// Setting the `SP1_MOCK_SAMPLE_RATE` environment variable enables the hybrid mode, see
+    /// [MockProver::hybrid], and `SP1_MOCK_SEED` sets the seed of the shard sampling.
     pub fn new() -> Self {
         let prover = SP1Prover::new();
-        Self { prover }
+        let sample_rate = env::var("SP1_MOCK_SAMPLE_RATE").map_or(0.0, |s| {
+            s.parse()
+                .expect("SP1_MOCK_SAMPLE_RATE must be a number between 0 and 1")
+        });
+        let seed = env::var("SP1_MOCK_SEED")
+            .ok()
+            .map(|s| s.parse().expect("SP1_MOCK_SEED must be an integer"));
+        Self::with_prover(prover, sample_rate, seed)
+    }
+
+    /// Creates a hybrid [MockProver], which proves each shard with probability `sample_rate`.
+    ///
+    /// The constraints of the sampled shards are checked with
+    /// [MachineProver::debug_shard_constraints], and proving fails with the failing row and chip
+    /// if they are not satisfied.
+    pub fn hybrid(sample_rate: f64) -> Self {
+        Self::with_prover(SP1Prover::new(), sample_rate, None)
+    }
+
+    /// Sets the seed of the shard sampling, to reproduce the shards sampled by a previous run.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    fn with_prover(prover: SP1Prover, sample_rate: f64, seed: Option<u64>) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&sample_rate),
+            "the sample rate must be between 0 and 1"
+        );
+        Self {
+            prover,
+            sample_rate,
+            seed,
+        }
+    }
+
+    /// Executes the program, and proves a random sample of its shards in hybrid mode.
+    fn execute<'a>(
+        &'a self,
+        pk: &SP1ProvingKey,
+        stdin: &SP1Stdin,
+        opts: &SP1CoreOpts,
+        mut context: SP1Context<'a>,
+    ) -> Result<(SP1PublicValues, Vec<ShardProof<CoreSC>>)> {
+        if self.sample_rate == 0.0 {
+            let (public_values, _) = self.prover.execute(&pk.elf, stdin, context)?;
+            return Ok((public_values, vec![]));
+        }
+
+        let seed = self.seed.unwrap_or_else(rand::random);
+        let mut rng = StdRng::seed_from_u64(seed);
+        let prover = &self.prover.core_prover;
+        let program = Program::from(pk.elf.as_slice());
+        context.subproof_verifier.replace(Arc::new(&self.prover));
+
+        // Execute the program once, and keep the records of the sampled shards.
+        let mut num_shards = 0;
+        let mut records = Vec::new();
+        let (public_values_stream, _) =
+            execute_shard_records(prover, program, stdin, opts, context, |shard_records| {
+                num_shards += shard_records.len();
+                records.extend(
+                    shard_records
+                        .into_iter()
+                        .filter(|_| rng.gen_bool(self.sample_rate)),
+                );
+            })?;
+        tracing::info!(
+            "proving {} of {} shards sampled with seed {}",
+            records.len(),
+            num_shards,
+            seed
+        );
+
+        // Check the constraints of the sampled shards.
+        let mut challenger = prover.config().challenger();
+        pk.pk.observe_into(&mut challenger);
+        for record in records.iter() {
+            prover
+                .debug_shard_constraints(&pk.pk, record, &mut challenger.clone())
+                .map_err(|e| anyhow!("the sampled shards are not valid: {}", e))?;
+        }
+
+        // Prove the sampled shards, against a challenger that observed all of them.
+        let shards = records
+            .into_iter()
+            .map(|record| {
+                let traces = prover.generate_traces(&record);
+                prover.commit(record, traces)
+            })
+            .collect::<Vec<_>>();
+        for data in shards.iter() {
+            prover.observe(
+                &mut challenger,
+                data.main_commit,
+                &data.public_values[0..prover.num_pv_elts()],
+            );
+        }
+        let shard_proofs = shards
+            .into_iter()
+            .map(|data| prover.open(&pk.pk, data, &mut challenger.clone()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Verify the proofs right away, so that every kind of proof checks the sampled shards.
+        self.verify_sampled_shards(&shard_proofs, &pk.vk)?;
+
+        Ok((SP1PublicValues::from(&public_values_stream), shard_proofs))
+    }
+
+    /// Verifies the proofs of the shards sampled in hybrid mode.
+    fn verify_sampled_shards(
+        &self,
+        shard_proofs: &[ShardProof<CoreSC>],
+        vk: &SP1VerifyingKey,
+    ) -> Result<(), MachineVerificationError<CoreSC>> {
+        let machine = self.prover.core_prover.machine();
+        let mut challenger = machine.config().challenger();
+        machine.verify_shards(&vk.vk, shard_proofs, &mut challenger)
     }
 }
 
@@ -54,60 +191,70 @@ impl Prover<DefaultProverComponents> for MockProver {
         context: SP1Context<'a>,
         kind: SP1ProofKind,
     ) -> Result<SP1ProofWithPublicValues> {
+        if context.precompiles.is_some() {
+            return Err(SP1CoreProverError::UnsupportedPrecompiles.into());
+        }
+        let (public_values, shard_proofs) =
+            self.execute(pk, &stdin, &opts.sp1_prover_opts.core_opts, context)?;
         match kind {
-            SP1ProofKind::Core => {
-                let (public_values, _) = self.prover.execute(&pk.elf, &stdin, context)?;
-                Ok(SP1ProofWithPublicValues {
-                    proof: SP1Proof::Core(vec![]),
-                    stdin,
-                    public_values,
-                    sp1_version: self.version().to_string(),
-                })
-            }
-            SP1ProofKind::Compressed => {
-                let (public_values, _) = self.prover.execute(&pk.elf, &stdin, context)?;
-                Ok(SP1ProofWithPublicValues {
-                    proof: SP1Proof::Compressed(ShardProof {
-                        commitment: ShardCommitment {
-                            main_commit: [BabyBear::zero(); 8].into(),
-                            permutation_commit: [BabyBear::zero(); 8].into(),
-                            quotient_commit: [BabyBear::zero(); 8].into(),
-                        },
-                        opened_values: ShardOpenedValues { chips: vec![] },
-                        opening_proof: TwoAdicFriPcsProof {
-                            fri_proof: FriProof {
-                                commit_phase_commits: vec![],
-                                query_proofs: vec![],
-                                final_poly: Default::default(),
-                                pow_witness: BabyBear::zero(),
-                            },
-                            query_openings: vec![],
+            SP1ProofKind::Core => Ok(SP1ProofWithPublicValues {
+                proof: SP1Proof::Core(shard_proofs),
+                stdin,
+                public_values,
+                sp1_version: self.version().to_string(),
+            }),
+            SP1ProofKind::Compressed => Ok(SP1ProofWithPublicValues {
+                proof: SP1Proof::Compressed(ShardProof {
+                    commitment: ShardCommitment {
+                        main_commit: [BabyBear::zero(); 8].into(),
+                        permutation_commit: [BabyBear::zero(); 8].into(),
+                        quotient_commit: [BabyBear::zero(); 8].into(),
+                    },
+                    opened_values: ShardOpenedValues { chips: vec![] },
+                    opening_proof: TwoAdicFriPcsProof {
+                        fri_proof: FriProof {
+                            commit_phase_commits: vec![],
+                            query_proofs: vec![],
+                            final_poly: Default::default(),
+                            pow_witness: BabyBear::zero(),
                         },
-                        chip_ordering: HashMap::new(),
-                        public_values: vec![],
-                    }),
-                    stdin,
-                    public_values,
-                    sp1_version: self.version().to_string(),
-                })
-            }
-            SP1ProofKind::Plonk => {
-                let (public_values, _) = self.prover.execute(&pk.elf, &stdin, context)?;
-                Ok(SP1ProofWithPublicValues {
-                    proof: SP1Proof::Plonk(PlonkBn254Proof {
-                        public_inputs: [
-                            pk.vk.hash_bn254().as_canonical_biguint().to_string(),
-                            public_values.hash().to_string(),
-                        ],
-                        encoded_proof: "".to_string(),
-                        raw_proof: "".to_string(),
-                        plonk_vkey_hash: [0; 32],
-                    }),
-                    stdin,
-                    public_values,
-                    sp1_version: self.version().to_string(),
-                })
-            }
+                        query_openings: vec![],
+                    },
+                    chip_ordering: HashMap::new(),
+                    public_values: vec![],
+                }),
+                stdin,
+                public_values,
+                sp1_version: self.version().to_string(),
+            }),
+            SP1ProofKind::Plonk => Ok(SP1ProofWithPublicValues {
+                proof: SP1Proof::Plonk(PlonkBn254Proof {
+                    public_inputs: [
+                        pk.vk.hash_bn254().as_canonical_biguint().to_string(),
+                        public_values.hash().to_string(),
+                    ],
+                    encoded_proof: "".to_string(),
+                    raw_proof: "".to_string(),
+                    plonk_vkey_hash: [0; 32],
+                }),
+                stdin,
+                public_values,
+                sp1_version: self.version().to_string(),
+            }),
+            SP1ProofKind::Groth16 => Ok(SP1ProofWithPublicValues {
+                proof: SP1Proof::Groth16(Groth16Bn254Proof {
+                    public_inputs: [
+                        pk.vk.hash_bn254().as_canonical_biguint().to_string(),
+                        public_values.hash().to_string(),
+                    ],
+                    encoded_proof: "".to_string(),
+                    raw_proof: "".to_string(),
+                    groth16_vkey_hash: [0; 32],
+                }),
+                stdin,
+                public_values,
+                sp1_version: self.version().to_string(),
+            }),
         }
     }
 
@@ -117,10 +264,17 @@ impl Prover<DefaultProverComponents> for MockProver {
         vkey: &SP1VerifyingKey,
     ) -> Result<(), SP1VerificationError> {
         match &bundle.proof {
+            SP1Proof::Core(shard_proofs) if !shard_proofs.is_empty() => self
+                .verify_sampled_shards(shard_proofs, vkey)
+                .map_err(SP1VerificationError::Core),
             SP1Proof::Plonk(PlonkBn254Proof { public_inputs, .. }) => {
                 verify_plonk_bn254_public_inputs(vkey, &bundle.public_values, public_inputs)
                     .map_err(SP1VerificationError::Plonk)
             }
+            SP1Proof::Groth16(Groth16Bn254Proof { public_inputs, .. }) => {
+                verify_groth16_bn254_public_inputs(vkey, &bundle.public_values, public_inputs)
+                    .map_err(SP1VerificationError::Groth16)
+            }
             _ => Ok(()),
         }
     }
@@ -2,6 +2,7 @@ mod local;
 mod mock;
 
 use anyhow::Result;
+use async_trait::async_trait;
 pub use local::LocalProver;
 pub use mock::MockProver;
 use sp1_core::runtime::SP1Context;
@@ -15,11 +16,12 @@ use sp1_prover::SP1CoreProofData;
 use sp1_prover::SP1Prover;
 use sp1_prover::SP1ReduceProof;
 use sp1_prover::{SP1ProvingKey, SP1Stdin, SP1VerifyingKey};
+use std::sync::Arc;
 use std::time::Duration;
 use strum_macros::EnumString;
 use thiserror::Error;
 
-use crate::install::try_install_plonk_bn254_artifacts;
+use crate::install::{try_install_groth16_bn254_artifacts, try_install_plonk_bn254_artifacts};
 use crate::SP1Proof;
 use crate::SP1ProofKind;
 use crate::SP1ProofWithPublicValues;
@@ -51,9 +53,12 @@ pub enum SP1VerificationError {
     Recursion(MachineVerificationError<InnerSC>),
     #[error("Plonk verification error: {0}")]
     Plonk(anyhow::Error),
+    #[error("Groth16 verification error: {0}")]
+    Groth16(anyhow::Error),
 }
 
 /// An implementation of [crate::ProverClient].
+#[async_trait]
 pub trait Prover<C: SP1ProverComponents>: Send + Sync {
     fn id(&self) -> ProverType;
 
@@ -75,8 +80,24 @@ pub trait Prover<C: SP1ProverComponents>: Send + Sync {
         kind: SP1ProofKind,
     ) -> Result<SP1ProofWithPublicValues>;
 
+    /// Prove the execution of a RISCV ELF with the given inputs without blocking the async runtime.
+    ///
+    /// By default, [Self::prove] is run on tokio's blocking thread pool.
+    async fn prove_async(
+        self: Arc<Self>,
+        pk: SP1ProvingKey,
+        stdin: SP1Stdin,
+        opts: ProofOpts,
+        kind: SP1ProofKind,
+    ) -> Result<SP1ProofWithPublicValues>
+    where
+        Self: 'static,
+    {
+        spawn_blocking(move || self.prove(&pk, stdin, opts, SP1Context::default(), kind)).await?
+    }
+
     /// Verify that an SP1 proof is valid given its vkey and metadata.
-    /// For Plonk proofs, verifies that the public inputs of the PlonkBn254 proof match
+    /// For Plonk and Groth16 proofs, verifies that the public inputs of the wrapped proof match
     /// the hash of the VK and the committed public values of the SP1ProofWithPublicValues.
     fn verify(
         &self,
@@ -110,6 +131,32 @@ pub trait Prover<C: SP1ProverComponents>: Send + Sync {
                     },
                 )
                 .map_err(SP1VerificationError::Plonk),
+            SP1Proof::Groth16(proof) => self
+                .sp1_prover()
+                .verify_groth16_bn254(
+                    &proof,
+                    vkey,
+                    &bundle.public_values,
+                    &if sp1_prover::build::sp1_dev_mode() {
+                        sp1_prover::build::groth16_bn254_artifacts_dev_dir()
+                    } else {
+                        try_install_groth16_bn254_artifacts()
+                    },
+                )
+                .map_err(SP1VerificationError::Groth16),
         }
     }
 }
+
+/// Runs `f` on tokio's blocking thread pool, resuming any panic on the calling task.
+///
+/// Returns an error if the task was cancelled, e.g. because the runtime is shutting down.
+pub(crate) async fn spawn_blocking<T: Send + 'static>(
+    f: impl FnOnce() -> T + Send + 'static,
+) -> Result<T> {
+    match tokio::task::spawn_blocking(f).await {
+        Ok(value) => Ok(value),
+        Err(err) if err.is_cancelled() => Err(anyhow::anyhow!("blocking task was cancelled")),
+        Err(err) => std::panic::resume_unwind(err.into_panic()),
+    }
+}
@@ -0,0 +1,161 @@
+//! A standalone verifier for SP1 core, compressed and Plonk BN254 proofs.
+//!
+//! Verifying a proof only needs the machine descriptions and the STARK verifier, so this crate
+//! depends on `sp1-core` and `sp1-recursion-core` for those, but not on the recursion compiler,
+//! the gnark bindings or an async runtime like `sp1-prover` and `sp1-sdk` do. The Plonk BN254
+//! proofs are verified natively, see [plonk].
+//!
+//! The verifying key of the compress program is an input of [verify_compressed_proof], since
+//! computing it requires compiling the recursion program. It can be obtained once from
+//! `SP1Prover::compress_vk` and stored alongside the program's verifying key.
+//!
+//! `sp1-core` is used without its `prover` feature, which leaves out the proving utilities, the
+//! parallel trace generation and their dependencies. This crate itself only needs `alloc`; the
+//! `std` feature adds loading the Plonk verifying key from the circuit artifacts.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub mod plonk;
+pub mod verify;
+
+use alloc::vec::Vec;
+
+use p3_baby_bear::BabyBear;
+use p3_commit::{Pcs, TwoAdicMultiplicativeCoset};
+use p3_field::{AbstractField, TwoAdicField};
+use sp1_core::stark::{
+    MachineVerificationError, RiscvAir, ShardProof, StarkGenericConfig, StarkMachine,
+    StarkVerifyingKey,
+};
+use sp1_core::utils::{BabyBearPoseidon2, DIGEST_SIZE};
+use sp1_primitives::poseidon2_hash;
+use sp1_recursion_core::stark::RecursionAir;
+
+pub use verify::{verify_compressed_proof, verify_core_proof};
+
+/// The configuration for the core prover.
+pub type CoreSC = BabyBearPoseidon2;
+
+/// The configuration for the inner prover.
+pub type InnerSC = BabyBearPoseidon2;
+
+/// The degree of the constraints of the compress program.
+pub const COMPRESS_DEGREE: usize = 3;
+
+pub type CompressAir<F> = RecursionAir<F, COMPRESS_DEGREE>;
+
+/// Hash a verifying key into a digest of BabyBear elements.
+///
+/// This is the digest committed to in the public values of the recursion programs.
+pub fn vk_digest<SC>(vk: &StarkVerifyingKey<SC>) -> [BabyBear; DIGEST_SIZE]
+where
+    SC: StarkGenericConfig<Val = BabyBear, Domain = TwoAdicMultiplicativeCoset<BabyBear>>,
+    <SC::Pcs as Pcs<SC::Challenge, SC::Challenger>>::Commitment: AsRef<[BabyBear; DIGEST_SIZE]>,
+{
+    let prep_domains = vk.chip_information.iter().map(|(_, domain, _)| domain);
+    let num_inputs = DIGEST_SIZE + 1 + (4 * prep_domains.len());
+    let mut inputs = Vec::with_capacity(num_inputs);
+    inputs.extend(vk.commit.as_ref());
+    inputs.push(vk.pc_start);
+    for domain in prep_domains {
+        inputs.push(BabyBear::from_canonical_usize(domain.log_n));
+        let size = 1 << domain.log_n;
+        inputs.push(BabyBear::from_canonical_usize(size));
+        let g = BabyBear::two_adic_generator(domain.log_n);
+        inputs.push(domain.shift);
+        inputs.push(g);
+    }
+
+    poseidon2_hash(inputs)
+}
+
+/// A verifier for the core and compressed proofs of the SP1 RISC-V zkVM.
+pub struct SP1Verifier {
+    core_machine: StarkMachine<CoreSC, RiscvAir<BabyBear>>,
+    compress_machine: StarkMachine<InnerSC, CompressAir<BabyBear>>,
+}
+
+impl SP1Verifier {
+    /// Initializes a new [SP1Verifier].
+    pub fn new() -> Self {
+        Self {
+            core_machine: RiscvAir::machine(CoreSC::default()),
+            compress_machine: CompressAir::machine(InnerSC::default()),
+        }
+    }
+
+    /// The machine of the core proofs.
+    pub fn core_machine(&self) -> &StarkMachine<CoreSC, RiscvAir<BabyBear>> {
+        &self.core_machine
+    }
+
+    /// The machine of the compressed proofs.
+    pub fn compress_machine(&self) -> &StarkMachine<InnerSC, CompressAir<BabyBear>> {
+        &self.compress_machine
+    }
+
+    /// Verify a core proof, see [verify_core_proof].
+    pub fn verify_core(
+        &self,
+        proof: &[ShardProof<CoreSC>],
+        vk: &StarkVerifyingKey<CoreSC>,
+    ) -> Result<(), MachineVerificationError<CoreSC>> {
+        verify_core_proof(&self.core_machine, proof, vk)
+    }
+
+    /// Verify a compressed proof, see [verify_compressed_proof].
+    pub fn verify_compressed(
+        &self,
+        proof: &ShardProof<InnerSC>,
+        vk: &StarkVerifyingKey<CoreSC>,
+        compress_vk: &StarkVerifyingKey<InnerSC>,
+    ) -> Result<(), MachineVerificationError<InnerSC>> {
+        verify_compressed_proof(&self.compress_machine, proof, vk, compress_vk)
+    }
+}
+
+impl Default for SP1Verifier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sp1_core::io::SP1Stdin;
+    use sp1_core::runtime::Program;
+    use sp1_core::stark::CpuProver;
+    use sp1_core::utils::tests::FIBONACCI_ELF;
+    use sp1_core::utils::{prove, setup_logger, SP1CoreOpts};
+
+    use super::*;
+
+    #[test]
+    fn test_verify_core() {
+        setup_logger();
+        let program = Program::from(FIBONACCI_ELF);
+        let verifier = SP1Verifier::new();
+        let (_, mut vk) = verifier.core_machine().setup(&program);
+        let (proof, _, _) = prove::<_, CpuProver<_, _>>(
+            program,
+            &SP1Stdin::new(),
+            CoreSC::default(),
+            SP1CoreOpts::default(),
+        )
+        .unwrap();
+        verifier.verify_core(&proof.shard_proofs, &vk).unwrap();
+
+        // A proof of the program does not verify against another start pc.
+        vk.pc_start += BabyBear::one();
+        assert!(matches!(
+            verifier.verify_core(&proof.shard_proofs, &vk),
+            Err(MachineVerificationError::InvalidPublicValues(_))
+        ));
+        assert!(matches!(
+            verifier.verify_core(&[], &vk),
+            Err(MachineVerificationError::EmptyProof)
+        ));
+    }
+}
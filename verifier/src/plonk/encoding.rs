@@ -0,0 +1,439 @@
+//! Decoding of the gnark encodings of Plonk BN254 verifying keys and proofs.
+
+use alloc::vec::Vec;
+
+use ark_bn254::{g1, g2, Fq, Fq2, Fr, G1Affine, G2Affine};
+use ark_ec::short_weierstrass::SWCurveConfig;
+use ark_ec::AffineRepr;
+use ark_ff::{BigInteger, Field, PrimeField, Zero};
+
+use super::kzg::{BatchOpeningProof, KzgVerifyingKey, OpeningProof};
+use super::{PlonkError, PlonkProof, PlonkVerifyingKey};
+
+/// The flags stored in the two most significant bits of the first byte of an encoded point.
+const MASK: u8 = 0b11 << 6;
+const UNCOMPRESSED: u8 = 0b00 << 6;
+const COMPRESSED_SMALLEST: u8 = 0b10 << 6;
+const COMPRESSED_LARGEST: u8 = 0b11 << 6;
+const COMPRESSED_INFINITY: u8 = 0b01 << 6;
+
+/// The size of the Solidity encoding of a proof without BSB22 commitments.
+const PROOF_SIZE: usize = 768;
+
+impl PlonkVerifyingKey {
+    /// Decodes a verifying key written by gnark's `VerifyingKey.WriteTo`, i.e. the `vk.bin` file in
+    /// the circuit artifacts.
+    pub fn from_gnark_bytes(bytes: &[u8]) -> Result<Self, PlonkError> {
+        let mut reader = Reader(bytes);
+        let size = reader.u64()?;
+        let size_inv = reader.fr()?;
+        let generator = reader.fr()?;
+        let nb_public_variables = reader.u64()?;
+        let coset_shift = reader.fr()?;
+        let s = [reader.g1()?, reader.g1()?, reader.g1()?];
+        let ql = reader.g1()?;
+        let qr = reader.g1()?;
+        let qm = reader.g1()?;
+        let qo = reader.g1()?;
+        let qk = reader.g1()?;
+        let nb_qcp = reader.u32()? as usize;
+        let qcp = (0..nb_qcp)
+            .map(|_| reader.g1())
+            .collect::<Result<Vec<_>, _>>()?;
+        let kzg = KzgVerifyingKey {
+            g1: reader.g1()?,
+            g2: [reader.g2()?, reader.g2()?],
+        };
+
+        // The commitment constraint indexes are at the end of the key, with one index per BSB22
+        // commitment. Anything in between, like the precomputed pairing lines of recent versions
+        // of gnark, is not needed to verify proofs and is skipped.
+        let indexes_size = 4 + 8 * nb_qcp;
+        let skipped = reader
+            .0
+            .len()
+            .checked_sub(indexes_size)
+            .ok_or(PlonkError::Truncated)?;
+        let mut reader = Reader(&reader.0[skipped..]);
+        if reader.u32()? as usize != nb_qcp {
+            return Err(PlonkError::InvalidVerifyingKey(
+                "the number of commitment constraint indexes does not match the number of qcp",
+            ));
+        }
+        let commitment_constraint_indexes = (0..nb_qcp)
+            .map(|_| reader.u64())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            size,
+            size_inv,
+            generator,
+            nb_public_variables,
+            coset_shift,
+            s,
+            ql,
+            qr,
+            qm,
+            qo,
+            qk,
+            qcp,
+            kzg,
+            commitment_constraint_indexes,
+        })
+    }
+}
+
+impl PlonkProof {
+    /// Decodes a proof written by gnark's `Proof.MarshalSolidity`, i.e. the `encoded_proof` of a
+    /// `PlonkBn254Proof`, for a circuit with `nb_commitments` BSB22 commitments.
+    ///
+    /// The Solidity encoding omits the opening of the linearized polynomial, which the verifier
+    /// computes from the other openings.
+    pub fn from_solidity_bytes(bytes: &[u8], nb_commitments: usize) -> Result<Self, PlonkError> {
+        let expected = PROOF_SIZE + nb_commitments * (32 + 64);
+        if bytes.len() != expected {
+            return Err(PlonkError::InvalidProofLength {
+                expected,
+                found: bytes.len(),
+            });
+        }
+
+        let mut reader = Reader(bytes);
+        let lro = [reader.raw_g1()?, reader.raw_g1()?, reader.raw_g1()?];
+        let h = [reader.raw_g1()?, reader.raw_g1()?, reader.raw_g1()?];
+        let mut claimed_values = (0..5).map(|_| reader.fr()).collect::<Result<Vec<_>, _>>()?;
+        let z = reader.raw_g1()?;
+        let z_shifted_value = reader.fr()?;
+        let batched_h = reader.raw_g1()?;
+        let z_shifted_h = reader.raw_g1()?;
+        for _ in 0..nb_commitments {
+            claimed_values.push(reader.fr()?);
+        }
+        let bsb22_commitments = (0..nb_commitments)
+            .map(|_| reader.raw_g1())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            lro,
+            z,
+            h,
+            bsb22_commitments,
+            batched_proof: BatchOpeningProof {
+                h: batched_h,
+                claimed_values,
+            },
+            z_shifted_opening: OpeningProof {
+                h: z_shifted_h,
+                claimed_value: z_shifted_value,
+            },
+        })
+    }
+}
+
+/// A reader over gnark encoded data.
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], PlonkError> {
+        if self.0.len() < n {
+            return Err(PlonkError::Truncated);
+        }
+        let (bytes, rest) = self.0.split_at(n);
+        self.0 = rest;
+        Ok(bytes)
+    }
+
+    fn u32(&mut self) -> Result<u32, PlonkError> {
+        Ok(u32::from_be_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64, PlonkError> {
+        Ok(u64::from_be_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn fr(&mut self) -> Result<Fr, PlonkError> {
+        fr_from_bytes(self.take(32)?)
+    }
+
+    fn g1(&mut self) -> Result<G1Affine, PlonkError> {
+        g1_from_compressed_bytes(self.take(32)?)
+    }
+
+    fn g2(&mut self) -> Result<G2Affine, PlonkError> {
+        g2_from_compressed_bytes(self.take(64)?)
+    }
+
+    fn raw_g1(&mut self) -> Result<G1Affine, PlonkError> {
+        g1_from_raw_bytes(self.take(64)?)
+    }
+}
+
+/// Decodes a big endian scalar, which must be canonical.
+pub(crate) fn fr_from_bytes(bytes: &[u8]) -> Result<Fr, PlonkError> {
+    let x = Fr::from_be_bytes_mod_order(bytes);
+    if fr_to_bytes(&x) != bytes {
+        return Err(PlonkError::InvalidScalar);
+    }
+    Ok(x)
+}
+
+/// Encodes a scalar in big endian, like gnark's `fr.Element.Marshal`.
+pub(crate) fn fr_to_bytes(x: &Fr) -> [u8; 32] {
+    x.into_bigint().to_bytes_be().try_into().unwrap()
+}
+
+/// Encodes a point uncompressed, like gnark's `G1Affine.RawBytes`.
+pub(crate) fn g1_to_raw_bytes(p: &G1Affine) -> [u8; 64] {
+    let mut bytes = [0u8; 64];
+    if let Some((x, y)) = p.xy() {
+        bytes[..32].copy_from_slice(&fq_to_bytes(x));
+        bytes[32..].copy_from_slice(&fq_to_bytes(y));
+    }
+    bytes
+}
+
+/// Decodes an uncompressed point, where the point at infinity is encoded as zeros.
+pub(crate) fn g1_from_raw_bytes(bytes: &[u8]) -> Result<G1Affine, PlonkError> {
+    if bytes.iter().all(|&b| b == 0) {
+        return Ok(G1Affine::zero());
+    }
+    if bytes[0] & MASK != UNCOMPRESSED {
+        return Err(PlonkError::InvalidPoint);
+    }
+    let p = G1Affine::new_unchecked(fq_from_bytes(&bytes[..32])?, fq_from_bytes(&bytes[32..])?);
+    if !p.is_on_curve() {
+        return Err(PlonkError::InvalidPoint);
+    }
+    Ok(p)
+}
+
+/// Decodes a compressed point, given by its `x` coordinate and the sign of its `y` coordinate.
+pub(crate) fn g1_from_compressed_bytes(bytes: &[u8]) -> Result<G1Affine, PlonkError> {
+    let flag = bytes[0] & MASK;
+    let mut x = <[u8; 32]>::try_from(bytes).map_err(|_| PlonkError::InvalidPoint)?;
+    x[0] &= !MASK;
+    if flag == COMPRESSED_INFINITY && x.iter().all(|&b| b == 0) {
+        return Ok(G1Affine::zero());
+    }
+    if flag != COMPRESSED_SMALLEST && flag != COMPRESSED_LARGEST {
+        return Err(PlonkError::InvalidPoint);
+    }
+
+    let x = fq_from_bytes(&x)?;
+    let mut y = (x.square() * x + g1::Config::COEFF_B)
+        .sqrt()
+        .ok_or(PlonkError::InvalidPoint)?;
+    if fq_is_largest(&y) != (flag == COMPRESSED_LARGEST) {
+        y = -y;
+    }
+    Ok(G1Affine::new_unchecked(x, y))
+}
+
+/// Decodes a compressed point of G2, given as `x.c1 || x.c0` and the sign of its `y` coordinate.
+pub(crate) fn g2_from_compressed_bytes(bytes: &[u8]) -> Result<G2Affine, PlonkError> {
+    let flag = bytes[0] & MASK;
+    let mut c1 = <[u8; 32]>::try_from(&bytes[..32]).map_err(|_| PlonkError::InvalidPoint)?;
+    c1[0] &= !MASK;
+    if flag == COMPRESSED_INFINITY && c1.iter().chain(&bytes[32..]).all(|&b| b == 0) {
+        return Ok(G2Affine::zero());
+    }
+    if flag != COMPRESSED_SMALLEST && flag != COMPRESSED_LARGEST {
+        return Err(PlonkError::InvalidPoint);
+    }
+
+    let x = Fq2::new(fq_from_bytes(&bytes[32..])?, fq_from_bytes(&c1)?);
+    let mut y = (x.square() * x + g2::Config::COEFF_B)
+        .sqrt()
+        .ok_or(PlonkError::InvalidPoint)?;
+    let is_largest = if y.c1.is_zero() {
+        fq_is_largest(&y.c0)
+    } else {
+        fq_is_largest(&y.c1)
+    };
+    if is_largest != (flag == COMPRESSED_LARGEST) {
+        y = -y;
+    }
+    let p = G2Affine::new_unchecked(x, y);
+    if !p.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(PlonkError::InvalidPoint);
+    }
+    Ok(p)
+}
+
+fn fq_from_bytes(bytes: &[u8]) -> Result<Fq, PlonkError> {
+    let x = Fq::from_be_bytes_mod_order(bytes);
+    if fq_to_bytes(&x) != bytes {
+        return Err(PlonkError::InvalidPoint);
+    }
+    Ok(x)
+}
+
+fn fq_to_bytes(x: &Fq) -> [u8; 32] {
+    x.into_bigint().to_bytes_be().try_into().unwrap()
+}
+
+/// Whether `y` is greater than `-y`, like gnark's `LexicographicallyLargest`.
+fn fq_is_largest(y: &Fq) -> bool {
+    y.into_bigint() > Fq::MODULUS_MINUS_ONE_DIV_TWO
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use ark_ec::{AffineRepr, CurveGroup};
+    use ark_ff::PrimeField;
+
+    use super::*;
+
+    /// Encodes a point compressed, like gnark's `G1Affine.Bytes`.
+    pub(crate) fn g1_to_compressed_bytes(p: &G1Affine) -> [u8; 32] {
+        let Some((x, y)) = p.xy() else {
+            let mut bytes = [0u8; 32];
+            bytes[0] = COMPRESSED_INFINITY;
+            return bytes;
+        };
+        let mut bytes = fq_to_bytes(x);
+        bytes[0] |= if fq_is_largest(y) {
+            COMPRESSED_LARGEST
+        } else {
+            COMPRESSED_SMALLEST
+        };
+        bytes
+    }
+
+    /// Encodes a point of G2 compressed, like gnark's `G2Affine.Bytes`.
+    pub(crate) fn g2_to_compressed_bytes(p: &G2Affine) -> [u8; 64] {
+        let (x, y) = p.xy().unwrap();
+        let mut bytes = [0u8; 64];
+        bytes[..32].copy_from_slice(&fq_to_bytes(&x.c1));
+        bytes[32..].copy_from_slice(&fq_to_bytes(&x.c0));
+        let is_largest = if y.c1.is_zero() {
+            fq_is_largest(&y.c0)
+        } else {
+            fq_is_largest(&y.c1)
+        };
+        bytes[0] |= if is_largest {
+            COMPRESSED_LARGEST
+        } else {
+            COMPRESSED_SMALLEST
+        };
+        bytes
+    }
+
+    #[test]
+    fn test_point_encodings() {
+        for k in [1u64, 2, 7, 1 << 40] {
+            let scalar = Fr::from(k);
+            for p in [
+                (G1Affine::generator() * scalar).into_affine(),
+                (-G1Affine::generator() * scalar).into_affine(),
+            ] {
+                let raw = g1_to_raw_bytes(&p);
+                assert_eq!(g1_from_raw_bytes(&raw).unwrap(), p);
+                let compressed = g1_to_compressed_bytes(&p);
+                assert_eq!(g1_from_compressed_bytes(&compressed).unwrap(), p);
+            }
+            for p in [
+                (G2Affine::generator() * scalar).into_affine(),
+                (-G2Affine::generator() * scalar).into_affine(),
+            ] {
+                let compressed = g2_to_compressed_bytes(&p);
+                assert_eq!(g2_from_compressed_bytes(&compressed).unwrap(), p);
+            }
+        }
+
+        assert!(g1_from_raw_bytes(&[0u8; 64]).unwrap().is_zero());
+        let mut bytes = g1_to_raw_bytes(&G1Affine::generator());
+        bytes[63] ^= 1;
+        assert!(matches!(
+            g1_from_raw_bytes(&bytes),
+            Err(PlonkError::InvalidPoint)
+        ));
+    }
+
+    #[test]
+    fn test_key_and_proof_encodings() {
+        let point = |k: u64| (G1Affine::generator() * Fr::from(k)).into_affine();
+        let g2 = [
+            G2Affine::generator(),
+            (G2Affine::generator() * Fr::from(3u64)).into_affine(),
+        ];
+
+        let mut bytes = Vec::new();
+        bytes.extend(16u64.to_be_bytes());
+        bytes.extend(fr_to_bytes(&Fr::from(16u64).inverse().unwrap()));
+        bytes.extend(fr_to_bytes(&Fr::from(5u64)));
+        bytes.extend(2u64.to_be_bytes());
+        bytes.extend(fr_to_bytes(&Fr::from(7u64)));
+        for k in 1..=8 {
+            bytes.extend(g1_to_compressed_bytes(&point(k)));
+        }
+        bytes.extend(1u32.to_be_bytes());
+        bytes.extend(g1_to_compressed_bytes(&point(9)));
+        bytes.extend(g1_to_compressed_bytes(&G1Affine::generator()));
+        bytes.extend(g2_to_compressed_bytes(&g2[0]));
+        bytes.extend(g2_to_compressed_bytes(&g2[1]));
+        // Precomputed pairing lines, which are skipped.
+        bytes.extend([0xab; 100]);
+        bytes.extend(1u32.to_be_bytes());
+        bytes.extend(42u64.to_be_bytes());
+
+        let vk = PlonkVerifyingKey::from_gnark_bytes(&bytes).unwrap();
+        assert_eq!(vk.size, 16);
+        assert_eq!(vk.nb_public_variables, 2);
+        assert_eq!(vk.s, [point(1), point(2), point(3)]);
+        assert_eq!(
+            [vk.ql, vk.qr, vk.qm, vk.qo, vk.qk],
+            [4, 5, 6, 7, 8].map(point)
+        );
+        assert_eq!(vk.qcp, vec![point(9)]);
+        assert_eq!(vk.kzg.g2, g2);
+        assert_eq!(vk.commitment_constraint_indexes, vec![42]);
+        assert!(matches!(
+            PlonkVerifyingKey::from_gnark_bytes(&bytes[..200]),
+            Err(PlonkError::Truncated)
+        ));
+
+        let mut bytes = Vec::new();
+        for k in 1..=6 {
+            bytes.extend(g1_to_raw_bytes(&point(k)));
+        }
+        for k in 1..=5 {
+            bytes.extend(fr_to_bytes(&Fr::from(k)));
+        }
+        bytes.extend(g1_to_raw_bytes(&point(7)));
+        bytes.extend(fr_to_bytes(&Fr::from(6u64)));
+        bytes.extend(g1_to_raw_bytes(&point(8)));
+        bytes.extend(g1_to_raw_bytes(&point(9)));
+        bytes.extend(fr_to_bytes(&Fr::from(7u64)));
+        bytes.extend(g1_to_raw_bytes(&point(10)));
+
+        let proof = PlonkProof::from_solidity_bytes(&bytes, 1).unwrap();
+        assert_eq!(proof.lro, [point(1), point(2), point(3)]);
+        assert_eq!(proof.h, [point(4), point(5), point(6)]);
+        assert_eq!(proof.z, point(7));
+        assert_eq!(
+            proof.batched_proof.claimed_values,
+            [1u64, 2, 3, 4, 5, 7].map(Fr::from)
+        );
+        assert_eq!(proof.batched_proof.h, point(8));
+        assert_eq!(proof.z_shifted_opening.h, point(9));
+        assert_eq!(proof.z_shifted_opening.claimed_value, Fr::from(6u64));
+        assert_eq!(proof.bsb22_commitments, vec![point(10)]);
+        assert!(matches!(
+            PlonkProof::from_solidity_bytes(&bytes, 0),
+            Err(PlonkError::InvalidProofLength { .. })
+        ));
+    }
+
+    #[test]
+    fn test_scalar_encoding() {
+        let x = -Fr::from(5u64);
+        assert_eq!(fr_from_bytes(&fr_to_bytes(&x)).unwrap(), x);
+
+        let modulus: [u8; 32] = Fr::MODULUS.to_bytes_be().try_into().unwrap();
+        assert!(matches!(
+            fr_from_bytes(&modulus),
+            Err(PlonkError::InvalidScalar)
+        ));
+    }
+}
@@ -0,0 +1,231 @@
+//! The verification of KZG opening proofs, following gnark-crypto.
+
+use alloc::vec::Vec;
+
+use ark_bn254::{Bn254, Fr, G1Affine, G1Projective, G2Affine};
+use ark_ec::pairing::Pairing;
+use ark_ec::{CurveGroup, VariableBaseMSM};
+use ark_ff::One;
+
+use super::encoding::{fr_to_bytes, g1_to_raw_bytes};
+use super::transcript::Transcript;
+use super::PlonkError;
+
+/// The verifying key of the KZG commitment scheme.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KzgVerifyingKey {
+    /// The generator of G1.
+    pub g1: G1Affine,
+    /// The generator of G2 and its multiple by the secret of the trusted setup.
+    pub g2: [G2Affine; 2],
+}
+
+/// A proof of the opening of a polynomial at a point.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpeningProof {
+    /// The commitment to the quotient of the opening.
+    pub h: G1Affine,
+    /// The claimed value of the polynomial at the point.
+    pub claimed_value: Fr,
+}
+
+/// A proof of the opening of several polynomials at the same point.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchOpeningProof {
+    /// The commitment to the folded quotient of the openings.
+    pub h: G1Affine,
+    /// The claimed values of the polynomials at the point.
+    pub claimed_values: Vec<Fr>,
+}
+
+/// Folds the openings of `digests` at `point` into the opening of a single digest.
+///
+/// The folding challenge is bound to the point, the digests, the claimed values and `data`.
+pub(crate) fn fold_proof(
+    digests: &[G1Affine],
+    proof: &BatchOpeningProof,
+    point: Fr,
+    data: &[u8],
+) -> Result<(OpeningProof, G1Affine), PlonkError> {
+    if digests.len() != proof.claimed_values.len() {
+        return Err(PlonkError::InvalidOpeningProof);
+    }
+
+    let mut transcript = Transcript::new(&["gamma"]);
+    transcript.bind("gamma", &fr_to_bytes(&point));
+    for digest in digests {
+        transcript.bind("gamma", &g1_to_raw_bytes(digest));
+    }
+    for value in &proof.claimed_values {
+        transcript.bind("gamma", &fr_to_bytes(value));
+    }
+    transcript.bind("gamma", data);
+    let gamma = transcript.compute_scalar("gamma");
+
+    let (digest, claimed_value) = fold(digests, &proof.claimed_values, gamma);
+    Ok((
+        OpeningProof {
+            h: proof.h,
+            claimed_value,
+        },
+        digest,
+    ))
+}
+
+/// Verifies the openings of `digests` at the corresponding `points` with a single pairing check.
+pub(crate) fn batch_verify_multi_points(
+    digests: &[G1Affine],
+    proofs: &[OpeningProof],
+    points: &[Fr],
+    vk: &KzgVerifyingKey,
+) -> Result<(), PlonkError> {
+    if digests.len() != proofs.len() || digests.len() != points.len() {
+        return Err(PlonkError::InvalidOpeningProof);
+    }
+
+    // The openings are combined with the powers of a challenge bound to all of them, where
+    // gnark-crypto samples random coefficients instead.
+    let mut transcript = Transcript::new(&["lambda"]);
+    for ((digest, proof), point) in digests.iter().zip(proofs).zip(points) {
+        transcript.bind("lambda", &g1_to_raw_bytes(digest));
+        transcript.bind("lambda", &g1_to_raw_bytes(&proof.h));
+        transcript.bind("lambda", &fr_to_bytes(&proof.claimed_value));
+        transcript.bind("lambda", &fr_to_bytes(point));
+    }
+    let lambda = transcript.compute_scalar("lambda");
+
+    // ∑ᵢλᵢ[Hᵢ(α)]G₁
+    let quotients = proofs.iter().map(|proof| proof.h).collect::<Vec<_>>();
+    let lambdas = powers(lambda, digests.len());
+    let folded_quotients = msm(&quotients, &lambdas);
+
+    // ∑ᵢλᵢ[fᵢ(α)]G₁ - [∑ᵢλᵢfᵢ(pᵢ)]G₁ + ∑ᵢλᵢpᵢ[Hᵢ(α)]G₁
+    let values = proofs
+        .iter()
+        .map(|proof| proof.claimed_value)
+        .collect::<Vec<_>>();
+    let (folded_digests, folded_values) = fold(digests, &values, lambda);
+    let scaled_lambdas = lambdas
+        .iter()
+        .zip(points)
+        .map(|(lambda, point)| *lambda * point)
+        .collect::<Vec<_>>();
+    let lhs = G1Projective::from(folded_digests) - vk.g1 * folded_values
+        + msm(&quotients, &scaled_lambdas);
+
+    // e(∑ᵢλᵢ[fᵢ(α) - fᵢ(pᵢ) + pᵢHᵢ(α)]G₁, G₂) * e(-∑ᵢλᵢ[Hᵢ(α)]G₁, [α]G₂) = 1
+    let check = Bn254::multi_pairing(
+        [lhs.into_affine(), (-folded_quotients).into_affine()],
+        vk.g2,
+    );
+    if !check.0.is_one() {
+        return Err(PlonkError::InvalidOpeningProof);
+    }
+    Ok(())
+}
+
+/// Returns `(∑ᵢγⁱ digestsᵢ, ∑ᵢγⁱ valuesᵢ)`.
+fn fold(digests: &[G1Affine], values: &[Fr], gamma: Fr) -> (G1Affine, Fr) {
+    let gammas = powers(gamma, digests.len());
+    let digest = msm(digests, &gammas).into_affine();
+    let value = values.iter().zip(&gammas).map(|(v, g)| *v * g).sum();
+    (digest, value)
+}
+
+/// Returns `[1, x, x², ..., xⁿ⁻¹]`.
+fn powers(x: Fr, n: usize) -> Vec<Fr> {
+    core::iter::successors(Some(Fr::one()), |power| Some(*power * x))
+        .take(n)
+        .collect()
+}
+
+fn msm(bases: &[G1Affine], scalars: &[Fr]) -> G1Projective {
+    G1Projective::msm(bases, scalars).expect("bases and scalars have the same length")
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_ec::AffineRepr;
+
+    use super::*;
+
+    /// Commits to the polynomial with the given coefficients, with a known trusted setup secret.
+    fn commit(coefficients: &[Fr], tau: Fr) -> G1Affine {
+        (G1Affine::generator() * evaluate(coefficients, tau)).into_affine()
+    }
+
+    fn evaluate(coefficients: &[Fr], x: Fr) -> Fr {
+        coefficients
+            .iter()
+            .rev()
+            .fold(Fr::from(0u64), |acc, c| acc * x + c)
+    }
+
+    /// Opens the polynomial at `point`, with a known trusted setup secret.
+    fn open(coefficients: &[Fr], point: Fr, tau: Fr) -> OpeningProof {
+        let claimed_value = evaluate(coefficients, point);
+        let quotient = (evaluate(coefficients, tau) - claimed_value) / (tau - point);
+        OpeningProof {
+            h: (G1Affine::generator() * quotient).into_affine(),
+            claimed_value,
+        }
+    }
+
+    #[test]
+    fn test_batch_verify_multi_points() {
+        let tau = Fr::from(0x5eed_u64);
+        let vk = KzgVerifyingKey {
+            g1: G1Affine::generator(),
+            g2: [
+                G2Affine::generator(),
+                (G2Affine::generator() * tau).into_affine(),
+            ],
+        };
+        let f = [1u64, 2, 3, 4].map(Fr::from);
+        let g = [5u64, 0, 7].map(Fr::from);
+        let digests = [commit(&f, tau), commit(&g, tau)];
+        let points = [Fr::from(11u64), Fr::from(13u64)];
+        let mut proofs = [open(&f, points[0], tau), open(&g, points[1], tau)];
+        batch_verify_multi_points(&digests, &proofs, &points, &vk).unwrap();
+
+        proofs[1].claimed_value += Fr::one();
+        assert!(matches!(
+            batch_verify_multi_points(&digests, &proofs, &points, &vk),
+            Err(PlonkError::InvalidOpeningProof)
+        ));
+    }
+
+    #[test]
+    fn test_fold_proof() {
+        let tau = Fr::from(0x5eed_u64);
+        let vk = KzgVerifyingKey {
+            g1: G1Affine::generator(),
+            g2: [
+                G2Affine::generator(),
+                (G2Affine::generator() * tau).into_affine(),
+            ],
+        };
+        let polynomials = [[1u64, 2, 3].map(Fr::from), [4u64, 5, 6].map(Fr::from)];
+        let digests = polynomials.map(|p| commit(&p, tau));
+        let point = Fr::from(17u64);
+
+        // Open the polynomial folded with the same challenge as the verifier.
+        let mut transcript = Transcript::new(&["gamma"]);
+        transcript.bind("gamma", &fr_to_bytes(&point));
+        for digest in &digests {
+            transcript.bind("gamma", &g1_to_raw_bytes(digest));
+        }
+        for p in &polynomials {
+            transcript.bind("gamma", &fr_to_bytes(&evaluate(p, point)));
+        }
+        let gamma = transcript.compute_scalar("gamma");
+        let folded = [0, 1, 2].map(|i| polynomials[0][i] + gamma * polynomials[1][i]);
+
+        let proof = BatchOpeningProof {
+            h: open(&folded, point, tau).h,
+            claimed_values: polynomials.iter().map(|p| evaluate(p, point)).collect(),
+        };
+        let (opening, digest) = fold_proof(&digests, &proof, point, &[]).unwrap();
+        batch_verify_multi_points(&[digest], &[opening], &[point], &vk).unwrap();
+    }
+}
@@ -0,0 +1,300 @@
+//! A native verifier for the Plonk BN254 proofs generated by gnark.
+//!
+//! The proofs are decoded from their Solidity encoding, i.e. the `encoded_proof` of a
+//! `PlonkBn254Proof`, and the verifying key from the `vk.bin` file in the circuit artifacts. The
+//! verification follows gnark's `plonk.Verify`, so it does not need the Go toolchain or Docker.
+//!
+//! Until the fixture generated by gnark (see `TestPlonkFixture` in `recursion/gnark-ffi/go/sp1`)
+//! is committed and the tests against it pass, `SP1Prover` still verifies Plonk proofs with gnark
+//! unless `SP1_NATIVE_PLONK_VERIFIER` is set.
+
+mod encoding;
+mod kzg;
+mod transcript;
+mod verify;
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::path::Path;
+
+use ark_bn254::{Fr, G1Affine};
+use ark_ff::PrimeField;
+use num_bigint::BigUint;
+use p3_field::PrimeField32;
+use sha2::{Digest, Sha256};
+use sp1_core::io::SP1PublicValues;
+use sp1_core::stark::StarkVerifyingKey;
+
+pub use kzg::{BatchOpeningProof, KzgVerifyingKey, OpeningProof};
+pub use verify::verify_plonk;
+
+use crate::{vk_digest, CoreSC};
+
+/// The name of the verifying key file in the circuit artifacts.
+pub const PLONK_VK_FILE: &str = "vk.bin";
+
+/// The verifying key of a Plonk circuit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlonkVerifyingKey {
+    /// The size of the evaluation domain.
+    pub size: u64,
+    /// The inverse of the size of the evaluation domain.
+    pub size_inv: Fr,
+    /// The generator of the evaluation domain.
+    pub generator: Fr,
+    /// The number of public inputs.
+    pub nb_public_variables: u64,
+    /// The shift of the cosets of the evaluation domain.
+    pub coset_shift: Fr,
+    /// The commitments to the permutation polynomials.
+    pub s: [G1Affine; 3],
+    /// The commitments to the selector polynomials.
+    pub ql: G1Affine,
+    pub qr: G1Affine,
+    pub qm: G1Affine,
+    pub qo: G1Affine,
+    pub qk: G1Affine,
+    /// The commitments to the selector polynomials of the BSB22 commitments.
+    pub qcp: Vec<G1Affine>,
+    /// The verifying key of the KZG commitment scheme.
+    pub kzg: KzgVerifyingKey,
+    /// The indexes of the constraints of the BSB22 commitments.
+    pub commitment_constraint_indexes: Vec<u64>,
+}
+
+/// A Plonk proof.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlonkProof {
+    /// The commitments to the left, right and output wires.
+    pub lro: [G1Affine; 3],
+    /// The commitment to the permutation polynomial.
+    pub z: G1Affine,
+    /// The commitments to the parts of the quotient polynomial.
+    pub h: [G1Affine; 3],
+    /// The BSB22 commitments.
+    pub bsb22_commitments: Vec<G1Affine>,
+    /// The opening at ζ of the wires, the first two permutation polynomials and the selectors of
+    /// the BSB22 commitments, in this order.
+    pub batched_proof: BatchOpeningProof,
+    /// The opening of the permutation polynomial at ωζ.
+    pub z_shifted_opening: OpeningProof,
+}
+
+#[derive(Debug)]
+pub enum PlonkError {
+    #[cfg(feature = "std")]
+    Io(std::io::Error),
+    Hex(hex::FromHexError),
+    Truncated,
+    InvalidPoint,
+    InvalidScalar,
+    InvalidVerifyingKey(&'static str),
+    InvalidProofLength {
+        expected: usize,
+        found: usize,
+    },
+    InvalidPublicInput(String),
+    InvalidPublicInputsLength {
+        expected: u64,
+        found: usize,
+    },
+    InvalidOpeningProof,
+    PlonkVerifyingKeyMismatch,
+    InvalidVerificationKey,
+    InvalidPublicValues,
+}
+
+// The error is implemented by hand rather than with `thiserror`, which needs `std` on our toolchain.
+impl fmt::Display for PlonkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            #[cfg(feature = "std")]
+            Self::Io(e) => write!(f, "io error: {}", e),
+            Self::Hex(e) => write!(f, "invalid hex encoding: {}", e),
+            Self::Truncated => write!(f, "the encoding is truncated"),
+            Self::InvalidPoint => write!(f, "invalid encoding of a point"),
+            Self::InvalidScalar => write!(f, "invalid encoding of a scalar"),
+            Self::InvalidVerifyingKey(reason) => write!(f, "invalid verifying key: {}", reason),
+            Self::InvalidProofLength { expected, found } => {
+                write!(f, "invalid proof length {}, expected {}", found, expected)
+            }
+            Self::InvalidPublicInput(input) => write!(f, "invalid public input {}", input),
+            Self::InvalidPublicInputsLength { expected, found } => write!(
+                f,
+                "invalid number of public inputs {}, expected {}",
+                found, expected
+            ),
+            Self::InvalidOpeningProof => write!(f, "the opening proof is invalid"),
+            Self::PlonkVerifyingKeyMismatch => write!(
+                f,
+                "the proof was generated with a different plonk verifying key"
+            ),
+            Self::InvalidVerificationKey => write!(
+                f,
+                "the verifying key does not match the inner plonk bn254 proof's committed verifying key"
+            ),
+            Self::InvalidPublicValues => write!(
+                f,
+                "the public values in the sp1 proof do not match the public values in the inner plonk bn254 proof"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PlonkError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Hex(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for PlonkError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<hex::FromHexError> for PlonkError {
+    fn from(e: hex::FromHexError) -> Self {
+        Self::Hex(e)
+    }
+}
+
+/// A verifier for the Plonk BN254 proofs of a circuit.
+#[derive(Debug, Clone)]
+pub struct PlonkBn254Verifier {
+    vk: PlonkVerifyingKey,
+    vkey_hash: [u8; 32],
+}
+
+impl PlonkBn254Verifier {
+    /// Creates a verifier from the bytes of the `vk.bin` file of the circuit.
+    pub fn new(vk_bytes: &[u8]) -> Result<Self, PlonkError> {
+        Ok(Self {
+            vk: PlonkVerifyingKey::from_gnark_bytes(vk_bytes)?,
+            vkey_hash: Sha256::digest(vk_bytes).into(),
+        })
+    }
+
+    /// Loads the verifier from the circuit artifacts in the build directory.
+    #[cfg(feature = "std")]
+    pub fn load(build_dir: &Path) -> Result<Self, PlonkError> {
+        Self::new(&std::fs::read(build_dir.join(PLONK_VK_FILE))?)
+    }
+
+    /// The verifying key of the circuit.
+    pub fn vk(&self) -> &PlonkVerifyingKey {
+        &self.vk
+    }
+
+    /// The SHA-256 hash of the verifying key file, which is committed to by the proofs as
+    /// `plonk_vkey_hash`.
+    pub fn vkey_hash(&self) -> [u8; 32] {
+        self.vkey_hash
+    }
+
+    /// Verifies a proof, given as the hex `encoded_proof` and the decimal `public_inputs` of a
+    /// `PlonkBn254Proof`.
+    pub fn verify(&self, encoded_proof: &str, public_inputs: &[String]) -> Result<(), PlonkError> {
+        let proof =
+            PlonkProof::from_solidity_bytes(&hex::decode(encoded_proof)?, self.vk.qcp.len())?;
+        let public_inputs = public_inputs
+            .iter()
+            .map(|input| parse_public_input(input).map(Fr::from))
+            .collect::<Result<Vec<_>, _>>()?;
+        verify_plonk(&proof, &self.vk, &public_inputs)
+    }
+}
+
+/// Verify the vk_hash and public_values_hash in the public inputs of a Plonk BN254 proof match the
+/// expected values.
+pub fn verify_plonk_bn254_public_inputs(
+    vk: &StarkVerifyingKey<CoreSC>,
+    public_values: &SP1PublicValues,
+    public_inputs: &[String],
+) -> Result<(), PlonkError> {
+    let [expected_vk_hash, expected_public_values_hash] = public_inputs else {
+        return Err(PlonkError::InvalidPublicInputsLength {
+            expected: 2,
+            found: public_inputs.len(),
+        });
+    };
+
+    // The digest of the verifying key, packed in a BN254 scalar with 31 bits per element.
+    let vk_hash = vk_digest(vk).iter().fold(BigUint::default(), |acc, word| {
+        (acc << 31) + word.as_canonical_u32()
+    });
+    if vk_hash != parse_public_input(expected_vk_hash)? {
+        return Err(PlonkError::InvalidVerificationKey);
+    }
+
+    if public_values.hash() != parse_public_input(expected_public_values_hash)? {
+        return Err(PlonkError::InvalidPublicValues);
+    }
+
+    Ok(())
+}
+
+/// Parses a decimal public input, which must be less than the scalar field modulus.
+fn parse_public_input(input: &str) -> Result<BigUint, PlonkError> {
+    match BigUint::parse_bytes(input.as_bytes(), 10) {
+        Some(value) if value < Fr::MODULUS.into() => Ok(value),
+        _ => Err(PlonkError::InvalidPublicInput(input.to_string())),
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use super::*;
+
+    /// Reads the fixture written by `TestPlonkFixture` in `recursion/gnark-ffi/go/sp1`: a proof of a
+    /// small circuit with two public inputs and a BSB22 commitment, generated by gnark.
+    fn read_fixture() -> (PlonkBn254Verifier, String, Vec<String>) {
+        let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src/plonk/testdata");
+        let verifier = PlonkBn254Verifier::load(&dir).unwrap();
+        let encoded_proof = fs::read_to_string(dir.join("proof.hex")).unwrap();
+        let public_inputs = fs::read_to_string(dir.join("public_inputs.txt"))
+            .unwrap()
+            .lines()
+            .map(ToString::to_string)
+            .collect();
+        (verifier, encoded_proof.trim().to_string(), public_inputs)
+    }
+
+    #[test]
+    #[ignore = "needs the gnark fixture, written by TestPlonkFixture with SP1_WRITE_PLONK_FIXTURE=1"]
+    fn test_verify_gnark_fixture() {
+        let (verifier, encoded_proof, public_inputs) = read_fixture();
+        assert_eq!(verifier.vk().nb_public_variables, 2);
+        assert_eq!(verifier.vk().qcp.len(), 1);
+        verifier.verify(&encoded_proof, &public_inputs).unwrap();
+    }
+
+    #[test]
+    #[ignore = "needs the gnark fixture, written by TestPlonkFixture with SP1_WRITE_PLONK_FIXTURE=1"]
+    fn test_verify_tampered_gnark_fixture() {
+        let (verifier, encoded_proof, public_inputs) = read_fixture();
+
+        let mut tampered_inputs = public_inputs.clone();
+        tampered_inputs[1] = "6".to_string();
+        assert!(verifier.verify(&encoded_proof, &tampered_inputs).is_err());
+
+        // Change the opening of the permutation polynomial at ωζ, which follows the six wire and
+        // quotient commitments, the five claimed values and the permutation commitment.
+        let mut tampered_proof = hex::decode(&encoded_proof).unwrap();
+        tampered_proof[6 * 64 + 5 * 32 + 64 + 31] ^= 1;
+        assert!(verifier
+            .verify(&hex::encode(tampered_proof), &public_inputs)
+            .is_err());
+    }
+}
@@ -0,0 +1,157 @@
+//! The Fiat-Shamir transcript and the hash to field of gnark.
+
+use alloc::vec::Vec;
+
+use ark_bn254::Fr;
+use ark_ff::PrimeField;
+use sha2::{Digest, Sha256};
+
+/// A Fiat-Shamir transcript with named challenges, like gnark's `fiatshamir.Transcript`.
+///
+/// A challenge is the SHA-256 hash of its name, the previous challenge and the values bound to it.
+pub(crate) struct Transcript {
+    challenges: Vec<Challenge>,
+}
+
+struct Challenge {
+    name: &'static str,
+    bindings: Vec<u8>,
+    value: Option<[u8; 32]>,
+}
+
+impl Transcript {
+    /// Creates a transcript for the challenges with the given names, in order.
+    pub(crate) fn new(names: &[&'static str]) -> Self {
+        Self {
+            challenges: names
+                .iter()
+                .map(|&name| Challenge {
+                    name,
+                    bindings: Vec::new(),
+                    value: None,
+                })
+                .collect(),
+        }
+    }
+
+    /// Binds a value to the challenge with the given name.
+    pub(crate) fn bind(&mut self, name: &str, value: &[u8]) {
+        let position = self.position(name);
+        let challenge = &mut self.challenges[position];
+        assert!(
+            challenge.value.is_none(),
+            "challenge {name} is already computed"
+        );
+        challenge.bindings.extend_from_slice(value);
+    }
+
+    /// Computes the challenge with the given name, which must follow the last computed challenge.
+    pub(crate) fn compute_challenge(&mut self, name: &str) -> [u8; 32] {
+        let position = self.position(name);
+        if let Some(value) = self.challenges[position].value {
+            return value;
+        }
+
+        let mut hasher = Sha256::new();
+        hasher.update(name.as_bytes());
+        if position != 0 {
+            let previous = self.challenges[position - 1]
+                .value
+                .unwrap_or_else(|| panic!("challenge preceding {name} is not computed"));
+            hasher.update(previous);
+        }
+        hasher.update(&self.challenges[position].bindings);
+        let value: [u8; 32] = hasher.finalize().into();
+        self.challenges[position].value = Some(value);
+        value
+    }
+
+    /// Computes the challenge with the given name, reduced to a scalar.
+    pub(crate) fn compute_scalar(&mut self, name: &str) -> Fr {
+        Fr::from_be_bytes_mod_order(&self.compute_challenge(name))
+    }
+
+    fn position(&self, name: &str) -> usize {
+        self.challenges
+            .iter()
+            .position(|challenge| challenge.name == name)
+            .unwrap_or_else(|| panic!("unknown challenge {name}"))
+    }
+}
+
+/// Hashes a message to a scalar, following RFC 9380 with `expand_message_xmd` and SHA-256.
+pub(crate) fn hash_to_field(msg: &[u8], dst: &[u8]) -> Fr {
+    // 48 bytes are enough for the reduction modulo the 254 bits modulus to be unbiased.
+    Fr::from_be_bytes_mod_order(&expand_message_xmd(msg, dst, 48))
+}
+
+/// The `expand_message_xmd` function of RFC 9380, with SHA-256.
+fn expand_message_xmd(msg: &[u8], dst: &[u8], len: usize) -> Vec<u8> {
+    let ell = len.div_ceil(32);
+    assert!(ell <= 255 && dst.len() <= 255 && len <= u16::MAX as usize);
+    let dst_prime = [dst, &[dst.len() as u8]].concat();
+
+    // b_0 = H(Z_pad || msg || I2OSP(len, 2) || I2OSP(0, 1) || DST_prime)
+    let b_0: [u8; 32] = Sha256::new()
+        .chain_update([0u8; 64])
+        .chain_update(msg)
+        .chain_update((len as u16).to_be_bytes())
+        .chain_update([0u8])
+        .chain_update(&dst_prime)
+        .finalize()
+        .into();
+
+    // b_1 = H(b_0 || I2OSP(1, 1) || DST_prime)
+    // b_i = H(strxor(b_0, b_(i - 1)) || I2OSP(i, 1) || DST_prime)
+    let mut uniform_bytes = Vec::with_capacity(ell * 32);
+    let mut b_i = [0u8; 32];
+    for i in 1..=ell {
+        let mut input = b_0;
+        for (x, y) in input.iter_mut().zip(b_i) {
+            *x ^= y;
+        }
+        b_i = Sha256::new()
+            .chain_update(input)
+            .chain_update([i as u8])
+            .chain_update(&dst_prime)
+            .finalize()
+            .into();
+        uniform_bytes.extend_from_slice(&b_i);
+    }
+    uniform_bytes.truncate(len);
+    uniform_bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_message_xmd() {
+        // Test vectors from RFC 9380, appendix K.1.
+        let dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
+        assert_eq!(
+            hex::encode(expand_message_xmd(b"", dst, 0x20)),
+            "68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235"
+        );
+        assert_eq!(
+            hex::encode(expand_message_xmd(b"abc", dst, 0x20)),
+            "d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615"
+        );
+    }
+
+    #[test]
+    fn test_transcript() {
+        let mut transcript = Transcript::new(&["alpha", "beta"]);
+        transcript.bind("alpha", b"one");
+        transcript.bind("alpha", b"two");
+        let alpha = transcript.compute_challenge("alpha");
+        let expected: [u8; 32] = Sha256::digest(b"alphaonetwo").into();
+        assert_eq!(alpha, expected);
+
+        let beta = transcript.compute_challenge("beta");
+        let expected: [u8; 32] = Sha256::digest([b"beta".as_slice(), &alpha].concat()).into();
+        assert_eq!(beta, expected);
+        assert_eq!(transcript.compute_challenge("alpha"), alpha);
+    }
+}
@@ -0,0 +1,183 @@
+//! The verification of gnark Plonk BN254 proofs, following gnark's `plonk.Verify`.
+
+use ark_bn254::{Fr, G1Affine, G1Projective};
+use ark_ec::{CurveGroup, VariableBaseMSM};
+use ark_ff::{Field, One, Zero};
+
+use super::encoding::{fr_to_bytes, g1_to_raw_bytes};
+use super::kzg::{batch_verify_multi_points, fold_proof};
+use super::transcript::{hash_to_field, Transcript};
+use super::{PlonkError, PlonkProof, PlonkVerifyingKey};
+
+/// The domain separation tag of the hash of the BSB22 commitments into public inputs.
+const BSB22_DST: &[u8] = b"BSB22-Plonk";
+
+/// Verifies a Plonk proof against the verifying key and the public inputs.
+pub fn verify_plonk(
+    proof: &PlonkProof,
+    vk: &PlonkVerifyingKey,
+    public_inputs: &[Fr],
+) -> Result<(), PlonkError> {
+    if proof.bsb22_commitments.len() != vk.qcp.len()
+        || vk.commitment_constraint_indexes.len() != vk.qcp.len()
+    {
+        return Err(PlonkError::InvalidVerifyingKey(
+            "the number of bsb22 commitments does not match the verifying key",
+        ));
+    }
+    if proof.batched_proof.claimed_values.len() != 5 + vk.qcp.len() {
+        return Err(PlonkError::InvalidOpeningProof);
+    }
+    if public_inputs.len() as u64 != vk.nb_public_variables {
+        return Err(PlonkError::InvalidPublicInputsLength {
+            expected: vk.nb_public_variables,
+            found: public_inputs.len(),
+        });
+    }
+
+    // Derive the challenges, where gamma is bound to the verifying key and the public inputs.
+    let mut transcript = Transcript::new(&["gamma", "beta", "alpha", "zeta"]);
+    for digest in vk.s.iter().chain([&vk.ql, &vk.qr, &vk.qm, &vk.qo, &vk.qk]) {
+        transcript.bind("gamma", &g1_to_raw_bytes(digest));
+    }
+    for digest in &vk.qcp {
+        transcript.bind("gamma", &g1_to_raw_bytes(digest));
+    }
+    for input in public_inputs {
+        transcript.bind("gamma", &fr_to_bytes(input));
+    }
+    let gamma = derive_challenge(&mut transcript, "gamma", &proof.lro);
+    let beta = derive_challenge(&mut transcript, "beta", &[]);
+    let alpha_deps = [proof.bsb22_commitments.as_slice(), &[proof.z]].concat();
+    let alpha = derive_challenge(&mut transcript, "alpha", &alpha_deps);
+    let zeta = derive_challenge(&mut transcript, "zeta", &proof.h);
+
+    // ζⁿ-1 and L₁(ζ) = (ζⁿ-1)/(n(ζ-1))
+    let one = Fr::one();
+    let zh_zeta = zeta.pow([vk.size]) - one;
+    let lagrange_one = zh_zeta * vk.size_inv * inverse(zeta - one)?;
+
+    // PI(ζ) = ∑ᵢLᵢ(ζ)wᵢ, where Lᵢ(ζ) = ωⁱ(ζⁿ-1)/(n(ζ-ωⁱ))
+    let mut pi = Fr::zero();
+    let mut acc_w = one;
+    for input in public_inputs {
+        pi += zh_zeta * vk.size_inv * acc_w * inverse(zeta - acc_w)? * input;
+        acc_w *= vk.generator;
+    }
+
+    // The BSB22 commitments are hashed into the public inputs of their constraints.
+    for (commitment, index) in proof
+        .bsb22_commitments
+        .iter()
+        .zip(&vk.commitment_constraint_indexes)
+    {
+        let hashed = hash_to_field(&g1_to_raw_bytes(commitment), BSB22_DST);
+        let w_pow_i = vk.generator.pow([vk.nb_public_variables + index]);
+        let lagrange = zh_zeta * vk.size_inv * w_pow_i * inverse(zeta - w_pow_i)?;
+        pi += lagrange * hashed;
+    }
+
+    let claimed_values = &proof.batched_proof.claimed_values;
+    let (l, r, o, s1, s2) = (
+        claimed_values[0],
+        claimed_values[1],
+        claimed_values[2],
+        claimed_values[3],
+        claimed_values[4],
+    );
+    let zu = proof.z_shifted_opening.claimed_value;
+
+    // α²*L₁(ζ)
+    let alpha_square_lagrange_one = lagrange_one * alpha * alpha;
+
+    // The opening of the linearized polynomial at ζ, which is the opposite of its constant term:
+    // -[PI(ζ) - α²*L₁(ζ) + α(l(ζ)+β*s1(ζ)+γ)(r(ζ)+β*s2(ζ)+γ)(o(ζ)+γ)*z(ωζ)]
+    let const_lin = (l + beta * s1 + gamma) * (r + beta * s2 + gamma) * (o + gamma) * alpha * zu;
+    let opening_lin_pol = -(const_lin - alpha_square_lagrange_one + pi);
+
+    // _s1 = α*(l(ζ)+β*s1(ζ)+γ)*(r(ζ)+β*s2(ζ)+γ)*β*z(ωζ)
+    let s1_coeff = (l + beta * s1 + gamma) * (r + beta * s2 + gamma) * beta * alpha * zu;
+
+    // _s2 = -α*(l(ζ)+β*ζ+γ)*(r(ζ)+β*u*ζ+γ)*(o(ζ)+β*u²*ζ+γ)
+    let u = vk.coset_shift;
+    let s2_coeff = -((l + beta * zeta + gamma)
+        * (r + beta * u * zeta + gamma)
+        * (o + beta * u * u * zeta + gamma)
+        * alpha);
+
+    // α²*L₁(ζ) + _s2
+    let z_coeff = alpha_square_lagrange_one + s2_coeff;
+
+    // -ζⁿ⁺²*(ζⁿ-1), -ζ²⁽ⁿ⁺²⁾*(ζⁿ-1), -(ζⁿ-1)
+    let zeta_n_plus_two = zeta.pow([vk.size + 2]);
+    let zeta_n_plus_two_zh = -(zeta_n_plus_two * zh_zeta);
+    let zeta_n_plus_two_square_zh = -(zeta_n_plus_two.square() * zh_zeta);
+    let zh = -zh_zeta;
+
+    // The digest of the linearized polynomial:
+    // ∑ᵢqcpᵢ(ζ)[Piᵢ] + l(ζ)*[Ql] + r(ζ)*[Qr] + l(ζ)r(ζ)*[Qm] + o(ζ)*[Qo] + [Qk] + _s1*[S3]
+    // + (α²*L₁(ζ) + _s2)*[Z] - (ζⁿ-1)*([H₀] + ζⁿ⁺²*[H₁] + ζ²⁽ⁿ⁺²⁾*[H₂])
+    let points = [
+        proof.bsb22_commitments.as_slice(),
+        &[vk.ql, vk.qr, vk.qm, vk.qo, vk.qk, vk.s[2], proof.z],
+        &proof.h,
+    ]
+    .concat();
+    let scalars = [
+        &claimed_values[5..],
+        &[
+            l,
+            r,
+            l * r,
+            o,
+            one,
+            s1_coeff,
+            z_coeff,
+            zh,
+            zeta_n_plus_two_zh,
+            zeta_n_plus_two_square_zh,
+        ],
+    ]
+    .concat();
+    let linearized_polynomial_digest = G1Projective::msm(&points, &scalars)
+        .expect("points and scalars have the same length")
+        .into_affine();
+
+    // Fold the openings at ζ, including the one of the linearized polynomial.
+    let mut batched_proof = proof.batched_proof.clone();
+    batched_proof.claimed_values.insert(0, opening_lin_pol);
+    let digests_to_fold = [
+        &[
+            linearized_polynomial_digest,
+            proof.lro[0],
+            proof.lro[1],
+            proof.lro[2],
+            vk.s[0],
+            vk.s[1],
+        ],
+        vk.qcp.as_slice(),
+    ]
+    .concat();
+    let (folded_proof, folded_digest) =
+        fold_proof(&digests_to_fold, &batched_proof, zeta, &fr_to_bytes(&zu))?;
+
+    // Verify the folded opening at ζ and the opening of Z at ωζ.
+    batch_verify_multi_points(
+        &[folded_digest, proof.z],
+        &[folded_proof, proof.z_shifted_opening.clone()],
+        &[zeta, zeta * vk.generator],
+        &vk.kzg,
+    )
+}
+
+/// Binds the points to the challenge and computes it.
+fn derive_challenge(transcript: &mut Transcript, name: &str, points: &[G1Affine]) -> Fr {
+    for point in points {
+        transcript.bind(name, &g1_to_raw_bytes(point));
+    }
+    transcript.compute_scalar(name)
+}
+
+fn inverse(x: Fr) -> Result<Fr, PlonkError> {
+    x.inverse().ok_or(PlonkError::InvalidOpeningProof)
+}
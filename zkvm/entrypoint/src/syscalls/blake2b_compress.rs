@@ -0,0 +1,25 @@
+#[cfg(target_os = "zkvm")]
+use core::arch::asm;
+
+/// Executes the BLAKE2b compression function on the given state and message block.
+///
+/// The state is the chaining value followed by the IV, with the offset counter and the final
+/// block flag already applied. Its first half is overwritten with the next chaining value.
+///
+/// ### Safety
+///
+/// The caller must ensure that `state` and `message` are valid pointers to data that is aligned
+/// along a four byte boundary, and that they don't overlap.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_blake2b_compress(state: *mut [u64; 16], message: *const [u64; 16]) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::BLAKE2B_COMPRESS,
+            in("a0") state,
+            in("a1") message,
+        );
+    }
+}
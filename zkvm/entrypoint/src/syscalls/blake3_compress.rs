@@ -0,0 +1,25 @@
+#[cfg(target_os = "zkvm")]
+use core::arch::asm;
+
+/// Executes the BLAKE3 compression function on the given state and message block.
+///
+/// The state is the chaining value, the IV, the counter, the block length and the flags. It is
+/// overwritten with the output of the compression.
+///
+/// ### Safety
+///
+/// The caller must ensure that `state` and `message` are valid pointers to data that is aligned
+/// along a four byte boundary, and that they don't overlap.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_blake3_compress(state: *mut [u32; 16], message: *const [u32; 16]) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::BLAKE3_COMPRESS,
+            in("a0") state,
+            in("a1") message,
+        );
+    }
+}
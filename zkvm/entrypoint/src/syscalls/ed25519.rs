@@ -57,3 +57,28 @@ pub extern "C" fn syscall_ed_decompress(point: &mut [u8; 64]) {
     #[cfg(not(target_os = "zkvm"))]
     unreachable!()
 }
+
+/// Multiplies a Ed25519 point by a scalar.
+///
+/// The result is stored in the point. The scalar is given as little-endian words.
+///
+/// ### Safety
+///
+/// The caller must ensure that `p` and `scalar` are valid pointers to data that is aligned along a
+/// four byte boundary, and that they do not overlap.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_ed_mul(p: *mut [u32; 16], scalar: *const [u32; 8]) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::ED_MUL,
+            in("a0") p,
+            in("a1") scalar,
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
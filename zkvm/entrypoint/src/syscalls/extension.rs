@@ -0,0 +1,26 @@
+#[cfg(target_os = "zkvm")]
+use core::arch::asm;
+
+/// Invokes a precompile registered with the runtime outside of `sp1-core`, identified by its
+/// syscall code.
+///
+/// ### Safety
+///
+/// The caller must ensure that `arg1` and `arg2` are valid arguments for the precompile, such as
+/// pointers to data with the layout and alignment it expects.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_extension(code: u32, arg1: u32, arg2: u32) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") code,
+            in("a0") arg1,
+            in("a1") arg2
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
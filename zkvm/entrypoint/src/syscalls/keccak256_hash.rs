@@ -0,0 +1,26 @@
+#[cfg(target_os = "zkvm")]
+use core::arch::asm;
+
+/// Computes the Keccak-256 hash of `len` bytes at `input` and writes it to `output`.
+///
+/// ### Safety
+///
+/// The caller must ensure that `input` is valid for reads of `len` bytes, rounded up to a whole
+/// word, and is aligned along a four byte boundary. `len` must be less than `2^24`.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_keccak256_hash(input: *const u8, len: usize, output: *mut [u32; 8]) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        let args = [input as u32, len as u32];
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::KECCAK256_HASH,
+            in("a0") args.as_ptr(),
+            in("a1") output
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
@@ -1,33 +1,53 @@
 mod bigint;
+mod blake2b_compress;
+mod blake3_compress;
 mod bls12381;
 mod bn254;
 mod ed25519;
+mod extension;
 mod halt;
 mod io;
+mod keccak256_hash;
 mod keccak_permute;
 mod memory;
+mod poseidon2_permute;
 mod secp256k1;
+mod secp256r1;
+mod sha512_compress;
+mod sha512_extend;
 mod sha_compress;
 mod sha_extend;
 mod sys;
-mod uint256_mul;
+mod uint256;
+mod uint384;
+mod uint512;
 mod unconstrained;
 #[cfg(feature = "verify")]
 mod verify;
 
 pub use bigint::*;
+pub use blake2b_compress::*;
+pub use blake3_compress::*;
 pub use bls12381::*;
 pub use bn254::*;
 pub use ed25519::*;
+pub use extension::*;
 pub use halt::*;
 pub use io::*;
+pub use keccak256_hash::*;
 pub use keccak_permute::*;
 pub use memory::*;
+pub use poseidon2_permute::*;
 pub use secp256k1::*;
+pub use secp256r1::*;
+pub use sha512_compress::*;
+pub use sha512_extend::*;
 pub use sha_compress::*;
 pub use sha_extend::*;
 pub use sys::*;
-pub use uint256_mul::*;
+pub use uint256::*;
+pub use uint384::*;
+pub use uint512::*;
 pub use unconstrained::*;
 #[cfg(feature = "verify")]
 pub use verify::*;
@@ -103,3 +123,99 @@ pub const BLS12381_ADD: u32 = 0x00_01_01_1E;
 
 /// Executes the `BLS12381_DOUBLE` precompile.
 pub const BLS12381_DOUBLE: u32 = 0x00_00_01_1F;
+
+/// Executes `SECP256R1_ADD`.
+pub const SECP256R1_ADD: u32 = 0x00_01_01_2C;
+
+/// Executes `SECP256R1_DOUBLE`.
+pub const SECP256R1_DOUBLE: u32 = 0x00_00_01_2D;
+
+/// Executes `SECP256R1_DECOMPRESS`.
+pub const SECP256R1_DECOMPRESS: u32 = 0x00_00_01_2E;
+
+/// Executes the `BN254_FP_ADD` precompile.
+pub const BN254_FP_ADD: u32 = 0x00_01_01_26;
+
+/// Executes the `BN254_FP_SUB` precompile.
+pub const BN254_FP_SUB: u32 = 0x00_01_01_27;
+
+/// Executes the `BN254_FP_MUL` precompile.
+pub const BN254_FP_MUL: u32 = 0x00_01_01_28;
+
+/// Executes the `BN254_FP2_ADD` precompile.
+pub const BN254_FP2_ADD: u32 = 0x00_01_01_29;
+
+/// Executes the `BN254_FP2_SUB` precompile.
+pub const BN254_FP2_SUB: u32 = 0x00_01_01_2A;
+
+/// Executes the `BN254_FP2_MUL` precompile.
+pub const BN254_FP2_MUL: u32 = 0x00_01_01_2B;
+
+/// Executes `SHA512_EXTEND`.
+pub const SHA512_EXTEND: u32 = 0x00_40_01_2F;
+
+/// Executes `SHA512_COMPRESS`.
+pub const SHA512_COMPRESS: u32 = 0x00_01_01_30;
+
+/// Executes the `UINT256_ADD` precompile.
+pub const UINT256_ADD: u32 = 0x00_01_01_31;
+
+/// Executes the `UINT256_SUB` precompile.
+pub const UINT256_SUB: u32 = 0x00_01_01_33;
+
+/// Executes the `UINT384_ADD` precompile.
+pub const UINT384_ADD: u32 = 0x00_01_01_34;
+
+/// Executes the `UINT384_SUB` precompile.
+pub const UINT384_SUB: u32 = 0x00_01_01_35;
+
+/// Executes the `UINT384_MUL` precompile.
+pub const UINT384_MUL: u32 = 0x00_01_01_36;
+
+/// Executes the `UINT512_ADD` precompile.
+pub const UINT512_ADD: u32 = 0x00_01_01_37;
+
+/// Executes the `UINT512_SUB` precompile.
+pub const UINT512_SUB: u32 = 0x00_01_01_38;
+
+/// Executes the `UINT512_MUL` precompile.
+pub const UINT512_MUL: u32 = 0x00_01_01_39;
+
+/// Executes `KECCAK256_HASH`.
+pub const KECCAK256_HASH: u32 = 0x00_01_01_3A;
+
+/// Executes `POSEIDON2_PERMUTE`.
+pub const POSEIDON2_PERMUTE: u32 = 0x00_01_01_3B;
+
+/// Executes `ED_MUL`.
+pub const ED_MUL: u32 = 0x00_01_01_3C;
+
+/// Executes `SECP256K1_MUL`.
+pub const SECP256K1_MUL: u32 = 0x00_01_01_3D;
+
+/// Executes `BN254_MUL`.
+pub const BN254_MUL: u32 = 0x00_01_01_3E;
+
+/// Executes `BLAKE3_COMPRESS`.
+pub const BLAKE3_COMPRESS: u32 = 0x00_01_01_3F;
+
+/// Executes `BLAKE2B_COMPRESS`.
+pub const BLAKE2B_COMPRESS: u32 = 0x00_01_01_40;
+
+/// Executes the `BLS12381_FP_ADD` precompile.
+pub const BLS12381_FP_ADD: u32 = 0x00_01_01_71;
+
+/// Executes the `BLS12381_FP_SUB` precompile.
+pub const BLS12381_FP_SUB: u32 = 0x00_01_01_72;
+
+/// Executes the `BLS12381_FP_MUL` precompile.
+pub const BLS12381_FP_MUL: u32 = 0x00_01_01_73;
+
+/// Executes the `BLS12381_FP2_ADD` precompile.
+pub const BLS12381_FP2_ADD: u32 = 0x00_01_01_77;
+
+/// Executes the `BLS12381_FP2_SUB` precompile.
+pub const BLS12381_FP2_SUB: u32 = 0x00_01_01_78;
+
+/// Executes the `BLS12381_FP2_MUL` precompile.
+pub const BLS12381_FP2_MUL: u32 = 0x00_01_01_79;
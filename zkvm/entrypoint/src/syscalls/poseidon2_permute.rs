@@ -0,0 +1,25 @@
+#[cfg(target_os = "zkvm")]
+use core::arch::asm;
+
+/// Executes the Poseidon2 permutation over BabyBear on the given state.
+///
+/// ### Safety
+///
+/// The caller must ensure that `state` is valid pointer to data that is aligned along a four
+/// byte boundary, and that every element is a canonical BabyBear element.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_poseidon2_permute(state: *mut [u32; 16]) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::POSEIDON2_PERMUTE,
+            in("a0") state,
+            in("a1") 0
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
@@ -0,0 +1,80 @@
+#[cfg(target_os = "zkvm")]
+use core::arch::asm;
+
+/// Uint384 modular addition operation.
+///
+/// The modulus is read from memory right after `y`, and a zero modulus stands for 2^384. The
+/// result is written over the first input.
+///
+/// ### Safety
+///
+/// The caller must ensure that `x` and `y` are valid pointers to data that is aligned along a four
+/// byte boundary.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_uint384_addmod(x: *mut [u32; 12], y: *const [u32; 12]) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::UINT384_ADD,
+            in("a0") x,
+            in("a1") y,
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
+
+/// Uint384 modular subtraction operation.
+///
+/// The modulus is read from memory right after `y`, and a zero modulus stands for 2^384. The
+/// result is written over the first input.
+///
+/// ### Safety
+///
+/// The caller must ensure that `x` and `y` are valid pointers to data that is aligned along a four
+/// byte boundary.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_uint384_submod(x: *mut [u32; 12], y: *const [u32; 12]) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::UINT384_SUB,
+            in("a0") x,
+            in("a1") y,
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
+
+/// Uint384 modular multiplication operation.
+///
+/// The modulus is read from memory right after `y`, and a zero modulus stands for 2^384. The
+/// result is written over the first input.
+///
+/// ### Safety
+///
+/// The caller must ensure that `x` and `y` are valid pointers to data that is aligned along a four
+/// byte boundary.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_uint384_mulmod(x: *mut [u32; 12], y: *const [u32; 12]) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::UINT384_MUL,
+            in("a0") x,
+            in("a1") y,
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
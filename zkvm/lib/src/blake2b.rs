@@ -0,0 +1,46 @@
+use crate::syscall_blake2b_compress;
+
+/// The number of bytes of a message block.
+pub const BLOCK_LEN: usize = 128;
+
+/// The BLAKE2b IV, which is the SHA-512 IV.
+pub const IV: [u64; 8] = [
+    0x6a09e667f3bcc908,
+    0xbb67ae8584caa73b,
+    0x3c6ef372fe94f82b,
+    0xa54ff53a5f1d36f1,
+    0x510e527fade682d1,
+    0x9b05688c2b3e6c1f,
+    0x1f83d9abfb41bd6b,
+    0x5be0cd19137e2179,
+];
+
+/// Compresses a message block into the chaining value `h`, in place.
+///
+/// `t` is the number of bytes hashed so far, including the ones of this block, and `last` is set
+/// for the final block. These are the arguments of the BLAKE2b compression function `F` of
+/// RFC 7693, which is also the one of the EIP-152 precompile with its 12 rounds.
+pub fn compress(h: &mut [u64; 8], m: &[u64; 16], t: u128, last: bool) {
+    let mut state = [0u64; 16];
+    state[..8].copy_from_slice(h);
+    state[8..].copy_from_slice(&IV);
+    state[12] ^= t as u64;
+    state[13] ^= (t >> 64) as u64;
+    if last {
+        state[14] = !state[14];
+    }
+
+    unsafe {
+        syscall_blake2b_compress(&mut state, m);
+    }
+    h.copy_from_slice(&state[..8]);
+}
+
+/// Compresses a message block, given as bytes, into the chaining value `h`, in place.
+pub fn compress_block(h: &mut [u64; 8], block: &[u8; BLOCK_LEN], t: u128, last: bool) {
+    let mut m = [0u64; 16];
+    for (word, chunk) in m.iter_mut().zip(block.chunks_exact(8)) {
+        *word = u64::from_le_bytes(chunk.try_into().unwrap());
+    }
+    compress(h, &m, t, last);
+}
@@ -0,0 +1,67 @@
+use crate::syscall_blake3_compress;
+
+/// The number of bytes of a message block.
+pub const BLOCK_LEN: usize = 64;
+
+/// The BLAKE3 IV, which is the SHA-256 IV.
+pub const IV: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+/// Compresses a message block into the full 16-word output of the compression function.
+///
+/// The arguments are the same as the ones of the portable implementation of the `blake3` crate,
+/// so that a patched crate can call this in place of it.
+pub fn compress(
+    cv: &[u32; 8],
+    block: &[u8; BLOCK_LEN],
+    block_len: u8,
+    counter: u64,
+    flags: u8,
+) -> [u32; 16] {
+    let mut state = [0u32; 16];
+    state[..8].copy_from_slice(cv);
+    state[8..12].copy_from_slice(&IV[..4]);
+    state[12] = counter as u32;
+    state[13] = (counter >> 32) as u32;
+    state[14] = block_len as u32;
+    state[15] = flags as u32;
+
+    let mut message = [0u32; 16];
+    for (word, chunk) in message.iter_mut().zip(block.chunks_exact(4)) {
+        *word = u32::from_le_bytes(chunk.try_into().unwrap());
+    }
+
+    unsafe {
+        syscall_blake3_compress(&mut state, &message);
+    }
+    state
+}
+
+/// Compresses a message block into the chaining value, in place.
+pub fn compress_in_place(
+    cv: &mut [u32; 8],
+    block: &[u8; BLOCK_LEN],
+    block_len: u8,
+    counter: u64,
+    flags: u8,
+) {
+    let state = compress(cv, block, block_len, counter, flags);
+    cv.copy_from_slice(&state[..8]);
+}
+
+/// Compresses a message block into 64 bytes of extended output.
+pub fn compress_xof(
+    cv: &[u32; 8],
+    block: &[u8; BLOCK_LEN],
+    block_len: u8,
+    counter: u64,
+    flags: u8,
+) -> [u8; 64] {
+    let state = compress(cv, block, block_len, counter, flags);
+    let mut out = [0u8; 64];
+    for (chunk, word) in out.chunks_exact_mut(4).zip(state) {
+        chunk.copy_from_slice(&word.to_le_bytes());
+    }
+    out
+}
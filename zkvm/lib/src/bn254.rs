@@ -1,9 +1,14 @@
-use crate::utils::AffinePoint;
-use crate::{syscall_bn254_add, syscall_bn254_double};
+use crate::utils::{double_and_add, reduce_scalar, AffinePoint, MulAssignError};
+use crate::{syscall_bn254_add, syscall_bn254_double, syscall_bn254_mul};
 
 /// The number of limbs in [Bn254AffinePoint].
 pub const N: usize = 16;
 
+/// The order of the BN254 G1 group, as little endian words.
+const GROUP_ORDER: [u32; N / 2] = [
+    0xF0000001, 0x43E1F593, 0x79B97091, 0x2833E848, 0x8181585D, 0xB85045B6, 0xE131A029, 0x30644E72,
+];
+
 /// An affine point on the BLS12-381 curve.
 #[derive(Copy, Clone)]
 #[repr(align(4))]
@@ -41,4 +46,25 @@ impl AffinePoint<N> for Bn254AffinePoint {
             syscall_bn254_double(a);
         }
     }
+
+    /// Multiplies `self` by the given scalar, reduced modulo the group order, with a single
+    /// fixed-window scalar multiplication. Scalars which aren't `N / 2` words long fall back to
+    /// double-and-add.
+    ///
+    /// Returns [MulAssignError::ScalarIsZero] if the scalar is a multiple of the group order.
+    fn mul_assign(&mut self, scalar: &[u32]) -> Result<(), MulAssignError> {
+        let Ok(mut scalar) = <[u32; N / 2]>::try_from(scalar) else {
+            return double_and_add(self, scalar);
+        };
+        reduce_scalar(&mut scalar, &GROUP_ORDER);
+        if scalar.iter().all(|&word| word == 0) {
+            return Err(MulAssignError::ScalarIsZero);
+        }
+
+        let a = self.limbs_mut();
+        unsafe {
+            syscall_bn254_mul(a, &scalar);
+        }
+        Ok(())
+    }
 }
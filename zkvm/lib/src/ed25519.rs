@@ -1,5 +1,5 @@
-use crate::syscall_ed_add;
-use crate::utils::AffinePoint;
+use crate::utils::{double_and_add, AffinePoint, MulAssignError};
+use crate::{syscall_ed_add, syscall_ed_mul};
 
 /// The number of limbs in [Ed25519AffinePoint].
 pub const N: usize = 16;
@@ -44,6 +44,26 @@ impl AffinePoint<N> for Ed25519AffinePoint {
             syscall_ed_add(a, a);
         }
     }
+
+    /// Multiplies `self` by the given scalar with a single fixed-window scalar multiplication.
+    /// Scalars which aren't `N / 2` words long fall back to double-and-add.
+    ///
+    /// The scalar isn't reduced, since the point may have a small order component and the
+    /// complete addition formula handles any scalar.
+    fn mul_assign(&mut self, scalar: &[u32]) -> Result<(), MulAssignError> {
+        let Ok(scalar) = <[u32; N / 2]>::try_from(scalar) else {
+            return double_and_add(self, scalar);
+        };
+        if scalar.iter().all(|&word| word == 0) {
+            return Err(MulAssignError::ScalarIsZero);
+        }
+
+        let a = self.limbs_mut();
+        unsafe {
+            syscall_ed_mul(a, &scalar);
+        }
+        Ok(())
+    }
 }
 
 impl Ed25519AffinePoint {
@@ -0,0 +1,267 @@
+//! Operations which are expensive to compute but cheap to verify.
+//!
+//! The results are computed outside of the zkVM by the runtime hooks, read from the input stream,
+//! and then verified with the multiplication precompiles, so they can be trusted by the program.
+
+use crate::io::{self, FD_BIGINT_DIVMOD_HOOK, FD_FP_INVERSE_HOOK, FD_FP_SQRT_HOOK};
+use crate::utils::{bytes_to_words_le, words_to_bytes_le};
+use crate::{
+    syscall_bls12381_fp_mulmod, syscall_bn254_fp_mulmod, syscall_uint256_mulmod,
+    syscall_uint512_addmod, syscall_uint512_mulmod,
+};
+
+/// The base field of a curve, whose inverses and square roots are computed by the runtime hooks.
+///
+/// The elements are given as little endian words, and must be canonical, i.e. less than the modulus.
+pub trait HookField<const N: usize> {
+    /// The identifier of the field in the input of the field hooks.
+    const ID: u8;
+
+    /// The modulus of the field.
+    const MODULUS: [u32; N];
+
+    /// The quadratic non-residue used by the square root hook to prove that an element is not a
+    /// square.
+    const NON_RESIDUE: [u32; N];
+
+    /// Multiplies `x` by `y` in place, with a multiplication precompile.
+    fn mul_assign(x: &mut [u32; N], y: &[u32; N]);
+
+    /// Returns the inverse of `x`, or `None` if `x` is zero.
+    fn inverse(x: &[u32; N]) -> Option<[u32; N]> {
+        if x.iter().all(|&word| word == 0) {
+            return None;
+        }
+
+        invoke_field_hook(FD_FP_INVERSE_HOOK, Self::ID, x);
+        let inverse = read_words();
+        assert!(
+            is_canonical(&inverse, &Self::MODULUS),
+            "hinted inverse is not canonical"
+        );
+
+        // Check that `x * inverse = 1`.
+        let mut product = inverse;
+        Self::mul_assign(&mut product, x);
+        let mut one = [0u32; N];
+        one[0] = 1;
+        assert_eq!(product, one, "hinted inverse is invalid");
+
+        Some(inverse)
+    }
+
+    /// Returns a square root of `x`, or `None` if `x` is not a square.
+    fn sqrt(x: &[u32; N]) -> Option<[u32; N]> {
+        invoke_field_hook(FD_FP_SQRT_HOOK, Self::ID, x);
+        let is_square = io::read_vec()[0] == 1;
+        let root = read_words();
+        assert!(
+            is_canonical(&root, &Self::MODULUS),
+            "hinted square root is not canonical"
+        );
+
+        let mut square = root;
+        Self::mul_assign(&mut square, &root);
+        if is_square {
+            // Check that `root^2 = x`.
+            assert_eq!(&square, x, "hinted square root is invalid");
+            Some(root)
+        } else {
+            // Check that `root^2 = x * NON_RESIDUE`, which is only possible if `x` is not a square.
+            // Zero is a square, and the only element for which both hold.
+            assert!(
+                x.iter().any(|&word| word != 0),
+                "hinted non-residue square root is invalid"
+            );
+            let mut expected = *x;
+            Self::mul_assign(&mut expected, &Self::NON_RESIDUE);
+            assert_eq!(
+                square, expected,
+                "hinted non-residue square root is invalid"
+            );
+            None
+        }
+    }
+}
+
+/// The base field of the Secp256k1 curve.
+pub struct Secp256k1BaseField;
+
+impl HookField<8> for Secp256k1BaseField {
+    const ID: u8 = 0;
+
+    const MODULUS: [u32; 8] = [
+        0xfffffc2f, 0xfffffffe, 0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff,
+        0xffffffff,
+    ];
+
+    /// The non-residue is -1, as `p = 3 mod 4`.
+    const NON_RESIDUE: [u32; 8] = [
+        0xfffffc2e, 0xfffffffe, 0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff,
+        0xffffffff,
+    ];
+
+    fn mul_assign(x: &mut [u32; 8], y: &[u32; 8]) {
+        uint256_mulmod(x, y, &Self::MODULUS);
+    }
+}
+
+/// The base field of the Secp256r1 curve.
+pub struct Secp256r1BaseField;
+
+impl HookField<8> for Secp256r1BaseField {
+    const ID: u8 = 1;
+
+    const MODULUS: [u32; 8] = [
+        0xffffffff, 0xffffffff, 0xffffffff, 0x00000000, 0x00000000, 0x00000000, 0x00000001,
+        0xffffffff,
+    ];
+
+    /// The non-residue is -1, as `p = 3 mod 4`.
+    const NON_RESIDUE: [u32; 8] = [
+        0xfffffffe, 0xffffffff, 0xffffffff, 0x00000000, 0x00000000, 0x00000000, 0x00000001,
+        0xffffffff,
+    ];
+
+    fn mul_assign(x: &mut [u32; 8], y: &[u32; 8]) {
+        uint256_mulmod(x, y, &Self::MODULUS);
+    }
+}
+
+/// The base field of the Bn254 curve.
+pub struct Bn254BaseField;
+
+impl HookField<8> for Bn254BaseField {
+    const ID: u8 = 2;
+
+    const MODULUS: [u32; 8] = [
+        0xd87cfd47, 0x3c208c16, 0x6871ca8d, 0x97816a91, 0x8181585d, 0xb85045b6, 0xe131a029,
+        0x30644e72,
+    ];
+
+    /// The non-residue is -1, as `p = 3 mod 4`.
+    const NON_RESIDUE: [u32; 8] = [
+        0xd87cfd46, 0x3c208c16, 0x6871ca8d, 0x97816a91, 0x8181585d, 0xb85045b6, 0xe131a029,
+        0x30644e72,
+    ];
+
+    fn mul_assign(x: &mut [u32; 8], y: &[u32; 8]) {
+        unsafe {
+            syscall_bn254_fp_mulmod(x, y);
+        }
+    }
+}
+
+/// The base field of the Ed25519 curve.
+pub struct Ed25519BaseField;
+
+impl HookField<8> for Ed25519BaseField {
+    const ID: u8 = 3;
+
+    const MODULUS: [u32; 8] = [
+        0xffffffed, 0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff,
+        0x7fffffff,
+    ];
+
+    /// The non-residue is 2, as `p = 5 mod 8`.
+    const NON_RESIDUE: [u32; 8] = [2, 0, 0, 0, 0, 0, 0, 0];
+
+    fn mul_assign(x: &mut [u32; 8], y: &[u32; 8]) {
+        uint256_mulmod(x, y, &Self::MODULUS);
+    }
+}
+
+/// The base field of the BLS12-381 curve.
+pub struct Bls12381BaseField;
+
+impl HookField<12> for Bls12381BaseField {
+    const ID: u8 = 4;
+
+    const MODULUS: [u32; 12] = [
+        0xffffaaab, 0xb9feffff, 0xb153ffff, 0x1eabfffe, 0xf6b0f624, 0x6730d2a0, 0xf38512bf,
+        0x64774b84, 0x434bacd7, 0x4b1ba7b6, 0x397fe69a, 0x1a0111ea,
+    ];
+
+    /// The non-residue is -1, as `p = 3 mod 4`.
+    const NON_RESIDUE: [u32; 12] = [
+        0xffffaaaa, 0xb9feffff, 0xb153ffff, 0x1eabfffe, 0xf6b0f624, 0x6730d2a0, 0xf38512bf,
+        0x64774b84, 0x434bacd7, 0x4b1ba7b6, 0x397fe69a, 0x1a0111ea,
+    ];
+
+    fn mul_assign(x: &mut [u32; 12], y: &[u32; 12]) {
+        unsafe {
+            syscall_bls12381_fp_mulmod(x, y);
+        }
+    }
+}
+
+/// Returns the quotient and the remainder of the division of `x` by `y`, as little endian words.
+///
+/// Panics if `y` is zero.
+pub fn u256_divmod(x: &[u32; 8], y: &[u32; 8]) -> ([u32; 8], [u32; 8]) {
+    assert!(y.iter().any(|&word| word != 0), "division by zero");
+
+    let mut buf = words_to_bytes_le(x);
+    buf.extend(words_to_bytes_le(y));
+    io::write(FD_BIGINT_DIVMOD_HOOK, &buf);
+    let quotient: [u32; 8] = read_words();
+    let remainder: [u32; 8] = read_words();
+
+    // Check that `remainder < y`, comparing from the most significant word.
+    assert!(
+        remainder.iter().rev().lt(y.iter().rev()),
+        "hinted remainder is invalid"
+    );
+
+    // Check that `quotient * y + remainder = x` over 512 bits, where neither operation overflows.
+    // The zero moduli after the operands stand for 2^512.
+    let mut result = [0u32; 16];
+    result[..8].copy_from_slice(&quotient);
+    let mut operand = [0u32; 32];
+    operand[..8].copy_from_slice(y);
+    unsafe {
+        syscall_uint512_mulmod(&mut result, operand.as_ptr() as *const [u32; 16]);
+    }
+    let mut operand = [0u32; 32];
+    operand[..8].copy_from_slice(&remainder);
+    unsafe {
+        syscall_uint512_addmod(&mut result, operand.as_ptr() as *const [u32; 16]);
+    }
+    assert_eq!(result[..8], x[..], "hinted quotient is invalid");
+    assert!(
+        result[8..].iter().all(|&word| word == 0),
+        "hinted quotient is invalid"
+    );
+
+    (quotient, remainder)
+}
+
+/// Whether `x` is less than `modulus`, comparing from the most significant word.
+fn is_canonical<const N: usize>(x: &[u32; N], modulus: &[u32; N]) -> bool {
+    x.iter().rev().lt(modulus.iter().rev())
+}
+
+/// Invokes a field hook on the element `x` of the field identified by `id`.
+fn invoke_field_hook<const N: usize>(fd: u32, id: u8, x: &[u32; N]) {
+    let mut buf = vec![id];
+    buf.extend(words_to_bytes_le(x));
+    io::write(fd, &buf);
+}
+
+/// Reads little endian words from the input stream.
+fn read_words<const N: usize>() -> [u32; N] {
+    bytes_to_words_le(&io::read_vec())
+        .try_into()
+        .expect("hinted value has an invalid length")
+}
+
+/// Multiplies `x` by `y` modulo `modulus` in place, with the uint256 multiplication precompile.
+fn uint256_mulmod(x: &mut [u32; 8], y: &[u32; 8], modulus: &[u32; 8]) {
+    // The precompile reads the modulus from memory right after `y`.
+    let mut operand = [0u32; 16];
+    operand[..8].copy_from_slice(y);
+    operand[8..].copy_from_slice(modulus);
+    unsafe {
+        syscall_uint256_mulmod(x, operand.as_ptr() as *const [u32; 8]);
+    }
+}
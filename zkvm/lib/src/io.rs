@@ -16,6 +16,15 @@ pub const FD_HINT: u32 = 4;
 /// The file descriptor for the `ecreover` hook.
 pub const FD_ECRECOVER_HOOK: u32 = 5;
 
+/// The file descriptor for the base field inverse hook.
+pub const FD_FP_INVERSE_HOOK: u32 = 6;
+
+/// The file descriptor for the base field square root hook.
+pub const FD_FP_SQRT_HOOK: u32 = 7;
+
+/// The file descriptor for the big integer division hook.
+pub const FD_BIGINT_DIVMOD_HOOK: u32 = 8;
+
 /// A writer that writes to a file descriptor inside the zkVM.
 struct SyscallWriter {
     fd: u32,
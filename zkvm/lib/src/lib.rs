@@ -3,11 +3,16 @@
 //! Documentation for these syscalls can be found in the zkVM entrypoint
 //! `sp1_zkvm::syscalls` module.
 
+pub mod blake2b;
+pub mod blake3;
 pub mod bls12381;
 pub mod bn254;
 pub mod ed25519;
+pub mod hints;
 pub mod io;
+pub mod poseidon2;
 pub mod secp256k1;
+pub mod secp256r1;
 pub mod unconstrained;
 pub mod utils;
 #[cfg(feature = "verify")]
@@ -29,9 +34,18 @@ extern "C" {
     /// Executes the SHA-256 compress operation on the given word array and a given state.
     pub fn syscall_sha256_compress(w: *mut [u32; 64], state: *mut [u32; 8]);
 
+    /// Executes the SHA-512 extend operation on the given word array.
+    pub fn syscall_sha512_extend(w: *mut [u64; 80]);
+
+    /// Executes the SHA-512 compress operation on the given word array and a given state.
+    pub fn syscall_sha512_compress(w: *mut [u64; 80], state: *mut [u64; 8]);
+
     /// Executes an Ed25519 curve addition on the given points.
     pub fn syscall_ed_add(p: *mut [u32; 16], q: *const [u32; 16]);
 
+    /// Executes an Ed25519 curve scalar multiplication on the given point.
+    pub fn syscall_ed_mul(p: *mut [u32; 16], scalar: *const [u32; 8]);
+
     /// Executes an Ed25519 curve decompression on the given point.
     pub fn syscall_ed_decompress(point: &mut [u8; 64]);
 
@@ -41,27 +55,117 @@ extern "C" {
     /// Executes an Secp256k1 curve doubling on the given point.
     pub fn syscall_secp256k1_double(p: *mut [u32; 16]);
 
+    /// Executes a Secp256k1 curve scalar multiplication on the given point.
+    pub fn syscall_secp256k1_mul(p: *mut [u32; 16], scalar: *const [u32; 8]);
+
     /// Executes an Secp256k1 curve decompression on the given point.
     pub fn syscall_secp256k1_decompress(point: &mut [u8; 64], is_odd: bool);
 
+    /// Executes a Secp256r1 curve addition on the given points.
+    pub fn syscall_secp256r1_add(p: *mut [u32; 16], q: *const [u32; 16]);
+
+    /// Executes a Secp256r1 curve doubling on the given point.
+    pub fn syscall_secp256r1_double(p: *mut [u32; 16]);
+
+    /// Executes a Secp256r1 curve decompression on the given point.
+    pub fn syscall_secp256r1_decompress(point: &mut [u8; 64], is_odd: bool);
+
     /// Executes a Bn254 curve addition on the given points.
     pub fn syscall_bn254_add(p: *mut [u32; 16], q: *const [u32; 16]);
 
     /// Executes a Bn254 curve doubling on the given point.
     pub fn syscall_bn254_double(p: *mut [u32; 16]);
 
+    /// Executes a Bn254 curve scalar multiplication on the given point.
+    pub fn syscall_bn254_mul(p: *mut [u32; 16], scalar: *const [u32; 8]);
+
     /// Executes a BLS12-381 curve addition on the given points.
     pub fn syscall_bls12381_add(p: *mut [u32; 24], q: *const [u32; 24]);
 
     /// Executes a BLS12-381 curve doubling on the given point.
     pub fn syscall_bls12381_double(p: *mut [u32; 24]);
 
+    /// Executes a Bn254 base field addition on the given inputs.
+    pub fn syscall_bn254_fp_addmod(x: *mut [u32; 8], y: *const [u32; 8]);
+
+    /// Executes a Bn254 base field subtraction on the given inputs.
+    pub fn syscall_bn254_fp_submod(x: *mut [u32; 8], y: *const [u32; 8]);
+
+    /// Executes a Bn254 base field multiplication on the given inputs.
+    pub fn syscall_bn254_fp_mulmod(x: *mut [u32; 8], y: *const [u32; 8]);
+
+    /// Executes a Bn254 quadratic extension field addition on the given inputs.
+    pub fn syscall_bn254_fp2_addmod(x: *mut [u32; 16], y: *const [u32; 16]);
+
+    /// Executes a Bn254 quadratic extension field subtraction on the given inputs.
+    pub fn syscall_bn254_fp2_submod(x: *mut [u32; 16], y: *const [u32; 16]);
+
+    /// Executes a Bn254 quadratic extension field multiplication on the given inputs.
+    pub fn syscall_bn254_fp2_mulmod(x: *mut [u32; 16], y: *const [u32; 16]);
+
+    /// Executes a BLS12-381 base field addition on the given inputs.
+    pub fn syscall_bls12381_fp_addmod(x: *mut [u32; 12], y: *const [u32; 12]);
+
+    /// Executes a BLS12-381 base field subtraction on the given inputs.
+    pub fn syscall_bls12381_fp_submod(x: *mut [u32; 12], y: *const [u32; 12]);
+
+    /// Executes a BLS12-381 base field multiplication on the given inputs.
+    pub fn syscall_bls12381_fp_mulmod(x: *mut [u32; 12], y: *const [u32; 12]);
+
+    /// Executes a BLS12-381 quadratic extension field addition on the given inputs.
+    pub fn syscall_bls12381_fp2_addmod(x: *mut [u32; 24], y: *const [u32; 24]);
+
+    /// Executes a BLS12-381 quadratic extension field subtraction on the given inputs.
+    pub fn syscall_bls12381_fp2_submod(x: *mut [u32; 24], y: *const [u32; 24]);
+
+    /// Executes a BLS12-381 quadratic extension field multiplication on the given inputs.
+    pub fn syscall_bls12381_fp2_mulmod(x: *mut [u32; 24], y: *const [u32; 24]);
+
     /// Executes the Keccak-256 permutation on the given state.
     pub fn syscall_keccak_permute(state: *mut [u64; 25]);
 
-    /// Executes an uint256 multiplication on the given inputs.
+    /// Computes the Keccak-256 hash of the given input.
+    pub fn syscall_keccak256_hash(input: *const u8, len: usize, output: *mut [u32; 8]);
+
+    /// Executes the Poseidon2 permutation over BabyBear on the given state.
+    pub fn syscall_poseidon2_permute(state: *mut [u32; 16]);
+
+    /// Executes the BLAKE3 compression function on the given state and message block.
+    pub fn syscall_blake3_compress(state: *mut [u32; 16], message: *const [u32; 16]);
+
+    /// Executes the BLAKE2b compression function on the given state and message block.
+    pub fn syscall_blake2b_compress(state: *mut [u64; 16], message: *const [u64; 16]);
+
+    /// Invokes a precompile registered with the runtime outside of `sp1-core`.
+    pub fn syscall_extension(code: u32, arg1: u32, arg2: u32);
+
+    /// Executes an uint256 modular addition on the given inputs.
+    pub fn syscall_uint256_addmod(x: *mut [u32; 8], y: *const [u32; 8]);
+
+    /// Executes an uint256 modular subtraction on the given inputs.
+    pub fn syscall_uint256_submod(x: *mut [u32; 8], y: *const [u32; 8]);
+
+    /// Executes an uint256 modular multiplication on the given inputs.
     pub fn syscall_uint256_mulmod(x: *mut [u32; 8], y: *const [u32; 8]);
 
+    /// Executes an uint384 modular addition on the given inputs.
+    pub fn syscall_uint384_addmod(x: *mut [u32; 12], y: *const [u32; 12]);
+
+    /// Executes an uint384 modular subtraction on the given inputs.
+    pub fn syscall_uint384_submod(x: *mut [u32; 12], y: *const [u32; 12]);
+
+    /// Executes an uint384 modular multiplication on the given inputs.
+    pub fn syscall_uint384_mulmod(x: *mut [u32; 12], y: *const [u32; 12]);
+
+    /// Executes an uint512 modular addition on the given inputs.
+    pub fn syscall_uint512_addmod(x: *mut [u32; 16], y: *const [u32; 16]);
+
+    /// Executes an uint512 modular subtraction on the given inputs.
+    pub fn syscall_uint512_submod(x: *mut [u32; 16], y: *const [u32; 16]);
+
+    /// Executes an uint512 modular multiplication on the given inputs.
+    pub fn syscall_uint512_mulmod(x: *mut [u32; 16], y: *const [u32; 16]);
+
     /// Enters unconstrained mode.
     pub fn syscall_enter_unconstrained() -> bool;
 
@@ -0,0 +1,43 @@
+use crate::syscall_poseidon2_permute;
+
+/// The width of the Poseidon2 state, in BabyBear elements.
+pub const WIDTH: usize = 16;
+
+/// The number of elements absorbed per permutation by [poseidon2_hash].
+pub const RATE: usize = 8;
+
+/// The BabyBear modulus. Every element passed to the permutation must be below it.
+pub const BABYBEAR_MODULUS: u32 = 0x78000001;
+
+/// Applies the Poseidon2 permutation over BabyBear to the state in place.
+///
+/// The elements are canonical BabyBear elements, i.e. `u32` values below [BABYBEAR_MODULUS].
+///
+/// # Panics
+///
+/// Panics if an element of the state is not below [BABYBEAR_MODULUS], since the precompile can't
+/// prove the permutation of non-canonical elements.
+pub fn poseidon2_permute(state: &mut [u32; WIDTH]) {
+    assert!(
+        state.iter().all(|&x| x < BABYBEAR_MODULUS),
+        "poseidon2 state elements must be below the BabyBear modulus"
+    );
+    unsafe {
+        syscall_poseidon2_permute(state);
+    }
+}
+
+/// Hashes canonical BabyBear elements into a digest of 8 elements.
+///
+/// Panics if an element is not below [BABYBEAR_MODULUS].
+///
+/// The sponge overwrites the first [RATE] elements of the state with each chunk of input, so the
+/// digest matches `sp1_primitives::poseidon2_hash` and the hashes of the recursion layer.
+pub fn poseidon2_hash(input: &[u32]) -> [u32; 8] {
+    let mut state = [0u32; WIDTH];
+    for chunk in input.chunks(RATE) {
+        state[..chunk.len()].copy_from_slice(chunk);
+        poseidon2_permute(&mut state);
+    }
+    state[..8].try_into().unwrap()
+}
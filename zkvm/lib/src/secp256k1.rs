@@ -1,9 +1,14 @@
-use crate::utils::AffinePoint;
-use crate::{syscall_secp256k1_add, syscall_secp256k1_double};
+use crate::utils::{double_and_add, reduce_scalar, AffinePoint, MulAssignError};
+use crate::{syscall_secp256k1_add, syscall_secp256k1_double, syscall_secp256k1_mul};
 
 /// The number of limbs in [Secp256k1AffinePoint].
 pub const N: usize = 16;
 
+/// The order of the Secp256k1 group, as little endian words.
+const GROUP_ORDER: [u32; N / 2] = [
+    0xD0364141, 0xBFD25E8C, 0xAF48A03B, 0xBAAEDCE6, 0xFFFFFFFE, 0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF,
+];
+
 /// An affine point on the Secp256k1 curve.
 #[derive(Copy, Clone)]
 #[repr(align(4))]
@@ -43,4 +48,25 @@ impl AffinePoint<N> for Secp256k1AffinePoint {
             syscall_secp256k1_double(a);
         }
     }
+
+    /// Multiplies `self` by the given scalar, reduced modulo the group order, with a single
+    /// fixed-window scalar multiplication. Scalars which aren't `N / 2` words long fall back to
+    /// double-and-add.
+    ///
+    /// Returns [MulAssignError::ScalarIsZero] if the scalar is a multiple of the group order.
+    fn mul_assign(&mut self, scalar: &[u32]) -> Result<(), MulAssignError> {
+        let Ok(mut scalar) = <[u32; N / 2]>::try_from(scalar) else {
+            return double_and_add(self, scalar);
+        };
+        reduce_scalar(&mut scalar, &GROUP_ORDER);
+        if scalar.iter().all(|&word| word == 0) {
+            return Err(MulAssignError::ScalarIsZero);
+        }
+
+        let a = self.limbs_mut();
+        unsafe {
+            syscall_secp256k1_mul(a, &scalar);
+        }
+        Ok(())
+    }
 }
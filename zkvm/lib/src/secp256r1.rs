@@ -0,0 +1,46 @@
+use crate::utils::AffinePoint;
+use crate::{syscall_secp256r1_add, syscall_secp256r1_double};
+
+/// The number of limbs in [Secp256r1AffinePoint].
+pub const N: usize = 16;
+
+/// An affine point on the Secp256r1 (NIST P-256) curve.
+#[derive(Copy, Clone)]
+#[repr(align(4))]
+pub struct Secp256r1AffinePoint(pub [u32; N]);
+
+impl AffinePoint<N> for Secp256r1AffinePoint {
+    /// The values are taken from https://neuromancer.sk/std/secg/secp256r1.
+    const GENERATOR: [u32; N] = [
+        3633889942, 4104206661, 770388896, 1996717441, 1671708914, 4173129445, 3777774151,
+        1796723186, 935285237, 3417718888, 1798397646, 734933847, 2081398294, 2397563722,
+        4263149467, 1340293858,
+    ];
+
+    fn new(limbs: [u32; N]) -> Self {
+        Self(limbs)
+    }
+
+    fn limbs_ref(&self) -> &[u32; N] {
+        &self.0
+    }
+
+    fn limbs_mut(&mut self) -> &mut [u32; N] {
+        &mut self.0
+    }
+
+    fn add_assign(&mut self, other: &Self) {
+        let a = self.limbs_mut();
+        let b = other.limbs_ref();
+        unsafe {
+            syscall_secp256r1_add(a, b);
+        }
+    }
+
+    fn double(&mut self) {
+        let a = self.limbs_mut();
+        unsafe {
+            syscall_secp256r1_double(a);
+        }
+    }
+}
@@ -53,30 +53,7 @@ pub trait AffinePoint<const N: usize>: Clone + Sized {
     /// Multiplies `self` by the given scalar.
     fn mul_assign(&mut self, scalar: &[u32]) -> Result<(), MulAssignError> {
         debug_assert!(scalar.len() == N / 2);
-
-        let mut res: Option<Self> = None;
-        let mut temp = self.clone();
-
-        let scalar_is_zero = scalar.iter().all(|&words| words == 0);
-        if scalar_is_zero {
-            return Err(MulAssignError::ScalarIsZero);
-        }
-
-        for &words in scalar.iter() {
-            for i in 0..32 {
-                if (words >> i) & 1 == 1 {
-                    match res.as_mut() {
-                        Some(res) => res.add_assign(&temp),
-                        None => res = Some(temp.clone()),
-                    };
-                }
-
-                temp.double();
-            }
-        }
-
-        *self = res.unwrap();
-        Ok(())
+        double_and_add(self, scalar)
     }
 
     /// Performs multi-scalar multiplication (MSM) on slices of bit vectors and points. Note:
@@ -112,9 +89,55 @@ pub trait AffinePoint<const N: usize>: Clone + Sized {
     }
 }
 
+/// Multiplies `point` by the little endian `scalar` with double-and-add.
+pub fn double_and_add<P: AffinePoint<N>, const N: usize>(
+    point: &mut P,
+    scalar: &[u32],
+) -> Result<(), MulAssignError> {
+    let mut res: Option<P> = None;
+    let mut temp = point.clone();
+
+    let scalar_is_zero = scalar.iter().all(|&words| words == 0);
+    if scalar_is_zero {
+        return Err(MulAssignError::ScalarIsZero);
+    }
+
+    for &words in scalar.iter() {
+        for i in 0..32 {
+            if (words >> i) & 1 == 1 {
+                match res.as_mut() {
+                    Some(res) => res.add_assign(&temp),
+                    None => res = Some(temp.clone()),
+                };
+            }
+
+            temp.double();
+        }
+    }
+
+    *point = res.unwrap();
+    Ok(())
+}
+
+/// Reduces the little endian `scalar` modulo the little endian `order` by repeated subtraction,
+/// which only takes a few iterations for the group orders of the supported curves.
+pub fn reduce_scalar<const W: usize>(scalar: &mut [u32; W], order: &[u32; W]) {
+    while scalar.iter().rev().cmp(order.iter().rev()).is_ge() {
+        let mut borrow = false;
+        for (word, &order_word) in scalar.iter_mut().zip(order.iter()) {
+            let (diff, borrow_1) = word.overflowing_sub(order_word);
+            let (diff, borrow_2) = diff.overflowing_sub(borrow as u32);
+            *word = diff;
+            borrow = borrow_1 || borrow_2;
+        }
+    }
+}
+
 /// Errors that can occur during scalar multiplication of an [`AffinePoint`].
 #[derive(Debug)]
 pub enum MulAssignError {
+    /// The scalar is zero, or a multiple of the group order, so the product is the point at
+    /// infinity.
     ScalarIsZero,
 }
 